@@ -582,6 +582,75 @@ mod tests {
         assert_same_with!(a, b, SameOptions::new().float_tolerance(1e-6));
     }
 
+    #[test]
+    fn float_relative_tolerance() {
+        let a = vec![1.0e-9_f64, 2.5e6, 0.0];
+        let b = vec![1.0000001e-9_f64, 2.5000002e6, 0.0];
+        assert_same_with!(a, b, SameOptions::new().float_relative_tolerance(1e-6));
+
+        // 1e-3 off is far more than a millionth of 1.0
+        match check_same_with(
+            &1.0_f64,
+            &1.001_f64,
+            SameOptions::new().float_relative_tolerance(1e-6),
+        ) {
+            Sameness::Different(_) => {}
+            _ => panic!("expected Different"),
+        }
+
+        // Near zero, relative tolerance alone is too strict; absolute covers it
+        let options = SameOptions::new()
+            .float_relative_tolerance(1e-6)
+            .float_tolerance(1e-12);
+        assert_same_with!(0.0_f64, 1e-13_f64, options);
+    }
+
+    #[test]
+    fn ignore_subsecond() {
+        use chrono::{DateTime, Utc};
+
+        #[derive(Facet)]
+        struct Sample {
+            at: DateTime<Utc>,
+            value: u32,
+        }
+
+        let at = |nanos| DateTime::<Utc>::from_timestamp(1_700_000_000, nanos).unwrap();
+        let a = Sample {
+            at: at(120_000_000),
+            value: 7,
+        };
+        let b = Sample {
+            at: at(870_000_000),
+            value: 7,
+        };
+
+        match check_same(&a, &b) {
+            Sameness::Different(_) => {}
+            _ => panic!("expected Different"),
+        }
+        assert_same_with!(a, b, SameOptions::new().ignore_subsecond());
+
+        let c = Sample {
+            at: DateTime::<Utc>::from_timestamp(1_700_000_001, 0).unwrap(),
+            value: 7,
+        };
+        match check_same_with(&a, &c, SameOptions::new().ignore_subsecond()) {
+            Sameness::Different(_) => {}
+            _ => panic!("expected Different"),
+        }
+
+        // Only times are affected, not strings that happen to contain a clock
+        match check_same_with(
+            &"12:00:00.1",
+            &"12:00:00.2",
+            SameOptions::new().ignore_subsecond(),
+        ) {
+            Sameness::Different(_) => {}
+            _ => panic!("expected Different"),
+        }
+    }
+
     // Tests for type inference (the key fix from issue #1161)
     mod type_inference {
         use super::*;
@@ -21,6 +21,13 @@ pub struct SameOptions {
     /// is less than or equal to this value.
     float_tolerance: Option<f64>,
 
+    /// Relative tolerance for floating-point comparisons, as a fraction of
+    /// the larger magnitude.
+    float_relative_tolerance: Option<f64>,
+
+    /// Whether to ignore fractional seconds when comparing times.
+    ignore_subsecond: bool,
+
     /// Similarity threshold for tree-based element matching in sequences.
     /// If set, sequence elements with structural similarity >= this threshold
     /// are paired for inline diffing rather than shown as remove+add.
@@ -63,6 +70,41 @@ impl SameOptions {
         self
     }
 
+    /// Set the relative tolerance for floating-point comparisons.
+    ///
+    /// When set, two `f32` or `f64` values are considered equal if:
+    /// `|left - right| <= tolerance * max(|left|, |right|)`
+    ///
+    /// This suits measured data spanning many orders of magnitude, where no
+    /// single absolute tolerance fits. It combines with
+    /// [`float_tolerance`](Self::float_tolerance): values within either
+    /// tolerance are equal, which also covers values near zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use facet_assert::{assert_same_with, SameOptions};
+    ///
+    /// let a = vec![1.0e-9_f64, 2.5e6];
+    /// let b = vec![1.0000001e-9_f64, 2.5000002e6];
+    ///
+    /// assert_same_with!(a, b, SameOptions::new().float_relative_tolerance(1e-6));
+    /// ```
+    pub fn float_relative_tolerance(mut self, tolerance: f64) -> Self {
+        self.float_relative_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Ignore fractional seconds when comparing times.
+    ///
+    /// When set, date/time values from `chrono`, `time` or `jiff` are
+    /// considered equal if they agree down to the second, e.g. timestamps
+    /// taken a few milliseconds apart.
+    pub fn ignore_subsecond(mut self) -> Self {
+        self.ignore_subsecond = true;
+        self
+    }
+
     /// Set the similarity threshold for tree-based element matching.
     ///
     /// When set, sequence elements with structural similarity >= this threshold
@@ -264,6 +306,12 @@ pub fn check_sameish_with_report<'f, 'mem, T: Facet<'f>, U: Facet<'f>>(
     if let Some(tol) = options.float_tolerance {
         diff_options = diff_options.with_float_tolerance(tol);
     }
+    if let Some(tol) = options.float_relative_tolerance {
+        diff_options = diff_options.with_float_relative_tolerance(tol);
+    }
+    if options.ignore_subsecond {
+        diff_options = diff_options.with_ignore_subsecond();
+    }
     if let Some(threshold) = options.similarity_threshold {
         diff_options = diff_options.with_similarity_threshold(threshold);
     }
@@ -14,7 +14,7 @@ unsafe fn btreemap_init_in_place_with_capacity<K, V>(
     unsafe { uninit.put(BTreeMap::<K, V>::new()) }
 }
 
-unsafe fn btreemap_insert<K: Eq + Ord + 'static, V: 'static>(
+unsafe fn btreemap_insert<K: Eq + Ord, V>(
     ptr: PtrMut,
     key: PtrMut,
     value: PtrMut,
@@ -27,18 +27,18 @@ unsafe fn btreemap_insert<K: Eq + Ord + 'static, V: 'static>(
     }
 }
 
-unsafe fn btreemap_len<K: 'static, V: 'static>(ptr: PtrConst) -> usize {
+unsafe fn btreemap_len<K, V>(ptr: PtrConst) -> usize {
     unsafe { ptr.get::<BTreeMap<K, V>>().len() }
 }
 
-unsafe fn btreemap_contains_key<K: Eq + Ord + 'static, V: 'static>(
+unsafe fn btreemap_contains_key<K: Eq + Ord, V>(
     ptr: PtrConst,
     key: PtrConst,
 ) -> bool {
     unsafe { ptr.get::<BTreeMap<K, V>>().contains_key(key.get()) }
 }
 
-unsafe fn btreemap_get_value_ptr<K: Eq + Ord + 'static, V: 'static>(
+unsafe fn btreemap_get_value_ptr<K: Eq + Ord, V>(
     ptr: PtrConst,
     key: PtrConst,
 ) -> Option<PtrConst> {
@@ -49,7 +49,7 @@ unsafe fn btreemap_get_value_ptr<K: Eq + Ord + 'static, V: 'static>(
     }
 }
 
-unsafe fn btreemap_iter_init<K: 'static, V: 'static>(ptr: PtrConst) -> PtrMut {
+unsafe fn btreemap_iter_init<K, V>(ptr: PtrConst) -> PtrMut {
     unsafe {
         let map = ptr.get::<BTreeMap<K, V>>();
         let iter: BTreeMapIterator<'_, K, V> = map.iter();
@@ -58,11 +58,11 @@ unsafe fn btreemap_iter_init<K: 'static, V: 'static>(ptr: PtrConst) -> PtrMut {
     }
 }
 
-unsafe fn btreemap_iter_next<K: 'static, V: 'static>(
+unsafe fn btreemap_iter_next<K, V>(
     iter_ptr: PtrMut,
 ) -> Option<(PtrConst, PtrConst)> {
     unsafe {
-        let state = iter_ptr.as_mut::<BTreeMapIterator<'static, K, V>>();
+        let state = iter_ptr.as_mut::<BTreeMapIterator<'_, K, V>>();
         state.next().map(|(key, value)| {
             (
                 PtrConst::new(key as *const K),
@@ -72,11 +72,11 @@ unsafe fn btreemap_iter_next<K: 'static, V: 'static>(
     }
 }
 
-unsafe fn btreemap_iter_next_back<K: 'static, V: 'static>(
+unsafe fn btreemap_iter_next_back<K, V>(
     iter_ptr: PtrMut,
 ) -> Option<(PtrConst, PtrConst)> {
     unsafe {
-        let state = iter_ptr.as_mut::<BTreeMapIterator<'static, K, V>>();
+        let state = iter_ptr.as_mut::<BTreeMapIterator<'_, K, V>>();
         state.next_back().map(|(key, value)| {
             (
                 PtrConst::new(key as *const K),
@@ -95,7 +95,7 @@ unsafe fn btreemap_iter_dealloc<K, V>(iter_ptr: PtrMut) {
 }
 
 /// Build a BTreeMap from a contiguous slice of (K, V) pairs.
-unsafe fn btreemap_from_pair_slice<K: Eq + Ord + 'static, V: 'static>(
+unsafe fn btreemap_from_pair_slice<K: Eq + Ord, V>(
     uninit: PtrUninit,
     pairs_ptr: *mut u8,
     count: usize,
@@ -110,25 +110,25 @@ unsafe fn btreemap_from_pair_slice<K: Eq + Ord + 'static, V: 'static>(
 }
 
 /// Drop for BTreeMap<K, V>
-unsafe fn btreemap_drop<K: 'static, V: 'static>(ox: OxPtrMut) {
+unsafe fn btreemap_drop<K, V>(ox: OxPtrMut) {
     unsafe {
         core::ptr::drop_in_place(ox.as_mut::<BTreeMap<K, V>>());
     }
 }
 
 /// Default for BTreeMap<K, V>
-unsafe fn btreemap_default<K: 'static, V: 'static>(ox: OxPtrMut) {
+unsafe fn btreemap_default<K, V>(ox: OxPtrMut) {
     unsafe { ox.ptr().as_uninit().put(BTreeMap::<K, V>::new()) };
 }
 
 // TODO: Debug, Hash, PartialEq, Eq, PartialOrd, Ord, for BTreeMap, BTreeSet
 unsafe impl<'a, K, V> Facet<'a> for BTreeMap<K, V>
 where
-    K: Facet<'a> + core::cmp::Eq + core::cmp::Ord + 'static,
-    V: Facet<'a> + 'static,
+    K: Facet<'a> + core::cmp::Eq + core::cmp::Ord,
+    V: Facet<'a>,
 {
     const SHAPE: &'static crate::Shape = &const {
-        const fn build_map_vtable<K: Eq + Ord + 'static, V: 'static>() -> MapVTable {
+        const fn build_map_vtable<K: Eq + Ord, V>() -> MapVTable {
             MapVTable::builder()
                 .init_in_place_with_capacity(btreemap_init_in_place_with_capacity::<K, V>)
                 .insert(btreemap_insert::<K, V>)
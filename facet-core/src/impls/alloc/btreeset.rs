@@ -14,7 +14,7 @@ unsafe fn btreeset_init_in_place_with_capacity<T>(uninit: PtrUninit, _capacity:
     unsafe { uninit.put(BTreeSet::<T>::new()) }
 }
 
-unsafe fn btreeset_insert<T: Eq + Ord + 'static>(ptr: PtrMut, item: PtrMut) -> bool {
+unsafe fn btreeset_insert<T: Eq + Ord>(ptr: PtrMut, item: PtrMut) -> bool {
     unsafe {
         let set = ptr.as_mut::<BTreeSet<T>>();
         let item = item.read::<T>();
@@ -22,15 +22,15 @@ unsafe fn btreeset_insert<T: Eq + Ord + 'static>(ptr: PtrMut, item: PtrMut) -> b
     }
 }
 
-unsafe fn btreeset_len<T: 'static>(ptr: PtrConst) -> usize {
+unsafe fn btreeset_len<T>(ptr: PtrConst) -> usize {
     unsafe { ptr.get::<BTreeSet<T>>().len() }
 }
 
-unsafe fn btreeset_contains<T: Eq + Ord + 'static>(ptr: PtrConst, item: PtrConst) -> bool {
+unsafe fn btreeset_contains<T: Eq + Ord>(ptr: PtrConst, item: PtrConst) -> bool {
     unsafe { ptr.get::<BTreeSet<T>>().contains(item.get()) }
 }
 
-unsafe fn btreeset_iter_init<T: 'static>(ptr: PtrConst) -> PtrMut {
+unsafe fn btreeset_iter_init<T>(ptr: PtrConst) -> PtrMut {
     unsafe {
         let set = ptr.get::<BTreeSet<T>>();
         let iter: BTreeSetIterator<'_, T> = set.iter();
@@ -39,16 +39,16 @@ unsafe fn btreeset_iter_init<T: 'static>(ptr: PtrConst) -> PtrMut {
     }
 }
 
-unsafe fn btreeset_iter_next<T: 'static>(iter_ptr: PtrMut) -> Option<PtrConst> {
+unsafe fn btreeset_iter_next<T>(iter_ptr: PtrMut) -> Option<PtrConst> {
     unsafe {
-        let state = iter_ptr.as_mut::<BTreeSetIterator<'static, T>>();
+        let state = iter_ptr.as_mut::<BTreeSetIterator<'_, T>>();
         state.next().map(|value| PtrConst::new(value as *const T))
     }
 }
 
-unsafe fn btreeset_iter_next_back<T: 'static>(iter_ptr: PtrMut) -> Option<PtrConst> {
+unsafe fn btreeset_iter_next_back<T>(iter_ptr: PtrMut) -> Option<PtrConst> {
     unsafe {
-        let state = iter_ptr.as_mut::<BTreeSetIterator<'static, T>>();
+        let state = iter_ptr.as_mut::<BTreeSetIterator<'_, T>>();
         state
             .next_back()
             .map(|value| PtrConst::new(value as *const T))
@@ -77,10 +77,10 @@ unsafe fn btreeset_default<T>(ox: OxPtrMut) {
 
 unsafe impl<'a, T> Facet<'a> for BTreeSet<T>
 where
-    T: Facet<'a> + core::cmp::Eq + core::cmp::Ord + 'static,
+    T: Facet<'a> + core::cmp::Eq + core::cmp::Ord,
 {
     const SHAPE: &'static crate::Shape = &const {
-        const fn build_set_vtable<T: Eq + Ord + 'static>() -> SetVTable {
+        const fn build_set_vtable<T: Eq + Ord>() -> SetVTable {
             SetVTable::builder()
                 .init_in_place_with_capacity(btreeset_init_in_place_with_capacity::<T>)
                 .insert(btreeset_insert::<T>)
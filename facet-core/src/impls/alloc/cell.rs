@@ -0,0 +1,133 @@
+use alloc::boxed::Box;
+use core::cell::{Ref, RefCell, RefMut};
+
+use crate::{
+    Def, Facet, KnownPointer, LockGuardVTable, LockResult, OxPtrMut, PointerDef, PointerFlags,
+    PointerVTable, PtrConst, PtrMut, PtrUninit, Shape, ShapeBuilder, Type, TypeNameOpts,
+    TypeOpsIndirect, UserType, VTableIndirect, Variance,
+};
+
+/// Writes the name of a lock-like wrapper around `T`, e.g. `RefCell<u32>`.
+pub(crate) fn type_name_lock<'a, T: Facet<'a>>(
+    name: &str,
+    f: &mut core::fmt::Formatter<'_>,
+    opts: TypeNameOpts,
+) -> core::fmt::Result {
+    write!(f, "{name}")?;
+    if let Some(opts) = opts.for_children() {
+        write!(f, "<")?;
+        T::SHAPE.write_type_name(f, opts)?;
+        write!(f, ">")?;
+    } else {
+        write!(f, "<…>")?;
+    }
+    Ok(())
+}
+
+/// Boxes `guard` and wraps it with the data it protects.
+///
+/// # Safety
+///
+/// `vtable` must drop the guard as a `Box<G>`.
+pub(crate) unsafe fn boxed_guard<G>(
+    guard: G,
+    data: PtrMut,
+    vtable: &'static LockGuardVTable,
+) -> LockResult {
+    let guard = PtrConst::new(Box::into_raw(Box::new(guard)));
+    unsafe { LockResult::new(data, guard, vtable) }
+}
+
+unsafe fn refcell_drop_ref<T>(guard: PtrConst) {
+    drop(unsafe { Box::from_raw(guard.as_ptr::<Ref<'_, T>>() as *mut Ref<'_, T>) });
+}
+
+unsafe fn refcell_drop_ref_mut<T>(guard: PtrConst) {
+    drop(unsafe { Box::from_raw(guard.as_ptr::<RefMut<'_, T>>() as *mut RefMut<'_, T>) });
+}
+
+// Borrows are boxed so the guard can outlive this call, and are released when
+// the `LockResult` is dropped
+unsafe fn refcell_read<'a, T: Facet<'a>>(this: PtrConst) -> Result<LockResult, ()> {
+    let cell = unsafe { this.get::<RefCell<T>>() };
+    let guard = cell.try_borrow().map_err(|_| ())?;
+    let data = PtrMut::new(&*guard as *const T as *mut T);
+    Ok(unsafe {
+        boxed_guard(
+            guard,
+            data,
+            &const {
+                LockGuardVTable {
+                    drop_in_place: refcell_drop_ref::<T>,
+                }
+            },
+        )
+    })
+}
+
+unsafe fn refcell_write<'a, T: Facet<'a>>(this: PtrConst) -> Result<LockResult, ()> {
+    let cell = unsafe { this.get::<RefCell<T>>() };
+    let mut guard = cell.try_borrow_mut().map_err(|_| ())?;
+    let data = PtrMut::new(&mut *guard as *mut T);
+    Ok(unsafe {
+        boxed_guard(
+            guard,
+            data,
+            &const {
+                LockGuardVTable {
+                    drop_in_place: refcell_drop_ref_mut::<T>,
+                }
+            },
+        )
+    })
+}
+
+unsafe fn refcell_new_into<'a, T: Facet<'a>>(this: PtrUninit, ptr: PtrMut) -> PtrMut {
+    unsafe { this.put(RefCell::new(ptr.read::<T>())) }
+}
+
+unsafe fn refcell_drop<T>(ox: OxPtrMut) {
+    unsafe { core::ptr::drop_in_place(ox.ptr().as_ptr::<RefCell<T>>() as *mut RefCell<T>) };
+}
+
+unsafe impl<'a, T: Facet<'a>> Facet<'a> for RefCell<T> {
+    const SHAPE: &'static Shape = &const {
+        ShapeBuilder::for_sized::<Self>("RefCell")
+            .type_name(|_, f, opts| type_name_lock::<T>("RefCell", f, opts))
+            .vtable_indirect(&VTableIndirect::EMPTY)
+            .type_ops_indirect(
+                &const {
+                    TypeOpsIndirect {
+                        drop_in_place: refcell_drop::<T>,
+                        default_in_place: None,
+                        clone_into: None,
+                        is_truthy: None,
+                    }
+                },
+            )
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Pointer(PointerDef {
+                vtable: &const {
+                    PointerVTable {
+                        read_fn: Some(refcell_read::<T>),
+                        write_fn: Some(refcell_write::<T>),
+                        new_into_fn: Some(refcell_new_into::<T>),
+                        ..PointerVTable::new()
+                    }
+                },
+                pointee: Some(T::SHAPE),
+                weak: None,
+                strong: None,
+                flags: PointerFlags::LOCK,
+                known: Some(KnownPointer::RefCell),
+            }))
+            .type_params(&[crate::TypeParam {
+                name: "T",
+                shape: T::SHAPE,
+            }])
+            .inner(T::SHAPE)
+            // RefCell<T> is invariant in T
+            .variance(Variance::INVARIANT)
+            .build()
+    };
+}
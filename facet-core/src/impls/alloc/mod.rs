@@ -1,6 +1,7 @@
 #![cfg(feature = "alloc")]
 
 mod arc;
+pub(crate) mod cell;
 mod boxed;
 mod rc;
 
@@ -0,0 +1,89 @@
+//! Common error types, rendered through their `Display` implementation.
+//!
+//! These are opaque scalars: they can be printed as their message, but not
+//! serialized or built back from one.
+
+use alloc::boxed::Box;
+use core::error::Error;
+
+use crate::{
+    Def, Facet, Shape, ShapeBuilder, Type, TypeOpsDirect, UserType, VTableDirect, type_ops_direct,
+    vtable_direct,
+};
+
+static IO_ERROR_TYPE_OPS: TypeOpsDirect = type_ops_direct!(std::io::Error =>);
+
+unsafe impl Facet<'_> for std::io::Error {
+    const SHAPE: &'static Shape = &const {
+        const VTABLE: VTableDirect = vtable_direct!(std::io::Error => Display, Debug,);
+
+        ShapeBuilder::for_sized::<std::io::Error>("io::Error")
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Scalar)
+            .vtable_direct(&VTABLE)
+            .type_ops_direct(&IO_ERROR_TYPE_OPS)
+            .send()
+            .sync()
+            .build()
+    };
+}
+
+static IO_ERROR_KIND_TYPE_OPS: TypeOpsDirect = type_ops_direct!(std::io::ErrorKind => Clone);
+
+unsafe impl Facet<'_> for std::io::ErrorKind {
+    const SHAPE: &'static Shape = &const {
+        const VTABLE: VTableDirect = vtable_direct!(std::io::ErrorKind =>
+            Display,
+            Debug,
+            Hash,
+            PartialEq,
+            PartialOrd,
+            Ord,
+        );
+
+        ShapeBuilder::for_sized::<std::io::ErrorKind>("io::ErrorKind")
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Scalar)
+            .vtable_direct(&VTABLE)
+            .type_ops_direct(&IO_ERROR_KIND_TYPE_OPS)
+            .eq()
+            .copy()
+            .send()
+            .sync()
+            .build()
+    };
+}
+
+static BOX_DYN_ERROR_TYPE_OPS: TypeOpsDirect = type_ops_direct!(Box<dyn Error> =>);
+
+unsafe impl Facet<'_> for Box<dyn Error> {
+    const SHAPE: &'static Shape = &const {
+        const VTABLE: VTableDirect = vtable_direct!(Box<dyn Error> => Display, Debug,);
+
+        ShapeBuilder::for_sized::<Box<dyn Error>>("Box<dyn Error>")
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Scalar)
+            .vtable_direct(&VTABLE)
+            .type_ops_direct(&BOX_DYN_ERROR_TYPE_OPS)
+            .build()
+    };
+}
+
+static BOX_DYN_ERROR_SEND_SYNC_TYPE_OPS: TypeOpsDirect =
+    type_ops_direct!(Box<dyn Error + Send + Sync> =>);
+
+unsafe impl Facet<'_> for Box<dyn Error + Send + Sync> {
+    const SHAPE: &'static Shape = &const {
+        const VTABLE: VTableDirect =
+            vtable_direct!(Box<dyn Error + Send + Sync> => Display, Debug,);
+
+        ShapeBuilder::for_sized::<Box<dyn Error + Send + Sync>>("Box<dyn Error + Send + Sync>")
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Scalar)
+            .vtable_direct(&VTABLE)
+            .type_ops_direct(&BOX_DYN_ERROR_SEND_SYNC_TYPE_OPS)
+            .send()
+            .sync()
+            .build()
+    };
+}
@@ -23,7 +23,7 @@ unsafe fn hashset_init_in_place_with_capacity<T, S: Default + BuildHasher>(
     }
 }
 
-unsafe fn hashset_insert<T: Eq + core::hash::Hash + 'static>(ptr: PtrMut, item: PtrMut) -> bool {
+unsafe fn hashset_insert<T: Eq + core::hash::Hash>(ptr: PtrMut, item: PtrMut) -> bool {
     unsafe {
         let set = ptr.as_mut::<HashSet<T>>();
         let item = item.read::<T>();
@@ -31,18 +31,18 @@ unsafe fn hashset_insert<T: Eq + core::hash::Hash + 'static>(ptr: PtrMut, item:
     }
 }
 
-unsafe fn hashset_len<T: 'static>(ptr: PtrConst) -> usize {
+unsafe fn hashset_len<T>(ptr: PtrConst) -> usize {
     unsafe { ptr.get::<HashSet<T>>().len() }
 }
 
-unsafe fn hashset_contains<T: Eq + core::hash::Hash + 'static>(
+unsafe fn hashset_contains<T: Eq + core::hash::Hash>(
     ptr: PtrConst,
     item: PtrConst,
 ) -> bool {
     unsafe { ptr.get::<HashSet<T>>().contains(item.get()) }
 }
 
-unsafe fn hashset_iter_init<T: 'static>(ptr: PtrConst) -> PtrMut {
+unsafe fn hashset_iter_init<T>(ptr: PtrConst) -> PtrMut {
     unsafe {
         let set = ptr.get::<HashSet<T>>();
         let iter: HashSetIterator<'_, T> = set.iter();
@@ -51,9 +51,9 @@ unsafe fn hashset_iter_init<T: 'static>(ptr: PtrConst) -> PtrMut {
     }
 }
 
-unsafe fn hashset_iter_next<T: 'static>(iter_ptr: PtrMut) -> Option<PtrConst> {
+unsafe fn hashset_iter_next<T>(iter_ptr: PtrMut) -> Option<PtrConst> {
     unsafe {
-        let state = iter_ptr.as_mut::<HashSetIterator<'static, T>>();
+        let state = iter_ptr.as_mut::<HashSetIterator<'_, T>>();
         state.next().map(|value| PtrConst::new(value as *const T))
     }
 }
@@ -184,26 +184,26 @@ unsafe fn hashset_partial_eq(a: OxPtrConst, b: OxPtrConst) -> Option<bool> {
 }
 
 /// Drop for HashSet<T, S>
-unsafe fn hashset_drop<T: 'static, S: 'static>(ox: OxPtrMut) {
+unsafe fn hashset_drop<T, S>(ox: OxPtrMut) {
     unsafe {
         core::ptr::drop_in_place(ox.as_mut::<HashSet<T, S>>());
     }
 }
 
 /// Default for HashSet<T, S>
-unsafe fn hashset_default<T: 'static, S: Default + BuildHasher + 'static>(ox: OxPtrMut) {
+unsafe fn hashset_default<T, S: Default + BuildHasher>(ox: OxPtrMut) {
     unsafe { ox.ptr().as_uninit().put(HashSet::<T, S>::default()) };
 }
 
 unsafe impl<'a, T, S> Facet<'a> for HashSet<T, S>
 where
-    T: Facet<'a> + core::cmp::Eq + core::hash::Hash + 'static,
-    S: Facet<'a> + Default + BuildHasher + 'static,
+    T: Facet<'a> + core::cmp::Eq + core::hash::Hash,
+    S: Facet<'a> + Default + BuildHasher,
 {
     const SHAPE: &'static Shape = &const {
         const fn build_set_vtable<
-            T: Eq + core::hash::Hash + 'static,
-            S: Default + BuildHasher + 'static,
+            T: Eq + core::hash::Hash,
+            S: Default + BuildHasher,
         >() -> SetVTable {
             SetVTable::builder()
                 .init_in_place_with_capacity(hashset_init_in_place_with_capacity::<T, S>)
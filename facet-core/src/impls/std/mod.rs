@@ -1,6 +1,8 @@
 #![cfg(feature = "std")]
 
+mod error;
 mod hashmap;
 mod hashset;
 
 mod path;
+mod sync;
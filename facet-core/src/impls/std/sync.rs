@@ -0,0 +1,189 @@
+use alloc::boxed::Box;
+use std::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+use crate::impls::alloc::cell::{boxed_guard, type_name_lock};
+use crate::{
+    Def, Facet, KnownPointer, LockGuardVTable, LockResult, OxPtrMut, PointerDef, PointerFlags,
+    PointerVTable, PtrConst, PtrMut, PtrUninit, Shape, ShapeBuilder, Type, TypeOpsIndirect,
+    UserType, VTableIndirect, Variance,
+};
+
+/// Turns a poisoned lock into a usable one: reflection only reads or replaces
+/// the value, so a panic elsewhere doesn't make it any less valid.
+fn unpoison<G>(result: Result<G, TryLockError<G>>) -> Result<G, ()> {
+    match result {
+        Ok(guard) => Ok(guard),
+        Err(TryLockError::Poisoned(poisoned)) => Ok(poisoned.into_inner()),
+        Err(TryLockError::WouldBlock) => Err(()),
+    }
+}
+
+// Mutex<T>
+
+unsafe fn mutex_drop_guard<T>(guard: PtrConst) {
+    drop(unsafe { Box::from_raw(guard.as_ptr::<MutexGuard<'_, T>>() as *mut MutexGuard<'_, T>) });
+}
+
+unsafe fn mutex_lock<'a, T: Facet<'a>>(this: PtrConst) -> Result<LockResult, ()> {
+    let mutex = unsafe { this.get::<Mutex<T>>() };
+    let mut guard = unpoison(mutex.try_lock())?;
+    let data = PtrMut::new(&mut *guard as *mut T);
+    Ok(unsafe {
+        boxed_guard(
+            guard,
+            data,
+            &const {
+                LockGuardVTable {
+                    drop_in_place: mutex_drop_guard::<T>,
+                }
+            },
+        )
+    })
+}
+
+unsafe fn mutex_new_into<'a, T: Facet<'a>>(this: PtrUninit, ptr: PtrMut) -> PtrMut {
+    unsafe { this.put(Mutex::new(ptr.read::<T>())) }
+}
+
+unsafe fn mutex_drop<T>(ox: OxPtrMut) {
+    unsafe { core::ptr::drop_in_place(ox.ptr().as_ptr::<Mutex<T>>() as *mut Mutex<T>) };
+}
+
+unsafe impl<'a, T: Facet<'a>> Facet<'a> for Mutex<T> {
+    const SHAPE: &'static Shape = &const {
+        ShapeBuilder::for_sized::<Self>("Mutex")
+            .type_name(|_, f, opts| type_name_lock::<T>("Mutex", f, opts))
+            .vtable_indirect(&VTableIndirect::EMPTY)
+            .type_ops_indirect(
+                &const {
+                    TypeOpsIndirect {
+                        drop_in_place: mutex_drop::<T>,
+                        default_in_place: None,
+                        clone_into: None,
+                        is_truthy: None,
+                    }
+                },
+            )
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Pointer(PointerDef {
+                vtable: &const {
+                    PointerVTable {
+                        lock_fn: Some(mutex_lock::<T>),
+                        new_into_fn: Some(mutex_new_into::<T>),
+                        ..PointerVTable::new()
+                    }
+                },
+                pointee: Some(T::SHAPE),
+                weak: None,
+                strong: None,
+                flags: PointerFlags::LOCK,
+                known: Some(KnownPointer::Mutex),
+            }))
+            .type_params(&[crate::TypeParam {
+                name: "T",
+                shape: T::SHAPE,
+            }])
+            .inner(T::SHAPE)
+            // Locks hand out mutable access, so they're invariant in T
+            .variance(Variance::INVARIANT)
+            .build()
+    };
+}
+
+// RwLock<T>
+
+unsafe fn rwlock_drop_read_guard<T>(guard: PtrConst) {
+    drop(unsafe {
+        Box::from_raw(guard.as_ptr::<RwLockReadGuard<'_, T>>() as *mut RwLockReadGuard<'_, T>)
+    });
+}
+
+unsafe fn rwlock_drop_write_guard<T>(guard: PtrConst) {
+    drop(unsafe {
+        Box::from_raw(guard.as_ptr::<RwLockWriteGuard<'_, T>>() as *mut RwLockWriteGuard<'_, T>)
+    });
+}
+
+unsafe fn rwlock_read<'a, T: Facet<'a>>(this: PtrConst) -> Result<LockResult, ()> {
+    let lock = unsafe { this.get::<RwLock<T>>() };
+    let guard = unpoison(lock.try_read())?;
+    let data = PtrMut::new(&*guard as *const T as *mut T);
+    Ok(unsafe {
+        boxed_guard(
+            guard,
+            data,
+            &const {
+                LockGuardVTable {
+                    drop_in_place: rwlock_drop_read_guard::<T>,
+                }
+            },
+        )
+    })
+}
+
+unsafe fn rwlock_write<'a, T: Facet<'a>>(this: PtrConst) -> Result<LockResult, ()> {
+    let lock = unsafe { this.get::<RwLock<T>>() };
+    let mut guard = unpoison(lock.try_write())?;
+    let data = PtrMut::new(&mut *guard as *mut T);
+    Ok(unsafe {
+        boxed_guard(
+            guard,
+            data,
+            &const {
+                LockGuardVTable {
+                    drop_in_place: rwlock_drop_write_guard::<T>,
+                }
+            },
+        )
+    })
+}
+
+unsafe fn rwlock_new_into<'a, T: Facet<'a>>(this: PtrUninit, ptr: PtrMut) -> PtrMut {
+    unsafe { this.put(RwLock::new(ptr.read::<T>())) }
+}
+
+unsafe fn rwlock_drop<T>(ox: OxPtrMut) {
+    unsafe { core::ptr::drop_in_place(ox.ptr().as_ptr::<RwLock<T>>() as *mut RwLock<T>) };
+}
+
+unsafe impl<'a, T: Facet<'a>> Facet<'a> for RwLock<T> {
+    const SHAPE: &'static Shape = &const {
+        ShapeBuilder::for_sized::<Self>("RwLock")
+            .type_name(|_, f, opts| type_name_lock::<T>("RwLock", f, opts))
+            .vtable_indirect(&VTableIndirect::EMPTY)
+            .type_ops_indirect(
+                &const {
+                    TypeOpsIndirect {
+                        drop_in_place: rwlock_drop::<T>,
+                        default_in_place: None,
+                        clone_into: None,
+                        is_truthy: None,
+                    }
+                },
+            )
+            .ty(Type::User(UserType::Opaque))
+            .def(Def::Pointer(PointerDef {
+                vtable: &const {
+                    PointerVTable {
+                        read_fn: Some(rwlock_read::<T>),
+                        write_fn: Some(rwlock_write::<T>),
+                        new_into_fn: Some(rwlock_new_into::<T>),
+                        ..PointerVTable::new()
+                    }
+                },
+                pointee: Some(T::SHAPE),
+                weak: None,
+                strong: None,
+                flags: PointerFlags::LOCK,
+                known: Some(KnownPointer::RwLock),
+            }))
+            .type_params(&[crate::TypeParam {
+                name: "T",
+                shape: T::SHAPE,
+            }])
+            .inner(T::SHAPE)
+            // Locks hand out mutable access, so they're invariant in T
+            .variance(Variance::INVARIANT)
+            .build()
+    };
+}
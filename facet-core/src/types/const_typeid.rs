@@ -77,9 +77,10 @@ impl Ord for ConstTypeId {
 impl Hash for ConstTypeId {
     #[inline]
     fn hash<H: Hasher>(&self, state: &mut H) {
-        // Hash the function pointer directly - much faster than calling it
-        // to get TypeId. The function pointer is unique per type within a process.
-        (self.type_id_fn as usize).hash(state);
+        // Hash the TypeId rather than the function pointer: the same generic
+        // instantiation can be emitted by several crates at different addresses,
+        // and equal ids must hash equally.
+        self.get().hash(state);
     }
 }
 
@@ -134,6 +134,9 @@ pub use ndarray::*;
 mod dynamic_value;
 pub use dynamic_value::*;
 
+mod trait_object;
+pub use trait_object::*;
+
 /// The semantic definition of a shape: is it more like a scalar, a map, a list?
 #[derive(Clone, Copy)]
 #[repr(C)]
@@ -197,6 +200,11 @@ pub enum Def {
     ///
     /// e.g. `facet_value::Value`, `serde_json::Value`
     DynamicValue(DynamicValueDef),
+
+    /// Trait object whose concrete type is resolved at runtime.
+    ///
+    /// e.g. `dyn Plugin`, the pointee of `Box<dyn Plugin>`
+    TraitObject(TraitObjectDef),
 }
 
 impl core::fmt::Debug for Def {
@@ -222,6 +230,7 @@ impl core::fmt::Debug for Def {
                 }
             }
             Def::DynamicValue(_) => write!(f, "DynamicValue"),
+            Def::TraitObject(_) => write!(f, "TraitObject"),
         }
     }
 }
@@ -390,6 +399,14 @@ impl Def {
         }
     }
 
+    /// Returns the `TraitObjectDef` wrapped in an `Ok` if this is a [`Def::TraitObject`].
+    pub fn into_trait_object(self) -> Result<TraitObjectDef, Self> {
+        match self {
+            Self::TraitObject(def) => Ok(def),
+            _ => Err(self),
+        }
+    }
+
     /// Returns the default `Type` for this `Def`.
     ///
     /// This is used by `ShapeBuilder` to infer the `ty` field from `def`.
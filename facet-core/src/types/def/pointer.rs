@@ -51,6 +51,12 @@ impl PointerDef {
                 Some(KnownPointer::Box | KnownPointer::Rc | KnownPointer::Arc)
             )
     }
+
+    /// Whether this pointer guards its value behind a lock or borrow flag,
+    /// like `Mutex`, `RwLock` or `RefCell`, so it must be locked to be read.
+    pub const fn is_lock(&self) -> bool {
+        self.vtable.read_fn.is_some() || self.vtable.lock_fn.is_some()
+    }
 }
 
 bitflags! {
@@ -140,6 +146,26 @@ pub struct LockResult {
 }
 
 impl LockResult {
+    /// Bundles locked data with the guard that keeps it locked.
+    ///
+    /// # Safety
+    ///
+    /// `data` must stay valid for as long as the guard is alive, and
+    /// `guard_vtable.drop_in_place` must release the guard behind `guard`
+    /// (and free it, if it was allocated).
+    #[must_use]
+    pub const unsafe fn new(
+        data: PtrMut,
+        guard: PtrConst,
+        guard_vtable: &'static LockGuardVTable,
+    ) -> Self {
+        Self {
+            data,
+            guard,
+            guard_vtable,
+        }
+    }
+
     /// Returns a reference to the locked data
     #[must_use]
     pub fn data(&self) -> &PtrMut {
@@ -0,0 +1,160 @@
+use core::any::TypeId;
+
+use crate::{PtrConst, Shape};
+
+/// Returns the [`TypeId`] of the concrete value behind a trait object.
+///
+/// # Safety
+///
+/// `value` must be a wide pointer to an initialized trait object of the
+/// shape this function belongs to.
+pub type TraitObjectTypeIdFn = unsafe fn(value: PtrConst) -> TypeId;
+
+/// Describes a trait object type like `dyn Plugin`.
+///
+/// The concrete type behind a trait object is only known at runtime. It is
+/// resolved through its [`TypeId`], by looking up shapes registered with
+/// [`register_shape`](crate::register_shape), so reflection can see through
+/// fields like `Box<dyn Plugin>` as long as every implementor registered its
+/// shape.
+///
+/// Shapes with this def are generated by [`impl_facet_for_dyn!`](crate::impl_facet_for_dyn).
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+pub struct TraitObjectDef {
+    /// See [`TraitObjectTypeIdFn`]
+    pub type_id: TraitObjectTypeIdFn,
+}
+
+impl TraitObjectDef {
+    /// Construct a `TraitObjectDef` from its type id function
+    #[inline]
+    pub const fn new(type_id: TraitObjectTypeIdFn) -> Self {
+        Self { type_id }
+    }
+
+    /// Returns the shape of the concrete value behind a trait object, if it
+    /// was registered.
+    ///
+    /// Always returns `None` without the `std` feature.
+    ///
+    /// # Safety
+    ///
+    /// `value` must be a wide pointer to an initialized trait object of the
+    /// shape this def belongs to.
+    #[inline]
+    pub unsafe fn concrete_shape(&self, value: PtrConst) -> Option<&'static Shape> {
+        #[cfg(feature = "std")]
+        {
+            crate::registered_shape(unsafe { (self.type_id)(value) })
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = value;
+            None
+        }
+    }
+}
+
+/// Implements `Facet` for `dyn Trait` and `Box<dyn Trait>`.
+///
+/// The trait must have [`Any`](core::any::Any) as a supertrait, so that the
+/// concrete type of a trait object can be found at runtime. Each concrete
+/// type must derive `Facet` and be registered with
+/// [`register_shape`](crate::register_shape) before its trait objects can be
+/// peeked into; unregistered ones are treated as opaque.
+///
+/// ```
+/// use core::any::Any;
+/// use facet_core::{Def, Facet, impl_facet_for_dyn};
+///
+/// pub trait Plugin: Any {
+///     fn name(&self) -> &str;
+/// }
+///
+/// impl_facet_for_dyn!(Plugin);
+///
+/// assert!(matches!(<dyn Plugin as Facet>::SHAPE.def, Def::TraitObject(_)));
+/// assert!(matches!(<Box<dyn Plugin> as Facet>::SHAPE.def, Def::Pointer(_)));
+/// ```
+#[macro_export]
+macro_rules! impl_facet_for_dyn {
+    ($trait:path) => {
+        const _: () = {
+            extern crate alloc as __alloc;
+
+            unsafe impl<'facet> $crate::Facet<'facet> for dyn $trait {
+                const SHAPE: &'static $crate::Shape = &const {
+                    unsafe fn concrete_type_id(value: $crate::PtrConst) -> ::core::any::TypeId {
+                        let object: &dyn $trait = unsafe { value.get::<dyn $trait>() };
+                        let any: &dyn ::core::any::Any = object;
+                        any.type_id()
+                    }
+
+                    $crate::ShapeBuilder::for_unsized::<dyn $trait>(::core::concat!(
+                        "dyn ",
+                        ::core::stringify!($trait)
+                    ))
+                    .ty($crate::Type::User($crate::UserType::Opaque))
+                    .def($crate::Def::TraitObject($crate::TraitObjectDef::new(
+                        concrete_type_id,
+                    )))
+                    .build()
+                };
+            }
+
+            unsafe impl<'facet> $crate::Facet<'facet> for __alloc::boxed::Box<dyn $trait> {
+                const SHAPE: &'static $crate::Shape = &const {
+                    unsafe fn drop_in_place(ox: $crate::OxPtrMut) {
+                        unsafe {
+                            ::core::ptr::drop_in_place(
+                                ox.ptr().as_mut_ptr::<__alloc::boxed::Box<dyn $trait>>(),
+                            )
+                        };
+                    }
+
+                    unsafe fn borrow(this: $crate::PtrConst) -> $crate::PtrConst {
+                        let boxed = unsafe { this.get::<__alloc::boxed::Box<dyn $trait>>() };
+                        let object: &dyn $trait = &**boxed;
+                        $crate::PtrConst::new(object as *const dyn $trait)
+                    }
+
+                    fn type_name(
+                        _shape: &'static $crate::Shape,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                        _opts: $crate::TypeNameOpts,
+                    ) -> ::core::fmt::Result {
+                        f.write_str(::core::concat!("Box<dyn ", ::core::stringify!($trait), ">"))
+                    }
+
+                    const TYPE_OPS: $crate::TypeOpsIndirect = $crate::TypeOpsIndirect {
+                        drop_in_place,
+                        default_in_place: None,
+                        clone_into: None,
+                        is_truthy: None,
+                    };
+
+                    const POINTER_VTABLE: $crate::PointerVTable = $crate::PointerVTable {
+                        borrow_fn: Some(borrow),
+                        ..$crate::PointerVTable::new()
+                    };
+
+                    $crate::ShapeBuilder::for_sized::<Self>("Box")
+                        .type_name(type_name)
+                        .vtable_indirect(&$crate::VTableIndirect::EMPTY)
+                        .type_ops_indirect(&TYPE_OPS)
+                        .ty($crate::Type::User($crate::UserType::Opaque))
+                        .def($crate::Def::Pointer($crate::PointerDef {
+                            vtable: &POINTER_VTABLE,
+                            pointee: Some(<dyn $trait as $crate::Facet>::SHAPE),
+                            weak: None,
+                            strong: None,
+                            flags: $crate::PointerFlags::EMPTY,
+                            known: Some($crate::KnownPointer::Box),
+                        }))
+                        .build()
+                };
+            }
+        };
+    };
+}
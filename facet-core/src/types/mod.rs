@@ -46,6 +46,19 @@ mod bitflags;
 mod const_typeid;
 pub use const_typeid::*;
 
+// Type ids that are stable across builds
+mod stable_id;
+pub use stable_id::*;
+
+// Shape registry (for resolving trait objects)
+#[cfg(feature = "std")]
+mod registry;
+#[cfg(feature = "std")]
+pub use registry::*;
+
+// Self-registration emitted by the derive macro
+mod registry_macro;
+
 // Scalar type identification
 mod scalar;
 pub use scalar::*;
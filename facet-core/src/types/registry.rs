@@ -0,0 +1,188 @@
+//! The process-wide registry of shapes, filled at runtime with
+//! [`register_shape`] and, with the `registry` feature, by derived types
+//! adding themselves before `main` runs.
+
+use alloc::collections::BTreeMap;
+use core::any::TypeId;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{Shape, StableTypeId};
+
+/// A registered shape, and the module of its type if it added itself
+#[derive(Clone, Copy)]
+struct Registered {
+    shape: &'static Shape,
+    #[cfg(feature = "registry")]
+    module_path: Option<&'static str>,
+}
+
+static SHAPES: RwLock<BTreeMap<TypeId, Registered>> = RwLock::new(BTreeMap::new());
+
+fn read_shapes() -> RwLockReadGuard<'static, BTreeMap<TypeId, Registered>> {
+    #[cfg(feature = "registry")]
+    add_derived_shapes();
+    SHAPES
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn write_shapes() -> RwLockWriteGuard<'static, BTreeMap<TypeId, Registered>> {
+    #[cfg(feature = "registry")]
+    add_derived_shapes();
+    SHAPES
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Adds the types that submitted a [`ShapeRegistration`], the first time the
+/// registry is used
+#[cfg(feature = "registry")]
+fn add_derived_shapes() {
+    static ADDED: std::sync::Once = std::sync::Once::new();
+    ADDED.call_once(|| {
+        let mut shapes = SHAPES
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        for registration in inventory::iter::<ShapeRegistration> {
+            shapes.insert(
+                registration.shape.type_id(),
+                Registered {
+                    shape: registration.shape,
+                    module_path: Some(registration.module_path),
+                },
+            );
+        }
+    });
+}
+
+/// Registers `shape` so it can be found by [`registered_shape`].
+///
+/// This is how trait objects (see [`TraitObjectDef`](crate::TraitObjectDef))
+/// are resolved to their concrete type: every type that can end up behind a
+/// `Box<dyn Trait>` should be registered once, typically at startup.
+/// Registering the same shape again is a no-op.
+pub fn register_shape(shape: &'static Shape) {
+    write_shapes().entry(shape.type_id()).or_insert(Registered {
+        shape,
+        #[cfg(feature = "registry")]
+        module_path: None,
+    });
+}
+
+/// Returns every registered shape, in no particular order.
+pub fn registered_shapes() -> alloc::vec::Vec<&'static Shape> {
+    read_shapes()
+        .values()
+        .map(|registered| registered.shape)
+        .collect()
+}
+
+/// Returns the registered shape of the type with the given [`TypeId`], if any.
+pub fn registered_shape(type_id: TypeId) -> Option<&'static Shape> {
+    read_shapes()
+        .get(&type_id)
+        .map(|registered| registered.shape)
+}
+
+/// Returns the registered shape with the given [`StableTypeId`], if any.
+///
+/// Unlike a `TypeId`, a stable id can come from another build or another
+/// process, e.g. as a type tag in a message.
+pub fn registered_shape_by_stable_id(id: StableTypeId) -> Option<&'static Shape> {
+    read_shapes()
+        .values()
+        .map(|registered| registered.shape)
+        .find(|shape| shape.stable_id() == id)
+}
+
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+pub use inventory as __inventory;
+
+/// A type that added itself to the [`registry`] through `#[derive(Facet)]`.
+#[cfg(feature = "registry")]
+#[derive(Clone, Copy, Debug)]
+pub struct ShapeRegistration {
+    module_path: &'static str,
+    shape: &'static Shape,
+}
+
+#[cfg(feature = "registry")]
+impl ShapeRegistration {
+    #[doc(hidden)]
+    pub const fn new(module_path: &'static str, shape: &'static Shape) -> Self {
+        Self { module_path, shape }
+    }
+
+    /// The module the type is declared in, e.g. `myapp::config`.
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    /// The shape of the type.
+    pub fn shape(&self) -> &'static Shape {
+        self.shape
+    }
+
+    /// Whether `path` is the module path followed by the type name.
+    fn is_named(&self, path: &str) -> bool {
+        path.strip_prefix(self.module_path)
+            .and_then(|rest| rest.strip_prefix("::"))
+            == Some(self.shape.type_identifier)
+    }
+}
+
+#[cfg(feature = "registry")]
+inventory::collect!(ShapeRegistration);
+
+/// The types that added themselves through `#[derive(Facet)]`, see
+/// [`registry`].
+#[cfg(feature = "registry")]
+#[derive(Debug)]
+pub struct Registry {
+    _private: (),
+}
+
+/// Returns the registry of derived types.
+///
+/// With the `registry` feature enabled, every type that derives `Facet` and
+/// has no type or const parameters is added to the shape registry before
+/// `main` runs, so tools can go from a name to a [`Shape`] at runtime:
+///
+/// ```ignore
+/// let shape = facet::registry().find("myapp::Config").unwrap();
+/// ```
+///
+/// Derived types can also be found with [`registered_shape`] and
+/// [`registered_shape_by_stable_id`], without registering them first. Shapes
+/// added with [`register_shape`] have no module path, so they can't be found
+/// by name.
+#[cfg(feature = "registry")]
+pub fn registry() -> &'static Registry {
+    static REGISTRY: Registry = Registry { _private: () };
+    &REGISTRY
+}
+
+#[cfg(feature = "registry")]
+impl Registry {
+    /// Returns the shape of the type at `path`, e.g. `myapp::config::Config`.
+    pub fn find(&self, path: &str) -> Option<&'static Shape> {
+        self.iter()
+            .find(|registration| registration.is_named(path))
+            .map(|registration| registration.shape)
+    }
+
+    /// Iterates over the derived types, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = ShapeRegistration> {
+        let registrations: alloc::vec::Vec<_> = read_shapes()
+            .values()
+            .filter_map(|registered| {
+                Some(ShapeRegistration::new(
+                    registered.module_path?,
+                    registered.shape,
+                ))
+            })
+            .collect();
+        registrations.into_iter()
+    }
+}
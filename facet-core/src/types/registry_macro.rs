@@ -0,0 +1,30 @@
+//! The hook `#[derive(Facet)]` uses to add types to the [`registry`](crate::registry).
+//!
+//! The derive macro can't see which features of this crate are enabled, so it
+//! always emits a call to `__register_shape!`, and the feature decides whether
+//! that call expands to anything.
+
+/// Adds a derived type to the registry. Expands to nothing unless the
+/// `registry` feature is enabled.
+#[doc(hidden)]
+#[cfg(feature = "registry")]
+#[macro_export]
+macro_rules! __register_shape {
+    ($ty:ty) => {
+        $crate::__inventory::submit! {
+            $crate::ShapeRegistration::new(
+                ::core::module_path!(),
+                <$ty as $crate::Facet<'static>>::SHAPE,
+            )
+        }
+    };
+}
+
+/// Adds a derived type to the registry. Expands to nothing unless the
+/// `registry` feature is enabled.
+#[doc(hidden)]
+#[cfg(not(feature = "registry"))]
+#[macro_export]
+macro_rules! __register_shape {
+    ($ty:ty) => {};
+}
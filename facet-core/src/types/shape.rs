@@ -167,6 +167,17 @@ impl core::hash::Hash for Shape {
 }
 
 impl Shape {
+    /// The [`TypeId`](core::any::TypeId) of the type this shape describes.
+    ///
+    /// Unlike the address of the shape, this is the same for every copy of a
+    /// shape within a process (e.g. `Vec<String>` instantiated from several
+    /// crates), so it can be used directly as a map key. `Shape` itself
+    /// compares and hashes by this id, so `&'static Shape` works as a key too.
+    #[inline]
+    pub fn type_id(&self) -> core::any::TypeId {
+        self.id.get()
+    }
+
     /// Check if this shape is of the given type
     #[inline]
     pub fn is_shape(&self, other: &Shape) -> bool {
@@ -492,7 +503,9 @@ impl Shape {
                     }
                     Def::Option(option_def) => option_def.t.computed_variance_impl(depth + 1),
                     // Leaf types with no type parameters - use declared variance
-                    Def::Scalar | Def::Undefined | Def::DynamicValue(_) => (self.variance)(self),
+                    Def::Scalar | Def::Undefined | Def::DynamicValue(_) | Def::TraitObject(_) => {
+                        (self.variance)(self)
+                    }
                 }
             }
         }
@@ -0,0 +1,148 @@
+use core::fmt;
+
+use crate::{Shape, ShapeLayout, StructType, Type, UserType};
+
+/// A type identifier that is the same in every build and every process.
+///
+/// [`ConstTypeId`](crate::ConstTypeId) wraps the compiler's `TypeId`, which
+/// may change whenever the code is recompiled. A `StableTypeId` is instead a
+/// 64-bit [FNV-1a](Fnv1a64) hash of what the shape describes: the full type name with its
+/// generic parameters, the size and alignment, and for structs and enums the
+/// names and type names of every variant and field. Registries and
+/// cross-process protocols can use it to agree on types, as long as both
+/// sides are built for targets with the same layout.
+///
+/// Two types with the same name and the same structure get the same id even
+/// if they live in different modules.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StableTypeId(u64);
+
+impl StableTypeId {
+    /// Wraps an id received from elsewhere, e.g. over the wire.
+    #[inline]
+    pub const fn from_u64(id: u64) -> Self {
+        Self(id)
+    }
+
+    /// The id as a number, e.g. to send it over the wire.
+    #[inline]
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Debug for StableTypeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StableTypeId({:016x})", self.0)
+    }
+}
+
+impl fmt::Display for StableTypeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// The 64-bit FNV-1a hash
+///
+/// Fast, tiny and the same on every platform, which makes it a good fit for
+/// fingerprints that are compared across builds and processes, like
+/// [`StableTypeId`]s. It is not meant to withstand deliberate collisions.
+/// Implements `fmt::Write`, so formatted text can be hashed without
+/// allocating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fnv1a64(u64);
+
+impl Fnv1a64 {
+    /// A hasher that hasn't been fed anything yet.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    /// Feeds `bytes` to the hasher.
+    #[inline]
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    /// The hash of everything fed so far.
+    #[inline]
+    pub const fn finish(&self) -> u64 {
+        self.0
+    }
+
+    /// Hashes `s` followed by a separator, so `("ab", "c")` and `("a", "bc")`
+    /// hash differently.
+    fn write_part(&mut self, s: &str) {
+        self.write(s.as_bytes());
+        self.write(&[0xff]);
+    }
+
+    fn write_shape_name(&mut self, shape: &Shape) {
+        let _ = fmt::write(self, format_args!("{shape}"));
+        self.write(&[0xff]);
+    }
+
+    fn write_fields(&mut self, struct_type: &StructType) {
+        self.write_part(match struct_type.kind {
+            crate::StructKind::Unit => "unit",
+            crate::StructKind::TupleStruct => "tuple struct",
+            crate::StructKind::Struct => "struct",
+            crate::StructKind::Tuple => "tuple",
+        });
+        for field in struct_type.fields {
+            self.write_part(field.name);
+            self.write_shape_name(field.shape());
+        }
+    }
+}
+
+impl Default for Fnv1a64 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Write for Fnv1a64 {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl Shape {
+    /// A [`StableTypeId`] for this shape, identical across builds and
+    /// processes.
+    ///
+    /// Field types are identified by name rather than hashed recursively, so
+    /// this is cheap and works for recursive types.
+    pub fn stable_id(&self) -> StableTypeId {
+        let mut hasher = Fnv1a64::new();
+        hasher.write_shape_name(self);
+        match self.layout {
+            ShapeLayout::Sized(layout) => {
+                hasher.write(&(layout.size() as u64).to_le_bytes());
+                hasher.write(&(layout.align() as u64).to_le_bytes());
+            }
+            ShapeLayout::Unsized => hasher.write_part("unsized"),
+        }
+        match self.ty {
+            Type::User(UserType::Struct(struct_type)) => hasher.write_fields(&struct_type),
+            Type::User(UserType::Enum(enum_type)) => {
+                for variant in enum_type.variants {
+                    hasher.write_part(variant.name);
+                    if let Some(discriminant) = variant.discriminant {
+                        hasher.write(&discriminant.to_le_bytes());
+                    }
+                    hasher.write_fields(&variant.data);
+                }
+            }
+            _ => {}
+        }
+        StableTypeId(hasher.finish())
+    }
+}
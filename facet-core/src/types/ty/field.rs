@@ -1,4 +1,4 @@
-use crate::{DefaultInPlaceFn, InvariantsFn, PtrConst};
+use crate::{DefaultInPlaceFn, InvariantsFn, InvariantsMessageFn, PtrConst};
 
 use super::Shape;
 
@@ -86,6 +86,10 @@ pub struct Field {
     /// key for the struct field (for tuples and tuple-structs, this is the 0-based index)
     pub name: &'static str,
 
+    /// the field's identifier as written in the Rust source, before any rename
+    /// rules are applied (e.g. `r#type`, or `0` for tuple fields)
+    pub ident: &'static str,
+
     /// shape of the inner type
     ///
     /// [`ShapeRef`] wraps a function that returns the shape, enabling lazy evaluation
@@ -137,6 +141,10 @@ pub struct Field {
     /// Set by `#[facet(invariants = fn_name)]`.
     pub invariants: Option<InvariantsFn>,
 
+    /// Like `invariants`, but says what's wrong when the check fails.
+    /// Set by `#[facet(invariants = fn_name)]`.
+    pub invariants_message: Option<InvariantsMessageFn>,
+
     /// Proxy definition for custom serialization/deserialization.
     /// Set by `#[facet(proxy = ProxyType)]`.
     #[cfg(feature = "alloc")]
@@ -175,6 +183,22 @@ impl Field {
         self.flags.contains(FieldFlags::SENSITIVE)
     }
 
+    /// Returns true if this field's value is encrypted in serialized output.
+    ///
+    /// Set by `#[facet(encrypt)]`, which also marks the field as sensitive.
+    #[inline]
+    pub fn is_encrypted(&self) -> bool {
+        self.has_builtin_attr("encrypt")
+    }
+
+    /// Returns true if this field is skipped by reflective equality.
+    ///
+    /// Set by `#[facet(ignore_in_eq)]`.
+    #[inline]
+    pub fn is_ignored_in_eq(&self) -> bool {
+        self.has_builtin_attr("ignore_in_eq")
+    }
+
     /// Returns true if this field has a default value.
     ///
     /// This returns true for both `#[facet(default)]` (uses the type's Default impl)
@@ -301,6 +325,70 @@ impl Field {
     pub fn effective_name(&self) -> &'static str {
         self.rename.unwrap_or(self.name)
     }
+
+    /// Returns the name this field takes under a rename profile.
+    ///
+    /// Profiles are declared with `#[facet(rename(json = "fooBar", db = "foo_bar"))]`
+    /// and stored as `rename::<profile>` attributes. Returns `None` if the field
+    /// has no rename for `profile`.
+    #[inline]
+    pub fn rename_for(&self, profile: &str) -> Option<&'static str> {
+        self.get_attr(Some("rename"), profile)
+            .and_then(|attr| attr.get_as::<&'static str>())
+            .copied()
+    }
+
+    /// Returns the item field a list field is sorted by, if declared with
+    /// `#[facet(sorted_by = "id")]`.
+    #[inline]
+    pub fn sorted_by(&self) -> Option<&'static str> {
+        self.get_builtin_attr("sorted_by")
+            .and_then(|attr| attr.get_as::<&'static str>())
+            .copied()
+    }
+
+    /// Returns the unit a numeric field is measured in, if declared with
+    /// `#[facet(unit = "bytes")]`.
+    #[inline]
+    pub fn unit(&self) -> Option<&'static str> {
+        self.get_builtin_attr("unit")
+            .and_then(|attr| attr.get_as::<&'static str>())
+            .copied()
+    }
+
+    /// Returns the codec binary formats compress this field's value with, if
+    /// declared with `#[facet(compress = "zstd")]`.
+    #[inline]
+    pub fn compression(&self) -> Option<&'static str> {
+        self.get_builtin_attr("compress")
+            .and_then(|attr| attr.get_as::<&'static str>())
+            .copied()
+    }
+
+    /// Returns the display order of this field among its siblings, if
+    /// declared with `#[facet(order = 1)]`.
+    ///
+    /// Human-facing output (pretty printers, schema generators) may list
+    /// fields by ascending order instead of declaration order. Fields without
+    /// an order come after all ordered fields.
+    #[inline]
+    pub fn order(&self) -> Option<i64> {
+        self.get_builtin_attr("order")
+            .and_then(|attr| attr.get_as::<i64>())
+            .copied()
+    }
+
+    /// Returns the display group of this field, if declared with
+    /// `#[facet(group = "network")]`.
+    ///
+    /// Human-facing output may cluster fields of the same group together and
+    /// render a header for each group.
+    #[inline]
+    pub fn group(&self) -> Option<&'static str> {
+        self.get_builtin_attr("group")
+            .and_then(|attr| attr.get_as::<&'static str>())
+            .copied()
+    }
 }
 
 /// A function that, if present, determines whether field should be included in the serialization
@@ -500,6 +588,7 @@ impl core::fmt::Display for FieldError {
 /// ```
 pub struct FieldBuilder {
     name: &'static str,
+    ident: &'static str,
     shape: ShapeRef,
     offset: usize,
     flags: FieldFlags,
@@ -510,6 +599,7 @@ pub struct FieldBuilder {
     default: Option<DefaultSource>,
     skip_serializing_if: Option<SkipSerializingIfFn>,
     invariants: Option<InvariantsFn>,
+    invariants_message: Option<InvariantsMessageFn>,
     #[cfg(feature = "alloc")]
     proxy: Option<&'static super::ProxyDef>,
     metadata: Option<&'static str>,
@@ -529,6 +619,7 @@ impl FieldBuilder {
     pub const fn new(name: &'static str, shape: fn() -> &'static Shape, offset: usize) -> Self {
         Self {
             name,
+            ident: name,
             shape: ShapeRef(shape),
             offset,
             flags: FieldFlags::empty(),
@@ -539,6 +630,7 @@ impl FieldBuilder {
             default: None,
             skip_serializing_if: None,
             invariants: None,
+            invariants_message: None,
             #[cfg(feature = "alloc")]
             proxy: None,
             metadata: None,
@@ -557,6 +649,13 @@ impl FieldBuilder {
         Self::new(name, shape, offset)
     }
 
+    /// Sets the Rust identifier for this field, when it differs from the name.
+    #[inline]
+    pub const fn ident(mut self, ident: &'static str) -> Self {
+        self.ident = ident;
+        self
+    }
+
     /// Sets the attributes for this field.
     #[inline]
     pub const fn attributes(mut self, attributes: &'static [FieldAttribute]) -> Self {
@@ -620,6 +719,13 @@ impl FieldBuilder {
         self
     }
 
+    /// Sets the invariants validation function that explains failures.
+    #[inline]
+    pub const fn invariants_message(mut self, f: InvariantsMessageFn) -> Self {
+        self.invariants_message = Some(f);
+        self
+    }
+
     /// Sets the proxy definition for custom ser/de.
     #[cfg(feature = "alloc")]
     #[inline]
@@ -648,6 +754,7 @@ impl FieldBuilder {
     pub const fn build(self) -> Field {
         Field {
             name: self.name,
+            ident: self.ident,
             shape: self.shape,
             offset: self.offset,
             flags: self.flags,
@@ -658,6 +765,7 @@ impl FieldBuilder {
             default: self.default,
             skip_serializing_if: self.skip_serializing_if,
             invariants: self.invariants,
+            invariants_message: self.invariants_message,
             #[cfg(feature = "alloc")]
             proxy: self.proxy,
             metadata: self.metadata,
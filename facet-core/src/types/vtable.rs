@@ -972,6 +972,36 @@ pub type DefaultInPlaceFn = unsafe fn(target: crate::PtrUninit) -> crate::PtrMut
 /// Used by the `#[facet(invariants = fn)]` attribute.
 pub type InvariantsFn = unsafe fn(value: crate::PtrConst) -> bool;
 
+/// Function type for type invariant validation that explains failures.
+/// Used by the `#[facet(invariants = fn)]` attribute on fields, alongside
+/// [`InvariantsFn`].
+pub type InvariantsMessageFn = unsafe fn(value: crate::PtrConst) -> Result<(), String>;
+
+/// What a `#[facet(invariants = fn)]` function may return: a `bool`, or a
+/// `Result` whose error says what's wrong (e.g. "port must be < 65536").
+pub trait InvariantsOutcome {
+    /// Converts the outcome into the error message to report, if any.
+    fn into_invariants_result(self) -> Result<(), String>;
+}
+
+impl InvariantsOutcome for bool {
+    #[inline]
+    fn into_invariants_result(self) -> Result<(), String> {
+        if self {
+            Ok(())
+        } else {
+            Err(String::from("invariant check failed"))
+        }
+    }
+}
+
+impl<E: fmt::Display> InvariantsOutcome for Result<(), E> {
+    #[inline]
+    fn into_invariants_result(self) -> Result<(), String> {
+        self.map_err(|e| alloc::string::ToString::to_string(&e))
+    }
+}
+
 /// Function type for truthiness checks used by skip_unless_truthy-style helpers.
 pub type TruthyFn = unsafe fn(value: crate::PtrConst) -> bool;
 
@@ -0,0 +1,42 @@
+use core::any::TypeId;
+use std::collections::{HashMap, HashSet};
+
+use facet_core::{ConstTypeId, Facet, Shape};
+
+#[test]
+fn type_id_matches_std() {
+    assert_eq!(<Vec<String>>::SHAPE.type_id(), TypeId::of::<Vec<String>>());
+    assert_ne!(<Vec<String>>::SHAPE.type_id(), <Vec<u8>>::SHAPE.type_id());
+}
+
+#[test]
+fn shapes_dedup_in_hash_collections() {
+    let shapes: HashSet<&'static Shape> = [
+        <Vec<String>>::SHAPE,
+        <Vec<String>>::SHAPE,
+        <Option<u32>>::SHAPE,
+        <Option<u32>>::SHAPE,
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(shapes.len(), 2);
+
+    let mut by_id = HashMap::new();
+    by_id.insert(<Vec<String>>::SHAPE.type_id(), "strings");
+    assert_eq!(by_id[&<Vec<String>>::SHAPE.type_id()], "strings");
+}
+
+#[test]
+fn const_type_id_hash_agrees_with_eq() {
+    use std::hash::{BuildHasher, RandomState};
+
+    let state = RandomState::new();
+    let a = ConstTypeId::of::<Vec<String>>();
+    let b = <Vec<String>>::SHAPE.id;
+    assert_eq!(a, b);
+    assert_eq!(state.hash_one(a), state.hash_one(b));
+    assert_eq!(
+        state.hash_one(a),
+        state.hash_one(TypeId::of::<Vec<String>>())
+    );
+}
@@ -27,7 +27,7 @@ mod serializer;
 
 pub use error::{CsvError, CsvErrorKind};
 pub use parser::CsvParser;
-pub use serializer::{CsvSerializeError, CsvSerializer, to_string, to_vec, to_writer};
+pub use serializer::{CsvSerializeError, CsvSerializer, escape, to_string, to_vec, to_writer};
 
 // Re-export DeserializeError for convenience
 pub use facet_format::DeserializeError;
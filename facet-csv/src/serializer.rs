@@ -2,6 +2,7 @@
 
 extern crate alloc;
 
+use alloc::borrow::Cow;
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -46,25 +47,23 @@ impl CsvSerializer {
     }
 
     fn write_csv_escaped(&mut self, s: &str) {
-        // Check if we need to quote the field
-        let needs_quoting =
-            s.contains(',') || s.contains('"') || s.contains('\n') || s.contains('\r');
-
-        if needs_quoting {
-            self.out.push(b'"');
-            for c in s.chars() {
-                if c == '"' {
-                    self.out.extend_from_slice(b"\"\"");
-                } else {
-                    let mut buf = [0u8; 4];
-                    let len = c.encode_utf8(&mut buf).len();
-                    self.out.extend_from_slice(&buf[..len]);
-                }
-            }
-            self.out.push(b'"');
-        } else {
-            self.out.extend_from_slice(s.as_bytes());
-        }
+        self.out.extend_from_slice(escape(s).as_bytes());
+    }
+}
+
+/// Quote `field` for a CSV row if it contains a comma, quote or line break,
+/// doubling any quotes in it.
+///
+/// ```
+/// assert_eq!(facet_csv::escape("plain"), "plain");
+/// assert_eq!(facet_csv::escape("a, \"b\""), "\"a, \"\"b\"\"\"");
+/// ```
+pub fn escape(field: &str) -> Cow<'_, str> {
+    let needs_quoting = field.contains([',', '"', '\n', '\r']);
+    if needs_quoting {
+        Cow::Owned(alloc::format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
     }
 }
 
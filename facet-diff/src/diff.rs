@@ -19,6 +19,17 @@ pub struct DiffOptions {
     /// is less than or equal to this value.
     pub float_tolerance: Option<f64>,
 
+    /// Relative tolerance for floating-point comparisons.
+    /// If set, two floats are considered equal if their absolute difference
+    /// is at most this fraction of the larger magnitude. Combines with
+    /// `float_tolerance`: floats within either tolerance are equal.
+    pub float_relative_tolerance: Option<f64>,
+
+    /// Whether to ignore fractional seconds when comparing times.
+    /// If set, date/time values (from `chrono`, `time` or `jiff`) that agree
+    /// down to the second are considered equal.
+    pub ignore_subsecond: bool,
+
     /// Similarity threshold for tree-based element matching in sequences.
     /// If set, sequence elements with structural similarity >= this threshold
     /// are paired for inline diffing rather than shown as remove+add.
@@ -40,6 +51,19 @@ impl DiffOptions {
         self
     }
 
+    /// Set the relative tolerance for floating-point comparisons, e.g.
+    /// `1e-9` for "equal to nine significant digits".
+    pub fn with_float_relative_tolerance(mut self, tolerance: f64) -> Self {
+        self.float_relative_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Ignore fractional seconds when comparing times.
+    pub fn with_ignore_subsecond(mut self) -> Self {
+        self.ignore_subsecond = true;
+        self
+    }
+
     /// Set the similarity threshold for tree-based element matching.
     ///
     /// When set, sequence elements with structural similarity >= this threshold
@@ -94,11 +118,9 @@ pub fn diff_new_peek_with_options<'mem, 'facet>(
     let to_has_partialeq = to.shape().is_partial_eq();
     let values_equal = from == to;
 
-    // Check float tolerance if configured
-    let float_equal = options
-        .float_tolerance
-        .map(|tol| check_float_tolerance(from, to, tol))
-        .unwrap_or(false);
+    // Check float and time tolerances if configured
+    let float_equal = !values_equal && check_float_tolerance(from, to, options);
+    let time_equal = !values_equal && options.ignore_subsecond && check_same_second(from, to);
 
     // log::trace!(
     //     "diff_new_peek: type={} same_type={} from_has_partialeq={} to_has_partialeq={} values_equal={}",
@@ -109,7 +131,11 @@ pub fn diff_new_peek_with_options<'mem, 'facet>(
     //     values_equal
     // );
 
-    if same_type && from_has_partialeq && to_has_partialeq && (values_equal || float_equal) {
+    if same_type
+        && from_has_partialeq
+        && to_has_partialeq
+        && (values_equal || float_equal || time_equal)
+    {
         return Diff::Equal { value: Some(from) };
     }
 
@@ -755,14 +781,83 @@ fn try_extract_float(peek: Peek) -> Option<f64> {
     }
 }
 
-/// Check if two Peek values are equal within the specified float tolerance
-fn check_float_tolerance(from: Peek, to: Peek, tolerance: f64) -> bool {
-    match (try_extract_float(from), try_extract_float(to)) {
-        (Some(f1), Some(f2)) => (f1 - f2).abs() <= tolerance,
+/// Check if two Peek values are equal within the configured float tolerances
+fn check_float_tolerance(from: Peek, to: Peek, options: &DiffOptions) -> bool {
+    if options.float_tolerance.is_none() && options.float_relative_tolerance.is_none() {
+        return false;
+    }
+    let (Some(f1), Some(f2)) = (try_extract_float(from), try_extract_float(to)) else {
+        return false;
+    };
+    let delta = (f1 - f2).abs();
+    options.float_tolerance.is_some_and(|tol| delta <= tol)
+        || options
+            .float_relative_tolerance
+            .is_some_and(|tol| delta <= tol * f1.abs().max(f2.abs()))
+}
+
+/// Check if two date/time values agree down to the second.
+///
+/// Times are opaque scalars from crates facet-diff doesn't depend on, so they
+/// are compared by their display text, with any fractional seconds after an
+/// `HH:MM:SS` clock dropped.
+fn check_same_second(from: Peek, to: Peek) -> bool {
+    let is_time = |peek: Peek| {
+        matches!(peek.shape().def, Def::Scalar)
+            && peek.scalar_type().is_none()
+            && peek.shape().is_display()
+    };
+    if !is_time(from) || !is_time(to) {
+        return false;
+    }
+    match (
+        truncate_seconds(&from.to_string()),
+        truncate_seconds(&to.to_string()),
+    ) {
+        (Some(a), Some(b)) => a == b,
         _ => false,
     }
 }
 
+/// `text` without the fraction after each `HH:MM:SS` clock, or `None` if it
+/// has no clock
+fn truncate_seconds(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let is_clock = |end: usize| {
+        end >= 8
+            && bytes[end - 8..end].iter().enumerate().all(|(i, &b)| {
+                if i % 3 == 2 {
+                    b == b':'
+                } else {
+                    b.is_ascii_digit()
+                }
+            })
+    };
+
+    let mut out = String::with_capacity(text.len());
+    let mut found = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        if is_clock(i) {
+            found = true;
+            if matches!(bytes[i], b'.' | b',') && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+                i += 1;
+                while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+                    i += 1;
+                }
+                continue;
+            }
+        }
+        let c = text[i..].chars().next()?;
+        out.push(c);
+        i += c.len_utf8();
+    }
+    if is_clock(bytes.len()) {
+        found = true;
+    }
+    found.then_some(out)
+}
+
 /// Dereference a pointer/reference to get the underlying value
 fn deref_if_pointer<'mem, 'facet>(peek: Peek<'mem, 'facet>) -> Peek<'mem, 'facet> {
     if let Ok(ptr) = peek.into_pointer()
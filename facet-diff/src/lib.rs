@@ -3,6 +3,9 @@
 #![doc = include_str!("../README.md")]
 
 mod diff;
+mod patch;
+#[cfg(feature = "repl")]
+mod repl;
 mod report;
 mod sequences;
 mod tree;
@@ -12,6 +15,9 @@ pub use diff::{
     diff_new_peek, diff_new_peek_with_options, format_diff, format_diff_compact,
     format_diff_compact_plain, format_diff_default,
 };
+pub use patch::{Hunk, Patch, PatchError};
+#[cfg(feature = "repl")]
+pub use repl::{Repl, ReplError};
 pub use report::DiffReport;
 pub use tree::{
     EditOp, FacetTree, NodeKind, NodeLabel, SimilarityResult, build_tree,
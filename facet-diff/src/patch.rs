@@ -0,0 +1,613 @@
+//! A reviewable, re-applicable textual patch format.
+//!
+//! A [`Patch`] is a list of [`Hunk`]s, each naming a location and the value
+//! found there before and after the change. Values are rendered as single-line
+//! JSON, so a patch reads like a unified diff and survives copy-paste:
+//!
+//! ```text
+//! @@ .server.port @@
+//! -8080
+//! +9090
+//! @@ .tags[1] @@
+//! -"beta"
+//! @@ .tags[1] @@
+//! +"rc"
+//! ```
+//!
+//! A hunk with both lines replaces a value, a `-` line alone removes a list
+//! element or map entry, and a `+` line alone inserts one. Hunks are applied in
+//! order, so list indices refer to the list as left by the previous hunks.
+//! Applying checks every `-` line against the current value and fails with
+//! [`PatchError::Conflict`] if it doesn't match.
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_diff::Patch;
+//!
+//! #[derive(Facet, Debug, PartialEq)]
+//! struct Config { name: String, port: u16 }
+//!
+//! let old = Config { name: "api".into(), port: 8080 };
+//! let new = Config { name: "api".into(), port: 9090 };
+//!
+//! let text = Patch::between(&old, &new).unwrap().to_string();
+//! assert_eq!(text, "@@ .port @@\n-8080\n+9090\n");
+//!
+//! let patch: Patch = text.parse().unwrap();
+//! assert_eq!(patch.apply(&old).unwrap(), new);
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use facet::Facet;
+use facet_core::{Def, Shape, StructKind, Type, UserType};
+use facet_reflect::Peek;
+use facet_value::Value;
+
+use crate::{Diff, Path, PathSegment, ReplaceGroup, Updates, UpdatesGroup, diff_new_peek};
+
+/// A textual patch: an ordered list of hunks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Patch {
+    /// The hunks, in application order
+    pub hunks: Vec<Hunk>,
+}
+
+/// One change in a [`Patch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// Where the change happens
+    pub path: Path,
+    /// The value before the change, as JSON (`None` for insertions)
+    pub old: Option<String>,
+    /// The value after the change, as JSON (`None` for removals)
+    pub new: Option<String>,
+}
+
+/// Errors from building, parsing or applying a [`Patch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    /// The patch text is malformed.
+    Parse {
+        /// 1-based line number
+        line: usize,
+        /// What was wrong with it
+        message: String,
+    },
+    /// A value could not be converted to or from JSON.
+    Value(String),
+    /// A hunk's path does not exist in the value being patched.
+    PathNotFound {
+        /// The missing path, as written in the patch
+        path: String,
+    },
+    /// The value at a hunk's path is not the one the patch expects.
+    Conflict {
+        /// The path, as written in the patch
+        path: String,
+        /// The `-` line of the hunk
+        expected: String,
+        /// What was actually found there
+        found: String,
+    },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::Parse { line, message } => write!(f, "line {line}: {message}"),
+            PatchError::Value(message) => write!(f, "{message}"),
+            PatchError::PathNotFound { path } => write!(f, "path {path} not found"),
+            PatchError::Conflict {
+                path,
+                expected,
+                found,
+            } => write!(f, "conflict at {path}: expected {expected}, found {found}"),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+impl Patch {
+    /// Build the patch that turns `from` into `to`.
+    pub fn between<'a, T: Facet<'a>>(from: &T, to: &T) -> Result<Self, PatchError> {
+        let diff = diff_new_peek(Peek::new(from), Peek::new(to));
+        Self::from_diff(&diff)
+    }
+
+    /// Build a patch from a computed diff.
+    pub fn from_diff(diff: &Diff<'_, '_>) -> Result<Self, PatchError> {
+        let mut hunks = Vec::new();
+        collect_hunks(diff, Path::new(), &mut hunks)?;
+        Ok(Self { hunks })
+    }
+
+    /// Returns true if the patch changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.hunks.is_empty()
+    }
+
+    /// Apply the patch to a copy of `value`.
+    pub fn apply<T: Facet<'static>>(&self, value: &T) -> Result<T, PatchError> {
+        let json = facet_json::to_string(value).map_err(|e| PatchError::Value(e.to_string()))?;
+        let mut tree: Value =
+            facet_json::from_str(&json).map_err(|e| PatchError::Value(e.to_string()))?;
+        self.apply_to_value(&mut tree)?;
+        facet_value::from_value(tree).map_err(|e| PatchError::Value(e.to_string()))
+    }
+
+    /// Apply the patch in place to a dynamic value.
+    pub fn apply_to_value(&self, root: &mut Value) -> Result<(), PatchError> {
+        for hunk in &self.hunks {
+            hunk.apply(root)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Patch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for hunk in &self.hunks {
+            writeln!(f, "@@ {} @@", PatchPath(&hunk.path))?;
+            if let Some(old) = &hunk.old {
+                writeln!(f, "-{old}")?;
+            }
+            if let Some(new) = &hunk.new {
+                writeln!(f, "+{new}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Patch {
+    type Err = PatchError;
+
+    fn from_str(text: &str) -> Result<Self, PatchError> {
+        let mut hunks: Vec<Hunk> = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let line_no = i + 1;
+            let err = |message: &str| PatchError::Parse {
+                line: line_no,
+                message: message.to_string(),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix("@@ ") {
+                let path = header
+                    .strip_suffix(" @@")
+                    .ok_or_else(|| err("hunk header must end with ` @@`"))?;
+                let path = parse_path(path).map_err(|m| err(&m))?;
+                hunks.push(Hunk {
+                    path,
+                    old: None,
+                    new: None,
+                });
+                continue;
+            }
+            let hunk = hunks
+                .last_mut()
+                .ok_or_else(|| err("expected a `@@ path @@` hunk header"))?;
+            if let Some(old) = line.strip_prefix('-') {
+                if hunk.old.is_some() || hunk.new.is_some() {
+                    return Err(err("`-` line must come first in its hunk"));
+                }
+                hunk.old = Some(old.to_string());
+            } else if let Some(new) = line.strip_prefix('+') {
+                if hunk.new.is_some() {
+                    return Err(err("hunk has more than one `+` line"));
+                }
+                hunk.new = Some(new.to_string());
+            } else {
+                return Err(err("expected `@@`, `-` or `+`"));
+            }
+        }
+        if let Some(pos) = hunks
+            .iter()
+            .position(|h| h.old.is_none() && h.new.is_none())
+        {
+            return Err(PatchError::Parse {
+                line: text.lines().count(),
+                message: format!("hunk {} has no `-` or `+` line", pos + 1),
+            });
+        }
+        Ok(Self { hunks })
+    }
+}
+
+impl Hunk {
+    fn apply(&self, root: &mut Value) -> Result<(), PatchError> {
+        let path_str = || PatchPath(&self.path).to_string();
+        let old = self.old.as_deref().map(parse_json).transpose()?;
+        let new = self.new.as_deref().map(parse_json).transpose()?;
+
+        let check = |found: &Value| match &old {
+            Some(expected) if expected != found => Err(PatchError::Conflict {
+                path: path_str(),
+                expected: self.old.clone().unwrap_or_default(),
+                found: facet_json::to_string(found).unwrap_or_else(|_| format!("{found:?}")),
+            }),
+            _ => Ok(()),
+        };
+
+        let Some((last, parents)) = self.path.0.split_last() else {
+            // Whole-value replacement
+            check(root)?;
+            if let Some(new) = new {
+                *root = new;
+            }
+            return Ok(());
+        };
+
+        let mut parent = &mut *root;
+        for segment in parents {
+            parent = child_mut(parent, segment)
+                .ok_or_else(|| PatchError::PathNotFound { path: path_str() })?;
+        }
+        let not_found = || PatchError::PathNotFound { path: path_str() };
+
+        match (old.is_some(), new) {
+            // Replace
+            (true, Some(new)) => {
+                let target = child_mut(parent, last).ok_or_else(not_found)?;
+                check(target)?;
+                *target = new;
+            }
+            // Remove
+            (true, None) => {
+                check(child_mut(parent, last).ok_or_else(not_found)?)?;
+                match last {
+                    PathSegment::Index(i) => {
+                        parent.as_array_mut().ok_or_else(not_found)?.remove(*i);
+                    }
+                    PathSegment::Field(key) | PathSegment::Key(key) | PathSegment::Variant(key) => {
+                        parent.as_object_mut().ok_or_else(not_found)?.remove(key);
+                    }
+                }
+            }
+            // Insert
+            (false, Some(new)) => match last {
+                PathSegment::Index(i) => {
+                    let array = parent.as_array_mut().ok_or_else(not_found)?;
+                    if *i > array.len() {
+                        return Err(not_found());
+                    }
+                    array.insert(*i, new);
+                }
+                PathSegment::Field(key) | PathSegment::Key(key) | PathSegment::Variant(key) => {
+                    parent
+                        .as_object_mut()
+                        .ok_or_else(not_found)?
+                        .insert(key.as_ref(), new);
+                }
+            },
+            (false, None) => {}
+        }
+        Ok(())
+    }
+}
+
+fn parse_json(text: &str) -> Result<Value, PatchError> {
+    facet_json::from_str(text).map_err(|e| PatchError::Value(format!("invalid value {text}: {e}")))
+}
+
+fn child_mut<'v>(value: &'v mut Value, segment: &PathSegment) -> Option<&'v mut Value> {
+    match segment {
+        PathSegment::Index(i) => value.as_array_mut()?.get_mut(*i),
+        PathSegment::Field(key) | PathSegment::Key(key) | PathSegment::Variant(key) => {
+            value.as_object_mut()?.get_mut(key)
+        }
+    }
+}
+
+fn to_json(peek: Peek<'_, '_>) -> Result<String, PatchError> {
+    facet_json::peek_to_string(peek).map_err(|e| PatchError::Value(e.to_string()))
+}
+
+fn collect_hunks(diff: &Diff<'_, '_>, path: Path, hunks: &mut Vec<Hunk>) -> Result<(), PatchError> {
+    match diff {
+        Diff::Equal { .. } => {}
+        Diff::Replace { from, to } => hunks.push(Hunk {
+            path,
+            old: Some(to_json(*from)?),
+            new: Some(to_json(*to)?),
+        }),
+        Diff::User {
+            from,
+            to,
+            variant,
+            value,
+        } => {
+            let is_option = matches!(from.def, Def::Option(_));
+            let base = match variant {
+                Some(v) if !is_option => path.with(PathSegment::Variant(Cow::Borrowed(*v))),
+                _ => path,
+            };
+            match value {
+                crate::Value::Struct {
+                    updates,
+                    deletions,
+                    insertions,
+                    ..
+                } => {
+                    let mut keys: Vec<&Cow<'static, str>> = updates
+                        .keys()
+                        .chain(deletions.keys())
+                        .chain(insertions.keys())
+                        .collect();
+                    keys.sort_by_key(|k| (field_position(from, to, *variant, k), *k));
+                    for key in keys {
+                        let key_path = base.with(key_segment(from, to, *variant, key));
+                        if let Some(diff) = updates.get(key) {
+                            collect_hunks(diff, key_path, hunks)?;
+                        } else if let Some(peek) = deletions.get(key) {
+                            hunks.push(Hunk {
+                                path: key_path,
+                                old: Some(to_json(*peek)?),
+                                new: None,
+                            });
+                        } else if let Some(peek) = insertions.get(key) {
+                            hunks.push(Hunk {
+                                path: key_path,
+                                old: None,
+                                new: Some(to_json(*peek)?),
+                            });
+                        }
+                    }
+                }
+                crate::Value::Tuple { updates } => {
+                    // Single-element tuples (`Some(x)`, newtypes) serialize as their element
+                    let inline = is_option || tuple_len(from, *variant) == Some(1);
+                    collect_sequence(updates, &base, inline, hunks)?;
+                }
+            }
+        }
+        Diff::Sequence { updates, .. } => collect_sequence(updates, &path, false, hunks)?,
+    }
+    Ok(())
+}
+
+/// Walks sequence updates, tracking indices in the partially patched sequence.
+fn collect_sequence(
+    updates: &Updates<'_, '_>,
+    base: &Path,
+    inline: bool,
+    hunks: &mut Vec<Hunk>,
+) -> Result<(), PatchError> {
+    let mut index = 0;
+    if let Some(group) = &updates.0.first {
+        collect_group(group, base, inline, &mut index, hunks)?;
+    }
+    for (unchanged, group) in &updates.0.values {
+        index += unchanged.len();
+        collect_group(group, base, inline, &mut index, hunks)?;
+    }
+    Ok(())
+}
+
+fn collect_group(
+    group: &UpdatesGroup<'_, '_>,
+    base: &Path,
+    inline: bool,
+    index: &mut usize,
+    hunks: &mut Vec<Hunk>,
+) -> Result<(), PatchError> {
+    if let Some(replace) = &group.0.first {
+        collect_replace(replace, base, inline, index, hunks)?;
+    }
+    for (diffs, replace) in &group.0.values {
+        for diff in diffs {
+            collect_hunks(diff, element_path(base, inline, *index), hunks)?;
+            *index += 1;
+        }
+        collect_replace(replace, base, inline, index, hunks)?;
+    }
+    if let Some(diffs) = &group.0.last {
+        for diff in diffs {
+            collect_hunks(diff, element_path(base, inline, *index), hunks)?;
+            *index += 1;
+        }
+    }
+    Ok(())
+}
+
+fn collect_replace(
+    group: &ReplaceGroup<'_, '_>,
+    base: &Path,
+    inline: bool,
+    index: &mut usize,
+    hunks: &mut Vec<Hunk>,
+) -> Result<(), PatchError> {
+    if group.removals.len() == group.additions.len() {
+        for (from, to) in group.removals.iter().zip(&group.additions) {
+            let nested = diff_new_peek(*from, *to);
+            collect_hunks(&nested, element_path(base, inline, *index), hunks)?;
+            *index += 1;
+        }
+        return Ok(());
+    }
+    // Each removal shifts the rest down, so they all happen at the same index
+    for from in &group.removals {
+        hunks.push(Hunk {
+            path: element_path(base, inline, *index),
+            old: Some(to_json(*from)?),
+            new: None,
+        });
+    }
+    for to in &group.additions {
+        hunks.push(Hunk {
+            path: element_path(base, inline, *index),
+            old: None,
+            new: Some(to_json(*to)?),
+        });
+        *index += 1;
+    }
+    Ok(())
+}
+
+fn element_path(base: &Path, inline: bool, index: usize) -> Path {
+    if inline {
+        base.clone()
+    } else {
+        base.with(PathSegment::Index(index))
+    }
+}
+
+/// Fields of a struct, or of the named variant of an enum.
+fn fields_of(shape: &'static Shape, variant: Option<&str>) -> Option<&'static [facet_core::Field]> {
+    match (&shape.ty, variant) {
+        (Type::User(UserType::Struct(st)), None) => Some(st.fields),
+        (Type::User(UserType::Enum(en)), Some(name)) => en
+            .variants
+            .iter()
+            .find(|v| v.name == name)
+            .map(|v| v.data.fields),
+        _ => None,
+    }
+}
+
+fn tuple_len(shape: &'static Shape, variant: Option<&str>) -> Option<usize> {
+    let kind = match (&shape.ty, variant) {
+        (Type::User(UserType::Struct(st)), None) => st.kind,
+        (Type::User(UserType::Enum(en)), Some(name)) => {
+            en.variants.iter().find(|v| v.name == name)?.data.kind
+        }
+        _ => return None,
+    };
+    matches!(kind, StructKind::TupleStruct | StructKind::Tuple)
+        .then(|| fields_of(shape, variant).map_or(0, |f| f.len()))
+}
+
+/// Declaration order of a field, so hunks follow the type rather than hash order.
+fn field_position(
+    from: &'static Shape,
+    to: &'static Shape,
+    variant: Option<&str>,
+    name: &str,
+) -> usize {
+    [from, to]
+        .into_iter()
+        .filter_map(|shape| fields_of(shape, variant))
+        .find_map(|fields| fields.iter().position(|f| f.name == name))
+        .unwrap_or(usize::MAX)
+}
+
+fn key_segment(
+    from: &'static Shape,
+    to: &'static Shape,
+    variant: Option<&str>,
+    key: &str,
+) -> PathSegment {
+    if let Some(field) = [from, to]
+        .into_iter()
+        .filter_map(|shape| fields_of(shape, variant))
+        .find_map(|fields| fields.iter().find(|f| f.name == key))
+    {
+        return PathSegment::Field(Cow::Borrowed(field.effective_name()));
+    }
+    if matches!(from.def, Def::Map(_)) {
+        // Map keys are diffed by their Debug rendering; string keys come back quoted
+        if let Ok(unquoted) = facet_json::from_str::<String>(key) {
+            return PathSegment::Key(Cow::Owned(unquoted));
+        }
+    }
+    PathSegment::Key(Cow::Owned(key.to_string()))
+}
+
+/// Renders a path the way patches spell it: `.server.tags[0]["a key"]`.
+struct PatchPath<'a>(&'a Path);
+
+impl fmt::Display for PatchPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let segments = &self.0.0;
+        if !matches!(
+            segments.first(),
+            Some(PathSegment::Field(name) | PathSegment::Variant(name)) if is_ident(name)
+        ) {
+            f.write_str(".")?;
+        }
+        for segment in segments {
+            match segment {
+                PathSegment::Field(name) | PathSegment::Variant(name) if is_ident(name) => {
+                    write!(f, ".{name}")?
+                }
+                PathSegment::Index(i) => write!(f, "[{i}]")?,
+                PathSegment::Field(key) | PathSegment::Key(key) | PathSegment::Variant(key) => {
+                    let quoted = facet_json::to_string(&key.to_string())
+                        .unwrap_or_else(|_| format!("{key:?}"));
+                    write!(f, "[{quoted}]")?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+pub(crate) fn parse_path(text: &str) -> Result<Path, String> {
+    let rest = text
+        .strip_prefix('.')
+        .ok_or_else(|| format!("path `{text}` must start with `.`"))?;
+    let mut path = Path::new();
+    let mut rest = rest;
+    let mut first = true;
+    while !rest.is_empty() {
+        if let Some(inner) = rest.strip_prefix('[') {
+            let end = closing_bracket(inner).ok_or_else(|| format!("unclosed `[` in `{text}`"))?;
+            let segment = &inner[..end];
+            if segment.starts_with('"') {
+                let key: String = facet_json::from_str(segment)
+                    .map_err(|e| format!("invalid key {segment}: {e}"))?;
+                path.push(PathSegment::Key(Cow::Owned(key)));
+            } else {
+                let index = segment
+                    .parse()
+                    .map_err(|_| format!("invalid index `{segment}` in `{text}`"))?;
+                path.push(PathSegment::Index(index));
+            }
+            rest = &inner[end + 1..];
+        } else {
+            let field = if first {
+                rest
+            } else {
+                rest.strip_prefix('.')
+                    .ok_or_else(|| format!("expected `.` or `[` in `{text}`"))?
+            };
+            let end = field.find(['.', '[']).unwrap_or(field.len());
+            let name = &field[..end];
+            if !is_ident(name) {
+                return Err(format!("invalid field name `{name}` in `{text}`"));
+            }
+            path.push(PathSegment::Field(Cow::Owned(name.to_string())));
+            rest = &field[end..];
+        }
+        first = false;
+    }
+    Ok(path)
+}
+
+/// Position of the `]` closing a bracket segment, skipping over a quoted key.
+fn closing_bracket(inner: &str) -> Option<usize> {
+    if !inner.starts_with('"') {
+        return inner.find(']');
+    }
+    let mut escaped = false;
+    for (i, c) in inner.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return inner[i + 1..].starts_with(']').then_some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,291 @@
+//! An admin console for application state.
+//!
+//! A [`Repl`] holds named, mutable references to the values an application
+//! wants to expose, and evaluates one-line commands against them:
+//!
+//! ```text
+//! show config.server          pretty-print a value
+//! set config.server.port 8081 replace a value, written as JSON
+//! diff                        what changed since the values were registered
+//! list                        the registered values and their types
+//! ```
+//!
+//! Paths start with the registered name, followed by the syntax of
+//! [`Peek::at`]: `config.servers[0].tags["env"]`. The value given to `set` is
+//! JSON, except that anything that isn't valid JSON is taken as a string, so
+//! `set config.name api` works without quotes.
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_diff::Repl;
+//!
+//! #[derive(Facet)]
+//! struct Server { host: String, port: u16 }
+//!
+//! #[derive(Facet)]
+//! struct Config { server: Server }
+//!
+//! let mut config = Config { server: Server { host: "localhost".into(), port: 8080 } };
+//! {
+//!     let mut repl = Repl::new().with_colors(false);
+//!     repl.register("config", &mut config).unwrap();
+//!     repl.eval("set config.server.port 8081").unwrap();
+//!     assert_eq!(repl.eval("show config.server.port").unwrap(), "8081");
+//! }
+//! assert_eq!(config.server.port, 8081);
+//! ```
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use facet::Facet;
+use facet_pretty::PrettyPrinter;
+use facet_reflect::{Peek, PeekPathError, ReflectError};
+use facet_value::Value;
+
+use crate::patch::parse_path;
+use crate::{DiffFormat, Hunk, Patch, PatchError, diff_new_peek, format_diff};
+
+const HELP: &str = "\
+show <path>          pretty-print the value at <path>
+set <path> <value>   replace the value at <path> (JSON, or a bare string)
+diff [name]          show what changed since registration
+list                 list registered values
+help                 show this help
+exit                 leave the console";
+
+/// Errors from evaluating a [`Repl`] command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplError {
+    /// The command isn't one the console knows.
+    UnknownCommand(String),
+    /// The command is missing arguments.
+    Usage(&'static str),
+    /// No value is registered under that name.
+    UnknownName(String),
+    /// The path doesn't lead to a value.
+    Path(PeekPathError),
+    /// The new value could not be applied.
+    Patch(PatchError),
+}
+
+impl fmt::Display for ReplError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplError::UnknownCommand(command) => {
+                write!(f, "unknown command `{command}`, try `help`")
+            }
+            ReplError::Usage(usage) => write!(f, "usage: {usage}"),
+            ReplError::UnknownName(name) => write!(f, "nothing is registered as `{name}`"),
+            ReplError::Path(err) => write!(f, "{err}"),
+            ReplError::Patch(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for ReplError {}
+
+impl From<PeekPathError> for ReplError {
+    fn from(err: PeekPathError) -> Self {
+        ReplError::Path(err)
+    }
+}
+
+impl From<PatchError> for ReplError {
+    fn from(err: PatchError) -> Self {
+        ReplError::Patch(err)
+    }
+}
+
+/// A registered value, with the type erased.
+trait Entry {
+    /// The current value
+    fn peek(&self) -> Peek<'_, 'static>;
+    /// The value as it was when registered
+    fn baseline(&self) -> Peek<'_, 'static>;
+    /// Apply `patch` to the current value
+    fn apply(&mut self, patch: &Patch) -> Result<(), PatchError>;
+}
+
+struct Registered<'a, T> {
+    value: &'a mut T,
+    baseline: T,
+}
+
+impl<T: Facet<'static>> Entry for Registered<'_, T> {
+    fn peek(&self) -> Peek<'_, 'static> {
+        Peek::new(&*self.value)
+    }
+
+    fn baseline(&self) -> Peek<'_, 'static> {
+        Peek::new(&self.baseline)
+    }
+
+    fn apply(&mut self, patch: &Patch) -> Result<(), PatchError> {
+        *self.value = patch.apply(&*self.value)?;
+        Ok(())
+    }
+}
+
+/// An interactive console over registered application state.
+///
+/// See the [module documentation](self) for the commands.
+pub struct Repl<'a> {
+    entries: Vec<(String, Box<dyn Entry + 'a>)>,
+    colors: bool,
+}
+
+impl Default for Repl<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Repl<'a> {
+    /// Creates a console with nothing registered.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            colors: true,
+        }
+    }
+
+    /// Enables or disables colored output.
+    pub fn with_colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Exposes `value` under `name`.
+    ///
+    /// A copy of the value is kept for `diff`, so it must be possible to
+    /// clone it through reflection.
+    pub fn register<T: Facet<'static>>(
+        &mut self,
+        name: impl Into<String>,
+        value: &'a mut T,
+    ) -> Result<&mut Self, ReflectError> {
+        let baseline = Peek::new(&*value).deep_clone()?.materialize::<T>()?;
+        let name = name.into();
+        self.entries.retain(|(existing, _)| *existing != name);
+        self.entries
+            .push((name, Box::new(Registered { value, baseline })));
+        Ok(self)
+    }
+
+    /// Evaluates one command, returning what it prints.
+    pub fn eval(&mut self, line: &str) -> Result<String, ReplError> {
+        let line = line.trim();
+        let (command, args) = line.split_once(' ').unwrap_or((line, ""));
+        let args = args.trim();
+        match command {
+            "" => Ok(String::new()),
+            "help" => Ok(HELP.to_string()),
+            "list" => Ok(self
+                .entries
+                .iter()
+                .map(|(name, entry)| format!("{name}: {}", entry.peek().shape()))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            "show" if !args.is_empty() => self.show(args),
+            "show" => Err(ReplError::Usage("show <path>")),
+            "set" => match args.split_once(' ') {
+                Some((path, value)) => self.set(path, value.trim()),
+                None => Err(ReplError::Usage("set <path> <value>")),
+            },
+            "diff" => self.diff(args),
+            _ => Err(ReplError::UnknownCommand(command.to_string())),
+        }
+    }
+
+    /// Reads commands from `input` until it ends or `exit` is entered,
+    /// writing a prompt and each command's output to `output`.
+    pub fn run(&mut self, mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        let mut line = String::new();
+        loop {
+            write!(output, "> ")?;
+            output.flush()?;
+            line.clear();
+            if input.read_line(&mut line)? == 0 || matches!(line.trim(), "exit" | "quit") {
+                return Ok(());
+            }
+            match self.eval(&line) {
+                Ok(text) if text.is_empty() => {}
+                Ok(text) => writeln!(output, "{text}")?,
+                Err(err) => writeln!(output, "error: {err}")?,
+            }
+        }
+    }
+
+    fn show(&self, path: &str) -> Result<String, ReplError> {
+        let (index, rest) = self.find(path)?;
+        let value = self.entries[index].1.peek().at(rest)?;
+        Ok(PrettyPrinter::new()
+            .with_colors(self.colors)
+            .format_peek(value))
+    }
+
+    fn set(&mut self, path: &str, value: &str) -> Result<String, ReplError> {
+        let (index, rest) = self.find(path)?;
+        let entry = &mut self.entries[index].1;
+
+        let old = to_json(entry.peek().at(rest)?)?;
+        let new = match facet_json::from_str::<Value>(value) {
+            Ok(value) => value,
+            // Not JSON, so take it as a bare string
+            Err(_) => Value::from(value),
+        };
+        let path = parse_path(&format!(".{rest}"))
+            .map_err(|message| PatchError::Parse { line: 1, message })?;
+
+        entry.apply(&Patch {
+            hunks: vec![Hunk {
+                path,
+                old: Some(old),
+                new: Some(to_json(Peek::new(&new))?),
+            }],
+        })?;
+        Ok(String::new())
+    }
+
+    fn diff(&self, name: &str) -> Result<String, ReplError> {
+        if !name.is_empty() && !self.entries.iter().any(|(n, _)| n == name) {
+            return Err(ReplError::UnknownName(name.to_string()));
+        }
+        let format = DiffFormat {
+            colors: self.colors,
+            ..DiffFormat::default()
+        };
+        let mut out = Vec::new();
+        for (entry_name, entry) in &self.entries {
+            if !name.is_empty() && entry_name != name {
+                continue;
+            }
+            let diff = diff_new_peek(entry.baseline(), entry.peek());
+            if !diff.is_equal() {
+                out.push(format!("{entry_name}:\n{}", format_diff(&diff, &format)));
+            }
+        }
+        if out.is_empty() {
+            return Ok("(no changes)".to_string());
+        }
+        Ok(out.join("\n"))
+    }
+
+    /// Splits `config.server.port` into the index of `config` and the rest
+    /// of the path, `server.port`.
+    fn find<'p>(&self, path: &'p str) -> Result<(usize, &'p str), ReplError> {
+        let end = path.find(['.', '[']).unwrap_or(path.len());
+        let (name, rest) = path.split_at(end);
+        let index = self
+            .entries
+            .iter()
+            .position(|(n, _)| n == name)
+            .ok_or_else(|| ReplError::UnknownName(name.to_string()))?;
+        Ok((index, rest.strip_prefix('.').unwrap_or(rest)))
+    }
+}
+
+fn to_json(peek: Peek<'_, '_>) -> Result<String, PatchError> {
+    facet_json::peek_to_string(peek).map_err(|e| PatchError::Value(e.to_string()))
+}
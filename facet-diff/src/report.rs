@@ -88,6 +88,11 @@ impl<'mem, 'facet> DiffReport<'mem, 'facet> {
         self.right
     }
 
+    /// Convert the diff into a re-applicable textual [`Patch`](crate::Patch).
+    pub fn to_patch(&self) -> Result<crate::Patch, crate::PatchError> {
+        crate::Patch::from_diff(&self.diff)
+    }
+
     /// Format the diff using the legacy tree display (same output as `Display` impl).
     pub fn legacy_string(&self) -> String {
         format!("{}", self.diff)
@@ -0,0 +1,207 @@
+//! Tests for the textual patch format
+
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_diff::{Patch, PatchError};
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+#[repr(u8)]
+enum Compression {
+    Off,
+    Gzip { level: u32 },
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Config {
+    name: String,
+    #[facet(rename = "srv")]
+    server: Server,
+    tags: Vec<String>,
+    labels: BTreeMap<String, String>,
+    timeout: Option<u32>,
+    compression: Compression,
+}
+
+fn roundtrip(old: &Config, new: &Config) -> String {
+    let text = Patch::between(old, new).unwrap().to_string();
+    let patch: Patch = text.parse().unwrap();
+    assert_eq!(&patch.apply(old).unwrap(), new, "patch:\n{text}");
+    text
+}
+
+#[test]
+fn test_nested_field_replace() {
+    let old = Config {
+        name: "api".into(),
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+        },
+        tags: vec!["alpha".into(), "beta".into(), "gamma".into()],
+        labels: [("team".to_string(), "core".to_string())].into(),
+        timeout: Some(30),
+        compression: Compression::Gzip { level: 6 },
+    };
+    let mut new = old.clone();
+    new.server.port = 9090;
+    new.compression = Compression::Gzip { level: 9 };
+
+    let text = roundtrip(&old, &new);
+    assert_eq!(
+        text,
+        "@@ .srv.port @@\n-8080\n+9090\n@@ .compression.Gzip.level @@\n-6\n+9\n"
+    );
+}
+
+#[test]
+fn test_list_insert_and_remove() {
+    let old = Config {
+        name: "api".into(),
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+        },
+        tags: vec!["alpha".into(), "beta".into(), "gamma".into()],
+        labels: [("team".to_string(), "core".to_string())].into(),
+        timeout: Some(30),
+        compression: Compression::Gzip { level: 6 },
+    };
+    let mut new = old.clone();
+    new.tags = vec!["alpha".into(), "rc".into(), "gamma".into(), "delta".into()];
+    roundtrip(&old, &new);
+
+    new.tags.clear();
+    roundtrip(&old, &new);
+}
+
+#[test]
+fn test_map_option_and_variant_change() {
+    let old = Config {
+        name: "api".into(),
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+        },
+        tags: vec!["alpha".into(), "beta".into(), "gamma".into()],
+        labels: [("team".to_string(), "core".to_string())].into(),
+        timeout: Some(30),
+        compression: Compression::Gzip { level: 6 },
+    };
+    let mut new = old.clone();
+    new.labels.insert("tier".into(), "gold".into());
+    new.labels.remove("team");
+    new.timeout = None;
+    new.compression = Compression::Off;
+
+    let text = roundtrip(&old, &new);
+    assert!(
+        text.contains("@@ .labels[\"tier\"] @@\n+\"gold\"\n"),
+        "{text}"
+    );
+    assert!(text.contains("@@ .timeout @@\n-30\n+null\n"), "{text}");
+    assert!(
+        text.contains("@@ .compression @@\n-{\"Gzip\":{\"level\":6}}\n+\"Off\"\n"),
+        "{text}"
+    );
+
+    // And back from the unit variant to the struct variant
+    roundtrip(&new, &old);
+}
+
+#[test]
+fn test_equal_values_give_empty_patch() {
+    let config = Config {
+        name: "api".into(),
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+        },
+        tags: vec!["alpha".into(), "beta".into(), "gamma".into()],
+        labels: [("team".to_string(), "core".to_string())].into(),
+        timeout: Some(30),
+        compression: Compression::Gzip { level: 6 },
+    };
+    let patch = Patch::between(&config, &config).unwrap();
+    assert!(patch.is_empty());
+    assert_eq!(patch.to_string(), "");
+}
+
+#[test]
+fn test_conflict_detected() {
+    let old = Config {
+        name: "api".into(),
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+        },
+        tags: vec!["alpha".into(), "beta".into(), "gamma".into()],
+        labels: [("team".to_string(), "core".to_string())].into(),
+        timeout: Some(30),
+        compression: Compression::Gzip { level: 6 },
+    };
+    let mut new = old.clone();
+    new.server.port = 9090;
+    let patch = Patch::between(&old, &new).unwrap();
+
+    let mut drifted = old.clone();
+    drifted.server.port = 8081;
+    assert_eq!(
+        patch.apply(&drifted),
+        Err(PatchError::Conflict {
+            path: ".srv.port".into(),
+            expected: "8080".into(),
+            found: "8081".into(),
+        })
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    let err = "-1\n".parse::<Patch>().unwrap_err();
+    assert!(matches!(err, PatchError::Parse { line: 1, .. }), "{err}");
+
+    let err = "@@ .a @@\n-1\n@@ port @@\n".parse::<Patch>().unwrap_err();
+    assert!(matches!(err, PatchError::Parse { line: 3, .. }), "{err}");
+
+    let err = "@@ .a @@\n".parse::<Patch>().unwrap_err();
+    assert!(matches!(err, PatchError::Parse { .. }), "{err}");
+
+    let config = Config {
+        name: "api".into(),
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+        },
+        tags: vec!["alpha".into(), "beta".into(), "gamma".into()],
+        labels: [("team".to_string(), "core".to_string())].into(),
+        timeout: Some(30),
+        compression: Compression::Gzip { level: 6 },
+    };
+    let patch: Patch = "@@ .nope @@\n-1\n+2\n".parse().unwrap();
+    assert!(matches!(
+        patch.apply(&config),
+        Err(PatchError::PathNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_quoted_keys_roundtrip() {
+    let mut old = BTreeMap::new();
+    old.insert("a key".to_string(), 1u32);
+    old.insert("x\"]y".to_string(), 2u32);
+    let mut new = old.clone();
+    new.insert("a key".to_string(), 3);
+    new.insert("x\"]y".to_string(), 4);
+
+    let text = Patch::between(&old, &new).unwrap().to_string();
+    assert!(text.contains("@@ .[\"a key\"] @@"), "{text}");
+    let patch: Patch = text.parse().unwrap();
+    assert_eq!(patch.apply(&old).unwrap(), new);
+}
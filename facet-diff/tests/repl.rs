@@ -0,0 +1,135 @@
+//! Tests for the admin console
+#![cfg(feature = "repl")]
+
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_diff::{Repl, ReplError};
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Config {
+    name: String,
+    servers: Vec<Server>,
+    limits: BTreeMap<String, u32>,
+}
+
+#[test]
+fn set_and_show() {
+    let mut config = Config {
+        name: "api".to_string(),
+        servers: vec![Server {
+            host: "localhost".to_string(),
+            port: 8080,
+        }],
+        limits: [("rps".to_string(), 100)].into_iter().collect(),
+    };
+    let mut flags = vec![true, false];
+    {
+        let mut repl = Repl::new().with_colors(false);
+        repl.register("config", &mut config)
+            .unwrap()
+            .register("flags", &mut flags)
+            .unwrap();
+
+        assert_eq!(
+            repl.eval("list").unwrap(),
+            "config: Config\nflags: Vec<bool>"
+        );
+        repl.eval("set config.servers[0].port 8081").unwrap();
+        repl.eval("set config.name billing").unwrap();
+        repl.eval(r#"set config.limits["rps"] 250"#).unwrap();
+        repl.eval("set flags[1] true").unwrap();
+
+        assert_eq!(repl.eval("show config.servers[0].port").unwrap(), "8081");
+        assert_eq!(repl.eval("show flags").unwrap(), "Vec<bool> [true, true]");
+    }
+    assert_eq!(config.servers[0].port, 8081);
+    assert_eq!(config.name, "billing");
+    assert_eq!(config.limits["rps"], 250);
+    assert_eq!(flags, [true, true]);
+}
+
+#[test]
+fn diff_since_registration() {
+    let mut config = Config {
+        name: "api".to_string(),
+        servers: vec![Server {
+            host: "localhost".to_string(),
+            port: 8080,
+        }],
+        limits: [("rps".to_string(), 100)].into_iter().collect(),
+    };
+    let mut repl = Repl::new().with_colors(false);
+    repl.register("config", &mut config).unwrap();
+
+    assert_eq!(repl.eval("diff").unwrap(), "(no changes)");
+    repl.eval("set config.servers[0].host example.com").unwrap();
+    let diff = repl.eval("diff config").unwrap();
+    assert!(diff.starts_with("config:\n"), "{diff}");
+    assert!(diff.contains("example.com"), "{diff}");
+}
+
+#[test]
+fn errors_leave_values_untouched() {
+    let original = Config {
+        name: "api".to_string(),
+        servers: vec![Server {
+            host: "localhost".to_string(),
+            port: 8080,
+        }],
+        limits: [("rps".to_string(), 100)].into_iter().collect(),
+    };
+    let mut config = original.clone();
+    {
+        let mut repl = Repl::new().with_colors(false);
+        repl.register("config", &mut config).unwrap();
+
+        assert!(matches!(
+            repl.eval("set config.servers[0].port not-a-port"),
+            Err(ReplError::Patch(_))
+        ));
+        assert!(matches!(
+            repl.eval("show config.servers[3]"),
+            Err(ReplError::Path(_))
+        ));
+        assert_eq!(
+            repl.eval("show settings.port"),
+            Err(ReplError::UnknownName("settings".to_string()))
+        );
+        assert_eq!(
+            repl.eval("frobnicate"),
+            Err(ReplError::UnknownCommand("frobnicate".to_string()))
+        );
+        assert!(matches!(
+            repl.eval("set config.name"),
+            Err(ReplError::Usage(_))
+        ));
+    }
+    assert_eq!(config, original);
+}
+
+#[test]
+fn run_reads_commands_until_exit() {
+    let mut port = 8080u16;
+    let mut output = Vec::new();
+    {
+        let mut repl = Repl::new().with_colors(false);
+        repl.register("port", &mut port).unwrap();
+        repl.run(
+            &b"show port\nset port 9090\nbogus\nexit\nset port 1\n"[..],
+            &mut output,
+        )
+        .unwrap();
+    }
+    assert_eq!(port, 9090);
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "> 8080\n> > error: unknown command `bogus`, try `help`\n> "
+    );
+}
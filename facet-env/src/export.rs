@@ -0,0 +1,298 @@
+//! Dumping values as environment variables.
+
+use std::fmt::Write;
+
+use facet_core::{Def, Facet, StructKind, Type, UserType};
+use facet_pretty::RedactionMode;
+use facet_reflect::{HasFields, Peek};
+
+/// Render `value` as `.env` lines, without a prefix.
+pub fn to_env_string<'facet, T: Facet<'facet>>(value: &T) -> String {
+    EnvExport::new().render(value)
+}
+
+/// One environment variable produced by [`EnvExport`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvVar {
+    /// The variable name, e.g. `APP_SERVER__PORT`
+    pub name: String,
+    /// The value, or `None` if it comes from a sensitive field and was withheld
+    pub value: Option<String>,
+    /// The replacement shown in place of a withheld value, e.g. `[REDACTED]`
+    pub redacted: Option<String>,
+}
+
+/// Options for exporting a value as environment variables
+#[derive(Debug, Clone, Default)]
+pub struct EnvExport {
+    prefix: Option<String>,
+    redaction: RedactionMode,
+    reveal_sensitive: bool,
+}
+
+impl EnvExport {
+    /// Options with no prefix, redacting sensitive fields with a placeholder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prepend `prefix` and `_` to every variable name.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// How sensitive values are redacted. [`RedactionMode::HashPreview`] lets
+    /// two exports be compared without printing the secrets themselves.
+    pub fn with_redaction(mut self, mode: RedactionMode) -> Self {
+        self.redaction = mode;
+        self
+    }
+
+    /// Export sensitive values as-is instead of redacting them.
+    pub fn reveal_sensitive(mut self, reveal: bool) -> Self {
+        self.reveal_sensitive = reveal;
+        self
+    }
+
+    /// The variables for `value`, in field order.
+    pub fn vars<'facet, T: Facet<'facet>>(&self, value: &T) -> Vec<EnvVar> {
+        self.vars_peek(Peek::new(value))
+    }
+
+    /// The variables for a type-erased value, in field order.
+    pub fn vars_peek(&self, peek: Peek<'_, '_>) -> Vec<EnvVar> {
+        let mut exporter = Exporter {
+            options: self,
+            prefix: normalize_prefix(self.prefix.as_deref().unwrap_or_default()),
+            vars: Vec::new(),
+        };
+        exporter.walk(peek, "", false);
+        exporter.vars
+    }
+
+    /// Render `value` as `.env` lines. Redacted variables are commented out,
+    /// so the output can be sourced without setting them to the placeholder.
+    pub fn render<'facet, T: Facet<'facet>>(&self, value: &T) -> String {
+        self.render_peek(Peek::new(value))
+    }
+
+    /// Render a type-erased value as `.env` lines.
+    pub fn render_peek(&self, peek: Peek<'_, '_>) -> String {
+        let mut out = String::new();
+        for var in self.vars_peek(peek) {
+            match (&var.value, &var.redacted) {
+                (Some(value), _) => writeln!(out, "{}={}", var.name, quote(value)).unwrap(),
+                (None, Some(redacted)) => writeln!(out, "# {}={redacted}", var.name).unwrap(),
+                (None, None) => {}
+            }
+        }
+        out
+    }
+}
+
+struct Exporter<'a> {
+    options: &'a EnvExport,
+    prefix: String,
+    vars: Vec<EnvVar>,
+}
+
+impl Exporter<'_> {
+    fn push(&mut self, path: &str, peek: Peek<'_, '_>, sensitive: bool) {
+        let name = match (self.prefix.is_empty(), path.is_empty()) {
+            (true, true) => "VALUE".to_string(),
+            (true, false) => path.to_string(),
+            (false, true) => self.prefix.clone(),
+            (false, false) => format!("{}_{path}", self.prefix),
+        };
+        if sensitive && !self.options.reveal_sensitive {
+            self.vars.push(EnvVar {
+                name,
+                value: None,
+                redacted: Some(self.options.redaction.render(peek)),
+            });
+        } else if let Some(value) = leaf_value(peek) {
+            self.vars.push(EnvVar {
+                name,
+                value: Some(value),
+                redacted: None,
+            });
+        }
+    }
+
+    fn walk(&mut self, peek: Peek<'_, '_>, name: &str, sensitive: bool) {
+        let shape = peek.shape();
+        match shape.def {
+            Def::Scalar => return self.push(name, peek, sensitive),
+            Def::Option(_) => {
+                if let Ok(option) = peek.into_option()
+                    && let Some(inner) = option.value()
+                {
+                    self.walk(inner, name, sensitive);
+                }
+                return;
+            }
+            Def::Pointer(_) => {
+                if let Ok(pointer) = peek.into_pointer()
+                    && let Some(inner) = pointer.borrow_inner()
+                {
+                    self.walk(inner, name, sensitive);
+                }
+                return;
+            }
+            Def::List(_) | Def::Array(_) | Def::Slice(_) => {
+                if let Ok(list) = peek.into_list_like() {
+                    for (index, item) in list.iter().enumerate() {
+                        self.walk(item, &join(name, &index.to_string()), sensitive);
+                    }
+                }
+                return;
+            }
+            Def::Set(_) => {
+                if let Ok(set) = peek.into_set() {
+                    for (index, item) in set.iter().enumerate() {
+                        self.walk(item, &join(name, &index.to_string()), sensitive);
+                    }
+                }
+                return;
+            }
+            Def::Map(_) => {
+                if let Ok(map) = peek.into_map() {
+                    for (key, value) in map.iter() {
+                        if let Some(key) = leaf_value(key) {
+                            self.walk(value, &join(name, &key), sensitive);
+                        }
+                    }
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(_)) if shape.inner.is_some() => {
+                let inner = peek.innermost_peek();
+                if inner.shape() != shape {
+                    self.walk(inner, name, sensitive);
+                }
+            }
+            Type::User(UserType::Struct(st)) => {
+                if let Ok(struct_) = peek.into_struct() {
+                    self.fields(&struct_, name, st.kind, sensitive);
+                }
+            }
+            Type::User(UserType::Enum(_)) => {
+                let Ok(enum_) = peek.into_enum() else {
+                    return;
+                };
+                let Ok(variant) = enum_.active_variant() else {
+                    return;
+                };
+                if variant.data.fields.is_empty() {
+                    self.push(name, peek, sensitive);
+                } else {
+                    let name = join(name, variant.name);
+                    self.fields(&enum_, &name, variant.data.kind, sensitive);
+                }
+            }
+            _ => self.push(name, peek, sensitive),
+        }
+    }
+
+    fn fields<'mem, 'facet>(
+        &mut self,
+        value: &impl HasFields<'mem, 'facet>,
+        name: &str,
+        kind: StructKind,
+        sensitive: bool,
+    ) {
+        let fields: Vec<_> = value
+            .fields_for_serialize()
+            .filter_map(|(item, value)| item.field.map(|field| (field, value)))
+            .collect();
+        // A single unnamed field is a newtype: it stands for the whole value
+        let newtype =
+            fields.len() == 1 && matches!(kind, StructKind::TupleStruct | StructKind::Tuple);
+        for (field, value) in fields {
+            let sensitive = sensitive || field.is_sensitive();
+            if newtype {
+                self.walk(value, name, sensitive);
+            } else {
+                self.walk(value, &join(name, field.name), sensitive);
+            }
+        }
+    }
+}
+
+/// The text of a string, number, `bool` or unit variant
+fn leaf_value(peek: Peek<'_, '_>) -> Option<String> {
+    let peek = if peek.shape().inner.is_some() {
+        peek.innermost_peek()
+    } else {
+        peek
+    };
+    if let Some(s) = peek.as_str() {
+        return Some(s.to_string());
+    }
+    if let Type::User(UserType::Enum(_)) = peek.shape().ty {
+        let variant = peek.into_enum().ok()?.active_variant().ok()?;
+        return variant
+            .data
+            .fields
+            .is_empty()
+            .then(|| variant.name.to_string());
+    }
+    peek.shape().is_display().then(|| peek.to_string())
+}
+
+/// `prefix` as it starts variable names, without the `_` that joins it to them
+pub(crate) fn normalize_prefix(prefix: &str) -> String {
+    sanitize(prefix).trim_end_matches('_').to_string()
+}
+
+/// `parent` followed by the segment for `segment`, joined with `__`
+pub(crate) fn join(parent: &str, segment: &str) -> String {
+    let segment = sanitize(segment);
+    if parent.is_empty() {
+        segment
+    } else {
+        format!("{parent}__{segment}")
+    }
+}
+
+/// Upper-case `name`, replacing anything that isn't allowed in a variable name with `_`
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// `value` as it should appear on the right-hand side of a `.env` line
+fn quote(value: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "_-.,:/@+%".contains(c);
+    if value.chars().all(plain) {
+        return value.to_string();
+    }
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' | '$' | '`' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,76 @@
+//! Environment variables for facet types.
+//!
+//! Nested values map to flat environment variable names: the path of field
+//! names leading to a value is upper-cased and joined with `__`, after an
+//! optional prefix joined with `_`. A `server.port` field under the `APP`
+//! prefix is `APP_SERVER__PORT`.
+//!
+//! - Items of lists, arrays and sets use their index as a segment
+//!   (`APP_HOSTS__0`), maps use their key (`APP_LABELS__TEAM`).
+//! - A unit variant is the value itself (`APP_MODE=Off`); a variant with
+//!   fields adds its name as a segment (`APP_MODE__LIMITED__RATE=10`).
+//! - Newtypes are transparent, `None` and skipped fields are left out.
+//! - Fields marked `#[facet(sensitive)]` are redacted unless told otherwise.
+//!
+//! # Loading
+//!
+//! [`from_env`] reads a value back from the environment, parsing each
+//! variable with the type's `FromStr`-like parse function. Fields with a
+//! default, `Option`s and collections may be left unset; every missing or
+//! invalid variable is reported in a single [`FromEnvError`]. Map keys are
+//! lower-cased, since variable names are upper-cased.
+//!
+//! ```
+//! use facet::Facet;
+//!
+//! #[derive(Facet)]
+//! struct Config {
+//!     port: u16,
+//!     hosts: Vec<String>,
+//! }
+//!
+//! let vars = [("APP_PORT", "8080"), ("APP_HOSTS__0", "a"), ("APP_HOSTS__1", "b")];
+//! let config: Config = facet_env::from_vars("app", vars).unwrap();
+//! assert_eq!(config.hosts, ["a", "b"]);
+//! ```
+//!
+//! # Exporting
+//!
+//! [`EnvExport`] dumps a value as `.env` lines, so the environment a service is
+//! deployed with can be compared against its structured config:
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_env::EnvExport;
+//!
+//! #[derive(Facet)]
+//! struct Config {
+//!     server: Server,
+//!     #[facet(sensitive)]
+//!     token: String,
+//! }
+//!
+//! #[derive(Facet)]
+//! struct Server {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! let config = Config {
+//!     server: Server { host: "localhost".into(), port: 8080 },
+//!     token: "hunter2".into(),
+//! };
+//! let text = EnvExport::new().with_prefix("app").render(&config);
+//! assert_eq!(
+//!     text,
+//!     "APP_SERVER__HOST=localhost\nAPP_SERVER__PORT=8080\n# APP_TOKEN=[REDACTED]\n"
+//! );
+//! ```
+
+#![warn(missing_docs)]
+
+mod export;
+mod load;
+
+pub use export::*;
+pub use load::*;
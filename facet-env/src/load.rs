@@ -0,0 +1,392 @@
+//! Loading values from environment variables.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use facet_core::{Def, Facet, Field, Shape, StructKind, Type, UserType};
+use facet_reflect::{Partial, ReflectError};
+
+use crate::export::{join, normalize_prefix};
+
+/// Load a `T` from the process environment, with variables named after `prefix`.
+///
+/// See the [crate docs](crate) for how fields map to variable names. All
+/// missing and invalid variables are reported at once.
+pub fn from_env<T: Facet<'static>>(prefix: &str) -> Result<T, FromEnvError> {
+    from_vars(prefix, std::env::vars())
+}
+
+/// Load a `T` from the given variables, e.g. the lines of a `.env` file.
+///
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet)]
+/// struct Config {
+///     server: Server,
+///     #[facet(default)]
+///     debug: bool,
+/// }
+///
+/// #[derive(Facet)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let vars = [("APP_SERVER__HOST", "localhost"), ("APP_SERVER__PORT", "8080")];
+/// let config: Config = facet_env::from_vars("app", vars).unwrap();
+/// assert_eq!(config.server.port, 8080);
+///
+/// let Err(err) = facet_env::from_vars::<Config, _, _>("app", [("APP_SERVER__PORT", "http")])
+/// else {
+///     panic!("the host is missing");
+/// };
+/// assert_eq!(err.errors.len(), 2);
+/// assert_eq!(
+///     err.to_string(),
+///     "2 environment variables are missing or invalid:\n  \
+///      APP_SERVER__HOST: missing (expected String)\n  \
+///      APP_SERVER__PORT: `http` is not a valid u16"
+/// );
+/// ```
+pub fn from_vars<T, K, V>(
+    prefix: &str,
+    vars: impl IntoIterator<Item = (K, V)>,
+) -> Result<T, FromEnvError>
+where
+    T: Facet<'static>,
+    K: Into<String>,
+    V: Into<String>,
+{
+    let mut loader = Loader {
+        prefix: normalize_prefix(prefix),
+        vars: vars
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.into()))
+            .collect(),
+        errors: Vec::new(),
+    };
+    let root = loader.name("");
+    let reflect_error = |err| {
+        FromEnvError::new(vec![EnvVarError {
+            name: root.clone(),
+            shape: T::SHAPE,
+            kind: EnvVarErrorKind::Reflect(err),
+        }])
+    };
+    let wip = Partial::alloc_owned::<T>().map_err(reflect_error)?;
+    match loader.walk(Some(wip), T::SHAPE, "") {
+        Some(wip) if loader.errors.is_empty() => wip
+            .build()
+            .and_then(|value| value.materialize())
+            .map_err(reflect_error),
+        _ => Err(FromEnvError::new(loader.errors)),
+    }
+}
+
+/// Every variable that kept [`from_env`] from loading a value
+#[derive(Debug, Clone)]
+pub struct FromEnvError {
+    /// The problems, in field order
+    pub errors: Vec<EnvVarError>,
+}
+
+impl FromEnvError {
+    fn new(errors: Vec<EnvVarError>) -> Self {
+        Self { errors }
+    }
+}
+
+impl fmt::Display for FromEnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.errors.as_slice() {
+            [error] => write!(f, "{error}"),
+            errors => {
+                write!(
+                    f,
+                    "{} environment variables are missing or invalid:",
+                    errors.len()
+                )?;
+                for error in errors {
+                    write!(f, "\n  {error}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for FromEnvError {}
+
+/// A variable that couldn't be loaded
+#[derive(Debug, Clone)]
+pub struct EnvVarError {
+    /// The variable name, e.g. `APP_SERVER__PORT`
+    pub name: String,
+    /// The type the variable was loaded as
+    pub shape: &'static Shape,
+    /// What went wrong
+    pub kind: EnvVarErrorKind,
+}
+
+/// The ways loading a variable can fail
+#[derive(Debug, Clone)]
+pub enum EnvVarErrorKind {
+    /// The variable is not set, and the field has no default
+    Missing,
+    /// The variable is set, but its value can't be parsed
+    Invalid {
+        /// The value of the variable
+        value: String,
+    },
+    /// The type can't be loaded from environment variables
+    Unsupported,
+    /// Building the value failed
+    Reflect(ReflectError),
+}
+
+impl fmt::Display for EnvVarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { name, shape, kind } = self;
+        match kind {
+            EnvVarErrorKind::Missing => write!(f, "{name}: missing (expected {shape})"),
+            EnvVarErrorKind::Invalid { value } => {
+                write!(f, "{name}: `{value}` is not a valid {shape}")
+            }
+            EnvVarErrorKind::Unsupported => {
+                write!(
+                    f,
+                    "{name}: {shape} can't be loaded from environment variables"
+                )
+            }
+            EnvVarErrorKind::Reflect(err) => write!(f, "{name}: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for EnvVarError {}
+
+/// The value being built; `None` once an error was found, while the rest of
+/// the shape is still walked to find the other errors.
+type Wip = Option<Partial<'static, false>>;
+
+struct Loader {
+    prefix: String,
+    vars: BTreeMap<String, String>,
+    errors: Vec<EnvVarError>,
+}
+
+impl Loader {
+    /// The variable name for `path`, as [`EnvExport`](crate::EnvExport) names it
+    fn name(&self, path: &str) -> String {
+        match (self.prefix.is_empty(), path.is_empty()) {
+            (true, true) => "VALUE".to_string(),
+            (true, false) => path.to_string(),
+            (false, true) => self.prefix.clone(),
+            (false, false) => format!("{}_{path}", self.prefix),
+        }
+    }
+
+    /// Whether a variable is set for `path` or anything nested in it
+    fn present(&self, path: &str) -> bool {
+        if path.is_empty() {
+            return true;
+        }
+        let name = self.name(path);
+        self.vars.contains_key(&name) || !self.children(&name).is_empty()
+    }
+
+    /// The distinct segments right below `name`, in variable order
+    fn children(&self, name: &str) -> Vec<String> {
+        let nested = format!("{name}__");
+        let mut segments: Vec<String> = Vec::new();
+        for var in self.vars.range(nested.clone()..).map(|(var, _)| var) {
+            let Some(rest) = var.strip_prefix(&nested) else {
+                break;
+            };
+            let segment = rest.split("__").next().unwrap_or(rest);
+            if !segment.is_empty() && segments.last().is_none_or(|last| last != segment) {
+                segments.push(segment.to_string());
+            }
+        }
+        segments
+    }
+
+    fn error(&mut self, name: String, shape: &'static Shape, kind: EnvVarErrorKind) -> Wip {
+        self.errors.push(EnvVarError { name, shape, kind });
+        None
+    }
+
+    /// Run `op` on the value being built, if there still is one, recording
+    /// its error if it fails
+    fn step(
+        &mut self,
+        wip: Wip,
+        path: &str,
+        shape: &'static Shape,
+        op: impl FnOnce(Partial<'static, false>) -> Result<Partial<'static, false>, ReflectError>,
+    ) -> Wip {
+        match op(wip?) {
+            Ok(wip) => Some(wip),
+            Err(err) => self.error(self.name(path), shape, EnvVarErrorKind::Reflect(err)),
+        }
+    }
+
+    fn walk(&mut self, wip: Wip, shape: &'static Shape, path: &str) -> Wip {
+        match shape.def {
+            Def::Option(def) => {
+                if !self.present(path) {
+                    return self.step(wip, path, shape, |wip| wip.set_default());
+                }
+                let wip = self.step(wip, path, shape, |wip| wip.begin_some());
+                let wip = self.walk(wip, def.t(), path);
+                return self.step(wip, path, shape, |wip| wip.end());
+            }
+            Def::Pointer(def) if def.pointee().is_some() => {
+                let wip = self.step(wip, path, shape, |wip| wip.begin_smart_ptr());
+                let wip = self.walk(wip, def.pointee().unwrap(), path);
+                return self.step(wip, path, shape, |wip| wip.end());
+            }
+            Def::List(def) => {
+                let mut wip = self.step(wip, path, shape, |wip| wip.begin_list());
+                for index in self.indices(path) {
+                    wip = self.step(wip, path, shape, |wip| wip.begin_list_item());
+                    wip = self.walk(wip, def.t(), &join(path, &index.to_string()));
+                    wip = self.step(wip, path, shape, |wip| wip.end());
+                }
+                return wip;
+            }
+            Def::Set(def) => {
+                let mut wip = self.step(wip, path, shape, |wip| wip.begin_set());
+                for index in self.indices(path) {
+                    wip = self.step(wip, path, shape, |wip| wip.begin_set_item());
+                    wip = self.walk(wip, def.t(), &join(path, &index.to_string()));
+                    wip = self.step(wip, path, shape, |wip| wip.end());
+                }
+                return wip;
+            }
+            Def::Map(def) => {
+                let mut wip = self.step(wip, path, shape, |wip| wip.begin_map());
+                for key in self.children(&self.name(path)) {
+                    let key_path = join(path, &key);
+                    // Names are upper-cased on export; keys are usually lower-case
+                    let text = key.to_lowercase();
+                    wip = self.step(wip, path, shape, |wip| wip.begin_key());
+                    wip = self.parse(wip, def.k(), &key_path, &text);
+                    wip = self.step(wip, path, shape, |wip| wip.end());
+                    wip = self.step(wip, path, shape, |wip| wip.begin_value());
+                    wip = self.walk(wip, def.v(), &key_path);
+                    wip = self.step(wip, path, shape, |wip| wip.end());
+                }
+                return wip;
+            }
+            Def::Scalar => return self.leaf(wip, shape, path),
+            _ => {}
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(st)) => self.fields(wip, st.fields, st.kind, path),
+            Type::User(UserType::Enum(en)) => {
+                let name = self.name(path);
+                if let Some(text) = self.vars.get(&name).cloned() {
+                    // A unit variant, named by the value
+                    let variant = en.variants.iter().find(|variant| {
+                        variant.data.fields.is_empty()
+                            && variant.name.eq_ignore_ascii_case(text.trim())
+                    });
+                    return match variant {
+                        Some(variant) => self.step(wip, path, shape, |wip| {
+                            wip.select_variant_named(variant.name)
+                        }),
+                        None => self.error(name, shape, EnvVarErrorKind::Invalid { value: text }),
+                    };
+                }
+                // A variant with fields, named by the next segment
+                let variant = en.variants.iter().find(|variant| {
+                    !variant.data.fields.is_empty() && self.present(&join(path, variant.name))
+                });
+                match variant {
+                    Some(variant) => {
+                        let wip = self.step(wip, path, shape, |wip| {
+                            wip.select_variant_named(variant.name)
+                        });
+                        let path = join(path, variant.name);
+                        self.fields(wip, variant.data.fields, variant.data.kind, &path)
+                    }
+                    None => self.error(name, shape, EnvVarErrorKind::Missing),
+                }
+            }
+            _ if shape.is_from_str() => self.leaf(wip, shape, path),
+            _ => self.error(self.name(path), shape, EnvVarErrorKind::Unsupported),
+        }
+    }
+
+    fn fields(
+        &mut self,
+        mut wip: Wip,
+        fields: &'static [Field],
+        kind: StructKind,
+        path: &str,
+    ) -> Wip {
+        // A single unnamed field is a newtype: it stands for the whole value
+        let newtype =
+            fields.len() == 1 && matches!(kind, StructKind::TupleStruct | StructKind::Tuple);
+        for (index, field) in fields.iter().enumerate() {
+            let shape = field.shape();
+            let path = if newtype {
+                path.to_string()
+            } else {
+                join(path, field.name)
+            };
+            let defaulted = field.has_default() || matches!(shape.def, Def::Option(_));
+            if field.should_skip_deserializing() || (defaulted && !self.present(&path)) {
+                wip = self.step(wip, &path, shape, |wip| wip.set_nth_field_to_default(index));
+                continue;
+            }
+            wip = self.step(wip, &path, shape, |wip| wip.begin_nth_field(index));
+            wip = self.walk(wip, shape, &path);
+            wip = self.step(wip, &path, shape, |wip| wip.end());
+        }
+        wip
+    }
+
+    /// The list indices set below `path`, up to the first gap
+    fn indices(&self, path: &str) -> impl Iterator<Item = usize> + use<> {
+        let mut indices: Vec<usize> = self
+            .children(&self.name(path))
+            .iter()
+            .filter_map(|segment| segment.parse().ok())
+            .collect();
+        indices.sort_unstable();
+        let len = indices
+            .iter()
+            .enumerate()
+            .take_while(|&(position, &index)| position == index)
+            .count();
+        0..len
+    }
+
+    /// The value of the variable for `path`, parsed as `shape`
+    fn leaf(&mut self, wip: Wip, shape: &'static Shape, path: &str) -> Wip {
+        let name = self.name(path);
+        match self.vars.get(&name).cloned() {
+            Some(text) => self.parse(wip, shape, path, &text),
+            None => self.error(name, shape, EnvVarErrorKind::Missing),
+        }
+    }
+
+    fn parse(&mut self, wip: Wip, shape: &'static Shape, path: &str, text: &str) -> Wip {
+        // Check the value on a scratch partial: it is checked even once the
+        // value being built was given up, and a failed parse consumes the partial
+        let parsed = Partial::alloc_shape_owned(shape).and_then(|wip| wip.parse_from_str(text));
+        match parsed {
+            Ok(_) => self.step(wip, path, shape, |wip| wip.parse_from_str(text)),
+            Err(ReflectError::ParseFailed { .. }) => {
+                let value = text.to_string();
+                self.error(self.name(path), shape, EnvVarErrorKind::Invalid { value })
+            }
+            Err(_) => self.error(self.name(path), shape, EnvVarErrorKind::Unsupported),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_env::{EnvExport, EnvVar, to_env_string};
+use facet_pretty::RedactionMode;
+
+#[derive(Facet)]
+struct Config {
+    log_level: String,
+    server: Server,
+    #[facet(rename = "db")]
+    database: Arc<Database>,
+    hosts: Vec<String>,
+    labels: BTreeMap<String, String>,
+    timeout: Option<u32>,
+    retries: Option<u32>,
+    storage: Storage,
+    scratch: Storage,
+    port: Port,
+    #[facet(skip, default)]
+    cache: Vec<u8>,
+}
+
+#[derive(Facet)]
+struct Server {
+    host: String,
+    port: u16,
+    tls: bool,
+}
+
+#[derive(Facet)]
+struct Database {
+    url: String,
+    #[facet(sensitive)]
+    password: String,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+enum Storage {
+    Memory,
+    Disk { quota_mb: u32 },
+}
+
+#[derive(Facet)]
+struct Port(u16);
+
+#[test]
+fn test_render() {
+    let config = Config {
+        log_level: "debug info".into(),
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+            tls: true,
+        },
+        database: Arc::new(Database {
+            url: "postgres://db:5432/app".into(),
+            password: "hunter2".into(),
+        }),
+        hosts: vec!["a.example".into(), "b.example".into()],
+        labels: BTreeMap::from([("team".into(), "core".into())]),
+        timeout: Some(30),
+        retries: None,
+        storage: Storage::Disk { quota_mb: 512 },
+        scratch: Storage::Memory,
+        port: Port(9090),
+        cache: vec![1, 2, 3],
+    };
+    let text = EnvExport::new().with_prefix("app").render(&config);
+    assert_eq!(
+        text,
+        "\
+APP_LOG_LEVEL=\"debug info\"
+APP_SERVER__HOST=localhost
+APP_SERVER__PORT=8080
+APP_SERVER__TLS=true
+APP_DB__URL=postgres://db:5432/app
+# APP_DB__PASSWORD=[REDACTED]
+APP_HOSTS__0=a.example
+APP_HOSTS__1=b.example
+APP_LABELS__TEAM=core
+APP_TIMEOUT=30
+APP_STORAGE__DISK__QUOTA_MB=512
+APP_SCRATCH=Memory
+APP_PORT=9090
+"
+    );
+}
+
+#[test]
+fn test_vars_and_sensitive() {
+    let config = Config {
+        log_level: "debug info".into(),
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+            tls: true,
+        },
+        database: Arc::new(Database {
+            url: "postgres://db:5432/app".into(),
+            password: "hunter2".into(),
+        }),
+        hosts: vec![],
+        labels: BTreeMap::new(),
+        timeout: None,
+        retries: None,
+        storage: Storage::Disk { quota_mb: 512 },
+        scratch: Storage::Memory,
+        port: Port(9090),
+        cache: vec![],
+    };
+    let vars = EnvExport::new().vars(&config);
+    let value_of = |name: &str| {
+        vars.iter()
+            .find(|v| v.name == name)
+            .and_then(|v| v.value.clone())
+    };
+    let Storage::Disk { quota_mb } = &config.storage else {
+        unreachable!()
+    };
+    assert_eq!(
+        value_of("STORAGE__DISK__QUOTA_MB"),
+        Some(quota_mb.to_string())
+    );
+    assert_eq!(value_of("SCRATCH").as_deref(), Some("Memory"));
+
+    let password = vars.iter().find(|v| v.name == "DB__PASSWORD").unwrap();
+    assert_eq!(password.value, None);
+    assert_eq!(password.redacted.as_deref(), Some("[REDACTED]"));
+
+    let vars = EnvExport::new().reveal_sensitive(true).vars(&config);
+    assert!(vars.contains(&EnvVar {
+        name: "DB__PASSWORD".into(),
+        value: Some("hunter2".into()),
+        redacted: None,
+    }));
+
+    // Hash previews match for equal secrets, without revealing them
+    let hashed = |password: &str| {
+        let database = Database {
+            url: String::new(),
+            password: password.into(),
+        };
+        let text = EnvExport::new()
+            .with_redaction(RedactionMode::HashPreview)
+            .render(&database);
+        assert!(!text.contains(password));
+        text.lines()
+            .find(|line| line.starts_with("# PASSWORD="))
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(hashed("hunter2"), hashed("hunter2"));
+    assert_ne!(hashed("hunter2"), hashed("hunter3"));
+}
+
+#[test]
+fn test_scalars_and_quoting() {
+    assert_eq!(to_env_string(&42u32), "VALUE=42\n");
+    assert_eq!(
+        EnvExport::new().with_prefix("PORT_").render(&8080u16),
+        "PORT=8080\n"
+    );
+    assert_eq!(
+        to_env_string(&"say \"hi\"\n$HOME".to_string()),
+        "VALUE=\"say \\\"hi\\\"\\n\\$HOME\"\n"
+    );
+}
@@ -0,0 +1,167 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_env::{EnvExport, EnvVarErrorKind, from_vars};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Config {
+    log_level: String,
+    server: Server,
+    #[facet(rename = "db")]
+    database: Arc<Database>,
+    hosts: Vec<String>,
+    tags: BTreeSet<String>,
+    labels: BTreeMap<String, String>,
+    timeout: Option<u32>,
+    retries: Option<u32>,
+    mode: Mode,
+    fallback: Mode,
+    port: Port,
+    #[facet(default = 4)]
+    workers: u8,
+    #[facet(skip, default)]
+    cache: Vec<u8>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+    tls: bool,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Database {
+    url: String,
+    #[facet(sensitive)]
+    password: String,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+enum Mode {
+    Off,
+    Limited { rate: u32 },
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Port(u16);
+
+#[test]
+fn test_roundtrip_with_export() {
+    let config = Config {
+        log_level: "debug info".into(),
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+            tls: true,
+        },
+        database: Arc::new(Database {
+            url: "postgres://db:5432/app".into(),
+            password: "hunter2".into(),
+        }),
+        hosts: vec!["a.example".into(), "b.example".into()],
+        tags: BTreeSet::from(["blue".into()]),
+        labels: BTreeMap::from([("team".into(), "core".into())]),
+        timeout: Some(30),
+        retries: None,
+        mode: Mode::Limited { rate: 10 },
+        fallback: Mode::Off,
+        port: Port(9090),
+        workers: 8,
+        cache: Vec::new(),
+    };
+    let vars = EnvExport::new()
+        .with_prefix("app")
+        .reveal_sensitive(true)
+        .vars(&config);
+    let loaded: Config = from_vars(
+        "app",
+        vars.into_iter()
+            .map(|var| (var.name, var.value.unwrap_or_default())),
+    )
+    .unwrap();
+    assert_eq!(loaded, config);
+}
+
+#[test]
+fn test_defaults_and_absent_collections() {
+    let loaded: Config = from_vars(
+        "APP_",
+        [
+            ("APP_LOG_LEVEL", "info"),
+            ("APP_SERVER__HOST", "example.com"),
+            ("APP_SERVER__PORT", "443"),
+            ("APP_SERVER__TLS", "true"),
+            ("APP_DB__URL", "sqlite::memory:"),
+            ("APP_DB__PASSWORD", ""),
+            ("APP_MODE", "off"),
+            ("APP_FALLBACK", "Off"),
+            ("APP_PORT", "80"),
+            ("APP_HOSTS__0", "first"),
+            ("APP_HOSTS__2", "after a gap"),
+            ("OTHER_SERVER__PORT", "1"),
+        ],
+    )
+    .unwrap();
+    assert_eq!(loaded.hosts, ["first"]);
+    assert!(loaded.tags.is_empty() && loaded.labels.is_empty());
+    assert_eq!((loaded.timeout, loaded.retries), (None, None));
+    assert_eq!(loaded.mode, Mode::Off);
+    assert_eq!(loaded.workers, 4);
+}
+
+#[test]
+fn test_every_error_is_reported() {
+    let err = from_vars::<Config, _, _>(
+        "app",
+        [
+            ("APP_SERVER__PORT", "eighty"),
+            ("APP_SERVER__TLS", "yes please"),
+            ("APP_MODE", "Turbo"),
+            ("APP_TIMEOUT", "-1"),
+            ("APP_PORT", "80"),
+        ],
+    )
+    .unwrap_err();
+
+    let found: Vec<_> = err
+        .errors
+        .iter()
+        .map(|error| {
+            let kind = match &error.kind {
+                EnvVarErrorKind::Missing => "missing",
+                EnvVarErrorKind::Invalid { .. } => "invalid",
+                other => panic!("unexpected {other:?}"),
+            };
+            (error.name.as_str(), kind)
+        })
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("APP_LOG_LEVEL", "missing"),
+            ("APP_SERVER__HOST", "missing"),
+            ("APP_SERVER__PORT", "invalid"),
+            ("APP_SERVER__TLS", "invalid"),
+            ("APP_DB__URL", "missing"),
+            ("APP_DB__PASSWORD", "missing"),
+            ("APP_TIMEOUT", "invalid"),
+            ("APP_MODE", "invalid"),
+            ("APP_FALLBACK", "missing"),
+        ]
+    );
+    assert!(
+        err.to_string()
+            .starts_with("9 environment variables are missing or invalid:\n  APP_LOG_LEVEL: missing (expected String)")
+    );
+}
+
+#[test]
+fn test_scalar_root() {
+    assert_eq!(from_vars::<u16, _, _>("", [("VALUE", "7")]).unwrap(), 7);
+    assert_eq!(from_vars::<u16, _, _>("port", [("PORT", "8")]).unwrap(), 8);
+    let err = from_vars::<u16, _, _>("port", [("VALUE", "8")]).unwrap_err();
+    assert_eq!(err.to_string(), "PORT: missing (expected u16)");
+}
@@ -0,0 +1,8 @@
+#![no_main]
+
+use facet_format_fuzz::Document;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = facet_json::from_slice::<Document>(data);
+});
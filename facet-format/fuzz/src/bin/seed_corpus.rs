@@ -0,0 +1,22 @@
+//! Writes the seed documents to `corpus/<target>/`, where `cargo fuzz run`
+//! picks them up.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+fn main() -> std::io::Result<()> {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus");
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for seed in facet_format_fuzz::seeds() {
+        let count = counts.entry(seed.target).or_default();
+        let dir = corpus.join(seed.target);
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(format!("seed-{count}")), &seed.bytes)?;
+        *count += 1;
+    }
+    for (target, count) in counts {
+        println!("wrote {count} seeds to corpus/{target}");
+    }
+    Ok(())
+}
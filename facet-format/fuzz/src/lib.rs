@@ -0,0 +1,101 @@
+//! Fuzz targets for the format deserializers, and the seed corpora for them.
+//!
+//! Every target deserializes its input into [`Document`]. To give the fuzzer a
+//! head start, write valid documents for each enabled format to the corpus
+//! directories first:
+//!
+//! ```text
+//! cargo run --bin seed_corpus
+//! cargo fuzz run fuzz_json
+//! ```
+//!
+//! The documents come from [`facet_value::seed_values`], so they follow
+//! `Document` when it changes. There is no CBOR format crate yet; a target for
+//! it belongs here once there is.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use facet::Facet;
+
+/// The type every target deserializes into, chosen to reach most of the
+/// deserializer: nested and recursive structs, tuple structs, arrays, sets,
+/// maps, smart pointers, and enums with each kind of variant.
+#[derive(Debug, Facet)]
+pub struct Document {
+    pub name: String,
+    pub version: Version,
+    pub port: u16,
+    pub ratio: f32,
+    pub origin: [i8; 2],
+    pub enabled: bool,
+    pub initial: char,
+    pub fallback: Option<Box<Document>>,
+    pub hosts: Vec<Host>,
+    pub tags: HashSet<String>,
+    pub limits: BTreeMap<String, Option<u64>>,
+    pub shared: Arc<str>,
+    pub mode: Mode,
+    #[facet(default)]
+    pub extra: Vec<u8>,
+}
+
+#[derive(Debug, Facet)]
+pub struct Version(pub u8, pub u8, pub u8);
+
+#[derive(Debug, Facet)]
+pub struct Host {
+    pub address: String,
+    pub weight: i64,
+}
+
+#[derive(Debug, Facet)]
+#[repr(u8)]
+pub enum Mode {
+    Off,
+    Fixed(u32),
+    Pair(i16, String),
+    Custom { factor: f64 },
+}
+
+/// A document for one fuzz target.
+pub struct Seed {
+    /// The fuzz target, which is also the name of its corpus directory
+    pub target: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+/// Serializes the seed documents for [`Document`] with every enabled format.
+///
+/// # Panics
+///
+/// Panics if a seed doesn't deserialize back into a `Document`, since a
+/// corpus of rejected inputs would defeat its purpose.
+pub fn seeds() -> Vec<Seed> {
+    let mut seeds = Vec::new();
+    for value in facet_value::seed_values(Document::SHAPE) {
+        #[cfg(feature = "json")]
+        {
+            let bytes = facet_json::to_vec(&value).expect("seed serializes to JSON");
+            if let Err(err) = facet_json::from_slice::<Document>(&bytes) {
+                panic!("JSON seed {value:?} is rejected: {err}");
+            }
+            seeds.push(Seed {
+                target: "fuzz_json",
+                bytes,
+            });
+        }
+        #[cfg(feature = "msgpack")]
+        {
+            let bytes = facet_msgpack::to_vec(&value).expect("seed serializes to MessagePack");
+            if let Err(err) = facet_msgpack::from_slice::<Document>(&bytes) {
+                panic!("MessagePack seed {value:?} is rejected: {err}");
+            }
+            seeds.push(Seed {
+                target: "fuzz_msgpack",
+                bytes,
+            });
+        }
+    }
+    seeds
+}
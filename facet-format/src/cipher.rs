@@ -0,0 +1,20 @@
+use facet_core::Field;
+
+/// Encrypts and decrypts the values of fields marked `#[facet(encrypt)]`.
+///
+/// Set one on a serializer (e.g. through the format's serialize options) to
+/// write encrypted fields as ciphertext strings, and on a
+/// [`FormatDeserializer`](crate::FormatDeserializer) with
+/// [`with_cipher`](crate::FormatDeserializer::with_cipher) to read them back.
+/// The plaintext is the field's string value, or its `Display` output for
+/// other scalars, which is parsed back with the type's `FromStr`.
+///
+/// Encrypted fields are never written or read as plaintext: without a cipher,
+/// both serialization and deserialization of such fields fail.
+pub trait FieldCipher {
+    /// Encrypt the value of `field`.
+    fn encrypt(&self, field: &Field, plaintext: &str) -> Result<String, String>;
+
+    /// Decrypt a value of `field` produced by [`FieldCipher::encrypt`].
+    fn decrypt(&self, field: &Field, ciphertext: &str) -> Result<String, String>;
+}
@@ -0,0 +1,76 @@
+//! Codecs for fields marked `#[facet(compress = "...")]`.
+//!
+//! Binary formats write the value of such a field as the compressed bytes
+//! of its contents, and decompress it on the way back in; text formats leave
+//! it alone. The codec is named by the attribute, so both sides agree on it
+//! through the shape. Supported codecs:
+//!
+//! - `"zstd"`, with the `zstd` feature
+//!
+//! Compressed fields must be `String`s or `Vec<u8>`s, possibly inside
+//! `Option`s.
+
+use facet_reflect::Peek;
+
+/// The codecs enabled in this build.
+pub const CODECS: &[&str] = &[
+    #[cfg(feature = "zstd")]
+    "zstd",
+];
+
+/// Compresses `data` with `codec`.
+pub fn compress(codec: &str, data: &[u8]) -> Result<Vec<u8>, String> {
+    match codec {
+        #[cfg(feature = "zstd")]
+        "zstd" => zstd::encode_all(data, 0).map_err(|err| err.to_string()),
+        _ => {
+            let _ = data;
+            Err(unknown_codec(codec))
+        }
+    }
+}
+
+/// Decompresses `data` with `codec`, failing if the result is longer than
+/// `max_len` bytes.
+///
+/// Decompression stops as soon as the limit is crossed, so a small input
+/// can't expand into an arbitrarily large allocation.
+pub fn decompress(codec: &str, data: &[u8], max_len: usize) -> Result<Vec<u8>, String> {
+    match codec {
+        #[cfg(feature = "zstd")]
+        "zstd" => {
+            use std::io::Read as _;
+
+            let decoder = zstd::Decoder::new(data).map_err(|err| err.to_string())?;
+            let mut out = Vec::new();
+            decoder
+                .take((max_len as u64).saturating_add(1))
+                .read_to_end(&mut out)
+                .map_err(|err| err.to_string())?;
+            if out.len() > max_len {
+                return Err(format!("decompressed data exceeds {max_len} bytes"));
+            }
+            Ok(out)
+        }
+        _ => {
+            let _ = (data, max_len);
+            Err(unknown_codec(codec))
+        }
+    }
+}
+
+/// The contents of a value of a compressed field: the bytes of a `String`
+/// or a `Vec<u8>`.
+pub fn field_bytes<'mem>(value: Peek<'mem, '_>) -> Option<&'mem [u8]> {
+    if let Ok(s) = value.get::<String>() {
+        return Some(s.as_bytes());
+    }
+    value.get::<Vec<u8>>().ok().map(Vec::as_slice)
+}
+
+fn unknown_codec(codec: &str) -> String {
+    match codec {
+        "zstd" => "the `zstd` codec needs the `zstd` feature".into(),
+        _ => format!("unknown compression codec `{codec}`"),
+    }
+}
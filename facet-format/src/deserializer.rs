@@ -1,8 +1,10 @@
 extern crate alloc;
 
 use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
 use alloc::format;
 use alloc::string::String;
+use alloc::sync::Arc;
 use core::fmt;
 
 use facet_core::{
@@ -12,9 +14,90 @@ pub use facet_path::{Path, PathStep};
 use facet_reflect::{HeapValue, Partial, ReflectError, is_spanned_shape};
 
 use crate::{
-    ContainerKind, FieldLocationHint, FormatParser, ParseEvent, ScalarTypeHint, ScalarValue,
+    ContainerKind, DeserializeLimits, FieldCipher, FieldLocationHint, FormatParser, Interner,
+    ParseEvent, ScalarTypeHint, ScalarValue,
 };
 
+/// What to do when an object in the input repeats a key.
+///
+/// Applies to struct fields, map entries and dynamic values. Keys are compared
+/// as they appear in the input, before any renaming.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateKeyPolicy {
+    /// The last occurrence overwrites earlier ones.
+    #[default]
+    LastWins,
+    /// The first occurrence is kept; later ones are skipped.
+    FirstWins,
+    /// Fail with [`DeserializeError::DuplicateKey`], reporting both occurrences.
+    Error,
+}
+
+/// Which ways of writing numbers, other than as plain numbers, are accepted.
+///
+/// These match what serializers write for numbers a format can't hold
+/// faithfully, like integers beyond 2^53 or NaN in JSON. By default, quoted
+/// numbers and non-finite strings are accepted, and `null` reads as `0.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberLeniency {
+    /// Accept numbers written as strings, e.g. `"18446744073709551615"` (default: true)
+    pub quoted_numbers: bool,
+    /// Accept `"NaN"`, `"Infinity"` and `"-Infinity"` for floats (default: true)
+    pub non_finite_strings: bool,
+    /// Read `null` as NaN for floats rather than `0.0` (default: false)
+    pub null_as_nan: bool,
+}
+
+impl Default for NumberLeniency {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NumberLeniency {
+    /// Create the default leniency.
+    pub const fn new() -> Self {
+        Self {
+            quoted_numbers: true,
+            non_finite_strings: true,
+            null_as_nan: false,
+        }
+    }
+
+    /// Only accept plain numbers.
+    pub const fn strict() -> Self {
+        Self {
+            quoted_numbers: false,
+            non_finite_strings: false,
+            null_as_nan: false,
+        }
+    }
+
+    /// Set whether numbers written as strings are accepted.
+    pub const fn quoted_numbers(mut self, accept: bool) -> Self {
+        self.quoted_numbers = accept;
+        self
+    }
+
+    /// Set whether `"NaN"`, `"Infinity"` and `"-Infinity"` are accepted for floats.
+    pub const fn non_finite_strings(mut self, accept: bool) -> Self {
+        self.non_finite_strings = accept;
+        self
+    }
+
+    /// Set whether `null` reads as NaN for floats.
+    pub const fn null_as_nan(mut self, enable: bool) -> Self {
+        self.null_as_nan = enable;
+        self
+    }
+}
+
+/// Keys seen so far in one object, with the span of their first occurrence.
+type SeenKeys = BTreeMap<String, Option<facet_reflect::Span>>;
+
+/// The most elements preallocated for a collection on the parser's word.
+const MAX_CAPACITY_HINT: usize = 4096;
+
 /// Generic deserializer that drives a format-specific parser directly into `Partial`.
 ///
 /// The const generic `BORROW` controls whether string data can be borrowed:
@@ -26,6 +109,20 @@ pub struct FormatDeserializer<'input, const BORROW: bool, P> {
     last_span: Option<facet_reflect::Span>,
     /// Current path through the type structure (for error reporting).
     current_path: Path,
+    /// How repeated keys in an object are handled.
+    duplicate_keys: DuplicateKeyPolicy,
+    /// Limits enforced on the input.
+    limits: DeserializeLimits,
+    /// Which non-numeric representations of numbers are accepted.
+    numbers: NumberLeniency,
+    /// Number of objects and sequences currently open.
+    depth: usize,
+    /// Total bytes of strings, bytes and keys consumed so far.
+    string_bytes: usize,
+    /// Deduplicates strings of `#[facet(intern)]` fields.
+    interner: Option<Arc<dyn Interner>>,
+    /// Decrypts `#[facet(encrypt)]` fields.
+    cipher: Option<Arc<dyn FieldCipher>>,
     _marker: core::marker::PhantomData<&'input ()>,
 }
 
@@ -36,6 +133,13 @@ impl<'input, P> FormatDeserializer<'input, true, P> {
             parser,
             last_span: None,
             current_path: Path::new(),
+            duplicate_keys: DuplicateKeyPolicy::LastWins,
+            limits: DeserializeLimits::new(),
+            numbers: NumberLeniency::new(),
+            depth: 0,
+            string_bytes: 0,
+            interner: None,
+            cipher: None,
             _marker: core::marker::PhantomData,
         }
     }
@@ -48,12 +152,52 @@ impl<'input, P> FormatDeserializer<'input, false, P> {
             parser,
             last_span: None,
             current_path: Path::new(),
+            duplicate_keys: DuplicateKeyPolicy::LastWins,
+            limits: DeserializeLimits::new(),
+            numbers: NumberLeniency::new(),
+            depth: 0,
+            string_bytes: 0,
+            interner: None,
+            cipher: None,
             _marker: core::marker::PhantomData,
         }
     }
 }
 
 impl<'input, const BORROW: bool, P> FormatDeserializer<'input, BORROW, P> {
+    /// Set how repeated keys in an object are handled (default: [`DuplicateKeyPolicy::LastWins`]).
+    pub const fn with_duplicate_key_policy(mut self, policy: DuplicateKeyPolicy) -> Self {
+        self.duplicate_keys = policy;
+        self
+    }
+
+    /// Set the limits enforced on the input (default: unlimited).
+    pub const fn with_limits(mut self, limits: DeserializeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Set which ways of writing numbers, other than as plain numbers, are
+    /// accepted (default: [`NumberLeniency::new`]).
+    pub const fn with_number_leniency(mut self, leniency: NumberLeniency) -> Self {
+        self.numbers = leniency;
+        self
+    }
+
+    /// Set the interner used for `#[facet(intern)]` fields (default: none, so
+    /// those fields get a fresh allocation per value).
+    pub fn with_interner(mut self, interner: Arc<dyn Interner>) -> Self {
+        self.interner = Some(interner);
+        self
+    }
+
+    /// Set the cipher used to decrypt `#[facet(encrypt)]` fields (default:
+    /// none, so deserializing such a field fails).
+    pub fn with_cipher(mut self, cipher: Arc<dyn FieldCipher>) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
     /// Consume the facade and return the underlying parser.
     pub fn into_inner(self) -> P {
         self.parser
@@ -153,9 +297,100 @@ where
             .ok_or(DeserializeError::UnexpectedEof { expected })?;
         // Capture the span of the consumed event for error reporting
         self.last_span = self.parser.current_span();
+        self.account_event(&event)?;
         Ok(event)
     }
 
+    /// Track nesting depth and string bytes of a consumed event against the limits.
+    fn account_event(
+        &mut self,
+        event: &ParseEvent<'input>,
+    ) -> Result<(), DeserializeError<P::Error>> {
+        let string_len = match event {
+            ParseEvent::StructStart(_) | ParseEvent::SequenceStart(_) => {
+                self.depth += 1;
+                if let Some(max) = self.limits.max_depth
+                    && self.depth > max
+                {
+                    return Err(self.limit_exceeded("nesting depth", max));
+                }
+                return Ok(());
+            }
+            ParseEvent::StructEnd | ParseEvent::SequenceEnd => {
+                self.depth = self.depth.saturating_sub(1);
+                return Ok(());
+            }
+            ParseEvent::FieldKey(key) => key.name.len(),
+            ParseEvent::Scalar(ScalarValue::Str(s)) => s.len(),
+            ParseEvent::Scalar(ScalarValue::Bytes(b)) => b.len(),
+            ParseEvent::VariantTag(tag) => tag.len(),
+            ParseEvent::OrderedField | ParseEvent::Scalar(_) => return Ok(()),
+        };
+        self.string_bytes = self.string_bytes.saturating_add(string_len);
+        if let Some(max) = self.limits.max_string_bytes
+            && self.string_bytes > max
+        {
+            return Err(self.limit_exceeded("string bytes", max));
+        }
+        Ok(())
+    }
+
+    /// Skip the next value, e.g. the value of an unknown field.
+    ///
+    /// With a depth or string limit set, self-describing input is skipped event
+    /// by event, so that skipped values count against the limits as well.
+    fn skip_value(&mut self) -> Result<(), DeserializeError<P::Error>> {
+        let limited = self.limits.max_depth.is_some() || self.limits.max_string_bytes.is_some();
+        if !limited || !self.parser.is_self_describing() {
+            return self.parser.skip_value().map_err(DeserializeError::Parser);
+        }
+        let mut open = 0usize;
+        loop {
+            match self.expect_event("value")? {
+                ParseEvent::StructStart(_) | ParseEvent::SequenceStart(_) => open += 1,
+                ParseEvent::StructEnd | ParseEvent::SequenceEnd => open = open.saturating_sub(1),
+                _ => {}
+            }
+            if open == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Count one more element of a collection of at most `max` elements.
+    fn count_element(
+        &self,
+        len: &mut usize,
+        max: Option<usize>,
+        limit: &'static str,
+    ) -> Result<(), DeserializeError<P::Error>> {
+        *len += 1;
+        match max {
+            Some(max) if *len > max => Err(self.limit_exceeded(limit, max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// How many elements to allocate up front for the collection that was just
+    /// started, from the length the parser announced for it
+    ///
+    /// The announced length comes from the input, so it's capped by the
+    /// collection's limit and by [`MAX_CAPACITY_HINT`]: a bogus length prefix
+    /// must not trigger a huge allocation before any element is read.
+    fn capacity_hint(&self, max: Option<usize>) -> usize {
+        let len = self.parser.container_len().unwrap_or(0);
+        len.min(max.unwrap_or(usize::MAX)).min(MAX_CAPACITY_HINT)
+    }
+
+    fn limit_exceeded(&self, limit: &'static str, max: usize) -> DeserializeError<P::Error> {
+        DeserializeError::LimitExceeded {
+            limit,
+            max,
+            span: self.last_span,
+            path: Some(self.path_clone()),
+        }
+    }
+
     /// Peek at the next event, returning an error if EOF is reached.
     #[inline]
     fn expect_peek(
@@ -186,6 +421,36 @@ where
         self.current_path.clone()
     }
 
+    /// Apply the duplicate key policy to `key`, which was just consumed.
+    ///
+    /// Returns `false` if the value that follows was skipped because an
+    /// earlier occurrence of the key wins.
+    fn admit_key(
+        &mut self,
+        seen: &mut SeenKeys,
+        key: &str,
+    ) -> Result<bool, DeserializeError<P::Error>> {
+        if self.duplicate_keys == DuplicateKeyPolicy::LastWins {
+            return Ok(true);
+        }
+        let Some(&first_span) = seen.get(key) else {
+            seen.insert(key.into(), self.last_span);
+            return Ok(true);
+        };
+        match self.duplicate_keys {
+            DuplicateKeyPolicy::Error => Err(DeserializeError::DuplicateKey {
+                key: key.into(),
+                first_span,
+                span: self.last_span,
+                path: Some(self.path_clone()),
+            }),
+            _ => {
+                self.skip_value()?;
+                Ok(false)
+            }
+        }
+    }
+
     /// Main deserialization entry point - deserialize into a Partial.
     pub fn deserialize_into(
         &mut self,
@@ -309,6 +574,217 @@ where
         }
     }
 
+    /// Deserialize the value of a struct field whose key was just read.
+    ///
+    /// For `#[facet(double_option)]` fields, an explicit null becomes `Some(None)`,
+    /// leaving `None` to mean the field was absent.
+    fn deserialize_field_value(
+        &mut self,
+        mut wip: Partial<'input, BORROW>,
+        field: &facet_core::Field,
+    ) -> Result<Partial<'input, BORROW>, DeserializeError<P::Error>> {
+        if field.is_encrypted() {
+            return self.deserialize_encrypted(wip, field);
+        }
+
+        if let Some(codec) = field.compression()
+            && self.parser.compresses_fields()
+        {
+            return self.deserialize_compressed(wip, field, codec);
+        }
+
+        if field.has_builtin_attr("intern")
+            && let Some(interner) = self.interner.clone()
+        {
+            return self.deserialize_interned(wip, &*interner);
+        }
+
+        let is_double_option = field.has_builtin_attr("double_option")
+            && matches!(wip.shape().def, Def::Option(opt) if matches!(opt.t.def, Def::Option(_)));
+        if !is_double_option || !self.parser.is_self_describing() {
+            return self.deserialize_into(wip);
+        }
+
+        let event = self.expect_peek("value for option")?;
+        if !matches!(event, ParseEvent::Scalar(ScalarValue::Null)) {
+            return self.deserialize_into(wip);
+        }
+        let _ = self.expect_event("null")?;
+        wip = wip.begin_some().map_err(DeserializeError::reflect)?;
+        wip = wip.set_default().map_err(DeserializeError::reflect)?;
+        wip = wip.end().map_err(DeserializeError::reflect)?;
+        Ok(wip)
+    }
+
+    /// Deserialize the ciphertext of an `#[facet(encrypt)]` field (possibly
+    /// inside `Option`s) and set the field from the decrypted value.
+    fn deserialize_encrypted(
+        &mut self,
+        mut wip: Partial<'input, BORROW>,
+        field: &facet_core::Field,
+    ) -> Result<Partial<'input, BORROW>, DeserializeError<P::Error>> {
+        if let Def::Option(_) = wip.shape().def {
+            self.parser.hint_option();
+            let event = self.expect_peek("value for option")?;
+            if matches!(event, ParseEvent::Scalar(ScalarValue::Null)) {
+                let _ = self.expect_event("null")?;
+                return wip.set_default().map_err(DeserializeError::reflect);
+            }
+            wip = wip.begin_some().map_err(DeserializeError::reflect)?;
+            wip = self.deserialize_encrypted(wip, field)?;
+            return wip.end().map_err(DeserializeError::reflect);
+        }
+
+        self.parser.hint_scalar_type(ScalarTypeHint::String);
+        let event = self.expect_event("encrypted string")?;
+        let ParseEvent::Scalar(ScalarValue::Str(ciphertext)) = event else {
+            return Err(DeserializeError::TypeMismatch {
+                expected: "encrypted string",
+                got: format!("{event:?}"),
+                span: self.last_span,
+                path: None,
+            });
+        };
+        let Some(cipher) = self.cipher.clone() else {
+            return Err(DeserializeError::Decrypt {
+                field: field.name,
+                message: "no cipher is configured".into(),
+                span: self.last_span,
+                path: None,
+            });
+        };
+        let plaintext =
+            cipher
+                .decrypt(field, &ciphertext)
+                .map_err(|message| DeserializeError::Decrypt {
+                    field: field.name,
+                    message,
+                    span: self.last_span,
+                    path: None,
+                })?;
+        if wip.shape().is_type::<String>() {
+            wip.set(plaintext).map_err(DeserializeError::reflect)
+        } else {
+            wip.parse_from_str(&plaintext)
+                .map_err(DeserializeError::reflect)
+        }
+    }
+
+    /// Deserialize the value of a struct field read by position, as
+    /// non-self-describing formats do.
+    fn deserialize_ordered_field(
+        &mut self,
+        wip: Partial<'input, BORROW>,
+        field: &facet_core::Field,
+    ) -> Result<Partial<'input, BORROW>, DeserializeError<P::Error>> {
+        if let Some(codec) = field.compression()
+            && self.parser.compresses_fields()
+        {
+            return self.deserialize_compressed(wip, field, codec);
+        }
+        self.deserialize_into(wip)
+    }
+
+    /// Deserialize the compressed bytes of an `#[facet(compress = "...")]`
+    /// field (possibly inside `Option`s) and set the field from their
+    /// decompressed contents.
+    fn deserialize_compressed(
+        &mut self,
+        mut wip: Partial<'input, BORROW>,
+        field: &facet_core::Field,
+        codec: &str,
+    ) -> Result<Partial<'input, BORROW>, DeserializeError<P::Error>> {
+        if let Def::Option(_) = wip.shape().def {
+            self.parser.hint_option();
+            let event = self.expect_peek("value for option")?;
+            if matches!(event, ParseEvent::Scalar(ScalarValue::Null)) {
+                let _ = self.expect_event("null")?;
+                return wip.set_default().map_err(DeserializeError::reflect);
+            }
+            wip = wip.begin_some().map_err(DeserializeError::reflect)?;
+            wip = self.deserialize_compressed(wip, field, codec)?;
+            return wip.end().map_err(DeserializeError::reflect);
+        }
+
+        self.parser.hint_scalar_type(ScalarTypeHint::Bytes);
+        let event = self.expect_event("compressed bytes")?;
+        let ParseEvent::Scalar(ScalarValue::Bytes(compressed)) = event else {
+            return Err(DeserializeError::TypeMismatch {
+                expected: "compressed bytes",
+                got: format!("{event:?}"),
+                span: self.last_span,
+                path: None,
+            });
+        };
+
+        // The decompressed contents count toward the string limit as well
+        let max_len = self
+            .limits
+            .max_string_bytes
+            .map_or(usize::MAX, |max| max.saturating_sub(self.string_bytes));
+        let decompress_error = |message| DeserializeError::Decompress {
+            field: field.name,
+            message,
+            span: self.last_span,
+            path: None,
+        };
+        let data = crate::compression::decompress(codec, &compressed, max_len)
+            .map_err(decompress_error)?;
+        self.string_bytes = self.string_bytes.saturating_add(data.len());
+
+        let shape = wip.shape();
+        if shape.is_type::<String>() {
+            let text = String::from_utf8(data)
+                .map_err(|err| decompress_error(format!("not valid UTF-8: {err}")))?;
+            wip.set(text).map_err(DeserializeError::reflect)
+        } else if shape.is_type::<Vec<u8>>() {
+            wip.set(data).map_err(DeserializeError::reflect)
+        } else {
+            Err(DeserializeError::Unsupported(format!(
+                "compressed field `{}` must be a String or a Vec<u8>, not {shape}",
+                field.name
+            )))
+        }
+    }
+
+    /// Deserialize an `Arc<str>` (possibly inside `Option`s) through `interner`.
+    fn deserialize_interned(
+        &mut self,
+        mut wip: Partial<'input, BORROW>,
+        interner: &dyn Interner,
+    ) -> Result<Partial<'input, BORROW>, DeserializeError<P::Error>> {
+        let shape = wip.shape();
+        if let Def::Option(_) = shape.def {
+            self.parser.hint_option();
+            let event = self.expect_peek("value for option")?;
+            if matches!(event, ParseEvent::Scalar(ScalarValue::Null)) {
+                let _ = self.expect_event("null")?;
+                return wip.set_default().map_err(DeserializeError::reflect);
+            }
+            wip = wip.begin_some().map_err(DeserializeError::reflect)?;
+            wip = self.deserialize_interned(wip, interner)?;
+            return wip.end().map_err(DeserializeError::reflect);
+        }
+        if shape.id != <Arc<str> as Facet>::SHAPE.id {
+            return self.deserialize_into(wip);
+        }
+
+        // Hint to non-self-describing parsers that a string is expected
+        self.parser.hint_scalar_type(ScalarTypeHint::String);
+        let event = self.expect_event("string for interned Arc<str>")?;
+        if let ParseEvent::Scalar(ScalarValue::Str(s)) = event {
+            wip.set(interner.intern(&s))
+                .map_err(DeserializeError::reflect)
+        } else {
+            Err(DeserializeError::TypeMismatch {
+                expected: "string for interned Arc<str>",
+                got: format!("{event:?}"),
+                span: self.last_span,
+                path: None,
+            })
+        }
+    }
+
     fn deserialize_option(
         &mut self,
         mut wip: Partial<'input, BORROW>,
@@ -870,6 +1346,7 @@ where
         let num_fields = struct_def.fields.len();
         let mut fields_set = alloc::vec![false; num_fields];
         let mut ordered_field_index = 0usize;
+        let mut seen_keys = SeenKeys::new();
 
         // Track xml::elements field state for collecting child elements into lists
         // When Some((idx, in_list)), we're collecting items into field at idx
@@ -897,7 +1374,7 @@ where
                         wip = wip
                             .begin_nth_field(idx)
                             .map_err(DeserializeError::reflect)?;
-                        wip = match self.deserialize_into(wip) {
+                        wip = match self.deserialize_ordered_field(wip, &struct_def.fields[idx]) {
                             Ok(wip) => wip,
                             Err(e) => {
                                 // Only add path if error doesn't already have one
@@ -923,7 +1400,10 @@ where
                     // Look up field in struct fields (direct match)
                     // Exclude xml::elements fields - they accumulate repeated child elements
                     // and must be handled via find_elements_field_for_element below
-                    let field_info = struct_def.fields.iter().enumerate().find(|(_, f)| {
+                    let field_info = struct_def.fields.iter().enumerate().find(|(idx, f)| {
+                        if key.location == FieldLocationHint::Index {
+                            return key.name.parse() == Ok(*idx);
+                        }
                         !f.is_elements()
                             && Self::field_matches_with_namespace(
                                 f,
@@ -934,7 +1414,23 @@ where
                             )
                     });
 
-                    if let Some((idx, _field)) = field_info {
+                    // Repeated children of an xml::elements field are items of its
+                    // list rather than duplicate keys
+                    let is_element = field_info.is_none()
+                        && key.location == FieldLocationHint::Child
+                        && self
+                            .find_elements_field_for_element(
+                                struct_def.fields,
+                                key.name.as_ref(),
+                                key.namespace.as_deref(),
+                                ns_all,
+                            )
+                            .is_some();
+                    if !is_element && !self.admit_key(&mut seen_keys, key.name.as_ref())? {
+                        continue;
+                    }
+
+                    if let Some((idx, field)) = field_info {
                         // End any open xml::elements field before switching to a different field
                         // Note: begin_list() doesn't push a frame, so we only end the field
                         if let Some((elem_idx, true)) = elements_field_state
@@ -950,7 +1446,7 @@ where
                         wip = wip
                             .begin_nth_field(idx)
                             .map_err(DeserializeError::reflect)?;
-                        wip = match self.deserialize_into(wip) {
+                        wip = match self.deserialize_field_value(wip, field) {
                             Ok(wip) => wip,
                             Err(e) => {
                                 // Only add path if error doesn't already have one
@@ -1050,7 +1546,7 @@ where
                         });
                     } else {
                         // Unknown field - skip it
-                        self.parser.skip_value().map_err(DeserializeError::Parser)?;
+                        self.skip_value()?;
                     }
                 }
                 other => {
@@ -1119,6 +1615,7 @@ where
                 return Err(DeserializeError::MissingField {
                     field: field.name,
                     type_name: wip.shape().type_identifier,
+                    doc: field.doc.first().map(|line| line.trim()),
                     span: self.last_span,
                     path: None,
                 });
@@ -1311,6 +1808,7 @@ where
                         return Err(DeserializeError::MissingField {
                             field: other_field.name,
                             type_name: struct_type_name,
+                            doc: other_field.doc.first().map(|line| line.trim()),
                             span: self.last_span,
                             path: None,
                         });
@@ -1403,6 +1901,7 @@ where
         // Track xml::elements field state for collecting child elements into lists
         // (field_idx, is_open)
         let mut elements_field_state: Option<(usize, bool)> = None;
+        let mut seen_keys = SeenKeys::new();
 
         loop {
             let event = self.expect_event("value")?;
@@ -1430,7 +1929,27 @@ where
                             )
                     });
 
-                    if let Some((idx, _field)) = direct_field_info {
+                    // Repeated children of an xml::elements field are items of its
+                    // list rather than duplicate keys, while keys of flattened
+                    // fields are subject to the policy like any other
+                    let is_element = direct_field_info.is_none()
+                        && matches!(
+                            key.location,
+                            FieldLocationHint::Child | FieldLocationHint::Text
+                        )
+                        && self
+                            .find_elements_field_for_element(
+                                struct_def.fields,
+                                key.name.as_ref(),
+                                key.namespace.as_deref(),
+                                ns_all,
+                            )
+                            .is_some();
+                    if !is_element && !self.admit_key(&mut seen_keys, key.name.as_ref())? {
+                        continue;
+                    }
+
+                    if let Some((idx, field)) = direct_field_info {
                         // End any open xml::elements field before switching to a different field
                         if let Some((elem_idx, true)) = elements_field_state
                             && elem_idx != idx
@@ -1442,7 +1961,7 @@ where
                         wip = wip
                             .begin_nth_field(idx)
                             .map_err(DeserializeError::reflect)?;
-                        wip = self.deserialize_into(wip)?;
+                        wip = self.deserialize_field_value(wip, field)?;
                         wip = wip.end().map_err(DeserializeError::reflect)?;
                         fields_set[idx] = true;
                         continue;
@@ -1679,7 +2198,7 @@ where
                             path: None,
                         });
                     } else {
-                        self.parser.skip_value().map_err(DeserializeError::Parser)?;
+                        self.skip_value()?;
                     }
                 }
                 other => {
@@ -1978,6 +2497,7 @@ where
         // Track currently open path segments: (field_name, is_option, is_variant)
         // The is_variant flag indicates if we've selected a variant at this level
         let mut open_segments: alloc::vec::Vec<(&str, bool, bool)> = alloc::vec::Vec::new();
+        let mut seen_keys = SeenKeys::new();
 
         loop {
             let event = self.expect_event("value")?;
@@ -1986,6 +2506,10 @@ where
                 ParseEvent::FieldKey(key) => {
                     // Look up field in the resolution
                     if let Some(field_info) = resolution.field(key.name.as_ref()) {
+                        if !self.admit_key(&mut seen_keys, key.name.as_ref())? {
+                            continue;
+                        }
+
                         let segments = field_info.path.segments();
 
                         // Check if this path ends with a Variant segment (externally-tagged enum)
@@ -2065,7 +2589,7 @@ where
                             path: None,
                         });
                     } else {
-                        self.parser.skip_value().map_err(DeserializeError::Parser)?;
+                        self.skip_value()?;
                     }
                 }
                 other => {
@@ -2245,6 +2769,7 @@ where
         // Track which fields have been set
         let num_fields = variant_fields.len();
         let mut fields_set = alloc::vec![false; num_fields];
+        let mut seen_keys = SeenKeys::new();
 
         // Process all fields
         loop {
@@ -2263,16 +2788,19 @@ where
                         )
                     });
 
-                    if let Some((idx, _field)) = field_info {
+                    if let Some((idx, field)) = field_info {
+                        if !self.admit_key(&mut seen_keys, key.name.as_ref())? {
+                            continue;
+                        }
                         wip = wip
                             .begin_nth_field(idx)
                             .map_err(DeserializeError::reflect)?;
-                        wip = self.deserialize_into(wip)?;
+                        wip = self.deserialize_field_value(wip, field)?;
                         wip = wip.end().map_err(DeserializeError::reflect)?;
                         fields_set[idx] = true;
                     } else {
                         // Unknown field - skip
-                        self.parser.skip_value().map_err(DeserializeError::Parser)?;
+                        self.skip_value()?;
                     }
                 }
                 other => {
@@ -2387,11 +2915,12 @@ where
         &mut self,
         mut wip: Partial<'input, BORROW>,
     ) -> Result<Partial<'input, BORROW>, DeserializeError<P::Error>> {
-        // Get field count for tuple hints (needed for non-self-describing formats like postcard)
-        let field_count = match &wip.shape().ty {
-            Type::User(UserType::Struct(def)) => def.fields.len(),
-            _ => 0, // Unit type or unknown - will be handled below
+        // Get fields for tuple hints (needed for non-self-describing formats like postcard)
+        let fields = match &wip.shape().ty {
+            Type::User(UserType::Struct(def)) => def.fields,
+            _ => &[], // Unit type or unknown - will be handled below
         };
+        let field_count = fields.len();
 
         // Hint to non-self-describing parsers how many fields to expect
         // Tuples are like positional structs, so we use hint_struct_fields
@@ -2406,7 +2935,7 @@ where
         if field_count == 1 && matches!(event, ParseEvent::Scalar(_)) {
             // Unwrap into field "0" and deserialize the scalar
             wip = wip.begin_field("0").map_err(DeserializeError::reflect)?;
-            wip = self.deserialize_into(wip)?;
+            wip = self.deserialize_field_value(wip, &fields[0])?;
             wip = wip.end().map_err(DeserializeError::reflect)?;
             return Ok(wip);
         }
@@ -2464,7 +2993,11 @@ where
             wip = wip
                 .begin_field(&field_name)
                 .map_err(DeserializeError::reflect)?;
-            wip = self.deserialize_into(wip)?;
+            // Tuple struct fields take the same attributes as named ones
+            wip = match fields.get(index) {
+                Some(field) => self.deserialize_field_value(wip, field)?,
+                None => self.deserialize_into(wip)?,
+            };
             wip = wip.end().map_err(DeserializeError::reflect)?;
             index += 1;
         }
@@ -2642,7 +3175,7 @@ where
                 match event {
                     ParseEvent::StructEnd => break,
                     ParseEvent::FieldKey(_) => {
-                        self.parser.skip_value().map_err(DeserializeError::Parser)?;
+                        self.skip_value()?;
                     }
                     other => {
                         return Err(DeserializeError::TypeMismatch {
@@ -2660,6 +3193,7 @@ where
         // Track which fields have been set
         let num_fields = variant_fields.len();
         let mut fields_set = alloc::vec![false; num_fields];
+        let mut seen_keys = SeenKeys::new();
 
         // Step 4: Process all fields (they can come in any order now)
         loop {
@@ -2669,7 +3203,7 @@ where
                 ParseEvent::FieldKey(key) => {
                     // Skip the tag field - already used
                     if key.name.as_ref() == tag_key {
-                        self.parser.skip_value().map_err(DeserializeError::Parser)?;
+                        self.skip_value()?;
                         continue;
                     }
 
@@ -2685,16 +3219,19 @@ where
                         )
                     });
 
-                    if let Some((idx, _field)) = field_info {
+                    if let Some((idx, field)) = field_info {
+                        if !self.admit_key(&mut seen_keys, key.name.as_ref())? {
+                            continue;
+                        }
                         wip = wip
                             .begin_nth_field(idx)
                             .map_err(DeserializeError::reflect)?;
-                        wip = self.deserialize_into(wip)?;
+                        wip = self.deserialize_field_value(wip, field)?;
                         wip = wip.end().map_err(DeserializeError::reflect)?;
                         fields_set[idx] = true;
                     } else {
                         // Unknown field - skip
-                        self.parser.skip_value().map_err(DeserializeError::Parser)?;
+                        self.skip_value()?;
                     }
                 }
                 other => {
@@ -2817,14 +3354,14 @@ where
                 ParseEvent::FieldKey(key) => {
                     if key.name.as_ref() == tag_key {
                         // Skip the tag field - already used
-                        self.parser.skip_value().map_err(DeserializeError::Parser)?;
+                        self.skip_value()?;
                     } else if key.name.as_ref() == content_key {
                         // Deserialize the content
                         wip = self.deserialize_enum_variant_content(wip)?;
                         content_seen = true;
                     } else {
                         // Unknown field - skip
-                        self.parser.skip_value().map_err(DeserializeError::Parser)?;
+                        self.skip_value()?;
                     }
                 }
                 other => {
@@ -2978,6 +3515,7 @@ where
 
                 let num_fields = variant_fields.len();
                 let mut fields_set = alloc::vec![false; num_fields];
+                let mut seen_keys = SeenKeys::new();
                 let mut ordered_field_index = 0usize;
 
                 loop {
@@ -2992,7 +3530,7 @@ where
                                 wip = wip
                                     .begin_nth_field(idx)
                                     .map_err(DeserializeError::reflect)?;
-                                wip = self.deserialize_into(wip)?;
+                                wip = self.deserialize_ordered_field(wip, &variant_fields[idx])?;
                                 wip = wip.end().map_err(DeserializeError::reflect)?;
                                 fields_set[idx] = true;
                             }
@@ -3009,16 +3547,19 @@ where
                                 )
                             });
 
-                            if let Some((idx, _field)) = field_info {
+                            if let Some((idx, field)) = field_info {
+                                if !self.admit_key(&mut seen_keys, key.name.as_ref())? {
+                                    continue;
+                                }
                                 wip = wip
                                     .begin_nth_field(idx)
                                     .map_err(DeserializeError::reflect)?;
-                                wip = self.deserialize_into(wip)?;
+                                wip = self.deserialize_field_value(wip, field)?;
                                 wip = wip.end().map_err(DeserializeError::reflect)?;
                                 fields_set[idx] = true;
                             } else {
                                 // Unknown field - skip
-                                self.parser.skip_value().map_err(DeserializeError::Parser)?;
+                                self.skip_value()?;
                             }
                         }
                         other => {
@@ -3404,7 +3945,11 @@ where
         };
 
         // Initialize the list
-        wip = wip.begin_list().map_err(DeserializeError::reflect)?;
+        let capacity = self.capacity_hint(self.limits.max_sequence_len);
+        wip = wip
+            .begin_list_with_capacity(capacity)
+            .map_err(DeserializeError::reflect)?;
+        let mut len = 0;
 
         loop {
             let event = self.expect_peek("value")?;
@@ -3421,6 +3966,7 @@ where
                 continue;
             }
 
+            self.count_element(&mut len, self.limits.max_sequence_len, "sequence length")?;
             wip = wip.begin_list_item().map_err(DeserializeError::reflect)?;
             wip = self.deserialize_into(wip)?;
             wip = wip.end().map_err(DeserializeError::reflect)?;
@@ -3537,7 +4083,11 @@ where
         };
 
         // Initialize the set
-        wip = wip.begin_set().map_err(DeserializeError::reflect)?;
+        let capacity = self.capacity_hint(self.limits.max_sequence_len);
+        wip = wip
+            .begin_set_with_capacity(capacity)
+            .map_err(DeserializeError::reflect)?;
+        let mut len = 0;
 
         loop {
             let event = self.expect_peek("value")?;
@@ -3554,6 +4104,7 @@ where
                 continue;
             }
 
+            self.count_element(&mut len, self.limits.max_sequence_len, "sequence length")?;
             wip = wip.begin_set_item().map_err(DeserializeError::reflect)?;
             wip = self.deserialize_into(wip)?;
             wip = wip.end().map_err(DeserializeError::reflect)?;
@@ -3572,17 +4123,27 @@ where
         let event = self.expect_event("value")?;
 
         // Initialize the map
-        wip = wip.begin_map().map_err(DeserializeError::reflect)?;
+        let capacity = self.capacity_hint(self.limits.max_map_len);
+        wip = wip
+            .begin_map_with_capacity(capacity)
+            .map_err(DeserializeError::reflect)?;
+        let mut len = 0;
 
         // Handle both self-describing (StructStart) and non-self-describing (SequenceStart) formats
         match event {
             ParseEvent::StructStart(_) => {
                 // Self-describing format (e.g., JSON): maps are represented as objects
+                let mut seen_keys = SeenKeys::new();
                 loop {
                     let event = self.expect_event("value")?;
                     match event {
                         ParseEvent::StructEnd => break,
                         ParseEvent::FieldKey(key) => {
+                            if !self.admit_key(&mut seen_keys, key.name.as_ref())? {
+                                continue;
+                            }
+                            self.count_element(&mut len, self.limits.max_map_len, "map length")?;
+
                             // Begin key
                             wip = wip.begin_key().map_err(DeserializeError::reflect)?;
                             wip = self.deserialize_map_key(wip, key.name)?;
@@ -3615,6 +4176,7 @@ where
                         }
                         ParseEvent::OrderedField => {
                             self.expect_event("value")?;
+                            self.count_element(&mut len, self.limits.max_map_len, "map length")?;
 
                             // Deserialize key
                             wip = wip.begin_key().map_err(DeserializeError::reflect)?;
@@ -3735,7 +4297,15 @@ where
             path: None,
         };
 
+        let is_float = matches!(shape.type_identifier, "f32" | "f64");
         match scalar {
+            ScalarValue::Null if is_float && self.numbers.null_as_nan => {
+                if shape.type_identifier == "f32" {
+                    wip = wip.set(f32::NAN).map_err(&reflect_err)?;
+                } else {
+                    wip = wip.set(f64::NAN).map_err(&reflect_err)?;
+                }
+            }
             ScalarValue::Null => {
                 wip = wip.set_default().map_err(&reflect_err)?;
             }
@@ -3867,6 +4437,7 @@ where
                 }
             }
             ScalarValue::Str(s) => {
+                self.check_quoted_number(shape, &s)?;
                 // Try parse_from_str first if the type supports it
                 if shape.vtable.has_parse() {
                     wip = wip.parse_from_str(s.as_ref()).map_err(&reflect_err)?;
@@ -3888,6 +4459,33 @@ where
         Ok(wip)
     }
 
+    /// Rejects a string read into a number if the [`NumberLeniency`] doesn't
+    /// accept that.
+    fn check_quoted_number(
+        &self,
+        shape: &'static facet_core::Shape,
+        s: &str,
+    ) -> Result<(), DeserializeError<P::Error>> {
+        let accepted = match shape.type_identifier {
+            "f32" | "f64" if s.parse::<f64>().is_ok_and(|n| !n.is_finite()) => {
+                self.numbers.non_finite_strings
+            }
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64"
+            | "i128" | "isize" | "f32" | "f64" => self.numbers.quoted_numbers,
+            _ => true,
+        };
+        if accepted {
+            Ok(())
+        } else {
+            Err(DeserializeError::TypeMismatch {
+                expected: "a number",
+                got: format!("the string {s:?}"),
+                span: self.last_span,
+                path: None,
+            })
+        }
+    }
+
     /// Set a string value, handling `&str`, `Cow<str>`, and `String` appropriately.
     fn set_string_value(
         &mut self,
@@ -4089,6 +4687,7 @@ where
                 // Array/list
                 self.expect_event("sequence start")?; // consume '['
                 wip = wip.begin_list().map_err(DeserializeError::reflect)?;
+                let mut len = 0;
 
                 loop {
                     let event = self.expect_peek("value or end")?;
@@ -4097,6 +4696,7 @@ where
                         break;
                     }
 
+                    self.count_element(&mut len, self.limits.max_sequence_len, "sequence length")?;
                     wip = wip.begin_list_item().map_err(DeserializeError::reflect)?;
                     wip = self.deserialize_dynamic_value(wip)?;
                     wip = wip.end().map_err(DeserializeError::reflect)?;
@@ -4106,6 +4706,8 @@ where
                 // Object/map/table
                 self.expect_event("struct start")?; // consume '{'
                 wip = wip.begin_map().map_err(DeserializeError::reflect)?;
+                let mut seen_keys = SeenKeys::new();
+                let mut len = 0;
 
                 loop {
                     let event = self.expect_peek("field key or end")?;
@@ -4128,6 +4730,11 @@ where
                         }
                     };
 
+                    if !self.admit_key(&mut seen_keys, &key)? {
+                        continue;
+                    }
+                    self.count_element(&mut len, self.limits.max_map_len, "map length")?;
+
                     // Begin the object entry and deserialize the value
                     wip = wip
                         .begin_object_entry(&key)
@@ -4191,12 +4798,58 @@ pub enum DeserializeError<E> {
         /// Description of why borrowing failed.
         message: String,
     },
+    /// A key appeared more than once in an object, with [`DuplicateKeyPolicy::Error`].
+    DuplicateKey {
+        /// The repeated key, as it appears in the input.
+        key: String,
+        /// Source span of the first occurrence (if available).
+        first_span: Option<facet_reflect::Span>,
+        /// Source span of the repeated occurrence (if available).
+        span: Option<facet_reflect::Span>,
+        /// Path through the type structure where the error occurred.
+        path: Option<Path>,
+    },
+    /// The input exceeded one of the configured [`DeserializeLimits`].
+    LimitExceeded {
+        /// The limit that was exceeded, e.g. `"nesting depth"`.
+        limit: &'static str,
+        /// The configured maximum.
+        max: usize,
+        /// Source span where the limit was exceeded (if available).
+        span: Option<facet_reflect::Span>,
+        /// Path through the type structure where the error occurred.
+        path: Option<Path>,
+    },
+    /// An `#[facet(encrypt)]` field could not be decrypted.
+    Decrypt {
+        /// The encrypted field.
+        field: &'static str,
+        /// Why decryption failed.
+        message: String,
+        /// Source span of the ciphertext (if available).
+        span: Option<facet_reflect::Span>,
+        /// Path through the type structure where the error occurred.
+        path: Option<Path>,
+    },
+    /// An `#[facet(compress = "...")]` field could not be decompressed.
+    Decompress {
+        /// The compressed field.
+        field: &'static str,
+        /// Why decompression failed.
+        message: String,
+        /// Source span of the compressed bytes (if available).
+        span: Option<facet_reflect::Span>,
+        /// Path through the type structure where the error occurred.
+        path: Option<Path>,
+    },
     /// Required field missing from input.
     MissingField {
         /// The field that is missing.
         field: &'static str,
         /// The type that contains the field.
         type_name: &'static str,
+        /// The first line of the field's doc comment, if it has one.
+        doc: Option<&'static str>,
         /// Source span where the struct was being parsed (if available).
         span: Option<facet_reflect::Span>,
         /// Path through the type structure where the error occurred.
@@ -4234,11 +4887,28 @@ impl<E: fmt::Display> fmt::Display for DeserializeError<E> {
             }
             DeserializeError::Unsupported(msg) => write!(f, "unsupported: {msg}"),
             DeserializeError::UnknownField { field, .. } => write!(f, "unknown field: {field}"),
+            DeserializeError::DuplicateKey { key, .. } => write!(f, "duplicate key: {key}"),
+            DeserializeError::LimitExceeded { limit, max, .. } => {
+                write!(f, "limit exceeded: {limit} is greater than {max}")
+            }
+            DeserializeError::Decrypt { field, message, .. } => {
+                write!(f, "cannot decrypt field `{field}`: {message}")
+            }
+            DeserializeError::Decompress { field, message, .. } => {
+                write!(f, "cannot decompress field `{field}`: {message}")
+            }
             DeserializeError::CannotBorrow { message } => write!(f, "{message}"),
             DeserializeError::MissingField {
-                field, type_name, ..
+                field,
+                type_name,
+                doc,
+                ..
             } => {
-                write!(f, "missing field `{field}` in type `{type_name}`")
+                write!(f, "missing field `{field}` in type `{type_name}`")?;
+                match doc {
+                    Some(doc) if !doc.is_empty() => write!(f, " ({doc})"),
+                    _ => Ok(()),
+                }
             }
             DeserializeError::ExpectedScalarGotStruct {
                 expected_shape,
@@ -4302,12 +4972,35 @@ impl<E> DeserializeError<E> {
             DeserializeError::Reflect { path, .. } => path.as_ref(),
             DeserializeError::TypeMismatch { path, .. } => path.as_ref(),
             DeserializeError::UnknownField { path, .. } => path.as_ref(),
+            DeserializeError::DuplicateKey { path, .. } => path.as_ref(),
+            DeserializeError::LimitExceeded { path, .. } => path.as_ref(),
+            DeserializeError::Decrypt { path, .. } => path.as_ref(),
+            DeserializeError::Decompress { path, .. } => path.as_ref(),
             DeserializeError::MissingField { path, .. } => path.as_ref(),
             DeserializeError::ExpectedScalarGotStruct { path, .. } => path.as_ref(),
             _ => None,
         }
     }
 
+    /// Get the source span where the error occurred, if available.
+    ///
+    /// Errors emitted by the parser itself carry their own location and are
+    /// not covered here.
+    pub fn span(&self) -> Option<facet_reflect::Span> {
+        match self {
+            DeserializeError::Reflect { span, .. } => *span,
+            DeserializeError::TypeMismatch { span, .. } => *span,
+            DeserializeError::UnknownField { span, .. } => *span,
+            DeserializeError::DuplicateKey { span, .. } => *span,
+            DeserializeError::LimitExceeded { span, .. } => *span,
+            DeserializeError::Decrypt { span, .. } => *span,
+            DeserializeError::Decompress { span, .. } => *span,
+            DeserializeError::MissingField { span, .. } => *span,
+            DeserializeError::ExpectedScalarGotStruct { span, .. } => *span,
+            _ => None,
+        }
+    }
+
     /// Add path information to an error (consumes and returns the modified error).
     pub fn with_path(self, new_path: Path) -> Self {
         match self {
@@ -4332,14 +5025,57 @@ impl<E> DeserializeError<E> {
                 span,
                 path: Some(new_path),
             },
+            DeserializeError::DuplicateKey {
+                key,
+                first_span,
+                span,
+                ..
+            } => DeserializeError::DuplicateKey {
+                key,
+                first_span,
+                span,
+                path: Some(new_path),
+            },
+            DeserializeError::LimitExceeded {
+                limit, max, span, ..
+            } => DeserializeError::LimitExceeded {
+                limit,
+                max,
+                span,
+                path: Some(new_path),
+            },
+            DeserializeError::Decrypt {
+                field,
+                message,
+                span,
+                ..
+            } => DeserializeError::Decrypt {
+                field,
+                message,
+                span,
+                path: Some(new_path),
+            },
+            DeserializeError::Decompress {
+                field,
+                message,
+                span,
+                ..
+            } => DeserializeError::Decompress {
+                field,
+                message,
+                span,
+                path: Some(new_path),
+            },
             DeserializeError::MissingField {
                 field,
                 type_name,
+                doc,
                 span,
                 ..
             } => DeserializeError::MissingField {
                 field,
                 type_name,
+                doc,
                 span,
                 path: Some(new_path),
             },
@@ -4367,6 +5103,10 @@ impl<E: miette::Diagnostic + 'static> miette::Diagnostic for DeserializeError<E>
             DeserializeError::Parser(e) => e.code(),
             DeserializeError::TypeMismatch { .. } => Some(Box::new("facet::type_mismatch")),
             DeserializeError::MissingField { .. } => Some(Box::new("facet::missing_field")),
+            DeserializeError::DuplicateKey { .. } => Some(Box::new("facet::duplicate_key")),
+            DeserializeError::LimitExceeded { .. } => Some(Box::new("facet::limit_exceeded")),
+            DeserializeError::Decrypt { .. } => Some(Box::new("facet::decrypt")),
+            DeserializeError::Decompress { .. } => Some(Box::new("facet::decompress")),
             _ => None,
         }
     }
@@ -4438,6 +5178,34 @@ impl<E: miette::Diagnostic + 'static> miette::Diagnostic for DeserializeError<E>
                 *span,
                 "unknown field",
             )))),
+            DeserializeError::DuplicateKey {
+                first_span, span, ..
+            } => {
+                let first =
+                    first_span.map(|span| miette::LabeledSpan::at(span, "first defined here"));
+                let repeated = span.map(|span| miette::LabeledSpan::at(span, "defined again here"));
+                Some(Box::new(first.into_iter().chain(repeated)))
+            }
+            DeserializeError::LimitExceeded {
+                span: Some(span),
+                limit,
+                ..
+            } => Some(Box::new(core::iter::once(miette::LabeledSpan::at(
+                *span,
+                format!("{limit} limit exceeded here"),
+            )))),
+            DeserializeError::Decrypt {
+                span: Some(span), ..
+            } => Some(Box::new(core::iter::once(miette::LabeledSpan::at(
+                *span,
+                "cannot decrypt this value",
+            )))),
+            DeserializeError::Decompress {
+                span: Some(span), ..
+            } => Some(Box::new(core::iter::once(miette::LabeledSpan::at(
+                *span,
+                "cannot decompress this value",
+            )))),
             DeserializeError::MissingField {
                 span: Some(span),
                 field,
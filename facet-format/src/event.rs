@@ -18,6 +18,9 @@ pub enum FieldLocationHint {
     Property,
     /// KDL positional argument.
     Argument,
+    /// Declaration index of the field, given in decimal as the key name
+    /// (MsgPack with indexed keys).
+    Index,
 }
 
 /// Field key with optional namespace (for XML).
@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+/// Deduplicates strings for fields marked `#[facet(intern)]`.
+///
+/// Set one on a [`FormatDeserializer`](crate::FormatDeserializer) with
+/// [`with_interner`](crate::FormatDeserializer::with_interner). Every string read into
+/// an interned `Arc<str>` field is passed to [`Interner::intern`], so datasets
+/// that repeat the same few strings keep one allocation per distinct value.
+pub trait Interner {
+    /// Return a shared string equal to `s`.
+    fn intern(&self, s: &str) -> Arc<str>;
+}
+
+/// An [`Interner`] that keeps every string it has seen.
+///
+/// It can be shared between deserializers (and threads) to deduplicate across
+/// documents.
+///
+/// ```
+/// use facet_format::{Interner, StringInterner};
+/// use std::sync::Arc;
+///
+/// let interner = StringInterner::new();
+/// let a = interner.intern("eu-west-1");
+/// let b = interner.intern("eu-west-1");
+/// assert!(Arc::ptr_eq(&a, &b));
+/// assert_eq!(interner.len(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct StringInterner {
+    strings: Mutex<HashSet<Arc<str>>>,
+}
+
+impl StringInterner {
+    /// Create an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of distinct strings interned so far.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns true if no string has been interned yet.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashSet<Arc<str>>> {
+        // The set is always left consistent, so a poisoned lock is still usable
+        self.strings
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Interner for StringInterner {
+    fn intern(&self, s: &str) -> Arc<str> {
+        let mut strings = self.lock();
+        if let Some(existing) = strings.get(s) {
+            return existing.clone();
+        }
+        let interned: Arc<str> = Arc::from(s);
+        strings.insert(interned.clone());
+        interned
+    }
+}
@@ -148,6 +148,7 @@ impl<'de, T: Facet<'de>, P: FormatParser<'de>> CompiledDeserializer<T, P> {
                 Err(DeserializeError::MissingField {
                     field: "unknown", // TODO: Track which field is missing
                     type_name: T::SHAPE.type_identifier,
+                    doc: None,
                     span: None, // JIT doesn't have span info
                     path: None, // JIT doesn't have path info
                 })
@@ -220,6 +221,10 @@ pub fn is_jit_compatible(shape: &'static Shape) -> bool {
 
 /// Check if a field type is supported for JIT compilation.
 fn is_field_type_supported(field: &Field) -> bool {
+    // Compressed fields go through the codec
+    if field.compression().is_some() {
+        return false;
+    }
     // Just check if WriteKind::from_shape can handle this type
     WriteKind::from_shape(field.shape()).is_some()
 }
@@ -186,6 +186,14 @@ fn ensure_format_jit_struct_supported_with_encoding(
             });
         }
 
+        // Compressed fields go through the codec
+        if field.compression().is_some() {
+            return Err(Tier2Incompatibility::CompressedField {
+                type_name,
+                field_name: field.name,
+            });
+        }
+
         // Field type must be supported (for normal, non-flattened fields)
         ensure_format_jit_field_type_supported(field.shape(), type_name, field.name)?;
     }
@@ -272,6 +272,14 @@ pub enum Tier2Incompatibility {
         field_name: &'static str,
     },
 
+    /// Field is compressed with `#[facet(compress = "...")]`
+    CompressedField {
+        /// The containing struct type.
+        type_name: &'static str,
+        /// The compressed field.
+        field_name: &'static str,
+    },
+
     /// Field type not supported
     UnsupportedFieldType {
         /// The containing struct type.
@@ -416,6 +424,16 @@ impl std::fmt::Display for Tier2Incompatibility {
                     type_name, field_name
                 )
             }
+            Self::CompressedField {
+                type_name,
+                field_name,
+            } => {
+                write!(
+                    f,
+                    "field `{}::{}` is compressed, which is not supported in Tier-2 JIT",
+                    type_name, field_name
+                )
+            }
             Self::UnsupportedFieldType {
                 type_name,
                 field_name,
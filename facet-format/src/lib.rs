@@ -3,25 +3,39 @@
 
 //! Prototype types for the format codex deserializer.
 
+mod cipher;
 mod deserializer;
 mod event;
 mod evidence;
+mod intern;
+mod limits;
 mod parser;
+mod projection;
 mod serializer;
 mod solver;
 mod visitor;
 
+pub mod compression;
 #[cfg(feature = "jit")]
 pub mod jit;
 
-pub use deserializer::{DeserializeError, FormatDeserializer};
+pub use cipher::FieldCipher;
+pub use deserializer::{DeserializeError, DuplicateKeyPolicy, FormatDeserializer, NumberLeniency};
 pub use event::{
     ContainerKind, FieldKey, FieldLocationHint, ParseEvent, ScalarValue, ValueTypeHint,
 };
 pub use evidence::FieldEvidence;
+pub use intern::{Interner, StringInterner};
+pub use limits::DeserializeLimits;
 #[cfg(feature = "jit")]
 pub use parser::FormatJitParser;
 pub use parser::{EnumVariantHint, FormatParser, ProbeStream, ScalarTypeHint};
-pub use serializer::{FieldOrdering, FormatSerializer, SerializeError, serialize_root};
+pub use projection::{
+    FieldProjection, ProjectionError, serialize_projected, serialize_with_fields,
+};
+pub use serializer::{
+    FieldOrdering, FormatSerializer, SerializeError, serialize_dirty, serialize_facet_map,
+    serialize_facet_vec, serialize_root,
+};
 pub use solver::{SolveOutcome, SolveVariantError, solve_variant};
 pub use visitor::{FieldMatch, StructFieldTracker};
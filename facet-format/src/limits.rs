@@ -0,0 +1,64 @@
+/// Limits enforced by [`FormatDeserializer`](crate::FormatDeserializer) on its input.
+///
+/// Every limit is disabled by default. Set them when deserializing untrusted
+/// input, so that a small malicious document can't exhaust the stack or memory.
+/// Exceeding a limit fails with
+/// [`DeserializeError::LimitExceeded`](crate::DeserializeError::LimitExceeded).
+///
+/// ```
+/// use facet_format::DeserializeLimits;
+///
+/// let limits = DeserializeLimits::new()
+///     .max_depth(32)
+///     .max_string_bytes(1 << 20)
+///     .max_sequence_len(10_000)
+///     .max_map_len(1_000);
+/// assert_eq!(limits.max_depth, Some(32));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeserializeLimits {
+    /// Maximum nesting of objects and sequences (default: unlimited)
+    pub max_depth: Option<usize>,
+    /// Maximum total bytes of strings, bytes and keys in the input (default: unlimited)
+    pub max_string_bytes: Option<usize>,
+    /// Maximum number of elements in a single list or set (default: unlimited)
+    pub max_sequence_len: Option<usize>,
+    /// Maximum number of entries in a single map or dynamic object (default: unlimited)
+    pub max_map_len: Option<usize>,
+}
+
+impl DeserializeLimits {
+    /// Create limits with everything unlimited.
+    pub const fn new() -> Self {
+        Self {
+            max_depth: None,
+            max_string_bytes: None,
+            max_sequence_len: None,
+            max_map_len: None,
+        }
+    }
+
+    /// Limit the nesting of objects and sequences.
+    pub const fn max_depth(mut self, max: usize) -> Self {
+        self.max_depth = Some(max);
+        self
+    }
+
+    /// Limit the total bytes of strings, bytes and keys in the input.
+    pub const fn max_string_bytes(mut self, max: usize) -> Self {
+        self.max_string_bytes = Some(max);
+        self
+    }
+
+    /// Limit the number of elements in a single list or set.
+    pub const fn max_sequence_len(mut self, max: usize) -> Self {
+        self.max_sequence_len = Some(max);
+        self
+    }
+
+    /// Limit the number of entries in a single map or dynamic object.
+    pub const fn max_map_len(mut self, max: usize) -> Self {
+        self.max_map_len = Some(max);
+        self
+    }
+}
@@ -76,6 +76,15 @@ pub trait FormatParser<'de> {
         true // Default: most formats are self-describing
     }
 
+    /// Whether fields marked `#[facet(compress = "...")]` are read as
+    /// compressed bytes; see [`compression`](crate::compression).
+    ///
+    /// Must match the format's serializer. Default: `false`, so such fields
+    /// are read like any other.
+    fn compresses_fields(&self) -> bool {
+        false
+    }
+
     /// Hint to the parser that a struct with the given number of fields is expected.
     ///
     /// For non-self-describing formats, this allows the parser to emit the correct
@@ -184,6 +193,17 @@ pub trait FormatParser<'de> {
         false
     }
 
+    /// Returns how many items (or map entries) the container whose start event
+    /// was just returned holds, if the wire format says so up front.
+    ///
+    /// The deserializer uses this as a capacity hint for the collection it
+    /// builds; it is never trusted for correctness. Formats with length
+    /// prefixes (postcard, MsgPack, ...) should override this; the default
+    /// returns `None`.
+    fn container_len(&self) -> Option<usize> {
+        None
+    }
+
     /// Returns the source span of the most recently consumed event.
     ///
     /// This is used for error reporting - when a deserialization error occurs,
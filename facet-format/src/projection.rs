@@ -0,0 +1,542 @@
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use facet_core::ScalarType;
+use facet_reflect::Peek;
+
+use crate::{
+    FieldCipher, FieldOrdering, FormatSerializer, ScalarValue, SerializeError, serialize_root,
+    serializer::field_key_name,
+};
+
+/// A set of field paths selecting which parts of a value get serialized.
+///
+/// Paths use dotted field names with bracketed list indices:
+///
+/// - `id` — the `id` field (and everything below it)
+/// - `owner.name` — only `name` inside the `owner` field
+/// - `items[*].price` — the `price` of every element of `items`
+/// - `items[0]` — only the first element of `items`
+/// - `[*].id` — the `id` of every element of a top-level list
+///
+/// Map entries are selected by key like struct fields. Selecting below a
+/// scalar (e.g. `name.first` when `name` is a string) selects the scalar.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldProjection {
+    root: Node,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Node {
+    /// The whole subtree at this node is selected.
+    all: bool,
+    children: Vec<(Segment, Node)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+    AnyIndex,
+}
+
+/// Error returned when a field selector can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectionError {
+    /// The selector that failed to parse.
+    pub path: String,
+    /// What is wrong with it.
+    pub reason: &'static str,
+}
+
+impl fmt::Display for ProjectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid field selector `{}`: {}", self.path, self.reason)
+    }
+}
+
+impl std::error::Error for ProjectionError {}
+
+impl FieldProjection {
+    /// Parse a list of field selectors.
+    pub fn new<I, P>(paths: I) -> Result<Self, ProjectionError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        let mut projection = Self::default();
+        for path in paths {
+            projection.add(path.as_ref())?;
+        }
+        Ok(projection)
+    }
+
+    /// Add one more field selector.
+    pub fn add(&mut self, path: &str) -> Result<(), ProjectionError> {
+        let segments = parse_path(path).map_err(|reason| ProjectionError {
+            path: path.to_string(),
+            reason,
+        })?;
+
+        let mut node = &mut self.root;
+        for segment in segments {
+            let idx = match node.children.iter().position(|(s, _)| *s == segment) {
+                Some(idx) => idx,
+                None => {
+                    node.children.push((segment, Node::default()));
+                    node.children.len() - 1
+                }
+            };
+            node = &mut node.children[idx].1;
+        }
+        node.all = true;
+        Ok(())
+    }
+}
+
+fn parse_path(path: &str) -> Result<Vec<Segment>, &'static str> {
+    if path.is_empty() {
+        return Err("selector is empty");
+    }
+
+    let mut segments = Vec::new();
+    let mut rest = path;
+    let mut expect_key = !path.starts_with('[');
+    loop {
+        if expect_key {
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            if end == 0 {
+                return Err("expected a field name");
+            }
+            if rest[..end].contains(']') {
+                return Err("unexpected `]`");
+            }
+            segments.push(Segment::Key(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+
+        while let Some(after) = rest.strip_prefix('[') {
+            let close = after.find(']').ok_or("unclosed `[`")?;
+            let index = &after[..close];
+            segments.push(if index == "*" {
+                Segment::AnyIndex
+            } else {
+                Segment::Index(
+                    index
+                        .parse()
+                        .map_err(|_| "list index must be `*` or a number")?,
+                )
+            });
+            rest = &after[close + 1..];
+        }
+
+        if rest.is_empty() {
+            return Ok(segments);
+        }
+        rest = rest
+            .strip_prefix('.')
+            .ok_or("expected `.` or `[` after a segment")?;
+        expect_key = true;
+    }
+}
+
+/// The part of the projection that applies to the current value.
+#[derive(Clone)]
+enum Selection<'p> {
+    All,
+    Nodes(Vec<&'p Node>),
+}
+
+impl<'p> Selection<'p> {
+    fn child(&self, matches: impl Fn(&Segment) -> bool) -> Option<Selection<'p>> {
+        let Selection::Nodes(nodes) = self else {
+            return Some(Selection::All);
+        };
+        let mut children = Vec::new();
+        for node in nodes {
+            for (segment, child) in &node.children {
+                if matches(segment) {
+                    if child.all {
+                        return Some(Selection::All);
+                    }
+                    children.push(child);
+                }
+            }
+        }
+        (!children.is_empty()).then_some(Selection::Nodes(children))
+    }
+
+    fn key(&self, key: &str) -> Option<Selection<'p>> {
+        self.child(|segment| matches!(segment, Segment::Key(k) if k == key))
+    }
+
+    fn index(&self, index: usize) -> Option<Selection<'p>> {
+        self.child(|segment| match segment {
+            Segment::Index(i) => *i == index,
+            Segment::AnyIndex => true,
+            Segment::Key(_) => false,
+        })
+    }
+}
+
+enum Frame<'p> {
+    Struct {
+        selection: Selection<'p>,
+        /// Selection for the value following the last emitted key.
+        pending: Option<Selection<'p>>,
+    },
+    Seq {
+        selection: Selection<'p>,
+        next_index: usize,
+    },
+}
+
+/// How an event relates to the value currently being emitted.
+enum ValueEvent {
+    /// Emits a prefix (option tag, enum variant) — the value continues.
+    Prefix,
+    /// Opens a container.
+    Open,
+    /// A complete scalar value.
+    Scalar,
+}
+
+/// A serializer adapter that drops every event outside the projection.
+struct ProjectingSerializer<'p, 's, S> {
+    inner: &'s mut S,
+    root: Option<Selection<'p>>,
+    stack: Vec<Frame<'p>>,
+    /// Selection carried over from a prefix event to the rest of the value.
+    prefixed: Option<Selection<'p>>,
+    /// When set, events are being dropped; the count is the container depth
+    /// inside the skipped value.
+    skipping: Option<usize>,
+}
+
+impl<'p, 's, S: FormatSerializer> ProjectingSerializer<'p, 's, S> {
+    /// Decide whether the value an event belongs to is emitted.
+    ///
+    /// Returns the value's selection if it is, or `None` after switching
+    /// to skip mode.
+    fn enter_value(&mut self, event: ValueEvent) -> Option<Selection<'p>> {
+        if let Some(depth) = &mut self.skipping {
+            match event {
+                ValueEvent::Prefix => {}
+                ValueEvent::Open => *depth += 1,
+                ValueEvent::Scalar if *depth == 0 => self.skipping = None,
+                ValueEvent::Scalar => {}
+            }
+            return None;
+        }
+
+        let selection = match self.prefixed.take() {
+            Some(selection) => Some(selection),
+            None => match self.stack.last_mut() {
+                None => self.root.take(),
+                Some(Frame::Struct { pending, .. }) => pending.take(),
+                Some(Frame::Seq {
+                    selection,
+                    next_index,
+                }) => {
+                    let index = *next_index;
+                    *next_index += 1;
+                    selection.index(index)
+                }
+            },
+        };
+
+        match selection {
+            Some(selection) => {
+                if matches!(event, ValueEvent::Prefix) {
+                    self.prefixed = Some(selection.clone());
+                }
+                Some(selection)
+            }
+            None => {
+                self.skipping = Some(0);
+                self.enter_value(event)
+            }
+        }
+    }
+
+    /// Handle the end of a container; returns true if it must be forwarded.
+    fn leave_container(&mut self) -> bool {
+        if let Some(depth) = &mut self.skipping {
+            *depth -= 1;
+            if *depth == 0 {
+                self.skipping = None;
+            }
+            return false;
+        }
+        self.stack.pop();
+        true
+    }
+
+    fn key_selection(&self, key: &str) -> Option<Selection<'p>> {
+        if self.skipping.is_some() {
+            return None;
+        }
+        match self.stack.last() {
+            Some(Frame::Struct { selection, .. }) => selection.key(key),
+            _ => None,
+        }
+    }
+}
+
+impl<'p, 's, S: FormatSerializer> FormatSerializer for ProjectingSerializer<'p, 's, S> {
+    type Error = S::Error;
+
+    fn begin_struct(&mut self) -> Result<(), Self::Error> {
+        if let Some(selection) = self.enter_value(ValueEvent::Open) {
+            self.stack.push(Frame::Struct {
+                selection,
+                pending: None,
+            });
+            self.inner.begin_struct()?;
+        }
+        Ok(())
+    }
+
+    fn field_key(&mut self, key: &str) -> Result<(), Self::Error> {
+        if self.skipping.is_some() {
+            return Ok(());
+        }
+        match self.key_selection(key) {
+            Some(selection) => {
+                if let Some(Frame::Struct { pending, .. }) = self.stack.last_mut() {
+                    *pending = Some(selection);
+                }
+                self.inner.field_key(key)
+            }
+            None => {
+                // Drop the key and the value that follows it
+                self.skipping = Some(0);
+                Ok(())
+            }
+        }
+    }
+
+    fn end_struct(&mut self) -> Result<(), Self::Error> {
+        if self.leave_container() {
+            self.inner.end_struct()?;
+        }
+        Ok(())
+    }
+
+    fn begin_seq(&mut self) -> Result<(), Self::Error> {
+        if let Some(selection) = self.enter_value(ValueEvent::Open) {
+            self.stack.push(Frame::Seq {
+                selection,
+                next_index: 0,
+            });
+            self.inner.begin_seq()?;
+        }
+        Ok(())
+    }
+
+    fn begin_seq_with_len(&mut self, len: usize) -> Result<(), Self::Error> {
+        if let Some(selection) = self.enter_value(ValueEvent::Open) {
+            // Only a fully selected sequence keeps its length
+            let all = matches!(selection, Selection::All);
+            self.stack.push(Frame::Seq {
+                selection,
+                next_index: 0,
+            });
+            if all {
+                self.inner.begin_seq_with_len(len)?;
+            } else {
+                self.inner.begin_seq()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn end_seq(&mut self) -> Result<(), Self::Error> {
+        if self.leave_container() {
+            self.inner.end_seq()?;
+        }
+        Ok(())
+    }
+
+    fn scalar(&mut self, scalar: ScalarValue<'_>) -> Result<(), Self::Error> {
+        if self.enter_value(ValueEvent::Scalar).is_some() {
+            self.inner.scalar(scalar)?;
+        }
+        Ok(())
+    }
+
+    fn field_metadata(&mut self, field: &facet_reflect::FieldItem) -> Result<(), Self::Error> {
+        if self.key_selection(field_key_name(self, field)).is_some() {
+            self.inner.field_metadata(field)?;
+        }
+        Ok(())
+    }
+
+    fn struct_metadata(&mut self, shape: &facet_core::Shape) -> Result<(), Self::Error> {
+        if self.skipping.is_none() {
+            self.inner.struct_metadata(shape)?;
+        }
+        Ok(())
+    }
+
+    fn variant_metadata(
+        &mut self,
+        variant: &'static facet_core::Variant,
+    ) -> Result<(), Self::Error> {
+        if self.skipping.is_none() {
+            self.inner.variant_metadata(variant)?;
+        }
+        Ok(())
+    }
+
+    fn preferred_field_order(&self) -> FieldOrdering {
+        self.inner.preferred_field_order()
+    }
+
+    fn raw_serialize_shape(&self) -> Option<&'static facet_core::Shape> {
+        self.inner.raw_serialize_shape()
+    }
+
+    fn field_cipher(&self) -> Option<&dyn FieldCipher> {
+        self.inner.field_cipher()
+    }
+
+    fn rename_profile(&self) -> Option<&str> {
+        self.inner.rename_profile()
+    }
+
+    fn compresses_fields(&self) -> bool {
+        self.inner.compresses_fields()
+    }
+
+    fn raw_scalar(&mut self, content: &str) -> Result<(), Self::Error> {
+        if self.enter_value(ValueEvent::Scalar).is_some() {
+            self.inner.raw_scalar(content)?;
+        }
+        Ok(())
+    }
+
+    fn typed_scalar(
+        &mut self,
+        scalar_type: ScalarType,
+        value: Peek<'_, '_>,
+    ) -> Result<(), Self::Error> {
+        if self.enter_value(ValueEvent::Scalar).is_some() {
+            self.inner.typed_scalar(scalar_type, value)?;
+        }
+        Ok(())
+    }
+
+    fn begin_option_some(&mut self) -> Result<(), Self::Error> {
+        if self.enter_value(ValueEvent::Prefix).is_some() {
+            self.inner.begin_option_some()?;
+        }
+        Ok(())
+    }
+
+    fn serialize_none(&mut self) -> Result<(), Self::Error> {
+        if self.enter_value(ValueEvent::Scalar).is_some() {
+            self.inner.serialize_none()?;
+        }
+        Ok(())
+    }
+
+    fn begin_enum_variant(
+        &mut self,
+        variant_index: usize,
+        variant_name: &'static str,
+    ) -> Result<(), Self::Error> {
+        if self.enter_value(ValueEvent::Prefix).is_some() {
+            self.inner.begin_enum_variant(variant_index, variant_name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serialize only the parts of a value selected by `projection`.
+///
+/// Structs and maps keep only the selected keys; lists keep only the
+/// selected elements. This is meant for self-describing formats — binary
+/// formats that rely on the shape to decode (like postcard) can't read the
+/// result back into the original type.
+pub fn serialize_projected<'mem, 'facet, S>(
+    serializer: &mut S,
+    value: Peek<'mem, 'facet>,
+    projection: &FieldProjection,
+) -> Result<(), SerializeError<S::Error>>
+where
+    S: FormatSerializer,
+{
+    let root = if projection.root.all {
+        Selection::All
+    } else {
+        Selection::Nodes(vec![&projection.root])
+    };
+    let mut projecting = ProjectingSerializer {
+        inner: serializer,
+        root: Some(root),
+        stack: Vec::new(),
+        prefixed: None,
+        skipping: None,
+    };
+    serialize_root(&mut projecting, value)
+}
+
+/// Serialize only the given field paths of a value.
+///
+/// See [`FieldProjection`] for the selector syntax. This is the generic
+/// building block for sparse field sets (`?fields=id,name,items[*].price`);
+/// use [`serialize_projected`] to parse the selectors once and reuse them.
+pub fn serialize_with_fields<'mem, 'facet, S>(
+    serializer: &mut S,
+    value: Peek<'mem, 'facet>,
+    fields: &[&str],
+) -> Result<(), SerializeError<S::Error>>
+where
+    S: FormatSerializer,
+{
+    let projection = FieldProjection::new(fields)
+        .map_err(|err| SerializeError::Unsupported(Cow::Owned(format!("{err}"))))?;
+    serialize_projected(serializer, value, &projection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_selectors() {
+        assert_eq!(
+            parse_path("items[*].price").unwrap(),
+            vec![
+                Segment::Key("items".into()),
+                Segment::AnyIndex,
+                Segment::Key("price".into()),
+            ]
+        );
+        assert_eq!(
+            parse_path("[2][*]").unwrap(),
+            vec![Segment::Index(2), Segment::AnyIndex]
+        );
+        assert_eq!(
+            parse_path("a.b").unwrap(),
+            vec![Segment::Key("a".into()), Segment::Key("b".into())]
+        );
+    }
+
+    #[test]
+    fn reject_bad_selectors() {
+        assert!(parse_path("").is_err());
+        assert!(parse_path("items[").is_err());
+        assert!(parse_path("items[x]").is_err());
+        assert!(parse_path("a..b").is_err());
+        assert!(parse_path("a]").is_err());
+        assert!(parse_path("a.").is_err());
+    }
+}
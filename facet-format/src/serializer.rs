@@ -3,10 +3,13 @@ extern crate alloc;
 use alloc::borrow::Cow;
 use core::fmt::Debug;
 
-use facet_core::{ScalarType, StructKind};
-use facet_reflect::{HasFields as _, Peek, ReflectError};
+use facet_core::{DynDateTimeKind, DynValueKind, ScalarType, StructKind};
+use facet_reflect::{
+    DirtyPaths, FacetMap, FacetVec, HasFields as _, OwnedFacet, Peek, PeekDynamicValue,
+    ReflectError,
+};
 
-use crate::ScalarValue;
+use crate::{FieldCipher, ScalarValue};
 
 /// Field ordering preference for serialization.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -82,6 +85,32 @@ pub trait FormatSerializer {
         None
     }
 
+    /// Rename profile to use for field names, e.g. `"json"` or `"db"`.
+    ///
+    /// Fields declared with `#[facet(rename(json = "fooBar", db = "foo_bar"))]`
+    /// are written under the name for this profile; fields without one keep
+    /// their usual name. Default: no profile.
+    fn rename_profile(&self) -> Option<&str> {
+        None
+    }
+
+    /// Cipher for fields marked `#[facet(encrypt)]`.
+    ///
+    /// Such fields are written as the ciphertext of their value, as a string.
+    /// Default: none, so serializing an encrypted field fails.
+    fn field_cipher(&self) -> Option<&dyn FieldCipher> {
+        None
+    }
+
+    /// Whether fields marked `#[facet(compress = "...")]` are written
+    /// compressed, as bytes; see [`compression`](crate::compression).
+    ///
+    /// Binary formats should return `true`. Default: `false`, so such fields
+    /// are written like any other.
+    fn compresses_fields(&self) -> bool {
+        false
+    }
+
     /// Emit a raw scalar value (for RawJson, etc.) without any encoding/escaping.
     ///
     /// The content is the format-specific raw representation that should be
@@ -218,6 +247,10 @@ pub enum SerializeError<E: Debug> {
     Unsupported(Cow<'static, str>),
     /// Internal invariant violation.
     Internal(Cow<'static, str>),
+    /// An encrypted field could not be encrypted.
+    Cipher(Cow<'static, str>),
+    /// A compressed field could not be compressed.
+    Compression(Cow<'static, str>),
 }
 
 impl<E: Debug> core::fmt::Display for SerializeError<E> {
@@ -227,6 +260,8 @@ impl<E: Debug> core::fmt::Display for SerializeError<E> {
             SerializeError::Reflect(err) => write!(f, "{err}"),
             SerializeError::Unsupported(msg) => f.write_str(msg.as_ref()),
             SerializeError::Internal(msg) => f.write_str(msg.as_ref()),
+            SerializeError::Cipher(msg) => f.write_str(msg.as_ref()),
+            SerializeError::Compression(msg) => f.write_str(msg.as_ref()),
         }
     }
 }
@@ -244,6 +279,105 @@ where
     shared_serialize(serializer, value)
 }
 
+/// Serialize a [`FacetVec`] as a sequence of `{"type": ..., "value": ...}`
+/// entries, so a reader can tell what type each value is.
+///
+/// The type is each value's [`OwnedFacet::type_tag`].
+pub fn serialize_facet_vec<S>(
+    serializer: &mut S,
+    values: &FacetVec,
+) -> Result<(), SerializeError<S::Error>>
+where
+    S: FormatSerializer,
+{
+    serializer
+        .begin_seq_with_len(values.len())
+        .map_err(SerializeError::Backend)?;
+    for value in values.values() {
+        serialize_tagged(serializer, value)?;
+    }
+    serializer.end_seq().map_err(SerializeError::Backend)
+}
+
+/// Serialize a [`FacetMap`] as a map from each key to a
+/// `{"type": ..., "value": ...}` entry, like [`serialize_facet_vec`].
+pub fn serialize_facet_map<S>(
+    serializer: &mut S,
+    values: &FacetMap,
+) -> Result<(), SerializeError<S::Error>>
+where
+    S: FormatSerializer,
+{
+    serializer.begin_struct().map_err(SerializeError::Backend)?;
+    for (key, value) in values.entries() {
+        serializer.field_key(key).map_err(SerializeError::Backend)?;
+        serialize_tagged(serializer, value)?;
+    }
+    serializer.end_struct().map_err(SerializeError::Backend)
+}
+
+/// Serialize only the parts of `root` marked in `dirty`, as a map from each
+/// changed path to the value now at that path.
+///
+/// A peer holding the previous version can apply the map with
+/// [`Peek::at`]-style paths, instead of receiving the whole document again.
+pub fn serialize_dirty<'mem, 'facet, S>(
+    serializer: &mut S,
+    root: Peek<'mem, 'facet>,
+    dirty: &DirtyPaths,
+) -> Result<(), SerializeError<S::Error>>
+where
+    S: FormatSerializer,
+{
+    serializer.begin_struct().map_err(SerializeError::Backend)?;
+    for (path, value) in dirty.subtrees(root) {
+        let value = value.map_err(|err| {
+            SerializeError::Unsupported(Cow::Owned(alloc::format!("changed path `{path}` {err}")))
+        })?;
+        serializer
+            .field_key(path)
+            .map_err(SerializeError::Backend)?;
+        shared_serialize(serializer, value)?;
+    }
+    serializer.end_struct().map_err(SerializeError::Backend)
+}
+
+fn serialize_tagged<S>(
+    serializer: &mut S,
+    value: &OwnedFacet,
+) -> Result<(), SerializeError<S::Error>>
+where
+    S: FormatSerializer,
+{
+    serializer.begin_struct().map_err(SerializeError::Backend)?;
+    serializer
+        .field_key("type")
+        .map_err(SerializeError::Backend)?;
+    serializer
+        .scalar(ScalarValue::Str(value.type_tag()))
+        .map_err(SerializeError::Backend)?;
+    serializer
+        .field_key("value")
+        .map_err(SerializeError::Backend)?;
+    shared_serialize(serializer, value.peek())?;
+    serializer.end_struct().map_err(SerializeError::Backend)
+}
+
+/// The key to write for a field, honoring the serializer's rename profile.
+pub(crate) fn field_key_name<'a, S>(
+    serializer: &S,
+    field_item: &'a facet_reflect::FieldItem,
+) -> &'a str
+where
+    S: FormatSerializer,
+{
+    serializer
+        .rename_profile()
+        .zip(field_item.field)
+        .and_then(|(profile, field)| field.rename_for(profile))
+        .unwrap_or(field_item.name.as_ref())
+}
+
 /// Helper to sort fields according to format preference
 fn sort_fields_if_needed<'mem, 'facet, S>(
     serializer: &S,
@@ -265,6 +399,125 @@ fn sort_fields_if_needed<'mem, 'facet, S>(
     }
 }
 
+/// Serialize the value of a struct or variant field, honoring field-level
+/// proxies and encryption.
+fn serialize_field_value<'mem, 'facet, S>(
+    serializer: &mut S,
+    field_item: &facet_reflect::FieldItem,
+    value: Peek<'mem, 'facet>,
+) -> Result<(), SerializeError<S::Error>>
+where
+    S: FormatSerializer,
+{
+    match field_item.field {
+        Some(field) if field.is_encrypted() => serialize_encrypted(serializer, &field, value),
+        Some(field) if field.compression().is_some() && serializer.compresses_fields() => {
+            serialize_compressed(serializer, &field, value)
+        }
+        Some(field) if field.proxy().is_some() => {
+            serialize_via_proxy(serializer, value, field.proxy().unwrap())
+        }
+        _ => shared_serialize(serializer, value),
+    }
+}
+
+/// Serialize the value of an `#[facet(encrypt)]` field as ciphertext.
+///
+/// `None` is written as-is; other values must be strings or displayable
+/// scalars.
+fn serialize_encrypted<'mem, 'facet, S>(
+    serializer: &mut S,
+    field: &facet_core::Field,
+    value: Peek<'mem, 'facet>,
+) -> Result<(), SerializeError<S::Error>>
+where
+    S: FormatSerializer,
+{
+    let value = deref_if_pointer(value);
+    if let Ok(opt) = value.into_option() {
+        return match opt.value() {
+            Some(inner) => {
+                serializer
+                    .begin_option_some()
+                    .map_err(SerializeError::Backend)?;
+                serialize_encrypted(serializer, field, inner)
+            }
+            None => serializer.serialize_none().map_err(SerializeError::Backend),
+        };
+    }
+
+    let plaintext = match value.as_str() {
+        Some(s) => Cow::Borrowed(s),
+        None if value.shape().vtable.has_display() => Cow::Owned(alloc::format!("{value}")),
+        None => {
+            return Err(SerializeError::Unsupported(Cow::Owned(alloc::format!(
+                "encrypted field `{}` must be a string or a displayable scalar, not {}",
+                field.name,
+                value.shape()
+            ))));
+        }
+    };
+    let Some(cipher) = serializer.field_cipher() else {
+        return Err(SerializeError::Cipher(Cow::Owned(alloc::format!(
+            "field `{}` is encrypted, but the serializer has no cipher",
+            field.name
+        ))));
+    };
+    let ciphertext = cipher.encrypt(field, &plaintext).map_err(|err| {
+        SerializeError::Cipher(Cow::Owned(alloc::format!(
+            "failed to encrypt field `{}`: {err}",
+            field.name
+        )))
+    })?;
+    serializer
+        .scalar(ScalarValue::Str(Cow::Owned(ciphertext)))
+        .map_err(SerializeError::Backend)
+}
+
+/// Serialize the value of an `#[facet(compress = "...")]` field as the
+/// compressed bytes of its contents.
+///
+/// `None` is written as-is.
+fn serialize_compressed<'mem, 'facet, S>(
+    serializer: &mut S,
+    field: &facet_core::Field,
+    value: Peek<'mem, 'facet>,
+) -> Result<(), SerializeError<S::Error>>
+where
+    S: FormatSerializer,
+{
+    let value = deref_if_pointer(value);
+    if let Ok(opt) = value.into_option() {
+        return match opt.value() {
+            Some(inner) => {
+                serializer
+                    .begin_option_some()
+                    .map_err(SerializeError::Backend)?;
+                serialize_compressed(serializer, field, inner)
+            }
+            None => serializer.serialize_none().map_err(SerializeError::Backend),
+        };
+    }
+
+    let Some(data) = crate::compression::field_bytes(value) else {
+        return Err(SerializeError::Unsupported(Cow::Owned(alloc::format!(
+            "compressed field `{}` must be a String or a Vec<u8>, not {}",
+            field.name,
+            value.shape()
+        ))));
+    };
+    let codec = field.compression().unwrap_or_default();
+    let compressed = crate::compression::compress(codec, data).map_err(|err| {
+        SerializeError::Compression(Cow::Owned(alloc::format!(
+            "failed to compress field `{}`: {err}",
+            field.name
+        )))
+    })?;
+    serializer
+        .scalar(ScalarValue::Bytes(Cow::Owned(compressed)))
+        .map_err(SerializeError::Backend)
+}
+
 fn shared_serialize<'mem, 'facet, S>(
     serializer: &mut S,
     value: Peek<'mem, 'facet>,
@@ -295,6 +548,17 @@ where
 
     let value = value.innermost_peek();
 
+    // Locks (Mutex, RwLock, RefCell) are serialized as the value they guard
+    if let Ok(ptr) = value.into_pointer()
+        && ptr.def().is_lock()
+    {
+        return ptr
+            .with_locked(|inner| shared_serialize(serializer, inner))
+            .unwrap_or(Err(SerializeError::Unsupported(Cow::Borrowed(
+                "cannot serialize a value that is locked elsewhere",
+            ))));
+    }
+
     // Check for container-level proxy - serialize through the proxy type
     if let Some(proxy_def) = value.shape().proxy {
         return serialize_via_proxy(serializer, value, proxy_def);
@@ -320,6 +584,10 @@ where
         };
     }
 
+    if let Ok(dynamic) = value.into_dynamic_value() {
+        return serialize_dynamic_value(serializer, dynamic);
+    }
+
     if let Ok(list) = value.into_list_like() {
         // Use begin_seq_with_len for binary formats that need length prefixes
         let items: alloc::vec::Vec<_> = list.iter().collect();
@@ -374,12 +642,7 @@ where
                 .begin_seq_with_len(fields.len())
                 .map_err(SerializeError::Backend)?;
             for (field_item, field_value) in fields {
-                // Check for field-level proxy
-                if let Some(proxy_def) = field_item.field.and_then(|f| f.proxy()) {
-                    serialize_via_proxy(serializer, field_value, proxy_def)?;
-                } else {
-                    shared_serialize(serializer, field_value)?;
-                }
+                serialize_field_value(serializer, &field_item, field_value)?;
             }
             serializer.end_seq().map_err(SerializeError::Backend)?;
         } else {
@@ -398,14 +661,9 @@ where
                     .field_metadata(&field_item)
                     .map_err(SerializeError::Backend)?;
                 serializer
-                    .field_key(&field_item.name)
+                    .field_key(field_key_name(serializer, &field_item))
                     .map_err(SerializeError::Backend)?;
-                // Check for field-level proxy
-                if let Some(proxy_def) = field_item.field.and_then(|f| f.proxy()) {
-                    serialize_via_proxy(serializer, field_value, proxy_def)?;
-                } else {
-                    shared_serialize(serializer, field_value)?;
-                }
+                serialize_field_value(serializer, &field_item, field_value)?;
             }
             serializer.end_struct().map_err(SerializeError::Backend)?;
         }
@@ -455,14 +713,9 @@ where
                                 .field_metadata(&field_item)
                                 .map_err(SerializeError::Backend)?;
                             serializer
-                                .field_key(&field_item.name)
+                                .field_key(field_key_name(serializer, &field_item))
                                 .map_err(SerializeError::Backend)?;
-                            // Check for field-level proxy
-                            if let Some(proxy_def) = field_item.field.and_then(|f| f.proxy()) {
-                                serialize_via_proxy(serializer, field_value, proxy_def)?;
-                            } else {
-                                shared_serialize(serializer, field_value)?;
-                            }
+                            serialize_field_value(serializer, &field_item, field_value)?;
                         }
                     }
                     StructKind::TupleStruct | StructKind::Tuple => {
@@ -501,14 +754,9 @@ where
                                 .field_metadata(&field_item)
                                 .map_err(SerializeError::Backend)?;
                             serializer
-                                .field_key(&field_item.name)
+                                .field_key(field_key_name(serializer, &field_item))
                                 .map_err(SerializeError::Backend)?;
-                            // Check for field-level proxy
-                            if let Some(proxy_def) = field_item.field.and_then(|f| f.proxy()) {
-                                serialize_via_proxy(serializer, field_value, proxy_def)?;
-                            } else {
-                                shared_serialize(serializer, field_value)?;
-                            }
+                            serialize_field_value(serializer, &field_item, field_value)?;
                         }
                         serializer.end_struct().map_err(SerializeError::Backend)?;
                     }
@@ -621,14 +869,9 @@ where
                         .field_metadata(&field_item)
                         .map_err(SerializeError::Backend)?;
                     serializer
-                        .field_key(&field_item.name)
+                        .field_key(field_key_name(serializer, &field_item))
                         .map_err(SerializeError::Backend)?;
-                    // Check for field-level proxy
-                    if let Some(proxy_def) = field_item.field.and_then(|f| f.proxy()) {
-                        serialize_via_proxy(serializer, field_value, proxy_def)?;
-                    } else {
-                        shared_serialize(serializer, field_value)?;
-                    }
+                    serialize_field_value(serializer, &field_item, field_value)?;
                 }
                 serializer.end_struct().map_err(SerializeError::Backend)?;
 
@@ -643,6 +886,111 @@ where
     )))
 }
 
+/// Serialize a dynamic value (like `facet_value::Value`) as the scalar,
+/// sequence or object it holds.
+fn serialize_dynamic_value<S>(
+    serializer: &mut S,
+    dynamic: PeekDynamicValue<'_, '_>,
+) -> Result<(), SerializeError<S::Error>>
+where
+    S: FormatSerializer,
+{
+    let scalar = match dynamic.kind() {
+        DynValueKind::Null => ScalarValue::Null,
+        DynValueKind::Bool => ScalarValue::Bool(dynamic.as_bool().unwrap_or_default()),
+        DynValueKind::Number => {
+            if let Some(n) = dynamic.as_i64() {
+                ScalarValue::I64(n)
+            } else if let Some(n) = dynamic.as_u64() {
+                ScalarValue::U64(n)
+            } else {
+                ScalarValue::F64(dynamic.as_f64().unwrap_or(f64::NAN))
+            }
+        }
+        DynValueKind::String => {
+            ScalarValue::Str(Cow::Borrowed(dynamic.as_str().unwrap_or_default()))
+        }
+        DynValueKind::Bytes => {
+            ScalarValue::Bytes(Cow::Borrowed(dynamic.as_bytes().unwrap_or_default()))
+        }
+        DynValueKind::DateTime => {
+            let datetime = dynamic
+                .as_datetime()
+                .ok_or(SerializeError::Internal(Cow::Borrowed(
+                    "dynamic value reported a datetime but returned none",
+                )))?;
+            ScalarValue::Str(Cow::Owned(format_dyn_datetime(datetime)))
+        }
+        DynValueKind::Array => {
+            let items: alloc::vec::Vec<_> = dynamic.array_iter().into_iter().flatten().collect();
+            serializer
+                .begin_seq_with_len(items.len())
+                .map_err(SerializeError::Backend)?;
+            for item in items {
+                shared_serialize(serializer, item)?;
+            }
+            return serializer.end_seq().map_err(SerializeError::Backend);
+        }
+        DynValueKind::Object => {
+            serializer.begin_struct().map_err(SerializeError::Backend)?;
+            for (key, value) in dynamic.object_iter().into_iter().flatten() {
+                serializer.field_key(key).map_err(SerializeError::Backend)?;
+                shared_serialize(serializer, value)?;
+            }
+            return serializer.end_struct().map_err(SerializeError::Backend);
+        }
+        DynValueKind::QName | DynValueKind::Uuid => {
+            return Err(SerializeError::Unsupported(Cow::Borrowed(
+                "qualified names and UUIDs in dynamic values are not supported",
+            )));
+        }
+    };
+    serializer.scalar(scalar).map_err(SerializeError::Backend)
+}
+
+/// RFC 3339 text for the parts of a dynamic datetime
+fn format_dyn_datetime(
+    (year, month, day, hour, minute, second, nanos, kind): (
+        i32,
+        u8,
+        u8,
+        u8,
+        u8,
+        u8,
+        u32,
+        DynDateTimeKind,
+    ),
+) -> alloc::string::String {
+    use core::fmt::Write as _;
+
+    let mut out = alloc::string::String::new();
+    let date = !matches!(kind, DynDateTimeKind::LocalTime);
+    let time = !matches!(kind, DynDateTimeKind::LocalDate);
+    if date {
+        let _ = write!(out, "{year:04}-{month:02}-{day:02}");
+    }
+    if date && time {
+        out.push('T');
+    }
+    if time {
+        let _ = write!(out, "{hour:02}:{minute:02}:{second:02}");
+        if nanos != 0 {
+            let fraction = alloc::format!("{nanos:09}");
+            let _ = write!(out, ".{}", fraction.trim_end_matches('0'));
+        }
+    }
+    if let DynDateTimeKind::Offset { offset_minutes } = kind {
+        if offset_minutes == 0 {
+            out.push('Z');
+        } else {
+            let sign = if offset_minutes < 0 { '-' } else { '+' };
+            let offset = offset_minutes.unsigned_abs();
+            let _ = write!(out, "{sign}{:02}:{:02}", offset / 60, offset % 60);
+        }
+    }
+    out
+}
+
 fn serialize_numeric_enum<S>(
     serializer: &mut S,
     variant: &'static facet_core::Variant,
@@ -720,14 +1068,9 @@ where
                     .field_metadata(&field_item)
                     .map_err(SerializeError::Backend)?;
                 serializer
-                    .field_key(&field_item.name)
+                    .field_key(field_key_name(serializer, &field_item))
                     .map_err(SerializeError::Backend)?;
-                // Check for field-level proxy
-                if let Some(proxy_def) = field_item.field.and_then(|f| f.proxy()) {
-                    serialize_via_proxy(serializer, field_value, proxy_def)?;
-                } else {
-                    shared_serialize(serializer, field_value)?;
-                }
+                serialize_field_value(serializer, &field_item, field_value)?;
             }
             serializer.end_struct().map_err(SerializeError::Backend)?;
             Ok(())
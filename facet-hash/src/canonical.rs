@@ -0,0 +1,345 @@
+//! The canonical encoding that gets hashed.
+//!
+//! Every value is written as a tag byte followed by its content, so the
+//! encoding is self-delimiting and two values encode the same exactly when
+//! they have the same structure:
+//!
+//! | tag | value | content |
+//! |-----|-------|---------|
+//! | `n` | `()`, `None`, null | nothing |
+//! | `b` | `bool` | one byte, 0 or 1 |
+//! | `i` | integers | `i128`, little-endian |
+//! | `u` | `u128` above `i128::MAX` | `u128`, little-endian |
+//! | `f` | `f32`, `f64` | `f64` bits, little-endian; `-0.0` and NaNs normalized |
+//! | `s` | strings, `char` | length, then UTF-8 |
+//! | `d` | other scalars | length, then their `Display` text |
+//! | `x` | dynamic bytes | length, then the bytes |
+//! | `L` | lists, arrays, tuples | length, then each item |
+//! | `T` | sets | length, then the items, sorted by encoding |
+//! | `M` | maps, structs | length, then key/value pairs, sorted by key encoding |
+//! | `E` | enum variants | name as a string, then `n`, `L` or `M` for the payload |
+//! | `R` | `Result` | 0 for `Ok` or 1 for `Err`, then the value |
+//!
+//! Lengths are `u64`, little-endian. Struct fields are keyed by their
+//! serialized names, so a struct encodes like a map from those names.
+//! `Option`, smart pointers and transparent wrappers encode as what they
+//! hold.
+
+use facet_core::{Def, ScalarType, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek};
+
+use crate::HashError;
+use crate::digest::Sink;
+
+pub(crate) fn encode(peek: Peek<'_, '_>, out: &mut impl Sink) -> Result<(), HashError> {
+    let shape = peek.shape();
+
+    if let Some(scalar) = peek.scalar_type() {
+        return scalar_value(peek, scalar, out);
+    }
+
+    match shape.def {
+        Def::Option(_) => {
+            return match peek.into_option().ok().and_then(|option| option.value()) {
+                Some(inner) => encode(inner, out),
+                None => {
+                    out.write(b"n");
+                    Ok(())
+                }
+            };
+        }
+        Def::Pointer(_) => {
+            let inner = peek
+                .into_pointer()
+                .ok()
+                .and_then(|pointer| pointer.borrow_inner())
+                .ok_or_else(|| HashError::UnreadablePointer {
+                    type_name: shape.to_string(),
+                })?;
+            return encode(inner, out);
+        }
+        Def::Result(_) => {
+            if let Ok(result) = peek.into_result() {
+                if let Some(ok) = result.ok() {
+                    out.write(b"R\0");
+                    return encode(ok, out);
+                }
+                if let Some(err) = result.err() {
+                    out.write(b"R\x01");
+                    return encode(err, out);
+                }
+            }
+        }
+        Def::List(_) | Def::Array(_) | Def::Slice(_) => {
+            if let Ok(list) = peek.into_list_like() {
+                out.write(b"L");
+                len(list.len(), out);
+                for item in list.iter() {
+                    encode(item, out)?;
+                }
+                return Ok(());
+            }
+        }
+        Def::Set(_) => {
+            if let Ok(set) = peek.into_set() {
+                let mut items = set.iter().map(to_vec).collect::<Result<Vec<_>, _>>()?;
+                items.sort_unstable();
+                out.write(b"T");
+                len(items.len(), out);
+                for item in &items {
+                    out.write(item);
+                }
+                return Ok(());
+            }
+        }
+        Def::Map(_) => {
+            if let Ok(map) = peek.into_map() {
+                let entries = map
+                    .iter()
+                    .map(|(key, value)| Ok((to_vec(key)?, to_vec(value)?)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                return sorted_entries(entries, out);
+            }
+        }
+        Def::DynamicValue(_) => return dynamic_value(peek, out),
+        _ => {}
+    }
+
+    if shape.inner.is_some() {
+        let inner = peek.innermost_peek();
+        if inner.shape() != shape {
+            return encode(inner, out);
+        }
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(struct_type)) => {
+            if let Ok(struct_) = peek.into_struct() {
+                return payload(struct_type.kind, &struct_, out);
+            }
+        }
+        Type::User(UserType::Enum(_)) => {
+            if let Ok(enum_) = peek.into_enum()
+                && let Ok(variant) = enum_.active_variant()
+            {
+                out.write(b"E");
+                string(variant.name, out);
+                return payload(variant.data.kind, &enum_, out);
+            }
+        }
+        _ => {}
+    }
+
+    if shape.is_display() {
+        out.write(b"d");
+        string(&peek.to_string(), out);
+        return Ok(());
+    }
+
+    Err(HashError::Unsupported {
+        type_name: shape.to_string(),
+    })
+}
+
+/// The fields of a struct or enum variant
+fn payload<'mem, 'facet>(
+    kind: StructKind,
+    fields: &impl HasFields<'mem, 'facet>,
+    out: &mut impl Sink,
+) -> Result<(), HashError> {
+    match kind {
+        StructKind::Unit => {
+            out.write(b"n");
+            Ok(())
+        }
+        StructKind::Tuple | StructKind::TupleStruct => {
+            let items = fields
+                .fields_for_serialize()
+                .map(|(_, value)| to_vec(value))
+                .collect::<Result<Vec<_>, _>>()?;
+            out.write(b"L");
+            len(items.len(), out);
+            for item in &items {
+                out.write(item);
+            }
+            Ok(())
+        }
+        StructKind::Struct => {
+            let entries = fields
+                .fields_for_serialize()
+                .map(|(item, value)| {
+                    let mut key = Vec::new();
+                    key.push(b's');
+                    string(&item.name, &mut key);
+                    Ok((key, to_vec(value)?))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            sorted_entries(entries, out)
+        }
+    }
+}
+
+fn sorted_entries(
+    mut entries: Vec<(Vec<u8>, Vec<u8>)>,
+    out: &mut impl Sink,
+) -> Result<(), HashError> {
+    entries.sort_unstable();
+    out.write(b"M");
+    len(entries.len(), out);
+    for (key, value) in &entries {
+        out.write(key);
+        out.write(value);
+    }
+    Ok(())
+}
+
+fn to_vec(peek: Peek<'_, '_>) -> Result<Vec<u8>, HashError> {
+    let mut out = Vec::new();
+    encode(peek, &mut out)?;
+    Ok(out)
+}
+
+fn scalar_value(
+    peek: Peek<'_, '_>,
+    scalar: ScalarType,
+    out: &mut impl Sink,
+) -> Result<(), HashError> {
+    macro_rules! int {
+        ($ty:ty) => {
+            peek.get::<$ty>().ok().map(|&n| integer(n as i128, out))
+        };
+    }
+
+    let done = match scalar {
+        ScalarType::Unit => {
+            out.write(b"n");
+            Some(())
+        }
+        ScalarType::Bool => peek
+            .get::<bool>()
+            .ok()
+            .map(|&b| out.write(&[b'b', b as u8])),
+        ScalarType::Char => peek.get::<char>().ok().map(|&c| {
+            out.write(b"s");
+            string(c.encode_utf8(&mut [0; 4]), out);
+        }),
+        ScalarType::F32 => peek.get::<f32>().ok().map(|&n| float(f64::from(n), out)),
+        ScalarType::F64 => peek.get::<f64>().ok().map(|&n| float(n, out)),
+        ScalarType::U8 => int!(u8),
+        ScalarType::U16 => int!(u16),
+        ScalarType::U32 => int!(u32),
+        ScalarType::U64 => int!(u64),
+        ScalarType::USize => int!(usize),
+        ScalarType::I8 => int!(i8),
+        ScalarType::I16 => int!(i16),
+        ScalarType::I32 => int!(i32),
+        ScalarType::I64 => int!(i64),
+        ScalarType::I128 => int!(i128),
+        ScalarType::ISize => int!(isize),
+        ScalarType::U128 => peek.get::<u128>().ok().map(|&n| match i128::try_from(n) {
+            Ok(n) => integer(n, out),
+            Err(_) => {
+                out.write(b"u");
+                out.write(&n.to_le_bytes());
+            }
+        }),
+        _ => peek.as_str().map(|s| {
+            out.write(b"s");
+            string(s, out);
+        }),
+    };
+    if done.is_some() {
+        return Ok(());
+    }
+
+    if peek.shape().is_display() {
+        out.write(b"d");
+        string(&peek.to_string(), out);
+        return Ok(());
+    }
+    Err(HashError::Unsupported {
+        type_name: peek.shape().to_string(),
+    })
+}
+
+fn dynamic_value(peek: Peek<'_, '_>, out: &mut impl Sink) -> Result<(), HashError> {
+    use facet_core::DynValueKind;
+
+    let unsupported = || HashError::Unsupported {
+        type_name: peek.shape().to_string(),
+    };
+    let value = peek.into_dynamic_value().map_err(|_| unsupported())?;
+    match value.kind() {
+        DynValueKind::Null => out.write(b"n"),
+        DynValueKind::Bool => out.write(&[b'b', value.as_bool().ok_or_else(unsupported)? as u8]),
+        DynValueKind::Number => {
+            if let Some(n) = value.as_i64() {
+                integer(i128::from(n), out);
+            } else if let Some(n) = value.as_u64() {
+                integer(i128::from(n), out);
+            } else {
+                float(value.as_f64().ok_or_else(unsupported)?, out);
+            }
+        }
+        DynValueKind::String => {
+            out.write(b"s");
+            string(value.as_str().ok_or_else(unsupported)?, out);
+        }
+        DynValueKind::Bytes => {
+            let bytes = value.as_bytes().ok_or_else(unsupported)?;
+            out.write(b"x");
+            len(bytes.len(), out);
+            out.write(bytes);
+        }
+        DynValueKind::Array => {
+            let items = value.array_iter().ok_or_else(unsupported)?;
+            out.write(b"L");
+            len(value.array_len().unwrap_or(0), out);
+            for item in items {
+                encode(item, out)?;
+            }
+        }
+        DynValueKind::Object => {
+            let entries = value
+                .object_iter()
+                .ok_or_else(unsupported)?
+                .map(|(key, value)| {
+                    let mut encoded = Vec::new();
+                    encoded.push(b's');
+                    string(key, &mut encoded);
+                    Ok((encoded, to_vec(value)?))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            sorted_entries(entries, out)?;
+        }
+        _ => return Err(unsupported()),
+    }
+    Ok(())
+}
+
+fn integer(n: i128, out: &mut impl Sink) {
+    out.write(b"i");
+    out.write(&n.to_le_bytes());
+}
+
+fn float(n: f64, out: &mut impl Sink) {
+    let n = if n.is_nan() {
+        f64::NAN
+    } else if n == 0.0 {
+        0.0
+    } else {
+        n
+    };
+    out.write(b"f");
+    out.write(&n.to_bits().to_le_bytes());
+}
+
+/// Length-prefixed UTF-8, without a tag
+fn string(s: &str, out: &mut impl Sink) {
+    len(s.len(), out);
+    out.write(s.as_bytes());
+}
+
+fn len(n: usize, out: &mut impl Sink) {
+    out.write(&(n as u64).to_le_bytes());
+}
@@ -0,0 +1,101 @@
+//! Hash algorithms and their output.
+
+use core::fmt;
+
+use sha2::Digest as _;
+
+/// The hash function applied to a value's canonical encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum HashAlgorithm {
+    /// 64-bit FNV-1a: fast, but not collision resistant. Fine for cache keys
+    /// over trusted data.
+    Fnv1a64,
+    /// SHA-256: slower, but safe for dedup keys over untrusted data.
+    Sha256,
+}
+
+/// The result of [`stable_hash`](crate::stable_hash).
+///
+/// Displays as lowercase hex, so it can be used directly as a key in a
+/// cache or a file name.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Digest {
+    algorithm: HashAlgorithm,
+    bytes: Vec<u8>,
+}
+
+impl Digest {
+    /// The algorithm that produced this digest.
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// The raw digest: 8 bytes for FNV-1a (big-endian), 32 for SHA-256.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.bytes {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Digest({:?}, {self})", self.algorithm)
+    }
+}
+
+/// Somewhere to write the canonical encoding
+pub(crate) trait Sink {
+    fn write(&mut self, bytes: &[u8]);
+}
+
+impl Sink for Vec<u8> {
+    fn write(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+/// A hash function in progress
+pub(crate) enum Hasher {
+    Fnv1a64(facet_core::Fnv1a64),
+    Sha256(sha2::Sha256),
+}
+
+impl Hasher {
+    pub(crate) fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Fnv1a64 => Hasher::Fnv1a64(facet_core::Fnv1a64::new()),
+            HashAlgorithm::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
+        }
+    }
+
+    pub(crate) fn finish(self) -> Digest {
+        match self {
+            Hasher::Fnv1a64(state) => Digest {
+                algorithm: HashAlgorithm::Fnv1a64,
+                bytes: state.finish().to_be_bytes().to_vec(),
+            },
+            Hasher::Sha256(hasher) => Digest {
+                algorithm: HashAlgorithm::Sha256,
+                bytes: hasher.finalize().to_vec(),
+            },
+        }
+    }
+}
+
+impl Sink for Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        match self {
+            Hasher::Fnv1a64(state) => state.write(bytes),
+            Hasher::Sha256(hasher) => hasher.update(bytes),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Hashing error types.
+
+use core::fmt;
+
+/// Error returned when a value has no canonical form to hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashError {
+    /// The type is opaque to reflection and has no `Display` impl, so there
+    /// is nothing stable to hash, e.g. a function pointer.
+    Unsupported {
+        /// The type.
+        type_name: String,
+    },
+    /// A pointer that can't be followed, e.g. a `Weak` or a raw pointer.
+    UnreadablePointer {
+        /// The pointer type.
+        type_name: String,
+    },
+}
+
+impl fmt::Display for HashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashError::Unsupported { type_name } => {
+                write!(f, "`{type_name}` has no stable form to hash")
+            }
+            HashError::UnreadablePointer { type_name } => {
+                write!(
+                    f,
+                    "can't follow the `{type_name}` pointer to hash its target"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for HashError {}
@@ -0,0 +1,74 @@
+//! Stable, content-addressed hashing of `Facet` values.
+//!
+//! [`stable_hash`] hashes what a value *is* — its field names and values —
+//! rather than how it sits in memory, so the result can be stored and
+//! compared across builds, platforms and processes. Use it for dedup keys,
+//! cache keys, or to tell whether a config changed since last time.
+//!
+//! The hash ignores:
+//!
+//! - field declaration order and struct layout,
+//! - the order of map and set entries, so `HashMap`s hash the same every run,
+//! - integer widths, so `5u8` and `5i64` hash the same,
+//! - wrappers: `Option` (`Some`), `Box`, `Arc` and transparent newtypes hash
+//!   as what they hold.
+//!
+//! A struct hashes like a map from its serialized field names, so `rename`
+//! applies, skipped fields are left out, flattened fields merge into the
+//! parent, and a struct hashes the same as the equivalent
+//! `facet_value::Value` object. Enum variants are hashed by name.
+//!
+//! Unlike `Peek::structural_hash`, which feeds a `Hasher` for in-process
+//! use, this never looks at type ids or `Hash` impls. The encoding is part
+//! of this crate's stability guarantee: changing it is a breaking change.
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use facet::Facet;
+//! use facet_hash::{HashAlgorithm, stable_hash};
+//! use facet_reflect::Peek;
+//!
+//! #[derive(Facet)]
+//! struct Job {
+//!     image: String,
+//!     env: HashMap<String, String>,
+//! }
+//!
+//! let job = Job {
+//!     image: "app:1.2".into(),
+//!     env: HashMap::from([("A".into(), "1".into()), ("B".into(), "2".into())]),
+//! };
+//! let key = stable_hash(Peek::new(&job), HashAlgorithm::Sha256).unwrap();
+//! assert_eq!(key.as_bytes().len(), 32);
+//!
+//! // Same contents, different insertion order: same key
+//! let same = Job {
+//!     image: "app:1.2".into(),
+//!     env: HashMap::from([("B".into(), "2".into()), ("A".into(), "1".into())]),
+//! };
+//! assert_eq!(stable_hash(Peek::new(&same), HashAlgorithm::Sha256).unwrap(), key);
+//! ```
+
+#![forbid(unsafe_code)]
+
+mod canonical;
+mod digest;
+mod error;
+
+pub use digest::{Digest, HashAlgorithm};
+pub use error::HashError;
+
+use facet_reflect::Peek;
+
+/// Hash the canonical structure of `peek` with `algorithm`.
+///
+/// Fails if the value contains something with no stable form, like a
+/// function pointer or a `Weak`.
+pub fn stable_hash(peek: Peek<'_, '_>, algorithm: HashAlgorithm) -> Result<Digest, HashError> {
+    let mut hasher = digest::Hasher::new(algorithm);
+    canonical::encode(peek, &mut hasher)?;
+    Ok(hasher.finish())
+}
@@ -0,0 +1,156 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_hash::{Digest, HashAlgorithm, HashError, stable_hash};
+use facet_reflect::Peek;
+
+fn sha<'a, T: Facet<'a>>(value: &T) -> Digest {
+    stable_hash(Peek::new(value), HashAlgorithm::Sha256).unwrap()
+}
+
+fn fnv<'a, T: Facet<'a>>(value: &T) -> Digest {
+    stable_hash(Peek::new(value), HashAlgorithm::Fnv1a64).unwrap()
+}
+
+#[derive(Facet)]
+struct Server {
+    host: String,
+    port: u16,
+    tags: Vec<String>,
+}
+
+#[derive(Facet)]
+struct ServerReordered {
+    tags: Vec<String>,
+    port: u64,
+    host: Box<str>,
+}
+
+#[test]
+fn test_known_values() {
+    #[derive(Facet)]
+    struct Status {
+        port: u16,
+        up: bool,
+    }
+
+    // The encoding is a compatibility promise: these must never change
+    assert_eq!(fnv(&()).to_string(), "af63e34c8601f871");
+    assert_eq!(
+        sha(&true).to_string(),
+        "0ffaac099f9b22205f96928a311ab077b5b9a4fdecc11660663ddf04b19493d5"
+    );
+    assert_eq!(
+        sha(&Status {
+            port: 8080,
+            up: true
+        })
+        .to_string(),
+        "014f84cb6b221996070000a93abb1d3e176916802ced919538a4a3265204a74b"
+    );
+    assert_eq!(fnv(&()).as_bytes().len(), 8);
+    assert_eq!(fnv(&()).algorithm(), HashAlgorithm::Fnv1a64);
+}
+
+#[test]
+fn test_layout_independence() {
+    let a = Server {
+        host: "db".into(),
+        port: 5432,
+        tags: vec!["primary".into()],
+    };
+    let b = ServerReordered {
+        tags: vec!["primary".into()],
+        port: 5432,
+        host: "db".into(),
+    };
+    assert_eq!(sha(&a), sha(&b));
+
+    // A struct hashes like the map of its fields
+    let map = facet_value::value!({
+        "host": "db",
+        "port": 5432,
+        "tags": ["primary"],
+    });
+    assert_eq!(sha(&a), sha(&map));
+
+    // Wrappers hash as what they hold
+    assert_eq!(sha(&Some(Arc::new(5u8))), sha(&5i64));
+    assert_eq!(sha(&None::<u32>), sha(&()));
+
+    // But values still matter
+    let c = Server { port: 5433, ..a };
+    assert_ne!(sha(&c), sha(&b));
+}
+
+#[test]
+fn test_order_independence() {
+    let mut forward = HashMap::new();
+    let mut backward = HashMap::new();
+    for i in 0..100u32 {
+        forward.insert(i, i * 2);
+        backward.insert(99 - i, (99 - i) * 2);
+    }
+    assert_eq!(fnv(&forward), fnv(&backward));
+    assert_eq!(
+        fnv(&forward),
+        fnv(&forward
+            .iter()
+            .map(|(k, v)| (*k, *v))
+            .collect::<BTreeMap<_, _>>())
+    );
+
+    let set: HashSet<&str> = ["a", "b", "c"].into();
+    let ordered: BTreeSet<&str> = ["c", "b", "a"].into();
+    assert_eq!(fnv(&set), fnv(&ordered));
+
+    // Lists keep their order
+    assert_ne!(fnv(&vec![1, 2]), fnv(&vec![2, 1]));
+}
+
+#[test]
+fn test_distinguishes_structure() {
+    #[derive(Facet, PartialEq)]
+    #[repr(u8)]
+    enum Geometry {
+        Point,
+        Circle { radius: u32 },
+        Rect(u32, u32),
+    }
+
+    // Variants and their payloads hash equal exactly when they compare equal
+    let shapes = [
+        Geometry::Point,
+        Geometry::Circle { radius: 1 },
+        Geometry::Circle { radius: 2 },
+        Geometry::Rect(1, 2),
+        Geometry::Rect(2, 1),
+    ];
+    for a in &shapes {
+        for b in &shapes {
+            assert_eq!(a == b, sha(a) == sha(b));
+        }
+    }
+    assert_ne!(sha(&Geometry::Point), sha(&"Point"));
+    assert_ne!(sha(&"1"), sha(&1));
+    assert_ne!(sha(&1.0f64), sha(&1));
+    assert_eq!(sha(&1.5f32), sha(&1.5f64));
+    assert_eq!(sha(&-0.0f64), sha(&0.0f64));
+    assert_ne!(sha(&vec!["ab", "c"]), sha(&vec!["a", "bc"]));
+    assert_ne!(sha(&u128::MAX), sha(&-1i128));
+}
+
+#[test]
+fn test_unsupported() {
+    struct Handle;
+
+    #[derive(Facet)]
+    struct Connection {
+        #[facet(opaque)]
+        handle: Handle,
+    }
+    let connection = Connection { handle: Handle };
+    let err = stable_hash(Peek::new(&connection), HashAlgorithm::Sha256).unwrap_err();
+    assert!(matches!(err, HashError::Unsupported { .. }), "{err}");
+}
@@ -0,0 +1,118 @@
+//! HCL parsing error types.
+
+use alloc::string::String;
+use core::fmt;
+
+/// Error type for HCL parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HclError {
+    kind: HclErrorKind,
+    /// Source span of the error, if available.
+    span: Option<facet_reflect::Span>,
+}
+
+impl HclError {
+    /// Create a new error with the given kind.
+    pub fn new(kind: HclErrorKind) -> Self {
+        Self { kind, span: None }
+    }
+
+    /// Create a new error with the given kind and span.
+    pub fn with_span(kind: HclErrorKind, span: facet_reflect::Span) -> Self {
+        Self {
+            kind,
+            span: Some(span),
+        }
+    }
+
+    /// Get the error kind.
+    pub fn kind(&self) -> &HclErrorKind {
+        &self.kind
+    }
+
+    /// Get the source span of the error, if known.
+    pub fn span(&self) -> Option<facet_reflect::Span> {
+        self.span
+    }
+}
+
+impl fmt::Display for HclError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            HclErrorKind::UnexpectedEof { expected } => {
+                write!(f, "unexpected end of input, expected {expected}")
+            }
+            HclErrorKind::UnexpectedToken { found, expected } => {
+                write!(f, "unexpected {found}, expected {expected}")
+            }
+            HclErrorKind::InvalidNumber { text } => write!(f, "invalid number `{text}`"),
+            HclErrorKind::InvalidEscape { text } => write!(f, "invalid escape `{text}`"),
+            HclErrorKind::UnterminatedString => write!(f, "unterminated string"),
+            HclErrorKind::Unsupported { what } => write!(f, "{what} is not supported"),
+            HclErrorKind::DuplicateBlock { name } => {
+                write!(f, "block `{name}` appears more than once")
+            }
+            HclErrorKind::MixedLabels { name } => {
+                write!(f, "blocks `{name}` have different numbers of labels")
+            }
+            HclErrorKind::InvalidUtf8 { message } => write!(f, "invalid UTF-8: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for HclError {}
+
+/// Specific kinds of HCL errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HclErrorKind {
+    /// Unexpected end of input.
+    UnexpectedEof {
+        /// What was expected at this point.
+        expected: &'static str,
+    },
+    /// A token that doesn't fit here.
+    UnexpectedToken {
+        /// Description of the token that was found.
+        found: String,
+        /// What was expected at this point.
+        expected: &'static str,
+    },
+    /// A number literal that can't be parsed.
+    InvalidNumber {
+        /// The literal.
+        text: String,
+    },
+    /// An unknown escape sequence in a string.
+    InvalidEscape {
+        /// The escape sequence.
+        text: String,
+    },
+    /// A string without its closing quote.
+    UnterminatedString,
+    /// An HCL feature this crate doesn't evaluate, e.g. variable references.
+    Unsupported {
+        /// The feature.
+        what: &'static str,
+    },
+    /// An unlabeled block repeated where the target holds a single value.
+    DuplicateBlock {
+        /// The block type.
+        name: String,
+    },
+    /// Blocks of the same type with different numbers of labels.
+    MixedLabels {
+        /// The block type.
+        name: String,
+    },
+    /// Invalid UTF-8 in input.
+    InvalidUtf8 {
+        /// The UTF-8 error details.
+        message: String,
+    },
+}
+
+impl From<HclErrorKind> for HclError {
+    fn from(kind: HclErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
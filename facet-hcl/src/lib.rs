@@ -0,0 +1,144 @@
+//! HCL parser and serializer using facet-format.
+//!
+//! This crate reads and writes the configuration subset of [HCL], the syntax
+//! of Terraform and friends, for any type implementing `Facet`.
+//!
+//! # Mapping to Rust Types
+//!
+//! - Attributes (`port = 8080`) are fields holding values: numbers, strings,
+//!   booleans, `null`, tuples (`[1, 2]`) and objects (`{ a = 1 }`).
+//! - Blocks (`server { ... }`) are fields holding structs.
+//! - Repeated blocks (`listener { ... }` twice) are fields holding a `Vec` of
+//!   structs.
+//! - Labeled blocks (`service "web" { ... }`) are fields holding a map of
+//!   structs, keyed by the label. More labels nest more maps.
+//! - Enums are externally tagged, so `mode "Limited" { rate = 10 }` and
+//!   `mode = "Off"` both fit `enum Mode { Off, Limited { rate: u32 } }`.
+//!
+//! Expressions beyond literals (variable references, function calls, template
+//! interpolation) are rejected rather than evaluated. `#`, `//` and `/* */`
+//! comments and `<<EOF` heredocs are supported.
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//!
+//! use facet::Facet;
+//! use facet_hcl::from_str;
+//!
+//! #[derive(Facet, Debug)]
+//! struct Config {
+//!     region: String,
+//!     service: BTreeMap<String, Service>,
+//! }
+//!
+//! #[derive(Facet, Debug)]
+//! struct Service {
+//!     image: String,
+//!     ports: Vec<u16>,
+//! }
+//!
+//! let hcl = r#"
+//! region = "eu-west-1"
+//!
+//! service "web" {
+//!   image = "nginx:1.27"
+//!   ports = [80, 443]
+//! }
+//!
+//! service "worker" {
+//!   image = "app:latest"
+//!   ports = []
+//! }
+//! "#;
+//!
+//! let config: Config = from_str(hcl).unwrap();
+//! assert_eq!(config.service["web"].ports, [80, 443]);
+//! assert_eq!(config.service["worker"].image, "app:latest");
+//! ```
+//!
+//! [HCL]: https://github.com/hashicorp/hcl
+
+#![forbid(unsafe_code)]
+
+extern crate alloc;
+
+use alloc::string::ToString;
+
+mod error;
+mod parser;
+mod serializer;
+
+pub use error::{HclError, HclErrorKind};
+pub use parser::{HclParser, HclProbe};
+pub use serializer::{HclSerializeError, HclSerializer, peek_to_string, to_string, to_vec};
+
+// Re-export DeserializeError for convenience
+pub use facet_format::DeserializeError;
+
+/// Deserialize a value from an HCL string into an owned type.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_hcl::from_str;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Config {
+///     name: String,
+///     listener: Vec<Listener>,
+/// }
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Listener {
+///     port: u16,
+/// }
+///
+/// let config: Config = from_str("name = \"api\"\nlistener {\n  port = 80\n}\n").unwrap();
+/// assert_eq!(config.listener, [Listener { port: 80 }]);
+/// ```
+pub fn from_str<T>(input: &str) -> Result<T, DeserializeError<HclError>>
+where
+    T: facet_core::Facet<'static>,
+{
+    use facet_format::FormatDeserializer;
+    let parser = HclParser::with_shape(input, T::SHAPE);
+    let mut de = FormatDeserializer::new_owned(parser);
+    de.deserialize()
+}
+
+/// Deserialize a value from an HCL string, allowing zero-copy borrowing.
+///
+/// This variant requires the input to outlive the result (`'input: 'facet`),
+/// enabling zero-copy deserialization of string values.
+pub fn from_str_borrowed<'input, 'facet, T>(
+    input: &'input str,
+) -> Result<T, DeserializeError<HclError>>
+where
+    T: facet_core::Facet<'facet>,
+    'input: 'facet,
+{
+    use facet_format::FormatDeserializer;
+    let parser = HclParser::with_shape(input, T::SHAPE);
+    let mut de = FormatDeserializer::new(parser);
+    de.deserialize()
+}
+
+/// Deserialize a value from HCL bytes into an owned type.
+///
+/// # Errors
+///
+/// Returns an error if the input is not valid UTF-8 or if deserialization fails.
+pub fn from_slice<T>(input: &[u8]) -> Result<T, DeserializeError<HclError>>
+where
+    T: facet_core::Facet<'static>,
+{
+    let s = core::str::from_utf8(input).map_err(|e| {
+        DeserializeError::Parser(HclError::new(HclErrorKind::InvalidUtf8 {
+            message: e.to_string(),
+        }))
+    })?;
+    from_str(s)
+}
@@ -0,0 +1,878 @@
+//! HCL parser implementation using FormatParser trait.
+//!
+//! The input is parsed into a small syntax tree first, then flattened into
+//! events:
+//!
+//! - The document and every block body → StructStart(Object) ... StructEnd
+//! - Attributes → FieldKey + value
+//! - Unlabeled blocks → FieldKey(type) + body, or a sequence of bodies when
+//!   the block is repeated or its field holds a list
+//! - Labeled blocks → FieldKey(type) + one nested object per label, so
+//!   `service "web" { ... }` reads like `service = { web = { ... } }`
+//!
+//! Whether a lone block is a struct or the single item of a list can't be
+//! told from the syntax, so the parser follows the target shape when it has
+//! one (see [`HclParser::with_shape`]).
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use facet_core::{Def, Field, Shape, Type, UserType};
+use facet_format::{
+    ContainerKind, FieldEvidence, FieldKey, FieldLocationHint, FormatParser, ParseEvent,
+    ProbeStream, ScalarValue,
+};
+use facet_reflect::Span;
+
+use crate::error::{HclError, HclErrorKind};
+
+/// HCL parser that converts HCL documents to FormatParser events.
+pub struct HclParser<'de> {
+    events: Vec<ParseEvent<'de>>,
+    /// Source spans for each event (parallel to events vec).
+    spans: Vec<Span>,
+    idx: usize,
+    pending_error: Option<HclError>,
+}
+
+impl<'de> HclParser<'de> {
+    /// Create a new HCL parser from input string.
+    ///
+    /// Without a target shape, a block is read as a list only when it is repeated.
+    pub fn new(input: &'de str) -> Self {
+        Self::build(input, Target::Unknown)
+    }
+
+    /// Create a new HCL parser that reads blocks the way `shape` expects them,
+    /// e.g. a single block into a `Vec` field as a list of one.
+    pub fn with_shape(input: &'de str, shape: &'static Shape) -> Self {
+        Self::build(input, Target::Shape(shape))
+    }
+
+    fn build(input: &'de str, target: Target) -> Self {
+        let result = Syntax::new(input).document().and_then(|body| {
+            let mut emitter = Emitter::default();
+            emitter.body(&body, target, Span::new(0, input.len()))?;
+            Ok(emitter)
+        });
+        match result {
+            Ok(emitter) => Self {
+                events: emitter.events,
+                spans: emitter.spans,
+                idx: 0,
+                pending_error: None,
+            },
+            Err(err) => Self {
+                events: Vec::new(),
+                spans: Vec::new(),
+                idx: 0,
+                pending_error: Some(err),
+            },
+        }
+    }
+
+    /// Build field evidence by looking ahead at remaining events.
+    fn build_probe(&self) -> Vec<FieldEvidence<'de>> {
+        let mut evidence = Vec::new();
+        if !matches!(self.events.get(self.idx), Some(ParseEvent::StructStart(_))) {
+            return evidence;
+        }
+
+        let mut depth = 0usize;
+        for (i, event) in self.events.iter().enumerate().skip(self.idx + 1) {
+            match event {
+                ParseEvent::StructStart(_) | ParseEvent::SequenceStart(_) => depth += 1,
+                ParseEvent::StructEnd | ParseEvent::SequenceEnd => {
+                    if depth == 0 {
+                        break;
+                    }
+                    depth -= 1;
+                }
+                ParseEvent::FieldKey(key) if depth == 0 => {
+                    evidence.push(match self.events.get(i + 1) {
+                        Some(ParseEvent::Scalar(value)) => FieldEvidence::with_scalar_value(
+                            key.name.clone(),
+                            key.location,
+                            None,
+                            value.clone(),
+                            None,
+                        ),
+                        _ => FieldEvidence::new(key.name.clone(), key.location, None, None),
+                    });
+                }
+                _ => {}
+            }
+        }
+        evidence
+    }
+}
+
+impl<'de> FormatParser<'de> for HclParser<'de> {
+    type Error = HclError;
+    type Probe<'a>
+        = HclProbe<'de>
+    where
+        Self: 'a;
+
+    fn next_event(&mut self) -> Result<Option<ParseEvent<'de>>, Self::Error> {
+        if let Some(err) = &self.pending_error {
+            return Err(err.clone());
+        }
+        let event = self.events.get(self.idx).cloned();
+        if event.is_some() {
+            self.idx += 1;
+        }
+        Ok(event)
+    }
+
+    fn peek_event(&mut self) -> Result<Option<ParseEvent<'de>>, Self::Error> {
+        if let Some(err) = &self.pending_error {
+            return Err(err.clone());
+        }
+        Ok(self.events.get(self.idx).cloned())
+    }
+
+    fn skip_value(&mut self) -> Result<(), Self::Error> {
+        let mut depth = 0usize;
+        loop {
+            let event = self
+                .next_event()?
+                .ok_or(HclError::new(HclErrorKind::UnexpectedEof {
+                    expected: "value",
+                }))?;
+            match event {
+                ParseEvent::StructStart(_) | ParseEvent::SequenceStart(_) => depth += 1,
+                ParseEvent::StructEnd | ParseEvent::SequenceEnd => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            if depth == 0 && !matches!(event, ParseEvent::FieldKey(_)) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn begin_probe(&mut self) -> Result<Self::Probe<'_>, Self::Error> {
+        let evidence = self.build_probe();
+        Ok(HclProbe { evidence, idx: 0 })
+    }
+
+    fn current_span(&self) -> Option<Span> {
+        // The span of the most recently consumed event
+        self.idx
+            .checked_sub(1)
+            .and_then(|i| self.spans.get(i).copied())
+    }
+}
+
+/// Field evidence for untagged-enum resolution, collected from buffered events.
+pub struct HclProbe<'de> {
+    evidence: Vec<FieldEvidence<'de>>,
+    idx: usize,
+}
+
+impl<'de> ProbeStream<'de> for HclProbe<'de> {
+    type Error = HclError;
+
+    fn next(&mut self) -> Result<Option<FieldEvidence<'de>>, Self::Error> {
+        let evidence = self.evidence.get(self.idx).cloned();
+        self.idx += 1;
+        Ok(evidence)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Syntax tree
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct Body<'de> {
+    items: Vec<Item<'de>>,
+}
+
+enum Item<'de> {
+    Attribute {
+        key: &'de str,
+        span: Span,
+        value: Expr<'de>,
+    },
+    Block(Block<'de>),
+}
+
+struct Block<'de> {
+    ty: &'de str,
+    labels: Vec<Cow<'de, str>>,
+    body: Body<'de>,
+    span: Span,
+}
+
+enum Expr<'de> {
+    Scalar(ScalarValue<'de>, Span),
+    Tuple(Vec<Expr<'de>>, Span),
+    Object(Vec<(Cow<'de, str>, Expr<'de>)>, Span),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'de> {
+    Ident(&'de str),
+    Str(Cow<'de, str>),
+    Number(ScalarValue<'de>),
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Equals,
+    Colon,
+    Comma,
+}
+
+impl Token<'_> {
+    fn describe(&self) -> String {
+        match self {
+            Token::Ident(name) => alloc::format!("identifier `{name}`"),
+            Token::Str(_) => "string".into(),
+            Token::Number(_) => "number".into(),
+            Token::LBrace => "`{`".into(),
+            Token::RBrace => "`}`".into(),
+            Token::LBracket => "`[`".into(),
+            Token::RBracket => "`]`".into(),
+            Token::Equals => "`=`".into(),
+            Token::Colon => "`:`".into(),
+            Token::Comma => "`,`".into(),
+        }
+    }
+}
+
+/// Tokenizer and recursive-descent parser for the supported HCL subset
+struct Syntax<'de> {
+    input: &'de str,
+    pos: usize,
+    peeked: Option<(Token<'de>, Span)>,
+}
+
+impl<'de> Syntax<'de> {
+    fn new(input: &'de str) -> Self {
+        Self {
+            input,
+            pos: 0,
+            peeked: None,
+        }
+    }
+
+    fn document(mut self) -> Result<Body<'de>, HclError> {
+        let body = self.body()?;
+        match self.next()? {
+            None => Ok(body),
+            Some((token, span)) => Err(unexpected(&token, span, "attribute or block")),
+        }
+    }
+
+    /// Attributes and blocks, up to a closing brace or the end of input
+    fn body(&mut self) -> Result<Body<'de>, HclError> {
+        let mut items = Vec::new();
+        while let Some((Token::Ident(name), span)) = self.peek()? {
+            self.next()?;
+            if let Some((Token::Equals, _)) = self.peek()? {
+                self.next()?;
+                let value = self.expr()?;
+                items.push(Item::Attribute {
+                    key: name,
+                    span,
+                    value,
+                });
+                continue;
+            }
+
+            let mut labels = Vec::new();
+            loop {
+                match self.expect("block label or `{`")? {
+                    (Token::Str(label), _) => labels.push(label),
+                    (Token::Ident(label), _) => labels.push(Cow::Borrowed(label)),
+                    (Token::LBrace, _) => break,
+                    (token, span) => return Err(unexpected(&token, span, "block label or `{`")),
+                }
+            }
+            let body = self.body()?;
+            match self.expect("`}`")? {
+                (Token::RBrace, end) => items.push(Item::Block(Block {
+                    ty: name,
+                    labels,
+                    body,
+                    span: join(span, end),
+                })),
+                (token, span) => return Err(unexpected(&token, span, "`}`")),
+            }
+        }
+        Ok(Body { items })
+    }
+
+    fn expr(&mut self) -> Result<Expr<'de>, HclError> {
+        let (token, span) = self.expect("value")?;
+        Ok(match token {
+            Token::Str(s) => Expr::Scalar(ScalarValue::Str(s), span),
+            Token::Number(n) => Expr::Scalar(n, span),
+            Token::Ident("true") => Expr::Scalar(ScalarValue::Bool(true), span),
+            Token::Ident("false") => Expr::Scalar(ScalarValue::Bool(false), span),
+            Token::Ident("null") => Expr::Scalar(ScalarValue::Null, span),
+            Token::Ident(_) => {
+                return Err(HclError::with_span(
+                    HclErrorKind::Unsupported {
+                        what: "a variable reference or function call",
+                    },
+                    span,
+                ));
+            }
+            Token::LBracket => {
+                let mut items = Vec::new();
+                loop {
+                    if let Some((Token::RBracket, end)) = self.peek()? {
+                        self.next()?;
+                        return Ok(Expr::Tuple(items, join(span, end)));
+                    }
+                    items.push(self.expr()?);
+                    match self.expect("`,` or `]`")? {
+                        (Token::Comma, _) => {}
+                        (Token::RBracket, end) => return Ok(Expr::Tuple(items, join(span, end))),
+                        (token, span) => return Err(unexpected(&token, span, "`,` or `]`")),
+                    }
+                }
+            }
+            Token::LBrace => {
+                let mut entries = Vec::new();
+                loop {
+                    let key = match self.expect("object key or `}`")? {
+                        (Token::RBrace, end) => return Ok(Expr::Object(entries, join(span, end))),
+                        (Token::Ident(key), _) => Cow::Borrowed(key),
+                        (Token::Str(key), _) => key,
+                        (token, span) => return Err(unexpected(&token, span, "object key or `}`")),
+                    };
+                    match self.expect("`=` or `:`")? {
+                        (Token::Equals | Token::Colon, _) => {}
+                        (token, span) => return Err(unexpected(&token, span, "`=` or `:`")),
+                    }
+                    entries.push((key, self.expr()?));
+                    // Entries are separated by commas or newlines
+                    if let Some((Token::Comma, _)) = self.peek()? {
+                        self.next()?;
+                    }
+                }
+            }
+            token => return Err(unexpected(&token, span, "value")),
+        })
+    }
+
+    fn expect(&mut self, expected: &'static str) -> Result<(Token<'de>, Span), HclError> {
+        self.next()?.ok_or_else(|| {
+            HclError::with_span(
+                HclErrorKind::UnexpectedEof { expected },
+                Span::new(self.input.len(), 0),
+            )
+        })
+    }
+
+    fn peek(&mut self) -> Result<Option<(Token<'de>, Span)>, HclError> {
+        if self.peeked.is_none() {
+            self.peeked = self.lex()?;
+        }
+        Ok(self.peeked.clone())
+    }
+
+    fn next(&mut self) -> Result<Option<(Token<'de>, Span)>, HclError> {
+        match self.peeked.take() {
+            Some(token) => Ok(Some(token)),
+            None => self.lex(),
+        }
+    }
+
+    fn lex(&mut self) -> Result<Option<(Token<'de>, Span)>, HclError> {
+        self.skip_trivia();
+        let bytes = self.input.as_bytes();
+        let start = self.pos;
+        let Some(&b) = bytes.get(start) else {
+            return Ok(None);
+        };
+        let single = |token| Ok(Some((token, Span::new(start, 1))));
+        self.pos += 1;
+        match b {
+            b'{' => single(Token::LBrace),
+            b'}' => single(Token::RBrace),
+            b'[' => single(Token::LBracket),
+            b']' => single(Token::RBracket),
+            b'=' => single(Token::Equals),
+            b':' => single(Token::Colon),
+            b',' => single(Token::Comma),
+            b'"' => {
+                let s = self.string(start)?;
+                Ok(Some((Token::Str(s), Span::new(start, self.pos - start))))
+            }
+            b'<' if bytes.get(start + 1) == Some(&b'<') => {
+                let s = self.heredoc(start)?;
+                Ok(Some((Token::Str(s), Span::new(start, self.pos - start))))
+            }
+            b'-' | b'0'..=b'9' => {
+                let end = self.input[start + 1..]
+                    .char_indices()
+                    .find(|&(i, c)| {
+                        let prev = self.input.as_bytes()[start + i];
+                        !(c.is_ascii_alphanumeric()
+                            || c == '.'
+                            || ((c == '+' || c == '-') && matches!(prev, b'e' | b'E')))
+                    })
+                    .map_or(self.input.len(), |(i, _)| start + 1 + i);
+                self.pos = end;
+                let text = &self.input[start..end];
+                let span = Span::new(start, end - start);
+                Ok(Some((Token::Number(parse_number(text, span)?), span)))
+            }
+            b if b.is_ascii_alphabetic() || b == b'_' => {
+                let end = self.input[start..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+                    .map_or(self.input.len(), |i| start + i);
+                self.pos = end;
+                Ok(Some((
+                    Token::Ident(&self.input[start..end]),
+                    Span::new(start, end - start),
+                )))
+            }
+            _ => {
+                let c = self.input[start..].chars().next().unwrap_or_default();
+                self.pos = start + c.len_utf8();
+                Err(HclError::with_span(
+                    HclErrorKind::UnexpectedToken {
+                        found: alloc::format!("character `{c}`"),
+                        expected: "attribute, block or value",
+                    },
+                    Span::new(start, c.len_utf8()),
+                ))
+            }
+        }
+    }
+
+    /// Skip whitespace and `#`, `//` and `/* */` comments
+    fn skip_trivia(&mut self) {
+        loop {
+            let rest = &self.input[self.pos..];
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if trimmed.starts_with('#') || trimmed.starts_with("//") {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else if let Some(comment) = trimmed.strip_prefix("/*") {
+                self.pos += comment.find("*/").map_or(trimmed.len(), |i| i + 4);
+            } else {
+                return;
+            }
+        }
+    }
+
+    /// A quoted string; `start` is the offset of the opening quote
+    fn string(&mut self, start: usize) -> Result<Cow<'de, str>, HclError> {
+        let rest = &self.input[start + 1..];
+        let mut escaped = false;
+        let mut end = None;
+        for (i, c) in rest.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => {
+                    end = Some(i);
+                    break;
+                }
+                '\n' => break,
+                _ => {}
+            }
+        }
+        let Some(end) = end else {
+            return Err(HclError::with_span(
+                HclErrorKind::UnterminatedString,
+                Span::new(start, 1),
+            ));
+        };
+        self.pos = start + 1 + end + 1;
+        unescape(&rest[..end], start + 1, true)
+    }
+
+    /// A `<<EOF` or `<<-EOF` heredoc; `start` is the offset of `<<`
+    fn heredoc(&mut self, start: usize) -> Result<Cow<'de, str>, HclError> {
+        let header = &self.input[start + 2..];
+        let (indented, header) = match header.strip_prefix('-') {
+            Some(header) => (true, header),
+            None => (false, header),
+        };
+        let line_end = header.find('\n').unwrap_or(header.len());
+        let marker = header[..line_end].trim_end();
+        if marker.is_empty()
+            || !marker
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(HclError::with_span(
+                HclErrorKind::UnexpectedToken {
+                    found: "`<<`".into(),
+                    expected: "heredoc marker like `<<EOF`",
+                },
+                Span::new(start, 2),
+            ));
+        }
+
+        let content_start = self.input.len() - header.len() + (line_end + 1).min(header.len());
+        let mut lines = Vec::new();
+        let mut offset = content_start;
+        loop {
+            if offset >= self.input.len() {
+                return Err(HclError::with_span(
+                    HclErrorKind::UnterminatedString,
+                    Span::new(start, content_start - start),
+                ));
+            }
+            let rest = &self.input[offset..];
+            let len = rest.find('\n').unwrap_or(rest.len());
+            let line = &rest[..len];
+            offset += len + 1;
+            if line.trim() == marker {
+                self.pos = offset - 1;
+                break;
+            }
+            lines.push(line);
+        }
+
+        let strip = if indented {
+            lines
+                .iter()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.len() - line.trim_start().len())
+                .min()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        let mut text = String::new();
+        for line in lines {
+            text.push_str(line.get(strip..).unwrap_or("").trim_end_matches('\r'));
+            text.push('\n');
+        }
+        Ok(Cow::Owned(
+            unescape(&text, content_start, false)?.into_owned(),
+        ))
+    }
+}
+
+/// Resolve escapes in string contents starting at byte `offset` of the input.
+/// Heredocs only get template escapes (`$${`, `%%{`), not backslash escapes.
+fn unescape(raw: &str, offset: usize, backslashes: bool) -> Result<Cow<'_, str>, HclError> {
+    let bytes = raw.as_bytes();
+    for i in 0..bytes.len().saturating_sub(1) {
+        let opens = matches!(bytes[i], b'$' | b'%') && bytes[i + 1] == b'{';
+        let doubled = i > 0 && bytes[i - 1] == bytes[i];
+        if opens && !doubled {
+            return Err(HclError::with_span(
+                HclErrorKind::Unsupported {
+                    what: "template interpolation",
+                },
+                Span::new(offset + i, 2),
+            ));
+        }
+    }
+    let escaped = (backslashes && raw.contains('\\')) || raw.contains("$${") || raw.contains("%%{");
+    if !escaped {
+        return Ok(Cow::Borrowed(raw));
+    }
+
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if backslashes => {
+                let invalid = |len: usize| {
+                    HclError::with_span(
+                        HclErrorKind::InvalidEscape {
+                            text: raw[i..(i + len).min(raw.len())].to_string(),
+                        },
+                        Span::new(offset + i, len),
+                    )
+                };
+                match chars.next().map(|(_, c)| c) {
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some('"') => out.push('"'),
+                    Some('\\') => out.push('\\'),
+                    Some(kind @ ('u' | 'U')) => {
+                        let len = if kind == 'u' { 4 } else { 8 };
+                        let hex = raw.get(i + 2..i + 2 + len).ok_or_else(|| invalid(2))?;
+                        let c = u32::from_str_radix(hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| invalid(2 + len))?;
+                        out.push(c);
+                        for _ in 0..len {
+                            chars.next();
+                        }
+                    }
+                    _ => return Err(invalid(2)),
+                }
+            }
+            '$' | '%' if raw[i..].starts_with("$${") || raw[i..].starts_with("%%{") => {
+                out.push(c);
+                chars.next();
+            }
+            c => out.push(c),
+        }
+    }
+    Ok(Cow::Owned(out))
+}
+
+fn parse_number(text: &str, span: Span) -> Result<ScalarValue<'static>, HclError> {
+    let invalid = || {
+        HclError::with_span(
+            HclErrorKind::InvalidNumber {
+                text: text.to_string(),
+            },
+            span,
+        )
+    };
+    if text.contains(['.', 'e', 'E']) {
+        return text.parse().map(ScalarValue::F64).map_err(|_| invalid());
+    }
+    if text.starts_with('-') {
+        if let Ok(n) = text.parse() {
+            return Ok(ScalarValue::I64(n));
+        }
+        return text.parse().map(ScalarValue::I128).map_err(|_| invalid());
+    }
+    if let Ok(n) = text.parse() {
+        return Ok(ScalarValue::U64(n));
+    }
+    text.parse().map(ScalarValue::U128).map_err(|_| invalid())
+}
+
+fn unexpected(token: &Token<'_>, span: Span, expected: &'static str) -> HclError {
+    HclError::with_span(
+        HclErrorKind::UnexpectedToken {
+            found: token.describe(),
+            expected,
+        },
+        span,
+    )
+}
+
+fn join(start: Span, end: Span) -> Span {
+    Span::new(start.offset, end.offset + end.len - start.offset)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Events
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// What the value being emitted will be deserialized into, as far as known
+#[derive(Clone, Copy)]
+enum Target {
+    Unknown,
+    Shape(&'static Shape),
+    /// The fields of an enum variant
+    Fields(&'static [Field]),
+}
+
+impl Target {
+    /// The target of the entry named `key` in this object
+    fn child(self, key: &str) -> Target {
+        let fields = match self {
+            Target::Unknown => return Target::Unknown,
+            Target::Fields(fields) => fields,
+            Target::Shape(shape) => {
+                let shape = unwrap(shape);
+                match (shape.def, shape.ty) {
+                    (Def::Map(map), _) => return Target::Shape(map.v),
+                    (_, Type::User(UserType::Struct(st))) => st.fields,
+                    (_, Type::User(UserType::Enum(en))) => {
+                        return en
+                            .variants
+                            .iter()
+                            .find(|v| v.name == key)
+                            .map_or(Target::Unknown, |v| Target::Fields(v.data.fields));
+                    }
+                    _ => return Target::Unknown,
+                }
+            }
+        };
+        find_field(fields, key).map_or(Target::Unknown, |f| Target::Shape(f.shape()))
+    }
+
+    /// The item target, if this is a list
+    fn items(self) -> Option<Target> {
+        let Target::Shape(shape) = self else {
+            return None;
+        };
+        match unwrap(shape).def {
+            Def::List(list) => Some(Target::Shape(list.t())),
+            Def::Array(array) => Some(Target::Shape(array.t)),
+            Def::Slice(slice) => Some(Target::Shape(slice.t)),
+            Def::Set(set) => Some(Target::Shape(set.t())),
+            _ => None,
+        }
+    }
+}
+
+/// See through options, smart pointers and transparent wrappers
+fn unwrap(mut shape: &'static Shape) -> &'static Shape {
+    loop {
+        shape = match (shape.def, shape.ty) {
+            (Def::Option(option), _) => option.t,
+            (Def::Pointer(pointer), _) => match pointer.pointee() {
+                Some(pointee) => pointee,
+                None => return shape,
+            },
+            (_, Type::User(UserType::Struct(_))) if shape.inner.is_some() => {
+                shape.inner.unwrap_or(shape)
+            }
+            _ => return shape,
+        };
+    }
+}
+
+/// The field named `key`, looking into flattened fields too
+fn find_field(fields: &'static [Field], key: &str) -> Option<&'static Field> {
+    fields.iter().find_map(|field| {
+        if field.is_flattened() {
+            match unwrap(field.shape()).ty {
+                Type::User(UserType::Struct(st)) => find_field(st.fields, key),
+                _ => None,
+            }
+        } else if field.name == key || field.alias == Some(key) {
+            Some(field)
+        } else {
+            None
+        }
+    })
+}
+
+#[derive(Default)]
+struct Emitter<'de> {
+    events: Vec<ParseEvent<'de>>,
+    spans: Vec<Span>,
+}
+
+impl<'de> Emitter<'de> {
+    fn push(&mut self, event: ParseEvent<'de>, span: Span) {
+        self.events.push(event);
+        self.spans.push(span);
+    }
+
+    fn key(&mut self, name: Cow<'de, str>, span: Span) {
+        self.push(
+            ParseEvent::FieldKey(FieldKey::new(name, FieldLocationHint::KeyValue)),
+            span,
+        );
+    }
+
+    fn body(&mut self, body: &Body<'de>, target: Target, span: Span) -> Result<(), HclError> {
+        self.push(ParseEvent::StructStart(ContainerKind::Object), span);
+        for (i, item) in body.items.iter().enumerate() {
+            match item {
+                Item::Attribute { key, span, value } => {
+                    self.key(Cow::Borrowed(key), *span);
+                    self.expr(value);
+                }
+                Item::Block(block) => {
+                    // All blocks of a type are emitted together, where the first one is
+                    let seen = body.items[..i]
+                        .iter()
+                        .any(|item| matches!(item, Item::Block(b) if b.ty == block.ty));
+                    if seen {
+                        continue;
+                    }
+                    let group: Vec<&Block<'de>> = body.items[i..]
+                        .iter()
+                        .filter_map(|item| match item {
+                            Item::Block(b) if b.ty == block.ty => Some(b),
+                            _ => None,
+                        })
+                        .collect();
+                    if group.iter().any(|b| b.labels.len() != block.labels.len()) {
+                        return Err(HclError::with_span(
+                            HclErrorKind::MixedLabels {
+                                name: block.ty.to_string(),
+                            },
+                            block.span,
+                        ));
+                    }
+                    self.key(Cow::Borrowed(block.ty), block.span);
+                    self.blocks(&group, 0, target.child(block.ty))?;
+                }
+            }
+        }
+        self.push(ParseEvent::StructEnd, span);
+        Ok(())
+    }
+
+    /// Blocks of one type, starting at label `depth`
+    fn blocks(
+        &mut self,
+        blocks: &[&Block<'de>],
+        depth: usize,
+        target: Target,
+    ) -> Result<(), HclError> {
+        let first = blocks[0];
+        if depth < first.labels.len() {
+            // One object per distinct label, in order of first appearance
+            self.push(ParseEvent::StructStart(ContainerKind::Object), first.span);
+            for (i, block) in blocks.iter().enumerate() {
+                let label = &block.labels[depth];
+                if blocks[..i].iter().any(|b| &b.labels[depth] == label) {
+                    continue;
+                }
+                let group: Vec<&Block<'de>> = blocks[i..]
+                    .iter()
+                    .copied()
+                    .filter(|b| &b.labels[depth] == label)
+                    .collect();
+                self.key(label.clone(), block.span);
+                self.blocks(&group, depth + 1, target.child(label))?;
+            }
+            self.push(ParseEvent::StructEnd, first.span);
+            return Ok(());
+        }
+
+        match (target.items(), blocks) {
+            (None, [block]) => self.body(&block.body, target, block.span),
+            (None, [_, second, ..]) if !matches!(target, Target::Unknown) => {
+                Err(HclError::with_span(
+                    HclErrorKind::DuplicateBlock {
+                        name: second.ty.to_string(),
+                    },
+                    second.span,
+                ))
+            }
+            (items, _) => {
+                let items = items.unwrap_or(Target::Unknown);
+                self.push(ParseEvent::SequenceStart(ContainerKind::Array), first.span);
+                for block in blocks {
+                    self.body(&block.body, items, block.span)?;
+                }
+                self.push(ParseEvent::SequenceEnd, first.span);
+                Ok(())
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr<'de>) {
+        match expr {
+            Expr::Scalar(value, span) => self.push(ParseEvent::Scalar(value.clone()), *span),
+            Expr::Tuple(items, span) => {
+                self.push(ParseEvent::SequenceStart(ContainerKind::Array), *span);
+                for item in items {
+                    self.expr(item);
+                }
+                self.push(ParseEvent::SequenceEnd, *span);
+            }
+            Expr::Object(entries, span) => {
+                self.push(ParseEvent::StructStart(ContainerKind::Object), *span);
+                for (key, value) in entries {
+                    self.key(key.clone(), *span);
+                    self.expr(value);
+                }
+                self.push(ParseEvent::StructEnd, *span);
+            }
+        }
+    }
+}
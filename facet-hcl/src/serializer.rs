@@ -0,0 +1,440 @@
+//! HCL serializer implementing the FormatSerializer trait.
+//!
+//! Output is buffered into a tree first, since whether a field becomes an
+//! attribute or a block depends on its whole value:
+//!
+//! - Structs become blocks: `server { ... }`
+//! - Maps (and externally tagged variants) whose values are all objects become
+//!   labeled blocks: `service "web" { ... }`
+//! - Lists of structs become repeated blocks
+//! - Everything else becomes an attribute: `port = 8080`, `tags = ["a", "b"]`
+//!
+//! `None` fields are left out.
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use facet_core::Facet;
+use facet_format::{FormatSerializer, ScalarValue, SerializeError, serialize_root};
+use facet_reflect::Peek;
+
+/// Error type for HCL serialization.
+#[derive(Debug)]
+pub struct HclSerializeError {
+    msg: String,
+}
+
+impl fmt::Display for HclSerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+impl std::error::Error for HclSerializeError {}
+
+impl HclSerializeError {
+    fn new(msg: impl Into<String>) -> Self {
+        Self { msg: msg.into() }
+    }
+}
+
+/// A value waiting to be written
+enum Node {
+    /// A literal, already in HCL syntax
+    Literal(String),
+    Null,
+    Tuple(Vec<Node>),
+    Object {
+        /// Whether this came from a struct, rather than a map or an enum wrapper
+        is_struct: bool,
+        entries: Vec<(String, Node)>,
+    },
+}
+
+impl Node {
+    fn is_struct(&self) -> bool {
+        matches!(
+            self,
+            Node::Object {
+                is_struct: true,
+                ..
+            }
+        )
+    }
+
+    /// Whether this is a non-empty map whose values are all objects, i.e. labeled blocks
+    fn is_labeled(&self) -> bool {
+        match self {
+            Node::Object {
+                is_struct: false,
+                entries,
+            } => {
+                !entries.is_empty()
+                    && entries
+                        .iter()
+                        .all(|(_, value)| matches!(value, Node::Object { .. }))
+            }
+            _ => false,
+        }
+    }
+}
+
+enum Frame {
+    Tuple(Vec<Node>),
+    Object {
+        is_struct: bool,
+        entries: Vec<(String, Node)>,
+        key: Option<String>,
+    },
+}
+
+/// HCL serializer with buffered output.
+#[derive(Default)]
+pub struct HclSerializer {
+    stack: Vec<Frame>,
+    root: Option<Node>,
+    next_is_struct: bool,
+}
+
+impl HclSerializer {
+    /// Create a new HCL serializer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Render the serialized document.
+    ///
+    /// Fails if the value wasn't a struct or map, or if a key can't be
+    /// written as an HCL identifier.
+    pub fn finish(self) -> Result<String, HclSerializeError> {
+        let Some(Node::Object { entries, .. }) = self.root else {
+            return Err(HclSerializeError::new(
+                "HCL documents must be structs or maps",
+            ));
+        };
+        let mut out = String::new();
+        write_body(&mut out, &entries, 0)?;
+        Ok(out)
+    }
+
+    fn value(&mut self, node: Node) -> Result<(), HclSerializeError> {
+        match self.stack.last_mut() {
+            None => self.root = Some(node),
+            Some(Frame::Tuple(items)) => items.push(node),
+            Some(Frame::Object { entries, key, .. }) => {
+                let key = key
+                    .take()
+                    .ok_or_else(|| HclSerializeError::new("value without a key"))?;
+                entries.push((key, node));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FormatSerializer for HclSerializer {
+    type Error = HclSerializeError;
+
+    fn struct_metadata(&mut self, _shape: &facet_core::Shape) -> Result<(), Self::Error> {
+        self.next_is_struct = true;
+        Ok(())
+    }
+
+    fn begin_struct(&mut self) -> Result<(), Self::Error> {
+        self.stack.push(Frame::Object {
+            is_struct: core::mem::take(&mut self.next_is_struct),
+            entries: Vec::new(),
+            key: None,
+        });
+        Ok(())
+    }
+
+    fn field_key(&mut self, name: &str) -> Result<(), Self::Error> {
+        match self.stack.last_mut() {
+            Some(Frame::Object { key, .. }) => {
+                *key = Some(name.to_string());
+                Ok(())
+            }
+            _ => Err(HclSerializeError::new("field key outside of an object")),
+        }
+    }
+
+    fn end_struct(&mut self) -> Result<(), Self::Error> {
+        match self.stack.pop() {
+            Some(Frame::Object {
+                is_struct, entries, ..
+            }) => self.value(Node::Object { is_struct, entries }),
+            _ => Err(HclSerializeError::new("unbalanced end of object")),
+        }
+    }
+
+    fn begin_seq(&mut self) -> Result<(), Self::Error> {
+        self.stack.push(Frame::Tuple(Vec::new()));
+        Ok(())
+    }
+
+    fn end_seq(&mut self) -> Result<(), Self::Error> {
+        match self.stack.pop() {
+            Some(Frame::Tuple(items)) => self.value(Node::Tuple(items)),
+            _ => Err(HclSerializeError::new("unbalanced end of sequence")),
+        }
+    }
+
+    fn scalar(&mut self, scalar: ScalarValue<'_>) -> Result<(), Self::Error> {
+        let literal = match scalar {
+            ScalarValue::Null => return self.value(Node::Null),
+            ScalarValue::Bool(b) => b.to_string(),
+            ScalarValue::I64(n) => n.to_string(),
+            ScalarValue::U64(n) => n.to_string(),
+            ScalarValue::I128(n) => n.to_string(),
+            ScalarValue::U128(n) => n.to_string(),
+            ScalarValue::F64(n) if n.is_finite() => n.to_string(),
+            ScalarValue::F64(n) => {
+                return Err(HclSerializeError::new(alloc::format!(
+                    "HCL can't represent the number {n}"
+                )));
+            }
+            ScalarValue::Str(s) => quote(&s),
+            ScalarValue::Bytes(bytes) => {
+                let items = bytes.iter().map(|b| Node::Literal(b.to_string())).collect();
+                return self.value(Node::Tuple(items));
+            }
+        };
+        self.value(Node::Literal(literal))
+    }
+}
+
+fn write_body(
+    out: &mut String,
+    entries: &[(String, Node)],
+    indent: usize,
+) -> Result<(), HclSerializeError> {
+    for (key, value) in entries {
+        let key = identifier(key)?;
+        match value {
+            Node::Null => {}
+            Node::Object { entries, .. } if value.is_struct() => {
+                write_block(out, key, &[], entries, indent)?;
+            }
+            Node::Object { .. } if value.is_labeled() => {
+                write_labeled(out, key, &mut Vec::new(), value, indent)?;
+            }
+            Node::Tuple(items) if !items.is_empty() && items.iter().all(Node::is_struct) => {
+                for item in items {
+                    if let Node::Object { entries, .. } = item {
+                        write_block(out, key, &[], entries, indent)?;
+                    }
+                }
+            }
+            _ => {
+                pad(out, indent);
+                write!(out, "{key} = ").unwrap();
+                write_expr(out, value, indent)?;
+                out.push('\n');
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write `value` as blocks labeled with `labels` plus its keys, stacking
+/// labels as long as the values are maps of objects themselves
+fn write_labeled(
+    out: &mut String,
+    ty: &str,
+    labels: &mut Vec<String>,
+    value: &Node,
+    indent: usize,
+) -> Result<(), HclSerializeError> {
+    let Node::Object { entries, .. } = value else {
+        return Ok(());
+    };
+    for (label, value) in entries {
+        labels.push(quote(label));
+        match value {
+            Node::Object { .. } if value.is_labeled() => {
+                write_labeled(out, ty, labels, value, indent)?;
+            }
+            Node::Object { entries, .. } => write_block(out, ty, labels, entries, indent)?,
+            _ => {}
+        }
+        labels.pop();
+    }
+    Ok(())
+}
+
+fn write_block(
+    out: &mut String,
+    ty: &str,
+    labels: &[String],
+    entries: &[(String, Node)],
+    indent: usize,
+) -> Result<(), HclSerializeError> {
+    pad(out, indent);
+    out.push_str(ty);
+    for label in labels {
+        out.push(' ');
+        out.push_str(label);
+    }
+    if entries.iter().all(|(_, value)| matches!(value, Node::Null)) {
+        out.push_str(" {}\n");
+        return Ok(());
+    }
+    out.push_str(" {\n");
+    write_body(out, entries, indent + 1)?;
+    pad(out, indent);
+    out.push_str("}\n");
+    Ok(())
+}
+
+fn write_expr(out: &mut String, value: &Node, indent: usize) -> Result<(), HclSerializeError> {
+    match value {
+        Node::Literal(literal) => out.push_str(literal),
+        Node::Null => out.push_str("null"),
+        Node::Tuple(items)
+            if items
+                .iter()
+                .all(|i| matches!(i, Node::Literal(_) | Node::Null)) =>
+        {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_expr(out, item, indent)?;
+            }
+            out.push(']');
+        }
+        Node::Tuple(items) => {
+            out.push_str("[\n");
+            for item in items {
+                pad(out, indent + 1);
+                write_expr(out, item, indent + 1)?;
+                out.push_str(",\n");
+            }
+            pad(out, indent);
+            out.push(']');
+        }
+        Node::Object { entries, .. } if entries.is_empty() => out.push_str("{}"),
+        Node::Object { entries, .. } => {
+            out.push_str("{\n");
+            for (key, value) in entries {
+                pad(out, indent + 1);
+                match identifier(key) {
+                    Ok(key) => out.push_str(key),
+                    Err(_) => out.push_str(&quote(key)),
+                }
+                out.push_str(" = ");
+                write_expr(out, value, indent + 1)?;
+                out.push('\n');
+            }
+            pad(out, indent);
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+fn pad(out: &mut String, indent: usize) {
+    for _ in 0..indent {
+        out.push_str("  ");
+    }
+}
+
+/// `key` if it can be written as an attribute name or block type
+fn identifier(key: &str) -> Result<&str, HclSerializeError> {
+    let mut chars = key.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(key)
+    } else {
+        Err(HclSerializeError::new(alloc::format!(
+            "`{key}` is not a valid HCL identifier"
+        )))
+    }
+}
+
+/// `s` as a quoted HCL string
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            // `${` and `%{` start templates; doubling the sigil escapes them
+            '$' | '%' if chars.peek() == Some(&'{') => {
+                out.push(c);
+                out.push(c);
+            }
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Serialize a value to an HCL string.
+///
+/// The value must be a struct or a map.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_hcl::to_string;
+///
+/// #[derive(Facet)]
+/// struct Config {
+///     name: String,
+///     server: Server,
+/// }
+///
+/// #[derive(Facet)]
+/// struct Server {
+///     port: u16,
+/// }
+///
+/// let config = Config {
+///     name: "api".into(),
+///     server: Server { port: 8080 },
+/// };
+/// assert_eq!(
+///     to_string(&config).unwrap(),
+///     "name = \"api\"\nserver {\n  port = 8080\n}\n"
+/// );
+/// ```
+pub fn to_string<'facet, T>(value: &T) -> Result<String, SerializeError<HclSerializeError>>
+where
+    T: Facet<'facet> + ?Sized,
+{
+    peek_to_string(Peek::new(value))
+}
+
+/// Serialize a value to HCL bytes.
+pub fn to_vec<'facet, T>(value: &T) -> Result<Vec<u8>, SerializeError<HclSerializeError>>
+where
+    T: Facet<'facet> + ?Sized,
+{
+    to_string(value).map(String::into_bytes)
+}
+
+/// Serialize a `Peek` instance to an HCL string.
+pub fn peek_to_string<'input, 'facet>(
+    peek: Peek<'input, 'facet>,
+) -> Result<String, SerializeError<HclSerializeError>> {
+    let mut serializer = HclSerializer::new();
+    serialize_root(&mut serializer, peek)?;
+    serializer.finish().map_err(SerializeError::Backend)
+}
@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_hcl::{HclErrorKind, from_str, to_string};
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Config {
+    name: String,
+    debug: bool,
+    ratio: f64,
+    tags: Vec<String>,
+    labels: BTreeMap<String, String>,
+    server: Server,
+    listener: Vec<Listener>,
+    service: BTreeMap<String, Service>,
+    health: Check,
+    readiness: Check,
+    timeout: Option<u32>,
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Listener {
+    port: u16,
+    tls: bool,
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Service {
+    image: String,
+    replicas: u32,
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+#[repr(u8)]
+enum Check {
+    Disabled,
+    Http { path: String, interval: u32 },
+}
+
+#[test]
+fn test_parse_blocks_and_attributes() {
+    let hcl = r#"
+# Terraform-style config
+name  = "api"
+debug = true
+ratio = 1.5e-1
+tags  = ["a", "b",]
+labels = {
+  team  = "core"
+  "cost center": "42"
+}
+
+server {
+  host = "localhost" // trailing comment
+  port = 8080
+}
+
+/* listeners */
+listener {
+  port = 80
+  tls  = false
+}
+
+listener {
+  port = 443
+  tls  = true
+}
+
+service "web" {
+  image    = "nginx"
+  replicas = 2
+}
+
+health "Http" {
+  path     = "/healthz"
+  interval = 10
+}
+
+readiness = "Disabled"
+timeout = 30
+"#;
+    let config: Config = from_str(hcl).unwrap();
+    assert_eq!(config.name, "api");
+    assert_eq!(config.ratio, 0.15);
+    assert_eq!(config.labels["cost center"], "42");
+    assert_eq!(config.server.port, 8080);
+    assert_eq!(config.listener.len(), 2);
+    assert!(config.listener[1].tls);
+    assert_eq!(config.service["web"].replicas, 2);
+    assert_eq!(
+        config.health,
+        Check::Http {
+            path: "/healthz".into(),
+            interval: 10
+        }
+    );
+    assert_eq!(config.readiness, Check::Disabled);
+    assert_eq!(config.timeout, Some(30));
+}
+
+#[test]
+fn test_single_block_into_list() {
+    #[derive(Facet, Debug, PartialEq)]
+    struct Listeners {
+        listener: Vec<Listener>,
+    }
+
+    let parsed: Listeners = from_str("listener {\n  port = 80\n  tls = false\n}\n").unwrap();
+    assert_eq!(
+        parsed.listener,
+        [Listener {
+            port: 80,
+            tls: false
+        }]
+    );
+}
+
+#[test]
+fn test_nested_labels() {
+    #[derive(Facet, Debug, PartialEq)]
+    struct Resources {
+        resource: BTreeMap<String, BTreeMap<String, Service>>,
+    }
+
+    let hcl = r#"
+resource "container" "web" {
+  image = "nginx"
+  replicas = 2
+}
+resource "container" "db" {
+  image = "postgres"
+  replicas = 1
+}
+"#;
+    let parsed: Resources = from_str(hcl).unwrap();
+    assert_eq!(parsed.resource["container"].len(), 2);
+    assert_eq!(parsed.resource["container"]["db"].image, "postgres");
+
+    let text = to_string(&parsed).unwrap();
+    assert!(
+        text.contains("resource \"container\" \"db\" {\n  image = \"postgres\"\n"),
+        "{text}"
+    );
+    assert_eq!(from_str::<Resources>(&text).unwrap(), parsed);
+}
+
+#[test]
+fn test_roundtrip() {
+    let config = Config {
+        name: "api \"edge\" ${env}".into(),
+        debug: true,
+        ratio: 0.5,
+        tags: vec!["a".into(), "b".into()],
+        labels: BTreeMap::from([
+            ("team".into(), "core".into()),
+            ("cost center".into(), "42".into()),
+        ]),
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+        },
+        listener: vec![
+            Listener {
+                port: 80,
+                tls: false,
+            },
+            Listener {
+                port: 443,
+                tls: true,
+            },
+        ],
+        service: BTreeMap::from([
+            (
+                "web".into(),
+                Service {
+                    image: "nginx".into(),
+                    replicas: 2,
+                },
+            ),
+            (
+                "worker".into(),
+                Service {
+                    image: "app".into(),
+                    replicas: 1,
+                },
+            ),
+        ]),
+        health: Check::Http {
+            path: "/healthz".into(),
+            interval: 10,
+        },
+        readiness: Check::Disabled,
+        timeout: None,
+    };
+    let text = to_string(&config).unwrap();
+    assert!(
+        text.contains("server {\n  host = \"localhost\"\n"),
+        "{text}"
+    );
+    assert!(text.contains("listener {\n  port = 443\n"), "{text}");
+    assert!(text.contains("service \"web\" {\n"), "{text}");
+    assert!(
+        text.contains("health \"Http\" {\n  path = \"/healthz\"\n  interval = 10\n}\n"),
+        "{text}"
+    );
+    assert!(text.contains("readiness = \"Disabled\"\n"), "{text}");
+    assert!(text.contains("\"cost center\" = \"42\""), "{text}");
+    assert!(text.contains("$${env}"), "{text}");
+    assert!(!text.contains("timeout"), "{text}");
+    assert_eq!(from_str::<Config>(&text).unwrap(), config);
+}
+
+#[test]
+fn test_heredoc() {
+    #[derive(Facet, Debug)]
+    struct Script {
+        body: String,
+        indented: String,
+    }
+
+    let hcl = "body = <<EOF\necho hi\n  done\nEOF\nindented = <<-EOT\n    a\n      b\n    EOT\n";
+    let script: Script = from_str(hcl).unwrap();
+    assert_eq!(script.body, "echo hi\n  done\n");
+    assert_eq!(script.indented, "a\n  b\n");
+}
+
+#[test]
+fn test_errors() {
+    let err = from_str::<Server>("host = var.host\nport = 1\n").unwrap_err();
+    assert!(err.to_string().contains("variable reference"), "{err}");
+
+    let err = from_str::<Server>("host = \"${var.host}\"\nport = 1\n").unwrap_err();
+    assert!(err.to_string().contains("interpolation"), "{err}");
+
+    let err = from_str::<Server>("host = \"x\"\nport = 1\nserver {\n").unwrap_err();
+    assert!(err.to_string().contains("end of input"), "{err}");
+
+    #[derive(Facet, Debug)]
+    struct Single {
+        server: Server,
+    }
+    let err = from_str::<Single>(
+        "server {\n host = \"a\"\n port = 1\n}\nserver {\n host = \"b\"\n port = 2\n}\n",
+    )
+    .unwrap_err();
+    match err {
+        facet_hcl::DeserializeError::Parser(err) => {
+            assert!(matches!(err.kind(), HclErrorKind::DuplicateBlock { .. }));
+            assert_eq!(err.span().unwrap().offset, 33);
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+}
@@ -0,0 +1,108 @@
+//! INI parsing error types.
+
+use alloc::string::String;
+use core::fmt;
+
+/// Error type for INI parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IniError {
+    kind: IniErrorKind,
+    /// Source span of the error, if available.
+    span: Option<facet_reflect::Span>,
+}
+
+impl IniError {
+    /// Create a new error with the given kind.
+    pub fn new(kind: IniErrorKind) -> Self {
+        Self { kind, span: None }
+    }
+
+    /// Create a new error with the given kind and span.
+    pub fn with_span(kind: IniErrorKind, span: facet_reflect::Span) -> Self {
+        Self {
+            kind,
+            span: Some(span),
+        }
+    }
+
+    /// Get the error kind.
+    pub fn kind(&self) -> &IniErrorKind {
+        &self.kind
+    }
+
+    /// Get the source span of the error, if known.
+    pub fn span(&self) -> Option<facet_reflect::Span> {
+        self.span
+    }
+}
+
+impl fmt::Display for IniError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            IniErrorKind::UnexpectedEof { expected } => {
+                write!(f, "unexpected end of input, expected {expected}")
+            }
+            IniErrorKind::InvalidLine => {
+                write!(f, "expected `key = value`, `[section]` or a comment")
+            }
+            IniErrorKind::InvalidSection { name } => write!(f, "invalid section name `{name}`"),
+            IniErrorKind::EmptyKey => write!(f, "missing key before `=`"),
+            IniErrorKind::UnterminatedString => write!(f, "unterminated string"),
+            IniErrorKind::InvalidEscape { text } => write!(f, "invalid escape `{text}`"),
+            IniErrorKind::TrailingCharacters => {
+                write!(f, "unexpected characters after the closing quote")
+            }
+            IniErrorKind::DuplicateKey { key } => {
+                write!(f, "key `{key}` appears more than once")
+            }
+            IniErrorKind::InvalidUtf8 { message } => write!(f, "invalid UTF-8: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for IniError {}
+
+/// Specific kinds of INI errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IniErrorKind {
+    /// Unexpected end of input.
+    UnexpectedEof {
+        /// What was expected at this point.
+        expected: &'static str,
+    },
+    /// A line that is neither a key/value pair, a section header nor a comment.
+    InvalidLine,
+    /// A section header that is unclosed, empty, or has an empty part
+    /// between dots.
+    InvalidSection {
+        /// The header as written.
+        name: String,
+    },
+    /// A `= value` line without a key.
+    EmptyKey,
+    /// A quoted value without its closing quote.
+    UnterminatedString,
+    /// An unknown escape sequence in a quoted value.
+    InvalidEscape {
+        /// The escape sequence.
+        text: String,
+    },
+    /// Something other than a comment after a quoted value.
+    TrailingCharacters,
+    /// A key set twice in the same section, or a section named like a key.
+    DuplicateKey {
+        /// The key.
+        key: String,
+    },
+    /// Invalid UTF-8 in input.
+    InvalidUtf8 {
+        /// The UTF-8 error details.
+        message: String,
+    },
+}
+
+impl From<IniErrorKind> for IniError {
+    fn from(kind: IniErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
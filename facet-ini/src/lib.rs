@@ -0,0 +1,137 @@
+//! INI parser and serializer using facet-format.
+//!
+//! This crate reads and writes INI files, the `key = value` configuration
+//! format many legacy tools still emit, for any type implementing `Facet`.
+//!
+//! # Mapping to Rust Types
+//!
+//! - Keys before the first `[section]` header are the top-level fields.
+//! - `[section]` headers are fields holding structs (or maps), and their
+//!   keys are that struct's fields. Dotted headers like `[server.tls]` nest
+//!   further.
+//! - Values are text, parsed into the field's type with its `FromStr`-style
+//!   parse hook: `port = 8080` fills a `u16`, `debug = true` a `bool`, and
+//!   `mode = Fast` a unit enum variant.
+//! - Missing keys leave `Option` fields as `None`.
+//!
+//! Values run to the end of the line and are trimmed; quote them
+//! (`"  padded  "`) to keep surrounding whitespace or to use `\n`, `\t`,
+//! `\"` and `\\` escapes. Lines starting with `;` or `#` are comments, and
+//! `key: value` works as well as `key = value`. Setting a key twice in one
+//! section is an error, while repeated section headers add to the same
+//! section.
+//!
+//! Lists have no INI form, so serializing one is an error.
+//!
+//! # Example
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_ini::from_str;
+//!
+//! #[derive(Facet, Debug)]
+//! struct Config {
+//!     name: String,
+//!     debug: bool,
+//!     database: Database,
+//! }
+//!
+//! #[derive(Facet, Debug)]
+//! struct Database {
+//!     host: String,
+//!     port: u16,
+//!     timeout_secs: Option<u32>,
+//! }
+//!
+//! let ini = r#"
+//! ; written by the installer
+//! name = inventory
+//! debug = false
+//!
+//! [database]
+//! host = db.internal
+//! port = 5432
+//! "#;
+//!
+//! let config: Config = from_str(ini).unwrap();
+//! assert_eq!(config.database.port, 5432);
+//! assert_eq!(config.database.timeout_secs, None);
+//! ```
+
+#![forbid(unsafe_code)]
+
+extern crate alloc;
+
+use alloc::string::ToString;
+
+mod error;
+mod parser;
+mod serializer;
+
+pub use error::{IniError, IniErrorKind};
+pub use parser::{IniParser, IniProbe};
+pub use serializer::{IniSerializeError, IniSerializer, peek_to_string, to_string, to_vec};
+
+// Re-export DeserializeError for convenience
+pub use facet_format::DeserializeError;
+
+/// Deserialize a value from an INI string into an owned type.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_ini::from_str;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Config {
+///     name: String,
+///     retries: u8,
+/// }
+///
+/// let config: Config = from_str("name = worker\nretries: 3\n").unwrap();
+/// assert_eq!(config.retries, 3);
+/// ```
+pub fn from_str<T>(input: &str) -> Result<T, DeserializeError<IniError>>
+where
+    T: facet_core::Facet<'static>,
+{
+    use facet_format::FormatDeserializer;
+    let parser = IniParser::new(input);
+    let mut de = FormatDeserializer::new_owned(parser);
+    de.deserialize()
+}
+
+/// Deserialize a value from an INI string, allowing zero-copy borrowing.
+///
+/// This variant requires the input to outlive the result (`'input: 'facet`),
+/// enabling zero-copy deserialization of unquoted values.
+pub fn from_str_borrowed<'input, 'facet, T>(
+    input: &'input str,
+) -> Result<T, DeserializeError<IniError>>
+where
+    T: facet_core::Facet<'facet>,
+    'input: 'facet,
+{
+    use facet_format::FormatDeserializer;
+    let parser = IniParser::new(input);
+    let mut de = FormatDeserializer::new(parser);
+    de.deserialize()
+}
+
+/// Deserialize a value from INI bytes into an owned type.
+///
+/// # Errors
+///
+/// Returns an error if the input is not valid UTF-8 or if deserialization fails.
+pub fn from_slice<T>(input: &[u8]) -> Result<T, DeserializeError<IniError>>
+where
+    T: facet_core::Facet<'static>,
+{
+    let s = core::str::from_utf8(input).map_err(|e| {
+        DeserializeError::Parser(IniError::new(IniErrorKind::InvalidUtf8 {
+            message: e.to_string(),
+        }))
+    })?;
+    from_str(s)
+}
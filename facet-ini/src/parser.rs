@@ -0,0 +1,368 @@
+//! INI parser implementation using FormatParser trait.
+//!
+//! The input is read into a tree of sections first, then flattened into
+//! events:
+//!
+//! - The document and every section → StructStart(Object) ... StructEnd
+//! - `key = value` → FieldKey + Scalar(Str)
+//! - `[section]` → FieldKey(section) + its keys as a nested object; dotted
+//!   headers like `[server.tls]` nest further
+//!
+//! Every value is a string scalar. The deserializer parses it into the
+//! target type through the type's `parse` hook, so `port = 8080` fills a
+//! `u16` and `debug = true` a `bool`.
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use facet_format::{
+    ContainerKind, FieldEvidence, FieldKey, FieldLocationHint, FormatParser, ParseEvent,
+    ProbeStream, ScalarValue,
+};
+use facet_reflect::Span;
+
+use crate::error::{IniError, IniErrorKind};
+
+/// INI parser that converts INI documents to FormatParser events.
+pub struct IniParser<'de> {
+    events: Vec<ParseEvent<'de>>,
+    /// Source spans for each event (parallel to events vec).
+    spans: Vec<Span>,
+    idx: usize,
+    pending_error: Option<IniError>,
+}
+
+impl<'de> IniParser<'de> {
+    /// Create a new INI parser from input string.
+    pub fn new(input: &'de str) -> Self {
+        match read(input) {
+            Ok(root) => {
+                let mut parser = Self {
+                    events: Vec::new(),
+                    spans: Vec::new(),
+                    idx: 0,
+                    pending_error: None,
+                };
+                parser.section(root, Span::new(0, input.len()));
+                parser
+            }
+            Err(err) => Self {
+                events: Vec::new(),
+                spans: Vec::new(),
+                idx: 0,
+                pending_error: Some(err),
+            },
+        }
+    }
+
+    fn push(&mut self, event: ParseEvent<'de>, span: Span) {
+        self.events.push(event);
+        self.spans.push(span);
+    }
+
+    fn section(&mut self, section: Section<'de>, span: Span) {
+        self.push(ParseEvent::StructStart(ContainerKind::Object), span);
+        for entry in section.entries {
+            self.push(
+                ParseEvent::FieldKey(FieldKey::new(
+                    Cow::Borrowed(entry.key),
+                    FieldLocationHint::KeyValue,
+                )),
+                entry.span,
+            );
+            match entry.value {
+                Value::Scalar(s, span) => self.push(ParseEvent::Scalar(ScalarValue::Str(s)), span),
+                Value::Section(section) => self.section(section, entry.span),
+            }
+        }
+        self.push(ParseEvent::StructEnd, span);
+    }
+
+    /// Build field evidence by looking ahead at remaining events.
+    fn build_probe(&self) -> Vec<FieldEvidence<'de>> {
+        let mut evidence = Vec::new();
+        if !matches!(self.events.get(self.idx), Some(ParseEvent::StructStart(_))) {
+            return evidence;
+        }
+
+        let mut depth = 0usize;
+        for (i, event) in self.events.iter().enumerate().skip(self.idx + 1) {
+            match event {
+                ParseEvent::StructStart(_) | ParseEvent::SequenceStart(_) => depth += 1,
+                ParseEvent::StructEnd | ParseEvent::SequenceEnd => {
+                    if depth == 0 {
+                        break;
+                    }
+                    depth -= 1;
+                }
+                ParseEvent::FieldKey(key) if depth == 0 => {
+                    evidence.push(match self.events.get(i + 1) {
+                        Some(ParseEvent::Scalar(value)) => FieldEvidence::with_scalar_value(
+                            key.name.clone(),
+                            key.location,
+                            None,
+                            value.clone(),
+                            None,
+                        ),
+                        _ => FieldEvidence::new(key.name.clone(), key.location, None, None),
+                    });
+                }
+                _ => {}
+            }
+        }
+        evidence
+    }
+}
+
+impl<'de> FormatParser<'de> for IniParser<'de> {
+    type Error = IniError;
+    type Probe<'a>
+        = IniProbe<'de>
+    where
+        Self: 'a;
+
+    fn next_event(&mut self) -> Result<Option<ParseEvent<'de>>, Self::Error> {
+        if let Some(err) = &self.pending_error {
+            return Err(err.clone());
+        }
+        let event = self.events.get(self.idx).cloned();
+        if event.is_some() {
+            self.idx += 1;
+        }
+        Ok(event)
+    }
+
+    fn peek_event(&mut self) -> Result<Option<ParseEvent<'de>>, Self::Error> {
+        if let Some(err) = &self.pending_error {
+            return Err(err.clone());
+        }
+        Ok(self.events.get(self.idx).cloned())
+    }
+
+    fn skip_value(&mut self) -> Result<(), Self::Error> {
+        let mut depth = 0usize;
+        loop {
+            let event = self
+                .next_event()?
+                .ok_or(IniError::new(IniErrorKind::UnexpectedEof {
+                    expected: "value",
+                }))?;
+            match event {
+                ParseEvent::StructStart(_) | ParseEvent::SequenceStart(_) => depth += 1,
+                ParseEvent::StructEnd | ParseEvent::SequenceEnd => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            if depth == 0 && !matches!(event, ParseEvent::FieldKey(_)) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn begin_probe(&mut self) -> Result<Self::Probe<'_>, Self::Error> {
+        let evidence = self.build_probe();
+        Ok(IniProbe { evidence, idx: 0 })
+    }
+
+    fn current_span(&self) -> Option<Span> {
+        // The span of the most recently consumed event
+        self.idx
+            .checked_sub(1)
+            .and_then(|i| self.spans.get(i).copied())
+    }
+}
+
+/// Field evidence for untagged-enum resolution, collected from buffered events.
+pub struct IniProbe<'de> {
+    evidence: Vec<FieldEvidence<'de>>,
+    idx: usize,
+}
+
+impl<'de> ProbeStream<'de> for IniProbe<'de> {
+    type Error = IniError;
+
+    fn next(&mut self) -> Result<Option<FieldEvidence<'de>>, Self::Error> {
+        let evidence = self.evidence.get(self.idx).cloned();
+        self.idx += 1;
+        Ok(evidence)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sections
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct Section<'de> {
+    entries: Vec<Entry<'de>>,
+}
+
+struct Entry<'de> {
+    key: &'de str,
+    /// The key, or the section header that created the section
+    span: Span,
+    value: Value<'de>,
+}
+
+enum Value<'de> {
+    Scalar(Cow<'de, str>, Span),
+    Section(Section<'de>),
+}
+
+impl<'de> Section<'de> {
+    /// The section at `path`, creating it and its parents as needed
+    fn descend(&mut self, path: &[(&'de str, Span)]) -> Result<&mut Section<'de>, IniError> {
+        let mut current = self;
+        for &(name, span) in path {
+            let idx = match current.entries.iter().position(|e| e.key == name) {
+                Some(idx) => idx,
+                None => {
+                    current.entries.push(Entry {
+                        key: name,
+                        span,
+                        value: Value::Section(Section::default()),
+                    });
+                    current.entries.len() - 1
+                }
+            };
+            current = match &mut current.entries[idx].value {
+                Value::Section(section) => section,
+                Value::Scalar(..) => return Err(duplicate(name, span)),
+            };
+        }
+        Ok(current)
+    }
+}
+
+fn duplicate(key: &str, span: Span) -> IniError {
+    IniError::with_span(
+        IniErrorKind::DuplicateKey {
+            key: key.to_string(),
+        },
+        span,
+    )
+}
+
+/// Reads the whole document into a tree of sections
+fn read(input: &str) -> Result<Section<'_>, IniError> {
+    let mut root = Section::default();
+    // The path of the current section, empty for the global section
+    let mut path: Vec<(&str, Span)> = Vec::new();
+
+    let mut offset = 0;
+    for raw in input.split_inclusive('\n') {
+        let line_start = offset;
+        offset += raw.len();
+
+        let line = raw.trim_end_matches(['\n', '\r']);
+        let content = line.trim_start();
+        let start = line_start + (line.len() - content.len());
+        let content = content.trim_end();
+        if content.is_empty() || content.starts_with([';', '#']) {
+            continue;
+        }
+        let span = Span::new(start, content.len());
+
+        if let Some(header) = content.strip_prefix('[') {
+            path = section_path(header, start + 1, span)?;
+            root.descend(&path)?;
+            continue;
+        }
+
+        let Some(sep) = content.find(['=', ':']) else {
+            return Err(IniError::with_span(IniErrorKind::InvalidLine, span));
+        };
+        let key = content[..sep].trim_end();
+        if key.is_empty() {
+            return Err(IniError::with_span(IniErrorKind::EmptyKey, span));
+        }
+        let key_span = Span::new(start, key.len());
+        let raw_value = &content[sep + 1..];
+        let value_start = start + sep + 1 + (raw_value.len() - raw_value.trim_start().len());
+        let raw_value = raw_value.trim_start();
+        let value_span = Span::new(value_start, raw_value.len());
+        let value = match raw_value.strip_prefix('"') {
+            Some(quoted) => Cow::Owned(unquote(quoted, value_span)?),
+            None => Cow::Borrowed(raw_value),
+        };
+
+        let section = root.descend(&path)?;
+        if section.entries.iter().any(|e| e.key == key) {
+            return Err(duplicate(key, key_span));
+        }
+        section.entries.push(Entry {
+            key,
+            span: key_span,
+            value: Value::Scalar(value, value_span),
+        });
+    }
+    Ok(root)
+}
+
+/// Splits the header after its `[` into dotted parts, with their spans
+fn section_path(header: &str, offset: usize, span: Span) -> Result<Vec<(&str, Span)>, IniError> {
+    let invalid = || {
+        IniError::with_span(
+            IniErrorKind::InvalidSection {
+                name: header.to_string(),
+            },
+            span,
+        )
+    };
+    let end = header.find(']').ok_or_else(invalid)?;
+    let rest = header[end + 1..].trim_start();
+    if !rest.is_empty() && !rest.starts_with([';', '#']) {
+        return Err(invalid());
+    }
+
+    let mut path = Vec::new();
+    let mut part_start = 0;
+    for part in header[..end].split('.') {
+        let name = part.trim();
+        if name.is_empty() {
+            return Err(invalid());
+        }
+        let lead = part.len() - part.trim_start().len();
+        path.push((name, Span::new(offset + part_start + lead, name.len())));
+        part_start += part.len() + 1;
+    }
+    Ok(path)
+}
+
+/// The value of a quoted string, given the text after its opening quote
+fn unquote(quoted: &str, span: Span) -> Result<String, IniError> {
+    let mut out = String::new();
+    let mut chars = quoted.char_indices();
+    loop {
+        match chars.next() {
+            Some((i, '"')) => {
+                let rest = quoted[i + 1..].trim_start();
+                if !rest.is_empty() && !rest.starts_with([';', '#']) {
+                    return Err(IniError::with_span(IniErrorKind::TrailingCharacters, span));
+                }
+                return Ok(out);
+            }
+            Some((_, '\\')) => match chars.next() {
+                Some((_, '"')) => out.push('"'),
+                Some((_, '\\')) => out.push('\\'),
+                Some((_, 'n')) => out.push('\n'),
+                Some((_, 'r')) => out.push('\r'),
+                Some((_, 't')) => out.push('\t'),
+                Some((_, c)) => {
+                    return Err(IniError::with_span(
+                        IniErrorKind::InvalidEscape {
+                            text: alloc::format!("\\{c}"),
+                        },
+                        span,
+                    ));
+                }
+                None => break,
+            },
+            Some((_, c)) => out.push(c),
+            None => break,
+        }
+    }
+    Err(IniError::with_span(IniErrorKind::UnterminatedString, span))
+}
@@ -0,0 +1,284 @@
+//! INI serializer implementing the FormatSerializer trait.
+//!
+//! Output is buffered into a tree first, since a section's keys have to be
+//! written before any of its subsections:
+//!
+//! - Scalars become `key = value` lines in the enclosing section
+//! - Structs and maps become `[section]`s, and nested ones `[parent.child]`
+//! - Lists have no INI form and are rejected
+//!
+//! `None` fields are left out.
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use facet_core::Facet;
+use facet_format::{FormatSerializer, ScalarValue, SerializeError, serialize_root};
+use facet_reflect::Peek;
+
+/// Error type for INI serialization.
+#[derive(Debug)]
+pub struct IniSerializeError {
+    msg: String,
+}
+
+impl fmt::Display for IniSerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+impl std::error::Error for IniSerializeError {}
+
+impl IniSerializeError {
+    fn new(msg: impl Into<String>) -> Self {
+        Self { msg: msg.into() }
+    }
+}
+
+/// A value waiting to be written
+enum Node {
+    /// A value, already quoted if needed
+    Value(String),
+    Null,
+    Section(Vec<(String, Node)>),
+}
+
+struct Frame {
+    entries: Vec<(String, Node)>,
+    key: Option<String>,
+}
+
+/// INI serializer with buffered output.
+#[derive(Default)]
+pub struct IniSerializer {
+    stack: Vec<Frame>,
+    root: Option<Node>,
+}
+
+impl IniSerializer {
+    /// Create a new INI serializer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Render the serialized document.
+    ///
+    /// Fails if the value wasn't a struct or map, or if a key can't be
+    /// written in INI.
+    pub fn finish(self) -> Result<String, IniSerializeError> {
+        let Some(Node::Section(entries)) = self.root else {
+            return Err(IniSerializeError::new(
+                "INI documents must be structs or maps",
+            ));
+        };
+        let mut out = String::new();
+        write_section(&mut out, &mut Vec::new(), &entries)?;
+        Ok(out)
+    }
+
+    fn value(&mut self, node: Node) -> Result<(), IniSerializeError> {
+        match self.stack.last_mut() {
+            None => self.root = Some(node),
+            Some(Frame { entries, key }) => {
+                let key = key
+                    .take()
+                    .ok_or_else(|| IniSerializeError::new("value without a key"))?;
+                entries.push((key, node));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FormatSerializer for IniSerializer {
+    type Error = IniSerializeError;
+
+    fn begin_struct(&mut self) -> Result<(), Self::Error> {
+        self.stack.push(Frame {
+            entries: Vec::new(),
+            key: None,
+        });
+        Ok(())
+    }
+
+    fn field_key(&mut self, name: &str) -> Result<(), Self::Error> {
+        match self.stack.last_mut() {
+            Some(frame) => {
+                frame.key = Some(name.to_string());
+                Ok(())
+            }
+            None => Err(IniSerializeError::new("field key outside of a section")),
+        }
+    }
+
+    fn end_struct(&mut self) -> Result<(), Self::Error> {
+        match self.stack.pop() {
+            Some(frame) => self.value(Node::Section(frame.entries)),
+            None => Err(IniSerializeError::new("unbalanced end of section")),
+        }
+    }
+
+    fn begin_seq(&mut self) -> Result<(), Self::Error> {
+        let key = self
+            .stack
+            .last()
+            .and_then(|frame| frame.key.as_deref())
+            .unwrap_or("the document");
+        Err(IniSerializeError::new(alloc::format!(
+            "INI can't represent lists (in `{key}`)"
+        )))
+    }
+
+    fn end_seq(&mut self) -> Result<(), Self::Error> {
+        Err(IniSerializeError::new("unbalanced end of sequence"))
+    }
+
+    fn scalar(&mut self, scalar: ScalarValue<'_>) -> Result<(), Self::Error> {
+        let value = match scalar {
+            ScalarValue::Null => return self.value(Node::Null),
+            ScalarValue::Bool(b) => b.to_string(),
+            ScalarValue::I64(n) => n.to_string(),
+            ScalarValue::U64(n) => n.to_string(),
+            ScalarValue::I128(n) => n.to_string(),
+            ScalarValue::U128(n) => n.to_string(),
+            ScalarValue::F64(n) => n.to_string(),
+            ScalarValue::Str(s) => quote(&s),
+            ScalarValue::Bytes(_) => return self.begin_seq(),
+        };
+        self.value(Node::Value(value))
+    }
+}
+
+/// Write the keys of the section at `path`, then its subsections
+fn write_section(
+    out: &mut String,
+    path: &mut Vec<String>,
+    entries: &[(String, Node)],
+) -> Result<(), IniSerializeError> {
+    for (key, value) in entries {
+        if let Node::Value(value) = value {
+            writeln!(out, "{} = {value}", key_name(key)?).unwrap();
+        }
+    }
+    for (key, value) in entries {
+        if let Node::Section(entries) = value {
+            path.push(section_name(key)?.to_string());
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            writeln!(out, "[{}]", path.join(".")).unwrap();
+            write_section(out, path, entries)?;
+            path.pop();
+        }
+    }
+    Ok(())
+}
+
+/// `key` if it can be written on the left of `=`
+fn key_name(key: &str) -> Result<&str, IniSerializeError> {
+    let valid = !key.is_empty()
+        && key.trim() == key
+        && !key.starts_with([';', '#', '['])
+        && !key.contains(['=', ':', '\n', '\r']);
+    if valid {
+        Ok(key)
+    } else {
+        Err(IniSerializeError::new(alloc::format!(
+            "`{key}` can't be written as an INI key"
+        )))
+    }
+}
+
+/// `key` if it can be written as one part of a `[section]` header
+fn section_name(key: &str) -> Result<&str, IniSerializeError> {
+    let valid = !key.is_empty() && key.trim() == key && !key.contains(['.', '[', ']', '\n', '\r']);
+    if valid {
+        Ok(key)
+    } else {
+        Err(IniSerializeError::new(alloc::format!(
+            "`{key}` can't be written as an INI section name"
+        )))
+    }
+}
+
+/// `s` as written after `=`, quoted only when reading it back verbatim
+/// wouldn't give `s`
+fn quote(s: &str) -> String {
+    let plain =
+        !s.is_empty() && s.trim() == s && !s.starts_with('"') && !s.chars().any(char::is_control);
+    if plain {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Serialize a value to an INI string.
+///
+/// The value must be a struct or a map.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_ini::to_string;
+///
+/// #[derive(Facet)]
+/// struct Config {
+///     name: String,
+///     server: Server,
+/// }
+///
+/// #[derive(Facet)]
+/// struct Server {
+///     port: u16,
+/// }
+///
+/// let config = Config {
+///     name: "api".into(),
+///     server: Server { port: 8080 },
+/// };
+/// assert_eq!(
+///     to_string(&config).unwrap(),
+///     "name = api\n\n[server]\nport = 8080\n"
+/// );
+/// ```
+pub fn to_string<'facet, T>(value: &T) -> Result<String, SerializeError<IniSerializeError>>
+where
+    T: Facet<'facet> + ?Sized,
+{
+    peek_to_string(Peek::new(value))
+}
+
+/// Serialize a value to INI bytes.
+pub fn to_vec<'facet, T>(value: &T) -> Result<Vec<u8>, SerializeError<IniSerializeError>>
+where
+    T: Facet<'facet> + ?Sized,
+{
+    to_string(value).map(String::into_bytes)
+}
+
+/// Serialize a `Peek` instance to an INI string.
+pub fn peek_to_string<'input, 'facet>(
+    peek: Peek<'input, 'facet>,
+) -> Result<String, SerializeError<IniSerializeError>> {
+    let mut serializer = IniSerializer::new();
+    serialize_root(&mut serializer, peek)?;
+    serializer.finish().map_err(SerializeError::Backend)
+}
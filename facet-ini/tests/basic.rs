@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_format::SerializeError;
+use facet_ini::{IniErrorKind, IniSerializeError, from_str, to_string};
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Config {
+    name: String,
+    debug: bool,
+    ratio: f64,
+    motd: String,
+    mode: Mode,
+    timeout: Option<u32>,
+    server: Server,
+    remote: BTreeMap<String, Remote>,
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+    tls: Tls,
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Tls {
+    cert: String,
+    verify: bool,
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+struct Remote {
+    url: String,
+}
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+#[repr(u8)]
+enum Mode {
+    Fast,
+    Safe,
+}
+
+#[test]
+fn test_parse_sections_and_values() {
+    let ini = r#"
+; global settings
+name = api
+debug: true
+ratio = 1.5e-1
+  motd = "  hi\tthere  "  ; trailing comment
+mode = Fast
+timeout = 30
+
+[server]
+host = localhost
+port = 8080
+
+# a nested section
+[ server . tls ]
+cert = /etc/ssl/api.pem
+verify = false
+
+[remote.origin]
+url = git@example.com:api.git
+"#;
+    let config: Config = from_str(ini).unwrap();
+    assert_eq!(config.name, "api");
+    assert!(config.debug);
+    assert_eq!(config.ratio, 0.15);
+    assert_eq!(config.motd, "  hi\tthere  ");
+    assert_eq!(config.mode, Mode::Fast);
+    assert_eq!(config.timeout, Some(30));
+    assert_eq!(config.server.port, 8080);
+    assert_eq!(config.server.tls.cert, "/etc/ssl/api.pem");
+    assert_eq!(config.remote["origin"].url, "git@example.com:api.git");
+}
+
+#[test]
+fn test_repeated_section_headers_merge() {
+    let ini = "[server]\nhost = a\n[server.tls]\ncert = c\nverify = true\n[server]\nport = 1\n";
+    let server: BTreeMap<String, Server> = from_str(ini).unwrap();
+    assert_eq!(server["server"].host, "a");
+    assert_eq!(server["server"].port, 1);
+    assert!(server["server"].tls.verify);
+}
+
+#[test]
+fn test_serialize() {
+    let config = Config {
+        name: "api".into(),
+        debug: true,
+        ratio: 0.5,
+        motd: "  hello,\n\"world\"  ".into(),
+        mode: Mode::Safe,
+        timeout: None,
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+            tls: Tls {
+                cert: "/etc/ssl/api.pem".into(),
+                verify: false,
+            },
+        },
+        remote: BTreeMap::from([
+            (
+                "origin".into(),
+                Remote {
+                    url: "git@example.com:api.git".into(),
+                },
+            ),
+            (
+                "backup".into(),
+                Remote {
+                    url: "https://example.com/api.git?a=1;b=2".into(),
+                },
+            ),
+        ]),
+    };
+    let text = to_string(&config).unwrap();
+    assert_eq!(
+        text,
+        r#"name = api
+debug = true
+ratio = 0.5
+motd = "  hello,\n\"world\"  "
+mode = Safe
+
+[server]
+host = localhost
+port = 8080
+
+[server.tls]
+cert = /etc/ssl/api.pem
+verify = false
+
+[remote]
+
+[remote.backup]
+url = https://example.com/api.git?a=1;b=2
+
+[remote.origin]
+url = git@example.com:api.git
+"#
+    );
+}
+
+#[test]
+fn test_roundtrip() {
+    let config = Config {
+        name: "api".into(),
+        debug: true,
+        ratio: 0.5,
+        motd: "  hello,\n\"world\"  ".into(),
+        mode: Mode::Safe,
+        timeout: None,
+        server: Server {
+            host: "localhost".into(),
+            port: 8080,
+            tls: Tls {
+                cert: "/etc/ssl/api.pem".into(),
+                verify: false,
+            },
+        },
+        remote: BTreeMap::from([
+            (
+                "origin".into(),
+                Remote {
+                    url: "git@example.com:api.git".into(),
+                },
+            ),
+            (
+                "backup".into(),
+                Remote {
+                    url: "https://example.com/api.git?a=1;b=2".into(),
+                },
+            ),
+        ]),
+    };
+    let text = to_string(&config).unwrap();
+    assert_eq!(from_str::<Config>(&text).unwrap(), config);
+}
+
+#[test]
+fn test_serialize_errors() {
+    #[derive(Facet)]
+    struct Tags {
+        tags: Vec<String>,
+    }
+    let message = |err: SerializeError<IniSerializeError>| match err {
+        SerializeError::Backend(err) => err.to_string(),
+        other => panic!("unexpected error: {other}"),
+    };
+
+    let err = to_string(&Tags {
+        tags: vec!["a".into()],
+    })
+    .unwrap_err();
+    assert!(message(err).contains("lists (in `tags`)"));
+
+    let err = to_string(&BTreeMap::from([("a=b", 1)])).unwrap_err();
+    assert!(message(err).contains("INI key"));
+
+    let err = to_string(&42u32).unwrap_err();
+    assert!(message(err).contains("structs or maps"));
+}
+
+#[test]
+fn test_parse_errors() {
+    let parser_error = |ini: &str| match from_str::<Server>(ini).unwrap_err() {
+        facet_ini::DeserializeError::Parser(err) => err,
+        other => panic!("unexpected error: {other}"),
+    };
+
+    let err = parser_error("host = a\nhost = b\n");
+    assert_eq!(
+        err.kind(),
+        &IniErrorKind::DuplicateKey { key: "host".into() }
+    );
+    assert_eq!(err.span().unwrap().offset, 9);
+
+    let err = parser_error("tls = x\n[tls]\n");
+    assert!(matches!(err.kind(), IniErrorKind::DuplicateKey { .. }));
+
+    let err = parser_error("host = a\njust some words\n");
+    assert_eq!(err.kind(), &IniErrorKind::InvalidLine);
+    assert_eq!(err.span().unwrap().offset, 9);
+
+    let err = parser_error("[server.]\n");
+    assert!(matches!(err.kind(), IniErrorKind::InvalidSection { .. }));
+
+    let err = parser_error("host = \"a\n");
+    assert_eq!(err.kind(), &IniErrorKind::UnterminatedString);
+
+    let err = parser_error("host = \"a\" b\n");
+    assert_eq!(err.kind(), &IniErrorKind::TrailingCharacters);
+
+    let err = from_str::<Server>("host = a\nport = eighty\n").unwrap_err();
+    assert!(err.to_string().contains("eighty"), "{err}");
+}
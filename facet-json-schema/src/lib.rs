@@ -26,6 +26,8 @@ use alloc::collections::BTreeMap;
 use alloc::string::String;
 use alloc::vec::Vec;
 
+pub mod ui;
+
 use facet::Facet;
 use facet_core::{Def, Field, Shape, StructKind, Type, UserType};
 
@@ -93,6 +95,23 @@ pub struct JsonSchema {
     /// Constant value
     #[facet(rename = "const")]
     pub const_: Option<String>,
+
+    /// Display order of a property, from `#[facet(order = N)]`
+    #[facet(rename = "x-order")]
+    pub order: Option<i64>,
+
+    /// Display group of a property, from `#[facet(group = "...")]`
+    #[facet(rename = "x-group")]
+    pub group: Option<String>,
+
+    /// Unit a numeric property is measured in, from `#[facet(unit = "...")]`
+    #[facet(rename = "x-unit")]
+    pub unit: Option<String>,
+
+    /// Codec binary formats compress a property with, from
+    /// `#[facet(compress = "...")]`
+    #[facet(rename = "x-compression")]
+    pub compression: Option<String>,
 }
 
 /// JSON Schema type
@@ -145,6 +164,10 @@ impl JsonSchema {
             description: None,
             title: None,
             const_: None,
+            order: None,
+            group: None,
+            unit: None,
+            compression: None,
         }
     }
 
@@ -426,7 +449,11 @@ impl SchemaContext {
                     }
 
                     let field_name = field.rename.unwrap_or(field.name);
-                    let field_schema = self.schema_for_shape(field.shape.get());
+                    let mut field_schema = self.schema_for_shape(field.shape.get());
+                    field_schema.order = field.order();
+                    field_schema.group = field.group().map(String::from);
+                    field_schema.unit = field.unit().map(String::from);
+                    field_schema.compression = field.compression().map(String::from);
 
                     // Check if field is required (not Option and no default)
                     let is_option = matches!(field.shape.get().def, Def::Option(_));
@@ -585,6 +612,58 @@ mod tests {
         insta::assert_snapshot!(schema);
     }
 
+    #[test]
+    fn test_field_order_and_group() {
+        #[derive(Facet)]
+        struct Config {
+            #[facet(order = 2, group = "network")]
+            port: u16,
+            #[facet(order = 1, group = "network")]
+            host: String,
+            name: String,
+        }
+
+        let schema = schema_for::<Config>();
+        let properties = schema.properties.unwrap();
+        assert_eq!(properties["port"].order, Some(2));
+        assert_eq!(properties["port"].group.as_deref(), Some("network"));
+        assert_eq!(properties["host"].order, Some(1));
+        assert_eq!(properties["name"].order, None);
+        assert_eq!(properties["name"].group, None);
+    }
+
+    #[test]
+    fn test_field_unit() {
+        #[derive(Facet)]
+        struct Upload {
+            #[facet(unit = "bytes")]
+            size: u64,
+            name: String,
+        }
+
+        let schema = schema_for::<Upload>();
+        let properties = schema.properties.unwrap();
+        assert_eq!(properties["size"].unit.as_deref(), Some("bytes"));
+        assert_eq!(properties["name"].unit, None);
+        assert!(to_schema::<Upload>().contains(r#""x-unit": "bytes""#));
+    }
+
+    #[test]
+    fn test_field_compression() {
+        #[derive(Facet)]
+        struct Attachment {
+            name: String,
+            #[facet(compress = "zstd")]
+            body: Vec<u8>,
+        }
+
+        let schema = schema_for::<Attachment>();
+        let properties = schema.properties.unwrap();
+        assert_eq!(properties["body"].compression.as_deref(), Some("zstd"));
+        assert_eq!(properties["name"].compression, None);
+        assert!(to_schema::<Attachment>().contains(r#""x-compression": "zstd""#));
+    }
+
     #[test]
     fn test_vec() {
         #[derive(Facet)]
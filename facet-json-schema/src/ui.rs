@@ -0,0 +1,423 @@
+//! Form-rendering metadata for admin frontends.
+//!
+//! [`to_ui_schema`] describes a type as a list of form fields: display order,
+//! labels and help text from doc comments, a widget for each field, and the
+//! choices of unit-only enums. Widgets are inferred from the field type and can
+//! be overridden with `#[facet(ui(...))]`:
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_json_schema::ui::ui_form_for;
+//!
+//! #[derive(Facet)]
+//! struct Login {
+//!     /// User name
+//!     #[facet(ui(placeholder = "jane@example.com"))]
+//!     user: String,
+//!     #[facet(ui(widget = "password"))]
+//!     secret: String,
+//! }
+//!
+//! let form = ui_form_for::<Login>();
+//! assert_eq!(form.fields[0].label, "User name");
+//! assert_eq!(form.fields[0].placeholder.as_deref(), Some("jane@example.com"));
+//! assert_eq!(form.fields[1].widget, "password");
+//! ```
+//!
+//! Recognized `ui(...)` keys are `widget`, `label`, `placeholder`, `readonly`
+//! and `hidden`; hidden and skipped fields are left out of the form.
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use facet::Facet;
+use facet_core::{Def, EnumType, Field, FieldFlags, Shape, StructKind, Type, UserType};
+
+/// A form for editing one type.
+#[derive(Debug, Clone, Facet)]
+pub struct UiForm {
+    /// Form title (type name)
+    pub title: String,
+
+    /// Description from the type's doc comments
+    pub description: Option<String>,
+
+    /// Fields in display order
+    pub fields: Vec<UiField>,
+}
+
+/// One input of a form.
+#[derive(Debug, Clone, Facet)]
+pub struct UiField {
+    /// Serialized field name
+    pub name: String,
+
+    /// Label, from `ui(label = ...)`, the first doc line, or the field name
+    pub label: String,
+
+    /// Remaining doc lines
+    pub help: Option<String>,
+
+    /// Widget to render: `checkbox`, `number`, `text`, `password`, `select`,
+    /// `variant`, `group`, `list`, `map`, or whatever `ui(widget = ...)` says
+    pub widget: String,
+
+    /// Whether a value must be provided (not an `Option` and no default)
+    pub required: bool,
+
+    /// Whether the field should be shown but not edited
+    pub readonly: bool,
+
+    /// Display group, from `#[facet(group = "...")]`
+    pub group: Option<String>,
+
+    /// Placeholder text, from `ui(placeholder = ...)`
+    pub placeholder: Option<String>,
+
+    /// For `select` and `variant`: the allowed variants
+    pub choices: Option<Vec<UiChoice>>,
+
+    /// For `group`: the nested fields
+    pub fields: Option<Vec<UiField>>,
+
+    /// For `list` and `map`: the element (or value) input
+    pub item: Option<Box<UiField>>,
+}
+
+/// One choice of a `select` or `variant` widget.
+#[derive(Debug, Clone, Facet)]
+pub struct UiChoice {
+    /// Serialized variant name
+    pub value: String,
+
+    /// Label, from the variant's first doc line or its name
+    pub label: String,
+}
+
+/// Describe a type as a form.
+pub fn ui_form_for<T: Facet<'static>>() -> UiForm {
+    let shape = T::SHAPE;
+    let mut ctx = UiContext::default();
+    let fields = match &shape.ty {
+        Type::User(UserType::Struct(st)) if st.kind == StructKind::Struct => {
+            ctx.fields_for_struct(shape, st.fields)
+        }
+        _ => {
+            let mut field = ctx.field_for_shape(shape);
+            field.name = shape.type_identifier.to_string();
+            field.label = humanize(shape.type_identifier);
+            field.required = true;
+            alloc::vec![field]
+        }
+    };
+    UiForm {
+        title: shape.type_identifier.to_string(),
+        description: doc_text(shape.doc),
+        fields,
+    }
+}
+
+/// Describe a type as a form, serialized to JSON.
+pub fn to_ui_schema<T: Facet<'static>>() -> String {
+    let form = ui_form_for::<T>();
+    facet_json::to_string_pretty(&form).expect("UI schema serialization should not fail")
+}
+
+/// Tracks the structs being expanded, so recursive types stop at an empty group.
+#[derive(Default)]
+struct UiContext {
+    in_progress: Vec<&'static str>,
+}
+
+impl UiContext {
+    fn fields_for_struct(
+        &mut self,
+        shape: &'static Shape,
+        fields: &'static [Field],
+    ) -> Vec<UiField> {
+        if self.in_progress.contains(&shape.type_identifier) {
+            return Vec::new();
+        }
+        self.in_progress.push(shape.type_identifier);
+
+        let mut visible: Vec<&'static Field> = fields
+            .iter()
+            .filter(|f| !f.flags.contains(FieldFlags::SKIP) && !f.has_attr(Some("ui"), "hidden"))
+            .collect();
+        // Ordered fields first, then the rest in declaration order
+        visible.sort_by_key(|f| (f.order().is_none(), f.order()));
+
+        let out = visible
+            .into_iter()
+            .map(|f| self.field_for_field(f))
+            .collect();
+        self.in_progress.pop();
+        out
+    }
+
+    fn field_for_field(&mut self, field: &'static Field) -> UiField {
+        let shape = field.shape();
+        let mut ui = self.field_for_shape(shape);
+
+        ui.name = field.effective_name().to_string();
+        ui.label = ui_str(field, "label")
+            .map(String::from)
+            .or_else(|| field.doc.first().map(|l| l.trim().to_string()))
+            .unwrap_or_else(|| humanize(field.name));
+        ui.help = field.doc.get(1..).and_then(doc_text);
+        ui.required = !matches!(shape.def, Def::Option(_)) && !field.has_default();
+        ui.readonly = field.has_attr(Some("ui"), "readonly");
+        ui.group = field.group().map(String::from);
+        ui.placeholder = ui_str(field, "placeholder").map(String::from);
+
+        if let Some(widget) = ui_str(field, "widget") {
+            ui.widget = widget.to_string();
+        } else if field.is_sensitive() {
+            ui.widget = "password".into();
+        }
+        ui
+    }
+
+    /// Widget and nested structure for a shape; the caller fills in the field metadata.
+    fn field_for_shape(&mut self, shape: &'static Shape) -> UiField {
+        let mut ui = UiField {
+            name: String::new(),
+            label: String::new(),
+            help: None,
+            widget: String::new(),
+            required: true,
+            readonly: false,
+            group: None,
+            placeholder: None,
+            choices: None,
+            fields: None,
+            item: None,
+        };
+
+        match &shape.def {
+            Def::Scalar => ui.widget = scalar_widget(shape).into(),
+            // Option<T> renders as T; `required` is decided by the field
+            Def::Option(opt) => return self.field_for_shape(opt.t),
+            Def::List(list) => {
+                ui.widget = "list".into();
+                ui.item = Some(Box::new(self.field_for_shape(list.t)));
+            }
+            Def::Array(arr) => {
+                ui.widget = "list".into();
+                ui.item = Some(Box::new(self.field_for_shape(arr.t)));
+            }
+            Def::Set(set) => {
+                ui.widget = "list".into();
+                ui.item = Some(Box::new(self.field_for_shape(set.t)));
+            }
+            Def::Map(map) => {
+                ui.widget = "map".into();
+                ui.item = Some(Box::new(self.field_for_shape(map.v)));
+            }
+            _ => match &shape.ty {
+                Type::User(UserType::Struct(st)) => match st.kind {
+                    StructKind::Struct => {
+                        ui.widget = "group".into();
+                        ui.fields = Some(self.fields_for_struct(shape, st.fields));
+                    }
+                    StructKind::TupleStruct if st.fields.len() == 1 => {
+                        return self.field_for_shape(st.fields[0].shape());
+                    }
+                    _ => ui.widget = "text".into(),
+                },
+                Type::User(UserType::Enum(en)) => {
+                    ui.widget = enum_widget(en).into();
+                    ui.choices = Some(choices(en));
+                }
+                _ => match shape.inner {
+                    Some(inner) => return self.field_for_shape(inner),
+                    None => ui.widget = "text".into(),
+                },
+            },
+        }
+        ui
+    }
+}
+
+fn scalar_widget(shape: &'static Shape) -> &'static str {
+    match shape.type_identifier {
+        "bool" => "checkbox",
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128"
+        | "isize" | "f32" | "f64" => "number",
+        _ => "text",
+    }
+}
+
+fn enum_widget(en: &EnumType) -> &'static str {
+    if en
+        .variants
+        .iter()
+        .all(|v| matches!(v.data.kind, StructKind::Unit))
+    {
+        "select"
+    } else {
+        "variant"
+    }
+}
+
+fn choices(en: &EnumType) -> Vec<UiChoice> {
+    en.variants
+        .iter()
+        .map(|v| UiChoice {
+            value: v.name.to_string(),
+            label: v
+                .doc
+                .first()
+                .map(|l| l.trim().to_string())
+                .unwrap_or_else(|| humanize(v.name)),
+        })
+        .collect()
+}
+
+fn ui_str(field: &Field, key: &str) -> Option<&'static str> {
+    field
+        .get_attr(Some("ui"), key)
+        .and_then(|a| a.get_as::<&'static str>())
+        .copied()
+}
+
+fn doc_text(lines: &[&str]) -> Option<String> {
+    let text = lines.join("\n").trim().to_string();
+    if text.is_empty() { None } else { Some(text) }
+}
+
+/// `max_connections` -> `Max connections`
+fn humanize(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for (i, c) in name.trim_start_matches("r#").chars().enumerate() {
+        if c == '_' {
+            out.push(' ');
+        } else if i == 0 {
+            out.extend(c.to_uppercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Level {
+        /// Only errors
+        Error,
+        Warn,
+        Info,
+    }
+
+    #[derive(Facet)]
+    struct Tls {
+        cert_path: String,
+    }
+
+    /// Server settings
+    #[derive(Facet)]
+    struct Settings {
+        /// Listen port
+        /// Must be above 1024 unless running as root
+        #[facet(order = 1, group = "network")]
+        port: u16,
+        #[facet(order = 0)]
+        name: String,
+        #[facet(sensitive)]
+        api_key: String,
+        #[facet(ui(widget = "textarea", label = "Message of the day", readonly))]
+        motd: Option<String>,
+        #[facet(ui(hidden))]
+        internal_id: u64,
+        level: Level,
+        tls: Tls,
+        tags: Vec<String>,
+        #[facet(default)]
+        verbose: bool,
+    }
+
+    #[test]
+    fn test_fields_in_display_order() {
+        let form = ui_form_for::<Settings>();
+        assert_eq!(form.title, "Settings");
+        assert_eq!(form.description.as_deref(), Some("Server settings"));
+        let names: Vec<&str> = form.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "name", "port", "api_key", "motd", "level", "tls", "tags", "verbose"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_labels_widgets_and_hints() {
+        let form = ui_form_for::<Settings>();
+        let field = |name: &str| form.fields.iter().find(|f| f.name == name).unwrap();
+
+        let port = field("port");
+        assert_eq!(port.label, "Listen port");
+        assert_eq!(
+            port.help.as_deref(),
+            Some("Must be above 1024 unless running as root")
+        );
+        assert_eq!(port.widget, "number");
+        assert_eq!(port.group.as_deref(), Some("network"));
+
+        assert_eq!(field("name").label, "Name");
+        assert_eq!(field("api_key").widget, "password");
+
+        let motd = field("motd");
+        assert_eq!(motd.widget, "textarea");
+        assert_eq!(motd.label, "Message of the day");
+        assert!(motd.readonly);
+        assert!(!motd.required);
+
+        let verbose = field("verbose");
+        assert_eq!(verbose.widget, "checkbox");
+        assert!(!verbose.required);
+    }
+
+    #[test]
+    fn test_nested_shapes() {
+        let form = ui_form_for::<Settings>();
+        let field = |name: &str| form.fields.iter().find(|f| f.name == name).unwrap();
+
+        let level = field("level");
+        assert_eq!(level.widget, "select");
+        let choices = level.choices.as_ref().unwrap();
+        assert_eq!(choices[0].value, "Error");
+        assert_eq!(choices[0].label, "Only errors");
+        assert_eq!(choices[1].label, "Warn");
+
+        let tls = field("tls");
+        assert_eq!(tls.widget, "group");
+        assert_eq!(tls.fields.as_ref().unwrap()[0].label, "Cert path");
+
+        let tags = field("tags");
+        assert_eq!(tags.widget, "list");
+        assert_eq!(tags.item.as_ref().unwrap().widget, "text");
+    }
+
+    #[test]
+    fn test_recursive_type() {
+        #[derive(Facet)]
+        struct Node {
+            label: String,
+            children: Vec<Node>,
+        }
+
+        let form = ui_form_for::<Node>();
+        let item = form.fields[1].item.as_ref().unwrap();
+        assert_eq!(item.widget, "group");
+        assert_eq!(item.fields.as_ref().unwrap().len(), 0);
+        assert!(to_ui_schema::<Node>().contains("\"widget\": \"list\""));
+    }
+}
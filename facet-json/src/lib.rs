@@ -32,11 +32,12 @@ pub use axum::{Json, JsonRejection};
 pub use parser::{JsonError, JsonParser};
 pub use raw_json::RawJson;
 pub use serializer::{
-    JsonSerializeError, JsonSerializer, SerializeOptions, peek_to_string, peek_to_string_pretty,
-    peek_to_string_with_options, peek_to_writer_std, peek_to_writer_std_pretty,
-    peek_to_writer_std_with_options, to_string, to_string_pretty, to_string_with_options, to_vec,
-    to_vec_pretty, to_vec_with_options, to_writer_std, to_writer_std_pretty,
-    to_writer_std_with_options,
+    JsonSerializeError, JsonSerializer, NonFiniteFloats, SerializeOptions, dirty_to_string,
+    facet_map_to_string, facet_vec_to_string, peek_to_string, peek_to_string_pretty,
+    peek_to_string_with_fields, peek_to_string_with_options, peek_to_writer_std,
+    peek_to_writer_std_pretty, peek_to_writer_std_with_options, to_string, to_string_pretty,
+    to_string_with_fields, to_string_with_options, to_vec, to_vec_pretty, to_vec_with_options,
+    to_writer_std, to_writer_std_pretty, to_writer_std_with_options,
 };
 
 // Re-export DeserializeError for convenience
@@ -195,4 +196,4 @@ pub use streaming::from_async_reader_futures;
 #[cfg(feature = "tokio")]
 pub use streaming::from_async_reader_tokio;
 #[cfg(feature = "streaming")]
-pub use streaming::from_reader;
+pub use streaming::{for_each_element, for_each_value, from_reader};
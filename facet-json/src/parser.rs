@@ -7,6 +7,7 @@ use facet_format::{
     ContainerKind, FieldEvidence, FieldKey, FieldLocationHint, FormatParser, ParseEvent,
     ProbeStream, ScalarValue,
 };
+use facet_reflect::Span;
 
 use crate::adapter::{SliceAdapter, SpannedAdapterToken, Token as AdapterToken};
 pub use crate::error::JsonError;
@@ -25,6 +26,12 @@ pub struct JsonParser<'de> {
     root_complete: bool,
     /// Absolute offset (in bytes) of the next unread token.
     current_offset: usize,
+    /// Span of the token that starts the most recently produced event.
+    produced_span: Option<Span>,
+    /// Span of the event cached in `event_peek`.
+    peeked_span: Option<Span>,
+    /// Span of the most recently consumed event.
+    event_span: Option<Span>,
 }
 
 #[derive(Debug)]
@@ -73,12 +80,16 @@ impl<'de> JsonParser<'de> {
             root_started: false,
             root_complete: false,
             current_offset: 0,
+            produced_span: None,
+            peeked_span: None,
+            event_span: None,
         }
     }
 
     fn consume_token(&mut self) -> Result<SpannedAdapterToken<'de>, JsonError> {
         let token = self.adapter.next_token().map_err(JsonError::from)?;
         self.current_offset = token.span.offset + token.span.len;
+        self.produced_span = Some(token.span);
         Ok(token)
     }
 
@@ -313,6 +324,8 @@ impl<'de> JsonParser<'de> {
                         }
                         AdapterToken::String(name) => {
                             self.expect_colon()?;
+                            // Report the key itself, not the colon after it
+                            self.produced_span = Some(token.span);
                             if let Some(ContextState::Object(state)) = self.stack.last_mut() {
                                 *state = ObjectState::Value;
                             }
@@ -557,9 +570,12 @@ impl<'de> FormatParser<'de> for JsonParser<'de> {
 
     fn next_event(&mut self) -> Result<Option<ParseEvent<'de>>, Self::Error> {
         if let Some(event) = self.event_peek.take() {
+            self.event_span = self.peeked_span.take();
             return Ok(Some(event));
         }
-        self.produce_event()
+        let event = self.produce_event()?;
+        self.event_span = self.produced_span;
+        Ok(event)
     }
 
     fn peek_event(&mut self) -> Result<Option<ParseEvent<'de>>, Self::Error> {
@@ -569,10 +585,15 @@ impl<'de> FormatParser<'de> for JsonParser<'de> {
         let event = self.produce_event()?;
         if let Some(ref e) = event {
             self.event_peek = Some(e.clone());
+            self.peeked_span = self.produced_span;
         }
         Ok(event)
     }
 
+    fn current_span(&self) -> Option<Span> {
+        self.event_span
+    }
+
     fn skip_value(&mut self) -> Result<(), Self::Error> {
         debug_assert!(
             self.event_peek.is_none(),
@@ -694,7 +715,6 @@ impl<'de> facet_format::FormatJitParser<'de> for JsonParser<'de> {
 
     fn jit_error(&self, _input: &'de [u8], error_pos: usize, error_code: i32) -> Self::Error {
         use crate::error::JsonErrorKind;
-        use facet_reflect::Span;
 
         let kind = match error_code {
             -100 => JsonErrorKind::UnexpectedEof { expected: "value" },
@@ -1,18 +1,63 @@
 extern crate alloc;
 
-use alloc::{string::String, vec::Vec};
+use alloc::{string::String, sync::Arc, vec::Vec};
 
 use facet_core::Facet;
-use facet_format::{FormatSerializer, ScalarValue, SerializeError, serialize_root};
-use facet_reflect::Peek;
+use facet_format::{
+    FieldCipher, FormatSerializer, ScalarValue, SerializeError, serialize_dirty,
+    serialize_facet_map, serialize_facet_vec, serialize_root, serialize_with_fields,
+};
+use facet_reflect::{DirtyPaths, FacetMap, FacetVec, Peek};
 
 /// Options for JSON serialization.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct SerializeOptions {
     /// Whether to pretty-print with indentation (default: false)
     pub pretty: bool,
     /// Indentation string for pretty-printing (default: "  ")
     pub indent: &'static str,
+    /// Rename profile for field names, e.g. `"json"` (default: none)
+    ///
+    /// See [`SerializeOptions::rename_profile`].
+    pub rename_profile: Option<&'static str>,
+    /// Cipher for `#[facet(encrypt)]` fields (default: none)
+    ///
+    /// See [`SerializeOptions::cipher`].
+    pub cipher: Option<Arc<dyn FieldCipher>>,
+    /// How NaN and infinite floats are written (default: [`NonFiniteFloats::Null`])
+    pub non_finite_floats: NonFiniteFloats,
+    /// Whether integers beyond ±(2^53 - 1) are written as strings (default: false)
+    ///
+    /// See [`SerializeOptions::large_integers_as_strings`].
+    pub large_integers_as_strings: bool,
+}
+
+/// How NaN and infinite floats, which JSON has no syntax for, are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NonFiniteFloats {
+    /// Fail serialization.
+    Reject,
+    /// Write `null`.
+    #[default]
+    Null,
+    /// Write `"NaN"`, `"Infinity"` or `"-Infinity"`.
+    String,
+}
+
+/// The largest integer a JavaScript number (an `f64`) holds exactly.
+const MAX_SAFE_INTEGER: u128 = (1 << 53) - 1;
+
+impl core::fmt::Debug for SerializeOptions {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SerializeOptions")
+            .field("pretty", &self.pretty)
+            .field("indent", &self.indent)
+            .field("rename_profile", &self.rename_profile)
+            .field("cipher", &self.cipher.as_ref().map(|_| ".."))
+            .field("non_finite_floats", &self.non_finite_floats)
+            .field("large_integers_as_strings", &self.large_integers_as_strings)
+            .finish()
+    }
 }
 
 impl Default for SerializeOptions {
@@ -20,6 +65,10 @@ impl Default for SerializeOptions {
         Self {
             pretty: false,
             indent: "  ",
+            rename_profile: None,
+            cipher: None,
+            non_finite_floats: NonFiniteFloats::Null,
+            large_integers_as_strings: false,
         }
     }
 }
@@ -42,6 +91,68 @@ impl SerializeOptions {
         self.pretty = true;
         self
     }
+
+    /// Write fields under the names from a rename profile.
+    ///
+    /// Fields declared with `#[facet(rename(json = "fooBar", db = "foo_bar"))]`
+    /// use the name given for `profile`; other fields keep their usual name.
+    ///
+    /// ```
+    /// use facet::Facet;
+    /// use facet_json::{SerializeOptions, to_string_with_options};
+    ///
+    /// #[derive(Facet)]
+    /// struct User {
+    ///     #[facet(rename(json = "userId", db = "user_id"))]
+    ///     user_id: u64,
+    ///     name: String,
+    /// }
+    ///
+    /// let user = User { user_id: 7, name: "ada".into() };
+    /// let options = SerializeOptions::new().rename_profile("json");
+    /// assert_eq!(
+    ///     to_string_with_options(&user, &options).unwrap(),
+    ///     r#"{"userId":7,"name":"ada"}"#
+    /// );
+    /// ```
+    pub fn rename_profile(mut self, profile: &'static str) -> Self {
+        self.rename_profile = Some(profile);
+        self
+    }
+
+    /// Write `#[facet(encrypt)]` fields as strings encrypted by `cipher`.
+    ///
+    /// Without a cipher, serializing a value with encrypted fields fails.
+    /// Read them back with a `FormatDeserializer` set up
+    /// [`with_cipher`](facet_format::FormatDeserializer::with_cipher).
+    pub fn cipher(mut self, cipher: Arc<dyn FieldCipher>) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    /// Set how NaN and infinite floats are written.
+    pub fn non_finite_floats(mut self, policy: NonFiniteFloats) -> Self {
+        self.non_finite_floats = policy;
+        self
+    }
+
+    /// Write integers beyond ±(2^53 - 1) as strings, so JavaScript consumers,
+    /// which read every number as an `f64`, don't silently round them.
+    ///
+    /// ```
+    /// use facet_json::{SerializeOptions, to_string_with_options};
+    ///
+    /// let ids = [7u64, u64::MAX];
+    /// let options = SerializeOptions::new().large_integers_as_strings();
+    /// assert_eq!(
+    ///     to_string_with_options(&ids, &options).unwrap(),
+    ///     r#"[7,"18446744073709551615"]"#
+    /// );
+    /// ```
+    pub fn large_integers_as_strings(mut self) -> Self {
+        self.large_integers_as_strings = true;
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -211,6 +322,34 @@ impl JsonSerializer {
             }
         }
     }
+
+    /// Writes an integer's digits, quoted if it's too large for JavaScript
+    /// and the options ask for that.
+    fn write_integer(&mut self, digits: &[u8], magnitude: u128) {
+        let quoted = self.options.large_integers_as_strings && magnitude > MAX_SAFE_INTEGER;
+        if quoted {
+            self.out.push(b'"');
+        }
+        self.out.extend_from_slice(digits);
+        if quoted {
+            self.out.push(b'"');
+        }
+    }
+
+    fn write_non_finite(&mut self, v: f64) -> Result<(), JsonSerializeError> {
+        match self.options.non_finite_floats {
+            NonFiniteFloats::Reject => {
+                return Err(JsonSerializeError {
+                    msg: "NaN and infinite floats cannot be represented in JSON",
+                });
+            }
+            NonFiniteFloats::Null => self.out.extend_from_slice(b"null"),
+            NonFiniteFloats::String if v.is_nan() => self.out.extend_from_slice(b"\"NaN\""),
+            NonFiniteFloats::String if v > 0.0 => self.out.extend_from_slice(b"\"Infinity\""),
+            NonFiniteFloats::String => self.out.extend_from_slice(b"\"-Infinity\""),
+        }
+        Ok(())
+    }
 }
 
 /// Check if any byte in the u128 equals the target byte.
@@ -245,6 +384,14 @@ impl Default for JsonSerializer {
 impl FormatSerializer for JsonSerializer {
     type Error = JsonSerializeError;
 
+    fn rename_profile(&self) -> Option<&str> {
+        self.options.rename_profile
+    }
+
+    fn field_cipher(&self) -> Option<&dyn FieldCipher> {
+        self.options.cipher.as_deref()
+    }
+
     fn begin_struct(&mut self) -> Result<(), Self::Error> {
         self.before_value()?;
         self.out.push(b'{');
@@ -325,32 +472,32 @@ impl FormatSerializer for JsonSerializer {
             }
             ScalarValue::I64(v) => {
                 #[cfg(feature = "fast")]
-                self.out
-                    .extend_from_slice(itoa::Buffer::new().format(v).as_bytes());
+                self.write_integer(
+                    itoa::Buffer::new().format(v).as_bytes(),
+                    v.unsigned_abs().into(),
+                );
                 #[cfg(not(feature = "fast"))]
-                self.out.extend_from_slice(v.to_string().as_bytes());
+                self.write_integer(v.to_string().as_bytes(), v.unsigned_abs().into());
             }
             ScalarValue::U64(v) => {
                 #[cfg(feature = "fast")]
-                self.out
-                    .extend_from_slice(itoa::Buffer::new().format(v).as_bytes());
+                self.write_integer(itoa::Buffer::new().format(v).as_bytes(), v.into());
                 #[cfg(not(feature = "fast"))]
-                self.out.extend_from_slice(v.to_string().as_bytes());
+                self.write_integer(v.to_string().as_bytes(), v.into());
             }
             ScalarValue::I128(v) => {
                 #[cfg(feature = "fast")]
-                self.out
-                    .extend_from_slice(itoa::Buffer::new().format(v).as_bytes());
+                self.write_integer(itoa::Buffer::new().format(v).as_bytes(), v.unsigned_abs());
                 #[cfg(not(feature = "fast"))]
-                self.out.extend_from_slice(v.to_string().as_bytes());
+                self.write_integer(v.to_string().as_bytes(), v.unsigned_abs());
             }
             ScalarValue::U128(v) => {
                 #[cfg(feature = "fast")]
-                self.out
-                    .extend_from_slice(itoa::Buffer::new().format(v).as_bytes());
+                self.write_integer(itoa::Buffer::new().format(v).as_bytes(), v);
                 #[cfg(not(feature = "fast"))]
-                self.out.extend_from_slice(v.to_string().as_bytes());
+                self.write_integer(v.to_string().as_bytes(), v);
             }
+            ScalarValue::F64(v) if !v.is_finite() => self.write_non_finite(v)?,
             ScalarValue::F64(v) => {
                 #[cfg(feature = "fast")]
                 self.out
@@ -616,6 +763,127 @@ pub fn peek_to_string_with_options<'input, 'facet>(
     Ok(String::from_utf8(bytes).expect("JSON output should always be valid UTF-8"))
 }
 
+// ── Type-erased collections ──
+
+/// Serialize a [`FacetVec`] to a JSON array of `{"type": ..., "value": ...}`
+/// objects.
+///
+/// # Example
+///
+/// ```
+/// use facet_json::facet_vec_to_string;
+/// use facet_reflect::FacetVec;
+///
+/// let mut events = FacetVec::new();
+/// events.push(7u8);
+/// events.push(String::from("done"));
+/// assert_eq!(
+///     facet_vec_to_string(&events).unwrap(),
+///     r#"[{"type":"u8","value":7},{"type":"String","value":"done"}]"#
+/// );
+/// ```
+pub fn facet_vec_to_string(
+    values: &FacetVec,
+) -> Result<String, SerializeError<JsonSerializeError>> {
+    let mut serializer = JsonSerializer::new();
+    serialize_facet_vec(&mut serializer, values)?;
+    Ok(String::from_utf8(serializer.finish()).expect("JSON output should always be valid UTF-8"))
+}
+
+/// Serialize a [`FacetMap`] to a JSON object mapping each key to a
+/// `{"type": ..., "value": ...}` object.
+pub fn facet_map_to_string(
+    values: &FacetMap,
+) -> Result<String, SerializeError<JsonSerializeError>> {
+    let mut serializer = JsonSerializer::new();
+    serialize_facet_map(&mut serializer, values)?;
+    Ok(String::from_utf8(serializer.finish()).expect("JSON output should always be valid UTF-8"))
+}
+
+// ── Dirty tracking ──
+
+/// Serialize the parts of a value marked in `dirty` to a JSON object mapping
+/// each changed path to its new value.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_json::dirty_to_string;
+/// use facet_reflect::{DirtyPaths, DirtyTracked};
+///
+/// #[derive(Facet)]
+/// #[facet(pod)]
+/// struct Server { host: String, port: u16 }
+///
+/// let mut server = Server { host: "localhost".into(), port: 8080 };
+/// let mut tracked = DirtyTracked::new(&mut server, DirtyPaths::new());
+/// tracked.set("port", 8081u16).unwrap();
+/// assert_eq!(
+///     dirty_to_string(tracked.peek(), tracked.sink()).unwrap(),
+///     r#"{"port":8081}"#
+/// );
+/// ```
+pub fn dirty_to_string<'mem, 'facet>(
+    root: Peek<'mem, 'facet>,
+    dirty: &DirtyPaths,
+) -> Result<String, SerializeError<JsonSerializeError>> {
+    let mut serializer = JsonSerializer::new();
+    serialize_dirty(&mut serializer, root, dirty)?;
+    Ok(String::from_utf8(serializer.finish()).expect("JSON output should always be valid UTF-8"))
+}
+
+// ── Projected serialization ──
+
+/// Serialize only the selected field paths of a value to a JSON string.
+///
+/// Selectors are dotted field paths with bracketed list indices, see
+/// [`facet_format::FieldProjection`] for the full syntax. This makes it easy
+/// to implement sparse field sets (`?fields=...`) for any response type.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_json::to_string_with_fields;
+///
+/// #[derive(Facet)]
+/// struct Item { name: String, price: u32 }
+///
+/// #[derive(Facet)]
+/// struct Order { id: u64, note: String, items: Vec<Item> }
+///
+/// let order = Order {
+///     id: 7,
+///     note: "leave at door".into(),
+///     items: vec![Item { name: "tea".into(), price: 4 }],
+/// };
+/// let json = to_string_with_fields(&order, &["id", "items[*].price"]).unwrap();
+/// assert_eq!(json, r#"{"id":7,"items":[{"price":4}]}"#);
+/// ```
+pub fn to_string_with_fields<'facet, T>(
+    value: &'_ T,
+    fields: &[&str],
+) -> Result<String, SerializeError<JsonSerializeError>>
+where
+    T: Facet<'facet> + ?Sized,
+{
+    peek_to_string_with_fields(Peek::new(value), fields)
+}
+
+/// Serialize only the selected field paths of a `Peek` to a JSON string.
+///
+/// See [`to_string_with_fields`].
+pub fn peek_to_string_with_fields<'input, 'facet>(
+    peek: Peek<'input, 'facet>,
+    fields: &[&str],
+) -> Result<String, SerializeError<JsonSerializeError>> {
+    let mut serializer = JsonSerializer::new();
+    serialize_with_fields(&mut serializer, peek, fields)?;
+    let bytes = serializer.finish();
+    Ok(String::from_utf8(bytes).expect("JSON output should always be valid UTF-8"))
+}
+
 // ── Writer-based serialization (std::io::Write) ──
 
 /// Serialize a value to JSON and write it to a `std::io::Write` writer.
@@ -9,6 +9,7 @@ use alloc::borrow::Cow;
 use alloc::rc::Rc;
 use alloc::vec::Vec;
 use core::cell::RefCell;
+use core::ops::ControlFlow;
 
 use corosensei::{Coroutine, CoroutineResult};
 use facet_core::Facet;
@@ -103,6 +104,242 @@ where
     }
 }
 
+/// Deserialize a top-level JSON array from a synchronous reader, one element at a time.
+///
+/// Each element is deserialized into `T` and handed to `f` before any more input
+/// is read, so memory use is bounded by one element plus the read buffer rather
+/// than by the whole array. Returns the number of elements visited.
+///
+/// `f` returns [`ControlFlow::Break`] to stop early, in which case the rest of
+/// the input is left unread, or an error to stop and return it. Deserialization
+/// errors are converted into the same error type.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use std::ops::ControlFlow;
+/// use facet::Facet;
+/// use facet_json::{DeserializeError, JsonError, for_each_element};
+///
+/// #[derive(Facet, Debug)]
+/// struct Event {
+///     id: u32,
+/// }
+///
+/// let json = br#"[{"id": 1}, {"id": 2}, {"id": 3}]"#;
+/// let mut total = 0;
+/// let count = for_each_element(
+///     Cursor::new(json),
+///     |event: Event| -> Result<_, DeserializeError<JsonError>> {
+///         total += event.id;
+///         Ok(ControlFlow::Continue(()))
+///     },
+/// )
+/// .unwrap();
+/// assert_eq!((count, total), (3, 6));
+/// ```
+#[cfg(feature = "std")]
+pub fn for_each_element<R, T, E, F>(reader: R, f: F) -> Result<usize, E>
+where
+    R: std::io::Read,
+    T: Facet<'static>,
+    E: From<DeserializeError<JsonError>>,
+    F: FnMut(T) -> Result<ControlFlow<()>, E>,
+{
+    let mut started = false;
+    for_each_parsed(
+        reader,
+        Some("JSON array"),
+        move |de| {
+            if !started {
+                started = true;
+                match de
+                    .parser_mut()
+                    .next_event()
+                    .map_err(DeserializeError::Parser)?
+                {
+                    Some(ParseEvent::SequenceStart(_)) => {}
+                    other => {
+                        return Err(DeserializeError::TypeMismatch {
+                            expected: "JSON array",
+                            got: alloc::format!("{other:?}"),
+                            span: None,
+                            path: None,
+                        });
+                    }
+                }
+            }
+
+            let next = de
+                .parser_mut()
+                .peek_event()
+                .map_err(DeserializeError::Parser)?;
+            if matches!(next, Some(ParseEvent::SequenceEnd)) {
+                de.parser_mut()
+                    .next_event()
+                    .map_err(DeserializeError::Parser)?;
+                return Ok(None);
+            }
+            de.deserialize::<T>().map(Some)
+        },
+        f,
+    )
+}
+
+/// Deserialize a stream of whitespace-separated JSON values from a synchronous
+/// reader, one value at a time.
+///
+/// This reads newline-delimited JSON (NDJSON, JSON Lines) as well as values
+/// simply concatenated together. Like [`for_each_element`], each value is
+/// deserialized into `T` and handed to `f` before any more input is read, so
+/// memory use is bounded by one value plus the read buffer, however large the
+/// stream. Returns the number of values visited; an empty stream has none.
+///
+/// Parsing stops at the first malformed value, with the error. As with
+/// [`for_each_element`], `f` can also stop early with [`ControlFlow::Break`]
+/// or with an error of its own.
+///
+/// # Example
+///
+/// ```
+/// use std::ops::ControlFlow;
+/// use facet::Facet;
+/// use facet_json::{DeserializeError, JsonError, for_each_value};
+///
+/// #[derive(Facet, Debug)]
+/// struct Event {
+///     id: u32,
+/// }
+///
+/// let ndjson = b"{\"id\": 1}\n{\"id\": 2}\n{\"id\": 3}\n";
+/// let mut total = 0;
+/// let count = for_each_value(
+///     &ndjson[..],
+///     |event: Event| -> Result<_, DeserializeError<JsonError>> {
+///         total += event.id;
+///         Ok(ControlFlow::Continue(()))
+///     },
+/// )
+/// .unwrap();
+/// assert_eq!((count, total), (3, 6));
+/// ```
+#[cfg(feature = "std")]
+pub fn for_each_value<R, T, E, F>(reader: R, f: F) -> Result<usize, E>
+where
+    R: std::io::Read,
+    T: Facet<'static>,
+    E: From<DeserializeError<JsonError>>,
+    F: FnMut(T) -> Result<ControlFlow<()>, E>,
+{
+    for_each_parsed(
+        reader,
+        None,
+        |de| {
+            if de
+                .parser_mut()
+                .begin_next_root()
+                .map_err(DeserializeError::Parser)?
+            {
+                de.deserialize::<T>().map(Some)
+            } else {
+                Ok(None)
+            }
+        },
+        f,
+    )
+}
+
+/// The deserializer [`for_each_parsed`] runs its parse step with.
+#[cfg(feature = "std")]
+type StreamingDeserializer<'y> =
+    FormatDeserializer<'static, false, StreamingJsonParser<StreamingAdapter<'y>>>;
+
+/// Shared driver for [`for_each_element`] and [`for_each_value`].
+///
+/// `next` runs inside the coroutine and parses one value, or returns `None`
+/// once there are no more. Each value is handed to `f` before any more input
+/// is read. Returns the number of values handed to `f`. Empty input is an
+/// error only if `expected` names what was expected instead.
+#[cfg(feature = "std")]
+fn for_each_parsed<R, T, E, N, F>(
+    mut reader: R,
+    expected: Option<&'static str>,
+    mut next: N,
+    mut f: F,
+) -> Result<usize, E>
+where
+    R: std::io::Read,
+    T: Facet<'static>,
+    E: From<DeserializeError<JsonError>>,
+    N: FnMut(&mut StreamingDeserializer<'_>) -> Result<Option<T>, DeserializeError<JsonError>>
+        + 'static,
+    F: FnMut(T) -> Result<ControlFlow<()>, E>,
+{
+    let buffer = Rc::new(RefCell::new(ScanBuffer::new()));
+    let buffer_for_coroutine = buffer.clone();
+    // Holds the value just parsed, until the driver hands it to `f`
+    let slot: Rc<RefCell<Option<T>>> = Rc::new(RefCell::new(None));
+    let slot_for_coroutine = slot.clone();
+
+    // Initial fill
+    let n = buffer.borrow_mut().refill(&mut reader).map_err(|e| {
+        DeserializeError::Parser(JsonError::without_span(JsonErrorKind::Io(e.to_string())))
+    })?;
+    if let (0, Some(expected)) = (n, expected) {
+        return Err(DeserializeError::Parser(JsonError::without_span(
+            JsonErrorKind::UnexpectedEof { expected },
+        ))
+        .into());
+    }
+
+    let mut coroutine: Coroutine<(), (), Result<(), DeserializeError<JsonError>>> =
+        Coroutine::new(move |yielder, ()| {
+            let adapter = StreamingAdapter::new(buffer_for_coroutine, yielder);
+            let parser = StreamingJsonParser::new(adapter);
+            let mut de = FormatDeserializer::new_owned(parser);
+
+            while let Some(item) = next(&mut de)? {
+                *slot_for_coroutine.borrow_mut() = Some(item);
+                // Let the driver run the callback before parsing any further
+                yielder.suspend(());
+            }
+            Ok(())
+        });
+
+    let mut count = 0;
+    loop {
+        match coroutine.resume(()) {
+            CoroutineResult::Yield(()) => {
+                // Either a value is ready, or the parser needs more data
+                let item = slot.borrow_mut().take();
+                if let Some(item) = item {
+                    count += 1;
+                    match f(item)? {
+                        ControlFlow::Continue(()) => continue,
+                        // Dropping the coroutine leaves the rest unparsed
+                        ControlFlow::Break(()) => return Ok(count),
+                    }
+                }
+
+                let mut buf = buffer.borrow_mut();
+                if buf.filled() == buf.capacity() {
+                    buf.grow();
+                }
+                let _n = buf.refill(&mut reader).map_err(|e| {
+                    DeserializeError::Parser(JsonError::without_span(JsonErrorKind::Io(
+                        e.to_string(),
+                    )))
+                })?;
+            }
+            CoroutineResult::Return(result) => {
+                result?;
+                return Ok(count);
+            }
+        }
+    }
+}
+
 /// Deserialize JSON from an async reader (tokio).
 ///
 /// This function streams the JSON input asynchronously, reading chunks as needed.
@@ -292,6 +529,20 @@ impl<A: TokenSource<'static>> StreamingJsonParser<A> {
         Ok(())
     }
 
+    /// Start the next of several root values in the stream, buffering its
+    /// first event. Returns `false` at the end of input instead.
+    fn begin_next_root(&mut self) -> Result<bool, JsonError> {
+        debug_assert!(self.stack.is_empty() && self.event_peek.is_none());
+        self.root_started = false;
+        self.root_complete = false;
+        let token = self.next_token()?;
+        if matches!(token.token, AdapterToken::Eof) {
+            return Ok(false);
+        }
+        self.event_peek = Some(self.parse_value_start_with_token(Some(token))?);
+        Ok(true)
+    }
+
     fn finish_value_in_parent(&mut self) {
         if let Some(context) = self.stack.last_mut() {
             match context {
@@ -0,0 +1,65 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use facet::Facet;
+use facet_json::{from_str, from_str_borrowed};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Request<'a> {
+    method: &'a str,
+    path: Cow<'a, str>,
+    tags: Vec<&'a str>,
+    referer: Option<&'a str>,
+    headers: BTreeMap<&'a str, Cow<'a, str>>,
+    flags: BTreeSet<&'a str>,
+    cookies: HashSet<&'a str>,
+}
+
+#[test]
+fn test_borrowed_containers() {
+    let json = r#"{
+        "method": "GET",
+        "path": "/index.html",
+        "tags": ["a", "b"],
+        "referer": "example.com",
+        "headers": {"host": "example.com", "accept": "*/*"},
+        "flags": ["gzip"],
+        "cookies": ["session"]
+    }"#;
+    let request: Request = from_str_borrowed(json).unwrap();
+
+    assert_eq!(request.method, "GET");
+    assert!(matches!(request.path, Cow::Borrowed("/index.html")));
+    assert_eq!(request.tags, ["a", "b"]);
+    assert_eq!(request.referer, Some("example.com"));
+    assert!(matches!(
+        request.headers["host"],
+        Cow::Borrowed("example.com")
+    ));
+    assert!(request.flags.contains("gzip"));
+    assert!(request.cookies.contains("session"));
+
+    // Everything borrowed points into the input
+    let input = json.as_bytes().as_ptr_range();
+    assert!(input.contains(&request.method.as_ptr()));
+    assert!(input.contains(&request.tags[1].as_ptr()));
+}
+
+#[test]
+fn test_borrowed_escapes() {
+    // Escaped strings can't be borrowed, but `Cow` falls back to owning them
+    let json = r#"{"method": "GET", "path": "/a\tb", "tags": [], "referer": null, "headers": {"x": "1\n2"}, "flags": [], "cookies": []}"#;
+    let request: Request = from_str_borrowed(json).unwrap();
+    assert!(matches!(request.path, Cow::Owned(ref path) if path == "/a\tb"));
+    assert!(matches!(request.headers["x"], Cow::Owned(ref value) if value == "1\n2"));
+
+    let json = r#"{"method": "GET", "path": "/", "tags": ["a\"b"], "referer": null, "headers": {}, "flags": [], "cookies": []}"#;
+    let err = from_str_borrowed::<Request>(json).unwrap_err();
+    assert!(err.to_string().contains("escape"), "{err}");
+}
+
+#[test]
+fn test_owned_input_cannot_be_borrowed() {
+    let json = r#"{"method": "GET", "path": "/", "tags": [], "referer": null, "headers": {}, "flags": [], "cookies": []}"#;
+    assert!(from_str::<Request>(json).is_err());
+}
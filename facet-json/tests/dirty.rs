@@ -0,0 +1,76 @@
+use facet::Facet;
+use facet_json::dirty_to_string;
+use facet_reflect::{DirtyPaths, DirtyTracked, Peek};
+
+#[derive(Facet)]
+#[facet(pod)]
+struct Board {
+    name: String,
+    columns: Vec<Column>,
+}
+
+#[derive(Facet)]
+#[facet(pod)]
+struct Column {
+    title: String,
+    cards: Vec<String>,
+}
+
+#[test]
+fn emits_only_changed_subtrees() {
+    let mut value = Board {
+        name: "roadmap".into(),
+        columns: vec![
+            Column {
+                title: "todo".into(),
+                cards: vec!["a".into(), "b".into()],
+            },
+            Column {
+                title: "done".into(),
+                cards: vec![],
+            },
+        ],
+    };
+    let mut tracked = DirtyTracked::new(&mut value, DirtyPaths::new());
+    assert_eq!(
+        dirty_to_string(tracked.peek(), tracked.sink()).unwrap(),
+        "{}"
+    );
+
+    tracked
+        .set("columns[0].cards[1]", String::from("c"))
+        .unwrap();
+    tracked
+        .set("columns[1].cards", vec![String::from("b")])
+        .unwrap();
+    assert_eq!(
+        dirty_to_string(tracked.peek(), tracked.sink()).unwrap(),
+        r#"{"columns[0].cards[1]":"c","columns[1].cards":["b"]}"#
+    );
+
+    tracked
+        .set(
+            "",
+            Board {
+                name: "archive".into(),
+                columns: vec![],
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        dirty_to_string(tracked.peek(), tracked.sink()).unwrap(),
+        r#"{"":{"name":"archive","columns":[]}}"#
+    );
+}
+
+#[test]
+fn fails_on_paths_missing_from_the_value() {
+    let mut paths = DirtyPaths::new();
+    paths.mark("columns[5]");
+    let board = Board {
+        name: "roadmap".into(),
+        columns: vec![],
+    };
+    let err = dirty_to_string(Peek::new(&board), &paths).unwrap_err();
+    assert!(err.to_string().contains("columns[5]"), "{err}");
+}
@@ -0,0 +1,69 @@
+use facet::Facet;
+
+use facet_json::{from_str, to_string};
+
+#[derive(Debug, Facet, PartialEq)]
+struct UserPatch {
+    name: Option<String>,
+    #[facet(double_option)]
+    nickname: Option<Option<String>>,
+}
+
+#[test]
+fn absent_null_and_value_are_distinct() {
+    let absent: UserPatch = from_str(r#"{"name":"ada"}"#).unwrap();
+    assert_eq!(absent.nickname, None);
+
+    let cleared: UserPatch = from_str(r#"{"name":"ada","nickname":null}"#).unwrap();
+    assert_eq!(cleared.nickname, Some(None));
+
+    let set: UserPatch = from_str(r#"{"nickname":"countess"}"#).unwrap();
+    assert_eq!(set.nickname, Some(Some("countess".to_string())));
+    assert_eq!(set.name, None);
+}
+
+#[test]
+fn absent_is_skipped_when_serializing() {
+    let patch = UserPatch {
+        name: None,
+        nickname: None,
+    };
+    assert_eq!(to_string(&patch).unwrap(), r#"{"name":null}"#);
+
+    let patch = UserPatch {
+        name: None,
+        nickname: Some(None),
+    };
+    let json = to_string(&patch).unwrap();
+    assert_eq!(json, r#"{"name":null,"nickname":null}"#);
+    assert_eq!(from_str::<UserPatch>(&json).unwrap(), patch);
+}
+
+#[test]
+fn double_option_in_enum_variant() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[repr(C)]
+    enum Command {
+        Update {
+            #[facet(double_option)]
+            parent: Option<Option<u32>>,
+        },
+    }
+
+    let cmd: Command = from_str(r#"{"Update":{"parent":null}}"#).unwrap();
+    assert_eq!(cmd, Command::Update { parent: Some(None) });
+
+    let cmd: Command = from_str(r#"{"Update":{}}"#).unwrap();
+    assert_eq!(cmd, Command::Update { parent: None });
+}
+
+#[test]
+fn without_the_attribute_null_means_none() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Plain {
+        nickname: Option<Option<String>>,
+    }
+
+    let plain: Plain = from_str(r#"{"nickname":null}"#).unwrap();
+    assert_eq!(plain.nickname, None);
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_format::{DeserializeError, DuplicateKeyPolicy, FormatDeserializer};
+use facet_json::JsonParser;
+
+#[derive(Debug, PartialEq, Facet)]
+struct Grant {
+    user: String,
+    role: String,
+}
+
+fn deserialize<T: Facet<'static>>(
+    json: &str,
+    policy: DuplicateKeyPolicy,
+) -> Result<T, DeserializeError<facet_json::JsonError>> {
+    let parser = JsonParser::new(json.as_bytes());
+    FormatDeserializer::new_owned(parser)
+        .with_duplicate_key_policy(policy)
+        .deserialize()
+}
+
+const GRANT: &str = r#"{"user": "alice", "role": "viewer", "role": "admin"}"#;
+
+#[test]
+fn last_wins_by_default() {
+    let grant: Grant = facet_json::from_str(GRANT).unwrap();
+    assert_eq!(grant.role, "admin");
+
+    let grant: Grant = deserialize(GRANT, DuplicateKeyPolicy::LastWins).unwrap();
+    assert_eq!(grant.role, "admin");
+}
+
+#[test]
+fn first_wins_skips_repeated_values() {
+    let grant: Grant = deserialize(GRANT, DuplicateKeyPolicy::FirstWins).unwrap();
+    assert_eq!(
+        grant,
+        Grant {
+            user: "alice".into(),
+            role: "viewer".into(),
+        }
+    );
+}
+
+#[test]
+fn error_reports_both_occurrences() {
+    let err = deserialize::<Grant>(GRANT, DuplicateKeyPolicy::Error).unwrap_err();
+    assert_eq!(err.to_string(), "duplicate key: role");
+
+    let DeserializeError::DuplicateKey {
+        key,
+        first_span: Some(first_span),
+        span: Some(span),
+        ..
+    } = err
+    else {
+        panic!("expected a duplicate key error with spans, got {err:?}");
+    };
+    assert_eq!(key, "role");
+    assert_eq!(first_span.offset, GRANT.find(r#""role""#).unwrap());
+    assert_eq!(span.offset, GRANT.rfind(r#""role""#).unwrap());
+}
+
+#[test]
+fn maps_follow_the_policy() {
+    let json = r#"{"a": 1, "b": 2, "a": 3}"#;
+
+    let map: HashMap<String, u32> = deserialize(json, DuplicateKeyPolicy::LastWins).unwrap();
+    assert_eq!(map["a"], 3);
+
+    let map: HashMap<String, u32> = deserialize(json, DuplicateKeyPolicy::FirstWins).unwrap();
+    assert_eq!(map["a"], 1);
+    assert_eq!(map.len(), 2);
+
+    let err = deserialize::<HashMap<String, u32>>(json, DuplicateKeyPolicy::Error).unwrap_err();
+    assert!(matches!(err, DeserializeError::DuplicateKey { key, .. } if key == "a"));
+}
+
+#[test]
+fn nested_objects_track_keys_separately() {
+    #[derive(Debug, Facet)]
+    struct Policy {
+        grants: Vec<Grant>,
+    }
+
+    let json = r#"{"grants": [{"user": "a", "role": "x"}, {"user": "b", "role": "y"}]}"#;
+    let policy: Policy = deserialize(json, DuplicateKeyPolicy::Error).unwrap();
+    assert_eq!(policy.grants[1].user, "b");
+
+    let json = r#"{"grants": [{"user": "a", "role": "x", "user": "b"}]}"#;
+    let err = deserialize::<Policy>(json, DuplicateKeyPolicy::Error).unwrap_err();
+    assert!(matches!(err, DeserializeError::DuplicateKey { key, .. } if key == "user"));
+}
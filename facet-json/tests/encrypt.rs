@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_core::{Field, Type, UserType};
+use facet_format::{DeserializeError, FieldCipher, FormatDeserializer, serialize_with_fields};
+use facet_json::{JsonParser, JsonSerializer, SerializeOptions, to_string, to_string_with_options};
+use facet_reflect::Peek;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Account {
+    user: String,
+    #[facet(encrypt)]
+    password: String,
+    #[facet(encrypt)]
+    pin: u32,
+    #[facet(encrypt)]
+    recovery: Option<String>,
+}
+
+/// Reverses the plaintext and tags it with the field name
+struct ReverseCipher;
+
+impl FieldCipher for ReverseCipher {
+    fn encrypt(&self, field: &Field, plaintext: &str) -> Result<String, String> {
+        Ok(format!(
+            "{}:{}",
+            field.name,
+            plaintext.chars().rev().collect::<String>()
+        ))
+    }
+
+    fn decrypt(&self, field: &Field, ciphertext: &str) -> Result<String, String> {
+        let reversed = ciphertext
+            .strip_prefix(field.name)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(|| format!("ciphertext is not for `{}`", field.name))?;
+        Ok(reversed.chars().rev().collect())
+    }
+}
+
+fn decode(json: &str) -> Result<Account, DeserializeError<facet_json::JsonError>> {
+    FormatDeserializer::new_owned(JsonParser::new(json.as_bytes()))
+        .with_cipher(Arc::new(ReverseCipher))
+        .deserialize()
+}
+
+#[test]
+fn encrypted_fields_roundtrip() {
+    let options = SerializeOptions::new().cipher(Arc::new(ReverseCipher));
+    let account = Account {
+        user: "ada".into(),
+        password: "hunter2".into(),
+        pin: 1234,
+        recovery: None,
+    };
+    let json = to_string_with_options(&account, &options).unwrap();
+    assert_eq!(
+        json,
+        r#"{"user":"ada","password":"password:2retnuh","pin":"pin:4321","recovery":null}"#
+    );
+    assert_eq!(decode(&json).unwrap(), account);
+
+    let with_recovery = Account {
+        recovery: Some("words".into()),
+        ..account
+    };
+    let json = to_string_with_options(&with_recovery, &options).unwrap();
+    assert!(json.contains(r#""recovery":"recovery:sdrow""#));
+    assert_eq!(decode(&json).unwrap(), with_recovery);
+}
+
+#[test]
+fn plaintext_is_never_written_or_read_without_a_cipher() {
+    let account = Account {
+        user: "ada".into(),
+        password: "hunter2".into(),
+        pin: 1234,
+        recovery: None,
+    };
+    let err = to_string(&account).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "field `password` is encrypted, but the serializer has no cipher"
+    );
+
+    let json = r#"{"user":"ada","password":"password:2retnuh","pin":"pin:4321","recovery":null}"#;
+    let err = facet_json::from_str::<Account>(json).unwrap_err();
+    assert!(matches!(
+        err,
+        DeserializeError::Decrypt {
+            field: "password",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn decryption_failures_are_reported() {
+    let json = r#"{"user":"ada","password":"pin:2retnuh","pin":"pin:4321","recovery":null}"#;
+    let err = decode(json).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "cannot decrypt field `password`: ciphertext is not for `password`"
+    );
+}
+
+#[test]
+fn encrypted_fields_are_sensitive() {
+    let Type::User(UserType::Struct(struct_type)) = Account::SHAPE.ty else {
+        panic!("Account is a struct");
+    };
+    let password = &struct_type.fields[1];
+    assert!(password.is_encrypted());
+    assert!(password.is_sensitive());
+    assert!(!struct_type.fields[0].is_encrypted());
+}
+
+#[test]
+fn encrypted_tuple_struct_fields_roundtrip() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Pair(u32, #[facet(encrypt)] String);
+
+    let options = SerializeOptions::new().cipher(Arc::new(ReverseCipher));
+    let json = to_string_with_options(&Pair(1, "secret".into()), &options).unwrap();
+    assert_eq!(json, r#"[1,"1:terces"]"#);
+
+    let pair: Pair = FormatDeserializer::new_owned(JsonParser::new(json.as_bytes()))
+        .with_cipher(Arc::new(ReverseCipher))
+        .deserialize()
+        .unwrap();
+    assert_eq!(pair, Pair(1, "secret".into()));
+}
+
+#[test]
+fn projected_fields_are_encrypted() {
+    let mut serializer =
+        JsonSerializer::with_options(SerializeOptions::new().cipher(Arc::new(ReverseCipher)));
+    let account = Account {
+        user: "ada".into(),
+        password: "hunter2".into(),
+        pin: 1234,
+        recovery: None,
+    };
+    serialize_with_fields(&mut serializer, Peek::new(&account), &["user", "password"]).unwrap();
+    assert_eq!(
+        String::from_utf8(serializer.finish()).unwrap(),
+        r#"{"user":"ada","password":"password:2retnuh"}"#
+    );
+}
@@ -0,0 +1,42 @@
+use facet::Facet;
+use facet_json::{facet_map_to_string, facet_vec_to_string};
+use facet_reflect::{FacetMap, FacetVec};
+
+#[derive(Facet)]
+#[facet(traits(Send, Sync))]
+struct Started {
+    id: u32,
+}
+
+#[derive(Facet)]
+#[facet(type_tag = "events.Stopped")]
+struct Stopped {
+    id: u32,
+    code: i32,
+}
+
+#[test]
+fn facet_vec_serializes_with_type_tags() {
+    let mut events = FacetVec::new();
+    events.push(Started { id: 1 });
+    events.push(Stopped { id: 1, code: 0 });
+    events.push(vec![1u8, 2]);
+
+    assert_eq!(
+        facet_vec_to_string(&events).unwrap(),
+        r#"[{"type":"Started","value":{"id":1}},{"type":"events.Stopped","value":{"id":1,"code":0}},{"type":"Vec<u8>","value":[1,2]}]"#
+    );
+    assert_eq!(facet_vec_to_string(&FacetVec::new()).unwrap(), "[]");
+}
+
+#[test]
+fn facet_map_serializes_with_type_tags() {
+    let mut plugins = FacetMap::new();
+    plugins.insert("b", true);
+    plugins.insert("a", Started { id: 7 });
+
+    assert_eq!(
+        facet_map_to_string(&plugins).unwrap(),
+        r#"{"a":{"type":"Started","value":{"id":7}},"b":{"type":"bool","value":true}}"#
+    );
+}
@@ -0,0 +1,93 @@
+//! Unknown fields caught by a flattened `facet_value::Value` or
+//! `HashMap<String, Value>` survive a read-modify-write cycle.
+
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_value::{Value, value};
+
+const INPUT: &str = r#"{"name":"svc","replicas":2,"probe":{"path":"/health","ports":[80,{"alt":8080}],"tls":null},"tags":["a","b"],"ratio":0.5}"#;
+
+#[derive(Facet, Debug)]
+struct WithMap {
+    name: String,
+    replicas: u32,
+    #[facet(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+#[derive(Facet, Debug)]
+struct WithValue {
+    name: String,
+    replicas: u32,
+    #[facet(flatten)]
+    extra: Value,
+}
+
+#[derive(Facet, Debug)]
+struct WithOptionalValue {
+    name: String,
+    #[facet(flatten)]
+    extra: Option<Value>,
+}
+
+fn parse(json: &str) -> Value {
+    facet_json::from_str(json).unwrap()
+}
+
+#[test]
+fn flattened_map_keeps_nested_unknown_fields() {
+    let mut config: WithMap = facet_json::from_str(INPUT).unwrap();
+    assert_eq!(
+        config.extra["probe"],
+        value!({"path": "/health", "ports": [80, {"alt": 8080}], "tls": null})
+    );
+
+    config.replicas = 3;
+    let output = facet_json::to_string(&config).unwrap();
+
+    let mut expected = parse(INPUT);
+    expected
+        .as_object_mut()
+        .unwrap()
+        .insert("replicas", Value::from(3));
+    assert_eq!(parse(&output), expected);
+}
+
+#[test]
+fn flattened_value_keeps_nested_unknown_fields() {
+    let mut config: WithValue = facet_json::from_str(INPUT).unwrap();
+    config.name = "renamed".into();
+    let output = facet_json::to_string(&config).unwrap();
+
+    let mut expected = parse(INPUT);
+    expected
+        .as_object_mut()
+        .unwrap()
+        .insert("name", Value::from("renamed"));
+    assert_eq!(parse(&output), expected);
+}
+
+#[test]
+fn flattened_option_value() {
+    let config = WithOptionalValue {
+        name: "svc".into(),
+        extra: Some(value!({"debug": true})),
+    };
+    assert_eq!(
+        facet_json::to_string(&config).unwrap(),
+        r#"{"name":"svc","debug":true}"#
+    );
+
+    let config = WithOptionalValue {
+        name: "svc".into(),
+        extra: None,
+    };
+    assert_eq!(facet_json::to_string(&config).unwrap(), r#"{"name":"svc"}"#);
+}
+
+#[test]
+fn values_serialize_on_their_own() {
+    let json = r#"{"list":[1,-2,2.5,"x",null,false],"empty":{}}"#;
+    assert_eq!(facet_json::to_string(&parse(json)).unwrap(), json);
+}
@@ -0,0 +1,191 @@
+#![cfg(feature = "streaming")]
+
+use std::cell::Cell;
+use std::io::Read;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+use facet::Facet;
+use facet_format::DeserializeError;
+use facet_json::{JsonError, for_each_element};
+
+/// Hands out the input a few bytes at a time and records how far it got.
+struct Trickle {
+    data: Vec<u8>,
+    pos: Rc<Cell<usize>>,
+}
+
+impl Read for Trickle {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let start = self.pos.get();
+        let n = buf.len().min(4).min(self.data.len() - start);
+        buf[..n].copy_from_slice(&self.data[start..start + n]);
+        self.pos.set(start + n);
+        Ok(n)
+    }
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Row {
+    id: u32,
+    name: String,
+}
+
+#[test]
+fn test_visits_each_element_in_order() {
+    let json = r#"[{"id": 1, "name": "a"}, {"id": 2, "name": "bb"}, {"id": 3, "name": "ccc"}]"#;
+    let mut rows = Vec::new();
+    let count = for_each_element(
+        json.as_bytes(),
+        |row: Row| -> Result<_, DeserializeError<JsonError>> {
+            rows.push(row);
+            Ok(ControlFlow::Continue(()))
+        },
+    )
+    .unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(
+        rows[1],
+        Row {
+            id: 2,
+            name: "bb".into()
+        }
+    );
+}
+
+#[test]
+fn test_callback_runs_before_rest_is_read() {
+    let items: Vec<String> = (0..50)
+        .map(|i| format!(r#"{{"id":{i},"name":"row"}}"#))
+        .collect();
+    let json = format!("[{}]", items.join(","));
+    let len = json.len();
+    let pos = Rc::new(Cell::new(0));
+    let reader = Trickle {
+        data: json.into_bytes(),
+        pos: pos.clone(),
+    };
+
+    let mut read_at_first = None;
+    let count = for_each_element(
+        reader,
+        |row: Row| -> Result<_, DeserializeError<JsonError>> {
+            if row.id == 0 {
+                read_at_first = Some(pos.get());
+            }
+            Ok(ControlFlow::Continue(()))
+        },
+    )
+    .unwrap();
+
+    assert_eq!(count, 50);
+    assert!(
+        read_at_first.unwrap() < len / 2,
+        "read {read_at_first:?} of {len}"
+    );
+}
+
+#[test]
+fn test_empty_array_and_scalars() {
+    let count = for_each_element(
+        &b"  [ ]  "[..],
+        |_: u32| -> Result<_, DeserializeError<JsonError>> { panic!("no elements") },
+    )
+    .unwrap();
+    assert_eq!(count, 0);
+
+    let mut sum = 0;
+    for_each_element(
+        &b"[1, 2, 3]"[..],
+        |n: u32| -> Result<_, DeserializeError<JsonError>> {
+            sum += n;
+            Ok(ControlFlow::Continue(()))
+        },
+    )
+    .unwrap();
+    assert_eq!(sum, 6);
+}
+
+#[test]
+fn test_errors() {
+    let err = for_each_element(
+        &br#"{"id": 1}"#[..],
+        |_: Row| -> Result<_, DeserializeError<JsonError>> { Ok(ControlFlow::Continue(())) },
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, DeserializeError::TypeMismatch { .. }),
+        "{err}"
+    );
+
+    let err = for_each_element(
+        &b""[..],
+        |_: u32| -> Result<_, DeserializeError<JsonError>> { Ok(ControlFlow::Continue(())) },
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("JSON array"), "{err}");
+
+    let mut seen = 0;
+    let err = for_each_element(
+        &b"[1, 2, \"three\"]"[..],
+        |_: u32| -> Result<_, DeserializeError<JsonError>> {
+            seen += 1;
+            Ok(ControlFlow::Continue(()))
+        },
+    )
+    .unwrap_err();
+    assert_eq!(seen, 2, "{err}");
+}
+
+#[test]
+fn test_callback_can_stop_early() {
+    let json = b"[1, 2, 3, \"not a number\"]";
+    let pos = Rc::new(Cell::new(0));
+    let reader = Trickle {
+        data: json.to_vec(),
+        pos: pos.clone(),
+    };
+
+    let mut seen = Vec::new();
+    let count = for_each_element(reader, |n: u32| -> Result<_, DeserializeError<JsonError>> {
+        seen.push(n);
+        Ok(if n == 2 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        })
+    })
+    .unwrap();
+    assert_eq!((count, seen), (2, vec![1, 2]));
+    assert!(pos.get() < json.len(), "read all {} bytes", json.len());
+}
+
+#[test]
+fn test_callback_errors_are_returned() {
+    #[derive(Debug, PartialEq)]
+    enum Error {
+        TooBig(u32),
+        Json(String),
+    }
+
+    impl From<DeserializeError<JsonError>> for Error {
+        fn from(err: DeserializeError<JsonError>) -> Self {
+            Error::Json(err.to_string())
+        }
+    }
+
+    let check = |n: u32| {
+        if n > 2 {
+            return Err(Error::TooBig(n));
+        }
+        Ok(ControlFlow::Continue(()))
+    };
+    assert_eq!(
+        for_each_element(&b"[1, 2, 3, 4]"[..], check),
+        Err(Error::TooBig(3))
+    );
+    assert!(matches!(
+        for_each_element(&b"[1, true]"[..], check),
+        Err(Error::Json(_))
+    ));
+}
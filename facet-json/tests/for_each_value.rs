@@ -0,0 +1,148 @@
+#![cfg(feature = "streaming")]
+
+use std::cell::Cell;
+use std::io::Read;
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+use facet::Facet;
+use facet_json::{DeserializeError, JsonError, for_each_value};
+
+/// Hands out the input a few bytes at a time and records how far it got.
+struct Trickle {
+    data: Vec<u8>,
+    pos: Rc<Cell<usize>>,
+}
+
+impl Read for Trickle {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let start = self.pos.get();
+        let n = buf.len().min(4).min(self.data.len() - start);
+        buf[..n].copy_from_slice(&self.data[start..start + n]);
+        self.pos.set(start + n);
+        Ok(n)
+    }
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Row {
+    id: u32,
+    tags: Vec<String>,
+}
+
+#[test]
+fn test_reads_ndjson_lazily() {
+    let lines: Vec<String> = (0..50)
+        .map(|i| format!(r#"{{"id":{i},"tags":["a","b"]}}"#))
+        .collect();
+    let ndjson = lines.join("\n") + "\n";
+    let len = ndjson.len();
+    let pos = Rc::new(Cell::new(0));
+    let reader = Trickle {
+        data: ndjson.into_bytes(),
+        pos: pos.clone(),
+    };
+
+    let mut ids = Vec::new();
+    let mut read_at_first = None;
+    let count = for_each_value(
+        reader,
+        |row: Row| -> Result<_, DeserializeError<JsonError>> {
+            if row.id == 0 {
+                read_at_first = Some(pos.get());
+            }
+            ids.push(row.id);
+            Ok(ControlFlow::Continue(()))
+        },
+    )
+    .unwrap();
+
+    assert_eq!(count, 50);
+    assert_eq!(ids, (0..50).collect::<Vec<_>>());
+    assert!(
+        read_at_first.unwrap() < len / 2,
+        "read {read_at_first:?} of {len}"
+    );
+}
+
+#[test]
+fn test_empty_and_concatenated_streams() {
+    let count = for_each_value(
+        &b""[..],
+        |_: u32| -> Result<_, DeserializeError<JsonError>> { panic!("no values") },
+    )
+    .unwrap();
+    assert_eq!(count, 0);
+    let count = for_each_value(
+        &b" \n\r\n "[..],
+        |_: u32| -> Result<_, DeserializeError<JsonError>> { panic!("no values") },
+    )
+    .unwrap();
+    assert_eq!(count, 0);
+
+    let mut values = Vec::new();
+    for_each_value(
+        &b"[1] [2]\n[3,4]\r\n[]"[..],
+        |v: Vec<u32>| -> Result<_, DeserializeError<JsonError>> {
+            values.push(v);
+            Ok(ControlFlow::Continue(()))
+        },
+    )
+    .unwrap();
+    assert_eq!(values, [vec![1], vec![2], vec![3, 4], vec![]]);
+}
+
+#[test]
+fn test_stops_at_first_bad_value() {
+    let mut seen = 0;
+    let err = for_each_value(
+        &b"{\"id\":1,\"tags\":[]}\n{\"id\":\"two\",\"tags\":[]}\n{\"id\":3,\"tags\":[]}\n"[..],
+        |_: Row| -> Result<_, DeserializeError<JsonError>> {
+            seen += 1;
+            Ok(ControlFlow::Continue(()))
+        },
+    )
+    .unwrap_err();
+    assert_eq!(seen, 1, "{err}");
+
+    let mut seen = 0;
+    let err = for_each_value(
+        &b"1\n2\n{"[..],
+        |_: u32| -> Result<_, DeserializeError<JsonError>> {
+            seen += 1;
+            Ok(ControlFlow::Continue(()))
+        },
+    )
+    .unwrap_err();
+    assert_eq!(seen, 2, "{err}");
+}
+
+#[test]
+fn test_callback_can_stop_early() {
+    let mut seen = Vec::new();
+    let count = for_each_value(
+        &b"1\n2\n3\n{"[..],
+        |n: u32| -> Result<_, DeserializeError<JsonError>> {
+            seen.push(n);
+            Ok(if n == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            })
+        },
+    )
+    .unwrap();
+    assert_eq!((count, seen), (2, vec![1, 2]));
+
+    let err = for_each_value(
+        &b"1\n2\n3\n"[..],
+        |n: u32| -> Result<_, Box<dyn std::error::Error>> {
+            if n == 2 {
+                return Err(format!("rejected {n}").into());
+            }
+            Ok(ControlFlow::Continue(()))
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "rejected 2");
+}
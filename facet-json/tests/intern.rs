@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_format::{FormatDeserializer, StringInterner};
+use facet_json::{JsonParser, from_str};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Event {
+    #[facet(intern)]
+    region: Arc<str>,
+    #[facet(intern)]
+    zone: Option<Arc<str>>,
+    id: u32,
+}
+
+fn deserialize_with(json: &str, interner: Arc<StringInterner>) -> Vec<Event> {
+    let parser = JsonParser::new(json.as_bytes());
+    FormatDeserializer::new_owned(parser)
+        .with_interner(interner)
+        .deserialize()
+        .unwrap()
+}
+
+const EVENTS: &str = r#"[
+    {"region": "eu-west-1", "zone": "a", "id": 1},
+    {"region": "eu-west-1", "zone": null, "id": 2},
+    {"region": "us-east-1", "zone": "a", "id": 3}
+]"#;
+
+#[test]
+fn interned_fields_share_allocations() {
+    let interner = Arc::new(StringInterner::new());
+    let events = deserialize_with(EVENTS, interner.clone());
+
+    assert!(Arc::ptr_eq(&events[0].region, &events[1].region));
+    assert!(Arc::ptr_eq(
+        events[0].zone.as_ref().unwrap(),
+        events[2].zone.as_ref().unwrap()
+    ));
+    assert_eq!(events[1].zone, None);
+    assert_eq!(&*events[2].region, "us-east-1");
+    assert_eq!(interner.len(), 3);
+}
+
+#[test]
+fn interner_is_shared_across_documents() {
+    let interner = Arc::new(StringInterner::new());
+    let first = deserialize_with(EVENTS, interner.clone());
+    let second = deserialize_with(EVENTS, interner.clone());
+    assert!(Arc::ptr_eq(&first[2].region, &second[2].region));
+    assert_eq!(interner.len(), 3);
+}
+
+#[test]
+fn intern_without_interner_allocates() {
+    let events: Vec<Event> = from_str(EVENTS).unwrap();
+    assert_eq!(events[0].region, events[1].region);
+    assert!(!Arc::ptr_eq(&events[0].region, &events[1].region));
+}
+
+#[test]
+fn intern_attr_is_reflected() {
+    use facet::{StructType, Type, UserType};
+
+    let Type::User(UserType::Struct(StructType { fields, .. })) = Event::SHAPE.ty else {
+        panic!("expected a struct");
+    };
+    assert!(fields[0].has_builtin_attr("intern"));
+    assert!(!fields[2].has_builtin_attr("intern"));
+}
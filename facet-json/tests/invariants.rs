@@ -0,0 +1,72 @@
+use facet::Facet;
+
+use facet_json::from_str;
+
+fn valid_port(port: &u32) -> Result<(), &'static str> {
+    if *port < 65536 {
+        Ok(())
+    } else {
+        Err("port must be < 65536")
+    }
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Listen {
+    host: String,
+    #[facet(invariants = valid_port)]
+    port: u32,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[facet(invariants = Pool::invariants)]
+struct Pool {
+    min: u32,
+    max: u32,
+}
+
+impl Pool {
+    fn invariants(&self) -> Result<(), String> {
+        if self.min <= self.max {
+            Ok(())
+        } else {
+            Err(format!(
+                "min ({}) must not exceed max ({})",
+                self.min, self.max
+            ))
+        }
+    }
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Server {
+    listen: Listen,
+    pools: Vec<Pool>,
+}
+
+#[test]
+fn valid_values_deserialize() {
+    let server: Server =
+        from_str(r#"{"listen":{"host":"localhost","port":8080},"pools":[{"min":1,"max":4}]}"#)
+            .unwrap();
+    assert_eq!(server.listen.port, 8080);
+    assert_eq!(server.pools, [Pool { min: 1, max: 4 }]);
+}
+
+#[test]
+fn field_invariant_fails_deserialization() {
+    let err = from_str::<Server>(r#"{"listen":{"host":"localhost","port":70000},"pools":[]}"#)
+        .unwrap_err();
+    assert!(err.to_string().contains("port must be < 65536"), "{err}");
+}
+
+#[test]
+fn nested_invariant_fails_deserialization() {
+    let err = from_str::<Server>(
+        r#"{"listen":{"host":"localhost","port":80},"pools":[{"min":1,"max":4},{"min":9,"max":2}]}"#,
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("min (9) must not exceed max (2)"),
+        "{err}"
+    );
+}
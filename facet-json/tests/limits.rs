@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_format::{DeserializeError, DeserializeLimits, FormatDeserializer};
+use facet_json::{JsonError, JsonParser};
+
+fn deserialize<T: Facet<'static>>(
+    json: &str,
+    limits: DeserializeLimits,
+) -> Result<T, DeserializeError<JsonError>> {
+    let parser = JsonParser::new(json.as_bytes());
+    FormatDeserializer::new_owned(parser)
+        .with_limits(limits)
+        .deserialize()
+}
+
+fn exceeded<T: Facet<'static> + core::fmt::Debug>(
+    json: &str,
+    limits: DeserializeLimits,
+) -> &'static str {
+    match deserialize::<T>(json, limits) {
+        Err(DeserializeError::LimitExceeded { limit, .. }) => limit,
+        other => panic!("expected a limit error, got {other:?}"),
+    }
+}
+
+#[derive(Debug, Facet)]
+struct Tree {
+    children: Vec<Tree>,
+}
+
+#[test]
+fn unlimited_by_default() {
+    let json = r#"{"children": [{"children": [{"children": []}]}]}"#;
+    let tree: Tree = deserialize(json, DeserializeLimits::default()).unwrap();
+    assert_eq!(tree.children[0].children.len(), 1);
+}
+
+#[test]
+fn nesting_depth() {
+    // Three levels of objects, each holding a sequence
+    let json = r#"{"children": [{"children": [{"children": []}]}]}"#;
+    deserialize::<Tree>(json, DeserializeLimits::new().max_depth(6)).unwrap();
+    assert_eq!(
+        exceeded::<Tree>(json, DeserializeLimits::new().max_depth(5)),
+        "nesting depth"
+    );
+
+    // Depth is released when containers close
+    let json = r#"[[[1]], [[2]], [[3]]]"#;
+    let nested: Vec<Vec<Vec<u8>>> =
+        deserialize(json, DeserializeLimits::new().max_depth(3)).unwrap();
+    assert_eq!(nested.len(), 3);
+}
+
+#[test]
+fn deeply_nested_input_fails_fast() {
+    let levels = 100_000;
+    let json = format!(
+        "{}{}",
+        r#"{"children": ["#.repeat(levels),
+        "]}".repeat(levels)
+    );
+    assert_eq!(
+        exceeded::<Tree>(&json, DeserializeLimits::new().max_depth(16)),
+        "nesting depth"
+    );
+}
+
+#[test]
+fn skipped_values_count_too() {
+    #[derive(Debug, Facet)]
+    struct Empty {}
+
+    let levels = 100_000;
+    let json = format!(
+        r#"{{"unknown": {}{}}}"#,
+        "[".repeat(levels),
+        "]".repeat(levels)
+    );
+    assert_eq!(
+        exceeded::<Empty>(&json, DeserializeLimits::new().max_depth(16)),
+        "nesting depth"
+    );
+
+    let json = r#"{"unknown": {"ignored": "0123456789"}}"#;
+    deserialize::<Empty>(json, DeserializeLimits::new().max_string_bytes(24)).unwrap();
+    assert_eq!(
+        exceeded::<Empty>(json, DeserializeLimits::new().max_string_bytes(23)),
+        "string bytes"
+    );
+}
+
+#[test]
+fn string_bytes() {
+    #[derive(Debug, Facet)]
+    struct Note {
+        body: String,
+    }
+
+    // "body" (4) + 10 bytes of content
+    let json = r#"{"body": "0123456789"}"#;
+    deserialize::<Note>(json, DeserializeLimits::new().max_string_bytes(14)).unwrap();
+    assert_eq!(
+        exceeded::<Note>(json, DeserializeLimits::new().max_string_bytes(13)),
+        "string bytes"
+    );
+}
+
+#[test]
+fn sequence_length() {
+    let limits = DeserializeLimits::new().max_sequence_len(3);
+    let list: Vec<u32> = deserialize("[1, 2, 3]", limits).unwrap();
+    assert_eq!(list, [1, 2, 3]);
+    assert_eq!(
+        exceeded::<Vec<u32>>("[1, 2, 3, 4]", limits),
+        "sequence length"
+    );
+
+    // The limit applies to each sequence separately
+    let lists: Vec<Vec<u32>> = deserialize("[[1, 2, 3], [4, 5, 6]]", limits).unwrap();
+    assert_eq!(lists.len(), 2);
+}
+
+#[test]
+fn map_length() {
+    let limits = DeserializeLimits::new().max_map_len(2);
+    let map: HashMap<String, u32> = deserialize(r#"{"a": 1, "b": 2}"#, limits).unwrap();
+    assert_eq!(map.len(), 2);
+
+    let err =
+        deserialize::<HashMap<String, u32>>(r#"{"a": 1, "b": 2, "c": 3}"#, limits).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "limit exceeded: map length is greater than 2"
+    );
+    let DeserializeError::LimitExceeded {
+        span: Some(span), ..
+    } = err
+    else {
+        panic!("expected a span");
+    };
+    assert_eq!(span.offset, 17);
+}
@@ -0,0 +1,104 @@
+use facet::Facet;
+use facet_format::{DeserializeError, FormatDeserializer, NumberLeniency};
+use facet_json::{
+    JsonError, JsonParser, NonFiniteFloats, SerializeOptions, to_string_with_options,
+};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Reading {
+    id: u64,
+    offset: i128,
+    value: f64,
+}
+
+fn deserialize<T: Facet<'static>>(
+    json: &str,
+    leniency: NumberLeniency,
+) -> Result<T, DeserializeError<JsonError>> {
+    let parser = JsonParser::new(json.as_bytes());
+    FormatDeserializer::new_owned(parser)
+        .with_number_leniency(leniency)
+        .deserialize()
+}
+
+#[test]
+fn non_finite_floats() {
+    let values = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1.5];
+
+    assert_eq!(
+        facet_json::to_string(&values).unwrap(),
+        "[null,null,null,1.5]"
+    );
+
+    let options = SerializeOptions::new().non_finite_floats(NonFiniteFloats::String);
+    assert_eq!(
+        to_string_with_options(&values, &options).unwrap(),
+        r#"["NaN","Infinity","-Infinity",1.5]"#
+    );
+
+    let options = SerializeOptions::new().non_finite_floats(NonFiniteFloats::Reject);
+    assert!(to_string_with_options(&values, &options).is_err());
+    assert!(to_string_with_options(&[1.5f32], &options).is_ok());
+}
+
+#[test]
+fn large_integers_as_strings() {
+    let reading = Reading {
+        id: (1 << 53) - 1,
+        offset: -(1 << 60),
+        value: 0.5,
+    };
+    let options = SerializeOptions::new().large_integers_as_strings();
+    let json = to_string_with_options(&reading, &options).unwrap();
+    assert_eq!(
+        json,
+        r#"{"id":9007199254740991,"offset":"-1152921504606846976","value":0.5}"#
+    );
+
+    // Quoted numbers are read back by default
+    let back: Reading = facet_json::from_str(&json).unwrap();
+    assert_eq!(back, reading);
+}
+
+#[test]
+fn non_finite_strings_round_trip() {
+    let options = SerializeOptions::new().non_finite_floats(NonFiniteFloats::String);
+    let json = to_string_with_options(&[f64::INFINITY, f64::NEG_INFINITY], &options).unwrap();
+    let back: Vec<f64> = facet_json::from_str(&json).unwrap();
+    assert_eq!(back, [f64::INFINITY, f64::NEG_INFINITY]);
+
+    let back: Vec<f64> = facet_json::from_str(r#"["NaN"]"#).unwrap();
+    assert!(back[0].is_nan());
+}
+
+#[test]
+fn null_as_nan() {
+    let back: Vec<f64> = deserialize("[null, 2.0]", NumberLeniency::new()).unwrap();
+    assert_eq!(back, [0.0, 2.0]);
+
+    let back: Vec<f32> =
+        deserialize("[null, 2.0]", NumberLeniency::new().null_as_nan(true)).unwrap();
+    assert!(back[0].is_nan());
+    assert_eq!(back[1], 2.0);
+}
+
+#[test]
+fn strict_leniency() {
+    let strict = NumberLeniency::strict();
+
+    let reading: Reading = deserialize(r#"{"id":1,"offset":-2,"value":3.5}"#, strict).unwrap();
+    assert_eq!(reading.id, 1);
+
+    assert!(deserialize::<Reading>(r#"{"id":"1","offset":-2,"value":3.5}"#, strict).is_err());
+    assert!(deserialize::<Vec<f64>>(r#"["NaN"]"#, strict).is_err());
+    assert!(deserialize::<Vec<f64>>(r#"["1.5"]"#, strict).is_err());
+
+    // Non-finite strings can be accepted without accepting quoted numbers
+    let leniency = strict.non_finite_strings(true);
+    assert!(deserialize::<Vec<f64>>(r#"["-Infinity"]"#, leniency).is_ok());
+    assert!(deserialize::<Vec<f64>>(r#"["1.5"]"#, leniency).is_err());
+
+    // Strings are still strings
+    let names: Vec<String> = deserialize(r#"["NaN", "42"]"#, strict).unwrap();
+    assert_eq!(names, ["NaN", "42"]);
+}
@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_json::{peek_to_string_with_fields, to_string_with_fields};
+use facet_reflect::Peek;
+
+#[derive(Facet)]
+struct Item {
+    name: String,
+    price: u32,
+    tags: Vec<String>,
+}
+
+#[derive(Facet)]
+struct Owner {
+    name: String,
+    email: Option<String>,
+}
+
+#[derive(Facet)]
+struct Order {
+    id: u64,
+    owner: Owner,
+    items: Vec<Item>,
+    labels: BTreeMap<String, u32>,
+    shipped: Option<Owner>,
+}
+
+#[test]
+fn top_level_fields() {
+    let order = Order {
+        id: 7,
+        owner: Owner {
+            name: "Ada".into(),
+            email: None,
+        },
+        items: vec![],
+        labels: BTreeMap::new(),
+        shipped: None,
+    };
+    let json = to_string_with_fields(&order, &["id"]).unwrap();
+    assert_eq!(json, r#"{"id":7}"#);
+}
+
+#[test]
+fn nested_and_wildcard_fields() {
+    let order = Order {
+        id: 7,
+        owner: Owner {
+            name: "Ada".into(),
+            email: Some("ada@example.com".into()),
+        },
+        items: vec![
+            Item {
+                name: "tea".into(),
+                price: 4,
+                tags: vec!["hot".into()],
+            },
+            Item {
+                name: "cake".into(),
+                price: 6,
+                tags: vec![],
+            },
+        ],
+        labels: BTreeMap::new(),
+        shipped: None,
+    };
+    let json = to_string_with_fields(&order, &["owner.name", "items[*].price"]).unwrap();
+    assert_eq!(
+        json,
+        r#"{"owner":{"name":"Ada"},"items":[{"price":4},{"price":6}]}"#
+    );
+}
+
+#[test]
+fn indexed_elements_merge_with_wildcards() {
+    let order = Order {
+        id: 7,
+        owner: Owner {
+            name: "Ada".into(),
+            email: None,
+        },
+        items: vec![
+            Item {
+                name: "tea".into(),
+                price: 4,
+                tags: vec!["hot".into()],
+            },
+            Item {
+                name: "cake".into(),
+                price: 6,
+                tags: vec![],
+            },
+        ],
+        labels: BTreeMap::new(),
+        shipped: None,
+    };
+    let json = to_string_with_fields(&order, &["items[1]", "items[*].name"]).unwrap();
+    assert_eq!(
+        json,
+        r#"{"items":[{"name":"tea"},{"name":"cake","price":6,"tags":[]}]}"#
+    );
+}
+
+#[test]
+fn map_keys_and_options() {
+    let order = Order {
+        id: 7,
+        owner: Owner {
+            name: "Ada".into(),
+            email: None,
+        },
+        items: vec![],
+        labels: BTreeMap::from([("a".into(), 1), ("b".into(), 2)]),
+        shipped: Some(Owner {
+            name: "Bob".into(),
+            email: None,
+        }),
+    };
+    let json = to_string_with_fields(&order, &["labels.b", "shipped.name"]).unwrap();
+    assert_eq!(json, r#"{"labels":{"b":2},"shipped":{"name":"Bob"}}"#);
+}
+
+#[test]
+fn whole_subtree_selection() {
+    let order = Order {
+        id: 7,
+        owner: Owner {
+            name: "Ada".into(),
+            email: Some("ada@example.com".into()),
+        },
+        items: vec![],
+        labels: BTreeMap::new(),
+        shipped: None,
+    };
+    let json = to_string_with_fields(&order, &["owner"]).unwrap();
+    assert_eq!(
+        json,
+        r#"{"owner":{"name":"Ada","email":"ada@example.com"}}"#
+    );
+}
+
+#[test]
+fn top_level_list() {
+    let items = vec![
+        Item {
+            name: "tea".into(),
+            price: 4,
+            tags: vec!["hot".into()],
+        },
+        Item {
+            name: "cake".into(),
+            price: 6,
+            tags: vec![],
+        },
+    ];
+    let json = peek_to_string_with_fields(Peek::new(&items), &["[*].name"]).unwrap();
+    assert_eq!(json, r#"[{"name":"tea"},{"name":"cake"}]"#);
+}
+
+#[test]
+fn invalid_selector_is_an_error() {
+    let order = Order {
+        id: 7,
+        owner: Owner {
+            name: "Ada".into(),
+            email: None,
+        },
+        items: vec![],
+        labels: BTreeMap::new(),
+        shipped: None,
+    };
+    let err = to_string_with_fields(&order, &["items[x]"]).unwrap_err();
+    assert!(err.to_string().contains("items[x]"), "{err}");
+}
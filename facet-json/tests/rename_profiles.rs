@@ -0,0 +1,94 @@
+use facet::Facet;
+
+use facet_format::serialize_with_fields;
+use facet_json::{JsonSerializer, SerializeOptions, to_string, to_string_with_options};
+use facet_reflect::Peek;
+
+#[derive(Debug, Facet, PartialEq)]
+#[facet(rename_all = "kebab-case")]
+struct Account {
+    #[facet(rename(json = "accountId", db = "account_id"))]
+    account_id: u64,
+    #[facet(rename(json = "displayName"))]
+    display_name: String,
+    created_at: u32,
+}
+
+#[test]
+fn rename_profiles_are_opt_in() {
+    let account = Account {
+        account_id: 42,
+        display_name: "Ada".to_string(),
+        created_at: 1700,
+    };
+    assert_eq!(
+        to_string(&account).unwrap(),
+        r#"{"account-id":42,"display-name":"Ada","created-at":1700}"#
+    );
+}
+
+#[test]
+fn rename_profile_selects_names() {
+    let json = SerializeOptions::new().rename_profile("json");
+    let account = Account {
+        account_id: 42,
+        display_name: "Ada".to_string(),
+        created_at: 1700,
+    };
+    assert_eq!(
+        to_string_with_options(&account, &json).unwrap(),
+        r#"{"accountId":42,"displayName":"Ada","created-at":1700}"#
+    );
+
+    let db = SerializeOptions::new().rename_profile("db");
+    assert_eq!(
+        to_string_with_options(&account, &db).unwrap(),
+        r#"{"account_id":42,"display-name":"Ada","created-at":1700}"#
+    );
+}
+
+#[test]
+fn rename_profile_applies_to_enum_fields() {
+    #[derive(Debug, Facet, PartialEq)]
+    #[repr(C)]
+    enum Event {
+        Created {
+            #[facet(rename(json = "userId"))]
+            user_id: u64,
+        },
+    }
+
+    let options = SerializeOptions::new().rename_profile("json");
+    assert_eq!(
+        to_string_with_options(&Event::Created { user_id: 1 }, &options).unwrap(),
+        r#"{"Created":{"userId":1}}"#
+    );
+}
+
+#[test]
+fn rename_profile_attrs_are_reflected() {
+    use facet::{StructType, Type, UserType};
+
+    let Type::User(UserType::Struct(StructType { fields, .. })) = Account::SHAPE.ty else {
+        panic!("expected a struct");
+    };
+    assert_eq!(fields[0].rename_for("db"), Some("account_id"));
+    assert_eq!(fields[1].rename_for("db"), None);
+    assert_eq!(fields[0].effective_name(), "account-id");
+}
+
+#[test]
+fn rename_profile_applies_to_projections() {
+    let mut serializer =
+        JsonSerializer::with_options(SerializeOptions::new().rename_profile("json"));
+    let account = Account {
+        account_id: 42,
+        display_name: "Ada".to_string(),
+        created_at: 1700,
+    };
+    serialize_with_fields(&mut serializer, Peek::new(&account), &["accountId"]).unwrap();
+    assert_eq!(
+        String::from_utf8(serializer.finish()).unwrap(),
+        r#"{"accountId":42}"#
+    );
+}
@@ -0,0 +1,57 @@
+//! The fuzzing seeds generated for a shape must be valid JSON documents for it.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_value::seed_values;
+
+#[derive(Debug, Facet)]
+struct Config {
+    name: String,
+    version: Version,
+    port: u16,
+    ratio: f32,
+    origin: [i8; 2],
+    enabled: bool,
+    initial: char,
+    fallback: Option<Box<Config>>,
+    hosts: Vec<Host>,
+    tags: HashSet<String>,
+    limits: BTreeMap<String, Option<u64>>,
+    shared: Arc<str>,
+    mode: Mode,
+    #[facet(default)]
+    extra: Vec<u8>,
+}
+
+#[derive(Debug, Facet)]
+struct Version(u8, u8, u8);
+
+#[derive(Debug, Facet)]
+struct Host {
+    address: String,
+    weight: i64,
+}
+
+#[derive(Debug, Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Mode {
+    Off,
+    Fixed(u32),
+    Pair(i16, String),
+    Custom { factor: f64 },
+}
+
+#[test]
+fn json_seeds_deserialize() {
+    let seeds = seed_values(Config::SHAPE);
+    assert_eq!(seeds.len(), 4);
+    for seed in seeds {
+        let json = facet_json::to_string(&seed).unwrap();
+        if let Err(err) = facet_json::from_str::<Config>(&json) {
+            panic!("seed {json} doesn't deserialize: {err}");
+        }
+    }
+}
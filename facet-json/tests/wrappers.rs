@@ -0,0 +1,45 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+
+use facet::Facet;
+use facet_json::{from_str, to_string};
+
+#[derive(Debug, Facet)]
+struct Session {
+    user: Option<Box<User>>,
+    hits: Arc<Mutex<u32>>,
+    roles: Arc<RwLock<Option<Vec<String>>>>,
+    scratch: Rc<RefCell<Vec<u8>>>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct User {
+    name: String,
+    manager: Option<Box<User>>,
+}
+
+#[test]
+fn nested_wrappers_round_trip() {
+    let json = r#"{"user":{"name":"ada","manager":{"name":"grace","manager":null}},"hits":3,"roles":["admin"],"scratch":[1,2]}"#;
+    let session: Session = from_str(json).unwrap();
+
+    let user = session.user.as_deref().unwrap();
+    assert_eq!(user.manager.as_deref().unwrap().name, "grace");
+    assert_eq!(*session.hits.lock().unwrap(), 3);
+    assert_eq!(
+        *session.roles.read().unwrap(),
+        Some(vec!["admin".to_string()])
+    );
+    assert_eq!(*session.scratch.borrow(), [1, 2]);
+
+    assert_eq!(to_string(&session).unwrap(), json);
+}
+
+#[test]
+fn locked_value_fails_to_serialize() {
+    let hits = Arc::new(Mutex::new(3u32));
+    let _guard = hits.lock().unwrap();
+    let err = to_string(&hits).unwrap_err();
+    assert!(err.to_string().contains("locked elsewhere"), "{err}");
+}
@@ -141,6 +141,17 @@ impl PFacetAttr {
     pub fn key_str(&self) -> String {
         self.key.to_string()
     }
+
+    /// Returns true for `rename(profile = "name", ...)`, as opposed to
+    /// a plain `rename = "name"`.
+    pub fn is_rename_profiles(&self) -> bool {
+        self.key == "rename"
+            && self
+                .args
+                .clone()
+                .into_iter()
+                .any(|t| matches!(t, proc_macro2::TokenTree::Punct(p) if p.as_char() == '='))
+    }
 }
 
 /// Parsed attr
@@ -624,7 +635,9 @@ impl PAttrs {
         for attr in &facet_attrs {
             if attr.is_builtin() {
                 match attr.key_str().as_str() {
-                    "rename" => {
+                    // `rename(json = "..", db = "..")` declares rename profiles,
+                    // which leave the field's own name alone
+                    "rename" if !attr.is_rename_profiles() => {
                         let s = attr.args.to_string();
                         let trimmed = s.trim().trim_matches('"');
                         *display_name = trimmed.to_string();
@@ -21,6 +21,7 @@ keyword! {
     KUnit = "unit";
     KNewtype = "newtype";
     KNewtypeStr = "newtype_str";
+    KNewtypeI64 = "newtype_i64";
     KNewtypeOptChar = "newtype_opt_char";
     KRec = "rec";
     KArbitrary = "arbitrary";
@@ -95,6 +96,8 @@ unsynn! {
         Newtype(KNewtype),
         /// newtype &'static str - stored directly for facet-core access
         NewtypeStr(KNewtypeStr),
+        /// newtype i64 - stored directly for facet-core access
+        NewtypeI64(KNewtypeI64),
         /// newtype `Option<char>` variant
         NewtypeOptChar(KNewtypeOptChar),
         /// struct variant with fields
@@ -144,6 +147,7 @@ enum ParsedVariantKind {
     Unit,
     Newtype,
     NewtypeStr,
+    NewtypeI64,
     NewtypeOptChar,
     Struct {
         struct_name: Ident,
@@ -219,6 +223,7 @@ impl VariantDef {
             VariantKindDef::Unit(_) => ParsedVariantKind::Unit,
             VariantKindDef::Newtype(_) => ParsedVariantKind::Newtype,
             VariantKindDef::NewtypeStr(_) => ParsedVariantKind::NewtypeStr,
+            VariantKindDef::NewtypeI64(_) => ParsedVariantKind::NewtypeI64,
             VariantKindDef::NewtypeOptChar(_) => ParsedVariantKind::NewtypeOptChar,
             VariantKindDef::Arbitrary(_) => ParsedVariantKind::Arbitrary,
             VariantKindDef::MakeT(_) => ParsedVariantKind::MakeT,
@@ -445,6 +450,13 @@ pub fn dispatch_attr(input: TokenStream2) -> TokenStream2 {
                         compile_error!("Internal error: newtype_str attributes should be handled directly in __attr, not through __dispatch_attr")
                     )
                 }
+                ParsedVariantKind::NewtypeI64 => {
+                    // Like newtype_str, newtype_i64 stores its value directly and is
+                    // handled in the __attr macro.
+                    quote_spanned!(attr_span =>
+                        compile_error!("Internal error: newtype_i64 attributes should be handled directly in __attr, not through __dispatch_attr")
+                    )
+                }
                 ParsedVariantKind::NewtypeOptChar => generate_newtype_opt_char_value(
                     crate_path,
                     &variant_ident,
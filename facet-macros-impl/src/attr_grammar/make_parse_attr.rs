@@ -26,6 +26,7 @@ keyword! {
     KCratePath = "crate_path";
     KChar = "char";
     KStr = "str";
+    KI64 = "i64";
     KBuiltin = "builtin";
     KMakeT = "make_t";
     KOr = "or";
@@ -145,6 +146,11 @@ unsynn! {
         _str: KStr,
     }
 
+    /// A 64-bit signed integer: `i64`
+    struct I64Type {
+        _i64: KI64,
+    }
+
     /// A static reference to some other type: `&'static SomeType`
     struct StaticRef {
         _amp: Amp,
@@ -304,6 +310,9 @@ enum VariantKind {
     /// Newtype holding `&'static str` - stored directly for facet-core access.
     /// Used for attributes like `tag`, `content`, `rename`, `rename_all`, `alias`.
     NewtypeStr,
+    /// Newtype holding `i64` - stored directly for facet-core access.
+    /// Used for attributes like `order`.
+    NewtypeI64,
     NewtypeOptionChar,
     Struct(proc_macro2::Ident),
     /// Arbitrary type like `Option<DefaultInPlaceFn>` - the tokens are passed through as-is
@@ -546,6 +555,7 @@ fn parse_storage_attr(attrs: &[OuterAttr]) -> std::result::Result<Storage, Strin
 ///
 /// Uses unsynn grammar parsing to handle:
 /// - `&'static str` → NewtypeStr
+/// - `i64` → NewtypeI64
 /// - `&'static SomeType` → Newtype
 /// - `Option<char>` → NewtypeOptionChar
 /// - `Option<&'static str>` → OptionalStr
@@ -568,6 +578,14 @@ fn analyze_variant_payload(tokens: &[TokenTree]) -> std::result::Result<VariantK
         }
     }
 
+    // i64 → NewtypeI64
+    {
+        let mut iter = token_stream.clone().to_token_iter();
+        if iter.parse::<I64Type>().is_ok() && iter.next().is_none() {
+            return Ok(VariantKind::NewtypeI64);
+        }
+    }
+
     // &'static SomeType → Newtype
     {
         let mut iter = token_stream.clone().to_token_iter();
@@ -789,6 +807,7 @@ impl ParsedGrammar {
                     VariantKind::Unit => quote! { #(#attrs)* #name },
                     VariantKind::Newtype(ty) => quote! { #(#attrs)* #name(#ty) },
                     VariantKind::NewtypeStr => quote! { #(#attrs)* #name(&'static str) },
+                    VariantKind::NewtypeI64 => quote! { #(#attrs)* #name(i64) },
                     VariantKind::NewtypeOptionChar => quote! { #(#attrs)* #name(Option<char>) },
                     VariantKind::Struct(struct_name) => {
                         quote! { #(#attrs)* #name(#struct_name) }
@@ -869,7 +888,10 @@ impl ParsedGrammar {
                         }
                     }
                     // Simple value types: use regular equality
-                    VariantKind::NewtypeStr | VariantKind::NewtypeOptionChar | VariantKind::OptionalStr => {
+                    VariantKind::NewtypeStr
+                    | VariantKind::NewtypeI64
+                    | VariantKind::NewtypeOptionChar
+                    | VariantKind::OptionalStr => {
                         quote! {
                             (Self::#variant_name(a), Self::#variant_name(b)) => a == b
                         }
@@ -974,6 +996,7 @@ impl ParsedGrammar {
                     VariantKind::Unit => quote! { #name: unit },
                     VariantKind::Newtype(_) => quote! { #name: newtype },
                     VariantKind::NewtypeStr => quote! { #name: newtype_str },
+                    VariantKind::NewtypeI64 => quote! { #name: newtype_i64 },
                     VariantKind::NewtypeOptionChar => quote! { #name: newtype_opt_char },
                     VariantKind::ArbitraryType(_) => quote! { #name: arbitrary },
                     VariantKind::MakeT { .. } => quote! { #name: make_t },
@@ -1375,6 +1398,48 @@ impl ParsedGrammar {
                             }};
                         }
                     }
+                    VariantKind::NewtypeI64 => {
+                        // NewtypeI64 stores i64 directly (not wrapped in Attr), so
+                        // facet-core can read attributes like `order`.
+                        quote! {
+                            // Field-level: no args is an error
+                            (@ns { $ns:path } #key_ident { $field:tt : $ty:ty }) => {{
+                                compile_error!(concat!(
+                                    "Attribute `",
+                                    stringify!(#key_ident),
+                                    "` requires an integer value: `",
+                                    stringify!(#key_ident),
+                                    " = 1`"
+                                ))
+                            }};
+                            // Field-level with `= value`: store integer directly
+                            (@ns { $ns:path } #key_ident { $field:tt : $ty:ty | = $val:expr }) => {{
+                                ::facet::Attr::new::<i64>(#ns_expr, #key_str, &$val)
+                            }};
+                            // Field-level with just expr
+                            (@ns { $ns:path } #key_ident { $field:tt : $ty:ty | $val:expr }) => {{
+                                ::facet::Attr::new::<i64>(#ns_expr, #key_str, &$val)
+                            }};
+                            // Container-level: no args is an error
+                            (@ns { $ns:path } #key_ident { }) => {{
+                                compile_error!(concat!(
+                                    "Attribute `",
+                                    stringify!(#key_ident),
+                                    "` requires an integer value: `",
+                                    stringify!(#key_ident),
+                                    " = 1`"
+                                ))
+                            }};
+                            // Container-level with `= value`: store integer directly
+                            (@ns { $ns:path } #key_ident { | = $val:expr }) => {{
+                                ::facet::Attr::new::<i64>(#ns_expr, #key_str, &$val)
+                            }};
+                            // Container-level with just expr
+                            (@ns { $ns:path } #key_ident { | $val:expr }) => {{
+                                ::facet::Attr::new::<i64>(#ns_expr, #key_str, &$val)
+                            }};
+                        }
+                    }
                     VariantKind::OptionalStr => {
                         // OptionalStr stores Option<&'static str> directly.
                         // - No args → None
@@ -6,6 +6,8 @@ use crate::{LifetimeName, RenameRule, process_enum, process_struct};
 
 /// Generate a static declaration that pre-evaluates `<T as Facet>::SHAPE`.
 /// Only emitted in release builds to avoid slowing down debug compile times.
+/// Also adds the type to the shape registry, which is a no-op unless facet's
+/// `registry` feature is enabled.
 /// Skipped for generic types since we can't create a static for an unmonomorphized type.
 pub(crate) fn generate_static_decl(
     type_name: &Ident,
@@ -25,6 +27,8 @@ pub(crate) fn generate_static_decl(
     quote! {
         #[cfg(not(debug_assertions))]
         static #static_name_ident: &'static #facet_crate::Shape = <#type_name as #facet_crate::Facet>::SHAPE;
+
+        #facet_crate::__register_shape!(#type_name);
     }
 }
 
@@ -1,6 +1,7 @@
 use super::*;
 use crate::process_struct::{
-    TraitSources, gen_field_from_pfield, gen_trait_bounds, gen_type_ops, gen_vtable,
+    TraitSources, gen_field_from_pfield, gen_marker_traits_call, gen_trait_bounds, gen_type_ops,
+    gen_vtable,
 };
 use proc_macro2::Literal;
 use quote::{format_ident, quote, quote_spanned};
@@ -265,6 +266,11 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
         quote! {}
     };
 
+    let marker_traits_call = gen_marker_traits_call(
+        pe.container.attrs.declared_traits.as_ref(),
+        has_type_or_const_generics,
+    );
+
     // Container-level proxy from PEnum - generates ProxyDef with conversion functions
     let proxy_call = {
         if let Some(attr) = pe
@@ -507,6 +513,7 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                                     &pfield,
                                     &shadow_struct_name,
                                     &facet_bgp,
+                                    parsed.clauses.as_ref(),
                                     Some(variant_offset.clone()),
                                     &facet_crate,
                                     skip_all_unless_truthy,
@@ -568,6 +575,7 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                                     pf,
                                     &shadow_struct_name,
                                     &facet_bgp,
+                                    parsed.clauses.as_ref(),
                                     Some(variant_offset.clone()),
                                     &facet_crate,
                                     skip_all_unless_truthy,
@@ -716,6 +724,7 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                                     &pf,
                                     &shadow_struct_name,
                                     &facet_bgp,
+                                    parsed.clauses.as_ref(),
                                     None,
                                     &facet_crate,
                                     skip_all_unless_truthy,
@@ -778,6 +787,7 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                                     pf,
                                     &shadow_struct_name,
                                     &facet_bgp,
+                                    parsed.clauses.as_ref(),
                                     None,
                                     &facet_crate,
                                     skip_all_unless_truthy,
@@ -976,6 +986,7 @@ pub(crate) fn process_enum(parsed: Enum) -> TokenStream {
                     #untagged_call
                     #is_numeric_call
                     #pod_call
+                    #marker_traits_call
                     #proxy_call
                     #variance_call
                     .build()
@@ -775,17 +775,119 @@ pub(crate) fn gen_trait_bounds(
     }
 }
 
+/// Generate shape builder calls recording the declared marker traits, so they
+/// show up in the shape's `MarkerTraits`. The assertions from
+/// [`gen_trait_bounds`] keep these honest.
+///
+/// Types with type or const parameters record none: whether `Wrapper<T>` is
+/// `Send` depends on `T`, and the assertions only check the declaration, not
+/// each instantiation, so recording the traits could claim `Send` for a
+/// `Wrapper<Rc<u8>>`.
+pub(crate) fn gen_marker_traits_call(
+    declared: Option<&DeclaredTraits>,
+    has_type_or_const_generics: bool,
+) -> TokenStream {
+    let Some(declared) = declared else {
+        return quote! {};
+    };
+    if has_type_or_const_generics {
+        return quote! {};
+    }
+    let mut calls = Vec::new();
+    if declared.copy {
+        calls.push(quote! { .copy() });
+    }
+    if declared.eq {
+        calls.push(quote! { .eq() });
+    }
+    if declared.send {
+        calls.push(quote! { .send() });
+    }
+    if declared.sync {
+        calls.push(quote! { .sync() });
+    }
+    if declared.unpin {
+        calls.push(quote! { .unpin() });
+    }
+    quote! { #(#calls)* }
+}
+
+/// Splits `#[facet(rename(json = "fooBar", db = "foo_bar"))]` into one
+/// `rename::profile` attribute per entry, holding the name as a `&'static str`.
+fn rename_profile_attrs(args: &TokenStream) -> Vec<TokenStream> {
+    let tokens: Vec<TokenTree> = args.clone().into_iter().collect();
+    let mut attrs = Vec::new();
+    for entry in tokens.split(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ',')) {
+        match entry {
+            [] => {}
+            [
+                TokenTree::Ident(profile),
+                TokenTree::Punct(eq),
+                TokenTree::Literal(name),
+            ] if eq.as_char() == '=' => {
+                let profile = profile.to_string();
+                attrs.push(quote! {
+                    𝟋Attr::new(::core::option::Option::Some("rename"), #profile, &#name)
+                });
+            }
+            other => {
+                let span = other[0].span();
+                attrs.push(quote_spanned! { span =>
+                    compile_error!("expected `profile = \"name\"` in `rename(...)`")
+                });
+            }
+        }
+    }
+    attrs
+}
+
+/// Splits the entries of `#[facet(ui(...))]` into `ui::key` attributes:
+/// `widget = "password"` stores the string, a bare `readonly` stores `true`.
+fn ui_attrs(args: &TokenStream) -> Vec<TokenStream> {
+    let tokens: Vec<TokenTree> = args.clone().into_iter().collect();
+    let mut attrs = Vec::new();
+    for entry in tokens.split(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ',')) {
+        match entry {
+            [] => {}
+            [TokenTree::Ident(key)] => {
+                let key = key.to_string();
+                attrs.push(quote! { 𝟋Attr::new(::core::option::Option::Some("ui"), #key, &true) });
+            }
+            [TokenTree::Ident(key), TokenTree::Punct(eq), value @ ..]
+                if eq.as_char() == '=' && !value.is_empty() =>
+            {
+                let key = key.to_string();
+                let value: TokenStream = value.iter().cloned().collect();
+                attrs
+                    .push(quote! { 𝟋Attr::new(::core::option::Option::Some("ui"), #key, &#value) });
+            }
+            other => {
+                let span = other[0].span();
+                attrs.push(quote_spanned! { span =>
+                    compile_error!("expected `key` or `key = value` in `ui(...)`")
+                });
+            }
+        }
+    }
+    attrs
+}
+
 /// Generates the `::facet::Field` definition `TokenStream` from a `PStructField`.
 pub(crate) fn gen_field_from_pfield(
     field: &PStructField,
     struct_name: &Ident,
     bgp: &BoundedGenericParams,
+    where_clauses: Option<&WhereClauses>,
     base_offset: Option<TokenStream>,
     facet_crate: &TokenStream,
     skip_all_unless_truthy: bool,
 ) -> TokenStream {
     let field_name_effective = &field.name.effective;
     let field_name_raw = &field.name.raw;
+    let field_ident = match field_name_raw {
+        IdentOrLiteral::Ident(ident) => ident.to_string(),
+        IdentOrLiteral::Literal(index) => index.to_string(),
+    };
     let field_type = &field.ty;
 
     let bgp_without_bounds = bgp.display_without_bounds();
@@ -833,11 +935,13 @@ pub(crate) fn gen_field_from_pfield(
     let mut default_value: Option<DefaultKind> = None;
     let mut skip_serializing_if_value: Option<TokenStream> = None;
     let mut invariants_value: Option<TokenStream> = None;
+    let mut invariants_message_value: Option<TokenStream> = None;
     let mut proxy_value: Option<TokenStream> = None;
     let mut metadata_value: Option<String> = None;
     let mut attribute_list: Vec<TokenStream> = Vec::new();
 
     let mut want_truthy_skip = skip_all_unless_truthy;
+    let mut want_absent_skip = false;
 
     for attr in &field.attrs.facet {
         if attr.is_builtin() {
@@ -889,7 +993,17 @@ pub(crate) fn gen_field_from_pfield(
                     let kind_str = args_str.trim_start_matches('=').trim();
                     metadata_value = Some(kind_str.to_string());
                 }
+                "ui" => {
+                    // ui(widget = "password", readonly) - form rendering hints,
+                    // stored as one `ui::key` attribute per entry
+                    attribute_list.extend(ui_attrs(&attr.args));
+                }
                 // Field attrs - store in dedicated field, don't add to attribute_list
+                "rename" if attr.is_rename_profiles() => {
+                    // rename(json = "fooBar", db = "foo_bar") - per-profile names,
+                    // stored as one `rename::profile` attribute per entry
+                    attribute_list.extend(rename_profile_attrs(&attr.args));
+                }
                 "rename" => {
                     // Extract the string literal from args
                     let args = &attr.args;
@@ -921,13 +1035,61 @@ pub(crate) fn gen_field_from_pfield(
                 "skip_unless_truthy" => {
                     want_truthy_skip = true;
                 }
+                "encrypt" => {
+                    // Kept in the attributes slice for serializers; the
+                    // plaintext must not show up in debug output either
+                    flags.push(quote! { 𝟋FF::SENSITIVE });
+                    let ext_attr =
+                        emit_attr_for_field(attr, field_name_raw, field_type, facet_crate);
+                    attribute_list.push(quote! { #ext_attr });
+                }
+                "double_option" => {
+                    // Kept in the attributes slice for deserializers; an absent
+                    // (outer `None`) value is also skipped when serializing
+                    want_absent_skip = true;
+                    let ext_attr =
+                        emit_attr_for_field(attr, field_name_raw, field_type, facet_crate);
+                    attribute_list.push(quote! { #ext_attr });
+                }
                 "invariants" => {
                     // User provides a function name: #[facet(invariants = fn_name)]
                     let args = &attr.args;
                     let args_str = args.to_string();
                     let fn_name_str = args_str.trim_start_matches('=').trim();
                     let fn_name: TokenStream = fn_name_str.parse().unwrap_or_else(|_| args.clone());
-                    invariants_value = Some(quote! { #fn_name });
+                    // Generate wrapper functions that convert PtrConst to the expected type
+                    // and accept both `bool` and `Result` return types. They're generic over
+                    // the container's parameters, since `#field_type` may name them.
+                    let wrapper_bgp = bgp.display_with_bounds();
+                    let wrapper_args = if bgp.params.is_empty() {
+                        quote! {}
+                    } else {
+                        let args = bgp.display_without_bounds();
+                        quote! { ::#args }
+                    };
+                    let wrapper_where = where_clauses.map(|wc| wc.to_token_stream());
+                    invariants_value = Some(quote! {
+                        {
+                            unsafe fn __field_invariants_wrapper #wrapper_bgp (
+                                ptr: #facet_crate::PtrConst,
+                            ) -> bool #wrapper_where {
+                                let value: &#field_type = unsafe { ptr.get() };
+                                #facet_crate::InvariantsOutcome::into_invariants_result(#fn_name(value)).is_ok()
+                            }
+                            __field_invariants_wrapper #wrapper_args
+                        }
+                    });
+                    invariants_message_value = Some(quote! {
+                        {
+                            unsafe fn __field_invariants_message_wrapper #wrapper_bgp (
+                                ptr: #facet_crate::PtrConst,
+                            ) -> #facet_crate::𝟋::𝟋Result<(), #facet_crate::𝟋::𝟋Str> #wrapper_where {
+                                let value: &#field_type = unsafe { ptr.get() };
+                                #facet_crate::InvariantsOutcome::into_invariants_result(#fn_name(value))
+                            }
+                            __field_invariants_message_wrapper #wrapper_args
+                        }
+                    });
                 }
                 "proxy" => {
                     // User provides a type: #[facet(proxy = ProxyType)]
@@ -984,6 +1146,18 @@ pub(crate) fn gen_field_from_pfield(
         }
     }
 
+    if skip_serializing_if_value.is_none() && want_absent_skip {
+        skip_serializing_if_value = Some(quote! {
+            {
+                unsafe fn __skip_absent(ptr: #facet_crate::PtrConst) -> bool {
+                    let value: &#field_type = unsafe { ptr.get() };
+                    value.is_none()
+                }
+                __skip_absent
+            }
+        });
+    }
+
     if skip_serializing_if_value.is_none() && want_truthy_skip {
         skip_serializing_if_value = Some(quote! {
             {
@@ -1192,6 +1366,10 @@ pub(crate) fn gen_field_from_pfield(
         Some(inv) => quote! { ::core::option::Option::Some(#inv) },
         None => quote! { ::core::option::Option::None },
     };
+    let invariants_message_expr = match &invariants_message_value {
+        Some(inv) => quote! { ::core::option::Option::Some(#inv) },
+        None => quote! { ::core::option::Option::None },
+    };
 
     // Proxy: Option (requires alloc feature in facet-core)
     // We always emit this field since we can't check facet-core's features from generated code.
@@ -1211,6 +1389,7 @@ pub(crate) fn gen_field_from_pfield(
     quote! {
         𝟋Fld {
             name: #field_name_effective,
+            ident: #field_ident,
             shape: #shape_ref_expr,
             offset: #final_offset,
             flags: #flags_expr,
@@ -1221,6 +1400,7 @@ pub(crate) fn gen_field_from_pfield(
             default: #default_expr,
             skip_serializing_if: #skip_ser_if_expr,
             invariants: #invariants_expr,
+            invariants_message: #invariants_message_expr,
             proxy: #proxy_expr,
             metadata: #metadata_expr,
         }
@@ -1377,8 +1557,10 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
         if !invariant_exprs.is_empty() {
             let tests = invariant_exprs.iter().map(|expr| {
                 quote! {
-                    if !#expr(value) {
-                        return 𝟋Result::Err(𝟋Str::from("invariant check failed"));
+                    if let 𝟋Result::Err(message) =
+                        #facet_crate::InvariantsOutcome::into_invariants_result(#expr(value))
+                    {
+                        return 𝟋Result::Err(message);
                     }
                 }
             });
@@ -1442,6 +1624,13 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
         }
     };
 
+    // Still need original AST for where clauses and type params for build_ helpers
+    let where_clauses_ast = match &parsed.kind {
+        StructKind::Struct { clauses, .. } => clauses.as_ref(),
+        StructKind::TupleStruct { clauses, .. } => clauses.as_ref(),
+        StructKind::UnitStruct { clauses, .. } => clauses.as_ref(),
+    };
+
     // Use PStruct for kind and fields
     let (kind, fields_vec) = match &ps.kind {
         PStructKind::Struct { fields } => {
@@ -1453,6 +1642,7 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
                         field,
                         struct_name,
                         &ps.container.bgp,
+                        where_clauses_ast,
                         None,
                         &facet_crate,
                         skip_all_unless_truthy,
@@ -1470,6 +1660,7 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
                         field,
                         struct_name,
                         &ps.container.bgp,
+                        where_clauses_ast,
                         None,
                         &facet_crate,
                         skip_all_unless_truthy,
@@ -1493,12 +1684,6 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
         quote! { .variance(𝟋CV) }
     };
 
-    // Still need original AST for where clauses and type params for build_ helpers
-    let where_clauses_ast = match &parsed.kind {
-        StructKind::Struct { clauses, .. } => clauses.as_ref(),
-        StructKind::TupleStruct { clauses, .. } => clauses.as_ref(),
-        StructKind::UnitStruct { clauses, .. } => clauses.as_ref(),
-    };
     let where_clauses = build_where_clauses(
         where_clauses_ast,
         parsed.generics.as_ref(),
@@ -1574,6 +1759,11 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
         quote! {}
     };
 
+    let marker_traits_call = gen_marker_traits_call(
+        ps.container.attrs.declared_traits.as_ref(),
+        has_type_or_const_generics,
+    );
+
     // Type tag from PStruct - returns builder call only if present
     let type_tag_call = {
         if let Some(type_tag) = ps.container.attrs.get_builtin_args("type_tag") {
@@ -1825,6 +2015,7 @@ pub(crate) fn process_struct(parsed: Struct) -> TokenStream {
                     #inner_call
                     #variance_call
                     #pod_call
+                    #marker_traits_call
                     .build()
             };
         }
@@ -60,7 +60,10 @@ pub use axum::{MsgPack, MsgPackRejection, MsgPackSerializeRejection};
 #[cfg(feature = "jit")]
 pub use jit::MsgPackJitFormat;
 pub use parser::MsgPackParser;
-pub use serializer::{MsgPackSerializeError, MsgPackSerializer, to_vec, to_writer};
+pub use serializer::{
+    MsgPackSerializeError, MsgPackSerializer, SerializeOptions, to_vec, to_vec_with_options,
+    to_writer,
+};
 
 // Re-export DeserializeError for convenience
 pub use facet_format::DeserializeError;
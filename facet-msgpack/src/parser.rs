@@ -193,6 +193,35 @@ impl<'de> MsgPackParser<'de> {
             })
     }
 
+    /// Read a map key: a string, or an unsigned integer field index as
+    /// written by [`crate::SerializeOptions::indexed_fields`].
+    fn read_key(&mut self) -> Result<(Cow<'de, str>, FieldLocationHint), MsgPackError> {
+        let index = match self.input.get(self.pos).copied() {
+            Some(prefix @ 0x00..=MSGPACK_POSFIXINT_MAX) => {
+                self.pos += 1;
+                prefix as u64
+            }
+            Some(MSGPACK_UINT8) => {
+                self.pos += 1;
+                self.read_byte()? as u64
+            }
+            Some(MSGPACK_UINT16) => {
+                self.pos += 1;
+                self.read_u16()? as u64
+            }
+            Some(MSGPACK_UINT32) => {
+                self.pos += 1;
+                self.read_u32()? as u64
+            }
+            Some(MSGPACK_UINT64) => {
+                self.pos += 1;
+                self.read_u64()?
+            }
+            _ => return Ok((self.read_string()?, FieldLocationHint::KeyValue)),
+        };
+        Ok((Cow::Owned(format!("{index}")), FieldLocationHint::Index))
+    }
+
     /// Read an array length.
     fn read_array_len(&mut self, prefix: u8) -> Result<usize, MsgPackError> {
         match prefix {
@@ -275,8 +304,8 @@ impl<'de> MsgPackParser<'de> {
         };
 
         if let Some(remaining) = expecting_key_remaining {
-            // We expect a key (string)
-            let key = self.read_string()?;
+            // We expect a key (string, or field index)
+            let (key, location) = self.read_key()?;
 
             // Update the stack: decrement remaining and transition to expecting value
             let new_remaining = remaining - 1;
@@ -286,10 +315,7 @@ impl<'de> MsgPackParser<'de> {
                 };
             }
 
-            return Ok(Some(ParseEvent::FieldKey(FieldKey::new(
-                key,
-                FieldLocationHint::KeyValue,
-            ))));
+            return Ok(Some(ParseEvent::FieldKey(FieldKey::new(key, location))));
         }
 
         // Parse the next value
@@ -861,6 +887,21 @@ impl<'de> FormatParser<'de> for MsgPackParser<'de> {
         Ok(event)
     }
 
+    fn container_len(&self) -> Option<usize> {
+        // Right after a start event, its container is on top of the stack
+        // with none of its items consumed yet
+        match self.stack.last()? {
+            ContextState::Array { remaining } | ContextState::MapKey { remaining } => {
+                Some(*remaining)
+            }
+            ContextState::MapValue { .. } => None,
+        }
+    }
+
+    fn compresses_fields(&self) -> bool {
+        true
+    }
+
     fn skip_value(&mut self) -> Result<(), Self::Error> {
         debug_assert!(
             self.event_peek.is_none(),
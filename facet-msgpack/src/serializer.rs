@@ -5,8 +5,34 @@ extern crate alloc;
 use alloc::{string::String, vec::Vec};
 use core::fmt::Write as _;
 
+use facet_core::{Field, StructType, Type, UserType};
 use facet_format::{FormatSerializer, ScalarValue, SerializeError};
 
+/// Options for MsgPack serialization.
+#[derive(Debug, Clone, Default)]
+pub struct SerializeOptions {
+    /// Write struct fields as their declaration index instead of their name
+    /// (default: false)
+    pub indexed_fields: bool,
+}
+
+impl SerializeOptions {
+    /// Create new default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Key struct fields by declaration index instead of by name.
+    ///
+    /// Payloads get smaller, but are only readable by a decoder that knows
+    /// the field order. [`crate::from_slice`] accepts both forms. Map keys and
+    /// fields flattened in from another struct keep their names.
+    pub fn indexed_fields(mut self) -> Self {
+        self.indexed_fields = true;
+        self
+    }
+}
+
 /// MsgPack serializer error.
 #[derive(Debug)]
 pub struct MsgPackSerializeError {
@@ -26,20 +52,40 @@ pub struct MsgPackSerializer {
     out: Vec<u8>,
     /// Stack tracking whether we're in a struct or sequence, and item counts
     stack: Vec<ContainerState>,
+    options: SerializeOptions,
+    /// Fields of the struct announced by `struct_metadata`, for indexed keys
+    pending_fields: Option<&'static [Field]>,
+    /// Index to write instead of the next field name
+    next_key_index: Option<usize>,
 }
 
 #[derive(Debug)]
 enum ContainerState {
-    Struct { count: usize, count_pos: usize },
-    Seq { count: usize, count_pos: usize },
+    Struct {
+        count: usize,
+        count_pos: usize,
+        fields: Option<&'static [Field]>,
+    },
+    Seq {
+        count: usize,
+        count_pos: usize,
+    },
 }
 
 impl MsgPackSerializer {
     /// Create a new MsgPack serializer.
     pub fn new() -> Self {
+        Self::with_options(SerializeOptions::default())
+    }
+
+    /// Create a new MsgPack serializer with the given options.
+    pub fn with_options(options: SerializeOptions) -> Self {
         Self {
             out: Vec::new(),
             stack: Vec::new(),
+            options,
+            pending_fields: None,
+            next_key_index: None,
         }
     }
 
@@ -48,7 +94,9 @@ impl MsgPackSerializer {
         // Patch up any remaining container counts (shouldn't happen with well-formed input)
         while let Some(state) = self.stack.pop() {
             match state {
-                ContainerState::Struct { count, count_pos } => {
+                ContainerState::Struct {
+                    count, count_pos, ..
+                } => {
                     self.patch_map_count(count_pos, count);
                 }
                 ContainerState::Seq { count, count_pos } => {
@@ -183,6 +231,13 @@ impl MsgPackSerializer {
         self.out.extend_from_slice(bytes);
     }
 
+    /// Count a value towards the enclosing sequence, if any.
+    fn count_seq_item(&mut self) {
+        if let Some(ContainerState::Seq { count, .. }) = self.stack.last_mut() {
+            *count += 1;
+        }
+    }
+
     /// Write a map header with placeholder count, return position of count.
     fn begin_map(&mut self) -> usize {
         // Use map32 format for flexibility (we'll patch it later)
@@ -260,11 +315,41 @@ impl Default for MsgPackSerializer {
 impl FormatSerializer for MsgPackSerializer {
     type Error = MsgPackSerializeError;
 
+    fn struct_metadata(&mut self, shape: &facet_core::Shape) -> Result<(), Self::Error> {
+        if let Type::User(UserType::Struct(StructType { fields, .. })) = shape.ty {
+            self.pending_fields = Some(fields);
+        }
+        Ok(())
+    }
+
+    fn field_metadata(&mut self, field_item: &facet_reflect::FieldItem) -> Result<(), Self::Error> {
+        self.next_key_index = None;
+        if !self.options.indexed_fields || field_item.flattened {
+            return Ok(());
+        }
+        if let (
+            Some(ContainerState::Struct {
+                fields: Some(fields),
+                ..
+            }),
+            Some(field),
+        ) = (self.stack.last(), &field_item.field)
+        {
+            // Fields flattened in from another struct aren't in this list
+            self.next_key_index = fields
+                .iter()
+                .position(|f| f.name == field.name && f.offset == field.offset);
+        }
+        Ok(())
+    }
+
     fn begin_struct(&mut self) -> Result<(), Self::Error> {
+        self.count_seq_item();
         let count_pos = self.begin_map();
         self.stack.push(ContainerState::Struct {
             count: 0,
             count_pos,
+            fields: self.pending_fields.take(),
         });
         Ok(())
     }
@@ -274,13 +359,18 @@ impl FormatSerializer for MsgPackSerializer {
         if let Some(ContainerState::Struct { count, .. }) = self.stack.last_mut() {
             *count += 1;
         }
-        self.write_str(key);
+        match self.next_key_index.take() {
+            Some(index) => self.write_u64(index as u64),
+            None => self.write_str(key),
+        }
         Ok(())
     }
 
     fn end_struct(&mut self) -> Result<(), Self::Error> {
         match self.stack.pop() {
-            Some(ContainerState::Struct { count, count_pos }) => {
+            Some(ContainerState::Struct {
+                count, count_pos, ..
+            }) => {
                 self.patch_map_count(count_pos, count);
                 Ok(())
             }
@@ -291,6 +381,7 @@ impl FormatSerializer for MsgPackSerializer {
     }
 
     fn begin_seq(&mut self) -> Result<(), Self::Error> {
+        self.count_seq_item();
         let count_pos = self.begin_array();
         self.stack.push(ContainerState::Seq {
             count: 0,
@@ -311,11 +402,12 @@ impl FormatSerializer for MsgPackSerializer {
         }
     }
 
+    fn compresses_fields(&self) -> bool {
+        true
+    }
+
     fn scalar(&mut self, scalar: ScalarValue<'_>) -> Result<(), Self::Error> {
-        // Increment count in current sequence
-        if let Some(ContainerState::Seq { count, .. }) = self.stack.last_mut() {
-            *count += 1;
-        }
+        self.count_seq_item();
 
         match scalar {
             ScalarValue::Null => self.write_nil(),
@@ -352,6 +444,34 @@ where
     Ok(ser.finish())
 }
 
+/// Serialize a value to MsgPack bytes with custom options.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_msgpack::{SerializeOptions, from_slice, to_vec, to_vec_with_options};
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Point { x: i32, y: i32 }
+///
+/// let point = Point { x: 10, y: 20 };
+/// let bytes = to_vec_with_options(&point, &SerializeOptions::new().indexed_fields()).unwrap();
+/// // {0: 10, 1: 20}
+/// assert_eq!(bytes, [0x82, 0x00, 0x0a, 0x01, 0x14]);
+/// assert!(bytes.len() < to_vec(&point).unwrap().len());
+/// assert_eq!(from_slice::<Point>(&bytes).unwrap(), point);
+/// ```
+pub fn to_vec_with_options<'facet, T>(
+    value: &T,
+    options: &SerializeOptions,
+) -> Result<Vec<u8>, SerializeError<MsgPackSerializeError>>
+where
+    T: facet_core::Facet<'facet>,
+{
+    let mut ser = MsgPackSerializer::with_options(options.clone());
+    facet_format::serialize_root(&mut ser, facet_reflect::Peek::new(value))?;
+    Ok(ser.finish())
+}
+
 /// Serialize a value to MsgPack bytes using a writer.
 pub fn to_writer<'facet, T, W>(writer: &mut W, value: &T) -> Result<(), std::io::Error>
 where
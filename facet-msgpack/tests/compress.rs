@@ -0,0 +1,107 @@
+//! Fields marked `#[facet(compress = "zstd")]` are written compressed.
+
+use facet::Facet;
+use facet_format::{compression, serialize_with_fields};
+use facet_msgpack::{MsgPackSerializer, from_slice, to_vec};
+use facet_reflect::Peek;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Page {
+    url: String,
+    #[facet(compress = "zstd")]
+    html: String,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+enum Upload {
+    Inline {
+        #[facet(compress = "zstd")]
+        data: Vec<u8>,
+    },
+    Remote {
+        url: String,
+    },
+}
+
+#[test]
+fn test_compressed_fields_roundtrip() {
+    let page = Page {
+        url: "https://example.com".into(),
+        html: "<p>hello</p>".repeat(1000),
+    };
+    let bytes = to_vec(&page).unwrap();
+    assert!(bytes.len() < 200, "{} bytes", bytes.len());
+    assert_eq!(from_slice::<Page>(&bytes).unwrap(), page);
+
+    let upload = Upload::Inline {
+        data: vec![7; 4096],
+    };
+    let bytes = to_vec(&upload).unwrap();
+    assert!(bytes.len() < 100, "{} bytes", bytes.len());
+    assert_eq!(from_slice::<Upload>(&bytes).unwrap(), upload);
+}
+
+/// The same struct, seen from serde with the page as raw bytes
+#[derive(Debug, Serialize, Deserialize)]
+struct RawPage {
+    url: String,
+    #[serde(with = "serde_bytes")]
+    html: Vec<u8>,
+}
+
+#[test]
+fn test_compressed_field_is_bin() {
+    let page = Page {
+        url: "https://example.com".into(),
+        html: "<p>hello</p>".into(),
+    };
+    let raw: RawPage = rmp_serde::from_slice(&to_vec(&page).unwrap()).unwrap();
+    let html = compression::decompress("zstd", &raw.html, usize::MAX).unwrap();
+    assert_eq!(html, page.html.as_bytes());
+}
+
+#[test]
+fn test_compressed_tuple_struct_fields_roundtrip() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Snapshot(u32, #[facet(compress = "zstd")] String);
+
+    let snapshot = Snapshot(1, "<p>hello</p>".repeat(1000));
+    let bytes = to_vec(&snapshot).unwrap();
+    assert!(bytes.len() < 200, "{} bytes", bytes.len());
+    assert_eq!(from_slice::<Snapshot>(&bytes).unwrap(), snapshot);
+}
+
+#[test]
+fn test_projected_fields_are_compressed() {
+    #[derive(Debug, Deserialize)]
+    struct RawHtml {
+        #[serde(with = "serde_bytes")]
+        html: Vec<u8>,
+    }
+
+    let page = Page {
+        url: "https://example.com".into(),
+        html: "<p>hello</p>".into(),
+    };
+    let mut serializer = MsgPackSerializer::new();
+    serialize_with_fields(&mut serializer, Peek::new(&page), &["html"]).unwrap();
+    let raw: RawHtml = rmp_serde::from_slice(&serializer.finish()).unwrap();
+    let html = compression::decompress("zstd", &raw.html, usize::MAX).unwrap();
+    assert_eq!(html, page.html.as_bytes());
+}
+
+#[test]
+fn test_corrupt_data_is_an_error() {
+    let raw = RawPage {
+        url: "https://example.com".into(),
+        html: b"not zstd".to_vec(),
+    };
+    let err = from_slice::<Page>(&rmp_serde::to_vec_named(&raw).unwrap()).unwrap_err();
+    assert!(
+        err.to_string()
+            .starts_with("cannot decompress field `html`"),
+        "{err}"
+    );
+}
@@ -4,8 +4,9 @@
 //! We use both facet-msgpack's serializer and rmp-serde to verify compatibility.
 
 use facet::Facet;
-use facet_msgpack::{from_slice, to_vec};
+use facet_msgpack::{SerializeOptions, from_slice, to_vec, to_vec_with_options};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // =============================================================================
 // Simple Types
@@ -270,3 +271,124 @@ fn test_enum_with_struct() {
     let result: SimpleEnum = from_slice(&bytes).unwrap();
     assert_eq!(result, value);
 }
+
+// =============================================================================
+// Containers inside sequences
+// =============================================================================
+
+#[derive(Debug, Facet, PartialEq)]
+struct Catalog {
+    points: Vec<Point>,
+    grid: Vec<Vec<u8>>,
+    events: Vec<SimpleEnum>,
+    by_name: HashMap<String, Point>,
+    corner: [u16; 3],
+    pair: (u8, String),
+    boxed: Option<Box<Point>>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn test_containers_in_sequences_roundtrip() {
+    let value = Catalog {
+        points: vec![Point { x: 1, y: 2 }, Point { x: -3, y: 4 }],
+        grid: vec![vec![1, 2], vec![]],
+        events: vec![
+            SimpleEnum::Unit,
+            SimpleEnum::WithData(7),
+            SimpleEnum::WithStruct { x: 1, y: 2 },
+        ],
+        by_name: HashMap::from([("origin".to_string(), Point { x: 0, y: 0 })]),
+        corner: [1, 2, 3],
+        pair: (9, "nine".to_string()),
+        boxed: Some(Box::new(Point { x: 5, y: 6 })),
+    };
+    let bytes = to_vec(&value).unwrap();
+    let result: Catalog = from_slice(&bytes).unwrap();
+    assert_eq!(result, value);
+}
+
+// =============================================================================
+// Indexed fields
+// =============================================================================
+
+#[test]
+fn test_indexed_fields_roundtrip() {
+    let value = Catalog {
+        points: vec![Point { x: 1, y: 2 }, Point { x: -3, y: 4 }],
+        grid: vec![vec![1, 2], vec![]],
+        events: vec![
+            SimpleEnum::Unit,
+            SimpleEnum::WithData(7),
+            SimpleEnum::WithStruct { x: 1, y: 2 },
+        ],
+        by_name: HashMap::from([("origin".to_string(), Point { x: 0, y: 0 })]),
+        corner: [1, 2, 3],
+        pair: (9, "nine".to_string()),
+        boxed: Some(Box::new(Point { x: 5, y: 6 })),
+    };
+    let options = SerializeOptions::new().indexed_fields();
+    let bytes = to_vec_with_options(&value, &options).unwrap();
+    assert!(bytes.len() < to_vec(&value).unwrap().len());
+    let result: Catalog = from_slice(&bytes).unwrap();
+    assert_eq!(result, value);
+}
+
+#[test]
+fn test_indexed_fields_skip_missing_options() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Sparse {
+        a: Option<u32>,
+        #[facet(skip_serializing_if = Option::is_none)]
+        b: Option<u32>,
+        c: String,
+    }
+
+    let value = Sparse {
+        a: Some(1),
+        b: None,
+        c: "c".to_string(),
+    };
+    let bytes = to_vec_with_options(&value, &SerializeOptions::new().indexed_fields()).unwrap();
+    // {0: 1, 2: "c"}
+    assert_eq!(bytes, [0x82, 0x00, 0x01, 0x02, 0xa1, b'c']);
+    let result: Sparse = from_slice(&bytes).unwrap();
+    assert_eq!(result, value);
+}
+
+#[test]
+fn test_indexed_fields_keep_map_keys() {
+    let value = HashMap::from([("k".to_string(), 1u8)]);
+    let bytes = to_vec_with_options(&value, &SerializeOptions::new().indexed_fields()).unwrap();
+    assert_eq!(bytes, to_vec(&value).unwrap());
+}
+
+// =============================================================================
+// Capacity hints
+// =============================================================================
+
+#[test]
+fn test_collections_are_preallocated() {
+    let samples: Vec<u32> = (0..1000).collect();
+    let bytes = to_vec(&samples).unwrap();
+    let result: Vec<u32> = from_slice(&bytes).unwrap();
+    assert_eq!(result, samples);
+    assert_eq!(result.capacity(), 1000);
+
+    let labels: HashMap<String, u32> = (0..100).map(|i| (i.to_string(), i)).collect();
+    let bytes = to_vec(&labels).unwrap();
+    let result: HashMap<String, u32> = from_slice(&bytes).unwrap();
+    assert_eq!(result, labels);
+}
+
+#[test]
+fn test_bogus_array_length() {
+    // array32 claiming u32::MAX items, followed by a single one
+    let bytes = [0xdd, 0xff, 0xff, 0xff, 0xff, 0x01];
+    assert!(from_slice::<Vec<u64>>(&bytes).is_err());
+}
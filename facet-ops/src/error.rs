@@ -0,0 +1,57 @@
+//! Sorting and filtering error types.
+
+use core::fmt;
+
+use facet_reflect::PeekPathError;
+
+/// Error returned when items can't be sorted or filtered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpsError {
+    /// The path doesn't lead anywhere in one of the items, e.g. because a
+    /// field name is misspelled.
+    Path {
+        /// The position of the item in the list.
+        index: usize,
+        /// Why the path didn't resolve.
+        error: PeekPathError,
+    },
+    /// The values at the path can't be compared, e.g. because their type
+    /// doesn't implement `PartialOrd`.
+    NotComparable {
+        /// The type of the values.
+        type_name: String,
+    },
+    /// A selector isn't of the form `path op value`.
+    InvalidSelector {
+        /// The selector.
+        selector: String,
+        /// What is wrong with it.
+        reason: &'static str,
+    },
+    /// The value in a selector isn't a value of the type it's compared to.
+    InvalidValue {
+        /// The value, as written in the selector.
+        value: String,
+        /// The type it's compared to.
+        type_name: String,
+    },
+}
+
+impl fmt::Display for OpsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpsError::Path { index, error } => write!(f, "item {index}: {error}"),
+            OpsError::NotComparable { type_name } => {
+                write!(f, "values of `{type_name}` can't be compared")
+            }
+            OpsError::InvalidSelector { selector, reason } => {
+                write!(f, "invalid selector `{selector}`: {reason}")
+            }
+            OpsError::InvalidValue { value, type_name } => {
+                write!(f, "`{value}` is not a valid `{type_name}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OpsError {}
@@ -0,0 +1,225 @@
+//! Keeping the items of a list whose value at a path matches a selector.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+
+use facet_core::{Type, UserType};
+use facet_reflect::{Partial, Peek, PeekListLike, ReflectError};
+
+use crate::OpsError;
+use crate::value::{compare, not_comparable, resolve, variant_index};
+
+/// How a value is compared to the one in a [`Selector`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+}
+
+impl Comparison {
+    /// The operators, longest first so `<=` isn't read as `<`
+    const OPERATORS: [(&'static str, Comparison); 6] = [
+        ("==", Comparison::Eq),
+        ("!=", Comparison::Ne),
+        ("<=", Comparison::Le),
+        (">=", Comparison::Ge),
+        ("<", Comparison::Lt),
+        (">", Comparison::Gt),
+    ];
+
+    /// The operator, as written in selectors
+    pub fn as_str(self) -> &'static str {
+        Self::OPERATORS
+            .iter()
+            .find(|(_, comparison)| *comparison == self)
+            .map(|(operator, _)| *operator)
+            .unwrap()
+    }
+
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Eq => ordering.is_eq(),
+            Comparison::Ne => ordering.is_ne(),
+            Comparison::Lt => ordering.is_lt(),
+            Comparison::Le => ordering.is_le(),
+            Comparison::Gt => ordering.is_gt(),
+            Comparison::Ge => ordering.is_ge(),
+        }
+    }
+}
+
+/// A condition on the value at a path, like `status == "open"` or
+/// `stats.retries >= 3`
+///
+/// The value is compared as the type it's compared to: strings as text,
+/// enums by variant name, and other scalars parsed from the selector, so
+/// `created_at < 2024-06-01T00:00:00Z` works for any timestamp type that
+/// parses from a string. Quotes around the value are optional, and needed
+/// only for values with leading or trailing spaces.
+///
+/// Enum variants are ordered as declared, like a derived `PartialOrd` does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    path: String,
+    comparison: Comparison,
+    value: String,
+}
+
+impl Selector {
+    /// A selector for the values at `path` that compare to `value` as
+    /// `comparison` says
+    pub fn new(path: impl Into<String>, comparison: Comparison, value: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            comparison,
+            value: value.into(),
+        }
+    }
+
+    /// The path to the compared values
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// How values are compared
+    pub fn comparison(&self) -> Comparison {
+        self.comparison
+    }
+
+    /// The value they are compared to, as text
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Whether the value at the path in `item` matches
+    ///
+    /// Missing values match nothing, not even `!=`.
+    pub fn matches(&self, item: Peek<'_, '_>) -> Result<bool, OpsError> {
+        self.matches_at(item, 0)
+    }
+
+    fn matches_at(&self, item: Peek<'_, '_>, index: usize) -> Result<bool, OpsError> {
+        let Some(value) = resolve(item, &self.path, index)? else {
+            return Ok(false);
+        };
+        let ordering = self.compare(value)?;
+        Ok(ordering.is_some_and(|ordering| self.comparison.holds(ordering)))
+    }
+
+    /// How `value` compares to the selector's value, `None` if it doesn't
+    /// at all, like a NaN
+    fn compare(&self, value: Peek<'_, '_>) -> Result<Option<Ordering>, OpsError> {
+        if let Some(text) = value.as_str() {
+            return Ok(Some(text.cmp(&self.value)));
+        }
+
+        if let Type::User(UserType::Enum(enum_type)) = value.shape().ty {
+            let active = variant_index(value).ok_or_else(|| not_comparable(value))?;
+            let wanted = enum_type
+                .variants
+                .iter()
+                .position(|variant| variant.name == self.value)
+                .ok_or_else(|| self.invalid_value(value))?;
+            return Ok(Some(active.cmp(&wanted)));
+        }
+
+        let parsed = Partial::alloc_shape(value.shape())
+            .and_then(|partial| partial.parse_from_str(&self.value))
+            .and_then(|partial| partial.build());
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(ReflectError::ParseFailed { .. }) => return Err(self.invalid_value(value)),
+            Err(_) => return Err(not_comparable(value)),
+        };
+        compare(value, parsed.peek())
+    }
+
+    fn invalid_value(&self, value: Peek<'_, '_>) -> OpsError {
+        OpsError::InvalidValue {
+            value: self.value.clone(),
+            type_name: value.shape().to_string(),
+        }
+    }
+}
+
+impl FromStr for Selector {
+    type Err = OpsError;
+
+    /// Parses `path op value`, where `op` is one of `==`, `!=`, `<`, `<=`,
+    /// `>` and `>=`
+    fn from_str(selector: &str) -> Result<Self, OpsError> {
+        let invalid = |reason| OpsError::InvalidSelector {
+            selector: selector.to_string(),
+            reason,
+        };
+
+        // The first operator outside of a bracketed map key
+        let mut depth = 0usize;
+        let mut found = None;
+        for (offset, c) in selector.char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth = depth.saturating_sub(1),
+                _ if depth == 0 => {
+                    found = Comparison::OPERATORS
+                        .iter()
+                        .find(|(operator, _)| selector[offset..].starts_with(operator))
+                        .map(|&(operator, comparison)| (offset, operator, comparison));
+                    if found.is_some() {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let (offset, operator, comparison) =
+            found.ok_or_else(|| invalid("expected one of `==`, `!=`, `<`, `<=`, `>`, `>=`"))?;
+
+        let path = selector[..offset].trim();
+        if path.is_empty() {
+            return Err(invalid("expected a path before the operator"));
+        }
+        let value = selector[offset + operator.len()..].trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .unwrap_or(value);
+        Ok(Selector::new(path, comparison, value))
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (path, operator, value) = (&self.path, self.comparison.as_str(), &self.value);
+        write!(f, "{path} {operator} \"{value}\"")
+    }
+}
+
+/// The items of `list` that match `selector`, in order
+///
+/// Fails if the selector's path doesn't resolve in one of the items, its
+/// value isn't a valid value of the type it's compared to, or that type
+/// can't be compared.
+pub fn filter_by<'mem, 'facet>(
+    list: PeekListLike<'mem, 'facet>,
+    selector: &Selector,
+) -> Result<Vec<Peek<'mem, 'facet>>, OpsError> {
+    let mut matching = Vec::new();
+    for (index, item) in list.iter().enumerate() {
+        if selector.matches_at(item, index)? {
+            matching.push(item);
+        }
+    }
+    Ok(matching)
+}
@@ -0,0 +1,56 @@
+//! Sorting and filtering lists of `Facet` values by path.
+//!
+//! Generic table views and CLIs let users pick the column to sort by and
+//! the rows to keep at runtime, as text. [`sort_by_path`] and [`filter_by`]
+//! take that text as a path, in the syntax of `Peek::at`, and compare the
+//! values it leads to through their `PartialOrd` impls, so no closure has
+//! to be written per type and field. Enums without one are ordered by
+//! variant, in declaration order.
+//!
+//! Values that are missing, because an `Option` on the way is `None` or a
+//! map has no such key, sort first in ascending order and match no
+//! selector.
+//!
+//! # Example
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_ops::{Order, Selector, filter_by, sort_by_path};
+//! use facet_reflect::Peek;
+//!
+//! #[derive(Facet)]
+//! struct Ticket {
+//!     title: String,
+//!     priority: u8,
+//!     assignee: Option<String>,
+//! }
+//!
+//! let mut tickets = vec![
+//!     Ticket { title: "Crash on start".into(), priority: 1, assignee: None },
+//!     Ticket { title: "Typo".into(), priority: 3, assignee: Some("ana".into()) },
+//!     Ticket { title: "Slow search".into(), priority: 2, assignee: Some("bo".into()) },
+//! ];
+//! sort_by_path(&mut tickets, "priority", Order::Desc).unwrap();
+//! assert_eq!(tickets[0].title, "Typo");
+//!
+//! let urgent: Selector = "priority <= 2".parse().unwrap();
+//! let list = Peek::new(&tickets).into_list_like().unwrap();
+//! let titles: Vec<_> = filter_by(list, &urgent)
+//!     .unwrap()
+//!     .into_iter()
+//!     .map(|ticket| ticket.at("title").unwrap().as_str().unwrap())
+//!     .collect();
+//! assert_eq!(titles, ["Slow search", "Crash on start"]);
+//! ```
+
+#![forbid(unsafe_code)]
+
+mod error;
+mod filter;
+mod sort;
+mod value;
+
+pub use error::OpsError;
+pub use filter::{Comparison, Selector, filter_by};
+pub use sort::{Order, sort_by_path};
+pub use value::value_at;
@@ -0,0 +1,105 @@
+//! Sorting a list by the values at a path.
+
+use core::cmp::Ordering;
+
+use facet_core::Facet;
+use facet_reflect::Peek;
+
+use crate::OpsError;
+use crate::value::{compare, resolve};
+
+/// The direction to sort in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    /// Smallest first
+    #[default]
+    Asc,
+    /// Largest first
+    Desc,
+}
+
+/// The value an item is sorted by
+enum Key<'mem, 'facet> {
+    /// Missing, or not even equal to itself, like a NaN
+    Absent,
+    Present(Peek<'mem, 'facet>),
+}
+
+/// Sorts `items` by the values at `path` in each, e.g. `"created_at"` or
+/// `"owner.name"`
+///
+/// The sort is stable. Missing values, and values that can't be compared
+/// even to themselves like NaNs, go first in ascending order and last in
+/// descending order.
+///
+/// Values are compared with their `PartialOrd` impl; enums without one are
+/// ordered by variant, in declaration order. Fails without reordering
+/// anything if the path doesn't resolve in one of the items, or the values
+/// it leads to can't be compared.
+pub fn sort_by_path<'a, T: Facet<'a>>(
+    items: &mut [T],
+    path: &str,
+    order: Order,
+) -> Result<(), OpsError> {
+    let mut positions: Vec<usize> = (0..items.len()).collect();
+    {
+        let keys = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let value = resolve(Peek::new(item), path, index)?;
+                Ok(match value {
+                    Some(value) => match compare(value, value)? {
+                        Some(_) => Key::Present(value),
+                        None => Key::Absent,
+                    },
+                    None => Key::Absent,
+                })
+            })
+            .collect::<Result<Vec<_>, OpsError>>()?;
+
+        let mut error = None;
+        positions.sort_by(|&a, &b| {
+            let ordering = match (&keys[a], &keys[b]) {
+                (Key::Absent, Key::Absent) => Ordering::Equal,
+                (Key::Absent, Key::Present(_)) => Ordering::Less,
+                (Key::Present(_), Key::Absent) => Ordering::Greater,
+                (Key::Present(a), Key::Present(b)) => match compare(*a, *b) {
+                    Ok(ordering) => ordering.unwrap_or(Ordering::Equal),
+                    Err(err) => {
+                        // Values of different types, e.g. in dynamic values
+                        error.get_or_insert(err);
+                        Ordering::Equal
+                    }
+                },
+            };
+            match order {
+                Order::Asc => ordering,
+                Order::Desc => ordering.reverse(),
+            }
+        });
+        if let Some(error) = error {
+            return Err(error);
+        }
+    }
+
+    permute(items, positions);
+    Ok(())
+}
+
+/// Moves the item at `positions[i]` to `i`, for each `i`
+fn permute<T>(items: &mut [T], mut positions: Vec<usize>) {
+    for start in 0..positions.len() {
+        // Follow the cycle through `start`, marking each position as done
+        let mut current = start;
+        while positions[current] != current {
+            let next = positions[current];
+            positions[current] = current;
+            if next == start {
+                break;
+            }
+            items.swap(current, next);
+            current = next;
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Finding the value a path leads to in an item, and comparing values.
+
+use core::cmp::Ordering;
+
+use facet_core::{Type, UserType};
+use facet_reflect::{Peek, PeekPathError, PeekPathErrorKind};
+
+use crate::OpsError;
+
+/// The value at `path` in `item`, as sorting and filtering see it
+///
+/// `Some`, `Box`, `Arc` and other smart pointers are stepped through, so
+/// values compare as what they hold. Returns `None` if the value is missing:
+/// an `Option` on the way is `None`, a map has no such key, a list is too
+/// short, or the field is one only some variants of an enum have.
+///
+/// Fails if the path doesn't lead anywhere in `item`'s type, e.g. because a
+/// field name is misspelled.
+pub fn value_at<'mem, 'facet>(
+    item: Peek<'mem, 'facet>,
+    path: &str,
+) -> Result<Option<Peek<'mem, 'facet>>, PeekPathError> {
+    let mut value = match item.at(path) {
+        Ok(value) => value,
+        Err(error) => {
+            return match error.kind {
+                PeekPathErrorKind::NoneValue { .. }
+                | PeekPathErrorKind::NoSuchKey { .. }
+                | PeekPathErrorKind::IndexOutOfBounds { .. } => Ok(None),
+                PeekPathErrorKind::NoSuchField { shape }
+                    if matches!(shape.ty, Type::User(UserType::Enum(_))) =>
+                {
+                    Ok(None)
+                }
+                _ => Err(error),
+            };
+        }
+    };
+    loop {
+        if let Ok(option) = value.into_option() {
+            match option.value() {
+                Some(inner) => value = inner,
+                None => return Ok(None),
+            }
+        } else if let Some(inner) = value
+            .into_pointer()
+            .ok()
+            .and_then(|pointer| pointer.borrow_inner())
+        {
+            value = inner;
+        } else {
+            return Ok(Some(value));
+        }
+    }
+}
+
+/// [`value_at`], for the item at `index` of its list
+pub(crate) fn resolve<'mem, 'facet>(
+    item: Peek<'mem, 'facet>,
+    path: &str,
+    index: usize,
+) -> Result<Option<Peek<'mem, 'facet>>, OpsError> {
+    value_at(item, path).map_err(|error| OpsError::Path { index, error })
+}
+
+/// How `a` compares to `b`
+///
+/// Values are compared with their `PartialOrd` impl; enums without one are
+/// ordered by variant, in declaration order.
+pub(crate) fn compare(a: Peek<'_, '_>, b: Peek<'_, '_>) -> Result<Option<Ordering>, OpsError> {
+    if let Ok(ordering) = a.partial_cmp(&b) {
+        return Ok(ordering);
+    }
+    match (variant_index(a), variant_index(b)) {
+        (Some(a_index), Some(b_index)) if a.shape() == b.shape() => Ok(Some(a_index.cmp(&b_index))),
+        _ => Err(not_comparable(a)),
+    }
+}
+
+/// The index of the active variant, if `value` is an enum
+pub(crate) fn variant_index(value: Peek<'_, '_>) -> Option<usize> {
+    value.into_enum().ok()?.variant_index().ok()
+}
+
+pub(crate) fn not_comparable(value: Peek<'_, '_>) -> OpsError {
+    OpsError::NotComparable {
+        type_name: value.shape().to_string(),
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_ops::{Comparison, OpsError, Order, Selector, filter_by, sort_by_path};
+use facet_reflect::Peek;
+
+#[derive(Debug, Facet)]
+#[repr(u8)]
+enum Status {
+    Open,
+    Blocked,
+    Closed,
+}
+
+#[derive(Debug, Facet)]
+struct Owner {
+    name: String,
+}
+
+#[derive(Debug, Facet)]
+struct Task {
+    id: u32,
+    title: String,
+    status: Status,
+    score: f64,
+    owner: Option<Owner>,
+    labels: HashMap<String, String>,
+}
+
+fn task(id: u32, title: &str, status: Status, score: f64, owner: Option<&str>) -> Task {
+    Task {
+        id,
+        title: title.to_string(),
+        status,
+        score,
+        owner: owner.map(|name| Owner {
+            name: name.to_string(),
+        }),
+        labels: HashMap::from([("team".to_string(), format!("t{}", id % 2))]),
+    }
+}
+
+fn ids(tasks: &[Task]) -> Vec<u32> {
+    tasks.iter().map(|task| task.id).collect()
+}
+
+fn filtered_ids(tasks: &[Task], selector: &str) -> Vec<u32> {
+    let list = Peek::new(tasks).into_list_like().unwrap();
+    filter_by(list, &selector.parse().unwrap())
+        .unwrap()
+        .into_iter()
+        .map(|task| *task.at("id").unwrap().get::<u32>().unwrap())
+        .collect()
+}
+
+#[test]
+fn sorts_by_scalar_fields() {
+    let mut tasks = vec![
+        task(3, "deploy", Status::Blocked, 0.5, Some("cy")),
+        task(1, "design", Status::Open, 2.0, None),
+        task(4, "review", Status::Closed, f64::NAN, Some("ana")),
+        task(2, "build", Status::Open, 1.0, Some("bo")),
+    ];
+    sort_by_path(&mut tasks, "id", Order::Asc).unwrap();
+    assert_eq!(ids(&tasks), [1, 2, 3, 4]);
+    sort_by_path(&mut tasks, "title", Order::Desc).unwrap();
+    assert_eq!(ids(&tasks), [4, 1, 3, 2]);
+}
+
+#[test]
+fn sort_is_stable() {
+    let mut tasks = vec![
+        task(3, "deploy", Status::Blocked, 0.5, Some("cy")),
+        task(1, "design", Status::Open, 2.0, None),
+        task(4, "review", Status::Closed, f64::NAN, Some("ana")),
+        task(2, "build", Status::Open, 1.0, Some("bo")),
+    ];
+    sort_by_path(&mut tasks, "status", Order::Asc).unwrap();
+    assert_eq!(ids(&tasks), [1, 2, 3, 4]);
+    sort_by_path(&mut tasks, "labels.team", Order::Desc).unwrap();
+    assert_eq!(ids(&tasks), [1, 3, 2, 4]);
+}
+
+#[test]
+fn missing_values_sort_first() {
+    let mut tasks = vec![
+        task(3, "deploy", Status::Blocked, 0.5, Some("cy")),
+        task(1, "design", Status::Open, 2.0, None),
+        task(4, "review", Status::Closed, f64::NAN, Some("ana")),
+        task(2, "build", Status::Open, 1.0, Some("bo")),
+    ];
+    sort_by_path(&mut tasks, "owner.name", Order::Asc).unwrap();
+    assert_eq!(ids(&tasks), [1, 4, 2, 3]);
+    sort_by_path(&mut tasks, "owner.name", Order::Desc).unwrap();
+    assert_eq!(ids(&tasks), [3, 2, 4, 1]);
+
+    // NaN is not comparable, even to itself
+    sort_by_path(&mut tasks, "score", Order::Asc).unwrap();
+    assert_eq!(ids(&tasks), [4, 3, 2, 1]);
+}
+
+#[test]
+fn sort_errors_leave_items_alone() {
+    let mut tasks = vec![
+        task(3, "deploy", Status::Blocked, 0.5, Some("cy")),
+        task(1, "design", Status::Open, 2.0, None),
+        task(4, "review", Status::Closed, f64::NAN, Some("ana")),
+        task(2, "build", Status::Open, 1.0, Some("bo")),
+    ];
+    let err = sort_by_path(&mut tasks, "owner.nmae", Order::Asc).unwrap_err();
+    assert!(
+        matches!(&err, OpsError::Path { index: 0, error } if error.segment == ".nmae"),
+        "{err}"
+    );
+    assert_eq!(ids(&tasks), [3, 1, 4, 2]);
+
+    #[derive(Facet)]
+    struct Unordered {
+        key: Owner,
+    }
+    let mut items = vec![
+        Unordered {
+            key: Owner { name: "a".into() },
+        },
+        Unordered {
+            key: Owner { name: "b".into() },
+        },
+    ];
+    let err = sort_by_path(&mut items, "key", Order::Asc).unwrap_err();
+    assert!(matches!(err, OpsError::NotComparable { .. }), "{err}");
+}
+
+#[test]
+fn filters_by_comparisons() {
+    let tasks = vec![
+        task(3, "deploy", Status::Blocked, 0.5, Some("cy")),
+        task(1, "design", Status::Open, 2.0, None),
+        task(4, "review", Status::Closed, f64::NAN, Some("ana")),
+        task(2, "build", Status::Open, 1.0, Some("bo")),
+    ];
+    assert_eq!(filtered_ids(&tasks, "id >= 3"), [3, 4]);
+    assert_eq!(filtered_ids(&tasks, "score < 1.5"), [3, 2]);
+    assert_eq!(filtered_ids(&tasks, "title == \"design\""), [1]);
+    assert_eq!(filtered_ids(&tasks, "title != design"), [3, 4, 2]);
+    assert_eq!(filtered_ids(&tasks, "labels[\"team\"] == t0"), [4, 2]);
+}
+
+#[test]
+fn filters_enums_by_variant() {
+    let tasks = vec![
+        task(3, "deploy", Status::Blocked, 0.5, Some("cy")),
+        task(1, "design", Status::Open, 2.0, None),
+        task(4, "review", Status::Closed, f64::NAN, Some("ana")),
+        task(2, "build", Status::Open, 1.0, Some("bo")),
+    ];
+    assert_eq!(filtered_ids(&tasks, "status == Open"), [1, 2]);
+    assert_eq!(filtered_ids(&tasks, "status > Open"), [3, 4]);
+
+    let list = Peek::new(&tasks).into_list_like().unwrap();
+    let err = filter_by(list, &"status == Done".parse().unwrap()).unwrap_err();
+    assert!(matches!(err, OpsError::InvalidValue { .. }), "{err}");
+}
+
+#[test]
+fn missing_values_match_nothing() {
+    let tasks = vec![
+        task(3, "deploy", Status::Blocked, 0.5, Some("cy")),
+        task(1, "design", Status::Open, 2.0, None),
+        task(4, "review", Status::Closed, f64::NAN, Some("ana")),
+        task(2, "build", Status::Open, 1.0, Some("bo")),
+    ];
+    assert_eq!(filtered_ids(&tasks, "owner.name != nobody"), [3, 4, 2]);
+    assert_eq!(filtered_ids(&tasks, "score != 0"), [3, 1, 2]);
+}
+
+#[test]
+fn invalid_values() {
+    let tasks = vec![
+        task(3, "deploy", Status::Blocked, 0.5, Some("cy")),
+        task(1, "design", Status::Open, 2.0, None),
+        task(4, "review", Status::Closed, f64::NAN, Some("ana")),
+        task(2, "build", Status::Open, 1.0, Some("bo")),
+    ];
+    let list = Peek::new(&tasks).into_list_like().unwrap();
+    let err = filter_by(list, &"id > many".parse().unwrap()).unwrap_err();
+    assert_eq!(err.to_string(), "`many` is not a valid `u32`");
+}
+
+#[test]
+fn parses_selectors() {
+    let selector: Selector = "labels[\"a<b\"] <= \" x \"".parse().unwrap();
+    assert_eq!(
+        selector,
+        Selector::new("labels[\"a<b\"]", Comparison::Le, " x ")
+    );
+    assert_eq!(selector.to_string(), "labels[\"a<b\"] <= \" x \"");
+
+    for invalid in ["id", "== 3", "labels[\"==\"]"] {
+        let err = invalid.parse::<Selector>().unwrap_err();
+        assert!(matches!(err, OpsError::InvalidSelector { .. }), "{err}");
+    }
+}
@@ -39,7 +39,7 @@ pub enum PathStep {
 /// This is a lightweight representation that only stores indices.
 /// The actual field names and type information can be reconstructed
 /// by replaying these steps against the original [`Shape`].
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Path {
     steps: Vec<PathStep>,
 }
@@ -328,6 +328,9 @@ fn get_pointer_inner_shape(shape: &Shape) -> Option<&'static Shape> {
     }
 }
 
+mod typed;
+pub use typed::TypedPath;
+
 #[cfg(feature = "pretty")]
 pub mod pretty;
 
@@ -0,0 +1,153 @@
+//! Compile-time checked field paths, built with the [`path!`](crate::path) macro.
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use facet_core::{Def, Facet, Shape, Type, UserType};
+
+use crate::{Path, PathStep, get_pointer_inner_shape};
+
+/// A field path from a root type `R` to a leaf of type `L`.
+///
+/// Built with [`path!`](crate::path), which makes the compiler check the field
+/// chain: a typo or a removed field is a build error rather than a lookup that
+/// fails at runtime. The path carries both the untyped [`Path`] (for matching
+/// against error paths and formatting) and accessors to the leaf value.
+pub struct TypedPath<R, L: ?Sized> {
+    path: Path,
+    root: &'static Shape,
+    get: fn(&R) -> &L,
+    get_mut: fn(&mut R) -> &mut L,
+    _marker: PhantomData<fn(&R) -> &L>,
+}
+
+impl<'facet, R: Facet<'facet>, L: ?Sized> TypedPath<R, L> {
+    /// Resolve `fields`, given as Rust identifiers rather than (possibly renamed)
+    /// field names, against `R`'s shape. Use [`path!`](crate::path) instead of
+    /// calling this directly: it generates `get` and `get_mut` from the same chain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a field can't be found in the shape, which means `R`'s `Facet`
+    /// impl doesn't describe the fields the accessors go through.
+    #[doc(hidden)]
+    pub fn new(fields: &[&str], get: fn(&R) -> &L, get_mut: fn(&mut R) -> &mut L) -> Self {
+        let mut path = Path::with_capacity(fields.len());
+        let mut shape = R::SHAPE;
+        for name in fields {
+            // Field access auto-derefs through smart pointers; so does the path
+            while let Def::Pointer(_) = shape.def {
+                path.push(PathStep::Deref);
+                shape = get_pointer_inner_shape(shape)
+                    .unwrap_or_else(|| panic!("path!: cannot see through `{shape}`"));
+            }
+            let Type::User(UserType::Struct(st)) = shape.ty else {
+                panic!("path!: `{shape}` is not a struct, so it has no field `{name}`");
+            };
+            let idx = st
+                .fields
+                .iter()
+                .position(|f| f.ident == *name)
+                .unwrap_or_else(|| panic!("path!: `{shape}` has no field `{name}`"));
+            path.push(PathStep::Field(idx as u32));
+            shape = st.fields[idx].shape();
+        }
+        Self {
+            path,
+            root: R::SHAPE,
+            get,
+            get_mut,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<R, L: ?Sized> TypedPath<R, L> {
+    /// The untyped path, as used in deserialization errors.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The shape the path starts from.
+    pub fn root_shape(&self) -> &'static Shape {
+        self.root
+    }
+
+    /// Borrow the leaf value.
+    pub fn get<'a>(&self, root: &'a R) -> &'a L {
+        (self.get)(root)
+    }
+
+    /// Mutably borrow the leaf value.
+    pub fn get_mut<'a>(&self, root: &'a mut R) -> &'a mut L {
+        (self.get_mut)(root)
+    }
+}
+
+/// Formats like [`Path::format_with_shape`], e.g. `server.port`.
+impl<R, L: ?Sized> fmt::Display for TypedPath<R, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.path.format_with_shape(self.root))
+    }
+}
+
+impl<R, L: ?Sized> fmt::Debug for TypedPath<R, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedPath")
+            .field("root", &self.root.type_identifier)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl<R, L: ?Sized> Clone for TypedPath<R, L> {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            root: self.root,
+            get: self.get,
+            get_mut: self.get_mut,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Build a [`TypedPath`] from a root type and a chain of field accesses.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_path::path;
+///
+/// #[derive(Facet)]
+/// struct Server { port: u16 }
+///
+/// #[derive(Facet)]
+/// struct Config { name: String, server: Server }
+///
+/// let port = path!(Config.server.port);
+/// assert_eq!(port.to_string(), "server.port");
+///
+/// let mut config = Config { name: "api".into(), server: Server { port: 80 } };
+/// *port.get_mut(&mut config) = 8080;
+/// assert_eq!(*port.get(&config), 8080);
+/// ```
+///
+/// The chain is type-checked like ordinary field access, so this fails to build:
+///
+/// ```compile_fail
+/// # use facet::Facet;
+/// # use facet_path::path;
+/// # #[derive(Facet)]
+/// # struct Config { port: u16 }
+/// let p = path!(Config.prot);
+/// ```
+#[macro_export]
+macro_rules! path {
+    ($root:ident $(. $field:tt)+) => {
+        $crate::TypedPath::<$root, _>::new(
+            &[$(stringify!($field)),+],
+            |root: &$root| &root $(.$field)+,
+            |root: &mut $root| &mut root $(.$field)+,
+        )
+    };
+}
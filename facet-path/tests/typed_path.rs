@@ -0,0 +1,123 @@
+//! Tests for compile-time checked paths built with `path!`
+
+use facet::Facet;
+use facet_path::{Path, PathStep, path};
+
+#[derive(Facet)]
+struct Port(u16);
+
+#[derive(Facet)]
+struct Tls {
+    cert: String,
+}
+
+#[derive(Facet)]
+struct Server {
+    host: String,
+    port: Port,
+    tls: Box<Tls>,
+}
+
+#[derive(Facet)]
+struct Config {
+    name: String,
+    server: Server,
+}
+
+#[test]
+fn test_steps_match_shape() {
+    facet_testhelpers::setup();
+
+    let p = path!(Config.server.port.0);
+    let mut expected = Path::new();
+    expected.push(PathStep::Field(1));
+    expected.push(PathStep::Field(1));
+    expected.push(PathStep::Field(0));
+    assert_eq!(p.path(), &expected);
+    assert_eq!(p.to_string(), "server.port.0");
+    assert_eq!(p.root_shape().type_identifier, "Config");
+}
+
+#[test]
+fn test_get_and_get_mut() {
+    facet_testhelpers::setup();
+
+    let mut config = Config {
+        name: "api".into(),
+        server: Server {
+            host: "localhost".into(),
+            port: Port(8080),
+            tls: Box::new(Tls {
+                cert: "cert.pem".into(),
+            }),
+        },
+    };
+    let host = path!(Config.server.host);
+    assert_eq!(host.get(&config), "localhost");
+    host.get_mut(&mut config).push_str(":443");
+    assert_eq!(config.server.host, "localhost:443");
+
+    let name = path!(Config.name);
+    assert_eq!(name.get(&config), "api");
+}
+
+#[test]
+fn test_through_box() {
+    facet_testhelpers::setup();
+
+    let config = Config {
+        name: "api".into(),
+        server: Server {
+            host: "localhost".into(),
+            port: Port(8080),
+            tls: Box::new(Tls {
+                cert: "cert.pem".into(),
+            }),
+        },
+    };
+    let cert = path!(Config.server.tls.cert);
+    assert_eq!(cert.get(&config), "cert.pem");
+    assert_eq!(
+        cert.path().steps(),
+        &[
+            PathStep::Field(1),
+            PathStep::Field(2),
+            PathStep::Deref,
+            PathStep::Field(0)
+        ]
+    );
+    assert_eq!(cert.to_string(), "server.tls.cert");
+}
+
+#[derive(Facet)]
+#[facet(rename_all = "camelCase")]
+struct Listener {
+    bind_address: String,
+    #[facet(rename = "maxConns")]
+    connection_limit: u32,
+    r#type: String,
+}
+
+#[test]
+fn test_renamed_fields_resolve_by_identifier() {
+    facet_testhelpers::setup();
+
+    let mut listener = Listener {
+        bind_address: "0.0.0.0".into(),
+        connection_limit: 64,
+        r#type: "tcp".into(),
+    };
+
+    let bind = path!(Listener.bind_address);
+    assert_eq!(bind.path().steps(), &[PathStep::Field(0)]);
+    assert_eq!(bind.get(&listener), "0.0.0.0");
+
+    let limit = path!(Listener.connection_limit);
+    assert_eq!(limit.path().steps(), &[PathStep::Field(1)]);
+    *limit.get_mut(&mut listener) = 128;
+    assert_eq!(listener.connection_limit, 128);
+
+    let kind = path!(Listener.r#type);
+    assert_eq!(kind.path().steps(), &[PathStep::Field(2)]);
+    assert_eq!(kind.get(&listener), "tcp");
+}
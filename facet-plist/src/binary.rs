@@ -0,0 +1,408 @@
+//! Reading and writing binary (`bplist00`) property lists.
+//!
+//! A binary property list is a header, a table of objects, a table of object
+//! offsets and a 32-byte trailer. Containers refer to their children by index
+//! into the offset table. Each object starts with a marker byte whose high
+//! nibble is its type and whose low nibble is a size or count; a count of
+//! `0xF` means the real count follows as an integer object.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use facet_reflect::Span;
+
+use crate::error::{PlistError, PlistErrorKind};
+use crate::value::{MAX_DEPTH, Value, ValueKind};
+
+pub(crate) const MAGIC: &[u8] = b"bplist0";
+
+const TRAILER_LEN: usize = 32;
+
+/// How many values a binary property list may expand to, per object
+const EXPANSION: usize = 64;
+
+struct Reader<'a> {
+    data: &'a [u8],
+    offsets: Vec<usize>,
+    ref_size: usize,
+    /// Objects currently being read, to catch reference cycles
+    ancestors: Vec<usize>,
+    /// How many more values may be read. Objects can be shared, so a small
+    /// input could otherwise expand into a huge tree.
+    budget: usize,
+}
+
+/// Parse a binary property list
+pub(crate) fn read(data: &[u8]) -> Result<Value, PlistError> {
+    let invalid = |reason| {
+        PlistError::with_span(
+            PlistErrorKind::InvalidBinary { reason },
+            Span::new(0, data.len()),
+        )
+    };
+    if !data.starts_with(MAGIC) || data.len() < 8 + TRAILER_LEN {
+        return Err(invalid("missing header or trailer"));
+    }
+    let trailer = &data[data.len() - TRAILER_LEN..];
+    let offset_size = trailer[6] as usize;
+    let ref_size = trailer[7] as usize;
+    let num_objects = be_uint(&trailer[8..16]);
+    let top_object = be_uint(&trailer[16..24]);
+    let table_offset = be_uint(&trailer[24..32]);
+    if !(1..=8).contains(&offset_size) || !(1..=8).contains(&ref_size) {
+        return Err(invalid("bad offset or reference size"));
+    }
+    let table_end = (num_objects as u128) * (offset_size as u128) + table_offset as u128;
+    if table_offset < 8 || table_end > (data.len() - TRAILER_LEN) as u128 {
+        return Err(invalid("offset table out of bounds"));
+    }
+    if top_object >= num_objects {
+        return Err(invalid("top object out of bounds"));
+    }
+
+    let table = &data[table_offset as usize..table_end as usize];
+    let offsets = table
+        .chunks(offset_size)
+        .map(|chunk| be_uint(chunk) as usize)
+        .collect();
+    let mut reader = Reader {
+        data: &data[..table_offset as usize],
+        offsets,
+        ref_size,
+        ancestors: Vec::new(),
+        budget: (num_objects as usize).saturating_mul(EXPANSION).max(4096),
+    };
+    reader.object(top_object as usize)
+}
+
+impl Reader<'_> {
+    fn error(&self, reason: &'static str, offset: usize) -> PlistError {
+        PlistError::with_span(
+            PlistErrorKind::InvalidBinary { reason },
+            Span::new(offset, 1),
+        )
+    }
+
+    fn bytes(&self, at: usize, len: usize, object: usize) -> Result<&[u8], PlistError> {
+        at.checked_add(len)
+            .and_then(|end| self.data.get(at..end))
+            .ok_or_else(|| self.error("object extends past the object table", object))
+    }
+
+    fn object(&mut self, index: usize) -> Result<Value, PlistError> {
+        let offset = *self
+            .offsets
+            .get(index)
+            .ok_or_else(|| self.error("object reference out of bounds", 0))?;
+        if self.ancestors.contains(&index) {
+            return Err(self.error("object contains itself", offset));
+        }
+        if self.ancestors.len() >= MAX_DEPTH {
+            return Err(self.error("objects nested too deeply", offset));
+        }
+        self.budget = self
+            .budget
+            .checked_sub(1)
+            .ok_or_else(|| self.error("shared objects expand too much", offset))?;
+        let marker = *self
+            .data
+            .get(offset)
+            .ok_or_else(|| self.error("object offset out of bounds", offset))?;
+        let low = (marker & 0x0F) as usize;
+
+        let kind = match marker >> 4 {
+            0x0 => match marker {
+                0x00 => ValueKind::Null,
+                0x08 => ValueKind::Bool(false),
+                0x09 => ValueKind::Bool(true),
+                _ => return Err(self.error("unknown marker", offset)),
+            },
+            0x1 => ValueKind::Integer(self.integer(offset)?.0),
+            0x2 => {
+                let bytes = self.bytes(offset + 1, 1 << low, offset)?;
+                ValueKind::Real(match bytes.len() {
+                    4 => f32::from_be_bytes(bytes.try_into().unwrap()) as f64,
+                    8 => f64::from_be_bytes(bytes.try_into().unwrap()),
+                    _ => return Err(self.error("unsupported real size", offset)),
+                })
+            }
+            0x3 if marker == 0x33 => {
+                let bytes = self.bytes(offset + 1, 8, offset)?;
+                ValueKind::Date(f64::from_be_bytes(bytes.try_into().unwrap()))
+            }
+            0x4 => {
+                let (len, start) = self.count(offset)?;
+                ValueKind::Data(self.bytes(start, len, offset)?.to_vec())
+            }
+            0x5 => {
+                let (len, start) = self.count(offset)?;
+                let bytes = self.bytes(start, len, offset)?;
+                if !bytes.is_ascii() {
+                    return Err(self.error("non-ASCII byte in ASCII string", offset));
+                }
+                ValueKind::String(bytes.iter().map(|&b| b as char).collect())
+            }
+            0x6 => {
+                let (len, start) = self.count(offset)?;
+                let bytes = self.bytes(start, len.saturating_mul(2), offset)?;
+                let units = bytes
+                    .chunks(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]));
+                ValueKind::String(
+                    char::decode_utf16(units)
+                        .collect::<Result<String, _>>()
+                        .map_err(|_| self.error("invalid UTF-16 string", offset))?,
+                )
+            }
+            0x7 => {
+                let (len, start) = self.count(offset)?;
+                let bytes = self.bytes(start, len, offset)?;
+                ValueKind::String(
+                    core::str::from_utf8(bytes)
+                        .map_err(|_| self.error("invalid UTF-8 string", offset))?
+                        .into(),
+                )
+            }
+            0x8 => ValueKind::Uid(be_uint(self.bytes(offset + 1, low + 1, offset)?)),
+            0xA => {
+                let refs = self.refs(offset, 1)?;
+                self.ancestors.push(index);
+                let items = refs
+                    .into_iter()
+                    .map(|child| self.object(child))
+                    .collect::<Result<_, _>>()?;
+                self.ancestors.pop();
+                ValueKind::Array(items)
+            }
+            0xD => {
+                let refs = self.refs(offset, 2)?;
+                let (keys, values) = refs.split_at(refs.len() / 2);
+                self.ancestors.push(index);
+                let mut entries = Vec::with_capacity(keys.len());
+                for (&key, &value) in keys.iter().zip(values) {
+                    let key = match self.object(key)? {
+                        Value {
+                            kind: ValueKind::String(key),
+                            ..
+                        } => key,
+                        other => {
+                            return Err(self.error("dictionary key is not a string", other.offset));
+                        }
+                    };
+                    entries.push((key, self.object(value)?));
+                }
+                self.ancestors.pop();
+                ValueKind::Dict(entries)
+            }
+            _ => return Err(self.error("unknown marker", offset)),
+        };
+        Ok(Value::new(kind, offset))
+    }
+
+    /// The integer object at `offset` and the offset just past it
+    fn integer(&self, offset: usize) -> Result<(i128, usize), PlistError> {
+        let marker = *self
+            .data
+            .get(offset)
+            .ok_or_else(|| self.error("object offset out of bounds", offset))?;
+        if marker >> 4 != 0x1 {
+            return Err(self.error("expected an integer", offset));
+        }
+        let len = 1usize << (marker & 0x0F);
+        let bytes = self.bytes(offset + 1, len, offset)?;
+        let n = match len {
+            1 | 2 | 4 => be_uint(bytes) as i128,
+            // 8-byte integers are signed
+            8 => be_uint(bytes) as i64 as i128,
+            16 => i128::from_be_bytes(bytes.try_into().unwrap()),
+            _ => return Err(self.error("unsupported integer size", offset)),
+        };
+        Ok((n, offset + 1 + len))
+    }
+
+    /// The element count of the object at `offset`, and where its contents start
+    fn count(&self, offset: usize) -> Result<(usize, usize), PlistError> {
+        let low = self.data[offset] & 0x0F;
+        if low != 0x0F {
+            return Ok((low as usize, offset + 1));
+        }
+        let (n, next) = self.integer(offset + 1)?;
+        let n = usize::try_from(n).map_err(|_| self.error("invalid count", offset))?;
+        Ok((n, next))
+    }
+
+    /// The object references of the container at `offset`, `per_item` per element
+    fn refs(&self, offset: usize, per_item: usize) -> Result<Vec<usize>, PlistError> {
+        let (len, start) = self.count(offset)?;
+        let total = len
+            .checked_mul(per_item * self.ref_size)
+            .ok_or_else(|| self.error("invalid count", offset))?;
+        let bytes = self.bytes(start, total, offset)?;
+        Ok(bytes
+            .chunks(self.ref_size)
+            .map(|chunk| be_uint(chunk) as usize)
+            .collect())
+    }
+}
+
+fn be_uint(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |n, &b| (n << 8) | b as u64)
+}
+
+/// Bytes needed to hold `n` as an unsigned integer
+fn width(n: u64) -> usize {
+    match n {
+        0..=0xFF => 1,
+        0x100..=0xFFFF => 2,
+        0x1_0000..=0xFFFF_FFFF => 4,
+        _ => 8,
+    }
+}
+
+struct Writer {
+    /// Encoded objects, in reference order
+    objects: Vec<Vec<u8>>,
+    ref_size: usize,
+}
+
+/// Write `value` as a binary property list
+pub(crate) fn write(value: &Value) -> Vec<u8> {
+    let mut writer = Writer {
+        objects: Vec::new(),
+        ref_size: width(count_objects(value).saturating_sub(1) as u64),
+    };
+    writer.add(value);
+
+    let mut out = Vec::from(&b"bplist00"[..]);
+    let mut offsets = Vec::with_capacity(writer.objects.len());
+    for object in &writer.objects {
+        offsets.push(out.len() as u64);
+        out.extend_from_slice(object);
+    }
+    let table_offset = out.len() as u64;
+    let offset_size = width(table_offset);
+    for offset in offsets {
+        push_uint(&mut out, offset, offset_size);
+    }
+    out.extend_from_slice(&[0; 6]);
+    out.push(offset_size as u8);
+    out.push(writer.ref_size as u8);
+    out.extend_from_slice(&(writer.objects.len() as u64).to_be_bytes());
+    // The top object is always written first
+    out.extend_from_slice(&0u64.to_be_bytes());
+    out.extend_from_slice(&table_offset.to_be_bytes());
+    out
+}
+
+fn count_objects(value: &Value) -> usize {
+    1 + match &value.kind {
+        ValueKind::Array(items) => items.iter().map(count_objects).sum(),
+        ValueKind::Dict(entries) => entries
+            .iter()
+            .map(|(_, value)| 1 + count_objects(value))
+            .sum(),
+        _ => 0,
+    }
+}
+
+impl Writer {
+    /// Encode `value` and its children, returning its reference
+    fn add(&mut self, value: &Value) -> usize {
+        let index = self.objects.len();
+        self.objects.push(Vec::new());
+        let mut out = Vec::new();
+        match &value.kind {
+            ValueKind::Null => out.push(0x00),
+            ValueKind::Bool(b) => out.push(0x08 | *b as u8),
+            ValueKind::Integer(n) => push_int(&mut out, *n),
+            ValueKind::Real(n) => {
+                out.push(0x23);
+                out.extend_from_slice(&n.to_be_bytes());
+            }
+            ValueKind::Date(seconds) => {
+                out.push(0x33);
+                out.extend_from_slice(&seconds.to_be_bytes());
+            }
+            ValueKind::Data(bytes) => {
+                push_marker(&mut out, 0x4, bytes.len());
+                out.extend_from_slice(bytes);
+            }
+            ValueKind::String(s) => self.push_string(&mut out, s),
+            ValueKind::Uid(n) => {
+                let len = width(*n);
+                out.push(0x80 | (len - 1) as u8);
+                push_uint(&mut out, *n, len);
+            }
+            ValueKind::Array(items) => {
+                let refs: Vec<_> = items.iter().map(|item| self.add(item)).collect();
+                push_marker(&mut out, 0xA, refs.len());
+                self.push_refs(&mut out, &refs);
+            }
+            ValueKind::Dict(entries) => {
+                let mut keys = Vec::with_capacity(entries.len());
+                let mut values = Vec::with_capacity(entries.len());
+                for (key, value) in entries {
+                    keys.push(self.objects.len());
+                    let mut encoded = Vec::new();
+                    self.push_string(&mut encoded, key);
+                    self.objects.push(encoded);
+                    values.push(self.add(value));
+                }
+                push_marker(&mut out, 0xD, entries.len());
+                self.push_refs(&mut out, &keys);
+                self.push_refs(&mut out, &values);
+            }
+        }
+        self.objects[index] = out;
+        index
+    }
+
+    fn push_string(&self, out: &mut Vec<u8>, s: &str) {
+        if s.is_ascii() {
+            push_marker(out, 0x5, s.len());
+            out.extend_from_slice(s.as_bytes());
+        } else {
+            let units: Vec<u16> = s.encode_utf16().collect();
+            push_marker(out, 0x6, units.len());
+            for unit in units {
+                out.extend_from_slice(&unit.to_be_bytes());
+            }
+        }
+    }
+
+    fn push_refs(&self, out: &mut Vec<u8>, refs: &[usize]) {
+        for &r in refs {
+            push_uint(out, r as u64, self.ref_size);
+        }
+    }
+}
+
+/// Write a marker with type nibble `ty` and a count
+fn push_marker(out: &mut Vec<u8>, ty: u8, count: usize) {
+    if count < 0x0F {
+        out.push(ty << 4 | count as u8);
+    } else {
+        out.push(ty << 4 | 0x0F);
+        push_int(out, count as i128);
+    }
+}
+
+/// Write an integer object in the smallest size that holds it. Negative
+/// numbers take 8 bytes, since only 8- and 16-byte integers are signed.
+fn push_int(out: &mut Vec<u8>, n: i128) {
+    if (0..=0xFFFF_FFFF).contains(&n) {
+        let len = width(n as u64);
+        out.push(0x10 | len.trailing_zeros() as u8);
+        push_uint(out, n as u64, len);
+    } else if let Ok(n) = i64::try_from(n) {
+        out.push(0x13);
+        out.extend_from_slice(&n.to_be_bytes());
+    } else {
+        out.push(0x14);
+        out.extend_from_slice(&n.to_be_bytes());
+    }
+}
+
+fn push_uint(out: &mut Vec<u8>, n: u64, len: usize) {
+    out.extend_from_slice(&n.to_be_bytes()[8 - len..]);
+}
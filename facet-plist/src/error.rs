@@ -0,0 +1,105 @@
+//! Property list parsing error types.
+
+use alloc::string::String;
+use core::fmt;
+
+/// Error type for property list parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlistError {
+    kind: PlistErrorKind,
+    /// Source span of the error, if available.
+    span: Option<facet_reflect::Span>,
+}
+
+impl PlistError {
+    /// Create a new error with the given kind.
+    pub fn new(kind: PlistErrorKind) -> Self {
+        Self { kind, span: None }
+    }
+
+    /// Create a new error with the given kind and span.
+    pub fn with_span(kind: PlistErrorKind, span: facet_reflect::Span) -> Self {
+        Self {
+            kind,
+            span: Some(span),
+        }
+    }
+
+    /// Get the error kind.
+    pub fn kind(&self) -> &PlistErrorKind {
+        &self.kind
+    }
+
+    /// Get the source span of the error, if known.
+    ///
+    /// For binary property lists this points at the offending object.
+    pub fn span(&self) -> Option<facet_reflect::Span> {
+        self.span
+    }
+}
+
+impl fmt::Display for PlistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            PlistErrorKind::Xml { message } => write!(f, "invalid XML: {message}"),
+            PlistErrorKind::UnexpectedElement { found, expected } => {
+                write!(f, "unexpected <{found}>, expected {expected}")
+            }
+            PlistErrorKind::UnexpectedEof { expected } => {
+                write!(f, "unexpected end of input, expected {expected}")
+            }
+            PlistErrorKind::InvalidValue { ty, text } => write!(f, "invalid {ty} `{text}`"),
+            PlistErrorKind::InvalidBinary { reason } => {
+                write!(f, "invalid binary property list: {reason}")
+            }
+            PlistErrorKind::InvalidUtf8 { message } => write!(f, "invalid UTF-8: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for PlistError {}
+
+/// Specific kinds of property list errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlistErrorKind {
+    /// The XML itself is malformed.
+    Xml {
+        /// The XML parser's message.
+        message: String,
+    },
+    /// An element that doesn't belong here, e.g. `<string>` where a `<key>` is required.
+    UnexpectedElement {
+        /// The element name.
+        found: String,
+        /// What was expected at this point.
+        expected: &'static str,
+    },
+    /// Unexpected end of input.
+    UnexpectedEof {
+        /// What was expected at this point.
+        expected: &'static str,
+    },
+    /// The text of an `<integer>`, `<real>` or `<data>` element can't be parsed.
+    InvalidValue {
+        /// The element name.
+        ty: &'static str,
+        /// The text.
+        text: String,
+    },
+    /// A malformed binary property list.
+    InvalidBinary {
+        /// What is wrong with it.
+        reason: &'static str,
+    },
+    /// Invalid UTF-8 in input.
+    InvalidUtf8 {
+        /// The UTF-8 error details.
+        message: String,
+    },
+}
+
+impl From<PlistErrorKind> for PlistError {
+    fn from(kind: PlistErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
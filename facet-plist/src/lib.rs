@@ -0,0 +1,127 @@
+//! Property list parser and serializer using facet-format.
+//!
+//! This crate reads and writes Apple [property lists], in both the XML and
+//! the binary (`bplist00`) encoding, for any type implementing `Facet`:
+//! application preferences, `Info.plist` files, launchd jobs and so on.
+//!
+//! # Mapping to Rust Types
+//!
+//! - `<dict>` holds structs and maps, `<array>` holds lists, sets and tuples.
+//! - `<string>`, `<integer>`, `<real>` and `<true/>`/`<false/>` hold scalars.
+//! - `<data>` holds `Vec<u8>` and `[u8; N]` fields, and is written for them.
+//! - `<date>` is read as an ISO 8601 string such as `2024-05-01T12:00:00Z`.
+//!   Dates are never written, since nothing marks a string field as a date.
+//! - `None` fields are left out. Enums are externally tagged.
+//!
+//! [`from_slice`] detects the encoding; the `to_*` functions pick one:
+//! [`to_string`] for XML, [`to_binary`] for binary.
+//!
+//! # Example
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_plist::{from_slice, from_str, to_binary};
+//!
+//! #[derive(Facet, Debug, PartialEq)]
+//! struct Prefs {
+//!     #[facet(rename = "ShowHiddenFiles")]
+//!     show_hidden_files: bool,
+//!     #[facet(rename = "RecentFolders")]
+//!     recent_folders: Vec<String>,
+//! }
+//!
+//! let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+//! <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+//! <plist version="1.0">
+//! <dict>
+//!     <key>ShowHiddenFiles</key>
+//!     <true/>
+//!     <key>RecentFolders</key>
+//!     <array>
+//!         <string>/Users/me/src</string>
+//!     </array>
+//! </dict>
+//! </plist>
+//! "#;
+//!
+//! let prefs: Prefs = from_str(xml).unwrap();
+//! assert!(prefs.show_hidden_files);
+//!
+//! let binary = to_binary(&prefs).unwrap();
+//! assert!(binary.starts_with(b"bplist00"));
+//! assert_eq!(from_slice::<Prefs>(&binary).unwrap(), prefs);
+//! ```
+//!
+//! [property lists]: https://developer.apple.com/documentation/foundation/propertylistserialization
+
+#![forbid(unsafe_code)]
+
+extern crate alloc;
+
+mod binary;
+mod error;
+mod parser;
+mod serializer;
+mod value;
+mod xml;
+
+pub use error::{PlistError, PlistErrorKind};
+pub use parser::{PlistParser, PlistProbe};
+pub use serializer::{
+    PlistSerializeError, PlistSerializer, peek_to_binary, peek_to_string, to_binary, to_string,
+    to_vec,
+};
+
+// Re-export DeserializeError for convenience
+pub use facet_format::DeserializeError;
+
+/// Deserialize a value from an XML property list.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_plist::from_str;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Job {
+///     #[facet(rename = "Label")]
+///     label: String,
+///     #[facet(rename = "RunAtLoad")]
+///     run_at_load: bool,
+/// }
+///
+/// let job: Job = from_str(
+///     "<plist><dict><key>Label</key><string>com.example.sync</string>\
+///      <key>RunAtLoad</key><true/></dict></plist>",
+/// )
+/// .unwrap();
+/// assert_eq!(job.label, "com.example.sync");
+/// ```
+pub fn from_str<T>(input: &str) -> Result<T, DeserializeError<PlistError>>
+where
+    T: facet_core::Facet<'static>,
+{
+    use facet_format::FormatDeserializer;
+    let parser = PlistParser::from_xml(input);
+    let mut de = FormatDeserializer::new_owned(parser);
+    de.deserialize()
+}
+
+/// Deserialize a value from a property list in either encoding.
+///
+/// Input starting with `bplist0` is read as a binary property list, anything
+/// else as XML.
+///
+/// # Errors
+///
+/// Returns an error if XML input is not valid UTF-8 or if deserialization fails.
+pub fn from_slice<T>(input: &[u8]) -> Result<T, DeserializeError<PlistError>>
+where
+    T: facet_core::Facet<'static>,
+{
+    use facet_format::FormatDeserializer;
+    let parser = PlistParser::new(input);
+    let mut de = FormatDeserializer::new_owned(parser);
+    de.deserialize()
+}
@@ -0,0 +1,253 @@
+//! Property list parser implementation using FormatParser trait.
+//!
+//! The input is read into a value tree first (see the `xml` and `binary`
+//! modules), then flattened into events:
+//!
+//! - `<dict>` → StructStart(Object) ... StructEnd, with a FieldKey per `<key>`
+//! - `<array>` → SequenceStart(Array) ... SequenceEnd
+//! - `<data>` → a sequence of byte values, so it fits `Vec<u8>`
+//! - `<date>` → an ISO 8601 string, e.g. `2024-05-01T12:00:00Z`
+//! - Everything else → Scalar
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use facet_format::{
+    ContainerKind, FieldEvidence, FieldKey, FieldLocationHint, FormatParser, ParseEvent,
+    ProbeStream, ScalarValue,
+};
+use facet_reflect::Span;
+
+use crate::error::{PlistError, PlistErrorKind};
+use crate::value::{Value, ValueKind, format_date};
+use crate::{binary, xml};
+
+/// Property list parser that converts XML or binary property lists to
+/// FormatParser events.
+pub struct PlistParser<'de> {
+    events: Vec<ParseEvent<'de>>,
+    /// Source spans for each event (parallel to events vec).
+    spans: Vec<Span>,
+    idx: usize,
+    pending_error: Option<PlistError>,
+}
+
+impl<'de> PlistParser<'de> {
+    /// Create a new parser for a property list in either encoding.
+    ///
+    /// Input starting with `bplist0` is read as a binary property list,
+    /// anything else as XML.
+    pub fn new(input: &[u8]) -> Self {
+        if input.starts_with(binary::MAGIC) {
+            return Self::build(binary::read(input));
+        }
+        match core::str::from_utf8(input) {
+            Ok(input) => Self::from_xml(input),
+            Err(e) => Self::build(Err(PlistError::with_span(
+                PlistErrorKind::InvalidUtf8 {
+                    message: e.to_string(),
+                },
+                Span::new(e.valid_up_to(), 1),
+            ))),
+        }
+    }
+
+    /// Create a new parser for an XML property list.
+    pub fn from_xml(input: &str) -> Self {
+        Self::build(xml::read(input))
+    }
+
+    fn build(result: Result<Value, PlistError>) -> Self {
+        match result {
+            Ok(value) => {
+                let mut parser = Self {
+                    events: Vec::new(),
+                    spans: Vec::new(),
+                    idx: 0,
+                    pending_error: None,
+                };
+                parser.emit(value);
+                parser
+            }
+            Err(err) => Self {
+                events: Vec::new(),
+                spans: Vec::new(),
+                idx: 0,
+                pending_error: Some(err),
+            },
+        }
+    }
+
+    fn push(&mut self, event: ParseEvent<'de>, span: Span) {
+        self.events.push(event);
+        self.spans.push(span);
+    }
+
+    fn emit(&mut self, value: Value) {
+        let span = Span::new(value.offset, 1);
+        let scalar = match value.kind {
+            ValueKind::Null => ScalarValue::Null,
+            ValueKind::Bool(b) => ScalarValue::Bool(b),
+            ValueKind::Integer(n) => integer(n),
+            ValueKind::Real(n) => ScalarValue::F64(n),
+            ValueKind::Date(seconds) => ScalarValue::Str(Cow::Owned(format_date(seconds))),
+            ValueKind::String(s) => ScalarValue::Str(Cow::Owned(s)),
+            ValueKind::Uid(n) => ScalarValue::U64(n),
+            ValueKind::Data(bytes) => {
+                self.push(ParseEvent::SequenceStart(ContainerKind::Array), span);
+                for byte in bytes {
+                    self.push(ParseEvent::Scalar(ScalarValue::U64(byte as u64)), span);
+                }
+                self.push(ParseEvent::SequenceEnd, span);
+                return;
+            }
+            ValueKind::Array(items) => {
+                self.push(ParseEvent::SequenceStart(ContainerKind::Array), span);
+                for item in items {
+                    self.emit(item);
+                }
+                self.push(ParseEvent::SequenceEnd, span);
+                return;
+            }
+            ValueKind::Dict(entries) => {
+                self.push(ParseEvent::StructStart(ContainerKind::Object), span);
+                for (key, value) in entries {
+                    let key_span = Span::new(value.offset, 1);
+                    self.push(
+                        ParseEvent::FieldKey(FieldKey::new(
+                            Cow::Owned(key),
+                            FieldLocationHint::KeyValue,
+                        )),
+                        key_span,
+                    );
+                    self.emit(value);
+                }
+                self.push(ParseEvent::StructEnd, span);
+                return;
+            }
+        };
+        self.push(ParseEvent::Scalar(scalar), span);
+    }
+
+    /// Build field evidence by looking ahead at remaining events.
+    fn build_probe(&self) -> Vec<FieldEvidence<'de>> {
+        let mut evidence = Vec::new();
+        if !matches!(self.events.get(self.idx), Some(ParseEvent::StructStart(_))) {
+            return evidence;
+        }
+
+        let mut depth = 0usize;
+        for (i, event) in self.events.iter().enumerate().skip(self.idx + 1) {
+            match event {
+                ParseEvent::StructStart(_) | ParseEvent::SequenceStart(_) => depth += 1,
+                ParseEvent::StructEnd | ParseEvent::SequenceEnd => {
+                    if depth == 0 {
+                        break;
+                    }
+                    depth -= 1;
+                }
+                ParseEvent::FieldKey(key) if depth == 0 => {
+                    evidence.push(match self.events.get(i + 1) {
+                        Some(ParseEvent::Scalar(value)) => FieldEvidence::with_scalar_value(
+                            key.name.clone(),
+                            key.location,
+                            None,
+                            value.clone(),
+                            None,
+                        ),
+                        _ => FieldEvidence::new(key.name.clone(), key.location, None, None),
+                    });
+                }
+                _ => {}
+            }
+        }
+        evidence
+    }
+}
+
+/// The narrowest scalar holding `n`
+fn integer<'de>(n: i128) -> ScalarValue<'de> {
+    if let Ok(n) = u64::try_from(n) {
+        ScalarValue::U64(n)
+    } else if let Ok(n) = i64::try_from(n) {
+        ScalarValue::I64(n)
+    } else {
+        ScalarValue::I128(n)
+    }
+}
+
+impl<'de> FormatParser<'de> for PlistParser<'de> {
+    type Error = PlistError;
+    type Probe<'a>
+        = PlistProbe<'de>
+    where
+        Self: 'a;
+
+    fn next_event(&mut self) -> Result<Option<ParseEvent<'de>>, Self::Error> {
+        if let Some(err) = &self.pending_error {
+            return Err(err.clone());
+        }
+        let event = self.events.get(self.idx).cloned();
+        if event.is_some() {
+            self.idx += 1;
+        }
+        Ok(event)
+    }
+
+    fn peek_event(&mut self) -> Result<Option<ParseEvent<'de>>, Self::Error> {
+        if let Some(err) = &self.pending_error {
+            return Err(err.clone());
+        }
+        Ok(self.events.get(self.idx).cloned())
+    }
+
+    fn skip_value(&mut self) -> Result<(), Self::Error> {
+        let mut depth = 0usize;
+        loop {
+            let event =
+                self.next_event()?
+                    .ok_or(PlistError::new(PlistErrorKind::UnexpectedEof {
+                        expected: "value",
+                    }))?;
+            match event {
+                ParseEvent::StructStart(_) | ParseEvent::SequenceStart(_) => depth += 1,
+                ParseEvent::StructEnd | ParseEvent::SequenceEnd => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            if depth == 0 && !matches!(event, ParseEvent::FieldKey(_)) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn begin_probe(&mut self) -> Result<Self::Probe<'_>, Self::Error> {
+        let evidence = self.build_probe();
+        Ok(PlistProbe { evidence, idx: 0 })
+    }
+
+    fn current_span(&self) -> Option<Span> {
+        // The span of the most recently consumed event
+        self.idx
+            .checked_sub(1)
+            .and_then(|i| self.spans.get(i).copied())
+    }
+}
+
+/// Field evidence for untagged-enum resolution, collected from buffered events.
+pub struct PlistProbe<'de> {
+    evidence: Vec<FieldEvidence<'de>>,
+    idx: usize,
+}
+
+impl<'de> ProbeStream<'de> for PlistProbe<'de> {
+    type Error = PlistError;
+
+    fn next(&mut self) -> Result<Option<FieldEvidence<'de>>, Self::Error> {
+        let evidence = self.evidence.get(self.idx).cloned();
+        self.idx += 1;
+        Ok(evidence)
+    }
+}
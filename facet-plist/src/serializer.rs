@@ -0,0 +1,254 @@
+//! Property list serializer implementing the FormatSerializer trait.
+//!
+//! Output is buffered into a value tree, then written as XML or binary:
+//!
+//! - Structs and maps become `<dict>`s
+//! - Lists, sets and tuples become `<array>`s
+//! - Fields holding bytes (`Vec<u8>`, `[u8; N]`) become `<data>`
+//! - `None` fields are left out; property lists have no null in XML
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use facet_core::{Def, Facet, Shape};
+use facet_format::{FormatSerializer, ScalarValue, SerializeError, serialize_root};
+use facet_reflect::Peek;
+
+use crate::value::{Value, ValueKind};
+use crate::{binary, xml};
+
+/// Error type for property list serialization.
+#[derive(Debug)]
+pub struct PlistSerializeError {
+    msg: String,
+}
+
+impl fmt::Display for PlistSerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+impl std::error::Error for PlistSerializeError {}
+
+impl PlistSerializeError {
+    pub(crate) fn new(msg: impl Into<String>) -> Self {
+        Self { msg: msg.into() }
+    }
+}
+
+enum Frame {
+    Array {
+        items: Vec<Value>,
+        /// Whether this holds the bytes of a `<data>` field
+        bytes: bool,
+    },
+    Dict {
+        entries: Vec<(String, Value)>,
+        key: Option<String>,
+    },
+}
+
+/// Property list serializer with buffered output.
+#[derive(Default)]
+pub struct PlistSerializer {
+    stack: Vec<Frame>,
+    root: Option<Value>,
+    /// Whether the next sequence is a byte field
+    next_is_bytes: bool,
+}
+
+impl PlistSerializer {
+    /// Create a new property list serializer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Render the serialized value as an XML property list.
+    ///
+    /// Fails if nothing was serialized or a null value is left outside a dict.
+    pub fn finish_xml(self) -> Result<String, PlistSerializeError> {
+        xml::write(&self.into_root()?)
+    }
+
+    /// Render the serialized value as a binary property list.
+    pub fn finish_binary(self) -> Result<Vec<u8>, PlistSerializeError> {
+        Ok(binary::write(&self.into_root()?))
+    }
+
+    fn into_root(self) -> Result<Value, PlistSerializeError> {
+        self.root
+            .ok_or_else(|| PlistSerializeError::new("no value was serialized"))
+    }
+
+    fn value(&mut self, kind: ValueKind) -> Result<(), PlistSerializeError> {
+        let value = Value::new(kind, 0);
+        match self.stack.last_mut() {
+            None => self.root = Some(value),
+            Some(Frame::Array { items, .. }) => items.push(value),
+            Some(Frame::Dict { entries, key }) => {
+                let key = key
+                    .take()
+                    .ok_or_else(|| PlistSerializeError::new("value without a key"))?;
+                if !matches!(value.kind, ValueKind::Null) {
+                    entries.push((key, value));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `shape` holds bytes, possibly optional
+fn is_bytes(shape: &Shape) -> bool {
+    match shape.def {
+        Def::List(def) => def.t().is_type::<u8>(),
+        Def::Array(def) => def.t().is_type::<u8>(),
+        Def::Slice(def) => def.t().is_type::<u8>(),
+        Def::Option(def) => is_bytes(def.t()),
+        _ => false,
+    }
+}
+
+impl FormatSerializer for PlistSerializer {
+    type Error = PlistSerializeError;
+
+    fn field_metadata(&mut self, field: &facet_reflect::FieldItem) -> Result<(), Self::Error> {
+        self.next_is_bytes = field.field.is_some_and(|f| is_bytes(f.shape()));
+        Ok(())
+    }
+
+    fn begin_struct(&mut self) -> Result<(), Self::Error> {
+        self.next_is_bytes = false;
+        self.stack.push(Frame::Dict {
+            entries: Vec::new(),
+            key: None,
+        });
+        Ok(())
+    }
+
+    fn field_key(&mut self, name: &str) -> Result<(), Self::Error> {
+        match self.stack.last_mut() {
+            Some(Frame::Dict { key, .. }) => {
+                *key = Some(name.to_string());
+                Ok(())
+            }
+            _ => Err(PlistSerializeError::new("field key outside of a dict")),
+        }
+    }
+
+    fn end_struct(&mut self) -> Result<(), Self::Error> {
+        match self.stack.pop() {
+            Some(Frame::Dict { entries, .. }) => self.value(ValueKind::Dict(entries)),
+            _ => Err(PlistSerializeError::new("unbalanced end of dict")),
+        }
+    }
+
+    fn begin_seq(&mut self) -> Result<(), Self::Error> {
+        self.stack.push(Frame::Array {
+            items: Vec::new(),
+            bytes: core::mem::take(&mut self.next_is_bytes),
+        });
+        Ok(())
+    }
+
+    fn end_seq(&mut self) -> Result<(), Self::Error> {
+        match self.stack.pop() {
+            Some(Frame::Array { items, bytes: true }) => {
+                let bytes = items
+                    .iter()
+                    .map(|item| match item.kind {
+                        ValueKind::Integer(n) => u8::try_from(n).ok(),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<u8>>>();
+                match bytes {
+                    Some(bytes) => self.value(ValueKind::Data(bytes)),
+                    None => self.value(ValueKind::Array(items)),
+                }
+            }
+            Some(Frame::Array { items, .. }) => self.value(ValueKind::Array(items)),
+            _ => Err(PlistSerializeError::new("unbalanced end of array")),
+        }
+    }
+
+    fn scalar(&mut self, scalar: ScalarValue<'_>) -> Result<(), Self::Error> {
+        self.next_is_bytes = false;
+        let kind = match scalar {
+            ScalarValue::Null => ValueKind::Null,
+            ScalarValue::Bool(b) => ValueKind::Bool(b),
+            ScalarValue::I64(n) => ValueKind::Integer(n as i128),
+            ScalarValue::U64(n) => ValueKind::Integer(n as i128),
+            ScalarValue::I128(n) => ValueKind::Integer(n),
+            ScalarValue::U128(n) => ValueKind::Integer(i128::try_from(n).map_err(|_| {
+                PlistSerializeError::new(alloc::format!(
+                    "property lists can't represent the integer {n}"
+                ))
+            })?),
+            ScalarValue::F64(n) => ValueKind::Real(n),
+            ScalarValue::Str(s) => ValueKind::String(s.into_owned()),
+            ScalarValue::Bytes(bytes) => ValueKind::Data(bytes.into_owned()),
+        };
+        self.value(kind)
+    }
+}
+
+/// Serialize a value to an XML property list.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_plist::to_string;
+///
+/// #[derive(Facet)]
+/// struct Prefs {
+///     volume: u8,
+/// }
+///
+/// let xml = to_string(&Prefs { volume: 7 }).unwrap();
+/// assert!(xml.contains("<dict>\n\t<key>volume</key>\n\t<integer>7</integer>\n</dict>"));
+/// ```
+pub fn to_string<'facet, T>(value: &T) -> Result<String, SerializeError<PlistSerializeError>>
+where
+    T: Facet<'facet> + ?Sized,
+{
+    peek_to_string(Peek::new(value))
+}
+
+/// Serialize a value to an XML property list, as bytes.
+pub fn to_vec<'facet, T>(value: &T) -> Result<Vec<u8>, SerializeError<PlistSerializeError>>
+where
+    T: Facet<'facet> + ?Sized,
+{
+    to_string(value).map(String::into_bytes)
+}
+
+/// Serialize a value to a binary property list.
+pub fn to_binary<'facet, T>(value: &T) -> Result<Vec<u8>, SerializeError<PlistSerializeError>>
+where
+    T: Facet<'facet> + ?Sized,
+{
+    peek_to_binary(Peek::new(value))
+}
+
+/// Serialize a `Peek` instance to an XML property list.
+pub fn peek_to_string<'input, 'facet>(
+    peek: Peek<'input, 'facet>,
+) -> Result<String, SerializeError<PlistSerializeError>> {
+    let mut serializer = PlistSerializer::new();
+    serialize_root(&mut serializer, peek)?;
+    serializer.finish_xml().map_err(SerializeError::Backend)
+}
+
+/// Serialize a `Peek` instance to a binary property list.
+pub fn peek_to_binary<'input, 'facet>(
+    peek: Peek<'input, 'facet>,
+) -> Result<Vec<u8>, SerializeError<PlistSerializeError>> {
+    let mut serializer = PlistSerializer::new();
+    serialize_root(&mut serializer, peek)?;
+    serializer.finish_binary().map_err(SerializeError::Backend)
+}
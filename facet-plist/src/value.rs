@@ -0,0 +1,121 @@
+//! The property list data model shared by the XML and binary encodings.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// A property list value, along with where it starts in the input
+pub(crate) struct Value {
+    pub kind: ValueKind,
+    /// Byte offset in the input, or 0 for values built by the serializer
+    pub offset: usize,
+}
+
+pub(crate) enum ValueKind {
+    /// Only representable in binary property lists
+    Null,
+    Bool(bool),
+    Integer(i128),
+    Real(f64),
+    /// Seconds since 2001-01-01T00:00:00Z
+    Date(f64),
+    Data(Vec<u8>),
+    String(String),
+    /// Object references in keyed archives; only representable in binary property lists
+    Uid(u64),
+    Array(Vec<Value>),
+    Dict(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn new(kind: ValueKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+}
+
+/// How deep containers may nest before the input is rejected
+pub(crate) const MAX_DEPTH: usize = 512;
+
+/// Seconds between the Unix epoch and the property list epoch, 2001-01-01
+const EPOCH_OFFSET: i64 = 978_307_200;
+
+/// Format a date as `YYYY-MM-DDTHH:MM:SSZ`, the form XML property lists use.
+/// Fractional seconds are dropped.
+pub(crate) fn format_date(seconds: f64) -> String {
+    let unix = seconds.floor() as i64 + EPOCH_OFFSET;
+    let (days, secs) = (unix.div_euclid(86_400), unix.rem_euclid(86_400));
+    let (year, month, day) = civil_from_days(days);
+    let mut out = String::with_capacity(20);
+    write!(
+        out,
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+    .unwrap();
+    out
+}
+
+/// Parse a date written as `YYYY-MM-DDTHH:MM:SSZ`
+pub(crate) fn parse_date(text: &str) -> Option<f64> {
+    let b = text.as_bytes();
+    if b.len() != 20
+        || b[4] != b'-'
+        || b[7] != b'-'
+        || b[10] != b'T'
+        || b[13] != b':'
+        || b[16] != b':'
+        || b[19] != b'Z'
+    {
+        return None;
+    }
+    let num = |range: core::ops::Range<usize>| -> Option<i64> {
+        let digits = &text[range];
+        digits
+            .bytes()
+            .all(|c| c.is_ascii_digit())
+            .then(|| digits.parse().ok())
+            .flatten()
+    };
+    let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    let (hour, minute, second) = (num(11..13)?, num(14..16)?, num(17..19)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 {
+        return None;
+    }
+    // Leap seconds are allowed
+    if second > 60 {
+        return None;
+    }
+    let unix = days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second;
+    Some((unix - EPOCH_OFFSET) as f64)
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic Gregorian date of a number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
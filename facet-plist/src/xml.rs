@@ -0,0 +1,426 @@
+//! Reading and writing XML property lists.
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use facet_reflect::Span;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+
+use crate::error::{PlistError, PlistErrorKind};
+use crate::serializer::PlistSerializeError;
+use crate::value::{MAX_DEPTH, Value, ValueKind, format_date, parse_date};
+
+/// An element whose children haven't all been read yet
+enum Open {
+    Plist(Option<Value>),
+    Array(Vec<Value>),
+    Dict {
+        entries: Vec<(String, Value)>,
+        key: Option<String>,
+    },
+    Text {
+        tag: TextTag,
+        text: String,
+    },
+    Bool(bool),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TextTag {
+    Key,
+    String,
+    Integer,
+    Real,
+    Date,
+    Data,
+}
+
+/// Parse an XML property list. A bare `<dict>` or other value without the
+/// `<plist>` wrapper is accepted too.
+pub(crate) fn read(input: &str) -> Result<Value, PlistError> {
+    let mut reader = Reader::from_str(input);
+    // (element, where it starts)
+    let mut stack: Vec<(Open, usize)> = Vec::new();
+    let mut root = None;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader.read_event().map_err(|e| {
+            PlistError::with_span(
+                PlistErrorKind::Xml {
+                    message: e.to_string(),
+                },
+                Span::new(reader.error_position() as usize, 0),
+            )
+        })?;
+        let end = reader.buffer_position() as usize;
+        let span = Span::new(start, end - start);
+
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let name = core::str::from_utf8(e.local_name().into_inner())
+                    .map_err(|err| {
+                        PlistError::with_span(
+                            PlistErrorKind::InvalidUtf8 {
+                                message: err.to_string(),
+                            },
+                            span,
+                        )
+                    })?
+                    .to_string();
+                if stack.len() >= MAX_DEPTH {
+                    return Err(PlistError::with_span(
+                        PlistErrorKind::Xml {
+                            message: "elements nested too deeply".to_string(),
+                        },
+                        span,
+                    ));
+                }
+                let open = open(&name, stack.last().map(|(open, _)| open), root.is_some())
+                    .map_err(|kind| PlistError::with_span(kind, span))?;
+                stack.push((open, start));
+                if matches!(event, Event::Empty(_)) {
+                    close(&mut stack, &mut root, end)?;
+                }
+            }
+            Event::End(_) => close(&mut stack, &mut root, end)?,
+            Event::Text(e) => {
+                let text = e.decode().map_err(|err| {
+                    PlistError::with_span(
+                        PlistErrorKind::Xml {
+                            message: err.to_string(),
+                        },
+                        span,
+                    )
+                })?;
+                push_text(&mut stack, &text, span)?;
+            }
+            Event::CData(e) => {
+                let text = core::str::from_utf8(&e).map_err(|err| {
+                    PlistError::with_span(
+                        PlistErrorKind::InvalidUtf8 {
+                            message: err.to_string(),
+                        },
+                        span,
+                    )
+                })?;
+                push_text(&mut stack, text, span)?;
+            }
+            Event::GeneralRef(e) => {
+                let name = e.decode().map_err(|err| {
+                    PlistError::with_span(
+                        PlistErrorKind::Xml {
+                            message: err.to_string(),
+                        },
+                        span,
+                    )
+                })?;
+                let resolved = resolve_entity(&name).ok_or_else(|| {
+                    PlistError::with_span(
+                        PlistErrorKind::Xml {
+                            message: alloc::format!("unknown entity `&{name};`"),
+                        },
+                        span,
+                    )
+                })?;
+                push_text(&mut stack, resolved.encode_utf8(&mut [0; 4]), span)?;
+            }
+            Event::Decl(_) | Event::Comment(_) | Event::PI(_) | Event::DocType(_) => {}
+            Event::Eof => break,
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(PlistError::with_span(
+            PlistErrorKind::UnexpectedEof {
+                expected: "closing tag",
+            },
+            Span::new(input.len(), 0),
+        ));
+    }
+    root.ok_or_else(|| {
+        PlistError::with_span(
+            PlistErrorKind::UnexpectedEof {
+                expected: "a property list value",
+            },
+            Span::new(input.len(), 0),
+        )
+    })
+}
+
+/// The element `name` opened inside `parent`
+fn open(name: &str, parent: Option<&Open>, has_root: bool) -> Result<Open, PlistErrorKind> {
+    let unexpected = |expected| PlistErrorKind::UnexpectedElement {
+        found: name.to_string(),
+        expected,
+    };
+    match parent {
+        Some(Open::Text { .. } | Open::Bool(_)) => return Err(unexpected("text")),
+        Some(Open::Dict { key: None, .. }) if name != "key" => return Err(unexpected("<key>")),
+        Some(Open::Dict { key: Some(_), .. }) if name == "key" => {
+            return Err(unexpected("a value"));
+        }
+        Some(Open::Array(_)) if name == "key" => return Err(unexpected("a value")),
+        Some(Open::Plist(Some(_))) => return Err(unexpected("</plist>")),
+        None if has_root => return Err(unexpected("end of document")),
+        None if name == "key" => return Err(unexpected("a value")),
+        _ => {}
+    }
+    let text = |tag| Open::Text {
+        tag,
+        text: String::new(),
+    };
+    Ok(match name {
+        "plist" if parent.is_none() => Open::Plist(None),
+        "dict" => Open::Dict {
+            entries: Vec::new(),
+            key: None,
+        },
+        "array" => Open::Array(Vec::new()),
+        "key" => text(TextTag::Key),
+        "string" => text(TextTag::String),
+        "integer" => text(TextTag::Integer),
+        "real" => text(TextTag::Real),
+        "date" => text(TextTag::Date),
+        "data" => text(TextTag::Data),
+        "true" => Open::Bool(true),
+        "false" => Open::Bool(false),
+        _ => return Err(unexpected("a property list element")),
+    })
+}
+
+/// Finish the innermost open element, which ends at `end`
+fn close(
+    stack: &mut Vec<(Open, usize)>,
+    root: &mut Option<Value>,
+    end: usize,
+) -> Result<(), PlistError> {
+    let Some((open, start)) = stack.pop() else {
+        return Err(PlistError::with_span(
+            PlistErrorKind::Xml {
+                message: "unmatched closing tag".to_string(),
+            },
+            Span::new(end, 0),
+        ));
+    };
+    let span = Span::new(start, end - start);
+    let kind = match open {
+        Open::Plist(value) => {
+            let value = value.ok_or_else(|| {
+                PlistError::with_span(
+                    PlistErrorKind::UnexpectedEof {
+                        expected: "a property list value",
+                    },
+                    span,
+                )
+            })?;
+            *root = Some(value);
+            return Ok(());
+        }
+        Open::Array(items) => ValueKind::Array(items),
+        Open::Dict { entries, key } => {
+            if key.is_some() {
+                return Err(PlistError::with_span(
+                    PlistErrorKind::UnexpectedElement {
+                        found: "/dict".to_string(),
+                        expected: "a value",
+                    },
+                    span,
+                ));
+            }
+            ValueKind::Dict(entries)
+        }
+        Open::Bool(b) => ValueKind::Bool(b),
+        Open::Text {
+            tag: TextTag::Key,
+            text,
+        } => {
+            if let Some((Open::Dict { key, .. }, _)) = stack.last_mut() {
+                *key = Some(text);
+            }
+            return Ok(());
+        }
+        Open::Text { tag, text } => {
+            text_value(tag, text).map_err(|kind| PlistError::with_span(kind, span))?
+        }
+    };
+
+    let value = Value::new(kind, start);
+    match stack.last_mut() {
+        None => *root = Some(value),
+        Some((Open::Plist(slot), _)) => *slot = Some(value),
+        Some((Open::Array(items), _)) => items.push(value),
+        Some((Open::Dict { entries, key }, _)) => {
+            // `open` only lets values in after a key
+            if let Some(key) = key.take() {
+                entries.push((key, value));
+            }
+        }
+        Some((Open::Text { .. } | Open::Bool(_), _)) => {}
+    }
+    Ok(())
+}
+
+fn text_value(tag: TextTag, text: String) -> Result<ValueKind, PlistErrorKind> {
+    let invalid = |ty: &'static str, text: &str| PlistErrorKind::InvalidValue {
+        ty,
+        text: text.to_string(),
+    };
+    Ok(match tag {
+        TextTag::Key | TextTag::String => ValueKind::String(text),
+        TextTag::Integer => {
+            let trimmed = text.trim();
+            let (negative, digits) = match trimmed.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+            };
+            let magnitude = match digits
+                .strip_prefix("0x")
+                .or_else(|| digits.strip_prefix("0X"))
+            {
+                Some(hex) => i128::from_str_radix(hex, 16),
+                None => digits.parse::<i128>(),
+            }
+            .map_err(|_| invalid("integer", trimmed))?;
+            ValueKind::Integer(if negative { -magnitude } else { magnitude })
+        }
+        TextTag::Real => ValueKind::Real(
+            text.trim()
+                .parse()
+                .map_err(|_| invalid("real", text.trim()))?,
+        ),
+        TextTag::Date => {
+            ValueKind::Date(parse_date(text.trim()).ok_or_else(|| invalid("date", text.trim()))?)
+        }
+        TextTag::Data => {
+            let compact: Vec<u8> = text.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+            ValueKind::Data(
+                STANDARD
+                    .decode(&compact)
+                    .map_err(|_| invalid("data", text.trim()))?,
+            )
+        }
+    })
+}
+
+/// Add character data to the innermost element. Only whitespace is allowed
+/// between elements.
+fn push_text(stack: &mut [(Open, usize)], text: &str, span: Span) -> Result<(), PlistError> {
+    match stack.last_mut() {
+        Some((Open::Text { text: buf, .. }, _)) => {
+            buf.push_str(text);
+            Ok(())
+        }
+        _ if text.trim().is_empty() => Ok(()),
+        _ => Err(PlistError::with_span(
+            PlistErrorKind::Xml {
+                message: alloc::format!("unexpected text `{}`", text.trim()),
+            },
+            span,
+        )),
+    }
+}
+
+fn resolve_entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "apos" => Some('\''),
+        "quot" => Some('"'),
+        _ => {
+            let code = name.strip_prefix('#')?;
+            let code = match code.strip_prefix('x') {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+const HEADER: &str = concat!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+    "<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" ",
+    "\"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n",
+    "<plist version=\"1.0\">\n",
+);
+
+/// Write `value` as an XML property list, indented with tabs like Apple's tools do.
+pub(crate) fn write(value: &Value) -> Result<String, PlistSerializeError> {
+    let mut out = String::from(HEADER);
+    write_value(&mut out, value, 0)?;
+    out.push_str("</plist>\n");
+    Ok(out)
+}
+
+fn write_value(out: &mut String, value: &Value, indent: usize) -> Result<(), PlistSerializeError> {
+    pad(out, indent);
+    match &value.kind {
+        ValueKind::Null => {
+            return Err(PlistSerializeError::new(
+                "XML property lists can't hold null values",
+            ));
+        }
+        ValueKind::Bool(true) => out.push_str("<true/>"),
+        ValueKind::Bool(false) => out.push_str("<false/>"),
+        ValueKind::Integer(n) => write!(out, "<integer>{n}</integer>").unwrap(),
+        ValueKind::Real(n) => write!(out, "<real>{n}</real>").unwrap(),
+        ValueKind::Date(seconds) => write!(out, "<date>{}</date>", format_date(*seconds)).unwrap(),
+        ValueKind::Data(bytes) => write!(out, "<data>{}</data>", STANDARD.encode(bytes)).unwrap(),
+        ValueKind::String(s) => write!(out, "<string>{}</string>", escape(s)).unwrap(),
+        // The convention of keyed archives
+        ValueKind::Uid(n) => {
+            write!(out, "<dict><key>CF$UID</key><integer>{n}</integer></dict>").unwrap()
+        }
+        ValueKind::Array(items) if items.is_empty() => out.push_str("<array/>"),
+        ValueKind::Array(items) => {
+            out.push_str("<array>\n");
+            for item in items {
+                write_value(out, item, indent + 1)?;
+            }
+            pad(out, indent);
+            out.push_str("</array>");
+        }
+        ValueKind::Dict(entries) if entries.is_empty() => out.push_str("<dict/>"),
+        ValueKind::Dict(entries) => {
+            out.push_str("<dict>\n");
+            for (key, value) in entries {
+                pad(out, indent + 1);
+                writeln!(out, "<key>{}</key>", escape(key)).unwrap();
+                write_value(out, value, indent + 1)?;
+            }
+            pad(out, indent);
+            out.push_str("</dict>");
+        }
+    }
+    out.push('\n');
+    Ok(())
+}
+
+fn pad(out: &mut String, indent: usize) {
+    for _ in 0..indent {
+        out.push('\t');
+    }
+}
+
+fn escape(s: &str) -> Cow<'_, str> {
+    if !s.contains(['&', '<', '>']) {
+        return Cow::Borrowed(s);
+    }
+    let mut out = String::with_capacity(s.len() + 8);
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            c => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
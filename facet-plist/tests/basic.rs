@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_plist::{PlistErrorKind, from_slice, from_str, to_binary, to_string};
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+#[facet(rename_all = "PascalCase")]
+struct Sample {
+    name: String,
+    count: u32,
+    negative: i64,
+    big: u64,
+    ratio: f64,
+    enabled: bool,
+    blob: Vec<u8>,
+    when: String,
+    tags: Vec<String>,
+    nested: BTreeMap<String, i32>,
+}
+
+/// `Sample`, written by Python's `plistlib` in binary form
+const SAMPLE_BINARY: &[u8] = &[
+    0x62, 0x70, 0x6c, 0x69, 0x73, 0x74, 0x30, 0x30, 0xda, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+    0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x16, 0x54, 0x4e, 0x61,
+    0x6d, 0x65, 0x55, 0x43, 0x6f, 0x75, 0x6e, 0x74, 0x58, 0x4e, 0x65, 0x67, 0x61, 0x74, 0x69, 0x76,
+    0x65, 0x53, 0x42, 0x69, 0x67, 0x55, 0x52, 0x61, 0x74, 0x69, 0x6f, 0x57, 0x45, 0x6e, 0x61, 0x62,
+    0x6c, 0x65, 0x64, 0x54, 0x42, 0x6c, 0x6f, 0x62, 0x54, 0x57, 0x68, 0x65, 0x6e, 0x54, 0x54, 0x61,
+    0x67, 0x73, 0x56, 0x4e, 0x65, 0x73, 0x74, 0x65, 0x64, 0x63, 0x00, 0x5a, 0x00, 0x6f, 0x00, 0xeb,
+    0x10, 0x03, 0x13, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe, 0x14, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x23, 0x3f, 0xd0, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x09, 0x43, 0x00, 0x01, 0xff, 0x33, 0x41, 0xc5, 0xf1, 0x33, 0x20,
+    0x00, 0x00, 0x00, 0xa2, 0x14, 0x15, 0x51, 0x61, 0x51, 0x62, 0xd1, 0x17, 0x18, 0x55, 0x44, 0x65,
+    0x70, 0x74, 0x68, 0x10, 0x01, 0x08, 0x1d, 0x22, 0x28, 0x31, 0x35, 0x3b, 0x43, 0x48, 0x4d, 0x52,
+    0x59, 0x60, 0x62, 0x6b, 0x7c, 0x85, 0x86, 0x8a, 0x93, 0x96, 0x98, 0x9a, 0x9d, 0xa3, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x19, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xa5,
+];
+
+const SAMPLE_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>Name</key>
+	<string>Zoë</string>
+	<key>Count</key>
+	<integer>3</integer>
+	<key>Negative</key>
+	<integer>-2</integer>
+	<key>Big</key>
+	<integer>9223372036854775813</integer>
+	<key>Ratio</key>
+	<real>0.25</real>
+	<key>Enabled</key>
+	<true/>
+	<key>Blob</key>
+	<data>
+	AAH/
+	</data>
+	<key>When</key>
+	<date>2024-05-01T12:00:00Z</date>
+	<key>Tags</key>
+	<array>
+		<string>a</string>
+		<string>b</string>
+	</array>
+	<key>Nested</key>
+	<dict>
+		<key>Depth</key>
+		<integer>1</integer>
+	</dict>
+</dict>
+</plist>
+"#;
+
+fn sample() -> Sample {
+    Sample {
+        name: "Zoë".into(),
+        count: 3,
+        negative: -2,
+        big: (1 << 63) + 5,
+        ratio: 0.25,
+        enabled: true,
+        blob: vec![0, 1, 255],
+        when: "2024-05-01T12:00:00Z".into(),
+        tags: vec!["a".into(), "b".into()],
+        nested: BTreeMap::from([("Depth".into(), 1)]),
+    }
+}
+
+#[test]
+fn test_read_xml() {
+    assert_eq!(from_str::<Sample>(SAMPLE_XML).unwrap(), sample());
+    assert_eq!(
+        from_slice::<Sample>(SAMPLE_XML.as_bytes()).unwrap(),
+        sample()
+    );
+}
+
+#[test]
+fn test_read_binary() {
+    assert_eq!(from_slice::<Sample>(SAMPLE_BINARY).unwrap(), sample());
+}
+
+#[test]
+fn test_xml_roundtrip() {
+    let xml = to_string(&sample()).unwrap();
+    assert!(
+        xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"),
+        "{xml}"
+    );
+    assert!(
+        xml.contains("\t<key>Blob</key>\n\t<data>AAH/</data>\n"),
+        "{xml}"
+    );
+    assert!(xml.contains("\t<key>Enabled</key>\n\t<true/>\n"), "{xml}");
+    assert_eq!(from_str::<Sample>(&xml).unwrap(), sample());
+}
+
+#[test]
+fn test_binary_roundtrip() {
+    #[derive(Facet, Debug, PartialEq)]
+    struct Many {
+        items: Vec<u32>,
+        text: String,
+        optional: Option<u8>,
+        fixed: [u8; 4],
+    }
+
+    let many = Many {
+        items: (0..300).map(|i| i * 70_000).collect(),
+        text: "x".repeat(20),
+        optional: None,
+        fixed: [1, 2, 3, 4],
+    };
+    let binary = to_binary(&many).unwrap();
+    assert!(binary.starts_with(b"bplist00"));
+    assert_eq!(from_slice::<Many>(&binary).unwrap(), many);
+
+    let sample_binary = to_binary(&sample()).unwrap();
+    assert_eq!(from_slice::<Sample>(&sample_binary).unwrap(), sample());
+}
+
+#[test]
+fn test_escapes_and_enums() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Action {
+        Open,
+        Run { path: String },
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Doc {
+        title: String,
+        actions: Vec<Action>,
+    }
+
+    let doc = Doc {
+        title: "a < b && c".into(),
+        actions: vec![
+            Action::Open,
+            Action::Run {
+                path: "/bin/sh".into(),
+            },
+        ],
+    };
+    let xml = to_string(&doc).unwrap();
+    assert!(
+        xml.contains("<string>a &lt; b &amp;&amp; c</string>"),
+        "{xml}"
+    );
+    assert_eq!(from_str::<Doc>(&xml).unwrap(), doc);
+    assert_eq!(from_slice::<Doc>(&to_binary(&doc).unwrap()).unwrap(), doc);
+}
+
+#[test]
+fn test_errors() {
+    let err = from_str::<Sample>("<plist><dict><string>x</string></dict></plist>").unwrap_err();
+    match err {
+        facet_plist::DeserializeError::Parser(err) => {
+            assert!(matches!(
+                err.kind(),
+                PlistErrorKind::UnexpectedElement {
+                    expected: "<key>",
+                    ..
+                }
+            ));
+            assert_eq!(err.span().unwrap().offset, 13);
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+
+    let err = from_str::<u32>("<integer>twelve</integer>").unwrap_err();
+    assert!(
+        err.to_string().contains("invalid integer `twelve`"),
+        "{err}"
+    );
+
+    let mut truncated = SAMPLE_BINARY.to_vec();
+    truncated.truncate(100);
+    let err = from_slice::<Sample>(&truncated).unwrap_err();
+    assert!(err.to_string().contains("binary property list"), "{err}");
+
+    // An array containing itself
+    let mut cyclic = b"bplist00".to_vec();
+    cyclic.extend_from_slice(&[0xA1, 0x00, 0x08]);
+    cyclic.extend_from_slice(&[0, 0, 0, 0, 0, 0, 1, 1]);
+    cyclic.extend_from_slice(&1u64.to_be_bytes());
+    cyclic.extend_from_slice(&0u64.to_be_bytes());
+    cyclic.extend_from_slice(&10u64.to_be_bytes());
+    let err = from_slice::<Vec<Vec<u8>>>(&cyclic).unwrap_err();
+    assert!(err.to_string().contains("contains itself"), "{err}");
+}
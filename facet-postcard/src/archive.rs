@@ -0,0 +1,309 @@
+//! Zero-copy archives of fixed-layout values.
+//!
+//! An archive is a small header followed by the bytes of a value exactly as
+//! they sit in memory, so reading one back is a pointer cast: [`access`]
+//! validates the header and the value's bytes and hands out a `&T` borrowing
+//! from the input. This makes archives suitable for memory-mapped files, where
+//! only the pages that are actually read get loaded.
+//!
+//! The header records the [`fingerprint`] of the value's shape and a hash of
+//! its memory layout (field offsets, sizes, alignments, endianness and pointer
+//! width), and [`access`] refuses archives whose shape or layout differ from
+//! the requested type's.
+//!
+//! Only types made of plain data can be archived: integers, floats, `bool`,
+//! `char`, `()`, arrays of those, and structs whose fields are all archivable.
+//! Anything owning or pointing to memory elsewhere, like `String`, `Vec` or
+//! `Box`, is rejected, as are enums.
+//!
+//! Unlike postcard, archives are not portable: they can only be read on
+//! platforms with the same layout for the archived type.
+//!
+//! # Example
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_postcard::archive;
+//!
+//! #[derive(Facet, Debug, PartialEq)]
+//! struct Sample {
+//!     timestamp: u64,
+//!     values: [f32; 4],
+//!     valid: bool,
+//! }
+//!
+//! let sample = Sample {
+//!     timestamp: 1_700_000_000,
+//!     values: [0.5, 1.5, 2.5, 3.5],
+//!     valid: true,
+//! };
+//! let bytes = archive::to_vec(&sample).unwrap();
+//!
+//! let archived: &Sample = archive::access(&bytes).unwrap();
+//! assert_eq!(archived, &sample);
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use facet_core::{Def, Facet, Fnv1a64, ScalarType, Shape, Type, UserType};
+
+use crate::rpc::fingerprint;
+
+/// The first bytes of every archive
+const MAGIC: &[u8; 8] = b"facetarc";
+
+/// Magic, shape fingerprint and layout hash
+const HEADER_LEN: usize = 24;
+
+/// An error from writing or reading an archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArchiveError {
+    /// The shape, or one of its fields, isn't plain data
+    Unsupported {
+        /// The shape that can't be archived
+        shape: &'static Shape,
+    },
+    /// The input is shorter than the header and value it should hold
+    Truncated,
+    /// The input doesn't start with the archive header
+    BadMagic,
+    /// The archive holds a value of a different shape
+    ShapeMismatch {
+        /// The fingerprint of the requested shape
+        expected: u64,
+        /// The fingerprint of the archived shape
+        found: u64,
+    },
+    /// The archive was written by a build that lays the value out differently
+    LayoutMismatch,
+    /// The value in the input isn't aligned for the requested type
+    Misaligned {
+        /// The alignment the value needs
+        align: usize,
+    },
+    /// The archive holds bytes that aren't a valid value, e.g. a `bool` that
+    /// is neither 0 nor 1
+    InvalidValue {
+        /// The shape of the invalid value
+        shape: &'static Shape,
+        /// Where the value starts, relative to the archived value
+        offset: usize,
+    },
+}
+
+impl fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArchiveError::Unsupported { shape } => {
+                write!(f, "{shape} can't be archived: it isn't plain data")
+            }
+            ArchiveError::Truncated => write!(f, "archive is truncated"),
+            ArchiveError::BadMagic => write!(f, "input is not an archive"),
+            ArchiveError::ShapeMismatch { expected, found } => write!(
+                f,
+                "archive shape mismatch: expected fingerprint {expected:016x}, found {found:016x}"
+            ),
+            ArchiveError::LayoutMismatch => {
+                write!(f, "archive was written with a different memory layout")
+            }
+            ArchiveError::Misaligned { align } => {
+                write!(f, "archived value is not aligned to {align} bytes")
+            }
+            ArchiveError::InvalidValue { shape, offset } => {
+                write!(f, "invalid {shape} at offset {offset} in archive")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArchiveError {}
+
+/// Archive `value`, which must be plain data
+///
+/// Padding between fields is written as zeros.
+pub fn to_vec<'a, T: Facet<'a>>(value: &T) -> Result<Vec<u8>, ArchiveError> {
+    let shape = T::SHAPE;
+    check(shape)?;
+
+    let start = payload_start(core::mem::align_of::<T>());
+    let mut out = vec![0u8; start + core::mem::size_of::<T>()];
+    out[..8].copy_from_slice(MAGIC);
+    out[8..16].copy_from_slice(&fingerprint(shape).to_le_bytes());
+    out[16..24].copy_from_slice(&layout_hash(shape).to_le_bytes());
+    // SAFETY: `value` is a `T`, which `check` made sure is plain data
+    // described by `shape`, and `out` has room for all of it.
+    unsafe { write(shape, (value as *const T).cast(), &mut out[start..]) };
+    Ok(out)
+}
+
+/// Borrow the value archived in `bytes` without copying it
+///
+/// Checks that the archive was written for the same shape and layout as `T`,
+/// and that every `bool` and `char` in it is valid. The value must be
+/// aligned for `T`, which holds for memory-mapped files and for the buffers
+/// returned by [`to_vec`] as long as `T` needs no more than the allocator's
+/// minimum alignment.
+pub fn access<T: Facet<'static>>(bytes: &[u8]) -> Result<&T, ArchiveError> {
+    let shape = T::SHAPE;
+    check(shape)?;
+
+    if bytes.len() < HEADER_LEN {
+        return Err(ArchiveError::Truncated);
+    }
+    if &bytes[..8] != MAGIC {
+        return Err(ArchiveError::BadMagic);
+    }
+    let expected = fingerprint(shape);
+    let found = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+    if found != expected {
+        return Err(ArchiveError::ShapeMismatch { expected, found });
+    }
+    if u64::from_le_bytes(bytes[16..24].try_into().unwrap()) != layout_hash(shape) {
+        return Err(ArchiveError::LayoutMismatch);
+    }
+
+    let align = core::mem::align_of::<T>();
+    let start = payload_start(align);
+    let payload = bytes
+        .get(start..start + core::mem::size_of::<T>())
+        .ok_or(ArchiveError::Truncated)?;
+    if !(payload.as_ptr() as usize).is_multiple_of(align) {
+        return Err(ArchiveError::Misaligned { align });
+    }
+    validate(shape, payload, 0)?;
+
+    // SAFETY: the payload is as large and as aligned as a `T`, it was written
+    // from a `T` with the same layout, and `validate` checked the only
+    // scalars that have invalid bit patterns.
+    Ok(unsafe { &*payload.as_ptr().cast::<T>() })
+}
+
+/// Where the value starts, after the header and enough padding to align it
+fn payload_start(align: usize) -> usize {
+    HEADER_LEN.next_multiple_of(align)
+}
+
+/// Make sure `shape` is plain data
+fn check(shape: &'static Shape) -> Result<(), ArchiveError> {
+    match shape.def {
+        Def::Array(def) => check(def.t()),
+        Def::Scalar => match shape.scalar_type() {
+            Some(
+                ScalarType::Unit
+                | ScalarType::Bool
+                | ScalarType::Char
+                | ScalarType::F32
+                | ScalarType::F64
+                | ScalarType::U8
+                | ScalarType::U16
+                | ScalarType::U32
+                | ScalarType::U64
+                | ScalarType::U128
+                | ScalarType::USize
+                | ScalarType::I8
+                | ScalarType::I16
+                | ScalarType::I32
+                | ScalarType::I64
+                | ScalarType::I128
+                | ScalarType::ISize,
+            ) => Ok(()),
+            _ => Err(ArchiveError::Unsupported { shape }),
+        },
+        Def::Undefined => match shape.ty {
+            Type::User(UserType::Struct(struct_type)) => struct_type
+                .fields
+                .iter()
+                .try_for_each(|field| check(field.shape())),
+            _ => Err(ArchiveError::Unsupported { shape }),
+        },
+        _ => Err(ArchiveError::Unsupported { shape }),
+    }
+}
+
+/// A hash of how a value of `shape` is laid out in memory on this platform
+fn layout_hash(shape: &'static Shape) -> u64 {
+    fn walk(hasher: &mut Fnv1a64, shape: &'static Shape) {
+        let layout = shape.layout.sized_layout().unwrap();
+        hasher.write(&(layout.size() as u64).to_le_bytes());
+        hasher.write(&(layout.align() as u64).to_le_bytes());
+        match (shape.def, shape.ty) {
+            (Def::Array(def), _) => walk(hasher, def.t()),
+            (Def::Undefined, Type::User(UserType::Struct(struct_type))) => {
+                for field in struct_type.fields {
+                    hasher.write(&(field.offset as u64).to_le_bytes());
+                    walk(hasher, field.shape());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut hasher = Fnv1a64::new();
+    hasher.write(&[cfg!(target_endian = "little") as u8]);
+    hasher.write(&(core::mem::size_of::<usize>() as u64).to_le_bytes());
+    walk(&mut hasher, shape);
+    hasher.finish()
+}
+
+/// Copy the value of `shape` at `src` into `out`, field by field so padding
+/// is never read
+///
+/// # Safety
+///
+/// `src` must point to an initialized value of `shape`, which must have
+/// passed [`check`], and `out` must be at least as large as the value.
+unsafe fn write(shape: &'static Shape, src: *const u8, out: &mut [u8]) {
+    match (shape.def, shape.ty) {
+        (Def::Array(def), _) => {
+            let stride = def.t().layout.sized_layout().unwrap().size();
+            for index in 0..def.n {
+                let offset = index * stride;
+                unsafe { write(def.t(), src.add(offset), &mut out[offset..]) };
+            }
+        }
+        (Def::Undefined, Type::User(UserType::Struct(struct_type))) => {
+            for field in struct_type.fields {
+                let offset = field.offset;
+                unsafe { write(field.shape(), src.add(offset), &mut out[offset..]) };
+            }
+        }
+        _ => {
+            let size = shape.layout.sized_layout().unwrap().size();
+            out[..size].copy_from_slice(unsafe { core::slice::from_raw_parts(src, size) });
+        }
+    }
+}
+
+/// Check that the scalars with invalid bit patterns hold valid values
+fn validate(shape: &'static Shape, bytes: &[u8], offset: usize) -> Result<(), ArchiveError> {
+    match (shape.def, shape.ty) {
+        (Def::Array(def), _) => {
+            let stride = def.t().layout.sized_layout().unwrap().size();
+            (0..def.n).try_for_each(|index| {
+                let start = index * stride;
+                validate(def.t(), &bytes[start..], offset + start)
+            })
+        }
+        (Def::Undefined, Type::User(UserType::Struct(struct_type))) => {
+            struct_type.fields.iter().try_for_each(|field| {
+                validate(field.shape(), &bytes[field.offset..], offset + field.offset)
+            })
+        }
+        _ => {
+            let valid = match shape.scalar_type() {
+                Some(ScalarType::Bool) => bytes[0] <= 1,
+                Some(ScalarType::Char) => {
+                    char::from_u32(u32::from_ne_bytes(bytes[..4].try_into().unwrap())).is_some()
+                }
+                _ => true,
+            };
+            if valid {
+                Ok(())
+            } else {
+                Err(ArchiveError::InvalidValue { shape, offset })
+            }
+        }
+    }
+}
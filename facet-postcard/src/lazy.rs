@@ -0,0 +1,626 @@
+//! Lazy, path-based access to postcard data.
+//!
+//! [`LazyValue`] walks encoded bytes using the value's [`Shape`]: stepping into
+//! a field, item or map entry only skips over what precedes it, and nothing is
+//! decoded until you ask for a value. This makes it cheap to pull a few values
+//! out of a large buffer, such as a memory-mapped file, without deserializing
+//! the whole thing.
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_postcard::{LazyValue, to_vec};
+//!
+//! #[derive(Facet)]
+//! struct Archive {
+//!     name: String,
+//!     entries: Vec<Entry>,
+//! }
+//!
+//! #[derive(Facet)]
+//! struct Entry {
+//!     path: String,
+//!     size: u64,
+//! }
+//!
+//! let archive = Archive {
+//!     name: "backup".into(),
+//!     entries: (0..1000)
+//!         .map(|i| Entry { path: format!("file-{i}"), size: i })
+//!         .collect(),
+//! };
+//! let bytes = to_vec(&archive).unwrap();
+//!
+//! let root = LazyValue::new::<Archive>(&bytes);
+//! assert_eq!(root.at("entries").unwrap().len().unwrap(), 1000);
+//! let size: u64 = root.at("entries[998].size").unwrap().deserialize().unwrap();
+//! assert_eq!(size, 998);
+//! ```
+//!
+//! Paths use the syntax of [`Peek::at`](facet_reflect::Peek::at). Fields with
+//! `skip_serializing_if` can't be skipped over, since whether they were written
+//! depends on their value.
+
+use alloc::format;
+use alloc::string::ToString;
+use core::fmt;
+use core::ops::Range;
+
+use facet_core::{Def, Facet, Field, FieldFlags, ScalarType, Shape, Type, UserType, Variant};
+use facet_format::{DeserializeError, FormatDeserializer};
+use facet_reflect::{
+    HeapValue, Partial, PeekPathError, PeekPathErrorKind, PeekPathSegment, PeekPathSegments,
+};
+
+use crate::error::codes;
+use crate::{PostcardError, PostcardParser};
+
+/// A value of a known shape inside a postcard buffer, not yet decoded.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Clone, Copy)]
+pub struct LazyValue<'input> {
+    input: &'input [u8],
+    offset: usize,
+    shape: &'static Shape,
+}
+
+/// Error returned when navigating or decoding a [`LazyValue`].
+#[derive(Debug)]
+pub enum LazyError {
+    /// A path segment names a field, index or key the value doesn't have.
+    Path(PeekPathError),
+    /// The input is malformed, or encodes something that can't be skipped over.
+    Postcard(PostcardError),
+    /// Decoding the value failed.
+    Deserialize(DeserializeError<PostcardError>),
+}
+
+impl fmt::Display for LazyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LazyError::Path(err) => write!(f, "{err}"),
+            LazyError::Postcard(err) => write!(f, "{err}"),
+            LazyError::Deserialize(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for LazyError {}
+
+impl From<PeekPathError> for LazyError {
+    fn from(err: PeekPathError) -> Self {
+        LazyError::Path(err)
+    }
+}
+
+impl From<PostcardError> for LazyError {
+    fn from(err: PostcardError) -> Self {
+        LazyError::Postcard(err)
+    }
+}
+
+impl From<DeserializeError<PostcardError>> for LazyError {
+    fn from(err: DeserializeError<PostcardError>) -> Self {
+        LazyError::Deserialize(err)
+    }
+}
+
+impl<'input> LazyValue<'input> {
+    /// The value of type `T` encoded at the start of `input`.
+    pub fn new<'facet, T: Facet<'facet> + ?Sized>(input: &'input [u8]) -> Self {
+        Self::with_shape(input, T::SHAPE)
+    }
+
+    /// The value of the given shape encoded at the start of `input`.
+    pub fn with_shape(input: &'input [u8], shape: &'static Shape) -> Self {
+        Self {
+            input,
+            offset: 0,
+            shape,
+        }
+    }
+
+    /// The shape of this value.
+    pub fn shape(&self) -> &'static Shape {
+        self.shape
+    }
+
+    /// Where this value starts in the input.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The encoded bytes of this value.
+    pub fn bytes(&self) -> Result<&'input [u8], LazyError> {
+        let mut cursor = self.cursor();
+        cursor.skip(self.shape)?;
+        Ok(&self.input[self.offset..cursor.pos])
+    }
+
+    /// Resolves a path like `entries[3].size` relative to this value.
+    ///
+    /// `Some`, pointers and transparent wrappers are stepped through, as with
+    /// [`Peek::at`](facet_reflect::Peek::at). Map keys are matched against the
+    /// text of each decoded key.
+    pub fn at(&self, path: &str) -> Result<LazyValue<'input>, LazyError> {
+        let mut current = *self;
+        for segment in PeekPathSegments::new(path) {
+            let (segment, range) = segment?;
+            current = current.step(&segment, path, range)?;
+        }
+        Ok(current)
+    }
+
+    /// The struct or enum variant field called `name`.
+    pub fn field(&self, name: &str) -> Result<LazyValue<'input>, LazyError> {
+        self.step(
+            &PeekPathSegment::Field(name.to_string()),
+            name,
+            0..name.len(),
+        )
+    }
+
+    /// The item at `index` of a list, array, set or tuple.
+    pub fn index(&self, index: usize) -> Result<LazyValue<'input>, LazyError> {
+        let text = format!("[{index}]");
+        self.step(&PeekPathSegment::Index(index), &text, 0..text.len())
+    }
+
+    /// The number of items of a list, array or set, or entries of a map.
+    pub fn len(&self) -> Result<usize, LazyError> {
+        match self.shape.def {
+            Def::List(_) | Def::Slice(_) | Def::Set(_) | Def::Map(_) => Ok(self.cursor().len()?),
+            Def::Array(def) => Ok(def.n),
+            _ => Err(self.not_kind("a list, array, set or map")),
+        }
+    }
+
+    /// Returns true if this list, array, set or map has no items.
+    pub fn is_empty(&self) -> Result<bool, LazyError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// The inner value of an `Option`, or `None` if it is `None`.
+    pub fn option(&self) -> Result<Option<LazyValue<'input>>, LazyError> {
+        let Def::Option(def) = self.shape.def else {
+            return Err(self.not_kind("an Option"));
+        };
+        let mut cursor = self.cursor();
+        Ok(cursor.option()?.then(|| cursor.value(def.t())))
+    }
+
+    /// The active variant of an enum.
+    pub fn variant(&self) -> Result<&'static Variant, LazyError> {
+        let Type::User(UserType::Enum(ty)) = self.shape.ty else {
+            return Err(self.not_kind("an enum"));
+        };
+        Ok(self.cursor().variant(ty.variants)?)
+    }
+
+    /// Decodes this value.
+    ///
+    /// The result can be inspected with [`HeapValue::peek`], or turned into a
+    /// concrete type; see also [`Self::deserialize`].
+    pub fn decode(&self) -> Result<HeapValue<'input>, LazyError> {
+        let bytes = self.bytes()?;
+        let wip = Partial::alloc_shape(self.shape)
+            .map_err(|err| LazyError::Deserialize(DeserializeError::reflect(err)))?;
+        let mut deserializer = FormatDeserializer::new(PostcardParser::new(bytes));
+        let wip = deserializer.deserialize_into(wip)?;
+        wip.build()
+            .map_err(|err| LazyError::Deserialize(DeserializeError::reflect(err)))
+    }
+
+    /// Decodes this value as a `T`, which must have this value's shape.
+    pub fn deserialize<T: Facet<'input>>(&self) -> Result<T, LazyError> {
+        if T::SHAPE != self.shape {
+            return Err(LazyError::Deserialize(DeserializeError::TypeMismatch {
+                expected: self.shape.type_identifier,
+                got: T::SHAPE.to_string(),
+                span: None,
+                path: None,
+            }));
+        }
+        self.decode()?
+            .materialize::<T>()
+            .map_err(|err| LazyError::Deserialize(DeserializeError::reflect(err)))
+    }
+
+    fn not_kind(&self, kind: &str) -> LazyError {
+        LazyError::Postcard(PostcardError {
+            code: codes::UNSUPPORTED,
+            pos: self.offset,
+            message: format!("`{}` is not {kind}", self.shape),
+            source_bytes: None,
+        })
+    }
+
+    fn cursor(&self) -> Cursor<'input> {
+        Cursor {
+            input: self.input,
+            pos: self.offset,
+        }
+    }
+
+    /// Steps through pointers, transparent wrappers and `Some`
+    fn unwrap(&self) -> Result<LazyValue<'input>, StepError> {
+        let mut value = *self;
+        loop {
+            let shape = value.shape;
+            value.shape = match (shape.def, shape.ty) {
+                (Def::Pointer(def), _) => def
+                    .pointee()
+                    .ok_or(PeekPathErrorKind::NotTraversable { shape })?,
+                (Def::Option(def), _) => {
+                    let mut cursor = value.cursor();
+                    if !cursor.option()? {
+                        return Err(PeekPathErrorKind::NoneValue { shape }.into());
+                    }
+                    value.offset = cursor.pos;
+                    def.t()
+                }
+                // A transparent struct is encoded as its only field
+                (Def::Undefined, Type::User(UserType::Struct(ty)))
+                    if shape.inner.is_some() && ty.fields.len() == 1 =>
+                {
+                    ty.fields[0].shape()
+                }
+                _ => return Ok(value),
+            };
+        }
+    }
+
+    fn step(
+        &self,
+        segment: &PeekPathSegment,
+        path: &str,
+        range: Range<usize>,
+    ) -> Result<LazyValue<'input>, LazyError> {
+        self.step_inner(segment).map_err(|err| match err {
+            StepError::Path(kind) => PeekPathError::new(path, range, kind).into(),
+            StepError::Postcard(err) => err.into(),
+            StepError::Lazy(err) => err,
+        })
+    }
+
+    fn step_inner(&self, segment: &PeekPathSegment) -> Result<LazyValue<'input>, StepError> {
+        let value = self.unwrap()?;
+        let shape = value.shape;
+        let mut cursor = value.cursor();
+
+        if let Def::Map(def) = shape.def {
+            let key = match segment {
+                PeekPathSegment::Field(key) | PeekPathSegment::Key(key) => key.clone(),
+                PeekPathSegment::Index(index) => index.to_string(),
+            };
+            for _ in 0..cursor.len()? {
+                let entry_key = cursor.value(def.k());
+                cursor.skip(def.k())?;
+                if key_matches(&entry_key, &key)? {
+                    return Ok(cursor.value(def.v()));
+                }
+                cursor.skip(def.v())?;
+            }
+            return Err(PeekPathErrorKind::NoSuchField { shape }.into());
+        }
+
+        match segment {
+            PeekPathSegment::Field(name) => {
+                let fields = match shape.ty {
+                    Type::User(UserType::Struct(ty)) => ty.fields,
+                    Type::User(UserType::Enum(ty)) => cursor.variant(ty.variants)?.data.fields,
+                    _ => return Err(PeekPathErrorKind::NotTraversable { shape }.into()),
+                };
+                cursor
+                    .find_field(fields, |_, field| field.name == name)?
+                    .ok_or(PeekPathErrorKind::NoSuchField { shape }.into())
+            }
+            PeekPathSegment::Index(index) => {
+                let (item, len) = match shape.def {
+                    Def::List(def) => (def.t(), cursor.len()?),
+                    Def::Slice(def) => (def.t(), cursor.len()?),
+                    Def::Set(def) => (def.t(), cursor.len()?),
+                    Def::Array(def) => (def.t(), def.n),
+                    _ => {
+                        // Tuples and tuple structs name their fields `0`, `1`, ...
+                        let Type::User(UserType::Struct(ty)) = shape.ty else {
+                            return Err(PeekPathErrorKind::NotTraversable { shape }.into());
+                        };
+                        return cursor.find_field(ty.fields, |i, _| i == *index)?.ok_or(
+                            PeekPathErrorKind::IndexOutOfBounds {
+                                index: *index,
+                                len: ty.fields.len(),
+                            }
+                            .into(),
+                        );
+                    }
+                };
+                if *index >= len {
+                    return Err(PeekPathErrorKind::IndexOutOfBounds { index: *index, len }.into());
+                }
+                cursor.skip_items(item, *index)?;
+                Ok(cursor.value(item))
+            }
+            PeekPathSegment::Key(_) => Err(PeekPathErrorKind::NotTraversable { shape }.into()),
+        }
+    }
+}
+
+impl fmt::Debug for LazyValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyValue")
+            .field("shape", &self.shape)
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+/// Whether the decoded map key has the text `key`
+fn key_matches(entry_key: &LazyValue<'_>, key: &str) -> Result<bool, LazyError> {
+    let decoded = entry_key.decode()?;
+    let peek = decoded.peek().innermost_peek();
+    Ok(match peek.as_str() {
+        Some(s) => s == key,
+        None => peek.shape().is_display() && peek.to_string() == key,
+    })
+}
+
+enum StepError {
+    Path(PeekPathErrorKind),
+    Postcard(PostcardError),
+    Lazy(LazyError),
+}
+
+impl From<PeekPathErrorKind> for StepError {
+    fn from(kind: PeekPathErrorKind) -> Self {
+        StepError::Path(kind)
+    }
+}
+
+impl From<PostcardError> for StepError {
+    fn from(err: PostcardError) -> Self {
+        StepError::Postcard(err)
+    }
+}
+
+impl From<LazyError> for StepError {
+    fn from(err: LazyError) -> Self {
+        StepError::Lazy(err)
+    }
+}
+
+/// Reads and skips over encoded values, mirroring the serializer
+struct Cursor<'input> {
+    input: &'input [u8],
+    pos: usize,
+}
+
+impl<'input> Cursor<'input> {
+    fn value(&self, shape: &'static Shape) -> LazyValue<'input> {
+        LazyValue {
+            input: self.input,
+            offset: self.pos,
+            shape,
+        }
+    }
+
+    fn error(&self, code: i32) -> PostcardError {
+        PostcardError::from_code(code, self.pos)
+    }
+
+    fn unsupported(&self, shape: &Shape, reason: &str) -> PostcardError {
+        PostcardError {
+            code: codes::UNSUPPORTED,
+            pos: self.pos,
+            message: format!("cannot skip over `{shape}`: {reason}"),
+            source_bytes: None,
+        }
+    }
+
+    fn byte(&mut self) -> Result<u8, PostcardError> {
+        let byte = *self
+            .input
+            .get(self.pos)
+            .ok_or(self.error(codes::UNEXPECTED_EOF))?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn advance(&mut self, len: usize) -> Result<(), PostcardError> {
+        if len > self.input.len() - self.pos {
+            return Err(self.error(codes::UNEXPECTED_END_OF_INPUT));
+        }
+        self.pos += len;
+        Ok(())
+    }
+
+    fn varint(&mut self) -> Result<u64, PostcardError> {
+        let mut result = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            result |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+        Err(self.error(codes::VARINT_OVERFLOW))
+    }
+
+    fn skip_varint(&mut self) -> Result<(), PostcardError> {
+        // A u128 takes at most 19 bytes
+        for _ in 0..19 {
+            if self.byte()? & 0x80 == 0 {
+                return Ok(());
+            }
+        }
+        Err(self.error(codes::VARINT_OVERFLOW))
+    }
+
+    fn len(&mut self) -> Result<usize, PostcardError> {
+        let len = self.varint()?;
+        usize::try_from(len).map_err(|_| self.error(codes::VARINT_OVERFLOW))
+    }
+
+    fn option(&mut self) -> Result<bool, PostcardError> {
+        match self.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => {
+                self.pos -= 1;
+                Err(self.error(codes::INVALID_OPTION_DISCRIMINANT))
+            }
+        }
+    }
+
+    /// Reads an enum's variant index; the cursor is left on its fields
+    fn variant(&mut self, variants: &'static [Variant]) -> Result<&'static Variant, PostcardError> {
+        let start = self.pos;
+        let index = self.varint()?;
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| variants.get(index))
+            .ok_or(PostcardError::from_code(
+                codes::INVALID_ENUM_DISCRIMINANT,
+                start,
+            ))
+    }
+
+    fn skip(&mut self, shape: &'static Shape) -> Result<(), PostcardError> {
+        match (shape.def, shape.ty) {
+            (Def::Scalar, _) => self.skip_scalar(shape),
+            (Def::List(def), _) => {
+                let len = self.len()?;
+                self.skip_items(def.t(), len)
+            }
+            (Def::Slice(def), _) => {
+                let len = self.len()?;
+                self.skip_items(def.t(), len)
+            }
+            (Def::Set(def), _) => {
+                let len = self.len()?;
+                self.skip_items(def.t(), len)
+            }
+            (Def::Array(def), _) => self.skip_items(def.t(), def.n),
+            (Def::Map(def), _) => {
+                for _ in 0..self.len()? {
+                    self.skip(def.k())?;
+                    self.skip(def.v())?;
+                }
+                Ok(())
+            }
+            (Def::Option(def), _) => {
+                if self.option()? {
+                    self.skip(def.t())?;
+                }
+                Ok(())
+            }
+            (Def::Result(def), _) => match self.varint()? {
+                0 => self.skip(def.t()),
+                1 => self.skip(def.e()),
+                _ => Err(self.error(codes::INVALID_ENUM_DISCRIMINANT)),
+            },
+            (Def::Pointer(def), _) => match def.pointee() {
+                Some(pointee) => self.skip(pointee),
+                None => Err(self.unsupported(shape, "the pointee is unknown")),
+            },
+            (_, Type::User(UserType::Struct(ty))) => self.skip_fields(ty.fields),
+            (_, Type::User(UserType::Enum(ty))) => {
+                let variant = self.variant(ty.variants)?;
+                self.skip_fields(variant.data.fields)
+            }
+            // `&str` and `&[u8]`
+            (_, Type::Pointer(_)) => self.skip_len_prefixed(),
+            _ => Err(self.unsupported(shape, "it has no postcard encoding")),
+        }
+    }
+
+    fn skip_items(&mut self, item: &'static Shape, count: usize) -> Result<(), PostcardError> {
+        if item.is_type::<u8>() {
+            return self.advance(count);
+        }
+        for _ in 0..count {
+            self.skip(item)?;
+        }
+        Ok(())
+    }
+
+    fn skip_fields(&mut self, fields: &'static [Field]) -> Result<(), PostcardError> {
+        self.find_field(fields, |_, _| false).map(|_| ())
+    }
+
+    /// Skips over fields until one matches, leaving the cursor on it
+    fn find_field(
+        &mut self,
+        fields: &'static [Field],
+        matches: impl Fn(usize, &Field) -> bool,
+    ) -> Result<Option<LazyValue<'input>>, PostcardError> {
+        for (index, field) in fields.iter().enumerate() {
+            if field.flags.contains(FieldFlags::SKIP)
+                || field.flags.contains(FieldFlags::SKIP_SERIALIZING)
+            {
+                continue;
+            }
+            if field.skip_serializing_if.is_some() {
+                return Err(self.unsupported(
+                    field.shape(),
+                    "its field has `skip_serializing_if`, so it may not have been written",
+                ));
+            }
+            if matches(index, field) {
+                return Ok(Some(self.value(field.shape())));
+            }
+            self.skip(field.shape())?;
+        }
+        Ok(None)
+    }
+
+    fn skip_len_prefixed(&mut self) -> Result<(), PostcardError> {
+        let len = self.len()?;
+        self.advance(len)
+    }
+
+    fn skip_scalar(&mut self, shape: &'static Shape) -> Result<(), PostcardError> {
+        #[cfg(any(feature = "uuid", feature = "ulid"))]
+        if matches!(shape.type_identifier, "Uuid" | "Ulid") {
+            return self.advance(16);
+        }
+        #[cfg(feature = "ordered-float")]
+        if shape.type_identifier == "NotNan"
+            && let Some(inner) = shape.inner
+        {
+            return self.skip_scalar(inner);
+        }
+
+        match shape.scalar_type() {
+            Some(ScalarType::Unit) => Ok(()),
+            Some(ScalarType::Bool | ScalarType::U8 | ScalarType::I8) => self.advance(1),
+            Some(ScalarType::F32) => self.advance(4),
+            Some(ScalarType::F64) => self.advance(8),
+            Some(
+                ScalarType::U16
+                | ScalarType::U32
+                | ScalarType::U64
+                | ScalarType::U128
+                | ScalarType::USize
+                | ScalarType::I16
+                | ScalarType::I32
+                | ScalarType::I64
+                | ScalarType::I128
+                | ScalarType::ISize,
+            ) => self.skip_varint(),
+            Some(ScalarType::Char | ScalarType::Str | ScalarType::String | ScalarType::CowStr) => {
+                self.skip_len_prefixed()
+            }
+            #[cfg(feature = "net")]
+            Some(
+                ScalarType::SocketAddr
+                | ScalarType::IpAddr
+                | ScalarType::Ipv4Addr
+                | ScalarType::Ipv6Addr,
+            ) => self.skip_len_prefixed(),
+            Some(_) => Err(self.unsupported(shape, "it has no postcard encoding")),
+            // Other scalars are written as strings
+            None => self.skip_len_prefixed(),
+        }
+    }
+}
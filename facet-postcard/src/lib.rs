@@ -38,15 +38,37 @@
 //! - **Tier-0 (Reflection)**: Fallback for all other types (nested enums, complex types)
 //!
 //! This ensures all `Facet` types can be deserialized.
+//!
+//! # Lazy access
+//!
+//! [`LazyValue`] reads single values out of postcard bytes by path, skipping
+//! over the rest instead of deserializing it.
+//!
+//! # RPC
+//!
+//! The [`rpc`] module frames method calls as postcard envelopes, checking on
+//! both ends that the payload shapes match.
+//!
+//! # Archives
+//!
+//! With the `archive` feature, the [`archive`] module writes plain-data values
+//! in their in-memory layout, so they can be read back from a buffer or a
+//! memory-mapped file without deserializing them.
 
-#![cfg_attr(not(feature = "jit"), forbid(unsafe_code))]
+#![cfg_attr(not(any(feature = "jit", feature = "archive")), forbid(unsafe_code))]
 
 extern crate alloc;
 
 mod error;
+mod lazy;
 mod parser;
 mod serialize;
 
+pub mod rpc;
+
+#[cfg(feature = "archive")]
+pub mod archive;
+
 #[cfg(feature = "jit")]
 pub mod jit;
 
@@ -58,6 +80,7 @@ pub use axum::{Postcard, PostcardRejection, PostcardSerializeRejection};
 pub use error::{PostcardError, SerializeError};
 #[cfg(feature = "jit")]
 pub use jit::PostcardJitFormat;
+pub use lazy::{LazyError, LazyValue};
 pub use parser::PostcardParser;
 pub use serialize::{Writer, peek_to_vec, to_vec, to_writer_fallible};
 
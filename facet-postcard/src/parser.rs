@@ -851,6 +851,10 @@ impl<'de> FormatParser<'de> for PostcardParser<'de> {
         false
     }
 
+    fn compresses_fields(&self) -> bool {
+        true
+    }
+
     fn hint_struct_fields(&mut self, num_fields: usize) {
         self.pending_struct_fields = Some(num_fields);
         // Clear any peeked OrderedField placeholder for sequences
@@ -867,6 +871,17 @@ impl<'de> FormatParser<'de> for PostcardParser<'de> {
         }
     }
 
+    fn container_len(&self) -> Option<usize> {
+        // Right after a start event, its length prefix is on top of the stack
+        match self.current_state() {
+            ParserState::InSequence { remaining_elements } => {
+                usize::try_from(*remaining_elements).ok()
+            }
+            ParserState::InMap { remaining_entries } => usize::try_from(*remaining_entries).ok(),
+            _ => None,
+        }
+    }
+
     fn hint_sequence(&mut self) {
         self.pending_sequence = true;
         // Clear any peeked OrderedField placeholder
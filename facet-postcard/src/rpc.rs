@@ -0,0 +1,458 @@
+//! Request/response envelopes for simple RPC over postcard.
+//!
+//! A request carries a method name, the [`fingerprint`] of the payload's
+//! shape and the postcard-encoded payload. [`RpcServer`] dispatches requests
+//! to handlers by method name, after checking that the client and the server
+//! agree on the payload's shape; responses are checked the same way by
+//! [`decode_response`]. This is enough for IPC between processes built from
+//! the same types, without a schema language.
+//!
+//! # Example
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_postcard::rpc::{RpcServer, decode_response, encode_request};
+//!
+//! #[derive(Facet)]
+//! struct Add {
+//!     a: i32,
+//!     b: i32,
+//! }
+//!
+//! let server = RpcServer::new().handle("add", |req: Add| req.a + req.b);
+//!
+//! let request = encode_request("add", &Add { a: 2, b: 3 }).unwrap();
+//! let response = server.dispatch(&request);
+//! assert_eq!(decode_response::<i32>(&response).unwrap(), 5);
+//! ```
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use facet_core::{Def, Facet, Field, FieldFlags, Fnv1a64, Shape, Type, UserType};
+
+use crate::{from_slice, to_vec};
+
+/// Response tags
+const OK: u8 = 0;
+const UNKNOWN_METHOD: u8 = 1;
+const SHAPE_MISMATCH: u8 = 2;
+const PAYLOAD: u8 = 3;
+const MALFORMED: u8 = 4;
+
+/// A structural hash of a shape
+///
+/// Two shapes have the same fingerprint if they have the same type names,
+/// field and variant names, proxies, and the same fingerprints for all of
+/// their fields, variants and elements, in the same order. Fields must also
+/// agree on `skip`, `skip_serializing`, `skip_deserializing` and `flatten`.
+/// Since the postcard encoding depends on exactly these, values of shapes
+/// with equal fingerprints can be exchanged safely. Fingerprints are stable
+/// across builds and platforms.
+pub fn fingerprint(shape: &'static Shape) -> u64 {
+    let mut hasher = Fingerprinter::new();
+    hasher.shape(shape);
+    hasher.finish()
+}
+
+/// FNV-1a over a canonical walk of a shape
+struct Fingerprinter {
+    hasher: Fnv1a64,
+    /// Shapes being hashed, to handle recursive types
+    stack: Vec<&'static Shape>,
+}
+
+impl Fingerprinter {
+    fn new() -> Self {
+        Self {
+            hasher: Fnv1a64::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes);
+    }
+
+    fn str(&mut self, s: &str) {
+        self.bytes(&(s.len() as u64).to_le_bytes());
+        self.bytes(s.as_bytes());
+    }
+
+    fn shape(&mut self, shape: &'static Shape) {
+        if let Some(depth) = self.stack.iter().position(|&s| s == shape) {
+            self.str("<recursive>");
+            self.bytes(&(depth as u64).to_le_bytes());
+            return;
+        }
+        self.stack.push(shape);
+
+        self.str(shape.type_identifier);
+        for param in shape.type_params {
+            self.shape(param.shape);
+        }
+        #[cfg(feature = "alloc")]
+        self.proxy(shape.proxy.map(|proxy| proxy.shape));
+        match shape.def {
+            Def::Map(def) => {
+                self.str("map");
+                self.shape(def.k());
+                self.shape(def.v());
+            }
+            Def::Set(def) => {
+                self.str("set");
+                self.shape(def.t());
+            }
+            Def::List(def) => {
+                self.str("list");
+                self.shape(def.t());
+            }
+            Def::Array(def) => {
+                self.str("array");
+                self.bytes(&(def.n as u64).to_le_bytes());
+                self.shape(def.t());
+            }
+            Def::Slice(def) => {
+                self.str("slice");
+                self.shape(def.t());
+            }
+            Def::Option(def) => {
+                self.str("option");
+                self.shape(def.t());
+            }
+            Def::Result(def) => {
+                self.str("result");
+                self.shape(def.t());
+                self.shape(def.e());
+            }
+            Def::Pointer(def) => {
+                self.str("pointer");
+                if let Some(pointee) = def.pointee() {
+                    self.shape(pointee);
+                }
+            }
+            _ => match shape.ty {
+                Type::User(UserType::Struct(struct_type)) => {
+                    self.str("struct");
+                    self.fields(struct_type.fields);
+                }
+                Type::User(UserType::Enum(enum_type)) => {
+                    self.str("enum");
+                    for variant in enum_type.variants {
+                        self.str(variant.name);
+                        self.fields(variant.data.fields);
+                    }
+                }
+                _ => {
+                    if let Some(inner) = shape.inner {
+                        self.str("inner");
+                        self.shape(inner);
+                    }
+                }
+            },
+        }
+
+        self.stack.pop();
+    }
+
+    fn fields(&mut self, fields: &'static [Field]) {
+        self.bytes(&(fields.len() as u64).to_le_bytes());
+        for field in fields {
+            self.str(field.name);
+            // These decide whether and where the field is encoded
+            let flags = field.flags
+                & (FieldFlags::SKIP
+                    | FieldFlags::SKIP_SERIALIZING
+                    | FieldFlags::SKIP_DESERIALIZING
+                    | FieldFlags::FLATTEN);
+            self.bytes(&flags.bits().to_le_bytes());
+            #[cfg(feature = "alloc")]
+            self.proxy(field.proxy_shape());
+            self.shape(field.shape());
+        }
+    }
+
+    /// A proxy is encoded in place of the value it stands in for
+    #[cfg(feature = "alloc")]
+    fn proxy(&mut self, proxy: Option<&'static Shape>) {
+        match proxy {
+            Some(proxy) => {
+                self.bytes(&[1]);
+                self.shape(proxy);
+            }
+            None => self.bytes(&[0]),
+        }
+    }
+}
+
+/// An error from encoding, dispatching or decoding an RPC envelope
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RpcError {
+    /// The envelope is truncated or corrupt
+    Malformed,
+    /// The server has no handler for this method
+    UnknownMethod {
+        /// The requested method
+        method: String,
+    },
+    /// The payload was encoded from a different shape than the one expected
+    ShapeMismatch {
+        /// The method of the request
+        method: String,
+        /// The fingerprint of the expected shape
+        expected: u64,
+        /// The fingerprint of the payload's shape
+        found: u64,
+    },
+    /// The payload could not be serialized or deserialized
+    Payload {
+        /// The underlying error message
+        message: String,
+    },
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RpcError::Malformed => write!(f, "malformed RPC envelope"),
+            RpcError::UnknownMethod { method } => write!(f, "unknown RPC method `{method}`"),
+            RpcError::ShapeMismatch {
+                method,
+                expected,
+                found,
+            } => write!(
+                f,
+                "shape mismatch for RPC method `{method}`: expected fingerprint {expected:016x}, found {found:016x}"
+            ),
+            RpcError::Payload { message } => write!(f, "invalid RPC payload: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+/// A decoded request envelope, borrowing from the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Request<'a> {
+    /// The method name
+    pub method: &'a str,
+    /// The [`fingerprint`] of the payload's shape
+    pub fingerprint: u64,
+    /// The postcard-encoded payload
+    pub payload: &'a [u8],
+}
+
+impl<'a> Request<'a> {
+    /// Decode a request envelope, without decoding its payload.
+    pub fn decode(bytes: &'a [u8]) -> Result<Self, RpcError> {
+        let mut reader = Reader(bytes);
+        let method = reader.str()?;
+        let fingerprint = reader.u64()?;
+        Ok(Self {
+            method,
+            fingerprint,
+            payload: reader.0,
+        })
+    }
+
+    /// Decode the payload, after checking its fingerprint against `T`.
+    pub fn payload<T: Facet<'static>>(&self) -> Result<T, RpcError> {
+        let expected = fingerprint(T::SHAPE);
+        if self.fingerprint != expected {
+            return Err(RpcError::ShapeMismatch {
+                method: self.method.to_string(),
+                expected,
+                found: self.fingerprint,
+            });
+        }
+        from_slice(self.payload).map_err(payload_error)
+    }
+}
+
+/// Encode a request envelope calling `method` with `payload`.
+pub fn encode_request<T: Facet<'static>>(method: &str, payload: &T) -> Result<Vec<u8>, RpcError> {
+    let mut out = Vec::new();
+    write_str(&mut out, method);
+    out.extend_from_slice(&fingerprint(T::SHAPE).to_le_bytes());
+    out.extend(to_vec(payload).map_err(payload_error)?);
+    Ok(out)
+}
+
+/// Decode a response envelope produced by [`RpcServer::dispatch`].
+///
+/// Errors reported by the server are returned as the matching [`RpcError`].
+pub fn decode_response<T: Facet<'static>>(bytes: &[u8]) -> Result<T, RpcError> {
+    let mut reader = Reader(bytes);
+    match reader.u8()? {
+        OK => {
+            let found = reader.u64()?;
+            let expected = fingerprint(T::SHAPE);
+            if found != expected {
+                return Err(RpcError::ShapeMismatch {
+                    method: String::new(),
+                    expected,
+                    found,
+                });
+            }
+            from_slice(reader.0).map_err(payload_error)
+        }
+        UNKNOWN_METHOD => Err(RpcError::UnknownMethod {
+            method: reader.str()?.to_string(),
+        }),
+        SHAPE_MISMATCH => Err(RpcError::ShapeMismatch {
+            method: reader.str()?.to_string(),
+            expected: reader.u64()?,
+            found: reader.u64()?,
+        }),
+        PAYLOAD => Err(RpcError::Payload {
+            message: reader.str()?.to_string(),
+        }),
+        MALFORMED => Err(RpcError::Malformed),
+        _ => Err(RpcError::Malformed),
+    }
+}
+
+type Handler = Box<dyn Fn(Request<'_>) -> Result<Vec<u8>, RpcError> + Send + Sync>;
+
+/// Dispatches request envelopes to handlers by method name
+#[derive(Default)]
+pub struct RpcServer {
+    handlers: BTreeMap<String, Handler>,
+}
+
+impl RpcServer {
+    /// A server without any handler
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `handler` for `method`, replacing any previous handler.
+    pub fn handle<Req, Resp, F>(mut self, method: impl Into<String>, handler: F) -> Self
+    where
+        Req: Facet<'static>,
+        Resp: Facet<'static>,
+        F: Fn(Req) -> Resp + Send + Sync + 'static,
+    {
+        let handler: Handler = Box::new(move |request| {
+            let response = handler(request.payload::<Req>()?);
+            let mut out = Vec::from([OK]);
+            out.extend_from_slice(&fingerprint(Resp::SHAPE).to_le_bytes());
+            out.extend(to_vec(&response).map_err(payload_error)?);
+            Ok(out)
+        });
+        self.handlers.insert(method.into(), handler);
+        self
+    }
+
+    /// Whether a handler is registered for `method`
+    pub fn has_method(&self, method: &str) -> bool {
+        self.handlers.contains_key(method)
+    }
+
+    /// Handle a request envelope, returning the response envelope.
+    ///
+    /// Errors are encoded in the response, for [`decode_response`] to return.
+    pub fn dispatch(&self, request: &[u8]) -> Vec<u8> {
+        let result =
+            Request::decode(request).and_then(|request| match self.handlers.get(request.method) {
+                Some(handler) => handler(request),
+                None => Err(RpcError::UnknownMethod {
+                    method: request.method.to_string(),
+                }),
+            });
+        result.unwrap_or_else(|error| encode_error(&error))
+    }
+}
+
+impl fmt::Debug for RpcServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcServer")
+            .field("methods", &self.handlers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+fn payload_error(error: impl fmt::Display) -> RpcError {
+    RpcError::Payload {
+        message: error.to_string(),
+    }
+}
+
+fn encode_error(error: &RpcError) -> Vec<u8> {
+    let mut out = Vec::new();
+    match error {
+        RpcError::Malformed => out.push(MALFORMED),
+        RpcError::UnknownMethod { method } => {
+            out.push(UNKNOWN_METHOD);
+            write_str(&mut out, method);
+        }
+        RpcError::ShapeMismatch {
+            method,
+            expected,
+            found,
+        } => {
+            out.push(SHAPE_MISMATCH);
+            write_str(&mut out, method);
+            out.extend_from_slice(&expected.to_le_bytes());
+            out.extend_from_slice(&found.to_le_bytes());
+        }
+        RpcError::Payload { message } => {
+            out.push(PAYLOAD);
+            write_str(&mut out, message);
+        }
+    }
+    out
+}
+
+/// Writes a string the way postcard does: varint length, then UTF-8 bytes.
+fn write_str(out: &mut Vec<u8>, s: &str) {
+    let mut len = s.len();
+    while len >= 0x80 {
+        out.push((len as u8) | 0x80);
+        len >>= 7;
+    }
+    out.push(len as u8);
+    out.extend_from_slice(s.as_bytes());
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], RpcError> {
+        if self.0.len() < n {
+            return Err(RpcError::Malformed);
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, RpcError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u64(&mut self) -> Result<u64, RpcError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn str(&mut self) -> Result<&'a str, RpcError> {
+        let mut len = 0usize;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.u8()?;
+            len |= usize::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                let bytes = self.take(len)?;
+                return core::str::from_utf8(bytes).map_err(|_| RpcError::Malformed);
+            }
+        }
+        Err(RpcError::Malformed)
+    }
+}
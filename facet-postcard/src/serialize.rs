@@ -14,7 +14,8 @@ use alloc::borrow::Cow;
 use alloc::vec::Vec;
 
 use facet_core::{Def, StructKind, Type, UserType};
-use facet_reflect::{HasFields, Peek};
+use facet_format::compression;
+use facet_reflect::{FieldItem, HasFields, Peek};
 
 use crate::error::SerializeError;
 
@@ -350,8 +351,8 @@ fn serialize_value<W: Writer>(peek: Peek<'_, '_>, writer: &mut W) -> Result<(),
                     let ps = peek.into_struct().map_err(|e| {
                         SerializeError::Custom(alloc::format!("Failed to convert to struct: {}", e))
                     })?;
-                    for (_, field_value) in ps.fields_for_serialize() {
-                        serialize_value(field_value, writer)?;
+                    for (field_item, field_value) in ps.fields_for_serialize() {
+                        serialize_field(&field_item, field_value, writer)?;
                     }
                     Ok(())
                 }
@@ -378,8 +379,8 @@ fn serialize_value<W: Writer>(peek: Peek<'_, '_>, writer: &mut W) -> Result<(),
                 Ok(())
             } else {
                 // Serialize fields in order
-                for (_, field_value) in pe.fields_for_serialize() {
-                    serialize_value(field_value, writer)?;
+                for (field_item, field_value) in pe.fields_for_serialize() {
+                    serialize_field(&field_item, field_value, writer)?;
                 }
                 Ok(())
             }
@@ -408,6 +409,50 @@ fn serialize_value<W: Writer>(peek: Peek<'_, '_>, writer: &mut W) -> Result<(),
     }
 }
 
+/// Serialize the value of a struct or variant field, compressing it if the
+/// field is marked `#[facet(compress = "...")]`.
+fn serialize_field<W: Writer>(
+    field_item: &FieldItem,
+    value: Peek<'_, '_>,
+    writer: &mut W,
+) -> Result<(), SerializeError> {
+    match field_item.field.and_then(|field| field.compression()) {
+        Some(codec) => serialize_compressed(field_item.name.as_ref(), codec, value, writer),
+        None => serialize_value(value, writer),
+    }
+}
+
+/// Serialize a compressed field as the length-prefixed compressed bytes of
+/// its contents, possibly inside `Option`s.
+fn serialize_compressed<W: Writer>(
+    name: &str,
+    codec: &str,
+    value: Peek<'_, '_>,
+    writer: &mut W,
+) -> Result<(), SerializeError> {
+    if let Ok(opt) = value.into_option() {
+        return match opt.value() {
+            Some(inner) => {
+                writer.write_byte(1)?; // Some
+                serialize_compressed(name, codec, inner, writer)
+            }
+            None => writer.write_byte(0), // None
+        };
+    }
+
+    let data = compression::field_bytes(value).ok_or_else(|| {
+        SerializeError::Custom(alloc::format!(
+            "compressed field `{name}` must be a String or a Vec<u8>, not {}",
+            value.shape()
+        ))
+    })?;
+    let compressed = compression::compress(codec, data).map_err(|err| {
+        SerializeError::Custom(alloc::format!("failed to compress field `{name}`: {err}"))
+    })?;
+    write_varint(compressed.len() as u64, writer)?;
+    writer.write_bytes(&compressed)
+}
+
 /// Serialize a scalar value with type-precise encoding.
 fn serialize_scalar<W: Writer>(peek: Peek<'_, '_>, writer: &mut W) -> Result<(), SerializeError> {
     use facet_reflect::ScalarType;
@@ -0,0 +1,113 @@
+#![cfg(feature = "archive")]
+
+use facet::Facet;
+use facet_postcard::archive::{self, ArchiveError};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(C)]
+struct Reading {
+    sensor: u16,
+    position: Point,
+    samples: [u32; 3],
+    flagged: bool,
+    unit: char,
+}
+
+#[test]
+fn roundtrip_borrows_from_the_input() {
+    let reading = Reading {
+        sensor: 7,
+        position: Point { x: 1.5, y: -2.0 },
+        samples: [10, 20, 30],
+        flagged: true,
+        unit: 'µ',
+    };
+    let bytes = archive::to_vec(&reading).unwrap();
+    let archived: &Reading = archive::access(&bytes).unwrap();
+    assert_eq!(archived, &reading);
+    assert!(
+        bytes
+            .as_ptr_range()
+            .contains(&(archived as *const Reading).cast())
+    );
+}
+
+#[test]
+fn padding_is_zeroed() {
+    let reading = Reading {
+        sensor: 7,
+        position: Point { x: 1.5, y: -2.0 },
+        samples: [10, 20, 30],
+        flagged: true,
+        unit: 'µ',
+    };
+    let bytes = archive::to_vec(&reading).unwrap();
+    // `sensor` is followed by padding up to the 8-byte aligned `position`
+    let start = bytes.len() - core::mem::size_of::<Reading>();
+    assert_eq!(&bytes[start + 2..start + 8], &[0; 6]);
+}
+
+#[test]
+fn rejects_other_shapes() {
+    let bytes = archive::to_vec(&Point { x: 1.0, y: 2.0 }).unwrap();
+    assert!(matches!(
+        archive::access::<[f64; 2]>(&bytes),
+        Err(ArchiveError::ShapeMismatch { .. })
+    ));
+    assert_eq!(
+        archive::access::<Point>(&bytes[..bytes.len() - 1]),
+        Err(ArchiveError::Truncated)
+    );
+    assert_eq!(
+        archive::access::<Point>(b"not an archive, just text"),
+        Err(ArchiveError::BadMagic)
+    );
+}
+
+#[test]
+fn rejects_invalid_values() {
+    let reading = Reading {
+        sensor: 7,
+        position: Point { x: 1.5, y: -2.0 },
+        samples: [10, 20, 30],
+        flagged: true,
+        unit: 'µ',
+    };
+    let mut bytes = archive::to_vec(&reading).unwrap();
+    let start = bytes.len() - core::mem::size_of::<Reading>();
+    let offset = core::mem::offset_of!(Reading, flagged);
+    bytes[start + offset] = 2;
+    assert_eq!(
+        archive::access::<Reading>(&bytes),
+        Err(ArchiveError::InvalidValue {
+            shape: bool::SHAPE,
+            offset,
+        })
+    );
+}
+
+#[test]
+fn rejects_types_that_are_not_plain_data() {
+    #[derive(Facet)]
+    struct Named {
+        id: u32,
+        name: String,
+    }
+
+    let named = Named {
+        id: 1,
+        name: "one".into(),
+    };
+    assert_eq!(
+        archive::to_vec(&named).err(),
+        Some(ArchiveError::Unsupported {
+            shape: String::SHAPE
+        })
+    );
+}
@@ -0,0 +1,38 @@
+//! Length prefixes are used to preallocate collections.
+
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_postcard::{from_slice, to_vec};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Batch {
+    samples: Vec<u32>,
+    labels: HashMap<u32, String>,
+}
+
+#[test]
+fn test_collections_are_preallocated() {
+    let batch = Batch {
+        samples: (0..1000).collect(),
+        labels: (0..100).map(|i| (i, i.to_string())).collect(),
+    };
+    let bytes = to_vec(&batch).unwrap();
+
+    let decoded: Batch = from_slice(&bytes).unwrap();
+    assert_eq!(decoded, batch);
+    // Growing one push at a time would have left room for 1024
+    assert_eq!(decoded.samples.capacity(), 1000);
+    assert!(decoded.labels.capacity() >= 100);
+}
+
+#[test]
+fn test_bogus_length_prefix() {
+    // A length of u64::MAX followed by a single element: preallocating what
+    // the prefix claims would abort, so this must fail with a plain error
+    let mut bytes = vec![0xff; 9];
+    bytes.push(0x01);
+    bytes.push(0x01);
+
+    assert!(from_slice::<Vec<u64>>(&bytes).is_err());
+}
@@ -0,0 +1,77 @@
+//! Fields marked `#[facet(compress = "zstd")]` are written compressed.
+
+use facet::Facet;
+use facet_format::{DeserializeError, DeserializeLimits, FormatDeserializer, compression};
+use facet_postcard::{PostcardParser, from_slice, to_vec};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Attachment {
+    name: String,
+    #[facet(compress = "zstd")]
+    body: Vec<u8>,
+    #[facet(compress = "zstd")]
+    caption: Option<String>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct PlainAttachment {
+    name: String,
+    body: Vec<u8>,
+    caption: Option<Vec<u8>>,
+}
+
+#[test]
+fn test_compressed_fields_roundtrip() {
+    let value = Attachment {
+        name: "log.txt".into(),
+        body: b"GET /health 200\n".repeat(500),
+        caption: Some("request log ".repeat(100)),
+    };
+    let bytes = to_vec(&value).unwrap();
+    assert!(bytes.len() < 500, "{} bytes", bytes.len());
+
+    let decoded: Attachment = from_slice(&bytes).unwrap();
+    assert_eq!(decoded, value);
+
+    let none = Attachment {
+        name: "log.txt".into(),
+        body: b"GET /health 200\n".repeat(500),
+        caption: None,
+    };
+    let decoded: Attachment = from_slice(&to_vec(&none).unwrap()).unwrap();
+    assert_eq!(decoded, none);
+}
+
+#[test]
+fn test_compressed_field_is_length_prefixed_bytes() {
+    let value = Attachment {
+        name: "log.txt".into(),
+        body: b"GET /health 200\n".repeat(500),
+        caption: None,
+    };
+    let bytes = to_vec(&value).unwrap();
+
+    // Without the attribute, the compressed data reads back as plain bytes
+    let raw: PlainAttachment = from_slice(&bytes).unwrap();
+    assert_eq!(raw.name, "log.txt");
+    let body = compression::decompress("zstd", &raw.body, usize::MAX).unwrap();
+    assert_eq!(body, value.body);
+}
+
+#[test]
+fn test_decompressed_size_is_limited() {
+    let value = Attachment {
+        name: "log.txt".into(),
+        body: b"GET /health 200\n".repeat(500),
+        caption: None,
+    };
+    let bytes = to_vec(&value).unwrap();
+    let result: Result<Attachment, _> = FormatDeserializer::new_owned(PostcardParser::new(&bytes))
+        .with_limits(DeserializeLimits::new().max_string_bytes(1000))
+        .deserialize();
+    let err = result.unwrap_err();
+    assert!(
+        matches!(err, DeserializeError::Decompress { field: "body", .. }),
+        "{err}"
+    );
+}
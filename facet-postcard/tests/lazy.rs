@@ -0,0 +1,252 @@
+use std::collections::{BTreeMap, HashSet};
+
+use facet::Facet;
+use facet_postcard::{LazyError, LazyValue, to_vec};
+use facet_reflect::PeekPathErrorKind;
+
+#[derive(Debug, Facet, PartialEq)]
+struct Inventory {
+    name: String,
+    items: Vec<Item>,
+    stock: BTreeMap<String, u32>,
+    tags: HashSet<u8>,
+    checksum: [u8; 4],
+    origin: (i16, f64),
+    fallback: Option<Box<Item>>,
+    state: State,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Item {
+    sku: u64,
+    label: String,
+    price: Price,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[facet(transparent)]
+struct Price(u32);
+
+#[derive(Debug, Facet, PartialEq)]
+struct Cached {
+    #[facet(skip)]
+    cache: Vec<u32>,
+    value: String,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum State {
+    Open,
+    Closed { reason: String, code: i32 },
+}
+
+fn item(sku: u64) -> Item {
+    Item {
+        sku,
+        label: format!("item-{sku}"),
+        price: Price(sku as u32 * 10),
+    }
+}
+
+#[test]
+fn reads_values_by_path() {
+    let inventory = Inventory {
+        name: "depot".into(),
+        items: (0..50).map(item).collect(),
+        stock: BTreeMap::from([("apples".to_string(), 3), ("pears".to_string(), 7)]),
+        tags: HashSet::from([9]),
+        checksum: [1, 2, 3, 4],
+        origin: (-5, 2.5),
+        fallback: Some(Box::new(item(99))),
+        state: State::Closed {
+            reason: "audit".into(),
+            code: -2,
+        },
+    };
+    let bytes = to_vec(&inventory).unwrap();
+    let root = LazyValue::new::<Inventory>(&bytes);
+
+    let read_u64 = |path: &str| root.at(path).unwrap().deserialize::<u64>().unwrap();
+    let read_u32 = |path: &str| root.at(path).unwrap().deserialize::<u32>().unwrap();
+    assert_eq!(read_u64("items[42].sku"), 42);
+    assert_eq!(
+        root.at("items[42].price")
+            .unwrap()
+            .deserialize::<Price>()
+            .unwrap(),
+        Price(420)
+    );
+    assert_eq!(read_u32("stock.pears"), 7);
+    assert_eq!(read_u32(r#"stock["apples"]"#), 3);
+    assert_eq!(read_u64("fallback.sku"), 99);
+    assert_eq!(root.at("tags[0]").unwrap().deserialize::<u8>().unwrap(), 9);
+    assert_eq!(
+        root.at("checksum[2]").unwrap().deserialize::<u8>().unwrap(),
+        3
+    );
+    assert_eq!(
+        root.at("origin[1]").unwrap().deserialize::<f64>().unwrap(),
+        2.5
+    );
+    assert_eq!(
+        root.at("state.reason")
+            .unwrap()
+            .deserialize::<String>()
+            .unwrap(),
+        "audit"
+    );
+    assert_eq!(
+        root.at("state.code").unwrap().deserialize::<i32>().unwrap(),
+        -2
+    );
+
+    let label = root
+        .field("items")
+        .unwrap()
+        .index(7)
+        .unwrap()
+        .field("label");
+    assert_eq!(label.unwrap().deserialize::<String>().unwrap(), "item-7");
+}
+
+#[test]
+fn decodes_whole_subtrees() {
+    let value = Inventory {
+        name: "depot".into(),
+        items: (0..50).map(item).collect(),
+        stock: BTreeMap::from([("apples".to_string(), 3), ("pears".to_string(), 7)]),
+        tags: HashSet::from([9]),
+        checksum: [1, 2, 3, 4],
+        origin: (-5, 2.5),
+        fallback: Some(Box::new(item(99))),
+        state: State::Closed {
+            reason: "audit".into(),
+            code: -2,
+        },
+    };
+    let bytes = to_vec(&value).unwrap();
+    let root = LazyValue::new::<Inventory>(&bytes);
+
+    assert_eq!(root.deserialize::<Inventory>().unwrap(), value);
+    assert_eq!(
+        root.at("items[3]").unwrap().deserialize::<Item>().unwrap(),
+        item(3)
+    );
+    assert_eq!(root.bytes().unwrap().len(), bytes.len());
+
+    let decoded = root.at("items[3].label").unwrap().decode().unwrap();
+    assert_eq!(decoded.peek().as_str(), Some("item-3"));
+}
+
+#[test]
+fn inspects_collections_and_enums() {
+    let inventory = Inventory {
+        name: "depot".into(),
+        items: (0..50).map(item).collect(),
+        stock: BTreeMap::from([("apples".to_string(), 3), ("pears".to_string(), 7)]),
+        tags: HashSet::from([9]),
+        checksum: [1, 2, 3, 4],
+        origin: (-5, 2.5),
+        fallback: Some(Box::new(item(99))),
+        state: State::Closed {
+            reason: "audit".into(),
+            code: -2,
+        },
+    };
+    let bytes = to_vec(&inventory).unwrap();
+    let root = LazyValue::new::<Inventory>(&bytes);
+
+    assert_eq!(root.at("items").unwrap().len().unwrap(), 50);
+    assert_eq!(root.at("stock").unwrap().len().unwrap(), 2);
+    assert_eq!(root.at("checksum").unwrap().len().unwrap(), 4);
+    assert_eq!(root.at("state").unwrap().variant().unwrap().name, "Closed");
+
+    let fallback = root.at("fallback").unwrap();
+    assert!(fallback.option().unwrap().is_some());
+}
+
+#[test]
+fn reports_missing_paths() {
+    let inventory = Inventory {
+        name: "depot".into(),
+        items: (0..50).map(item).collect(),
+        stock: BTreeMap::from([("apples".to_string(), 3), ("pears".to_string(), 7)]),
+        tags: HashSet::from([9]),
+        checksum: [1, 2, 3, 4],
+        origin: (-5, 2.5),
+        fallback: Some(Box::new(item(99))),
+        state: State::Closed {
+            reason: "audit".into(),
+            code: -2,
+        },
+    };
+    let bytes = to_vec(&inventory).unwrap();
+    let root = LazyValue::new::<Inventory>(&bytes);
+
+    let Err(LazyError::Path(err)) = root.at("items[50].sku") else {
+        panic!("expected a path error");
+    };
+    assert_eq!(err.segment, "[50]");
+    assert_eq!(
+        err.kind,
+        PeekPathErrorKind::IndexOutOfBounds { index: 50, len: 50 }
+    );
+
+    let Err(LazyError::Path(err)) = root.at("items[0].weight") else {
+        panic!("expected a path error");
+    };
+    assert_eq!(err.segment, ".weight");
+
+    let none = to_vec(&Inventory {
+        fallback: None,
+        ..inventory
+    })
+    .unwrap();
+    let Err(LazyError::Path(err)) = LazyValue::new::<Inventory>(&none).at("fallback.sku") else {
+        panic!("expected a path error");
+    };
+    assert!(matches!(err.kind, PeekPathErrorKind::NoneValue { .. }));
+}
+
+#[test]
+fn rejects_mismatched_types_and_truncated_input() {
+    let inventory = Inventory {
+        name: "depot".into(),
+        items: (0..50).map(item).collect(),
+        stock: BTreeMap::from([("apples".to_string(), 3), ("pears".to_string(), 7)]),
+        tags: HashSet::from([9]),
+        checksum: [1, 2, 3, 4],
+        origin: (-5, 2.5),
+        fallback: Some(Box::new(item(99))),
+        state: State::Closed {
+            reason: "audit".into(),
+            code: -2,
+        },
+    };
+    let bytes = to_vec(&inventory).unwrap();
+    let root = LazyValue::new::<Inventory>(&bytes);
+    assert!(matches!(
+        root.at("items[0].sku").unwrap().deserialize::<u32>(),
+        Err(LazyError::Deserialize(_))
+    ));
+
+    let truncated = &bytes[..bytes.len() / 2];
+    let root = LazyValue::new::<Inventory>(truncated);
+    assert!(matches!(root.at("state"), Err(LazyError::Postcard(_))));
+}
+
+#[test]
+fn skipped_fields_are_not_in_the_encoding() {
+    let bytes = to_vec(&Cached {
+        cache: vec![1, 2, 3],
+        value: "kept".into(),
+    })
+    .unwrap();
+    let root = LazyValue::new::<Cached>(&bytes);
+    let value = root.at("value").unwrap();
+    assert_eq!(value.offset(), 0);
+    assert_eq!(value.deserialize::<String>().unwrap(), "kept");
+    assert!(matches!(root.at("cache"), Err(LazyError::Path(_))));
+}
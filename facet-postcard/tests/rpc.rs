@@ -0,0 +1,168 @@
+use facet::Facet;
+use facet_postcard::rpc::{
+    Request, RpcError, RpcServer, decode_response, encode_request, fingerprint,
+};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Lookup {
+    key: String,
+    limit: u32,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Entry {
+    key: String,
+    values: Vec<u32>,
+}
+
+mod v2 {
+    use facet::Facet;
+
+    /// Same name as the first version, but with a different field
+    #[derive(Debug, Facet, PartialEq)]
+    pub struct Lookup {
+        pub key: String,
+        pub limit: u64,
+    }
+}
+
+/// The same struct, with and without its cache field encoded
+mod cached {
+    pub mod sent {
+        use facet::Facet;
+
+        #[derive(Debug, Facet)]
+        pub struct Page {
+            pub offset: u32,
+            pub total: u32,
+        }
+    }
+
+    pub mod skipped {
+        use facet::Facet;
+
+        #[derive(Debug, Facet)]
+        pub struct Page {
+            pub offset: u32,
+            #[facet(skip)]
+            pub total: u32,
+        }
+    }
+}
+
+#[derive(Debug, Facet)]
+struct Tree {
+    children: Vec<Tree>,
+}
+
+#[test]
+fn dispatches_by_method() {
+    let server = RpcServer::new()
+        .handle("lookup", |req: Lookup| Entry {
+            values: (0..req.limit).collect(),
+            key: req.key,
+        })
+        .handle("ping", |(): ()| "pong".to_string());
+    let request = encode_request(
+        "lookup",
+        &Lookup {
+            key: "k".into(),
+            limit: 3,
+        },
+    )
+    .unwrap();
+    let entry: Entry = decode_response(&server.dispatch(&request)).unwrap();
+    assert_eq!(
+        entry,
+        Entry {
+            key: "k".into(),
+            values: vec![0, 1, 2],
+        }
+    );
+
+    let request = encode_request("ping", &()).unwrap();
+    let pong: String = decode_response(&server.dispatch(&request)).unwrap();
+    assert_eq!(pong, "pong");
+}
+
+#[test]
+fn request_envelope_decodes() {
+    let bytes = encode_request("ping", &()).unwrap();
+    let request = Request::decode(&bytes).unwrap();
+    assert_eq!(request.method, "ping");
+    assert_eq!(request.fingerprint, fingerprint(<()>::SHAPE));
+    request.payload::<()>().unwrap();
+}
+
+#[test]
+fn unknown_method_is_reported() {
+    let request = encode_request("nope", &()).unwrap();
+    let err = decode_response::<()>(&RpcServer::new().dispatch(&request)).unwrap_err();
+    assert_eq!(
+        err,
+        RpcError::UnknownMethod {
+            method: "nope".into()
+        }
+    );
+}
+
+#[test]
+fn request_shape_mismatch_is_rejected() {
+    let request = encode_request(
+        "lookup",
+        &v2::Lookup {
+            key: "k".into(),
+            limit: 3,
+        },
+    )
+    .unwrap();
+    let server = RpcServer::new().handle("lookup", |req: Lookup| Entry {
+        values: (0..req.limit).collect(),
+        key: req.key,
+    });
+    let err = decode_response::<Entry>(&server.dispatch(&request)).unwrap_err();
+    assert_eq!(
+        err,
+        RpcError::ShapeMismatch {
+            method: "lookup".into(),
+            expected: fingerprint(Lookup::SHAPE),
+            found: fingerprint(v2::Lookup::SHAPE),
+        }
+    );
+}
+
+#[test]
+fn response_shape_mismatch_is_rejected() {
+    let request = encode_request("ping", &()).unwrap();
+    let server = RpcServer::new().handle("ping", |(): ()| "pong".to_string());
+    let err = decode_response::<u32>(&server.dispatch(&request)).unwrap_err();
+    assert!(matches!(err, RpcError::ShapeMismatch { .. }), "{err}");
+}
+
+#[test]
+fn malformed_envelope_is_rejected() {
+    assert_eq!(
+        decode_response::<()>(&RpcServer::new().dispatch(&[0x05, b'p'])).unwrap_err(),
+        RpcError::Malformed
+    );
+    assert_eq!(decode_response::<()>(&[]).unwrap_err(), RpcError::Malformed);
+}
+
+#[test]
+fn fingerprints_are_structural() {
+    assert_eq!(fingerprint(Lookup::SHAPE), fingerprint(Lookup::SHAPE));
+    assert_ne!(fingerprint(Lookup::SHAPE), fingerprint(v2::Lookup::SHAPE));
+    assert_ne!(
+        fingerprint(<Vec<u32>>::SHAPE),
+        fingerprint(<Vec<u64>>::SHAPE)
+    );
+    assert_ne!(fingerprint(Tree::SHAPE), fingerprint(<Vec<Tree>>::SHAPE));
+}
+
+#[test]
+fn fingerprints_cover_skipped_fields() {
+    assert_ne!(
+        fingerprint(cached::sent::Page::SHAPE),
+        fingerprint(cached::skipped::Page::SHAPE)
+    );
+}
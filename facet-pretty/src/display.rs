@@ -1,25 +1,71 @@
 //! Display trait implementations for pretty-printing Facet types
 
-use core::fmt::{self, Display, Formatter};
+use core::fmt::{self, Debug, Display, Formatter};
 
 use crate::printer::PrettyPrinter;
 use facet_core::Facet;
 
 /// Display wrapper for any type that implements Facet
+///
+/// It also implements `Debug`, with the same output.
 pub struct PrettyDisplay<'a, T: Facet<'a> + ?Sized> {
     pub(crate) value: &'a T,
     pub(crate) printer: PrettyPrinter,
 }
 
-impl<'a, T: Facet<'a>> Display for PrettyDisplay<'a, T> {
+impl<'a, T: Facet<'a> + ?Sized> Display for PrettyDisplay<'a, T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         self.printer.format_to(self.value, f)
     }
 }
 
+impl<'a, T: Facet<'a> + ?Sized> Debug for PrettyDisplay<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.printer.format_to(self.value, f)
+    }
+}
+
+/// Pretty-prints the wrapped value through `Debug` and `Display`
+///
+/// Nothing is formatted until the wrapper is, so it can be handed to
+/// `format!`, `tracing` fields or `anyhow` contexts at no cost when they
+/// end up unused. The printer is [`PrettyPrinter::current`] at the time of
+/// formatting; [`PrettyPrinter::debug`] wraps a value with a given printer.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_pretty::{FacetDebug, PrettyPrinter, with_thread_printer};
+///
+/// #[derive(Facet)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let point = Point { x: 1, y: 2 };
+/// let plain = PrettyPrinter::new().with_colors(false);
+/// let text = with_thread_printer(plain, || format!("moved to {:?}", FacetDebug(&point)));
+/// assert_eq!(text, "moved to Point {\n  x: 1,\n  y: 2,\n}");
+/// ```
+#[derive(Clone, Copy)]
+pub struct FacetDebug<'a, T: ?Sized>(pub &'a T);
+
+impl<'a, T: Facet<'a> + ?Sized> Debug for FacetDebug<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        PrettyPrinter::current().format_to(self.0, f)
+    }
+}
+
+impl<'a, T: Facet<'a> + ?Sized> Display for FacetDebug<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        PrettyPrinter::current().format_to(self.0, f)
+    }
+}
+
 /// Extension trait for Facet types to easily pretty-print them
 pub trait FacetPretty<'a>: Facet<'a> {
-    /// Get a displayable wrapper that pretty-prints this value
+    /// Get a displayable wrapper that pretty-prints this value with
+    /// [`PrettyPrinter::current`]
     fn pretty(&'a self) -> PrettyDisplay<'a, Self>;
 
     /// Get a displayable wrapper with custom printer settings
@@ -30,7 +76,7 @@ impl<'a, T: Facet<'a>> FacetPretty<'a> for T {
     fn pretty(&'a self) -> PrettyDisplay<'a, Self> {
         PrettyDisplay {
             value: self,
-            printer: PrettyPrinter::default(),
+            printer: PrettyPrinter::current(),
         }
     }
 
@@ -78,4 +124,25 @@ mod tests {
         // Just check that it contains the field name and doesn't panic
         assert!(output.contains("field"));
     }
+
+    #[test]
+    fn test_debug_matches_display() {
+        let test = TestStruct { field: 42 };
+        let printer = PrettyPrinter::new().with_colors(false);
+
+        let wrapped = printer.debug(&test);
+        assert_eq!(format!("{wrapped:?}"), printer.format(&test));
+        assert_eq!(format!("{wrapped:?}"), format!("{wrapped}"));
+    }
+
+    #[test]
+    fn test_facet_debug_formats_lazily() {
+        let test = TestStruct { field: 42 };
+        let wrapped = FacetDebug(&test);
+
+        // The printer is picked when formatting, not when wrapping
+        let printer = PrettyPrinter::new().with_colors(false).with_indent_size(4);
+        let output = crate::with_thread_printer(printer.clone(), || format!("{wrapped:?}"));
+        assert_eq!(output, printer.format(&test));
+    }
 }
@@ -0,0 +1,136 @@
+//! Pretty-printing errors with their source chain
+//!
+//! [`PrettyPrinter::format_error`] renders an error's message, the fields of
+//! its `Facet` shape (so context such as paths or ids shows up, and fields
+//! marked `#[facet(sensitive)]` stay redacted), then every error in its
+//! [`source`](core::error::Error::source) chain:
+//!
+//! ```text
+//! could not load config
+//! LoadError {
+//!   path: "/etc/app.toml",
+//!   token: [REDACTED],
+//! }
+//! Caused by:
+//!   0: permission denied
+//! ```
+
+use core::error::Error;
+use core::fmt::{self, Display, Write as _};
+
+use facet_core::{Def, Facet, Type, UserType};
+use facet_reflect::Peek;
+
+use crate::printer::PrettyPrinter;
+
+/// Iterator over an error and its sources, outermost first
+///
+/// ```
+/// use facet_pretty::ErrorChain;
+///
+/// let err = std::io::Error::other("disk full");
+/// let messages: Vec<String> = ErrorChain::new(&err).map(|e| e.to_string()).collect();
+/// assert_eq!(messages, ["disk full"]);
+/// ```
+#[derive(Clone)]
+pub struct ErrorChain<'a> {
+    next: Option<&'a (dyn Error + 'static)>,
+}
+
+impl<'a> ErrorChain<'a> {
+    /// Start a chain at `error`
+    pub fn new(error: &'a (dyn Error + 'static)) -> Self {
+        Self { next: Some(error) }
+    }
+}
+
+impl<'a> Iterator for ErrorChain<'a> {
+    type Item = &'a (dyn Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = current.source();
+        Some(current)
+    }
+}
+
+/// Display wrapper returned by [`PrettyPrinter::error`]
+pub struct PrettyError<'a, E: ?Sized> {
+    error: &'a E,
+    printer: PrettyPrinter,
+}
+
+impl<'a, E> Display for PrettyError<'a, E>
+where
+    E: Facet<'a> + Error + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.printer.format_error(self.error))
+    }
+}
+
+impl PrettyPrinter {
+    /// Format an error: its message, its fields, then its source chain
+    ///
+    /// The fields are left out for errors whose shape has none to show, such as
+    /// `io::Error` or unit structs.
+    pub fn format_error<'a, E>(&self, error: &E) -> String
+    where
+        E: Facet<'a> + Error + 'static,
+    {
+        let mut out = error.to_string();
+        if has_fields(Peek::new(error)) {
+            out.push('\n');
+            out.push_str(&self.format(error));
+        }
+        self.write_sources(&mut out, error);
+        out
+    }
+
+    /// Format an error that has no `Facet` shape: its message, then its source chain
+    pub fn format_error_chain(&self, error: &(dyn Error + 'static)) -> String {
+        let mut out = error.to_string();
+        self.write_sources(&mut out, error);
+        out
+    }
+
+    /// Get a displayable wrapper that renders `error` with [`Self::format_error`]
+    pub fn error<'a, E>(&self, error: &'a E) -> PrettyError<'a, E>
+    where
+        E: Facet<'a> + Error + 'static,
+    {
+        PrettyError {
+            error,
+            printer: self.clone(),
+        }
+    }
+
+    fn write_sources(&self, out: &mut String, error: &(dyn Error + 'static)) {
+        let Some(source) = error.source() else {
+            return;
+        };
+        out.push_str("\nCaused by:");
+        for (index, cause) in ErrorChain::new(source).enumerate() {
+            // Keep multi-line messages aligned under their number
+            let message = cause.to_string().replace('\n', "\n     ");
+            write!(out, "\n  {index}: {message}").unwrap();
+        }
+    }
+}
+
+/// Whether the error's shape has fields worth printing next to its message
+fn has_fields(value: Peek<'_, '_>) -> bool {
+    let shape = value.shape();
+    if !matches!(shape.def, Def::Undefined) {
+        return false;
+    }
+    match shape.ty {
+        Type::User(UserType::Struct(ty)) => !ty.fields.is_empty(),
+        Type::User(UserType::Enum(_)) => value
+            .into_enum()
+            .ok()
+            .and_then(|e| e.active_variant().ok())
+            .is_some_and(|variant| !variant.data.fields.is_empty()),
+        _ => false,
+    }
+}
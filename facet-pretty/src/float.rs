@@ -0,0 +1,178 @@
+//! Floating-point formatting options for pretty-printing
+
+use core::fmt::{self, Display, LowerExp, Write};
+
+/// Notation used when rendering floating-point numbers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FloatNotation {
+    /// Always positional notation (`1234.5`, `0.0001`)
+    Fixed,
+    /// Always scientific notation (`1.2345e3`, `1e-4`)
+    Scientific,
+    /// Positional notation when `small <= |value| < large`, scientific otherwise.
+    ///
+    /// Zero is always rendered positionally.
+    Auto {
+        /// Magnitudes below this threshold switch to scientific notation
+        small: f64,
+        /// Magnitudes at or above this threshold switch to scientific notation
+        large: f64,
+    },
+}
+
+impl FloatNotation {
+    /// Positional notation for magnitudes in `[1e-5, 1e16)`, scientific otherwise
+    pub const AUTO: Self = Self::Auto {
+        small: 1e-5,
+        large: 1e16,
+    };
+
+    fn use_scientific(&self, value: f64) -> bool {
+        match *self {
+            Self::Fixed => false,
+            Self::Scientific => true,
+            Self::Auto { small, large } => {
+                let magnitude = value.abs();
+                magnitude != 0.0 && (magnitude < small || magnitude >= large)
+            }
+        }
+    }
+}
+
+/// Controls how `f32` and `f64` values are rendered by the printer
+///
+/// The default renders the shortest representation that round-trips back to
+/// the same value, in positional notation — the same output as `Display`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatFormat {
+    precision: Option<usize>,
+    notation: FloatNotation,
+}
+
+impl Default for FloatFormat {
+    fn default() -> Self {
+        Self::shortest()
+    }
+}
+
+impl FloatFormat {
+    /// Shortest round-trip representation in positional notation
+    pub const fn shortest() -> Self {
+        Self {
+            precision: None,
+            notation: FloatNotation::Fixed,
+        }
+    }
+
+    /// Set the number of digits after the decimal point
+    ///
+    /// In scientific notation this is the number of mantissa digits after
+    /// the decimal point.
+    pub const fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Go back to the shortest round-trip representation
+    pub const fn with_shortest_roundtrip(mut self) -> Self {
+        self.precision = None;
+        self
+    }
+
+    /// Set the notation
+    pub const fn with_notation(mut self, notation: FloatNotation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// The configured precision, `None` meaning shortest round-trip
+    pub const fn precision(&self) -> Option<usize> {
+        self.precision
+    }
+
+    /// The configured notation
+    pub const fn notation(&self) -> FloatNotation {
+        self.notation
+    }
+
+    /// Write a float using this format
+    ///
+    /// NaN and infinities are always written as `NaN`, `inf` and `-inf`.
+    pub fn write<T, W>(&self, f: &mut W, value: T) -> fmt::Result
+    where
+        T: Display + LowerExp + Into<f64> + Copy,
+        W: Write + ?Sized,
+    {
+        let as_f64: f64 = value.into();
+        if !as_f64.is_finite() {
+            return write!(f, "{value}");
+        }
+
+        match (self.notation.use_scientific(as_f64), self.precision) {
+            (false, None) => write!(f, "{value}"),
+            (false, Some(precision)) => write!(f, "{value:.precision$}"),
+            (true, None) => write!(f, "{value:e}"),
+            (true, Some(precision)) => write!(f, "{value:.precision$e}"),
+        }
+    }
+
+    /// Format a float using this format into a new string
+    pub fn format<T>(&self, value: T) -> String
+    where
+        T: Display + LowerExp + Into<f64> + Copy,
+    {
+        let mut out = String::new();
+        self.write(&mut out, value)
+            .expect("writing to a String cannot fail");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matches_display() {
+        let format = FloatFormat::default();
+        for value in [0.1f64, 1.0, -2.5, 1e300, 1e-300, 123456.789] {
+            assert_eq!(format.format(value), value.to_string());
+        }
+        // f32 must not pick up the extra digits of its f64 widening
+        assert_eq!(format.format(0.1f32), "0.1");
+    }
+
+    #[test]
+    fn test_fixed_precision() {
+        let format = FloatFormat::shortest().with_precision(2);
+        assert_eq!(format.format(1.23456f64), "1.23");
+        assert_eq!(format.format(2.0f32), "2.00");
+        assert_eq!(format.with_shortest_roundtrip().format(2.0f64), "2");
+    }
+
+    #[test]
+    fn test_scientific() {
+        let format = FloatFormat::shortest().with_notation(FloatNotation::Scientific);
+        assert_eq!(format.format(1234.5f64), "1.2345e3");
+        assert_eq!(format.with_precision(1).format(1234.5f64), "1.2e3");
+    }
+
+    #[test]
+    fn test_auto_thresholds() {
+        let format = FloatFormat::shortest().with_notation(FloatNotation::AUTO);
+        assert_eq!(format.format(0.0f64), "0");
+        assert_eq!(format.format(42.5f64), "42.5");
+        assert_eq!(format.format(1e-7f64), "1e-7");
+        assert_eq!(format.format(-3e20f64), "-3e20");
+    }
+
+    #[test]
+    fn test_non_finite() {
+        let format = FloatFormat::shortest()
+            .with_precision(3)
+            .with_notation(FloatNotation::Scientific);
+        assert_eq!(format.format(f64::NAN), "NaN");
+        assert_eq!(format.format(f64::INFINITY), "inf");
+        assert_eq!(format.format(f32::NEG_INFINITY), "-inf");
+    }
+}
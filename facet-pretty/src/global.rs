@@ -0,0 +1,95 @@
+//! Process-wide and per-thread printer configuration
+//!
+//! Configure the printer once at startup with [`set_default_printer`]; it is
+//! then used by [`FacetPretty::pretty`](crate::FacetPretty::pretty) and
+//! [`fmt_debug`]. A thread can temporarily swap in another printer with
+//! [`with_thread_printer`], e.g. to turn colors off while writing a log file.
+
+use core::cell::RefCell;
+use core::fmt;
+use std::sync::OnceLock;
+
+use facet_core::Facet;
+
+use crate::printer::PrettyPrinter;
+
+static DEFAULT_PRINTER: OnceLock<PrettyPrinter> = OnceLock::new();
+
+std::thread_local! {
+    static THREAD_PRINTER: RefCell<Option<PrettyPrinter>> = const { RefCell::new(None) };
+}
+
+/// Set the printer used by `.pretty()` and [`fmt_debug`] across the process
+///
+/// This can only be done once; if a default was already set, the given
+/// printer is handed back as the error.
+// Handing the printer back, like `OnceLock::set`, is worth its size
+#[allow(clippy::result_large_err)]
+pub fn set_default_printer(printer: PrettyPrinter) -> Result<(), PrettyPrinter> {
+    DEFAULT_PRINTER.set(printer)
+}
+
+/// Run `f` with `printer` as the current printer of this thread
+///
+/// Overrides nest, and the previous printer is restored when `f` returns or
+/// panics.
+///
+/// ```
+/// use facet_pretty::{FacetPretty, PrettyPrinter, with_thread_printer};
+///
+/// let plain = PrettyPrinter::new().with_colors(false);
+/// let text = with_thread_printer(plain, || 42u32.pretty().to_string());
+/// assert_eq!(text, "42");
+/// ```
+pub fn with_thread_printer<R>(printer: PrettyPrinter, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<PrettyPrinter>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            THREAD_PRINTER.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    let previous = THREAD_PRINTER.with(|current| current.borrow_mut().replace(printer));
+    let _restore = Restore(previous);
+    f()
+}
+
+impl PrettyPrinter {
+    /// The printer in effect on this thread
+    ///
+    /// That is the innermost [`with_thread_printer`] override, else the
+    /// printer given to [`set_default_printer`], else [`PrettyPrinter::default`].
+    pub fn current() -> PrettyPrinter {
+        THREAD_PRINTER
+            .with(|current| current.borrow().clone())
+            .or_else(|| DEFAULT_PRINTER.get().cloned())
+            .unwrap_or_default()
+    }
+}
+
+/// Format `value` with the current printer, for use in `Debug` implementations
+///
+/// ```
+/// use core::fmt;
+/// use facet::Facet;
+///
+/// #[derive(Facet)]
+/// struct Token {
+///     #[facet(sensitive)]
+///     secret: String,
+/// }
+///
+/// impl fmt::Debug for Token {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         facet_pretty::fmt_debug(self, f)
+///     }
+/// }
+///
+/// let token = Token { secret: "hunter2".into() };
+/// assert!(!format!("{token:?}").contains("hunter2"));
+/// ```
+pub fn fmt_debug<'a, T: ?Sized + Facet<'a>>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    PrettyPrinter::current().format_to(value, f)
+}
@@ -0,0 +1,211 @@
+//! Shared-pointer bookkeeping for printing `Rc`/`Arc` graphs
+//!
+//! Before printing, the parts of the value graph that can hold smart pointers
+//! are scanned once to find pointees that are reachable through more than one
+//! of them. While printing, the first
+//! occurrence of such a node is labeled `&1` and printed in full; every later
+//! occurrence (including back-references that would form a cycle) is printed
+//! as `*1`. Since every cycle in an owned value has to go through a pointer,
+//! this guarantees termination.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+use facet_core::{Def, Field, KnownPointer, Shape, Type, UserType};
+use facet_reflect::{HasFields, Peek, ValueId};
+
+/// What to print when the printer reaches a pointee
+pub(crate) enum PointeeVisit {
+    /// Reached through a single pointer: print it normally
+    Unique,
+    /// First occurrence of a shared node: print `&label` then the value
+    First(usize),
+    /// Repeated occurrence of a shared node: print `*label` only
+    Repeat(usize),
+    /// A back-reference to a node that the scan didn't see as shared
+    Cycle,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct SharedNodes {
+    /// Pointees reachable through more than one pointer, with their label once printed
+    shared: BTreeMap<ValueId, Option<usize>>,
+    /// Pointees currently being printed
+    in_progress: BTreeSet<ValueId>,
+    next_label: usize,
+    /// Names of the fields leading to the value being printed
+    pub(crate) field_path: Vec<&'static str>,
+}
+
+impl SharedNodes {
+    /// Scan `root` for pointees that are reachable more than once
+    ///
+    /// Only the parts of `root` whose shape can hold a pointer to a shared
+    /// value are walked, so values without `Rc`, `Arc` or references aren't
+    /// walked at all.
+    pub(crate) fn scan(root: Peek<'_, '_>) -> Self {
+        let mut scan = Scan::default();
+        scan.value(root);
+        Self {
+            shared: scan.shared,
+            ..Self::default()
+        }
+    }
+
+    /// Called when the printer follows a pointer to `pointee`
+    ///
+    /// Every call returning [`PointeeVisit::Unique`] or [`PointeeVisit::First`]
+    /// must be paired with a call to [`SharedNodes::leave`].
+    pub(crate) fn enter(&mut self, pointee: ValueId) -> PointeeVisit {
+        match self.shared.get_mut(&pointee) {
+            Some(Some(label)) => PointeeVisit::Repeat(*label),
+            Some(label @ None) => {
+                self.next_label += 1;
+                *label = Some(self.next_label);
+                self.in_progress.insert(pointee);
+                PointeeVisit::First(self.next_label)
+            }
+            None if !self.in_progress.insert(pointee) => PointeeVisit::Cycle,
+            None => PointeeVisit::Unique,
+        }
+    }
+
+    /// Called once the printer is done with a pointee
+    pub(crate) fn leave(&mut self, pointee: ValueId) {
+        self.in_progress.remove(&pointee);
+    }
+}
+
+#[derive(Default)]
+struct Scan {
+    seen: BTreeSet<ValueId>,
+    shared: BTreeMap<ValueId, Option<usize>>,
+    /// Whether values of a shape can hold a shared pointer, by shape address
+    may_share: BTreeMap<usize, bool>,
+}
+
+impl Scan {
+    fn value(&mut self, value: Peek<'_, '_>) {
+        let shape = value.shape();
+        let may_share = *self
+            .may_share
+            .entry(shape as *const Shape as usize)
+            .or_insert_with(|| may_share(shape, &mut Vec::new()));
+        if !may_share {
+            return;
+        }
+
+        match shape.def {
+            Def::Pointer(_) => {
+                let Ok(ptr) = value.into_pointer() else {
+                    return;
+                };
+                let Some(pointee) = ptr.borrow_inner() else {
+                    // Locks own their value, so it can't be shared through them
+                    ptr.with_locked(|inner| self.value(inner));
+                    return;
+                };
+                if !self.seen.insert(pointee.id()) {
+                    self.shared.insert(pointee.id(), None);
+                    return;
+                }
+                self.value(pointee);
+            }
+            Def::Option(_) => {
+                if let Some(inner) = value.into_option().ok().and_then(|option| option.value()) {
+                    self.value(inner);
+                }
+            }
+            Def::Result(_) => {
+                if let Ok(result) = value.into_result()
+                    && let Some(inner) = result.ok().or_else(|| result.err())
+                {
+                    self.value(inner);
+                }
+            }
+            Def::Map(_) => {
+                if let Ok(map) = value.into_map() {
+                    for (key, value) in map.iter() {
+                        self.value(key);
+                        self.value(value);
+                    }
+                }
+            }
+            Def::Set(_) => {
+                if let Ok(set) = value.into_set() {
+                    for item in set.iter() {
+                        self.value(item);
+                    }
+                }
+            }
+            _ => {
+                if let Ok(list) = value.into_list_like() {
+                    for item in list.iter() {
+                        self.value(item);
+                    }
+                } else if let Ok(struct_) = value.into_struct() {
+                    for (_, field) in struct_.fields() {
+                        self.value(field);
+                    }
+                } else if let Ok(enum_) = value.into_enum() {
+                    for (_, field) in enum_.fields() {
+                        self.value(field);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether a value of `shape` can hold a pointer to a value that may be
+/// reached through another pointer too
+///
+/// `stack` holds the shapes being checked, so recursive types terminate.
+fn may_share(shape: &'static Shape, stack: &mut Vec<&'static Shape>) -> bool {
+    if stack.contains(&shape) {
+        return false;
+    }
+    stack.push(shape);
+    let fields_may_share = |fields: &'static [Field], stack: &mut Vec<&'static Shape>| {
+        fields.iter().any(|field| may_share(field.shape(), stack))
+    };
+    let result = match shape.def {
+        Def::Pointer(def) => match def.known {
+            // These own their pointee, so it can only be shared through
+            // something inside it
+            Some(
+                KnownPointer::Box
+                | KnownPointer::Cell
+                | KnownPointer::RefCell
+                | KnownPointer::OnceCell
+                | KnownPointer::Mutex
+                | KnownPointer::RwLock,
+            ) => def
+                .pointee()
+                .is_none_or(|pointee| may_share(pointee, stack)),
+            _ => true,
+        },
+        Def::Option(def) => may_share(def.t(), stack),
+        Def::Result(def) => may_share(def.t(), stack) || may_share(def.e(), stack),
+        Def::Map(def) => may_share(def.k(), stack) || may_share(def.v(), stack),
+        Def::Set(def) => may_share(def.t(), stack),
+        Def::List(def) => may_share(def.t(), stack),
+        Def::Array(def) => may_share(def.t(), stack),
+        Def::Slice(def) => may_share(def.t(), stack),
+        Def::Scalar => false,
+        Def::Undefined => match shape.ty {
+            Type::User(UserType::Struct(struct_type)) => {
+                fields_may_share(struct_type.fields, stack)
+            }
+            Type::User(UserType::Enum(enum_type)) => enum_type
+                .variants
+                .iter()
+                .any(|variant| fields_may_share(variant.data.fields, stack)),
+            Type::User(UserType::Opaque) | Type::Primitive(_) => false,
+            _ => true,
+        },
+        _ => true,
+    };
+    stack.pop();
+    result
+}
@@ -0,0 +1,94 @@
+//! Writing pretty-printed values to `std::io` streams
+
+use core::fmt;
+use std::io::{self, IsTerminal, Write as _};
+
+use facet_core::Facet;
+use facet_reflect::Peek;
+
+use crate::graph::SharedNodes;
+use crate::printer::PrettyPrinter;
+
+/// Forwards `fmt::Write` calls to an `io::Write`, keeping the first I/O error
+struct IoAdapter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoAdapter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+impl PrettyPrinter {
+    /// Format a value straight into an `io::Write`, without building a `String`
+    ///
+    /// Colors are written as configured; see [`Self::print`] for output that
+    /// adapts to the terminal.
+    pub fn format_to_io<'a, T: ?Sized + Facet<'a>, W: io::Write>(
+        &self,
+        value: &T,
+        writer: W,
+    ) -> io::Result<()> {
+        self.format_peek_to_io(Peek::new(value), writer)
+    }
+
+    /// Format a [`Peek`] straight into an `io::Write`, without building a `String`
+    pub fn format_peek_to_io<W: io::Write>(
+        &self,
+        value: Peek<'_, '_>,
+        writer: W,
+    ) -> io::Result<()> {
+        let mut adapter = IoAdapter {
+            inner: writer,
+            error: None,
+        };
+        let result = self.format_peek_internal(value, &mut adapter, &mut SharedNodes::scan(value));
+        match (result, adapter.error) {
+            (_, Some(err)) => Err(err),
+            (Err(_), None) => Err(io::Error::other("formatting failed")),
+            (Ok(()), None) => Ok(()),
+        }
+    }
+
+    /// Print a value to stdout, followed by a newline
+    ///
+    /// Colors are turned off when stdout is not a terminal. Output is buffered
+    /// and written as it is produced.
+    pub fn print<'a, T: ?Sized + Facet<'a>>(&self, value: &T) -> io::Result<()> {
+        let stdout = io::stdout();
+        let colors = stdout.is_terminal();
+        self.print_to(value, stdout.lock(), colors)
+    }
+
+    /// Print a value to stderr, followed by a newline
+    ///
+    /// Colors are turned off when stderr is not a terminal.
+    pub fn eprint<'a, T: ?Sized + Facet<'a>>(&self, value: &T) -> io::Result<()> {
+        let stderr = io::stderr();
+        let colors = stderr.is_terminal();
+        self.print_to(value, stderr.lock(), colors)
+    }
+
+    fn print_to<'a, T: ?Sized + Facet<'a>>(
+        &self,
+        value: &T,
+        stream: impl io::Write,
+        terminal: bool,
+    ) -> io::Result<()> {
+        let mut out = io::BufWriter::new(stream);
+        if terminal {
+            self.format_to_io(value, &mut out)?;
+        } else {
+            self.clone()
+                .with_colors(false)
+                .format_to_io(value, &mut out)?;
+        }
+        out.write_all(b"\n")?;
+        out.flush()
+    }
+}
@@ -0,0 +1,240 @@
+//! Physical memory layout of structs, as a table
+//!
+//! [`LayoutReport`] lists the fields of a struct in memory order with their
+//! offsets, sizes and alignments, shows the padding between them and where
+//! cache lines begin, and suggests a field order that wastes less space.
+
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt;
+
+use facet_core::{Facet, Shape, Type, UserType};
+
+/// The usual cache line size on x86-64 and most ARM cores
+const CACHE_LINE: usize = 64;
+
+/// Where a field sits in memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldLayout {
+    /// The field name
+    pub name: &'static str,
+    /// The field type
+    pub shape: &'static Shape,
+    /// Offset from the start of the struct, in bytes
+    pub offset: usize,
+    /// Size in bytes
+    pub size: usize,
+    /// Alignment in bytes
+    pub align: usize,
+}
+
+/// A stretch of bytes that belongs to no field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Padding {
+    /// Offset from the start of the struct, in bytes
+    pub offset: usize,
+    /// Size in bytes
+    pub size: usize,
+}
+
+/// The memory layout of a struct, computed from its [`Shape`]
+///
+/// Displaying the report prints a table of the fields in memory order, with
+/// padding and cache-line boundaries called out:
+///
+/// ```
+/// use facet::Facet;
+/// use facet_pretty::LayoutReport;
+///
+/// #[derive(Facet)]
+/// #[repr(C)]
+/// struct Packet {
+///     flag: bool,
+///     len: u64,
+///     kind: u16,
+/// }
+///
+/// let report = LayoutReport::of::<Packet>().unwrap();
+/// assert_eq!(report.padding_bytes(), 13);
+/// assert_eq!(
+///     report.to_string(),
+///     "\
+/// Packet: 24 bytes, align 8, 13 bytes of padding
+///  offset  size  align  field
+///       0     1      1  flag: bool
+///       1     7         (padding)
+///       8     8      8  len: u64
+///      16     2      2  kind: u16
+///      18     6         (padding)
+/// Ordering the fields as len, kind, flag would make it 16 bytes (8 fewer)
+/// "
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct LayoutReport {
+    /// The struct
+    pub shape: &'static Shape,
+    /// Size in bytes
+    pub size: usize,
+    /// Alignment in bytes
+    pub align: usize,
+    /// The sized fields, in memory order
+    pub fields: Vec<FieldLayout>,
+    cache_line: usize,
+}
+
+impl LayoutReport {
+    /// The layout of `T`, or `None` if it isn't a sized struct
+    pub fn of<'a, T: Facet<'a>>() -> Option<Self> {
+        Self::for_shape(T::SHAPE)
+    }
+
+    /// The layout of the struct described by `shape`, or `None` if it isn't
+    /// a sized struct
+    pub fn for_shape(shape: &'static Shape) -> Option<Self> {
+        let Type::User(UserType::Struct(st)) = shape.ty else {
+            return None;
+        };
+        let layout = shape.layout.sized_layout().ok()?;
+        let mut fields: Vec<FieldLayout> = st
+            .fields
+            .iter()
+            .filter_map(|field| {
+                let layout = field.shape().layout.sized_layout().ok()?;
+                Some(FieldLayout {
+                    name: field.name,
+                    shape: field.shape(),
+                    offset: field.offset,
+                    size: layout.size(),
+                    align: layout.align(),
+                })
+            })
+            .collect();
+        fields.sort_by_key(|field| (field.offset, field.size));
+        Some(Self {
+            shape,
+            size: layout.size(),
+            align: layout.align(),
+            fields,
+            cache_line: CACHE_LINE,
+        })
+    }
+
+    /// Mark cache lines of `bytes` instead of the default 64
+    pub fn with_cache_line(mut self, bytes: usize) -> Self {
+        self.cache_line = bytes.max(1);
+        self
+    }
+
+    /// The gaps between fields and after the last one
+    pub fn padding(&self) -> Vec<Padding> {
+        let mut padding = Vec::new();
+        let mut end = 0;
+        for field in &self.fields {
+            if field.offset > end {
+                padding.push(Padding {
+                    offset: end,
+                    size: field.offset - end,
+                });
+            }
+            end = end.max(field.offset + field.size);
+        }
+        if self.size > end {
+            padding.push(Padding {
+                offset: end,
+                size: self.size - end,
+            });
+        }
+        padding
+    }
+
+    /// How many bytes are padding
+    pub fn padding_bytes(&self) -> usize {
+        self.padding().iter().map(|padding| padding.size).sum()
+    }
+
+    /// A field order that makes the struct smaller, with the size it would
+    /// have, or `None` if no order does better
+    ///
+    /// Fields are ordered by decreasing alignment, as the compiler does for
+    /// `repr(Rust)` structs; this is mostly useful for `repr(C)` ones.
+    pub fn suggested_order(&self) -> Option<(Vec<&'static str>, usize)> {
+        let mut fields = self.fields.clone();
+        fields.sort_by_key(|field| core::cmp::Reverse(field.align));
+        let mut end = 0usize;
+        for field in &fields {
+            end = end.next_multiple_of(field.align) + field.size;
+        }
+        let size = end.next_multiple_of(self.align);
+        (size < self.size).then(|| (fields.iter().map(|field| field.name).collect(), size))
+    }
+
+    /// Whether the field starts and ends in different cache lines
+    fn straddles(&self, field: &FieldLayout) -> bool {
+        field.size > 0
+            && field.offset / self.cache_line != (field.offset + field.size - 1) / self.cache_line
+    }
+}
+
+impl fmt::Display for LayoutReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let padding = self.padding();
+        let wasted: usize = padding.iter().map(|padding| padding.size).sum();
+        write!(
+            f,
+            "{}: {} bytes, align {}",
+            self.shape, self.size, self.align
+        )?;
+        match wasted {
+            0 => writeln!(f)?,
+            1 => writeln!(f, ", 1 byte of padding")?,
+            n => writeln!(f, ", {n} bytes of padding")?,
+        }
+        writeln!(f, " offset  size  align  field")?;
+
+        let mut rows: Vec<(usize, Option<&FieldLayout>, usize)> = self
+            .fields
+            .iter()
+            .map(|field| (field.offset, Some(field), field.size))
+            .chain(
+                padding
+                    .iter()
+                    .map(|padding| (padding.offset, None, padding.size)),
+            )
+            .collect();
+        rows.sort_by_key(|&(offset, field, _)| (offset, field.is_some()));
+
+        let mut line = 0;
+        for (offset, field, size) in rows {
+            if offset / self.cache_line > line {
+                line = offset / self.cache_line;
+                writeln!(
+                    f,
+                    "{:-^36}",
+                    format!(" cache line {line} (byte {}) ", line * self.cache_line)
+                )?;
+            }
+            write!(f, "{offset:>7}  {size:>4}  ")?;
+            match field {
+                Some(field) => {
+                    write!(f, "{:>5}  {}: {}", field.align, field.name, field.shape)?;
+                    if self.straddles(field) {
+                        write!(f, "  <- straddles a cache line")?;
+                    }
+                    writeln!(f)?;
+                }
+                None => writeln!(f, "{:>5}  (padding)", "")?,
+            }
+        }
+
+        if let Some((order, size)) = self.suggested_order() {
+            writeln!(
+                f,
+                "Ordering the fields as {} would make it {size} bytes ({} fewer)",
+                order.join(", "),
+                self.size - size
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -7,10 +7,37 @@ extern crate alloc;
 
 mod color;
 mod display;
+mod error;
+mod float;
+mod global;
+mod graph;
+mod io;
+mod layout;
+mod locale;
 mod printer;
+mod redaction;
+mod report;
+mod sample;
 mod shape;
+mod style;
+mod theme;
+#[cfg(feature = "jiff")]
+mod time;
+mod units;
 
 pub use color::*;
 pub use display::*;
+pub use error::*;
+pub use float::*;
+pub use global::*;
+pub use layout::*;
+pub use locale::*;
 pub use printer::*;
+pub use redaction::*;
+pub use report::*;
 pub use shape::*;
+pub use style::Style;
+pub use theme::Theme;
+#[cfg(feature = "jiff")]
+pub use time::TimeFormat;
+pub use units::{humanize_bytes, humanize_seconds};
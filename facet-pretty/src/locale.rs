@@ -0,0 +1,286 @@
+//! Locale conventions for rendering numbers and dates in user-facing output
+
+use alloc::string::String;
+
+use facet_core::{ScalarType, Shape};
+
+/// Order of the day, month and year components in a rendered date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOrder {
+    /// `2024-01-15` (ISO 8601)
+    YearMonthDay,
+    /// `15.01.2024`
+    DayMonthYear,
+    /// `01/15/2024`
+    MonthDayYear,
+}
+
+/// Controls how numbers and dates are rendered by the printer
+///
+/// The default locale leaves everything as `Display` renders it: no
+/// thousands separators, `.` as decimal separator and ISO 8601 dates.
+///
+/// Dates are localized for time-affinity scalars (e.g. `chrono`, `jiff` or
+/// `time` types, and datetimes in dynamic values) whose text starts with an
+/// ISO 8601 date. The time of day, if any, is kept as is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    thousands_separator: Option<char>,
+    decimal_separator: char,
+    date_order: DateOrder,
+    date_separator: char,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::iso()
+    }
+}
+
+impl Locale {
+    /// No grouping, `.` decimals, ISO 8601 dates: `1234567.5`, `2024-01-15`
+    pub const fn iso() -> Self {
+        Self {
+            thousands_separator: None,
+            decimal_separator: '.',
+            date_order: DateOrder::YearMonthDay,
+            date_separator: '-',
+        }
+    }
+
+    /// US English: `1,234,567.5`, `01/15/2024`
+    pub const fn en_us() -> Self {
+        Self {
+            thousands_separator: Some(','),
+            decimal_separator: '.',
+            date_order: DateOrder::MonthDayYear,
+            date_separator: '/',
+        }
+    }
+
+    /// British English: `1,234,567.5`, `15/01/2024`
+    pub const fn en_gb() -> Self {
+        Self {
+            thousands_separator: Some(','),
+            decimal_separator: '.',
+            date_order: DateOrder::DayMonthYear,
+            date_separator: '/',
+        }
+    }
+
+    /// German: `1.234.567,5`, `15.01.2024`
+    pub const fn de_de() -> Self {
+        Self {
+            thousands_separator: Some('.'),
+            decimal_separator: ',',
+            date_order: DateOrder::DayMonthYear,
+            date_separator: '.',
+        }
+    }
+
+    /// French: `1 234 567,5` (with a narrow no-break space), `15/01/2024`
+    pub const fn fr_fr() -> Self {
+        Self {
+            thousands_separator: Some('\u{202F}'),
+            decimal_separator: ',',
+            date_order: DateOrder::DayMonthYear,
+            date_separator: '/',
+        }
+    }
+
+    /// Set the character between groups of three integer digits, or `None` for no grouping
+    pub const fn with_thousands_separator(mut self, separator: Option<char>) -> Self {
+        self.thousands_separator = separator;
+        self
+    }
+
+    /// Set the character between the integer and fractional parts
+    pub const fn with_decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Set the order of date components and the character between them
+    pub const fn with_date_format(mut self, order: DateOrder, separator: char) -> Self {
+        self.date_order = order;
+        self.date_separator = separator;
+        self
+    }
+
+    /// Localize a number rendered in Rust syntax, e.g. `-1234.5` or `1.5e-7`
+    ///
+    /// Text that isn't a plain decimal number (`NaN`, `inf`, hex) is returned as is.
+    pub fn format_number(&self, text: &str) -> String {
+        let (sign, unsigned) = match text.strip_prefix(['-', '+']) {
+            Some(rest) => text.split_at(text.len() - rest.len()),
+            None => ("", text),
+        };
+        let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+            Some(idx) => unsigned.split_at(idx),
+            None => (unsigned, ""),
+        };
+        let (integer, fraction) = match mantissa.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (mantissa, None),
+        };
+        if integer.is_empty() || !integer.bytes().all(|b| b.is_ascii_digit()) {
+            return text.into();
+        }
+
+        let mut out = String::with_capacity(text.len() + integer.len() / 3);
+        out.push_str(sign);
+        for (idx, digit) in integer.chars().enumerate() {
+            if idx > 0
+                && (integer.len() - idx) % 3 == 0
+                && let Some(separator) = self.thousands_separator
+            {
+                out.push(separator);
+            }
+            out.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            out.push(self.decimal_separator);
+            out.push_str(fraction);
+        }
+        out.push_str(exponent);
+        out
+    }
+
+    /// Localize the leading ISO 8601 date of `text`, e.g. `2024-01-15T10:30:00Z`
+    ///
+    /// A `T` between the date and the time becomes a space. Returns `None` if
+    /// `text` doesn't start with a date.
+    pub fn format_date(&self, text: &str) -> Option<String> {
+        let (year, month, day, rest) = split_iso_date(text)?;
+        let time = match rest.strip_prefix('T') {
+            Some(time) => Some(time),
+            None if rest.is_empty() => None,
+            None => Some(rest.strip_prefix(' ')?),
+        };
+
+        let sep = self.date_separator;
+        let mut out = match self.date_order {
+            DateOrder::YearMonthDay => format!("{year}{sep}{month}{sep}{day}"),
+            DateOrder::DayMonthYear => format!("{day}{sep}{month}{sep}{year}"),
+            DateOrder::MonthDayYear => format!("{month}{sep}{day}{sep}{year}"),
+        };
+        if let Some(time) = time {
+            out.push(' ');
+            out.push_str(time);
+        }
+        Some(out)
+    }
+
+    /// Localize the `Display` text of a scalar of the given shape
+    pub(crate) fn format_scalar(&self, shape: &Shape, text: String) -> String {
+        match shape.scalar_type() {
+            Some(
+                ScalarType::F32
+                | ScalarType::F64
+                | ScalarType::U8
+                | ScalarType::U16
+                | ScalarType::U32
+                | ScalarType::U64
+                | ScalarType::U128
+                | ScalarType::USize
+                | ScalarType::I8
+                | ScalarType::I16
+                | ScalarType::I32
+                | ScalarType::I64
+                | ScalarType::I128
+                | ScalarType::ISize,
+            ) => self.format_number(&text),
+            Some(_) => text,
+            None if is_date_time(shape) => self.format_date(&text).unwrap_or(text),
+            None => text,
+        }
+    }
+}
+
+/// Whether `shape` is one of the `chrono`, `jiff` or `time` date and time types
+pub(crate) fn is_date_time(shape: &Shape) -> bool {
+    matches!(
+        shape.type_identifier,
+        // chrono
+        "DateTime<Utc>"
+            | "DateTime<Local>"
+            | "DateTime<FixedOffset>"
+            | "NaiveDateTime"
+            | "NaiveDate"
+            | "NaiveTime"
+            // jiff
+            | "Timestamp"
+            | "Zoned"
+            | "DateTime"
+            // time
+            | "UtcDateTime"
+            | "OffsetDateTime"
+    )
+}
+
+/// Splits `[+-]YYYY-MM-DD` off the start of `text`
+fn split_iso_date(text: &str) -> Option<(&str, &str, &str, &str)> {
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+
+    let sign_len = usize::from(text.starts_with(['-', '+']));
+    let year_len = sign_len + text[sign_len..].find('-')?;
+    let (year, rest) = text.split_at(year_len);
+    if year.len() - sign_len < 4 || !digits(&year[sign_len..]) {
+        return None;
+    }
+    let month = rest.get(1..3)?;
+    let day = rest.get(4..6)?;
+    if !digits(month) || !digits(day) || rest.get(3..4)? != "-" {
+        return None;
+    }
+    Some((year, month, day, &rest[6..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_grouping() {
+        let locale = Locale::en_us();
+        assert_eq!(locale.format_number("0"), "0");
+        assert_eq!(locale.format_number("999"), "999");
+        assert_eq!(locale.format_number("1000"), "1,000");
+        assert_eq!(locale.format_number("-1234567"), "-1,234,567");
+        assert_eq!(locale.format_number("1234567.125"), "1,234,567.125");
+        assert_eq!(locale.format_number("1234.5e-7"), "1,234.5e-7");
+        assert_eq!(locale.format_number("NaN"), "NaN");
+        assert_eq!(locale.format_number("-inf"), "-inf");
+    }
+
+    #[test]
+    fn test_decimal_comma() {
+        let locale = Locale::de_de();
+        assert_eq!(locale.format_number("1234567.5"), "1.234.567,5");
+        assert_eq!(Locale::fr_fr().format_number("12345.5"), "12\u{202F}345,5");
+        assert_eq!(Locale::iso().format_number("1234567.5"), "1234567.5");
+    }
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(
+            Locale::de_de().format_date("2024-01-15").as_deref(),
+            Some("15.01.2024")
+        );
+        assert_eq!(
+            Locale::en_us()
+                .format_date("2024-01-15T10:30:00Z")
+                .as_deref(),
+            Some("01/15/2024 10:30:00Z")
+        );
+        assert_eq!(
+            Locale::en_gb()
+                .format_date("2024-01-15 10:30:00 +01:00")
+                .as_deref(),
+            Some("15/01/2024 10:30:00 +01:00")
+        );
+        assert_eq!(Locale::en_us().format_date("10:30:00"), None);
+        assert_eq!(Locale::en_us().format_date("2024-01-15x"), None);
+        assert_eq!(Locale::en_us().format_date("24-01-15"), None);
+    }
+}
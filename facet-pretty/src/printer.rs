@@ -2,6 +2,7 @@
 
 use alloc::borrow::Cow;
 use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
 use core::{
     fmt::{self, Write},
     hash::{Hash, Hasher},
@@ -10,15 +11,27 @@ use core::{
 use std::hash::DefaultHasher;
 
 use facet_core::{
-    Def, DynDateTimeKind, DynValueKind, Facet, Field, PointerType, PrimitiveType, SequenceType,
-    Shape, StructKind, StructType, TextualType, Type, TypeNameOpts, UserType,
+    Def, DynDateTimeKind, DynValueKind, Facet, Field, FieldFlags, PointerType, PrimitiveType,
+    ScalarType, SequenceType, Shape, StructKind, StructType, TextualType, Type, TypeNameOpts,
+    UserType,
 };
-use facet_reflect::{Peek, ValueId};
+use facet_reflect::Peek;
 
 use owo_colors::{OwoColorize, Rgb};
 
 use crate::color::ColorGenerator;
+use crate::display::PrettyDisplay;
+use crate::float::FloatFormat;
+use crate::graph::{PointeeVisit, SharedNodes};
+use crate::locale::{Locale, is_date_time};
+use crate::redaction::{RedactionMode, RedactionPolicy};
+use crate::sample::{Sample, Sampled};
 use crate::shape::{FieldSpan, Path, PathSegment, Span};
+use crate::style::{LineWriter, Style, visible_width};
+use crate::theme::{Theme, colors_by_default};
+#[cfg(feature = "jiff")]
+use crate::time::TimeFormat;
+use crate::units;
 
 /// Tokyo Night color palette (RGB values from official theme)
 ///
@@ -108,17 +121,46 @@ pub mod tokyo_night {
 }
 
 /// A formatter for pretty-printing Facet types
+#[derive(Clone)]
 pub struct PrettyPrinter {
     /// usize::MAX is a special value that means indenting with tabs instead of spaces
     indent_size: usize,
     max_depth: Option<usize>,
     color_generator: ColorGenerator,
     use_colors: bool,
+    theme: Theme,
     list_u8_as_bytes: bool,
+    /// Render byte lists as a hex dump with offsets and an ASCII gutter
+    bytes_as_hex: bool,
     /// Skip type names for Options (show `Some(x)` instead of `Option<T>::Some(x)`)
     minimal_option_names: bool,
     /// Whether to show doc comments in output
     show_doc_comments: bool,
+    /// How `f32`/`f64` values are rendered
+    float_format: FloatFormat,
+    /// How sensitive field values are rendered
+    redaction_mode: RedactionMode,
+    /// Decides which fields to redact, besides sensitive ones
+    redaction_policy: Option<Arc<dyn RedactionPolicy>>,
+    /// Locale for numbers and dates, `None` for plain `Display` output
+    locale: Option<Locale>,
+    /// Time zone and form of timestamps, `None` for plain `Display` output
+    #[cfg(feature = "jiff")]
+    time_format: Option<TimeFormat>,
+    /// List struct fields by their declared `#[facet(order = N)]`
+    field_order: bool,
+    /// Cluster struct fields by `#[facet(group = "...")]` and render a header per group
+    group_headers: bool,
+    /// Render fields with a known `#[facet(unit = "...")]` as `1.2 GiB`, `3m 20s`, ...
+    humanize_units: bool,
+    /// Whether values that fit on one line are printed on one line
+    style: Style,
+    /// The line width [`Style::Compact`] fills output up to
+    max_width: usize,
+    /// How many of the first items of a collection to print, `None` for all
+    max_list_items: Option<usize>,
+    /// How many of the last items to print after the ones left out
+    list_tail_items: usize,
 }
 
 impl Default for PrettyPrinter {
@@ -127,10 +169,25 @@ impl Default for PrettyPrinter {
             indent_size: 2,
             max_depth: None,
             color_generator: ColorGenerator::default(),
-            use_colors: std::env::var_os("NO_COLOR").is_none(),
+            use_colors: colors_by_default(),
+            theme: Theme::default(),
             list_u8_as_bytes: true,
+            bytes_as_hex: false,
             minimal_option_names: false,
             show_doc_comments: false,
+            float_format: FloatFormat::default(),
+            redaction_mode: RedactionMode::default(),
+            redaction_policy: None,
+            locale: None,
+            #[cfg(feature = "jiff")]
+            time_format: None,
+            field_order: false,
+            group_headers: false,
+            humanize_units: true,
+            style: Style::default(),
+            max_width: 80,
+            max_list_items: None,
+            list_tail_items: 0,
         }
     }
 }
@@ -160,11 +217,30 @@ impl PrettyPrinter {
     }
 
     /// Enable or disable colors
+    ///
+    /// By default, colors are used unless the `NO_COLOR` environment variable
+    /// is set, or stdout isn't a terminal.
     pub fn with_colors(mut self, use_colors: bool) -> Self {
         self.use_colors = use_colors;
         self
     }
 
+    /// Set the colors used for each kind of token, e.g. [`Theme::light`] for
+    /// terminals with a light background
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Render lists of `u8` as a hex dump: 16 bytes per row, each row starting
+    /// with its offset and ending with the bytes as ASCII
+    ///
+    /// Only applies when the list is printed over multiple lines.
+    pub fn with_bytes_as_hex(mut self, hex: bool) -> Self {
+        self.bytes_as_hex = hex;
+        self
+    }
+
     /// Use minimal names for Options (show `Some(x)` instead of `Option<T>::Some(x)`)
     pub fn with_minimal_option_names(mut self, minimal: bool) -> Self {
         self.minimal_option_names = minimal;
@@ -177,17 +253,146 @@ impl PrettyPrinter {
         self
     }
 
+    /// Set how `f32`/`f64` values are rendered
+    pub fn with_float_format(mut self, format: FloatFormat) -> Self {
+        self.float_format = format;
+        self
+    }
+
+    /// Render floats with a fixed number of digits after the decimal point
+    ///
+    /// Shorthand for adjusting the precision of the current [`FloatFormat`].
+    pub fn with_float_precision(mut self, precision: usize) -> Self {
+        self.float_format = self.float_format.with_precision(precision);
+        self
+    }
+
+    /// Set how values of sensitive fields are rendered
+    pub fn with_redaction_mode(mut self, mode: RedactionMode) -> Self {
+        self.redaction_mode = mode;
+        self
+    }
+
+    /// Let `policy` decide which fields to redact and how
+    ///
+    /// Fields marked `#[facet(sensitive)]` are redacted whatever the policy
+    /// says; see [`RedactionPolicy`].
+    pub fn with_redaction_policy(mut self, policy: impl RedactionPolicy + 'static) -> Self {
+        self.redaction_policy = Some(Arc::new(policy));
+        self
+    }
+
+    /// Render numbers and dates following the conventions of `locale`
+    ///
+    /// Meant for user-facing reports; the output is no longer valid Rust syntax.
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Render timestamps in another time zone, with a pattern, or relative
+    /// to now, see [`TimeFormat`]
+    ///
+    /// Timestamps rendered this way are not localized further.
+    #[cfg(feature = "jiff")]
+    pub fn with_time_format(mut self, time_format: TimeFormat) -> Self {
+        self.time_format = Some(time_format);
+        self
+    }
+
+    /// List struct fields by their declared `#[facet(order = N)]` instead of
+    /// declaration order
+    ///
+    /// Fields without an order come after all ordered fields.
+    pub fn with_field_order(mut self, respect: bool) -> Self {
+        self.field_order = respect;
+        self
+    }
+
+    /// Keep struct fields of the same `#[facet(group = "...")]` together and
+    /// render a `// group` header before each group
+    ///
+    /// Headers are omitted when a value is printed on a single line.
+    pub fn with_group_headers(mut self, show: bool) -> Self {
+        self.group_headers = show;
+        self
+    }
+
+    /// Render numeric fields marked `#[facet(unit = "bytes")]` or
+    /// `#[facet(unit = "seconds")]` in human-readable form (`1.2 GiB`, `3m 20s`)
+    ///
+    /// Enabled by default; when disabled, such fields print as plain numbers.
+    pub fn with_humanized_units(mut self, humanize: bool) -> Self {
+        self.humanize_units = humanize;
+        self
+    }
+
+    /// Set how values are laid out over lines
+    ///
+    /// With [`Style::Compact`], output fills lines up to the
+    /// [maximum width](Self::with_max_width).
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the number of columns [`Style::Compact`] output should fit in
+    ///
+    /// Values are kept on the line they start on when the rest of that line
+    /// has room for them, field names, indentation and the closing comma
+    /// included; otherwise they are broken over lines, as are the values
+    /// inside them that don't fit either. Lists of numbers and other scalars
+    /// that don't fit on one line are wrapped, with as many items per line as
+    /// fit. A single value wider than this, like a long string, still goes on
+    /// its own line. Defaults to 80.
+    pub fn with_max_width(mut self, max_width: usize) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Print at most `max` items of each list, set and map
+    ///
+    /// Longer collections print their first `max` items, then a marker like
+    /// `... 99990 more` for the rest, so printing a huge collection stays
+    /// quick and its output readable. Byte lists printed as a hex dump are
+    /// always printed whole. By default, every item is printed.
+    pub fn with_max_list_items(mut self, max: usize) -> Self {
+        self.max_list_items = Some(max);
+        self
+    }
+
+    /// Also print the last `count` items of collections cut short by
+    /// [`with_max_list_items`](Self::with_max_list_items), after the marker
+    ///
+    /// Only collections with more than `max + count` items are cut short.
+    pub fn with_list_tail_items(mut self, count: usize) -> Self {
+        self.list_tail_items = count;
+        self
+    }
+
     /// Format a value to a string
     pub fn format<'a, T: ?Sized + Facet<'a>>(&self, value: &T) -> String {
         let value = Peek::new(value);
 
         let mut output = String::new();
-        self.format_peek_internal(value, &mut output, &mut BTreeMap::new())
+        self.format_peek_internal(value, &mut output, &mut SharedNodes::scan(value))
             .expect("Formatting failed");
 
         output
     }
 
+    /// Wrap a value so it is printed with this printer when formatted with
+    /// `{:?}` or `{}`
+    ///
+    /// Unlike [`format`](Self::format), nothing is printed until the wrapper
+    /// is formatted, if ever.
+    pub fn debug<'a, T: ?Sized + Facet<'a>>(&self, value: &'a T) -> PrettyDisplay<'a, T> {
+        PrettyDisplay {
+            value,
+            printer: self.clone(),
+        }
+    }
+
     /// Format a value to a formatter
     pub fn format_to<'a, T: ?Sized + Facet<'a>>(
         &self,
@@ -195,13 +400,13 @@ impl PrettyPrinter {
         f: &mut fmt::Formatter<'_>,
     ) -> fmt::Result {
         let value = Peek::new(value);
-        self.format_peek_internal(value, f, &mut BTreeMap::new())
+        self.format_peek_internal(value, f, &mut SharedNodes::scan(value))
     }
 
     /// Format a value to a string
     pub fn format_peek(&self, value: Peek<'_, '_>) -> String {
         let mut output = String::new();
-        self.format_peek_internal(value, &mut output, &mut BTreeMap::new())
+        self.format_peek_internal(value, &mut output, &mut SharedNodes::scan(value))
             .expect("Formatting failed");
         output
     }
@@ -249,34 +454,74 @@ impl PrettyPrinter {
     pub(crate) fn format_peek_internal_(
         &self,
         value: Peek<'_, '_>,
-        f: &mut dyn Write,
-        visited: &mut BTreeMap<ValueId, usize>,
+        f: &mut LineWriter<'_>,
+        visited: &mut SharedNodes,
         format_depth: usize,
         type_depth: usize,
         short: bool,
     ) -> fmt::Result {
+        if self.style == Style::Compact && !short {
+            // Render the value on one line, and keep it if it fits
+            let mut line = String::new();
+            let mut trial = visited.clone();
+            self.format_peek_internal_(
+                value,
+                &mut LineWriter::new(&mut line, f.column()),
+                &mut trial,
+                format_depth,
+                type_depth,
+                true,
+            )?;
+            // Nested values are followed by a comma or a closing delimiter
+            let after = usize::from(format_depth > 0);
+            if !line.contains('\n') && f.column() + visible_width(&line) + after <= self.max_width {
+                *visited = trial;
+                return f.write_str(&line);
+            }
+        }
+
         let mut value = value;
+        let mut entered = Vec::new();
         while let Ok(ptr) = value.into_pointer()
             && let Some(pointee) = ptr.borrow_inner()
         {
             value = pointee;
+            match visited.enter(value.id()) {
+                PointeeVisit::Unique => entered.push(value.id()),
+                PointeeVisit::First(label) => {
+                    entered.push(value.id());
+                    self.write_keyword(f, &format!("&{label} "))?;
+                }
+                PointeeVisit::Repeat(label) => {
+                    entered.into_iter().for_each(|id| visited.leave(id));
+                    return self.write_keyword(f, &format!("*{label}"));
+                }
+                PointeeVisit::Cycle => {
+                    entered.into_iter().for_each(|id| visited.leave(id));
+                    self.write_type_name(f, &value)?;
+                    return self.write_comment(f, " /* cycle */");
+                }
+            }
         }
-        let shape = value.shape();
 
-        if let Some(prev_type_depth) = visited.insert(value.id(), type_depth) {
-            self.write_type_name(f, &value)?;
-            self.write_punctuation(f, " { ")?;
-            self.write_comment(
-                f,
-                &format!(
-                    "/* cycle detected at {} (first seen at type_depth {}) */",
-                    value.id(),
-                    prev_type_depth,
-                ),
-            )?;
-            visited.remove(&value.id());
-            return Ok(());
-        }
+        let result =
+            self.format_pointee_internal(value, f, visited, format_depth, type_depth, short);
+        entered.into_iter().for_each(|id| visited.leave(id));
+        result
+    }
+
+    /// Format a value that is not a pointer (or whose pointers were already followed)
+    #[allow(clippy::too_many_arguments)]
+    fn format_pointee_internal(
+        &self,
+        value: Peek<'_, '_>,
+        f: &mut LineWriter<'_>,
+        visited: &mut SharedNodes,
+        format_depth: usize,
+        type_depth: usize,
+        short: bool,
+    ) -> fmt::Result {
+        let shape = value.shape();
 
         match (shape.def, shape.ty) {
             (_, Type::Primitive(PrimitiveType::Textual(TextualType::Str))) => {
@@ -300,7 +545,7 @@ impl PrettyPrinter {
                 }
                 write!(f, "\"")?;
                 if self.use_colors {
-                    write!(f, "{}", value.color(tokyo_night::STRING))?;
+                    write!(f, "{}", value.color(self.theme.string))?;
                 } else {
                     write!(f, "{value}")?;
                 }
@@ -314,7 +559,7 @@ impl PrettyPrinter {
                 let s = value.get::<alloc::string::String>().unwrap();
                 write!(f, "\"")?;
                 if self.use_colors {
-                    write!(f, "{}", s.color(tokyo_night::STRING))?;
+                    write!(f, "{}", s.color(self.theme.string))?;
                 } else {
                     write!(f, "{s}")?;
                 }
@@ -354,6 +599,26 @@ impl PrettyPrinter {
                     self.write_punctuation(f, suffix)?;
                 }
             }
+            (Def::Pointer(def), _) if def.is_lock() => {
+                let ptr = value.into_pointer().unwrap();
+                self.write_type_name(f, &value)?;
+                self.write_punctuation(f, "(")?;
+                let inner = ptr.with_locked(|inner| {
+                    self.format_peek_internal_(
+                        inner,
+                        f,
+                        visited,
+                        format_depth,
+                        type_depth + 1,
+                        short,
+                    )
+                });
+                match inner {
+                    Some(result) => result?,
+                    None => self.write_comment(f, "/* locked */")?,
+                }
+                self.write_punctuation(f, ")")?;
+            }
 
             (_, Type::Pointer(PointerType::Raw(_) | PointerType::Function(_))) => {
                 self.write_type_name(f, &value)?;
@@ -523,25 +788,60 @@ impl PrettyPrinter {
                 self.write_type_name(f, &value)?;
 
                 if !list.is_empty() {
-                    if list.def().t().is_type::<u8>() && self.list_u8_as_bytes {
+                    if list.def().t().is_type::<u8>() && self.bytes_as_hex && !short {
+                        self.write_punctuation(f, " [")?;
+                        let bytes: Vec<u8> =
+                            list.iter().map(|item| *item.get::<u8>().unwrap()).collect();
+                        for (row, chunk) in bytes.chunks(16).enumerate() {
+                            writeln!(f)?;
+                            self.indent(f, format_depth + 1)?;
+                            self.write_comment(f, &format!("{:08x}", row * 16))?;
+                            write!(f, " ")?;
+                            for column in 0..16 {
+                                if column == 8 {
+                                    write!(f, " ")?;
+                                }
+                                match chunk.get(column) {
+                                    Some(&byte) => {
+                                        write!(f, " ")?;
+                                        self.write_byte(f, byte)?;
+                                    }
+                                    None => write!(f, "   ")?,
+                                }
+                            }
+                            let ascii: String = chunk
+                                .iter()
+                                .map(|&byte| {
+                                    if byte.is_ascii_graphic() || byte == b' ' {
+                                        byte as char
+                                    } else {
+                                        '.'
+                                    }
+                                })
+                                .collect();
+                            write!(f, "  ")?;
+                            self.write_punctuation(f, "|")?;
+                            write!(f, "{ascii}")?;
+                            self.write_punctuation(f, "|")?;
+                        }
+                        writeln!(f)?;
+                        self.indent(f, format_depth)?;
+                        self.write_punctuation(f, "]")?;
+                    } else if list.def().t().is_type::<u8>() && self.list_u8_as_bytes {
                         self.write_punctuation(f, " [")?;
-                        for (idx, item) in list.iter().enumerate() {
+                        let len = list.len();
+                        for (idx, item) in self.sample(list.iter(), len).enumerate() {
                             if !short && idx % 16 == 0 {
                                 writeln!(f)?;
                                 self.indent(f, format_depth + 1)?;
                             }
                             write!(f, " ")?;
 
-                            let byte = *item.get::<u8>().unwrap();
-                            if self.use_colors {
-                                let mut hasher = DefaultHasher::new();
-                                byte.hash(&mut hasher);
-                                let hash = hasher.finish();
-                                let color = self.color_generator.generate_color(hash);
-                                let rgb = Rgb(color.r, color.g, color.b);
-                                write!(f, "{}", format!("{byte:02x}").color(rgb))?;
-                            } else {
-                                write!(f, "{byte:02x}")?;
+                            match item {
+                                Sampled::Item(item) => {
+                                    self.write_byte(f, *item.get::<u8>().unwrap())?
+                                }
+                                Sampled::Omitted(count) => self.write_omitted(f, count)?,
                             }
                         }
                         if !short {
@@ -556,29 +856,47 @@ impl PrettyPrinter {
 
                         self.write_punctuation(f, " [")?;
                         let len = list.len();
-                        for (idx, item) in list.iter().enumerate() {
-                            if !short && !is_simple {
-                                writeln!(f)?;
-                                self.indent(f, format_depth + 1)?;
-                            } else if idx > 0 {
-                                write!(f, " ")?;
-                            }
-                            self.format_peek_internal_(
-                                item,
+                        if self.style == Style::Compact && !short && is_simple {
+                            // Too long for one line: wrap the items instead
+                            self.fill_items(
+                                self.sample(list.iter(), len),
                                 f,
                                 visited,
                                 format_depth + 1,
                                 type_depth + 1,
-                                short || is_simple,
                             )?;
-
-                            if (!short && !is_simple) || idx + 1 < len {
-                                self.write_punctuation(f, ",")?;
-                            }
-                        }
-                        if !short && !is_simple {
                             writeln!(f)?;
                             self.indent(f, format_depth)?;
+                        } else {
+                            let items = self.sample(list.iter(), len);
+                            let len = items.len();
+                            for (idx, item) in items.enumerate() {
+                                if !short && !is_simple {
+                                    writeln!(f)?;
+                                    self.indent(f, format_depth + 1)?;
+                                } else if idx > 0 {
+                                    write!(f, " ")?;
+                                }
+                                match item {
+                                    Sampled::Item(item) => self.format_peek_internal_(
+                                        item,
+                                        f,
+                                        visited,
+                                        format_depth + 1,
+                                        type_depth + 1,
+                                        short || is_simple,
+                                    )?,
+                                    Sampled::Omitted(count) => self.write_omitted(f, count)?,
+                                }
+
+                                if (!short && !is_simple) || idx + 1 < len {
+                                    self.write_punctuation(f, ",")?;
+                                }
+                            }
+                            if !short && !is_simple {
+                                writeln!(f)?;
+                                self.indent(f, format_depth)?;
+                            }
                         }
                         self.write_punctuation(f, "]")?;
                     }
@@ -593,24 +911,28 @@ impl PrettyPrinter {
                 let value = value.into_set().unwrap();
                 self.write_punctuation(f, " [")?;
                 if !value.is_empty() {
-                    let len = value.len();
-                    for (idx, item) in value.iter().enumerate() {
+                    let items = self.sample(value.iter(), value.len());
+                    let len = items.len();
+                    for (idx, item) in items.enumerate() {
                         if !short {
                             writeln!(f)?;
                             self.indent(f, format_depth + 1)?;
+                        } else if idx > 0 {
+                            write!(f, " ")?;
+                        }
+                        match item {
+                            Sampled::Item(item) => self.format_peek_internal_(
+                                item,
+                                f,
+                                visited,
+                                format_depth + 1,
+                                type_depth + 1,
+                                short,
+                            )?,
+                            Sampled::Omitted(count) => self.write_omitted(f, count)?,
                         }
-                        self.format_peek_internal_(
-                            item,
-                            f,
-                            visited,
-                            format_depth + 1,
-                            type_depth + 1,
-                            short,
-                        )?;
                         if !short || idx + 1 < len {
                             self.write_punctuation(f, ",")?;
-                        } else {
-                            write!(f, " ")?;
                         }
                     }
                     if !short {
@@ -630,33 +952,39 @@ impl PrettyPrinter {
                 self.write_punctuation(f, " [")?;
 
                 if !value.is_empty() {
-                    let len = value.len();
-                    for (idx, (key, value)) in value.iter().enumerate() {
+                    let entries = self.sample(value.iter(), value.len());
+                    let len = entries.len();
+                    for (idx, entry) in entries.enumerate() {
                         if !short {
                             writeln!(f)?;
                             self.indent(f, format_depth + 1)?;
+                        } else if idx > 0 {
+                            write!(f, " ")?;
+                        }
+                        match entry {
+                            Sampled::Item((key, value)) => {
+                                self.format_peek_internal_(
+                                    key,
+                                    f,
+                                    visited,
+                                    format_depth + 1,
+                                    type_depth + 1,
+                                    key_is_short,
+                                )?;
+                                self.write_punctuation(f, " => ")?;
+                                self.format_peek_internal_(
+                                    value,
+                                    f,
+                                    visited,
+                                    format_depth + 1,
+                                    type_depth + 1,
+                                    short,
+                                )?;
+                            }
+                            Sampled::Omitted(count) => self.write_omitted(f, count)?,
                         }
-                        self.format_peek_internal_(
-                            key,
-                            f,
-                            visited,
-                            format_depth + 1,
-                            type_depth + 1,
-                            key_is_short,
-                        )?;
-                        self.write_punctuation(f, " => ")?;
-                        self.format_peek_internal_(
-                            value,
-                            f,
-                            visited,
-                            format_depth + 1,
-                            type_depth + 1,
-                            short,
-                        )?;
                         if !short || idx + 1 < len {
                             self.write_punctuation(f, ",")?;
-                        } else {
-                            write!(f, " ")?;
                         }
                     }
                     if !short {
@@ -685,7 +1013,7 @@ impl PrettyPrinter {
                         } else if let Some(n) = dyn_val.as_u64() {
                             self.format_number(f, &n.to_string())?;
                         } else if let Some(n) = dyn_val.as_f64() {
-                            self.format_number(f, &n.to_string())?;
+                            self.format_number(f, &self.float_format.format(n))?;
                         }
                     }
                     DynValueKind::String => {
@@ -704,25 +1032,32 @@ impl PrettyPrinter {
                             self.write_punctuation(f, "[]")?;
                         } else {
                             self.write_punctuation(f, "[")?;
-                            for idx in 0..len {
+                            let items = self.sample(0..len, len);
+                            let len = items.len();
+                            for (idx, item) in items.enumerate() {
                                 if !short {
                                     writeln!(f)?;
                                     self.indent(f, format_depth + 1)?;
+                                } else if idx > 0 {
+                                    write!(f, " ")?;
                                 }
-                                if let Some(elem) = dyn_val.array_get(idx) {
-                                    self.format_peek_internal_(
-                                        elem,
-                                        f,
-                                        visited,
-                                        format_depth + 1,
-                                        type_depth + 1,
-                                        short,
-                                    )?;
+                                match item {
+                                    Sampled::Item(item) => {
+                                        if let Some(elem) = dyn_val.array_get(item) {
+                                            self.format_peek_internal_(
+                                                elem,
+                                                f,
+                                                visited,
+                                                format_depth + 1,
+                                                type_depth + 1,
+                                                short,
+                                            )?;
+                                        }
+                                    }
+                                    Sampled::Omitted(count) => self.write_omitted(f, count)?,
                                 }
                                 if !short || idx + 1 < len {
                                     self.write_punctuation(f, ",")?;
-                                } else {
-                                    write!(f, " ")?;
                                 }
                             }
                             if !short {
@@ -738,22 +1073,31 @@ impl PrettyPrinter {
                             self.write_punctuation(f, "{}")?;
                         } else {
                             self.write_punctuation(f, "{")?;
-                            for idx in 0..len {
+                            let entries = self.sample(0..len, len);
+                            let len = entries.len();
+                            for (idx, entry) in entries.enumerate() {
                                 if !short {
                                     writeln!(f)?;
                                     self.indent(f, format_depth + 1)?;
+                                } else {
+                                    write!(f, " ")?;
                                 }
-                                if let Some((key, val)) = dyn_val.object_get_entry(idx) {
-                                    self.write_field_name(f, key)?;
-                                    self.write_punctuation(f, ": ")?;
-                                    self.format_peek_internal_(
-                                        val,
-                                        f,
-                                        visited,
-                                        format_depth + 1,
-                                        type_depth + 1,
-                                        short,
-                                    )?;
+                                match entry {
+                                    Sampled::Item(entry) => {
+                                        if let Some((key, val)) = dyn_val.object_get_entry(entry) {
+                                            self.write_field_name(f, key)?;
+                                            self.write_punctuation(f, ": ")?;
+                                            self.format_peek_internal_(
+                                                val,
+                                                f,
+                                                visited,
+                                                format_depth + 1,
+                                                type_depth + 1,
+                                                short,
+                                            )?;
+                                        }
+                                    }
+                                    Sampled::Omitted(count) => self.write_omitted(f, count)?,
                                 }
                                 if !short || idx + 1 < len {
                                     self.write_punctuation(f, ",")?;
@@ -774,59 +1118,62 @@ impl PrettyPrinter {
                         if let Some((year, month, day, hour, minute, second, nanos, kind)) =
                             dyn_val.as_datetime()
                         {
+                            let mut text = String::new();
                             match kind {
                                 DynDateTimeKind::Offset { offset_minutes } => {
                                     if nanos > 0 {
                                         write!(
-                                            f,
+                                            text,
                                             "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09}",
                                             year, month, day, hour, minute, second, nanos
                                         )?;
                                     } else {
                                         write!(
-                                            f,
+                                            text,
                                             "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
                                             year, month, day, hour, minute, second
                                         )?;
                                     }
                                     if offset_minutes == 0 {
-                                        write!(f, "Z")?;
+                                        write!(text, "Z")?;
                                     } else {
                                         let sign = if offset_minutes >= 0 { '+' } else { '-' };
                                         let abs = offset_minutes.abs();
-                                        write!(f, "{}{:02}:{:02}", sign, abs / 60, abs % 60)?;
+                                        write!(text, "{}{:02}:{:02}", sign, abs / 60, abs % 60)?;
                                     }
                                 }
                                 DynDateTimeKind::LocalDateTime => {
                                     if nanos > 0 {
                                         write!(
-                                            f,
+                                            text,
                                             "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09}",
                                             year, month, day, hour, minute, second, nanos
                                         )?;
                                     } else {
                                         write!(
-                                            f,
+                                            text,
                                             "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
                                             year, month, day, hour, minute, second
                                         )?;
                                     }
                                 }
                                 DynDateTimeKind::LocalDate => {
-                                    write!(f, "{:04}-{:02}-{:02}", year, month, day)?;
+                                    write!(text, "{:04}-{:02}-{:02}", year, month, day)?;
                                 }
                                 DynDateTimeKind::LocalTime => {
                                     if nanos > 0 {
                                         write!(
-                                            f,
+                                            text,
                                             "{:02}:{:02}:{:02}.{:09}",
                                             hour, minute, second, nanos
                                         )?;
                                     } else {
-                                        write!(f, "{:02}:{:02}:{:02}", hour, minute, second)?;
+                                        write!(text, "{:02}:{:02}:{:02}", hour, minute, second)?;
                                     }
                                 }
                             }
+                            let localized = self.localized_time(&text);
+                            write!(f, "{}", localized.unwrap_or(text))?;
                         }
                     }
                     DynValueKind::QName => {
@@ -850,8 +1197,8 @@ impl PrettyPrinter {
     fn format_tuple_fields<'mem, 'facet>(
         &self,
         peek_field: &dyn Fn(usize) -> Peek<'mem, 'facet>,
-        f: &mut dyn Write,
-        visited: &mut BTreeMap<ValueId, usize>,
+        f: &mut LineWriter<'_>,
+        visited: &mut SharedNodes,
         format_depth: usize,
         type_depth: usize,
         fields: &[Field],
@@ -881,10 +1228,15 @@ impl PrettyPrinter {
                             self.indent(f, format_depth + 1)?;
                         }
                     }
+                } else if idx > 0 {
+                    write!(f, " ")?;
                 }
 
-                if fields[idx].is_sensitive() {
-                    self.write_redacted(f, "[REDACTED]")?;
+                visited.field_path.push(fields[idx].name);
+                if let Some(mode) = self.redaction(visited, &fields[idx], peek_field(idx)) {
+                    self.write_redacted(f, &mode.render(peek_field(idx)))?;
+                } else if let Some(text) = self.humanized(&fields[idx], peek_field(idx)) {
+                    self.write_humanized(f, &text)?;
                 } else {
                     self.format_peek_internal_(
                         peek_field(idx),
@@ -895,11 +1247,10 @@ impl PrettyPrinter {
                         short,
                     )?;
                 }
+                visited.field_path.pop();
 
                 if !short || idx + 1 < fields.len() {
                     self.write_punctuation(f, ",")?;
-                } else {
-                    write!(f, " ")?;
                 }
             }
             if !short {
@@ -915,19 +1266,28 @@ impl PrettyPrinter {
     fn format_struct_fields<'mem, 'facet>(
         &self,
         peek_field: &dyn Fn(usize) -> Peek<'mem, 'facet>,
-        f: &mut dyn Write,
-        visited: &mut BTreeMap<ValueId, usize>,
+        f: &mut LineWriter<'_>,
+        visited: &mut SharedNodes,
         format_depth: usize,
         type_depth: usize,
         fields: &[Field],
         short: bool,
     ) -> fmt::Result {
         self.write_punctuation(f, " {")?;
-        if !fields.is_empty() {
-            for idx in 0..fields.len() {
+        let order = self.field_display_order(fields);
+        if !order.is_empty() {
+            for (pos, &idx) in order.iter().enumerate() {
                 if !short {
                     writeln!(f)?;
                     self.indent(f, format_depth + 1)?;
+
+                    if let Some(group) = self.group_header(fields, &order, pos) {
+                        self.write_comment(f, &format!("// {group}"))?;
+                        writeln!(f)?;
+                        self.indent(f, format_depth + 1)?;
+                    }
+                } else {
+                    write!(f, " ")?;
                 }
 
                 if self.show_doc_comments {
@@ -940,8 +1300,11 @@ impl PrettyPrinter {
 
                 self.write_field_name(f, fields[idx].name)?;
                 self.write_punctuation(f, ": ")?;
-                if fields[idx].is_sensitive() {
-                    self.write_redacted(f, "[REDACTED]")?;
+                visited.field_path.push(fields[idx].name);
+                if let Some(mode) = self.redaction(visited, &fields[idx], peek_field(idx)) {
+                    self.write_redacted(f, &mode.render(peek_field(idx)))?;
+                } else if let Some(text) = self.humanized(&fields[idx], peek_field(idx)) {
+                    self.write_humanized(f, &text)?;
                 } else {
                     self.format_peek_internal_(
                         peek_field(idx),
@@ -952,8 +1315,9 @@ impl PrettyPrinter {
                         short,
                     )?;
                 }
+                visited.field_path.pop();
 
-                if !short || idx + 1 < fields.len() {
+                if !short || pos + 1 < order.len() {
                     self.write_punctuation(f, ",")?;
                 } else {
                     write!(f, " ")?;
@@ -968,6 +1332,85 @@ impl PrettyPrinter {
         Ok(())
     }
 
+    /// Indices of `fields` in the order they should be printed, leaving out skipped fields
+    fn field_display_order(&self, fields: &[Field]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..fields.len())
+            .filter(|&idx| !fields[idx].flags.contains(FieldFlags::SKIP))
+            .collect();
+        if self.field_order {
+            order.sort_by_key(|&idx| fields[idx].order().unwrap_or(i64::MAX));
+        }
+        if self.group_headers {
+            // Groups are kept in the order of their first field
+            let mut groups: Vec<Option<&str>> = Vec::new();
+            for &idx in &order {
+                if !groups.contains(&fields[idx].group()) {
+                    groups.push(fields[idx].group());
+                }
+            }
+            order.sort_by_key(|&idx| groups.iter().position(|&g| g == fields[idx].group()));
+        }
+        order
+    }
+
+    /// The group header to print before the field at `pos` of `order`, if any
+    fn group_header(&self, fields: &[Field], order: &[usize], pos: usize) -> Option<&'static str> {
+        if !self.group_headers {
+            return None;
+        }
+        let group = fields[order[pos]].group()?;
+        match pos.checked_sub(1) {
+            Some(prev) if fields[order[prev]].group() == Some(group) => None,
+            _ => Some(group),
+        }
+    }
+
+    /// Write scalar items on indented lines, as many per line as fit in the
+    /// maximum width
+    fn fill_items<'mem, 'facet>(
+        &self,
+        items: impl Iterator<Item = Sampled<Peek<'mem, 'facet>>>,
+        f: &mut LineWriter<'_>,
+        visited: &mut SharedNodes,
+        format_depth: usize,
+        type_depth: usize,
+    ) -> fmt::Result {
+        for (idx, item) in items.enumerate() {
+            let mut text = String::new();
+            match item {
+                Sampled::Item(item) => self.format_peek_internal_(
+                    item,
+                    &mut LineWriter::new(&mut text, 0),
+                    visited,
+                    format_depth,
+                    type_depth,
+                    true,
+                )?,
+                Sampled::Omitted(count) => self.write_omitted(&mut text, count)?,
+            }
+            // A space before the item and a comma after it
+            if idx == 0 || f.column() + visible_width(&text) + 2 > self.max_width {
+                writeln!(f)?;
+                self.indent(f, format_depth)?;
+            } else {
+                write!(f, " ")?;
+            }
+            f.write_str(&text)?;
+            self.write_punctuation(f, ",")?;
+        }
+        Ok(())
+    }
+
+    /// Sample the `len` items of a collection as the list limits say
+    fn sample<I: Iterator>(&self, items: I, len: usize) -> Sample<I> {
+        Sample::new(items, len, self.max_list_items, self.list_tail_items)
+    }
+
+    /// Write the marker for `count` items left out of a collection
+    fn write_omitted(&self, f: &mut dyn Write, count: usize) -> fmt::Result {
+        self.write_comment(f, &format!("... {count} more"))
+    }
+
     fn indent(&self, f: &mut dyn Write, indent: usize) -> fmt::Result {
         if self.indent_size == usize::MAX {
             write!(f, "{:\t<width$}", "", width = indent)
@@ -981,25 +1424,25 @@ impl PrettyPrinter {
         &self,
         value: Peek<'_, '_>,
         f: &mut dyn Write,
-        visited: &mut BTreeMap<ValueId, usize>,
+        visited: &mut SharedNodes,
     ) -> fmt::Result {
-        self.format_peek_internal_(value, f, visited, 0, 0, false)
+        self.format_peek_internal_(value, &mut LineWriter::new(f, 0), visited, 0, 0, false)
     }
 
     /// Format a scalar value
     fn format_scalar(&self, value: Peek, f: &mut dyn Write) -> fmt::Result {
-        // Generate a color for this shape
-        let mut hasher = DefaultHasher::new();
-        value.shape().id.hash(&mut hasher);
-        let hash = hasher.finish();
-        let color = self.color_generator.generate_color(hash);
+        let color = self.scalar_color(value.shape());
 
         // Display the value
-        struct DisplayWrapper<'mem, 'facet>(&'mem Peek<'mem, 'facet>);
+        struct DisplayWrapper<'mem, 'facet>(&'mem Peek<'mem, 'facet>, &'mem FloatFormat);
 
         impl fmt::Display for DisplayWrapper<'_, '_> {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                if self.0.shape().is_display() {
+                if let Ok(&v) = self.0.get::<f64>() {
+                    self.1.write(f, v)?;
+                } else if let Ok(&v) = self.0.get::<f32>() {
+                    self.1.write(f, v)?;
+                } else if self.0.shape().is_display() {
                     write!(f, "{}", self.0)?;
                 } else if self.0.shape().is_debug() {
                     write!(f, "{:?}", self.0)?;
@@ -1011,30 +1454,107 @@ impl PrettyPrinter {
             }
         }
 
+        let display = DisplayWrapper(&value, &self.float_format);
+        let localized = self
+            .localizes()
+            .then(|| self.localized(value.shape(), display.to_string()));
+
         // Apply color if needed and display
-        if self.use_colors {
-            let rgb = Rgb(color.r, color.g, color.b);
-            write!(f, "{}", DisplayWrapper(&value).color(rgb))?;
-        } else {
-            write!(f, "{}", DisplayWrapper(&value))?;
+        match (localized, self.use_colors) {
+            (Some(text), true) => write!(f, "{}", text.color(color))?,
+            (Some(text), false) => write!(f, "{text}")?,
+            (None, true) => write!(f, "{}", display.color(color))?,
+            (None, false) => write!(f, "{display}")?,
         }
 
         Ok(())
     }
 
+    /// The theme's color for scalars of the kind of `shape`
+    fn scalar_color(&self, shape: &Shape) -> Rgb {
+        match shape.scalar_type() {
+            Some(ScalarType::Bool | ScalarType::Unit) => self.theme.keyword,
+            Some(ScalarType::Char | ScalarType::Str | ScalarType::String | ScalarType::CowStr) => {
+                self.theme.string
+            }
+            Some(
+                ScalarType::F32
+                | ScalarType::F64
+                | ScalarType::U8
+                | ScalarType::U16
+                | ScalarType::U32
+                | ScalarType::U64
+                | ScalarType::U128
+                | ScalarType::USize
+                | ScalarType::I8
+                | ScalarType::I16
+                | ScalarType::I32
+                | ScalarType::I64
+                | ScalarType::I128
+                | ScalarType::ISize,
+            ) => self.theme.number,
+            _ => match self.theme.other_scalar {
+                Some(color) => color,
+                None => {
+                    // Each type gets its own color
+                    let mut hasher = DefaultHasher::new();
+                    shape.id.hash(&mut hasher);
+                    let color = self.color_generator.generate_color(hasher.finish());
+                    Rgb(color.r, color.g, color.b)
+                }
+            },
+        }
+    }
+
     /// Write a keyword (null, true, false) with coloring
     fn write_keyword(&self, f: &mut dyn Write, keyword: &str) -> fmt::Result {
         if self.use_colors {
-            write!(f, "{}", keyword.color(tokyo_night::KEYWORD))
+            write!(f, "{}", keyword.color(self.theme.keyword))
         } else {
             write!(f, "{keyword}")
         }
     }
 
+    /// Whether scalars are rendered other than as `Display` does
+    fn localizes(&self) -> bool {
+        #[cfg(feature = "jiff")]
+        if self.time_format.is_some() {
+            return true;
+        }
+        self.locale.is_some()
+    }
+
+    /// Render the `Display` text of a scalar following the locale and time
+    /// format
+    fn localized(&self, shape: &Shape, text: String) -> String {
+        if shape.scalar_type().is_none() {
+            if !is_date_time(shape) {
+                return text;
+            }
+            return self.localized_time(&text).unwrap_or(text);
+        }
+        match self.locale {
+            Some(locale) => locale.format_scalar(shape, text),
+            None => text,
+        }
+    }
+
+    /// Render a date or time following the time format and locale, or `None`
+    /// if neither applies to it
+    fn localized_time(&self, text: &str) -> Option<String> {
+        #[cfg(feature = "jiff")]
+        if let Some(time) = self.time_format.as_ref().and_then(|t| t.format(text)) {
+            return Some(time);
+        }
+        self.locale.and_then(|locale| locale.format_date(text))
+    }
+
     /// Format a number for dynamic values
     fn format_number(&self, f: &mut dyn Write, s: &str) -> fmt::Result {
+        let localized = self.locale.map(|locale| locale.format_number(s));
+        let s = localized.as_deref().unwrap_or(s);
         if self.use_colors {
-            write!(f, "{}", s.color(tokyo_night::NUMBER))
+            write!(f, "{}", s.color(self.theme.number))
         } else {
             write!(f, "{s}")
         }
@@ -1043,7 +1563,7 @@ impl PrettyPrinter {
     /// Format a string for dynamic values
     fn format_string(&self, f: &mut dyn Write, s: &str) -> fmt::Result {
         if self.use_colors {
-            write!(f, "\"{}\"", s.color(tokyo_night::STRING))
+            write!(f, "\"{}\"", s.color(self.theme.string))
         } else {
             write!(f, "{s:?}")
         }
@@ -1070,7 +1590,7 @@ impl PrettyPrinter {
         let type_name = TypeNameWriter(peek);
 
         if self.use_colors {
-            write!(f, "{}", type_name.color(tokyo_night::TYPE_NAME).bold())
+            write!(f, "{}", type_name.color(self.theme.type_name).bold())
         } else {
             write!(f, "{type_name}")
         }
@@ -1087,7 +1607,7 @@ impl PrettyPrinter {
     /// Write styled field name to formatter
     fn write_field_name(&self, f: &mut dyn Write, name: &str) -> fmt::Result {
         if self.use_colors {
-            write!(f, "{}", name.color(tokyo_night::FIELD_NAME))
+            write!(f, "{}", name.color(self.theme.field_name))
         } else {
             write!(f, "{name}")
         }
@@ -1095,26 +1615,70 @@ impl PrettyPrinter {
 
     /// Write styled punctuation to formatter
     fn write_punctuation(&self, f: &mut dyn Write, text: &str) -> fmt::Result {
-        if self.use_colors {
-            write!(f, "{}", text.dimmed())
-        } else {
-            write!(f, "{text}")
+        match (self.use_colors, self.theme.punctuation) {
+            (true, Some(color)) => write!(f, "{}", text.color(color)),
+            (true, None) => write!(f, "{}", text.dimmed()),
+            (false, _) => write!(f, "{text}"),
         }
     }
 
     /// Write styled comment to formatter
     fn write_comment(&self, f: &mut dyn Write, text: &str) -> fmt::Result {
         if self.use_colors {
-            write!(f, "{}", text.color(tokyo_night::MUTED))
+            write!(f, "{}", text.color(self.theme.comment))
         } else {
             write!(f, "{text}")
         }
     }
 
+    /// Write a byte as two hex digits, colored by its value
+    fn write_byte(&self, f: &mut dyn Write, byte: u8) -> fmt::Result {
+        if self.use_colors {
+            let mut hasher = DefaultHasher::new();
+            byte.hash(&mut hasher);
+            let hash = hasher.finish();
+            let color = self.color_generator.generate_color(hash);
+            let rgb = Rgb(color.r, color.g, color.b);
+            write!(f, "{}", format!("{byte:02x}").color(rgb))
+        } else {
+            write!(f, "{byte:02x}")
+        }
+    }
+
     /// Write styled redacted value to formatter
+    /// The humanized text for a field with a known unit, if enabled
+    /// How the value of `field` is redacted, if it is
+    fn redaction(
+        &self,
+        visited: &SharedNodes,
+        field: &Field,
+        value: Peek<'_, '_>,
+    ) -> Option<RedactionMode> {
+        let chosen = self
+            .redaction_policy
+            .as_ref()
+            .and_then(|policy| policy.redact(&visited.field_path, field, value));
+        chosen.or_else(|| field.is_sensitive().then(|| self.redaction_mode.clone()))
+    }
+
+    fn humanized(&self, field: &Field, value: Peek<'_, '_>) -> Option<String> {
+        if !self.humanize_units {
+            return None;
+        }
+        units::humanize(field.unit()?, value)
+    }
+
+    fn write_humanized(&self, f: &mut dyn Write, text: &str) -> fmt::Result {
+        if self.use_colors {
+            write!(f, "{}", text.color(self.theme.number))
+        } else {
+            write!(f, "{text}")
+        }
+    }
+
     fn write_redacted(&self, f: &mut dyn Write, text: &str) -> fmt::Result {
         if self.use_colors {
-            write!(f, "{}", text.color(tokyo_night::ERROR).bold())
+            write!(f, "{}", text.color(self.theme.redacted).bold())
         } else {
             write!(f, "{text}")
         }
@@ -1139,18 +1703,13 @@ impl PrettyPrinter {
         let mut output = SpanTrackingOutput::new();
         let printer = Self {
             use_colors: false, // Always disable colors for span tracking
-            indent_size: self.indent_size,
-            max_depth: self.max_depth,
-            color_generator: self.color_generator.clone(),
-            list_u8_as_bytes: self.list_u8_as_bytes,
-            minimal_option_names: self.minimal_option_names,
-            show_doc_comments: self.show_doc_comments,
+            ..self.clone()
         };
         printer
             .format_unified(
                 value,
                 &mut output,
-                &mut BTreeMap::new(),
+                &mut SharedNodes::scan(value),
                 0,
                 0,
                 false,
@@ -1170,36 +1729,76 @@ impl PrettyPrinter {
         &self,
         value: Peek<'_, '_>,
         out: &mut O,
-        visited: &mut BTreeMap<ValueId, usize>,
+        visited: &mut SharedNodes,
         format_depth: usize,
         type_depth: usize,
         short: bool,
         current_path: Path,
     ) -> fmt::Result {
+        // Record the start of this value
+        let value_start = out.position();
+
         let mut value = value;
+        let mut entered = Vec::new();
         while let Ok(ptr) = value.into_pointer()
             && let Some(pointee) = ptr.borrow_inner()
         {
             value = pointee;
+            let stop = match visited.enter(value.id()) {
+                PointeeVisit::Unique => {
+                    entered.push(value.id());
+                    false
+                }
+                PointeeVisit::First(label) => {
+                    entered.push(value.id());
+                    write!(out, "&{label} ")?;
+                    false
+                }
+                PointeeVisit::Repeat(label) => {
+                    write!(out, "*{label}")?;
+                    true
+                }
+                PointeeVisit::Cycle => {
+                    write!(out, "{} /* cycle */", value.shape().type_identifier)?;
+                    true
+                }
+            };
+            if stop {
+                entered.into_iter().for_each(|id| visited.leave(id));
+                let value_end = out.position();
+                out.record_span(current_path, (value_start, value_end));
+                return Ok(());
+            }
         }
-        let shape = value.shape();
 
-        // Record the start of this value
-        let value_start = out.position();
+        let result = self.format_unified_pointee(
+            value,
+            out,
+            visited,
+            format_depth,
+            type_depth,
+            short,
+            current_path,
+            value_start,
+        );
+        entered.into_iter().for_each(|id| visited.leave(id));
+        result
+    }
 
-        if let Some(prev_type_depth) = visited.insert(value.id(), type_depth) {
-            write!(out, "{} {{ ", shape.type_identifier)?;
-            write!(
-                out,
-                "/* cycle detected at {} (first seen at type_depth {}) */",
-                value.id(),
-                prev_type_depth,
-            )?;
-            visited.remove(&value.id());
-            let value_end = out.position();
-            out.record_span(current_path, (value_start, value_end));
-            return Ok(());
-        }
+    /// Format a value that is not a pointer (or whose pointers were already followed)
+    #[allow(clippy::too_many_arguments)]
+    fn format_unified_pointee<O: FormatOutput>(
+        &self,
+        value: Peek<'_, '_>,
+        out: &mut O,
+        visited: &mut SharedNodes,
+        format_depth: usize,
+        type_depth: usize,
+        short: bool,
+        current_path: Path,
+        value_start: usize,
+    ) -> fmt::Result {
+        let shape = value.shape();
 
         match (shape.def, shape.ty) {
             (_, Type::Primitive(PrimitiveType::Textual(TextualType::Str))) => {
@@ -1231,6 +1830,26 @@ impl PrettyPrinter {
                     write!(out, "None")?;
                 }
             }
+            (Def::Pointer(def), _) if def.is_lock() => {
+                let ptr = value.into_pointer().unwrap();
+                write!(out, "{}(", shape.type_identifier)?;
+                let inner = ptr.with_locked(|inner| {
+                    self.format_unified(
+                        inner,
+                        out,
+                        visited,
+                        format_depth,
+                        type_depth + 1,
+                        short,
+                        current_path.clone(),
+                    )
+                });
+                match inner {
+                    Some(result) => result?,
+                    None => write!(out, "/* locked */")?,
+                }
+                write!(out, ")")?;
+            }
             (
                 _,
                 Type::User(UserType::Struct(
@@ -1244,10 +1863,17 @@ impl PrettyPrinter {
                 if matches!(ty.kind, StructKind::Struct) {
                     let struct_peek = value.into_struct().unwrap();
                     write!(out, " {{")?;
-                    for (i, field) in ty.fields.iter().enumerate() {
+                    let order = self.field_display_order(ty.fields);
+                    for (pos, &i) in order.iter().enumerate() {
+                        let field = &ty.fields[i];
                         if !short {
                             writeln!(out)?;
                             self.indent_to_output(out, format_depth + 1)?;
+
+                            if let Some(group) = self.group_header(ty.fields, &order, pos) {
+                                writeln!(out, "// {group}")?;
+                                self.indent_to_output(out, format_depth + 1)?;
+                            }
                         }
                         // Record field name span
                         let field_name_start = out.position();
@@ -1281,7 +1907,7 @@ impl PrettyPrinter {
                             (field_value_start, field_value_end),
                         );
 
-                        if !short || i + 1 < ty.fields.len() {
+                        if !short || pos + 1 < order.len() {
                             write!(out, ",")?;
                         }
                     }
@@ -1348,10 +1974,19 @@ impl PrettyPrinter {
                             StructKind::Unit => {}
                             StructKind::Struct => {
                                 write!(out, " {{")?;
-                                for (i, field) in variant.data.fields.iter().enumerate() {
+                                let fields = variant.data.fields;
+                                let order = self.field_display_order(fields);
+                                for (pos, &i) in order.iter().enumerate() {
+                                    let field = &fields[i];
                                     if !short {
                                         writeln!(out)?;
                                         self.indent_to_output(out, format_depth + 1)?;
+
+                                        if let Some(group) = self.group_header(fields, &order, pos)
+                                        {
+                                            writeln!(out, "// {group}")?;
+                                            self.indent_to_output(out, format_depth + 1)?;
+                                        }
                                     }
                                     let field_name_start = out.position();
                                     write!(out, "{}", field.name)?;
@@ -1383,7 +2018,7 @@ impl PrettyPrinter {
                                         (field_value_start, field_value_end),
                                     );
 
-                                    if !short || i + 1 < variant.data.fields.len() {
+                                    if !short || pos + 1 < order.len() {
                                         write!(out, ",")?;
                                     }
                                 }
@@ -1525,8 +2160,6 @@ impl PrettyPrinter {
             }
         }
 
-        visited.remove(&value.id());
-
         // Record span for this value
         let value_end = out.position();
         out.record_span(current_path, (value_start, value_end));
@@ -1535,8 +2168,21 @@ impl PrettyPrinter {
     }
 
     fn format_scalar_to_output(&self, value: Peek<'_, '_>, out: &mut impl Write) -> fmt::Result {
+        if self.localizes() {
+            let mut text = String::new();
+            self.format_scalar_plain(value, &mut text)?;
+            return out.write_str(&self.localized(value.shape(), text));
+        }
+        self.format_scalar_plain(value, out)
+    }
+
+    fn format_scalar_plain(&self, value: Peek<'_, '_>, out: &mut impl Write) -> fmt::Result {
         // Use Display or Debug trait to format scalar values
-        if value.shape().is_display() {
+        if let Ok(&v) = value.get::<f64>() {
+            self.float_format.write(out, v)
+        } else if let Ok(&v) = value.get::<f32>() {
+            self.float_format.write(out, v)
+        } else if value.shape().is_display() {
             write!(out, "{}", value)
         } else if value.shape().is_debug() {
             write!(out, "{:?}", value)
@@ -1673,9 +2319,9 @@ mod tests {
         let printer = PrettyPrinter::default();
         assert_eq!(printer.indent_size, 2);
         assert_eq!(printer.max_depth, None);
-        // use_colors defaults to true unless NO_COLOR is set
+        // use_colors defaults to true unless NO_COLOR is set or stdout isn't a terminal
         // In tests, NO_COLOR=1 is set via nextest config for consistent snapshots
-        assert_eq!(printer.use_colors, std::env::var_os("NO_COLOR").is_none());
+        assert_eq!(printer.use_colors, colors_by_default());
     }
 
     #[test]
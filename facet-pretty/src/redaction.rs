@@ -0,0 +1,213 @@
+//! Redaction of sensitive values
+
+use alloc::borrow::Cow;
+use core::fmt::Write as _;
+use core::hash::Hasher;
+
+use facet_core::Field;
+use facet_reflect::Peek;
+use sha2::{Digest, Sha256};
+
+/// Number of digest bytes shown in a hash preview (rendered as hex)
+const HASH_PREVIEW_BYTES: usize = 4;
+
+/// How values of fields marked `#[facet(sensitive)]` are rendered
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RedactionMode {
+    /// Replace the value with `[REDACTED]`
+    #[default]
+    Placeholder,
+
+    /// Replace the value with a short SHA-256 preview of its hash, e.g. `[sha256:ab12cd34…]`
+    ///
+    /// The digest is computed over the bytes the value feeds to its `Hash`
+    /// implementation (falling back to a structural hash for types without
+    /// one), so two redacted values can be compared for equality without
+    /// either of them being printed.
+    HashPreview,
+
+    /// Replace the value with the given text
+    Text(Cow<'static, str>),
+
+    /// Show only the last `n` characters of the value, masking the rest with
+    /// `*`, e.g. `************1234`
+    ///
+    /// Values with `n` characters or fewer are masked entirely, and values
+    /// that can't be displayed fall back to [`RedactionMode::Placeholder`].
+    ShowLast(usize),
+}
+
+impl RedactionMode {
+    /// Render the replacement text for a redacted value
+    pub fn render(&self, value: Peek<'_, '_>) -> String {
+        match self {
+            RedactionMode::Placeholder => String::from("[REDACTED]"),
+            RedactionMode::HashPreview => {
+                let digest = sha256_preview(value);
+                let mut out = String::from("[sha256:");
+                for byte in &digest[..HASH_PREVIEW_BYTES] {
+                    write!(out, "{byte:02x}").unwrap();
+                }
+                out.push_str("…]");
+                out
+            }
+            RedactionMode::Text(text) => text.to_string(),
+            RedactionMode::ShowLast(n) => {
+                let value = value.innermost_peek();
+                let text = match value.as_str() {
+                    Some(s) => s.to_string(),
+                    None if value.shape().vtable.has_display() => value.to_string(),
+                    None => return RedactionMode::Placeholder.render(value),
+                };
+                let len = text.chars().count();
+                let shown = if len > *n { *n } else { 0 };
+                let mut out = "*".repeat(len - shown);
+                out.extend(text.chars().skip(len - shown));
+                out
+            }
+        }
+    }
+}
+
+/// Decides which field values a [`PrettyPrinter`](crate::PrettyPrinter)
+/// redacts, and how
+///
+/// The policy is asked about every field printed, with the names of the
+/// fields leading to it (outermost first, ending with the field itself).
+/// Fields marked `#[facet(sensitive)]` are always redacted: for those, the
+/// policy only picks the [`RedactionMode`], and the printer's default mode is
+/// used when it returns `None`.
+///
+/// Implemented for closures, so a policy can be written inline:
+///
+/// ```
+/// use facet::{Facet, Field};
+/// use facet_reflect::Peek;
+/// use facet_pretty::{PrettyPrinter, RedactionMode};
+///
+/// #[derive(Facet)]
+/// struct Payment {
+///     card_number: String,
+///     amount: u32,
+/// }
+///
+/// let printer = PrettyPrinter::new()
+///     .with_colors(false)
+///     .with_redaction_policy(|path: &[&str], _field: &Field, _value: Peek<'_, '_>| {
+///         (path.last() == Some(&"card_number")).then_some(RedactionMode::ShowLast(4))
+///     });
+/// let payment = Payment {
+///     card_number: "4111111111111111".into(),
+///     amount: 42,
+/// };
+/// assert!(printer.format(&payment).contains("************1111"));
+/// ```
+pub trait RedactionPolicy: Send + Sync {
+    /// How to redact the value of `field`, or `None` to leave it alone
+    fn redact(&self, path: &[&str], field: &Field, value: Peek<'_, '_>) -> Option<RedactionMode>;
+}
+
+impl<F> RedactionPolicy for F
+where
+    F: Fn(&[&str], &Field, Peek<'_, '_>) -> Option<RedactionMode> + Send + Sync,
+{
+    fn redact(&self, path: &[&str], field: &Field, value: Peek<'_, '_>) -> Option<RedactionMode> {
+        self(path, field, value)
+    }
+}
+
+/// Compute the SHA-256 digest of the value's hash input
+fn sha256_preview(value: Peek<'_, '_>) -> [u8; 32] {
+    let mut hasher = Sha256Hasher(Sha256::new());
+    if value.hash(&mut hasher).is_err() {
+        value.structural_hash(&mut hasher);
+    }
+    hasher.0.finalize().into()
+}
+
+/// Adapter feeding everything written to a [`Hasher`] into SHA-256
+struct Sha256Hasher(Sha256);
+
+impl Hasher for Sha256Hasher {
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().finalize();
+        u64::from_le_bytes(digest[..8].try_into().unwrap())
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholder() {
+        let secret = String::from("hunter2");
+        assert_eq!(
+            RedactionMode::Placeholder.render(Peek::new(&secret)),
+            "[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        let secret = String::from("hunter2");
+        let mode = RedactionMode::Text("<withheld>".into());
+        assert_eq!(mode.render(Peek::new(&secret)), "<withheld>");
+    }
+
+    #[test]
+    fn test_show_last() {
+        let card = String::from("4111111111111111");
+        assert_eq!(
+            RedactionMode::ShowLast(4).render(Peek::new(&card)),
+            "************1111"
+        );
+
+        let pin = 1234u32;
+        assert_eq!(RedactionMode::ShowLast(4).render(Peek::new(&pin)), "****");
+
+        let bytes = vec![1u8, 2, 3];
+        assert_eq!(
+            RedactionMode::ShowLast(4).render(Peek::new(&bytes)),
+            "[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn test_hash_preview_is_deterministic() {
+        let a = String::from("hunter2");
+        let b = String::from("hunter2");
+        let c = String::from("hunter3");
+
+        let render = |s: &String| RedactionMode::HashPreview.render(Peek::new(s));
+        assert_eq!(render(&a), render(&b));
+        assert_ne!(render(&a), render(&c));
+        assert!(render(&a).starts_with("[sha256:"));
+        assert!(render(&a).ends_with("…]"));
+        assert!(!render(&a).contains("hunter2"));
+    }
+
+    #[test]
+    fn test_hash_preview_without_hash_impl() {
+        #[derive(facet::Facet)]
+        struct Credentials {
+            user: String,
+            token: Vec<u8>,
+        }
+
+        let a = Credentials {
+            user: "root".into(),
+            token: vec![1, 2, 3],
+        };
+        let b = Credentials {
+            user: "root".into(),
+            token: vec![1, 2, 4],
+        };
+        let render = |c: &Credentials| RedactionMode::HashPreview.render(Peek::new(c));
+        assert_ne!(render(&a), render(&b));
+    }
+}
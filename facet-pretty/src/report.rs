@@ -0,0 +1,161 @@
+//! Application state dumps for panic hooks and error reports
+//!
+//! Register the values you want to see in a crash dump with [`register_state`],
+//! then either call [`install_panic_hook`] or attach a [`StateReport`] to your
+//! error reports. Values are rendered with the configured [`PrettyPrinter`], so
+//! fields marked `#[facet(sensitive)]` stay redacted.
+
+use alloc::borrow::Cow;
+use alloc::sync::Arc;
+use core::fmt::{self, Display};
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError, TryLockError};
+
+use facet_core::Facet;
+
+use crate::printer::PrettyPrinter;
+
+type RenderFn = Box<dyn Fn(&PrettyPrinter) -> String + Send + Sync>;
+
+struct StateEntry {
+    id: u64,
+    name: Cow<'static, str>,
+    render: RenderFn,
+}
+
+static REGISTRY: Mutex<Vec<Arc<StateEntry>>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Handle to a value registered with [`register_state`]
+///
+/// The value is removed from the registry when the handle is dropped.
+#[must_use = "the state is unregistered as soon as the handle is dropped"]
+#[derive(Debug)]
+pub struct StateHandle {
+    id: u64,
+}
+
+impl StateHandle {
+    /// Keep the value registered for the rest of the process lifetime
+    pub fn leak(self) {
+        core::mem::forget(self);
+    }
+}
+
+impl Drop for StateHandle {
+    fn drop(&mut self) {
+        let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+        registry.retain(|entry| entry.id != self.id);
+    }
+}
+
+/// Register a shared value to be included in panic dumps and state reports
+pub fn register_state<T>(name: impl Into<Cow<'static, str>>, value: Arc<T>) -> StateHandle
+where
+    T: Facet<'static> + Send + Sync + 'static,
+{
+    register_state_with(name, move |printer| printer.format(&*value))
+}
+
+/// Register a closure that renders a piece of application state
+///
+/// Use this for state behind a lock or any other value that cannot be shared
+/// as an `Arc<T>`. The closure is called from inside the panic hook, so it
+/// must not panic and should not block on locks that the panicking thread
+/// may hold — prefer `try_lock` there.
+pub fn register_state_with<F>(name: impl Into<Cow<'static, str>>, render: F) -> StateHandle
+where
+    F: Fn(&PrettyPrinter) -> String + Send + Sync + 'static,
+{
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+    registry.push(Arc::new(StateEntry {
+        id,
+        name: name.into(),
+        render: Box::new(render),
+    }));
+    StateHandle { id }
+}
+
+/// A rendering of all registered application state
+///
+/// Implements [`Display`], so it can be attached to error reports
+/// (e.g. as an `eyre`/`anyhow` context or a `tracing` field) without
+/// rendering anything until the report is actually printed.
+#[derive(Clone)]
+pub struct StateReport {
+    printer: PrettyPrinter,
+}
+
+impl StateReport {
+    /// Create a report that renders values with the given printer
+    pub fn new(printer: PrettyPrinter) -> Self {
+        Self { printer }
+    }
+
+    /// Returns true if no state is currently registered
+    pub fn is_empty(&self) -> bool {
+        REGISTRY
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty()
+    }
+
+    fn write_entries(&self, f: &mut dyn fmt::Write, registry: &[Arc<StateEntry>]) -> fmt::Result {
+        for entry in registry {
+            writeln!(f, "{}: {}", entry.name, (entry.render)(&self.printer))?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for StateReport {
+    fn default() -> Self {
+        Self::new(PrettyPrinter::default())
+    }
+}
+
+impl Display for StateReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Render closures run outside the lock, so they may register state
+        // or print reports themselves
+        let registry = REGISTRY
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        self.write_entries(f, &registry)
+    }
+}
+
+/// Render all registered state with the default printer
+pub fn state_report() -> StateReport {
+    StateReport::default()
+}
+
+/// Install a panic hook that dumps all registered state to stderr
+///
+/// The previously installed hook runs first, so the usual panic message
+/// (and backtrace, if enabled) is still printed.
+pub fn install_panic_hook(printer: PrettyPrinter) {
+    let previous = std::panic::take_hook();
+    let report = StateReport::new(printer);
+    std::panic::set_hook(Box::new(move |info| {
+        previous(info);
+
+        // A panic while the registry is locked (e.g. while registering state
+        // on this thread) must not deadlock the hook.
+        let registry = match REGISTRY.try_lock() {
+            Ok(registry) => registry.clone(),
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner().clone(),
+            Err(TryLockError::WouldBlock) => return,
+        };
+        if registry.is_empty() {
+            return;
+        }
+
+        let mut dump = String::from("application state at time of panic:\n");
+        if report.write_entries(&mut dump, &registry).is_ok() {
+            eprint!("{dump}");
+        }
+    }));
+}
@@ -0,0 +1,87 @@
+//! Leaving the middle out of long collections
+
+/// An item of a collection, or the marker standing in for the items left out
+pub(crate) enum Sampled<T> {
+    Item(T),
+    Omitted(usize),
+}
+
+/// The first `head` items of a collection, a marker for the items after
+/// them, and the last `tail` items
+pub(crate) struct Sample<I> {
+    items: I,
+    /// Items yielded so far, counting the omitted ones
+    index: usize,
+    head: usize,
+    omitted: usize,
+    /// Items and markers left to yield
+    remaining: usize,
+}
+
+impl<I: Iterator> Sample<I> {
+    /// Sample the `len` items of `items`, keeping at most `max` of the first
+    /// ones and `tail` of the last ones
+    ///
+    /// Collections with no more than `max + tail` items are kept whole.
+    pub(crate) fn new(items: I, len: usize, max: Option<usize>, tail: usize) -> Self {
+        let (head, omitted) = match max {
+            Some(max) if len > max.saturating_add(tail) => (max, len - max - tail),
+            _ => (len, 0),
+        };
+        Self {
+            items,
+            index: 0,
+            head,
+            omitted,
+            remaining: len - omitted + usize::from(omitted > 0),
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Sample<I> {
+    type Item = Sampled<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        if self.index == self.head && self.omitted > 0 {
+            self.items.nth(self.omitted - 1)?;
+            self.index += self.omitted;
+            return Some(Sampled::Omitted(self.omitted));
+        }
+        self.index += 1;
+        self.items.next().map(Sampled::Item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<I: Iterator> ExactSizeIterator for Sample<I> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(len: usize, max: Option<usize>, tail: usize) -> Vec<String> {
+        Sample::new(0..len, len, max, tail)
+            .map(|item| match item {
+                Sampled::Item(n) => n.to_string(),
+                Sampled::Omitted(count) => format!("({count})"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(sample(5, None, 2), ["0", "1", "2", "3", "4"]);
+        assert_eq!(sample(5, Some(3), 0), ["0", "1", "2", "(2)"]);
+        assert_eq!(sample(7, Some(2), 2), ["0", "1", "(3)", "5", "6"]);
+        assert_eq!(sample(4, Some(2), 2), ["0", "1", "2", "3"]);
+        assert_eq!(sample(3, Some(0), 1), ["(2)", "2"]);
+        assert_eq!(Sample::new(0..7, 7, Some(2), 2).len(), 5);
+    }
+}
@@ -0,0 +1,95 @@
+//! Layout styles for the printer
+
+use core::fmt::{self, Write};
+
+/// How the printer lays values out over lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Style {
+    /// One field or item per line, each followed by a comma
+    #[default]
+    Expanded,
+
+    /// Values that fit in the printer's width on one line, like
+    /// `Blah { foo: 42, bar: "hi" }`, without trailing commas
+    ///
+    /// A value that doesn't fit is expanded over multiple lines, and its
+    /// fields and items are given the same treatment, so only the parts
+    /// that need it take more than one line.
+    Compact,
+}
+
+/// The number of columns `text` takes on a terminal, leaving out the
+/// escape sequences used for colors
+pub(crate) fn visible_width(text: &str) -> usize {
+    let mut column = Column::default();
+    column.advance(text);
+    column.column
+}
+
+/// Where the cursor is on the current line, following output that may be
+/// written a few characters at a time
+#[derive(Debug, Clone, Copy, Default)]
+struct Column {
+    column: usize,
+    escape: Escape,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Escape {
+    #[default]
+    None,
+    /// Just after `\x1b`
+    Start,
+    /// Inside a CSI sequence, which ends with a byte in `@..=~`
+    Csi,
+}
+
+impl Column {
+    fn advance(&mut self, text: &str) {
+        for c in text.chars() {
+            match (self.escape, c) {
+                (Escape::None, '\x1b') => self.escape = Escape::Start,
+                (Escape::None, '\n') => self.column = 0,
+                // Tab stops vary, assume 4 columns like the printer's tab indentation
+                (Escape::None, '\t') => self.column += 4,
+                (Escape::None, _) => self.column += 1,
+                (Escape::Start, '[') => self.escape = Escape::Csi,
+                (Escape::Start, _) => self.escape = Escape::None,
+                (Escape::Csi, '@'..='~') => self.escape = Escape::None,
+                (Escape::Csi, _) => {}
+            }
+        }
+    }
+}
+
+/// A writer that keeps track of the column it's at, so the printer can tell
+/// how much room is left on the line
+pub(crate) struct LineWriter<'a> {
+    out: &'a mut dyn Write,
+    column: Column,
+}
+
+impl<'a> LineWriter<'a> {
+    /// Write to `out`, which is already at `column`
+    pub(crate) fn new(out: &'a mut dyn Write, column: usize) -> Self {
+        Self {
+            out,
+            column: Column {
+                column,
+                escape: Escape::None,
+            },
+        }
+    }
+
+    /// The visible column the next character goes to
+    pub(crate) fn column(&self) -> usize {
+        self.column.column
+    }
+}
+
+impl Write for LineWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.column.advance(s);
+        self.out.write_str(s)
+    }
+}
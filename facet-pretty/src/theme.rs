@@ -0,0 +1,101 @@
+//! Color themes, and whether to use colors at all
+
+use std::io::IsTerminal;
+
+use owo_colors::Rgb;
+
+use crate::printer::tokyo_night;
+
+/// The colors the printer uses for each kind of token
+///
+/// Start from a preset and change what you need:
+///
+/// ```
+/// use facet_pretty::{PrettyPrinter, Theme, tokyo_night};
+///
+/// let theme = Theme {
+///     field_name: tokyo_night::CYAN,
+///     ..Theme::dark()
+/// };
+/// let printer = PrettyPrinter::new().with_theme(theme);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Type names, in bold
+    pub type_name: Rgb,
+    /// Struct field names
+    pub field_name: Rgb,
+    /// Brackets, commas and other punctuation; `None` dims the terminal's
+    /// own text color
+    pub punctuation: Option<Rgb>,
+    /// Doc comments and other annotations
+    pub comment: Rgb,
+    /// Strings and characters
+    pub string: Rgb,
+    /// Numbers, and the sizes and durations humanized from them
+    pub number: Rgb,
+    /// Booleans, `()` and `null`
+    pub keyword: Rgb,
+    /// Other scalars, like UUIDs, paths and timestamps; `None` gives each
+    /// type its own color from the printer's
+    /// [`ColorGenerator`](crate::ColorGenerator)
+    pub other_scalar: Option<Rgb>,
+    /// Redacted values, in bold
+    pub redacted: Rgb,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// For dark backgrounds, from the [Tokyo Night](tokyo_night) palette
+    pub const fn dark() -> Self {
+        Self {
+            type_name: tokyo_night::TYPE_NAME,
+            field_name: tokyo_night::FIELD_NAME,
+            punctuation: None,
+            comment: tokyo_night::MUTED,
+            string: tokyo_night::STRING,
+            number: tokyo_night::NUMBER,
+            keyword: tokyo_night::KEYWORD,
+            other_scalar: None,
+            redacted: tokyo_night::ERROR,
+        }
+    }
+
+    /// For light backgrounds, from the Tokyo Night Day palette
+    pub const fn light() -> Self {
+        Self {
+            // #2e7de9
+            type_name: Rgb(46, 125, 233),
+            // #118c74
+            field_name: Rgb(17, 140, 116),
+            punctuation: None,
+            // #6172b0
+            comment: Rgb(97, 114, 176),
+            // #587539
+            string: Rgb(88, 117, 57),
+            // #b15c00
+            number: Rgb(177, 92, 0),
+            // #9854f1
+            keyword: Rgb(152, 84, 241),
+            // #007197, generated colors are too light on white
+            other_scalar: Some(Rgb(0, 113, 151)),
+            // #c64343
+            redacted: Rgb(198, 67, 67),
+        }
+    }
+}
+
+/// Whether to color output when not told either way
+///
+/// Not when `NO_COLOR` is set to anything but the empty string, see
+/// <https://no-color.org>, nor when stdout isn't a terminal, e.g. when it
+/// is piped to a file.
+pub(crate) fn colors_by_default() -> bool {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    !no_color && std::io::stdout().is_terminal()
+}
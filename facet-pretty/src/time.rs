@@ -0,0 +1,195 @@
+//! Rendering timestamps in a chosen time zone, or relative to now
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::time::Duration;
+
+use jiff::Timestamp;
+use jiff::tz::{Offset, TimeZone};
+
+use crate::units::humanize_seconds;
+
+/// Controls how the printer renders timestamps
+///
+/// Applies to time-affinity scalars (e.g. `chrono`, `jiff` or `time` types,
+/// and datetimes in dynamic values) whose text is an instant: an RFC 3339
+/// timestamp like `2024-01-15T10:30:00Z` or `2024-01-15T11:30:00+01:00`,
+/// possibly followed by a time zone annotation, or `chrono`'s
+/// `2024-01-15 10:30:00 UTC`. Dates and datetimes without an offset don't
+/// name an instant, so they are left to the [locale](crate::Locale).
+///
+/// ```
+/// use facet_pretty::{PrettyPrinter, TimeFormat};
+/// use jiff::tz::TimeZone;
+///
+/// let paris = TimeZone::get("Europe/Paris").unwrap();
+/// let printer = PrettyPrinter::new()
+///     .with_colors(false)
+///     .with_time_format(TimeFormat::in_zone(paris).with_pattern("%d %b %Y %H:%M %Z"));
+///
+/// let time: jiff::Timestamp = "2024-07-14T08:00:00Z".parse().unwrap();
+/// assert_eq!(printer.format(&time), "14 Jul 2024 10:00 CEST");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeFormat {
+    zone: TimeZone,
+    pattern: Option<String>,
+    relative_within: Option<Duration>,
+    now: Option<Timestamp>,
+}
+
+impl TimeFormat {
+    /// Times in UTC: `2024-01-15T10:30:00Z`
+    pub fn utc() -> Self {
+        Self::in_zone(TimeZone::UTC)
+    }
+
+    /// Times in the system time zone, falling back to UTC if it can't be
+    /// determined
+    pub fn local() -> Self {
+        Self::in_zone(TimeZone::system())
+    }
+
+    /// Times in `zone`, with its offset: `2024-01-15T11:30:00+01:00`
+    ///
+    /// Zones are looked up by name with [`TimeZone::get`], e.g.
+    /// `TimeZone::get("Europe/Paris")`.
+    pub fn in_zone(zone: TimeZone) -> Self {
+        Self {
+            zone,
+            pattern: None,
+            relative_within: None,
+            now: None,
+        }
+    }
+
+    /// Render times with a `strftime`-style pattern, like `%Y-%m-%d %H:%M %Z`
+    ///
+    /// See [`jiff::fmt::strtime`] for the directives. A time the pattern
+    /// can't render is written in RFC 3339 form instead.
+    pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    /// Render times less than `within` away from now relative to it, like
+    /// `3m 20s ago` or `in 5s`
+    ///
+    /// Meant for log-oriented output, where recent events matter most.
+    pub fn with_relative_within(mut self, within: Duration) -> Self {
+        self.relative_within = Some(within);
+        self
+    }
+
+    /// Measure relative times from `now` rather than the current time, e.g.
+    /// for reproducible output
+    pub fn with_now(mut self, now: Timestamp) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Render the instant in `text` following this format
+    ///
+    /// Returns `None` if `text` isn't an instant.
+    pub fn format(&self, text: &str) -> Option<String> {
+        let timestamp = parse_instant(text)?;
+
+        if let Some(within) = self.relative_within {
+            let now = self.now.unwrap_or_else(Timestamp::now);
+            let elapsed = now.as_second() - timestamp.as_second();
+            if elapsed.unsigned_abs() <= within.as_secs() {
+                let ago = humanize_seconds(elapsed.unsigned_abs() as f64);
+                return Some(if elapsed < 0 {
+                    format!("in {ago}")
+                } else {
+                    format!("{ago} ago")
+                });
+            }
+        }
+
+        let zoned = timestamp.to_zoned(self.zone.clone());
+        if let Some(pattern) = &self.pattern
+            && let Ok(text) = jiff::fmt::strtime::format(pattern.as_bytes(), &zoned)
+        {
+            return Some(text);
+        }
+        let offset = zoned.offset();
+        Some(if offset == Offset::UTC {
+            timestamp.to_string()
+        } else {
+            timestamp.display_with_offset(offset).to_string()
+        })
+    }
+}
+
+/// Parses an RFC 3339 timestamp, or `chrono`'s `Display` form of one
+fn parse_instant(text: &str) -> Option<Timestamp> {
+    if let Ok(timestamp) = text.parse() {
+        return Some(timestamp);
+    }
+    // `2024-01-15 10:30:00 UTC` or `2024-01-15 10:30:00 +01:00`
+    let (datetime, offset) = text.rsplit_once(' ')?;
+    let offset = if offset == "UTC" { "Z" } else { offset };
+    format!("{datetime}{offset}").parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zones() {
+        let utc = TimeFormat::utc();
+        assert_eq!(
+            utc.format("2024-01-15T11:30:00+01:00").as_deref(),
+            Some("2024-01-15T10:30:00Z")
+        );
+        let tokyo = TimeFormat::in_zone(TimeZone::get("Asia/Tokyo").unwrap());
+        assert_eq!(
+            tokyo.format("2024-01-15T10:30:00.5Z").as_deref(),
+            Some("2024-01-15T19:30:00.5+09:00")
+        );
+    }
+
+    #[test]
+    fn test_chrono_forms() {
+        let utc = TimeFormat::utc();
+        assert_eq!(
+            utc.format("2024-01-15 10:30:00 UTC").as_deref(),
+            Some("2024-01-15T10:30:00Z")
+        );
+        assert_eq!(
+            utc.format("2024-01-15 11:30:00 +01:00").as_deref(),
+            Some("2024-01-15T10:30:00Z")
+        );
+    }
+
+    #[test]
+    fn test_not_instants() {
+        let utc = TimeFormat::utc();
+        assert_eq!(utc.format("2024-01-15T10:30:00"), None);
+        assert_eq!(utc.format("2024-01-15"), None);
+        assert_eq!(utc.format("10:30:00"), None);
+        assert_eq!(utc.format("hello world"), None);
+    }
+
+    #[test]
+    fn test_relative() {
+        let noon: Timestamp = "2024-01-15T12:00:00Z".parse().unwrap();
+        let format = TimeFormat::utc()
+            .with_relative_within(Duration::from_secs(3600))
+            .with_now(noon);
+        assert_eq!(
+            format.format("2024-01-15T11:57:00Z").as_deref(),
+            Some("3m ago")
+        );
+        assert_eq!(
+            format.format("2024-01-15T12:00:05Z").as_deref(),
+            Some("in 5s")
+        );
+        assert_eq!(
+            format.format("2024-01-15T10:00:00Z").as_deref(),
+            Some("2024-01-15T10:00:00Z")
+        );
+    }
+}
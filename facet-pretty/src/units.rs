@@ -0,0 +1,124 @@
+//! Human-readable rendering of fields marked `#[facet(unit = "...")]`
+//!
+//! The printer shows `#[facet(unit = "bytes")]` fields as `1.2 GiB` and
+//! `#[facet(unit = "seconds")]` (or `"milliseconds"`) fields as `3m 20s`.
+//! Other units are printed as plain numbers.
+
+use facet_core::ScalarType;
+use facet_reflect::Peek;
+
+const BYTE_UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+const DURATION_UNITS: [(u64, &str); 4] = [(86_400, "d"), (3_600, "h"), (60, "m"), (1, "s")];
+
+/// Render a byte count with binary prefixes
+///
+/// ```
+/// use facet_pretty::humanize_bytes;
+///
+/// assert_eq!(humanize_bytes(512.0), "512 B");
+/// assert_eq!(humanize_bytes(1536.0), "1.5 KiB");
+/// assert_eq!(humanize_bytes(1_288_490_189.0), "1.2 GiB");
+/// ```
+pub fn humanize_bytes(bytes: f64) -> String {
+    if !bytes.is_finite() || bytes.abs() < 1024.0 {
+        return format!("{} B", one_decimal(bytes));
+    }
+    let mut scaled = bytes;
+    let mut unit = BYTE_UNITS[0];
+    for next in BYTE_UNITS {
+        scaled /= 1024.0;
+        unit = next;
+        if scaled.abs() < 1024.0 {
+            break;
+        }
+    }
+    format!("{} {unit}", one_decimal(scaled))
+}
+
+/// Render a duration in seconds with its two most significant units
+///
+/// ```
+/// use facet_pretty::humanize_seconds;
+///
+/// assert_eq!(humanize_seconds(200.0), "3m 20s");
+/// assert_eq!(humanize_seconds(3600.0), "1h");
+/// assert_eq!(humanize_seconds(1.5), "1.5s");
+/// assert_eq!(humanize_seconds(0.25), "250ms");
+/// ```
+pub fn humanize_seconds(seconds: f64) -> String {
+    if !seconds.is_finite() {
+        return format!("{seconds}s");
+    }
+    if seconds < 0.0 {
+        return format!("-{}", humanize_seconds(-seconds));
+    }
+    if seconds > 0.0 && seconds < 1.0 {
+        return format!("{}ms", one_decimal(seconds * 1000.0));
+    }
+    if seconds < 60.0 {
+        return format!("{}s", one_decimal(seconds));
+    }
+
+    let total = seconds.round() as u64;
+    let largest = DURATION_UNITS
+        .iter()
+        .position(|&(size, _)| total >= size)
+        .unwrap_or(DURATION_UNITS.len() - 1);
+    let (size, suffix) = DURATION_UNITS[largest];
+    let mut out = format!("{}{suffix}", total / size);
+    // Only the unit right below the largest one is worth showing
+    let rest = total % size;
+    if let Some(&(size, suffix)) = DURATION_UNITS.get(largest + 1)
+        && rest >= size
+    {
+        out.push_str(&format!(" {}{suffix}", rest / size));
+    }
+    out
+}
+
+/// The humanized form of `value` in `unit`, if the unit is known and the
+/// value is a number
+pub(crate) fn humanize(unit: &str, value: Peek<'_, '_>) -> Option<String> {
+    let number = as_f64(value)?;
+    match unit {
+        "bytes" => Some(humanize_bytes(number)),
+        "seconds" => Some(humanize_seconds(number)),
+        "milliseconds" => Some(humanize_seconds(number / 1000.0)),
+        _ => None,
+    }
+}
+
+fn as_f64(value: Peek<'_, '_>) -> Option<f64> {
+    macro_rules! read {
+        ($ty:ty) => {
+            *value.get::<$ty>().ok()? as f64
+        };
+    }
+    Some(match value.scalar_type()? {
+        ScalarType::U8 => read!(u8),
+        ScalarType::U16 => read!(u16),
+        ScalarType::U32 => read!(u32),
+        ScalarType::U64 => read!(u64),
+        ScalarType::U128 => read!(u128),
+        ScalarType::USize => read!(usize),
+        ScalarType::I8 => read!(i8),
+        ScalarType::I16 => read!(i16),
+        ScalarType::I32 => read!(i32),
+        ScalarType::I64 => read!(i64),
+        ScalarType::I128 => read!(i128),
+        ScalarType::ISize => read!(isize),
+        ScalarType::F32 => read!(f32),
+        ScalarType::F64 => read!(f64),
+        _ => return None,
+    })
+}
+
+/// `value` with at most one decimal, dropping a trailing `.0`
+fn one_decimal(value: f64) -> String {
+    let text = format!("{value:.1}");
+    match text.strip_suffix(".0") {
+        Some(whole) => whole.to_string(),
+        None => text,
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_pretty::{PrettyPrinter, Style};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet)]
+struct Blah {
+    foo: u32,
+    bar: String,
+}
+
+#[derive(Debug, Facet)]
+struct Point(i32, i32);
+
+#[derive(Debug, Facet)]
+struct Deployment {
+    name: String,
+    replicas: Vec<Blah>,
+    origin: Point,
+    labels: BTreeMap<String, u8>,
+}
+
+#[test]
+fn fits_on_one_line() {
+    let blah = Blah {
+        foo: 42,
+        bar: "hi".to_string(),
+    };
+    assert_eq!(
+        PrettyPrinter::new()
+            .with_colors(false)
+            .with_style(Style::Compact)
+            .format(&blah),
+        "Blah { foo: 42, bar: \"hi\" }"
+    );
+    assert_eq!(
+        PrettyPrinter::new()
+            .with_colors(false)
+            .with_style(Style::Compact)
+            .format(&Point(3, -4)),
+        "Point(3, -4)"
+    );
+}
+
+#[test]
+fn expanded_by_default() {
+    let blah = Blah {
+        foo: 42,
+        bar: "hi".to_string(),
+    };
+    let output = PrettyPrinter::new().with_colors(false).format(&blah);
+    assert_eq!(output, "Blah {\n  foo: 42,\n  bar: \"hi\",\n}");
+}
+
+#[test]
+fn only_values_too_wide_are_expanded() {
+    let deployment = Deployment {
+        name: "api".to_string(),
+        replicas: vec![
+            Blah {
+                foo: 42,
+                bar: "hi".to_string(),
+            },
+            Blah {
+                foo: 42,
+                bar: "hi".to_string(),
+            },
+        ],
+        origin: Point(3, -4),
+        labels: [("tier".to_string(), 1), ("zone".to_string(), 2)].into(),
+    };
+    let expected = "\
+Deployment {
+  name: \"api\",
+  replicas: Vec<Blah> [
+    Blah { foo: 42, bar: \"hi\" },
+    Blah { foo: 42, bar: \"hi\" },
+  ],
+  origin: Point(3, -4),
+  labels: BTreeMap<String, u8> [
+    \"tier\" => 1,
+    \"zone\" => 2,
+  ],
+}";
+    assert_eq!(
+        PrettyPrinter::new()
+            .with_colors(false)
+            .with_style(Style::Compact)
+            .with_max_width(50)
+            .format(&deployment),
+        expected
+    );
+}
+
+#[test]
+fn wide_budget_keeps_everything_on_one_line() {
+    let deployment = Deployment {
+        name: "api".to_string(),
+        replicas: vec![
+            Blah {
+                foo: 42,
+                bar: "hi".to_string(),
+            },
+            Blah {
+                foo: 42,
+                bar: "hi".to_string(),
+            },
+        ],
+        origin: Point(3, -4),
+        labels: [("tier".to_string(), 1), ("zone".to_string(), 2)].into(),
+    };
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_style(Style::Compact)
+        .with_max_width(usize::MAX)
+        .format(&deployment);
+    assert!(!output.contains('\n'), "{output}");
+    assert!(
+        !output.contains(",]") && !output.contains(", ]"),
+        "{output}"
+    );
+    assert!(
+        output.ends_with("[\"tier\" => 1, \"zone\" => 2] }"),
+        "{output}"
+    );
+}
+
+#[test]
+fn colors_do_not_count_toward_the_width() {
+    let blah = Blah {
+        foo: 42,
+        bar: "hi".to_string(),
+    };
+    let plain = PrettyPrinter::new()
+        .with_colors(false)
+        .with_style(Style::Compact)
+        .format(&blah);
+    let colored = PrettyPrinter::new()
+        .with_colors(true)
+        .with_style(Style::Compact)
+        .with_max_width(plain.len())
+        .format(&blah);
+    assert!(!colored.contains('\n'), "{colored}");
+    assert!(colored.len() > plain.len());
+
+    let narrower = PrettyPrinter::new()
+        .with_colors(false)
+        .with_style(Style::Compact)
+        .with_max_width(plain.len() - 1)
+        .format(&blah);
+    assert_eq!(narrower, "Blah {\n  foo: 42,\n  bar: \"hi\",\n}");
+}
+
+#[derive(Debug, Facet)]
+struct Labeled {
+    a_rather_long_field_name: Blah,
+    samples: Vec<u32>,
+}
+
+#[test]
+fn field_names_count_toward_the_width() {
+    let labeled = Labeled {
+        a_rather_long_field_name: Blah {
+            foo: 42,
+            bar: "hi".to_string(),
+        },
+        samples: (1000..1024).collect(),
+    };
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_style(Style::Compact)
+        .with_max_width(40)
+        .format(&labeled);
+    for line in output.lines() {
+        assert!(line.len() <= 40, "{line:?} in\n{output}");
+    }
+    assert!(output.contains("\n    foo: 42,\n"), "{output}");
+}
+
+#[test]
+fn long_lists_of_scalars_fill_lines() {
+    let expected = "\
+Labeled {
+  a_rather_long_field_name: Blah { foo: 42, bar: \"hi\" },
+  samples: Vec<u32> [
+    1000, 1001, 1002, 1003, 1004, 1005, 1006, 1007, 1008,
+    1009, 1010, 1011, 1012, 1013, 1014, 1015, 1016, 1017,
+    1018, 1019, 1020, 1021, 1022, 1023,
+  ],
+}";
+    let labeled = Labeled {
+        a_rather_long_field_name: Blah {
+            foo: 42,
+            bar: "hi".to_string(),
+        },
+        samples: (1000..1024).collect(),
+    };
+    assert_eq!(
+        PrettyPrinter::new()
+            .with_colors(false)
+            .with_style(Style::Compact)
+            .with_max_width(60)
+            .format(&labeled),
+        expected
+    );
+}
@@ -0,0 +1,130 @@
+use core::error::Error;
+use core::fmt;
+
+use facet::Facet;
+use facet_pretty::{ErrorChain, PrettyPrinter};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet)]
+struct LoadError {
+    path: String,
+    #[facet(sensitive)]
+    token: String,
+    source: std::io::Error,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not load {}", self.path)
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[derive(Debug, Facet)]
+struct StartupError {
+    inner: LoadError,
+}
+
+impl fmt::Display for StartupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("startup failed")
+    }
+}
+
+impl Error for StartupError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.inner)
+    }
+}
+
+#[test]
+fn error_with_fields_and_source() {
+    let err = LoadError {
+        path: "/etc/app.toml".to_string(),
+        token: "hunter2".to_string(),
+        source: std::io::Error::new(std::io::ErrorKind::PermissionDenied, "permission denied"),
+    };
+    let rendered = PrettyPrinter::new().with_colors(false).format_error(&err);
+    assert_eq!(
+        rendered,
+        "could not load /etc/app.toml\n\
+         LoadError {\n  \
+           path: \"/etc/app.toml\",\n  \
+           token: [REDACTED],\n  \
+           source: permission denied,\n\
+         }\n\
+         Caused by:\n  \
+           0: permission denied"
+    );
+    assert!(!rendered.contains("hunter2"));
+}
+
+#[test]
+fn nested_sources_are_numbered() {
+    let err = StartupError {
+        inner: LoadError {
+            path: "/etc/app.toml".to_string(),
+            token: "hunter2".to_string(),
+            source: std::io::Error::new(std::io::ErrorKind::PermissionDenied, "permission denied"),
+        },
+    };
+    let rendered = PrettyPrinter::new()
+        .with_colors(false)
+        .format_error_chain(&err);
+    assert_eq!(
+        rendered,
+        "startup failed\n\
+         Caused by:\n  \
+           0: could not load /etc/app.toml\n  \
+           1: permission denied"
+    );
+    let chain: Vec<String> = ErrorChain::new(&err).map(|e| e.to_string()).collect();
+    assert_eq!(chain.len(), 3);
+}
+
+#[test]
+fn error_wrapper_displays_like_format_error() {
+    let err = LoadError {
+        path: "/etc/app.toml".to_string(),
+        token: "hunter2".to_string(),
+        source: std::io::Error::new(std::io::ErrorKind::PermissionDenied, "permission denied"),
+    };
+    let printer = PrettyPrinter::new().with_colors(false);
+    assert_eq!(printer.error(&err).to_string(), printer.format_error(&err));
+}
+
+#[test]
+fn io_errors_print_only_their_message() {
+    let err = std::io::Error::other("disk full");
+    assert_eq!(
+        PrettyPrinter::new().with_colors(false).format_error(&err),
+        "disk full"
+    );
+}
+
+#[test]
+fn boxed_errors_in_fields() {
+    #[derive(Facet)]
+    struct Job {
+        id: u32,
+        failure: Option<Box<dyn Error + Send + Sync>>,
+        kind: std::io::ErrorKind,
+    }
+
+    let job = Job {
+        id: 7,
+        failure: Some("timed out".into()),
+        kind: std::io::ErrorKind::TimedOut,
+    };
+    let rendered = PrettyPrinter::new()
+        .with_colors(false)
+        .with_minimal_option_names(true)
+        .format(&job);
+    assert!(rendered.contains("failure: Some(timed out)"), "{rendered}");
+    assert!(rendered.contains("kind: timed out"), "{rendered}");
+}
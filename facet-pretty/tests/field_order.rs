@@ -0,0 +1,85 @@
+use facet::Facet;
+use facet_pretty::PrettyPrinter;
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet)]
+struct ServerConfig {
+    #[facet(order = 0)]
+    name: String,
+    #[facet(group = "network", order = 3)]
+    port: u16,
+    #[facet(group = "limits")]
+    max_connections: u32,
+    #[facet(group = "network", order = 2)]
+    host: String,
+    #[facet(order = 1)]
+    debug: bool,
+}
+
+#[test]
+fn declaration_order_by_default() {
+    let config = ServerConfig {
+        name: "api".to_string(),
+        port: 8080,
+        max_connections: 100,
+        host: "localhost".to_string(),
+        debug: false,
+    };
+    let output = PrettyPrinter::new().with_colors(false).format(&config);
+    let names: Vec<_> = output
+        .lines()
+        .filter_map(|line| line.trim().split_once(':').map(|(name, _)| name))
+        .collect();
+    assert_eq!(names, ["name", "port", "max_connections", "host", "debug"]);
+    assert!(!output.contains("//"));
+}
+
+#[test]
+fn declared_order_and_group_headers() {
+    let config = ServerConfig {
+        name: "api".to_string(),
+        port: 8080,
+        max_connections: 100,
+        host: "localhost".to_string(),
+        debug: false,
+    };
+    let expected = "\
+ServerConfig {
+  name: \"api\",
+  debug: false,
+  // network
+  host: \"localhost\",
+  port: 8080,
+  // limits
+  max_connections: 100,
+}";
+    let printer = PrettyPrinter::new()
+        .with_colors(false)
+        .with_field_order(true)
+        .with_group_headers(true);
+    assert_eq!(printer.format(&config), expected);
+
+    // The span-tracking printer follows the same layout
+    let formatted = printer.format_peek_with_spans(facet_reflect::Peek::new(&config));
+    assert_eq!(formatted.text, expected);
+}
+
+#[test]
+fn field_order_without_groups() {
+    let config = ServerConfig {
+        name: "api".to_string(),
+        port: 8080,
+        max_connections: 100,
+        host: "localhost".to_string(),
+        debug: false,
+    };
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_field_order(true)
+        .format(&config);
+    let names: Vec<_> = output
+        .lines()
+        .filter_map(|line| line.trim().split_once(':').map(|(name, _)| name))
+        .collect();
+    assert_eq!(names, ["name", "debug", "host", "port", "max_connections"]);
+}
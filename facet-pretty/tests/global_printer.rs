@@ -0,0 +1,80 @@
+use core::fmt;
+
+use facet::Facet;
+use facet_pretty::{
+    FacetPretty, PrettyPrinter, fmt_debug, set_default_printer, with_thread_printer,
+};
+use facet_testhelpers::test;
+
+#[derive(Facet)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl fmt::Debug for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_debug(self, f)
+    }
+}
+
+#[test]
+fn thread_override_applies_to_pretty_and_debug() {
+    let point = Point { x: 1, y: 2 };
+    let (pretty, debug) = with_thread_printer(
+        PrettyPrinter::new().with_colors(false).with_indent_size(4),
+        || (point.pretty().to_string(), format!("{point:?}")),
+    );
+    assert_eq!(pretty, "Point {\n    x: 1,\n    y: 2,\n}");
+    assert_eq!(debug, pretty);
+}
+
+#[test]
+fn thread_overrides_nest_and_restore() {
+    let value = [1u8, 2];
+    with_thread_printer(
+        PrettyPrinter::new().with_colors(false).with_indent_size(1),
+        || {
+            let outer = value.pretty().to_string();
+            let inner = with_thread_printer(
+                PrettyPrinter::new().with_colors(false).with_indent_size(3),
+                || value.pretty().to_string(),
+            );
+            assert_ne!(outer, inner);
+            assert_eq!(value.pretty().to_string(), outer);
+        },
+    );
+}
+
+#[test]
+fn thread_override_is_restored_after_panic() {
+    let result = std::panic::catch_unwind(|| {
+        with_thread_printer(
+            PrettyPrinter::new().with_colors(false).with_indent_size(7),
+            || panic!("boom"),
+        )
+    });
+    assert!(result.is_err());
+    let text = with_thread_printer(PrettyPrinter::new().with_colors(false), || {
+        Point { x: 0, y: 0 }.pretty().to_string()
+    });
+    assert_eq!(text, "Point {\n  x: 0,\n  y: 0,\n}");
+}
+
+#[test]
+fn default_printer_is_set_once_and_shared_across_threads() {
+    let first = set_default_printer(PrettyPrinter::new().with_colors(false).with_indent_size(6));
+    assert!(first.is_ok());
+    assert!(set_default_printer(PrettyPrinter::new().with_colors(false)).is_err());
+
+    let text = std::thread::spawn(|| Point { x: 3, y: 4 }.pretty().to_string())
+        .join()
+        .unwrap();
+    assert_eq!(text, "Point {\n      x: 3,\n      y: 4,\n}");
+
+    // A thread override still wins over the default
+    let text = with_thread_printer(PrettyPrinter::new().with_colors(false), || {
+        Point { x: 3, y: 4 }.pretty().to_string()
+    });
+    assert_eq!(text, "Point {\n  x: 3,\n  y: 4,\n}");
+}
@@ -0,0 +1,62 @@
+use std::io;
+
+use facet::Facet;
+use facet_pretty::PrettyPrinter;
+use facet_testhelpers::test;
+
+#[derive(Facet)]
+struct Inventory {
+    name: String,
+    items: Vec<u32>,
+}
+
+#[test]
+fn format_to_io_matches_format() {
+    let printer = PrettyPrinter::new().with_colors(false);
+    let mut out = Vec::new();
+    let inventory = Inventory {
+        name: "depot".to_string(),
+        items: (0..100).collect(),
+    };
+    printer.format_to_io(&inventory, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), printer.format(&inventory));
+}
+
+#[test]
+fn format_to_io_reports_write_errors() {
+    struct Full(usize);
+
+    impl io::Write for Full {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.0 < buf.len() {
+                return Err(io::Error::new(io::ErrorKind::StorageFull, "no space left"));
+            }
+            self.0 -= buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let inventory = Inventory {
+        name: "depot".to_string(),
+        items: (0..100).collect(),
+    };
+    let err = PrettyPrinter::new()
+        .format_to_io(&inventory, Full(16))
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::StorageFull);
+}
+
+#[test]
+fn print_and_eprint_write_to_std_streams() {
+    let printer = PrettyPrinter::new();
+    let inventory = Inventory {
+        name: "depot".to_string(),
+        items: (0..100).collect(),
+    };
+    printer.print(&inventory).unwrap();
+    printer.eprint(&inventory).unwrap();
+}
@@ -0,0 +1,60 @@
+use facet::Facet;
+use facet_pretty::{LayoutReport, Padding};
+
+#[derive(Facet)]
+#[repr(C)]
+struct Wide {
+    tag: u8,
+    data: [u8; 70],
+    count: u32,
+}
+
+#[derive(Facet)]
+struct Compact {
+    flag: bool,
+    len: u64,
+    kind: u16,
+}
+
+#[test]
+fn cache_lines_and_straddling_fields() {
+    let report = LayoutReport::of::<Wide>().unwrap();
+    assert_eq!(
+        report.padding(),
+        [Padding {
+            offset: 71,
+            size: 1
+        }]
+    );
+    assert_eq!(
+        report.to_string(),
+        "\
+Wide: 76 bytes, align 4, 1 byte of padding
+ offset  size  align  field
+      0     1      1  tag: u8
+      1    70      1  data: [u8; 70]  <- straddles a cache line
+------ cache line 1 (byte 64) ------
+     71     1         (padding)
+     72     4      4  count: u32
+"
+    );
+
+    let small_lines = LayoutReport::of::<Wide>().unwrap().with_cache_line(32);
+    assert!(small_lines.to_string().contains(" cache line 2 (byte 64) "));
+}
+
+#[test]
+fn rust_repr_structs_are_already_ordered() {
+    let report = LayoutReport::of::<Compact>().unwrap();
+    assert_eq!(report.size, 16);
+    assert_eq!(report.fields[0].name, "len");
+    assert_eq!(report.suggested_order(), None);
+    assert!(!report.to_string().contains("Ordering"));
+}
+
+#[test]
+fn only_sized_structs_have_a_layout() {
+    assert!(LayoutReport::of::<u32>().is_none());
+    assert!(LayoutReport::of::<Option<Compact>>().is_none());
+    assert!(LayoutReport::of::<(u8, u32)>().is_some());
+}
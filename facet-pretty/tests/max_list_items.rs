@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_pretty::{PrettyPrinter, Style};
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn long_lists_print_their_first_items() {
+    let numbers: Vec<u32> = (0..100_000).collect();
+    assert_eq!(
+        PrettyPrinter::new()
+            .with_colors(false)
+            .with_max_list_items(3)
+            .format(&numbers),
+        "Vec<u32> [0, 1, 2, ... 99997 more]"
+    );
+}
+
+#[test]
+fn tail_items_follow_the_marker() {
+    let numbers: Vec<u32> = (0..100_000).collect();
+    assert_eq!(
+        PrettyPrinter::new()
+            .with_colors(false)
+            .with_max_list_items(3)
+            .with_list_tail_items(2)
+            .format(&numbers),
+        "Vec<u32> [0, 1, 2, ... 99995 more, 99998, 99999]"
+    );
+
+    let points: Vec<Point> = (0..10).map(|i| Point { x: i, y: -i }).collect();
+    let expected = "\
+Vec<Point> [
+  Point {
+    x: 0,
+    y: 0,
+  },
+  ... 8 more,
+  Point {
+    x: 9,
+    y: -9,
+  },
+]";
+    assert_eq!(
+        PrettyPrinter::new()
+            .with_colors(false)
+            .with_max_list_items(1)
+            .with_list_tail_items(1)
+            .format(&points),
+        expected
+    );
+}
+
+#[test]
+fn short_lists_are_printed_whole() {
+    let five = [1u8, 2, 3, 4, 5];
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_max_list_items(3)
+        .with_list_tail_items(2)
+        .format(&five.to_vec());
+    assert!(!output.contains("more"), "{output}");
+    assert!(output.contains("05"), "{output}");
+
+    let numbers: Vec<u32> = (0..100_000).collect();
+    let all = PrettyPrinter::new().with_colors(false).format(&numbers);
+    assert!(all.ends_with("99998, 99999]"));
+}
+
+#[test]
+fn maps_are_truncated_too() {
+    let map: BTreeMap<u32, &str> = [(1, "one"), (2, "two"), (3, "three"), (4, "four")].into();
+    let expected = "\
+BTreeMap<u32, &str> [
+  1 => \"one\",
+  2 => \"two\",
+  3 => \"three\",
+  ... 1 more,
+]";
+    assert_eq!(
+        PrettyPrinter::new()
+            .with_colors(false)
+            .with_max_list_items(3)
+            .format(&map),
+        expected
+    );
+}
+
+#[test]
+fn compact_style_fills_lines_up_to_the_marker() {
+    let numbers: Vec<u32> = (0..100_000).collect();
+    let expected = "\
+Vec<u32> [
+  0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11,
+  12, 13, 14, 15, 16, 17, 18, 19, 20,
+  ... 99979 more,
+]";
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_max_list_items(21)
+        .with_style(Style::Compact)
+        .with_max_width(40)
+        .format(&numbers);
+    assert_eq!(output, expected);
+}
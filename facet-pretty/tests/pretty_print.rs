@@ -136,6 +136,36 @@ fn test_sensitive_fields() {
     assert!(!buffer.contains("TOP SECRET PASSWORD"));
 }
 
+#[test]
+fn test_skipped_fields() {
+    #[derive(Facet)]
+    struct Session {
+        user: String,
+        #[facet(skip, default)]
+        cache: Vec<u8>,
+    }
+
+    let session = Session {
+        user: "alice".to_string(),
+        cache: vec![1, 2, 3],
+    };
+    let output = PrettyPrinter::new().with_colors(false).format(&session);
+
+    assert!(output.contains("user"));
+    assert!(!output.contains("cache"), "{output}");
+
+    #[derive(Facet)]
+    struct OnlySkipped {
+        #[facet(skip, default)]
+        hidden: u32,
+    }
+
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .format(&OnlySkipped { hidden: 7 });
+    assert!(!output.contains("hidden"), "{output}");
+}
+
 #[test]
 fn test_tuple() {
     let printer = PrettyPrinter::new().with_colors(false);
@@ -191,3 +221,189 @@ fn test_map() {
     let map = BTreeMap::from([("abc", 1), ("def", 2)]);
     assert_snapshot!(printer.format(&map));
 }
+
+#[derive(Debug, Facet)]
+struct Measurement {
+    ratio: f64,
+    tiny: f32,
+    huge: f64,
+}
+
+#[test]
+fn test_float_format() {
+    use facet_pretty::{FloatFormat, FloatNotation};
+
+    let value = Measurement {
+        ratio: 2.0 / 3.0,
+        tiny: 0.000_001_25,
+        huge: 6.02e23,
+    };
+
+    let printer = PrettyPrinter::new().with_colors(false).with_float_format(
+        FloatFormat::shortest()
+            .with_precision(3)
+            .with_notation(FloatNotation::AUTO),
+    );
+    let output = printer.format(&value);
+    assert!(output.contains("ratio: 0.667,"), "{output}");
+    assert!(output.contains("tiny: 1.250e-6,"), "{output}");
+    assert!(output.contains("huge: 6.020e23,"), "{output}");
+
+    let printer = PrettyPrinter::new()
+        .with_colors(false)
+        .with_float_format(FloatFormat::shortest().with_notation(FloatNotation::Scientific));
+    let output = printer.format(&value);
+    assert!(output.contains("tiny: 1.25e-6,"), "{output}");
+}
+
+#[test]
+fn test_sensitive_fields_hash_preview() {
+    use facet_pretty::RedactionMode;
+
+    let a = TestSecrets {
+        normal_field: "visible".to_string(),
+        sensitive_field: "TOP SECRET PASSWORD".to_string(),
+    };
+    let b = TestSecrets {
+        normal_field: "also visible".to_string(),
+        sensitive_field: "TOP SECRET PASSWORD".to_string(),
+    };
+
+    let printer = PrettyPrinter::new()
+        .with_colors(false)
+        .with_redaction_mode(RedactionMode::HashPreview);
+    let out_a = printer.format(&a);
+    let out_b = printer.format(&b);
+
+    assert!(!out_a.contains("TOP SECRET PASSWORD"));
+    assert!(!out_a.contains("[REDACTED]"));
+
+    let preview = |out: &str| {
+        let start = out.find("[sha256:").expect("hash preview missing");
+        let end = start + out[start..].find(']').unwrap();
+        out[start..=end].to_string()
+    };
+    assert_eq!(preview(&out_a), preview(&out_b));
+}
+
+#[derive(Debug, Facet)]
+struct AuditEntry {
+    user: String,
+    card: Card,
+    secrets: TestSecrets,
+}
+
+#[derive(Debug, Facet)]
+struct Card {
+    number: String,
+    holder: String,
+}
+
+#[test]
+fn test_redaction_policy() {
+    use facet_core::Field;
+    use facet_pretty::RedactionMode;
+    use facet_reflect::Peek;
+
+    let entry = AuditEntry {
+        user: "alice".to_string(),
+        card: Card {
+            number: "4111111111111111".to_string(),
+            holder: "Alice Liddell".to_string(),
+        },
+        secrets: TestSecrets {
+            normal_field: "visible".to_string(),
+            sensitive_field: "TOP SECRET PASSWORD".to_string(),
+        },
+    };
+
+    let printer = PrettyPrinter::new()
+        .with_colors(false)
+        .with_redaction_mode(RedactionMode::Text("<withheld>".into()))
+        .with_redaction_policy(
+            |path: &[&str], _field: &Field, value: Peek<'_, '_>| match path {
+                ["card", "number"] => Some(RedactionMode::ShowLast(4)),
+                ["card", _] if value.shape().is_type::<String>() => {
+                    Some(RedactionMode::Text("<pii>".into()))
+                }
+                _ => None,
+            },
+        );
+    let output = printer.format(&entry);
+
+    assert!(output.contains("user: \"alice\""), "{output}");
+    assert!(output.contains("number: ************1111,"), "{output}");
+    assert!(output.contains("holder: <pii>,"), "{output}");
+    assert!(output.contains("normal_field: \"visible\""), "{output}");
+    // Sensitive fields stay redacted, with the printer's mode
+    assert!(output.contains("sensitive_field: <withheld>,"), "{output}");
+    assert!(!output.contains("4111111111111111"), "{output}");
+    assert!(!output.contains("Liddell"), "{output}");
+}
+
+#[derive(Debug, Facet)]
+struct Invoice {
+    total: f64,
+    items: u32,
+    balance: i64,
+    issued: chrono::NaiveDate,
+    paid_at: chrono::DateTime<chrono::Utc>,
+    reference: String,
+}
+
+#[test]
+fn test_locale() {
+    use chrono::TimeZone;
+    use facet_pretty::{DateOrder, Locale};
+
+    let invoice = Invoice {
+        total: 1234567.5,
+        items: 12000,
+        balance: -98765,
+        issued: chrono::NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+        paid_at: chrono::Utc.with_ymd_and_hms(2024, 2, 1, 9, 30, 0).unwrap(),
+        reference: "2024-01-15".to_string(),
+    };
+
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_locale(Locale::de_de())
+        .format(&invoice);
+    assert!(output.contains("total: 1.234.567,5,"), "{output}");
+    assert!(output.contains("items: 12.000,"), "{output}");
+    assert!(output.contains("balance: -98.765,"), "{output}");
+    assert!(output.contains("issued: 15.01.2024,"), "{output}");
+    assert!(
+        output.contains("paid_at: 01.02.2024 09:30:00Z,"),
+        "{output}"
+    );
+    // Strings are left alone, even when they look like dates
+    assert!(output.contains("reference: \"2024-01-15\","), "{output}");
+    let path = camino::Utf8PathBuf::from("2024-01-15");
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_locale(Locale::de_de())
+        .format(&path);
+    assert!(output.contains("2024-01-15"), "{output}");
+
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_locale(Locale::en_us().with_date_format(DateOrder::YearMonthDay, '/'))
+        .format(&invoice);
+    assert!(output.contains("total: 1,234,567.5,"), "{output}");
+    assert!(output.contains("issued: 2024/01/15,"), "{output}");
+
+    // No locale: plain Display output
+    let output = PrettyPrinter::new().with_colors(false).format(&invoice);
+    assert!(output.contains("total: 1234567.5,"), "{output}");
+    assert!(output.contains("issued: 2024-01-15,"), "{output}");
+}
+
+#[test]
+fn test_bytes_as_hex() {
+    let printer = PrettyPrinter::new()
+        .with_colors(false)
+        .with_bytes_as_hex(true);
+    let bytes: Vec<u8> = b"Hello, world!\n\x00\x01\x02\xffbinary tail".to_vec();
+    assert_snapshot!(printer.format(&bytes));
+}
@@ -0,0 +1,73 @@
+use std::sync::{Arc, Mutex};
+
+use facet::Facet;
+use facet_pretty::{
+    PrettyPrinter, StateReport, install_panic_hook, register_state, register_state_with,
+};
+
+#[derive(Facet)]
+struct ServerState {
+    port: u16,
+    #[facet(sensitive)]
+    api_key: String,
+}
+
+#[derive(Facet)]
+struct Counters {
+    requests: u64,
+}
+
+// The registry is process-global, so everything lives in one test to keep
+// parallel test threads from observing each other's entries.
+#[test]
+fn test_state_report_and_panic_hook() {
+    let printer = PrettyPrinter::new().with_colors(false);
+    let report = StateReport::new(printer.clone());
+    assert!(report.is_empty());
+
+    let server = Arc::new(ServerState {
+        port: 8080,
+        api_key: "sk-live-1234".to_string(),
+    });
+    let server_handle = register_state("server", server);
+
+    let counters = Arc::new(Mutex::new(Counters { requests: 0 }));
+    let counters_handle = register_state_with("counters", {
+        let counters = counters.clone();
+        move |printer| match counters.try_lock() {
+            Ok(counters) => printer.format(&*counters),
+            Err(_) => "<locked>".to_string(),
+        }
+    });
+
+    counters.lock().unwrap().requests = 42;
+
+    let output = report.to_string();
+    assert!(output.contains("server: ServerState"), "{output}");
+    assert!(output.contains("port: 8080"), "{output}");
+    assert!(output.contains("[REDACTED]"), "{output}");
+    assert!(!output.contains("sk-live-1234"), "{output}");
+    assert!(output.contains("requests: 42"), "{output}");
+
+    // Render closures may use the registry themselves
+    let nested_handle = register_state_with("nested", |_| {
+        format!("empty = {}", StateReport::default().is_empty())
+    });
+    let output = report.to_string();
+    assert!(output.contains("nested: empty = false"), "{output}");
+    drop(nested_handle);
+
+    // The hook must not interfere with unwinding
+    install_panic_hook(printer);
+    let result = std::panic::catch_unwind(|| panic!("boom"));
+    assert!(result.is_err());
+    let _ = std::panic::take_hook();
+
+    drop(server_handle);
+    let output = report.to_string();
+    assert!(!output.contains("server"), "{output}");
+    assert!(output.contains("counters"), "{output}");
+
+    drop(counters_handle);
+    assert!(report.is_empty());
+}
@@ -0,0 +1,185 @@
+use std::cell::RefCell;
+use std::ptr::NonNull;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, RwLock};
+
+use facet::Facet;
+use facet_pretty::PrettyPrinter;
+use facet_testhelpers::test;
+
+#[derive(Debug, Facet)]
+struct Leaf {
+    value: u32,
+}
+
+#[derive(Debug, Facet)]
+struct Pair {
+    left: Arc<Leaf>,
+    right: Arc<Leaf>,
+    other: Arc<Leaf>,
+}
+
+#[derive(Debug, Facet)]
+struct Graph {
+    nodes: Vec<Rc<Leaf>>,
+}
+
+#[derive(Debug, Facet)]
+struct Node {
+    name: String,
+    next: Option<NonNull<Node>>,
+}
+
+#[derive(Debug, Facet)]
+struct Locked {
+    mutex: Mutex<Leaf>,
+    rwlock: RwLock<u32>,
+    cell: RefCell<String>,
+}
+
+#[derive(Debug, Facet)]
+struct SharedNode {
+    name: String,
+    next: Option<Arc<Mutex<SharedNode>>>,
+}
+
+#[test]
+fn shared_arc_printed_once() {
+    let shared = Arc::new(Leaf { value: 1 });
+    let pair = Pair {
+        left: shared.clone(),
+        right: shared,
+        other: Arc::new(Leaf { value: 2 }),
+    };
+    let output = PrettyPrinter::new().with_colors(false).format(&pair);
+    assert!(output.contains("left: &1 Leaf"), "{output}");
+    assert!(output.contains("right: *1"), "{output}");
+    // Unshared pointers are printed without labels
+    assert!(output.contains("other: Leaf"), "{output}");
+    assert_eq!(output.matches("value").count(), 2, "{output}");
+}
+
+#[test]
+fn shared_labels_number_in_order() {
+    let a = Rc::new(Leaf { value: 1 });
+    let b = Rc::new(Leaf { value: 2 });
+    let graph = Graph {
+        nodes: vec![b.clone(), a.clone(), b, a],
+    };
+    let output = PrettyPrinter::new().with_colors(false).format(&graph);
+    let first_b = output.find("&1").unwrap();
+    let first_a = output.find("&2").unwrap();
+    let repeat_b = output.find("*1").unwrap();
+    let repeat_a = output.find("*2").unwrap();
+    assert!(
+        first_b < first_a && first_a < repeat_b && repeat_b < repeat_a,
+        "{output}"
+    );
+}
+
+#[test]
+fn pointer_cycle_terminates() {
+    let a = Box::into_raw(Box::new(Node {
+        name: "a".to_string(),
+        next: None,
+    }));
+    let b = Box::into_raw(Box::new(Node {
+        name: "b".to_string(),
+        next: NonNull::new(a),
+    }));
+    unsafe { (*a).next = NonNull::new(b) };
+
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .format(unsafe { &*a });
+    let spans = PrettyPrinter::new()
+        .with_colors(false)
+        .format_peek_with_spans(facet_reflect::Peek::new(unsafe { &*a }));
+
+    unsafe {
+        drop(Box::from_raw(a));
+        drop(Box::from_raw(b));
+    }
+
+    assert!(output.contains("&1 Node"), "{output}");
+    assert!(output.contains("*1"), "{output}");
+    assert!(spans.text.contains("*1"), "{}", spans.text);
+}
+
+#[test]
+fn locks_print_their_contents() {
+    let locked = Locked {
+        mutex: Mutex::new(Leaf { value: 7 }),
+        rwlock: RwLock::new(3),
+        cell: RefCell::new("hi".to_string()),
+    };
+    let output = PrettyPrinter::new().with_colors(false).format(&locked);
+    assert!(output.contains("mutex: Mutex<Leaf>(Leaf {"), "{output}");
+    assert!(output.contains("value: 7"), "{output}");
+    assert!(output.contains("rwlock: RwLock<u32>(3)"), "{output}");
+    assert!(
+        output.contains(r#"cell: RefCell<String>("hi")"#),
+        "{output}"
+    );
+}
+
+#[test]
+fn held_lock_is_not_waited_on() {
+    let mutex = Mutex::new(1u32);
+    let cell = RefCell::new(2u32);
+    let _guard = mutex.lock().unwrap();
+    let _borrow = cell.borrow_mut();
+    assert_eq!(
+        PrettyPrinter::new().with_colors(false).format(&mutex),
+        "Mutex<u32>(/* locked */)"
+    );
+    assert_eq!(
+        PrettyPrinter::new().with_colors(false).format(&cell),
+        "RefCell<u32>(/* locked */)"
+    );
+}
+
+#[test]
+fn cycle_through_arc_mutex_terminates() {
+    let a = Arc::new(Mutex::new(SharedNode {
+        name: "a".to_string(),
+        next: None,
+    }));
+    let b = Arc::new(Mutex::new(SharedNode {
+        name: "b".to_string(),
+        next: Some(a.clone()),
+    }));
+    a.lock().unwrap().next = Some(b.clone());
+
+    let output = PrettyPrinter::new().with_colors(false).format(&a);
+    let spans = PrettyPrinter::new()
+        .with_colors(false)
+        .format_peek_with_spans(facet_reflect::Peek::new(&a));
+
+    // Break the cycle so both nodes are freed
+    a.lock().unwrap().next = None;
+
+    assert!(output.contains("&1 Mutex<SharedNode>("), "{output}");
+    assert!(output.contains(r#"name: "b""#), "{output}");
+    assert!(output.contains("*1"), "{output}");
+    assert!(spans.text.contains("*1"), "{}", spans.text);
+}
+
+#[test]
+fn shared_pointers_behind_owning_ones() {
+    #[derive(Facet)]
+    struct Wrapped {
+        boxed: Box<Option<Rc<Leaf>>>,
+        locked: Mutex<Vec<Rc<Leaf>>>,
+    }
+
+    let shared = Rc::new(Leaf { value: 1 });
+    let wrapped = Wrapped {
+        boxed: Box::new(Some(shared.clone())),
+        locked: Mutex::new(vec![shared]),
+    };
+    let output = PrettyPrinter::new().with_colors(false).format(&wrapped);
+    assert!(output.contains("&1 Leaf"), "{output}");
+    assert!(output.contains("*1"), "{output}");
+    assert_eq!(output.matches("value").count(), 1, "{output}");
+}
@@ -0,0 +1,82 @@
+use facet::Facet;
+use facet_pretty::{PrettyPrinter, Theme};
+use facet_testhelpers::test;
+use owo_colors::Rgb;
+
+#[derive(Facet)]
+struct Job {
+    name: String,
+    retries: u32,
+    done: bool,
+}
+
+fn fg(Rgb(r, g, b): Rgb) -> String {
+    format!("\x1b[38;2;{r};{g};{b}m")
+}
+
+fn colored(theme: Theme) -> String {
+    let job = Job {
+        name: "build".to_string(),
+        retries: 3,
+        done: false,
+    };
+    PrettyPrinter::new()
+        .with_colors(true)
+        .with_theme(theme)
+        .format(&job)
+}
+
+#[test]
+fn scalars_are_colored_by_kind() {
+    let theme = Theme::light();
+    let output = colored(theme);
+    assert!(
+        output.contains(&format!("{}build", fg(theme.string))),
+        "{output:?}"
+    );
+    assert!(
+        output.contains(&format!("{}3", fg(theme.number))),
+        "{output:?}"
+    );
+    assert!(
+        output.contains(&format!("{}false", fg(theme.keyword))),
+        "{output:?}"
+    );
+    assert!(
+        output.contains(&format!("{}retries", fg(theme.field_name))),
+        "{output:?}"
+    );
+}
+
+#[test]
+fn presets_differ() {
+    assert_ne!(colored(Theme::dark()), colored(Theme::light()));
+    assert_eq!(colored(Theme::default()), colored(Theme::dark()));
+}
+
+#[test]
+fn punctuation_color() {
+    let dimmed = colored(Theme::dark());
+    assert!(dimmed.contains("\x1b[2m}"), "{dimmed:?}");
+
+    let color = Rgb(1, 2, 3);
+    let output = colored(Theme {
+        punctuation: Some(color),
+        ..Theme::dark()
+    });
+    assert!(output.contains(&format!("{}}}", fg(color))), "{output:?}");
+}
+
+#[test]
+fn theme_is_unused_without_colors() {
+    let job = Job {
+        name: "build".to_string(),
+        retries: 3,
+        done: false,
+    };
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_theme(Theme::light())
+        .format(&job);
+    assert!(!output.contains('\x1b'), "{output:?}");
+}
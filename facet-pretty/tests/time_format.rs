@@ -0,0 +1,87 @@
+#![cfg(feature = "jiff")]
+
+use core::time::Duration;
+
+use facet::Facet;
+use facet_pretty::{Locale, PrettyPrinter, TimeFormat};
+use facet_testhelpers::test;
+use jiff::Timestamp;
+use jiff::tz::TimeZone;
+
+#[derive(Facet)]
+struct Event {
+    at: Timestamp,
+    logged: chrono::DateTime<chrono::Utc>,
+    scheduled: jiff::civil::DateTime,
+}
+
+#[test]
+fn instants_move_to_the_zone() {
+    let kolkata = TimeZone::get("Asia/Kolkata").unwrap();
+    let event = Event {
+        at: "2024-01-15T10:30:00Z".parse().unwrap(),
+        logged: "2024-01-15T10:29:58Z".parse().unwrap(),
+        scheduled: "2024-01-16T09:00:00".parse().unwrap(),
+    };
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_time_format(TimeFormat::in_zone(kolkata))
+        .format(&event);
+    assert_eq!(
+        output,
+        "Event {\n  at: 2024-01-15T16:00:00+05:30,\n  logged: 2024-01-15T15:59:58+05:30,\n  scheduled: 2024-01-16T09:00:00,\n}"
+    );
+}
+
+#[test]
+fn pattern() {
+    let event = Event {
+        at: "2024-01-15T10:30:00Z".parse().unwrap(),
+        logged: "2024-01-15T10:29:58Z".parse().unwrap(),
+        scheduled: "2024-01-16T09:00:00".parse().unwrap(),
+    };
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_time_format(TimeFormat::utc().with_pattern("%H:%M:%S %Z"))
+        .format(&event);
+    assert!(output.contains("at: 10:30:00 UTC,"), "{output}");
+    assert!(output.contains("logged: 10:29:58 UTC,"), "{output}");
+}
+
+#[test]
+fn recent_times_are_relative() {
+    let now: Timestamp = "2024-01-15T10:32:30Z".parse().unwrap();
+    let time_format = TimeFormat::utc()
+        .with_relative_within(Duration::from_secs(150))
+        .with_now(now);
+    let event = Event {
+        at: "2024-01-15T10:30:00Z".parse().unwrap(),
+        logged: "2024-01-15T10:29:58Z".parse().unwrap(),
+        scheduled: "2024-01-16T09:00:00".parse().unwrap(),
+    };
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_time_format(time_format)
+        .format(&event);
+    assert!(output.contains("at: 2m 30s ago,"), "{output}");
+    assert!(output.contains("logged: 2024-01-15T10:29:58Z,"), "{output}");
+}
+
+#[test]
+fn civil_times_are_left_to_the_locale() {
+    let event = Event {
+        at: "2024-01-15T10:30:00Z".parse().unwrap(),
+        logged: "2024-01-15T10:29:58Z".parse().unwrap(),
+        scheduled: "2024-01-16T09:00:00".parse().unwrap(),
+    };
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_time_format(TimeFormat::utc())
+        .with_locale(Locale::de_de())
+        .format(&event);
+    assert!(output.contains("at: 2024-01-15T10:30:00Z,"), "{output}");
+    assert!(
+        output.contains("scheduled: 16.01.2024 09:00:00,"),
+        "{output}"
+    );
+}
@@ -0,0 +1,70 @@
+use facet::Facet;
+use facet_pretty::{PrettyPrinter, humanize_bytes, humanize_seconds};
+use facet_testhelpers::test;
+
+#[derive(Facet)]
+struct Transfer {
+    #[facet(unit = "bytes")]
+    size: u64,
+    #[facet(unit = "seconds")]
+    elapsed: f64,
+    #[facet(unit = "milliseconds")]
+    timeout: u32,
+    #[facet(unit = "meters")]
+    distance: u32,
+    retries: u32,
+}
+
+#[test]
+fn known_units_are_humanized() {
+    let transfer = Transfer {
+        size: 1_288_490_189,
+        elapsed: 200.0,
+        timeout: 1500,
+        distance: 42,
+        retries: 3,
+    };
+    let output = PrettyPrinter::new().with_colors(false).format(&transfer);
+    assert!(output.contains("size: 1.2 GiB,"), "{output}");
+    assert!(output.contains("elapsed: 3m 20s,"), "{output}");
+    assert!(output.contains("timeout: 1.5s,"), "{output}");
+    // Units without a humanized form print as plain numbers
+    assert!(output.contains("distance: 42,"), "{output}");
+    assert!(output.contains("retries: 3,"), "{output}");
+}
+
+#[test]
+fn humanizing_can_be_disabled() {
+    let transfer = Transfer {
+        size: 1_288_490_189,
+        elapsed: 200.0,
+        timeout: 1500,
+        distance: 42,
+        retries: 3,
+    };
+    let output = PrettyPrinter::new()
+        .with_colors(false)
+        .with_humanized_units(false)
+        .format(&transfer);
+    assert!(output.contains("size: 1288490189,"), "{output}");
+    assert!(output.contains("elapsed: 200,"), "{output}");
+}
+
+#[test]
+fn byte_counts() {
+    assert_eq!(humanize_bytes(0.0), "0 B");
+    assert_eq!(humanize_bytes(1023.0), "1023 B");
+    assert_eq!(humanize_bytes(1024.0), "1 KiB");
+    assert_eq!(humanize_bytes(5.5 * 1024.0 * 1024.0), "5.5 MiB");
+    assert_eq!(humanize_bytes(-2048.0), "-2 KiB");
+}
+
+#[test]
+fn durations() {
+    assert_eq!(humanize_seconds(0.0), "0s");
+    assert_eq!(humanize_seconds(0.0004), "0.4ms");
+    assert_eq!(humanize_seconds(59.0), "59s");
+    assert_eq!(humanize_seconds(3725.0), "1h 2m");
+    assert_eq!(humanize_seconds(90_000.0), "1d 1h");
+    assert_eq!(humanize_seconds(-200.0), "-3m 20s");
+}
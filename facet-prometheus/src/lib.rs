@@ -0,0 +1,425 @@
+//! Prometheus text exposition for facet structs.
+//!
+//! This crate renders any `Facet` value holding numbers as the Prometheus
+//! [text exposition format], so a plain stats struct can back a `/metrics`
+//! endpoint without per-type code.
+//!
+//! # Mapping to metrics
+//!
+//! - Every number (and `bool`, as `0` or `1`) becomes a sample. Its metric
+//!   name is the path of field names leading to it, joined with `_`.
+//! - Doc comments on fields become `# HELP` lines.
+//! - Metrics are gauges, unless marked with `#[facet(prometheus::counter)]`,
+//!   which also applies to every metric nested under the field.
+//! - Fields marked with `#[facet(prometheus::label)]` become labels on all
+//!   metrics of their struct, including nested ones. This is how items of a
+//!   `Vec` are told apart.
+//! - Map keys become a label named `key`, or the name given with
+//!   `#[facet(prometheus::key_label = "name")]`.
+//! - `None` values are skipped, and so are fields that hold no numbers.
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//!
+//! use facet::Facet;
+//! use facet_prometheus as prometheus;
+//! use facet_prometheus::Exposition;
+//!
+//! #[derive(Facet)]
+//! struct Stats {
+//!     /// Open connections
+//!     connections: u32,
+//!     #[facet(prometheus::counter, prometheus::key_label = "route")]
+//!     requests: BTreeMap<String, u64>,
+//!     pools: Vec<Pool>,
+//! }
+//!
+//! #[derive(Facet)]
+//! struct Pool {
+//!     #[facet(prometheus::label)]
+//!     name: String,
+//!     idle: u32,
+//! }
+//!
+//! let stats = Stats {
+//!     connections: 3,
+//!     requests: BTreeMap::from([("/".to_string(), 10)]),
+//!     pools: vec![Pool {
+//!         name: "db".into(),
+//!         idle: 2,
+//!     }],
+//! };
+//! let text = Exposition::new().with_prefix("app").render(&stats);
+//! assert!(text.contains("app_requests{route=\"/\"} 10\n"));
+//! ```
+//!
+//! renders:
+//!
+//! ```text
+//! # HELP app_connections Open connections
+//! # TYPE app_connections gauge
+//! app_connections 3
+//! # TYPE app_requests counter
+//! app_requests{route="/"} 10
+//! # TYPE app_pools_idle gauge
+//! app_pools_idle{name="db"} 2
+//! ```
+//!
+//! [text exposition format]: https://prometheus.io/docs/instrumenting/exposition_formats/
+
+#![warn(missing_docs)]
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use facet_core::{Def, Facet, Field, ScalarType, Type, UserType};
+use facet_reflect::{HasFields, Peek};
+
+// Prometheus attribute grammar for field configuration.
+// This allows users to write #[facet(prometheus::label)] etc.
+facet::define_attr_grammar! {
+    ns "prometheus";
+    crate_path ::facet_prometheus;
+
+    /// Prometheus attribute types for field configuration.
+    pub enum Attr {
+        /// Uses the field's value as a label on the metrics of its struct.
+        ///
+        /// Usage: `#[facet(prometheus::label)]`
+        Label,
+        /// Marks the metrics under this field as counters instead of gauges.
+        ///
+        /// Usage: `#[facet(prometheus::counter)]`
+        Counter,
+        /// Names the label holding the keys of a map field (default: `key`).
+        ///
+        /// Usage: `#[facet(prometheus::key_label = "route")]`
+        KeyLabel(&'static str),
+    }
+}
+
+/// Render `value` as Prometheus text exposition, without a metric prefix.
+pub fn to_string<'facet, T: Facet<'facet>>(value: &T) -> String {
+    Exposition::new().render(value)
+}
+
+/// Options for rendering Prometheus text exposition
+#[derive(Debug, Clone, Default)]
+pub struct Exposition {
+    prefix: Option<String>,
+    labels: Vec<(String, String)>,
+}
+
+impl Exposition {
+    /// Options with no prefix and no constant labels
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prepend `prefix` and `_` to every metric name.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Add a label to every sample, e.g. the instance name.
+    pub fn with_label(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.push((sanitize(&name.into()), value.into()));
+        self
+    }
+
+    /// Render `value` as Prometheus text exposition.
+    pub fn render<'facet, T: Facet<'facet>>(&self, value: &T) -> String {
+        self.render_peek(Peek::new(value))
+    }
+
+    /// Render a type-erased value as Prometheus text exposition.
+    pub fn render_peek(&self, peek: Peek<'_, '_>) -> String {
+        let mut collector = Collector::default();
+        let mut labels = self.labels.clone();
+        let name = self.prefix.as_deref().map(sanitize).unwrap_or_default();
+        collector.walk(peek, &name, &mut labels, &[], Kind::Gauge, "key");
+
+        let mut out = String::new();
+        for metric in &collector.metrics {
+            let help = metric.help.trim();
+            if !help.is_empty() {
+                let help = help.replace('\\', "\\\\").replace('\n', "\\n");
+                writeln!(out, "# HELP {} {help}", metric.name).unwrap();
+            }
+            writeln!(out, "# TYPE {} {}", metric.name, metric.kind.as_str()).unwrap();
+            for (labels, value) in &metric.samples {
+                out.push_str(&metric.name);
+                if !labels.is_empty() {
+                    out.push('{');
+                    for (index, (name, value)) in labels.iter().enumerate() {
+                        if index > 0 {
+                            out.push(',');
+                        }
+                        write!(out, "{name}=\"{}\"", escape_label(value)).unwrap();
+                    }
+                    out.push('}');
+                }
+                writeln!(out, " {value}").unwrap();
+            }
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Gauge,
+    Counter,
+}
+
+impl Kind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Kind::Gauge => "gauge",
+            Kind::Counter => "counter",
+        }
+    }
+}
+
+struct Metric {
+    name: String,
+    help: String,
+    kind: Kind,
+    samples: Vec<(Vec<(String, String)>, String)>,
+}
+
+/// Samples grouped by metric, in the order metrics were first seen
+#[derive(Default)]
+struct Collector {
+    metrics: Vec<Metric>,
+    index: BTreeMap<String, usize>,
+}
+
+impl Collector {
+    fn sample(
+        &mut self,
+        name: &str,
+        labels: &[(String, String)],
+        doc: &[&str],
+        kind: Kind,
+        value: String,
+    ) {
+        let name = if name.is_empty() { "value" } else { name };
+        let index = *self.index.entry(name.to_string()).or_insert_with(|| {
+            self.metrics.push(Metric {
+                name: name.to_string(),
+                help: doc
+                    .iter()
+                    .map(|line| line.trim())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                kind,
+                samples: Vec::new(),
+            });
+            self.metrics.len() - 1
+        });
+        self.metrics[index].samples.push((labels.to_vec(), value));
+    }
+
+    fn walk(
+        &mut self,
+        peek: Peek<'_, '_>,
+        name: &str,
+        labels: &mut Vec<(String, String)>,
+        doc: &[&str],
+        kind: Kind,
+        key_label: &str,
+    ) {
+        let shape = peek.shape();
+        match shape.def {
+            Def::Scalar => {
+                if let Some(value) = sample_value(peek) {
+                    self.sample(name, labels, doc, kind, value);
+                }
+                return;
+            }
+            Def::Option(_) => {
+                if let Ok(option) = peek.into_option()
+                    && let Some(inner) = option.value()
+                {
+                    self.walk(inner, name, labels, doc, kind, key_label);
+                }
+                return;
+            }
+            Def::Pointer(_) => {
+                if let Ok(pointer) = peek.into_pointer()
+                    && let Some(inner) = pointer.borrow_inner()
+                {
+                    self.walk(inner, name, labels, doc, kind, key_label);
+                }
+                return;
+            }
+            Def::List(_) | Def::Array(_) | Def::Slice(_) => {
+                if let Ok(list) = peek.into_list_like() {
+                    for item in list.iter() {
+                        self.walk(item, name, labels, doc, kind, key_label);
+                    }
+                }
+                return;
+            }
+            Def::Set(_) => {
+                if let Ok(set) = peek.into_set() {
+                    for item in set.iter() {
+                        self.walk(item, name, labels, doc, kind, key_label);
+                    }
+                }
+                return;
+            }
+            Def::Map(_) => {
+                if let Ok(map) = peek.into_map() {
+                    for (key, value) in map.iter() {
+                        let Some(key) = label_value(key) else {
+                            continue;
+                        };
+                        labels.push((key_label.to_string(), key));
+                        self.walk(value, name, labels, doc, kind, "key");
+                        labels.pop();
+                    }
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(_)) if shape.inner.is_some() => {
+                let inner = peek.innermost_peek();
+                if inner.shape() != shape {
+                    self.walk(inner, name, labels, doc, kind, key_label);
+                }
+            }
+            Type::User(UserType::Struct(_)) => {
+                let Ok(struct_) = peek.into_struct() else {
+                    return;
+                };
+                let fields: Vec<_> = struct_
+                    .fields_for_serialize()
+                    .filter_map(|(item, value)| item.field.map(|field| (field, value)))
+                    .collect();
+
+                let len = labels.len();
+                for (field, value) in &fields {
+                    if field.has_attr(Some("prometheus"), "label")
+                        && let Some(value) = label_value(*value)
+                    {
+                        labels.push((sanitize(field.name), value));
+                    }
+                }
+                for (field, value) in &fields {
+                    if !field.has_attr(Some("prometheus"), "label") {
+                        self.field(field, *value, name, labels, kind);
+                    }
+                }
+                labels.truncate(len);
+            }
+            _ => {}
+        }
+    }
+
+    fn field(
+        &mut self,
+        field: &Field,
+        value: Peek<'_, '_>,
+        parent: &str,
+        labels: &mut Vec<(String, String)>,
+        kind: Kind,
+    ) {
+        let name = sanitize(field.name);
+        let name = if parent.is_empty() {
+            name
+        } else {
+            format!("{parent}_{name}")
+        };
+        let kind = if field.has_attr(Some("prometheus"), "counter") {
+            Kind::Counter
+        } else {
+            kind
+        };
+        let key_label = field
+            .get_attr(Some("prometheus"), "key_label")
+            .and_then(|attr| attr.get_as::<&str>().copied())
+            .unwrap_or("key");
+        self.walk(value, &name, labels, field.doc, kind, key_label);
+    }
+}
+
+/// The sample value of a number or `bool`, if `peek` is one
+fn sample_value(peek: Peek<'_, '_>) -> Option<String> {
+    let peek = if peek.shape().inner.is_some() {
+        peek.innermost_peek()
+    } else {
+        peek
+    };
+    Some(match peek.scalar_type()? {
+        ScalarType::Bool => if *peek.get::<bool>().ok()? { "1" } else { "0" }.to_string(),
+        ScalarType::F32 => float(f64::from(*peek.get::<f32>().ok()?)),
+        ScalarType::F64 => float(*peek.get::<f64>().ok()?),
+        ScalarType::U8
+        | ScalarType::U16
+        | ScalarType::U32
+        | ScalarType::U64
+        | ScalarType::U128
+        | ScalarType::USize
+        | ScalarType::I8
+        | ScalarType::I16
+        | ScalarType::I32
+        | ScalarType::I64
+        | ScalarType::I128
+        | ScalarType::ISize => peek.to_string(),
+        _ => return None,
+    })
+}
+
+fn float(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// The label value of a string, number or unit variant
+fn label_value(peek: Peek<'_, '_>) -> Option<String> {
+    if let Some(s) = peek.as_str() {
+        return Some(s.to_string());
+    }
+    if let Type::User(UserType::Enum(_)) = peek.shape().ty
+        && let Ok(variant) = peek.into_enum().ok()?.active_variant()
+        && variant.data.fields.is_empty()
+    {
+        return Some(variant.name.to_string());
+    }
+    if let Ok(option) = peek.into_option() {
+        return label_value(option.value()?);
+    }
+    peek.shape().vtable.has_display().then(|| peek.to_string())
+}
+
+/// Replaces characters that are not allowed in metric and label names.
+fn sanitize(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_prometheus as prometheus;
+use facet_prometheus::{Exposition, to_string};
+
+#[derive(Facet)]
+struct ServerStats {
+    /// Seconds since start
+    uptime: f64,
+    healthy: bool,
+    #[facet(prometheus::counter)]
+    errors: Errors,
+    workers: Vec<Worker>,
+    queue: Option<u32>,
+    version: String,
+}
+
+#[derive(Facet)]
+struct Errors {
+    /// Requests that timed out,
+    /// including retries
+    timeouts: u64,
+    refused: u64,
+}
+
+#[derive(Facet)]
+struct Worker {
+    #[facet(prometheus::label)]
+    id: u32,
+    #[facet(prometheus::label)]
+    state: State,
+    busy_ms: u64,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+enum State {
+    Idle,
+    Running,
+}
+
+#[test]
+fn renders_nested_struct() {
+    let stats = ServerStats {
+        uptime: 12.5,
+        healthy: true,
+        errors: Errors {
+            timeouts: 4,
+            refused: 0,
+        },
+        workers: vec![
+            Worker {
+                id: 0,
+                state: State::Running,
+                busy_ms: 120,
+            },
+            Worker {
+                id: 1,
+                state: State::Idle,
+                busy_ms: 0,
+            },
+        ],
+        queue: None,
+        version: "1.2.3".into(),
+    };
+    assert_eq!(
+        to_string(&stats),
+        "\
+# HELP uptime Seconds since start
+# TYPE uptime gauge
+uptime 12.5
+# TYPE healthy gauge
+healthy 1
+# HELP errors_timeouts Requests that timed out, including retries
+# TYPE errors_timeouts counter
+errors_timeouts 4
+# TYPE errors_refused counter
+errors_refused 0
+# TYPE workers_busy_ms gauge
+workers_busy_ms{id=\"0\",state=\"Running\"} 120
+workers_busy_ms{id=\"1\",state=\"Idle\"} 0
+"
+    );
+}
+
+#[test]
+fn renders_prefix_and_constant_labels() {
+    let stats = ServerStats {
+        uptime: 12.5,
+        healthy: true,
+        errors: Errors {
+            timeouts: 4,
+            refused: 0,
+        },
+        workers: vec![Worker {
+            id: 1,
+            state: State::Idle,
+            busy_ms: 0,
+        }],
+        queue: None,
+        version: "1.2.3".into(),
+    };
+    let text = Exposition::new()
+        .with_prefix("my-app")
+        .with_label("instance", "a\"b")
+        .render(&stats);
+    assert!(text.contains("my_app_uptime{instance=\"a\\\"b\"} 12.5\n"));
+    assert!(
+        text.contains("my_app_workers_busy_ms{instance=\"a\\\"b\",id=\"1\",state=\"Idle\"} 0\n")
+    );
+}
+
+#[test]
+fn renders_maps_and_special_floats() {
+    #[derive(Facet)]
+    struct Latency {
+        #[facet(prometheus::key_label = "route")]
+        p99: HashMap<String, f64>,
+        buckets: HashMap<String, f32>,
+    }
+
+    let latency = Latency {
+        p99: HashMap::from([("/login".to_string(), f64::INFINITY)]),
+        buckets: HashMap::from([("fast".to_string(), f32::NAN)]),
+    };
+    assert_eq!(
+        to_string(&latency),
+        "\
+# TYPE p99 gauge
+p99{route=\"/login\"} +Inf
+# TYPE buckets gauge
+buckets{key=\"fast\"} NaN
+"
+    );
+}
+
+#[test]
+fn renders_root_scalar() {
+    assert_eq!(
+        Exposition::new().with_prefix("answer").render(&42u8),
+        "# TYPE answer gauge\nanswer 42\n"
+    );
+}
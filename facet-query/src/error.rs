@@ -0,0 +1,57 @@
+//! Query error types.
+
+use core::fmt;
+
+use facet_ops::OpsError;
+use facet_reflect::PeekPathError;
+
+/// Error returned when a query can't be run or its results rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// A selector couldn't be applied to one of the items, e.g. because its
+    /// path is misspelled or its value isn't of the compared type.
+    Select(OpsError),
+    /// A column's path doesn't lead anywhere in one of the items.
+    Column {
+        /// The position of the item among the selected ones.
+        index: usize,
+        /// Why the path didn't resolve.
+        error: PeekPathError,
+    },
+    /// The output format isn't one of `table`, `csv` and `json`.
+    UnknownFormat {
+        /// The format, as given.
+        name: String,
+    },
+    /// An item couldn't be serialized as JSON.
+    Json(String),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::Select(err) => write!(f, "{err}"),
+            QueryError::Column { index, error } => write!(f, "item {index}: {error}"),
+            QueryError::UnknownFormat { name } => write!(
+                f,
+                "unknown output format `{name}`, expected `table`, `csv` or `json`"
+            ),
+            QueryError::Json(msg) => write!(f, "JSON serialization failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            QueryError::Select(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<OpsError> for QueryError {
+    fn from(err: OpsError) -> Self {
+        QueryError::Select(err)
+    }
+}
@@ -0,0 +1,54 @@
+//! The formats query results can be rendered in.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::QueryError;
+
+/// How [`Query::render`](crate::Query::render) writes the selected items
+///
+/// Parses from the names a CLI's `--output` flag would take: `table`, `csv`
+/// and `json`, in any case.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    /// Aligned columns under an upper-case header, for people
+    #[default]
+    Table,
+    /// A header row of column names, then one row per item
+    Csv,
+    /// A JSON array of the items
+    Json,
+}
+
+impl OutputFormat {
+    /// Every format, in the order they are listed in help text
+    pub const ALL: [OutputFormat; 3] = [OutputFormat::Table, OutputFormat::Csv, OutputFormat::Json];
+
+    /// The format's name, as parsed
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Json => "json",
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = QueryError;
+
+    fn from_str(name: &str) -> Result<Self, QueryError> {
+        OutputFormat::ALL
+            .into_iter()
+            .find(|format| format.as_str().eq_ignore_ascii_case(name))
+            .ok_or_else(|| QueryError::UnknownFormat {
+                name: name.to_string(),
+            })
+    }
+}
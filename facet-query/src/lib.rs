@@ -0,0 +1,75 @@
+//! Selecting items of `Facet` values and rendering them as a table, CSV or
+//! JSON.
+//!
+//! This is the backbone of `kubectl`-style CLIs over typed data: the user
+//! narrows a list down with selectors like `status == Running` (see
+//! [`facet_ops::Selector`]), picks columns by path, and chooses the output
+//! format with a flag. A [`Query`] holds the selectors and columns, and
+//! renders any value in any [`OutputFormat`]:
+//!
+//! - `table`: aligned columns under an upper-case header, for people.
+//!   Missing values read `<none>`.
+//! - `csv`: a header row of column names, then a row per item, for
+//!   spreadsheets and scripts. Missing values are left empty.
+//! - `json`: an array of the items, for `jq`. With columns picked, each item
+//!   is an object of its columns.
+//!
+//! Strings and other scalars are written as text in tables and CSV, and
+//! nested values like lists as compact JSON.
+//!
+//! # Example
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_query::{OutputFormat, Query};
+//!
+//! #[derive(Facet)]
+//! #[repr(u8)]
+//! enum Phase {
+//!     Pending,
+//!     Running,
+//! }
+//!
+//! #[derive(Facet)]
+//! struct Pod {
+//!     name: String,
+//!     phase: Phase,
+//!     restarts: u32,
+//!     node: Option<String>,
+//! }
+//!
+//! let pods = vec![
+//!     Pod { name: "api-7f9c".into(), phase: Phase::Running, restarts: 0, node: Some("node-1".into()) },
+//!     Pod { name: "worker-x2".into(), phase: Phase::Pending, restarts: 3, node: None },
+//!     Pod { name: "db-0".into(), phase: Phase::Running, restarts: 12, node: Some("node-2".into()) },
+//! ];
+//!
+//! // e.g. from `--selector 'restarts > 0' --output table`
+//! let query = Query::new().filter("restarts > 0".parse().unwrap());
+//! let format: OutputFormat = "table".parse().unwrap();
+//! assert_eq!(
+//!     query.render(&pods, format).unwrap(),
+//!     "\
+//! NAME        PHASE     RESTARTS   NODE
+//! worker-x2   Pending   3          <none>
+//! db-0        Running   12         node-2
+//! "
+//! );
+//!
+//! let query = query.with_columns(["name", "restarts"]);
+//! assert_eq!(
+//!     query.render(&pods, OutputFormat::Csv).unwrap(),
+//!     "name,restarts\nworker-x2,3\ndb-0,12\n"
+//! );
+//! ```
+
+#![forbid(unsafe_code)]
+
+mod error;
+mod format;
+mod query;
+mod render;
+
+pub use error::QueryError;
+pub use format::OutputFormat;
+pub use query::Query;
@@ -0,0 +1,190 @@
+//! Selecting the items of a value and rendering them.
+
+use facet_core::{Facet, FieldFlags, Shape, Type, UserType};
+use facet_ops::{OpsError, Selector};
+use facet_reflect::Peek;
+
+use crate::render::{self, Column};
+use crate::{OutputFormat, QueryError};
+
+/// Which items of a value to render, and which of their values
+///
+/// The items of a list, array, slice or set are queried one by one; any
+/// other value is queried as a single item. Items are kept if they match
+/// every selector given to [`filter`](Self::filter), and rendered in their
+/// original order.
+///
+/// Struct items get one column per field by default, named by its
+/// serialized name, so `rename` and `rename_all` are honored. Skipped fields
+/// are left out, flattened structs add their own fields, and sensitive
+/// fields read `[REDACTED]`. Other items get a single `value` column.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    selectors: Vec<Selector>,
+    columns: Option<Vec<String>>,
+}
+
+impl Query {
+    /// A query keeping every item, with the default columns
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only the items that match `selector`, as well as any selectors
+    /// given before
+    pub fn filter(mut self, selector: Selector) -> Self {
+        self.selectors.push(selector);
+        self
+    }
+
+    /// Render the values at these paths of each item rather than its fields,
+    /// like `name` or `spec.ports[0]`
+    ///
+    /// Paths are in the syntax of `Peek::at`, and each column is named by
+    /// its path.
+    pub fn with_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// The items of `value` that match every selector, in order
+    ///
+    /// Fails if a selector can't be applied to one of the items.
+    pub fn select<'mem, 'facet>(
+        &self,
+        value: Peek<'mem, 'facet>,
+    ) -> Result<Vec<Peek<'mem, 'facet>>, QueryError> {
+        self.select_items(value).map(|(selected, _)| selected)
+    }
+
+    /// Render the selected items of `value` in `format`, ending with a line
+    /// break
+    pub fn render<'facet, T: Facet<'facet> + ?Sized>(
+        &self,
+        value: &T,
+        format: OutputFormat,
+    ) -> Result<String, QueryError> {
+        self.render_peek(Peek::new(value), format)
+    }
+
+    /// [`render`](Self::render), for a value only known through a [`Peek`]
+    pub fn render_peek(
+        &self,
+        value: Peek<'_, '_>,
+        format: OutputFormat,
+    ) -> Result<String, QueryError> {
+        let (selected, item_shape) = self.select_items(value)?;
+        let columns = match &self.columns {
+            Some(paths) => paths
+                .iter()
+                .map(|path| Column {
+                    name: path.clone(),
+                    path: path.clone(),
+                    sensitive: false,
+                })
+                .collect(),
+            None => default_columns(item_shape),
+        };
+
+        match format {
+            OutputFormat::Table => Ok(render::table(
+                &columns,
+                &render::cells(&selected, &columns)?,
+            )),
+            OutputFormat::Csv => Ok(render::csv(&columns, &render::cells(&selected, &columns)?)),
+            // Whole items keep their nested values, skipped fields and the
+            // like as JSON has them
+            OutputFormat::Json if self.columns.is_none() => render::json_items(&selected),
+            OutputFormat::Json => render::json_columns(&selected, &columns),
+        }
+    }
+
+    /// The selected items of `value`, and the shape of all its items
+    fn select_items<'mem, 'facet>(
+        &self,
+        value: Peek<'mem, 'facet>,
+    ) -> Result<(Vec<Peek<'mem, 'facet>>, &'static Shape), QueryError> {
+        let (items, item_shape) = items(value);
+        let mut selected = Vec::new();
+        for (index, item) in items.into_iter().enumerate() {
+            if self.matches(item, index)? {
+                selected.push(item);
+            }
+        }
+        Ok((selected, item_shape))
+    }
+
+    fn matches(&self, item: Peek<'_, '_>, index: usize) -> Result<bool, QueryError> {
+        for selector in &self.selectors {
+            let matches = selector.matches(item).map_err(|err| match err {
+                OpsError::Path { error, .. } => OpsError::Path { index, error },
+                err => err,
+            })?;
+            if !matches {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// The items of `value`, and their shape
+fn items<'mem, 'facet>(value: Peek<'mem, 'facet>) -> (Vec<Peek<'mem, 'facet>>, &'static Shape) {
+    match value.into_list_like() {
+        Ok(list) => {
+            let shape = list.def().t();
+            (list.iter().collect(), shape)
+        }
+        Err(_) => match value.into_set() {
+            Ok(set) => {
+                let shape = set.def().t();
+                (set.iter().collect(), shape)
+            }
+            Err(_) => (vec![value], value.shape()),
+        },
+    }
+}
+
+/// A column per field of struct items, or a single one for other items
+fn default_columns(shape: &'static Shape) -> Vec<Column> {
+    let mut columns = Vec::new();
+    if !field_columns(shape, "", &mut columns) {
+        columns.push(Column {
+            name: "value".to_string(),
+            path: String::new(),
+            sensitive: false,
+        });
+    }
+    columns
+}
+
+/// Add the columns of the fields of `shape` to `columns`, if it is a struct
+fn field_columns(shape: &'static Shape, prefix: &str, columns: &mut Vec<Column>) -> bool {
+    let Type::User(UserType::Struct(st)) = shape.ty else {
+        return false;
+    };
+    for field in st.fields {
+        if field.flags.contains(FieldFlags::SKIP)
+            || field.flags.contains(FieldFlags::SKIP_SERIALIZING)
+        {
+            continue;
+        }
+        let path = format!("{prefix}{}", field.name);
+        if field.is_flattened() {
+            // Flattened maps and enums only name their columns at runtime,
+            // and are left out
+            field_columns(field.shape(), &format!("{path}."), columns);
+            continue;
+        }
+        columns.push(Column {
+            name: field.name.to_string(),
+            path,
+            sensitive: field.is_sensitive(),
+        });
+    }
+    true
+}
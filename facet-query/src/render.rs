@@ -0,0 +1,211 @@
+//! Writing selected items as a table, CSV or JSON.
+
+use facet_core::{Type, UserType};
+use facet_ops::value_at;
+use facet_reflect::Peek;
+
+use crate::QueryError;
+
+/// What the table shows for missing values
+const MISSING: &str = "<none>";
+
+/// Space between table columns
+const GAP: &str = "   ";
+
+/// A column of the output: a header, and where its values are in each item
+#[derive(Debug, Clone)]
+pub(crate) struct Column {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) sensitive: bool,
+}
+
+/// The text of each column of each item, `None` where values are missing
+pub(crate) fn cells(
+    items: &[Peek<'_, '_>],
+    columns: &[Column],
+) -> Result<Vec<Vec<Option<String>>>, QueryError> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, &item)| {
+            columns
+                .iter()
+                .map(|column| {
+                    let value = value_at(item, &column.path)
+                        .map_err(|error| QueryError::Column { index, error })?;
+                    match value {
+                        Some(_) if column.sensitive => Ok(Some("[REDACTED]".to_string())),
+                        Some(value) => cell_text(value).map(Some),
+                        None => Ok(None),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Strings as they are, unit variants by name, other scalars through their
+/// `Display` impl, and anything else as compact JSON
+fn cell_text(value: Peek<'_, '_>) -> Result<String, QueryError> {
+    if let Some(text) = value.as_str() {
+        return Ok(text.to_string());
+    }
+    if let Type::User(UserType::Enum(_)) = value.shape().ty
+        && let Some(variant) = value.into_enum().ok().and_then(|e| e.active_variant().ok())
+        && variant.data.fields.is_empty()
+    {
+        return Ok(variant.name.to_string());
+    }
+    if value.scalar_type().is_some() && value.shape().is_display() {
+        return Ok(value.to_string());
+    }
+    json(value, false)
+}
+
+/// Columns padded to their widest value under an upper-case header
+///
+/// Line breaks and tabs in values are shown as spaces, so every item stays
+/// on its own line.
+pub(crate) fn table(columns: &[Column], rows: &[Vec<Option<String>>]) -> String {
+    let header: Vec<String> = columns.iter().map(|c| c.name.to_uppercase()).collect();
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| {
+                    cell.as_deref()
+                        .unwrap_or(MISSING)
+                        .replace(['\n', '\r', '\t'], " ")
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = header.iter().map(|name| name.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    for line in core::iter::once(&header).chain(&rows) {
+        for (column, cell) in line.iter().enumerate() {
+            if column > 0 {
+                out.push_str(GAP);
+            }
+            out.push_str(cell);
+            if column + 1 < line.len() {
+                let padding = widths[column] - cell.chars().count();
+                out.extend(core::iter::repeat_n(' ', padding));
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// A header row of column names, then a row per item with missing values
+/// left empty
+pub(crate) fn csv(columns: &[Column], rows: &[Vec<Option<String>>]) -> String {
+    let mut out = String::new();
+    let header = columns.iter().map(|column| column.name.as_str());
+    write_csv_row(&mut out, header);
+    for row in rows {
+        write_csv_row(
+            &mut out,
+            row.iter().map(|cell| cell.as_deref().unwrap_or("")),
+        );
+    }
+    out
+}
+
+fn write_csv_row<'a>(out: &mut String, fields: impl Iterator<Item = &'a str>) {
+    for (index, field) in fields.enumerate() {
+        if index > 0 {
+            out.push(',');
+        }
+        out.push_str(&facet_csv::escape(field));
+    }
+    out.push('\n');
+}
+
+/// A pretty-printed JSON array of the items
+pub(crate) fn json_items(items: &[Peek<'_, '_>]) -> Result<String, QueryError> {
+    let elements = items
+        .iter()
+        .map(|&item| json(item, true))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(json_array(&elements))
+}
+
+/// A pretty-printed JSON array of objects holding the columns of each item,
+/// keyed by column name, with missing values `null`
+pub(crate) fn json_columns(
+    items: &[Peek<'_, '_>],
+    columns: &[Column],
+) -> Result<String, QueryError> {
+    let mut elements = Vec::with_capacity(items.len());
+    for (index, &item) in items.iter().enumerate() {
+        if columns.is_empty() {
+            elements.push("{}".to_string());
+            continue;
+        }
+        let mut object = String::from("{\n");
+        for (position, column) in columns.iter().enumerate() {
+            if position > 0 {
+                object.push_str(",\n");
+            }
+            let value = value_at(item, &column.path)
+                .map_err(|error| QueryError::Column { index, error })?;
+            let value = match value {
+                Some(value) => json(value, true)?,
+                None => "null".to_string(),
+            };
+            object.push_str("  ");
+            object.push_str(&json(Peek::new(&column.name), false)?);
+            object.push_str(": ");
+            push_indented(&mut object, &value, "  ");
+        }
+        object.push_str("\n}");
+        elements.push(object);
+    }
+    Ok(json_array(&elements))
+}
+
+fn json(value: Peek<'_, '_>, pretty: bool) -> Result<String, QueryError> {
+    let result = if pretty {
+        facet_json::peek_to_string_pretty(value)
+    } else {
+        facet_json::peek_to_string(value)
+    };
+    result.map_err(|err| QueryError::Json(err.to_string()))
+}
+
+fn json_array(elements: &[String]) -> String {
+    if elements.is_empty() {
+        return "[]\n".to_string();
+    }
+    let mut out = String::from("[\n");
+    for (index, element) in elements.iter().enumerate() {
+        if index > 0 {
+            out.push_str(",\n");
+        }
+        out.push_str("  ");
+        push_indented(&mut out, element, "  ");
+    }
+    out.push_str("\n]\n");
+    out
+}
+
+/// Push `text`, indenting all lines but the first by `indent`
+fn push_indented(out: &mut String, text: &str, indent: &str) {
+    for (index, line) in text.lines().enumerate() {
+        if index > 0 {
+            out.push('\n');
+            out.push_str(indent);
+        }
+        out.push_str(line);
+    }
+}
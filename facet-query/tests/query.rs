@@ -0,0 +1,333 @@
+use facet::Facet;
+use facet_ops::{Comparison, OpsError, Selector};
+use facet_query::{OutputFormat, Query, QueryError};
+
+#[derive(Debug, Facet)]
+struct Meta {
+    region: String,
+}
+
+#[derive(Debug, Facet)]
+#[facet(rename_all = "camelCase")]
+struct Service {
+    name: String,
+    port_count: u8,
+    tags: Vec<String>,
+    owner: Option<String>,
+    #[facet(flatten)]
+    meta: Meta,
+    #[facet(sensitive)]
+    token: String,
+    #[facet(skip)]
+    cache: u32,
+}
+
+#[test]
+fn table_has_a_column_per_serialized_field() {
+    let services = vec![
+        Service {
+            name: "api".into(),
+            port_count: 2,
+            tags: vec!["public".into(), "v2".into()],
+            owner: Some("Platform, Core".into()),
+            meta: Meta {
+                region: "eu".into(),
+            },
+            token: "hunter2".into(),
+            cache: 7,
+        },
+        Service {
+            name: "search".into(),
+            port_count: 1,
+            tags: vec![],
+            owner: None,
+            meta: Meta {
+                region: "us".into(),
+            },
+            token: "swordfish".into(),
+            cache: 0,
+        },
+    ];
+    let output = Query::new().render(&services, OutputFormat::Table).unwrap();
+    let expected = "\
+NAME     PORTCOUNT   TAGS              OWNER            REGION   TOKEN
+api      2           [\"public\",\"v2\"]   Platform, Core   eu       [REDACTED]
+search   1           []                <none>           us       [REDACTED]
+";
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn csv_quotes_fields_and_leaves_missing_values_empty() {
+    let services = vec![
+        Service {
+            name: "api".into(),
+            port_count: 2,
+            tags: vec!["public".into(), "v2".into()],
+            owner: Some("Platform, Core".into()),
+            meta: Meta {
+                region: "eu".into(),
+            },
+            token: "hunter2".into(),
+            cache: 7,
+        },
+        Service {
+            name: "search".into(),
+            port_count: 1,
+            tags: vec![],
+            owner: None,
+            meta: Meta {
+                region: "us".into(),
+            },
+            token: "swordfish".into(),
+            cache: 0,
+        },
+    ];
+    let output = Query::new().render(&services, OutputFormat::Csv).unwrap();
+    let expected = "\
+name,portCount,tags,owner,region,token
+api,2,\"[\"\"public\"\",\"\"v2\"\"]\",\"Platform, Core\",eu,[REDACTED]
+search,1,[],,us,[REDACTED]
+";
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn json_renders_whole_items() {
+    let query = Query::new().filter("name == search".parse().unwrap());
+    let services = vec![
+        Service {
+            name: "api".into(),
+            port_count: 2,
+            tags: vec!["public".into(), "v2".into()],
+            owner: Some("Platform, Core".into()),
+            meta: Meta {
+                region: "eu".into(),
+            },
+            token: "hunter2".into(),
+            cache: 7,
+        },
+        Service {
+            name: "search".into(),
+            port_count: 1,
+            tags: vec![],
+            owner: None,
+            meta: Meta {
+                region: "us".into(),
+            },
+            token: "swordfish".into(),
+            cache: 0,
+        },
+    ];
+    let output = query.render(&services, OutputFormat::Json).unwrap();
+    assert!(
+        output.starts_with("[\n  {\n    \"name\": \"search\",\n"),
+        "{output}"
+    );
+    assert!(output.contains("\n    \"region\": \"us\""), "{output}");
+    assert!(!output.contains("cache"), "{output}");
+    assert!(output.ends_with("\n  }\n]\n"), "{output}");
+
+    let none = Query::new().filter("portCount > 5".parse().unwrap());
+    assert_eq!(none.render(&services, OutputFormat::Json).unwrap(), "[]\n");
+}
+
+#[test]
+fn columns_pick_values_by_path() {
+    let query = Query::new().with_columns(["name", "tags[0]", "meta.region"]);
+    let services = vec![
+        Service {
+            name: "api".into(),
+            port_count: 2,
+            tags: vec!["public".into(), "v2".into()],
+            owner: Some("Platform, Core".into()),
+            meta: Meta {
+                region: "eu".into(),
+            },
+            token: "hunter2".into(),
+            cache: 7,
+        },
+        Service {
+            name: "search".into(),
+            port_count: 1,
+            tags: vec![],
+            owner: None,
+            meta: Meta {
+                region: "us".into(),
+            },
+            token: "swordfish".into(),
+            cache: 0,
+        },
+    ];
+    assert_eq!(
+        query.render(&services, OutputFormat::Table).unwrap(),
+        "\
+NAME     TAGS[0]   META.REGION
+api      public    eu
+search   <none>    us
+"
+    );
+    assert_eq!(
+        query.render(&services, OutputFormat::Json).unwrap(),
+        "\
+[
+  {
+    \"name\": \"api\",
+    \"tags[0]\": \"public\",
+    \"meta.region\": \"eu\"
+  },
+  {
+    \"name\": \"search\",
+    \"tags[0]\": null,
+    \"meta.region\": \"us\"
+  }
+]
+"
+    );
+}
+
+#[test]
+fn selectors_all_have_to_match() {
+    let query = Query::new()
+        .filter("portCount >= 1".parse().unwrap())
+        .filter("meta.region != eu".parse().unwrap())
+        .with_columns(["name"]);
+    let services = vec![
+        Service {
+            name: "api".into(),
+            port_count: 2,
+            tags: vec!["public".into(), "v2".into()],
+            owner: Some("Platform, Core".into()),
+            meta: Meta {
+                region: "eu".into(),
+            },
+            token: "hunter2".into(),
+            cache: 7,
+        },
+        Service {
+            name: "search".into(),
+            port_count: 1,
+            tags: vec![],
+            owner: None,
+            meta: Meta {
+                region: "us".into(),
+            },
+            token: "swordfish".into(),
+            cache: 0,
+        },
+    ];
+    assert_eq!(
+        query.render(&services, OutputFormat::Csv).unwrap(),
+        "name\nsearch\n"
+    );
+    assert_eq!(
+        query.render(&services, OutputFormat::Table).unwrap(),
+        "NAME\nsearch\n"
+    );
+
+    let nothing = query.filter("name == api".parse().unwrap());
+    assert_eq!(
+        nothing.render(&services, OutputFormat::Table).unwrap(),
+        "NAME\n"
+    );
+}
+
+#[test]
+fn other_values_are_single_items() {
+    let service = Service {
+        name: "api".into(),
+        port_count: 2,
+        tags: vec![],
+        owner: None,
+        meta: Meta {
+            region: "eu".into(),
+        },
+        token: "hunter2".into(),
+        cache: 7,
+    };
+    let output = Query::new()
+        .with_columns(["name", "portCount"])
+        .render(&service, OutputFormat::Csv)
+        .unwrap();
+    assert_eq!(output, "name,portCount\napi,2\n");
+
+    let numbers = [3u32, 14, 15];
+    let query = Query::new().filter(Selector::new("", Comparison::Ge, "10"));
+    assert_eq!(
+        query.render(&numbers, OutputFormat::Table).unwrap(),
+        "VALUE\n14\n15\n"
+    );
+}
+
+#[test]
+fn formats_parse_from_flag_values() {
+    assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+    assert_eq!("CSV".parse::<OutputFormat>().unwrap(), OutputFormat::Csv);
+    assert_eq!(OutputFormat::default(), OutputFormat::Table);
+    for format in OutputFormat::ALL {
+        assert_eq!(format.to_string().parse::<OutputFormat>().unwrap(), format);
+    }
+    let err = "yaml".parse::<OutputFormat>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown output format `yaml`, expected `table`, `csv` or `json`"
+    );
+}
+
+#[test]
+fn bad_paths_name_the_item() {
+    let services = vec![
+        Service {
+            name: "api".into(),
+            port_count: 2,
+            tags: vec!["public".into(), "v2".into()],
+            owner: Some("Platform, Core".into()),
+            meta: Meta {
+                region: "eu".into(),
+            },
+            token: "hunter2".into(),
+            cache: 7,
+        },
+        Service {
+            name: "search".into(),
+            port_count: 1,
+            tags: vec![],
+            owner: None,
+            meta: Meta {
+                region: "us".into(),
+            },
+            token: "swordfish".into(),
+            cache: 0,
+        },
+    ];
+    let err = Query::new()
+        .with_columns(["name", "nmae"])
+        .render(&services, OutputFormat::Table)
+        .unwrap_err();
+    assert!(
+        matches!(err, QueryError::Column { index: 0, .. }),
+        "{err:?}"
+    );
+
+    let err = Query::new()
+        .filter("name == search".parse().unwrap())
+        .filter("portCount == many".parse().unwrap())
+        .render(&services, OutputFormat::Table)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        QueryError::Select(OpsError::InvalidValue {
+            value: "many".into(),
+            type_name: "u8".into(),
+        })
+    );
+
+    let err = Query::new()
+        .filter("owner.nmae == x".parse().unwrap())
+        .render(&services, OutputFormat::Json)
+        .unwrap_err();
+    assert!(
+        matches!(err, QueryError::Select(OpsError::Path { index: 0, .. })),
+        "{err:?}"
+    );
+}
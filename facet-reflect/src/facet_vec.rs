@@ -0,0 +1,196 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use facet_core::Facet;
+
+use crate::{OwnedFacet, Peek};
+
+/// A list of values of different types, each carrying its own shape.
+///
+/// Useful for event buses and plugin registries: values go in with
+/// [`push`](Self::push), and come out as [`Peek`]s or, for the types a
+/// consumer knows about, as typed references.
+///
+/// # Example
+///
+/// ```
+/// use facet_reflect::FacetVec;
+///
+/// let mut events = FacetVec::new();
+/// events.push(42u32);
+/// events.push(String::from("deployed"));
+///
+/// assert_eq!(events.get_as::<u32>(0), Some(&42));
+/// assert_eq!(events.iter_of::<String>().collect::<Vec<_>>(), ["deployed"]);
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct FacetVec {
+    items: Vec<OwnedFacet>,
+}
+
+impl FacetVec {
+    /// Creates an empty list.
+    pub const fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Appends a value.
+    pub fn push<T: Facet<'static> + Send + Sync>(&mut self, value: T) {
+        self.items.push(OwnedFacet::new(value));
+    }
+
+    /// Appends a value that is already type-erased.
+    pub fn push_owned(&mut self, value: OwnedFacet) {
+        self.items.push(value);
+    }
+
+    /// Removes and returns the value at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> OwnedFacet {
+        self.items.remove(index)
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the value at `index`.
+    pub fn get(&self, index: usize) -> Option<&OwnedFacet> {
+        self.items.get(index)
+    }
+
+    /// Returns the value at `index` if it is a `T`.
+    pub fn get_as<T: Facet<'static>>(&self, index: usize) -> Option<&T> {
+        self.items.get(index)?.downcast_ref()
+    }
+
+    /// Iterates over the values as [`Peek`]s.
+    pub fn iter(&self) -> impl Iterator<Item = Peek<'_, 'static>> {
+        self.items.iter().map(OwnedFacet::peek)
+    }
+
+    /// Iterates over the values that are a `T`, skipping the others.
+    pub fn iter_of<T: Facet<'static>>(&self) -> impl Iterator<Item = &T> {
+        self.items.iter().filter_map(OwnedFacet::downcast_ref)
+    }
+
+    /// Iterates over the type-erased values.
+    pub fn values(&self) -> core::slice::Iter<'_, OwnedFacet> {
+        self.items.iter()
+    }
+}
+
+impl FromIterator<OwnedFacet> for FacetVec {
+    fn from_iter<I: IntoIterator<Item = OwnedFacet>>(iter: I) -> Self {
+        Self {
+            items: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for FacetVec {
+    type Item = OwnedFacet;
+    type IntoIter = alloc::vec::IntoIter<OwnedFacet>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+/// A map from names to values of different types, each carrying its own
+/// shape.
+///
+/// Entries are kept sorted by key.
+///
+/// # Example
+///
+/// ```
+/// use facet_reflect::FacetMap;
+///
+/// let mut plugins = FacetMap::new();
+/// plugins.insert("retries", 3u8);
+/// plugins.insert("greeting", String::from("hello"));
+///
+/// assert_eq!(plugins.get_as::<u8>("retries"), Some(&3));
+/// assert_eq!(plugins.get_as::<u8>("greeting"), None);
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct FacetMap {
+    entries: BTreeMap<String, OwnedFacet>,
+}
+
+impl FacetMap {
+    /// Creates an empty map.
+    pub const fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Inserts a value, returning the one it replaces.
+    pub fn insert<T: Facet<'static> + Send + Sync>(
+        &mut self,
+        key: impl Into<String>,
+        value: T,
+    ) -> Option<OwnedFacet> {
+        self.insert_owned(key, OwnedFacet::new(value))
+    }
+
+    /// Inserts a value that is already type-erased, returning the one it
+    /// replaces.
+    pub fn insert_owned(
+        &mut self,
+        key: impl Into<String>,
+        value: OwnedFacet,
+    ) -> Option<OwnedFacet> {
+        self.entries.insert(key.into(), value)
+    }
+
+    /// Removes and returns the value for `key`.
+    pub fn remove(&mut self, key: &str) -> Option<OwnedFacet> {
+        self.entries.remove(key)
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the value for `key`.
+    pub fn get(&self, key: &str) -> Option<&OwnedFacet> {
+        self.entries.get(key)
+    }
+
+    /// Returns the value for `key` if it is a `T`.
+    pub fn get_as<T: Facet<'static>>(&self, key: &str) -> Option<&T> {
+        self.entries.get(key)?.downcast_ref()
+    }
+
+    /// Iterates over the entries in key order, with the values as [`Peek`]s.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Peek<'_, 'static>)> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.peek()))
+    }
+
+    /// Iterates over the entries in key order, with the values type-erased.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &OwnedFacet)> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.as_str(), value))
+    }
+}
@@ -22,6 +22,16 @@ mod resolution;
 #[cfg(feature = "alloc")]
 pub use resolution::*;
 
+#[cfg(feature = "alloc")]
+mod owned_facet;
+#[cfg(feature = "alloc")]
+pub use owned_facet::*;
+
+#[cfg(feature = "alloc")]
+mod facet_vec;
+#[cfg(feature = "alloc")]
+pub use facet_vec::*;
+
 mod peek;
 pub use peek::*;
 
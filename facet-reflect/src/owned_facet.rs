@@ -0,0 +1,187 @@
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+
+use crate::{HeapValue, Peek, ReflectError, trace};
+use facet_core::{Characteristic, Facet, MarkerTraits, PtrMut, Shape};
+
+/// An owned, type-erased value: a heap allocation plus the [`Shape`] that
+/// describes it.
+///
+/// This is `Box<dyn Any>`, but reflectable: the value can be inspected with
+/// [`peek`](Self::peek), cloned with [`try_clone`](Self::try_clone), compared
+/// with `==` and downcast back to its concrete type.
+///
+/// `OwnedFacet` is `Send` and `Sync`. Values put in with [`new`](Self::new)
+/// are checked by the compiler; for values coming from a [`HeapValue`], the
+/// caller of [`from_heap_value`](Self::from_heap_value) vouches for it.
+pub struct OwnedFacet {
+    data: PtrMut,
+    shape: &'static Shape,
+}
+
+// SAFETY: every constructor makes sure the value is `Send` and `Sync`, either
+// through trait bounds or through the caller's promise.
+unsafe impl Send for OwnedFacet {}
+unsafe impl Sync for OwnedFacet {}
+
+impl OwnedFacet {
+    /// Moves `value` to the heap.
+    pub fn new<T: Facet<'static> + Send + Sync>(value: T) -> Self {
+        let data = PtrMut::new(alloc::boxed::Box::into_raw(alloc::boxed::Box::new(value)));
+        Self {
+            data,
+            shape: T::SHAPE,
+        }
+    }
+
+    /// Takes ownership of a value built with [`Partial`](crate::Partial).
+    ///
+    /// # Returns
+    ///
+    /// `Err` if the shape isn't marked both `Send` and `Sync`. Derived types
+    /// without type parameters get these marks from
+    /// `#[facet(traits(Send, Sync))]`.
+    ///
+    /// # Safety
+    ///
+    /// The value's type must be `Send` and `Sync`. The shape's marker traits
+    /// are not proof of that: shapes can be built by hand, and nothing ties
+    /// their marks to the type.
+    pub unsafe fn from_heap_value<const BORROW: bool>(
+        value: HeapValue<'static, BORROW>,
+    ) -> Result<Self, ReflectError> {
+        let shape = value.shape();
+        if !shape
+            .marker_traits
+            .contains(MarkerTraits::SEND.union(MarkerTraits::SYNC))
+        {
+            return Err(ReflectError::OperationFailed {
+                shape,
+                operation: "type is not marked Send and Sync",
+            });
+        }
+
+        let data = shape.allocate().map_err(|_| ReflectError::Unsized {
+            shape,
+            operation: "OwnedFacet::from_heap_value",
+        })?;
+        let size = shape
+            .layout
+            .sized_layout()
+            .map_or(0, |layout| layout.size());
+        // SAFETY: both allocations hold `size` bytes for this shape, and the
+        // value is moved rather than dropped
+        unsafe {
+            value.move_out(|src| {
+                let (src, dst) = (src.as_mut_byte_ptr(), data.as_mut_byte_ptr());
+                core::ptr::copy_nonoverlapping(src, dst, size)
+            });
+        }
+        Ok(Self {
+            // SAFETY: the value was just moved in
+            data: unsafe { data.assume_init() },
+            shape,
+        })
+    }
+
+    /// Returns the shape of the value.
+    pub fn shape(&self) -> &'static Shape {
+        self.shape
+    }
+
+    /// The tag naming the value's type when it is serialized among values of
+    /// other types: the shape's `#[facet(type_tag = "...")]` if it has one,
+    /// and its type name otherwise.
+    pub fn type_tag(&self) -> Cow<'static, str> {
+        match self.shape.type_tag {
+            Some(tag) => Cow::Borrowed(tag),
+            None => Cow::Owned(self.shape.to_string()),
+        }
+    }
+
+    /// Returns a peek that allows exploring the value.
+    pub fn peek(&self) -> Peek<'_, 'static> {
+        // SAFETY: `data` holds an initialized value of `shape`
+        unsafe { Peek::unchecked_new(self.data.as_const(), self.shape) }
+    }
+
+    /// Clones the value with its `Clone` implementation.
+    ///
+    /// # Returns
+    ///
+    /// `Err` if the type doesn't implement `Clone`.
+    pub fn try_clone(&self) -> Result<Self, ReflectError> {
+        let shape = self.shape;
+        if !shape.is(Characteristic::Clone) {
+            return Err(ReflectError::MissingCharacteristic {
+                shape,
+                characteristic: Characteristic::Clone,
+            });
+        }
+
+        let data = shape.allocate().map_err(|_| ReflectError::Unsized {
+            shape,
+            operation: "OwnedFacet::try_clone",
+        })?;
+        // SAFETY: `data` is uninitialized memory for this shape, and clone_into
+        // initializes it
+        unsafe {
+            let data = data.assume_init();
+            shape.call_clone_into(self.data.as_const(), data);
+            Ok(Self { data, shape })
+        }
+    }
+
+    /// Returns a reference to the value if it is a `T`.
+    pub fn downcast_ref<T: Facet<'static>>(&self) -> Option<&T> {
+        if self.shape != T::SHAPE {
+            return None;
+        }
+        // SAFETY: the shapes match, so the value is a `T`
+        Some(unsafe { self.data.as_const().get::<T>() })
+    }
+
+    /// Moves the value out if it is a `T`, and gives it back otherwise.
+    pub fn downcast<T: Facet<'static>>(self) -> Result<T, Self> {
+        if self.shape != T::SHAPE {
+            return Err(self);
+        }
+        let this = core::mem::ManuallyDrop::new(self);
+        // SAFETY: the shapes match, so the value is a `T`; the allocation is
+        // freed without dropping the value that was read out
+        unsafe {
+            let value = this.data.as_const().read::<T>();
+            let _ = this.shape.deallocate_mut(this.data);
+            Ok(value)
+        }
+    }
+}
+
+impl Drop for OwnedFacet {
+    fn drop(&mut self) {
+        trace!("Dropping owned facet of shape '{}'", self.shape);
+        // SAFETY: `data` holds an initialized value of `shape` that was
+        // allocated for it
+        unsafe {
+            self.shape.call_drop_in_place(self.data);
+            let _ = self.shape.deallocate_mut(self.data);
+        }
+    }
+}
+
+impl PartialEq for OwnedFacet {
+    fn eq(&self, other: &Self) -> bool {
+        self.shape == other.shape
+            && unsafe {
+                self.shape
+                    .call_partial_eq(self.data.as_const(), other.data.as_const())
+            }
+            .unwrap_or(false)
+    }
+}
+
+impl core::fmt::Debug for OwnedFacet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.peek(), f)
+    }
+}
@@ -0,0 +1,42 @@
+//! Building whole values from shape metadata alone.
+
+use facet_core::{Facet, Shape};
+
+use crate::{HeapValue, Partial, ReflectError};
+
+/// Build a default `T` from its shape, even if `T` doesn't implement `Default`.
+///
+/// See [`Partial::set_default_deep`] for how each kind of type is defaulted.
+///
+/// ```
+/// use facet::Facet;
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Limits { max: u32 }
+///
+/// #[derive(Facet, Debug, PartialEq)]
+/// struct Config {
+///     name: String,
+///     tags: Vec<String>,
+///     limits: Limits,
+///     #[facet(default = 8080)]
+///     port: u16,
+/// }
+///
+/// let config: Config = facet_reflect::default_value().unwrap();
+/// assert_eq!(config.limits, Limits { max: 0 });
+/// assert_eq!(config.port, 8080);
+/// ```
+pub fn default_value<'facet, T: Facet<'facet>>() -> Result<T, ReflectError> {
+    Partial::alloc::<T>()?
+        .set_default_deep()?
+        .build()?
+        .materialize()
+}
+
+/// Build a default value for `shape`, even if the type doesn't implement `Default`.
+///
+/// See [`Partial::set_default_deep`] for how each kind of type is defaulted.
+pub fn build_default(shape: &'static Shape) -> Result<HeapValue<'static>, ReflectError> {
+    Partial::alloc_shape(shape)?.set_default_deep()?.build()
+}
@@ -71,6 +71,23 @@ impl<'facet, const BORROW: bool> HeapValue<'facet, BORROW> {
         trace!("HeapValue::materialize: Successfully materialized value");
         Ok(res)
     }
+
+    /// Returns a mutable pointer to the value.
+    pub(crate) fn data_mut(&mut self) -> PtrMut {
+        PtrMut::new(self.guard.as_ref().unwrap().ptr.as_ptr())
+    }
+
+    /// Hands the value over to `f`, then frees the allocation without dropping it.
+    ///
+    /// # Safety
+    ///
+    /// `f` must take ownership of the value behind the pointer (e.g. by moving it
+    /// somewhere else with `ptr::copy` or a vtable function that reads it out).
+    pub(crate) unsafe fn move_out(mut self, f: impl FnOnce(PtrMut)) {
+        let guard = self.guard.take().unwrap();
+        f(PtrMut::new(guard.ptr.as_ptr()));
+        drop(guard); // free memory (but don't drop in place)
+    }
 }
 
 impl<'facet, const BORROW: bool> HeapValue<'facet, BORROW> {
@@ -122,6 +122,12 @@ use core::marker::PhantomData;
 mod heap_value;
 pub use heap_value::*;
 
+mod so_far;
+pub use so_far::*;
+
+mod default_value;
+pub use default_value::*;
+
 use facet_core::{
     Def, EnumType, Field, PtrUninit, Shape, SliceBuilderVTable, Type, UserType, Variant,
 };
@@ -866,6 +872,43 @@ impl Frame {
         false
     }
 
+    /// Runs the shape's `#[facet(invariants = ...)]` check, if it has one.
+    ///
+    /// The value must be fully initialized.
+    fn check_invariants(&self) -> Result<(), ReflectError> {
+        let value = unsafe { self.data.assume_init().as_const() };
+        match unsafe { self.shape.call_invariants(value) } {
+            Some(Err(message)) => Err(ReflectError::UserInvariantFailed {
+                message,
+                shape: self.shape,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Runs `field`'s `#[facet(invariants = ...)]` check, if it has one and
+    /// this frame holds that field's value.
+    ///
+    /// The value must be fully initialized.
+    fn check_field_invariants(&self, field: &Field) -> Result<(), ReflectError> {
+        if field.shape() != self.shape {
+            return Ok(());
+        }
+        let value = unsafe { self.data.assume_init().as_const() };
+        let outcome = match (field.invariants_message, field.invariants) {
+            (Some(invariants), _) => unsafe { invariants(value) },
+            (None, Some(invariants)) => match unsafe { invariants(value) } {
+                true => Ok(()),
+                false => Err(alloc::string::String::from("invariant check failed")),
+            },
+            (None, None) => return Ok(()),
+        };
+        outcome.map_err(|message| ReflectError::UserInvariantFailed {
+            message,
+            shape: self.shape,
+        })
+    }
+
     /// Returns an error if the value is not fully initialized
     fn require_full_initialization(&self) -> Result<(), ReflectError> {
         match self.tracker {
@@ -21,21 +21,11 @@ impl<'facet, const BORROW: bool> Partial<'facet, BORROW> {
             return Err(e);
         }
 
-        // Check invariants if present
-        // Safety: The value is fully initialized at this point (we just checked with require_full_initialization)
-        let value_ptr = unsafe { frame.data.assume_init().as_const() };
-        if let Some(result) = unsafe { frame.shape.call_invariants(value_ptr) } {
-            match result {
-                Ok(()) => {
-                    // Invariants passed
-                }
-                Err(message) => {
-                    // Put the frame back so Drop can handle cleanup properly
-                    let shape = frame.shape;
-                    self.frames_mut().push(frame);
-                    return Err(ReflectError::UserInvariantFailed { message, shape });
-                }
-            }
+        // Check invariants if present (the value is fully initialized at this point)
+        if let Err(e) = frame.check_invariants() {
+            // Put the frame back so Drop can handle cleanup properly
+            self.frames_mut().push(frame);
+            return Err(e);
         }
 
         // Mark as built to prevent Drop from cleaning up the value
@@ -11,7 +11,16 @@ impl<const BORROW: bool> Partial<'_, BORROW> {
     /// `begin_list` does not clear the list if it was previously initialized.
     /// `begin_list` does not push a new frame to the stack, and thus does not
     /// require `end` to be called afterwards.
-    pub fn begin_list(mut self) -> Result<Self, ReflectError> {
+    pub fn begin_list(self) -> Result<Self, ReflectError> {
+        self.begin_list_with_capacity(0)
+    }
+
+    /// Like [`Partial::begin_list`], but makes room for `capacity` items up front
+    /// when the list is initialized here.
+    ///
+    /// Formats that know how many items follow (e.g. length-prefixed binary
+    /// formats) use this to avoid reallocating while filling the collection.
+    pub fn begin_list_with_capacity(mut self, capacity: usize) -> Result<Self, ReflectError> {
         crate::trace!("begin_list()");
         let frame = self.frames_mut().last_mut().unwrap();
 
@@ -92,9 +101,8 @@ impl<const BORROW: bool> Partial<'_, BORROW> {
                     }
                 };
 
-                // Initialize the list with default capacity (0)
                 unsafe {
-                    init_fn(frame.data, 0);
+                    init_fn(frame.data, capacity);
                 }
 
                 // Update tracker to List state and mark as initialized
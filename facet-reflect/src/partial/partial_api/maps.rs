@@ -10,7 +10,16 @@ impl<const BORROW: bool> Partial<'_, BORROW> {
     /// It does _not_ push a new frame onto the stack.
     ///
     /// For `Def::DynamicValue` types, this initializes as an object instead of a map.
-    pub fn begin_map(mut self) -> Result<Self, ReflectError> {
+    pub fn begin_map(self) -> Result<Self, ReflectError> {
+        self.begin_map_with_capacity(0)
+    }
+
+    /// Like [`Partial::begin_map`], but makes room for `capacity` entries up front
+    /// when the map is initialized here.
+    ///
+    /// Formats that know how many entries follow (e.g. length-prefixed binary
+    /// formats) use this to avoid reallocating while filling the collection.
+    pub fn begin_map_with_capacity(mut self, capacity: usize) -> Result<Self, ReflectError> {
         let frame = self.frames_mut().last_mut().unwrap();
 
         // Check tracker state before initializing
@@ -78,9 +87,8 @@ impl<const BORROW: bool> Partial<'_, BORROW> {
             Def::Map(map_def) => {
                 let init_fn = map_def.vtable.init_in_place_with_capacity;
 
-                // Initialize the map with default capacity (0)
                 unsafe {
-                    init_fn(frame.data, 0);
+                    init_fn(frame.data, capacity);
                 }
 
                 // Update tracker to Map state and mark as initialized
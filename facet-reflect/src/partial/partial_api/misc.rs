@@ -204,8 +204,31 @@ impl<'facet, const BORROW: bool> Partial<'facet, BORROW> {
                 return Err(e);
             }
 
-            // Validate the frame is fully initialized
-            if let Err(e) = frame.require_full_initialization() {
+            // Find the field this frame fills in, so its field-level invariants run too.
+            // The parent is located the same way as when marking the field initialized below.
+            let parent_field = path.last().and_then(|field_name| {
+                let parent_path = &path[..path.len() - 1];
+                let parent_frame = if parent_path.is_empty() {
+                    self.frames().get(start_depth.saturating_sub(1))
+                } else {
+                    stored_frames
+                        .get(parent_path)
+                        .or_else(|| self.frames().get(start_depth + parent_path.len() - 1))
+                };
+                parent_frame.and_then(|parent| Self::find_field(parent, field_name))
+            });
+
+            // Validate the frame is fully initialized and upholds its invariants
+            if let Err(e) = frame
+                .require_full_initialization()
+                .and_then(|()| frame.check_invariants())
+                .and_then(|()| match parent_field {
+                    Some(field) if !frame.using_custom_deserialization => {
+                        frame.check_field_invariants(field)
+                    }
+                    _ => Ok(()),
+                })
+            {
                 // With the ownership transfer model:
                 // - Parent's iset was cleared when we entered this field
                 // - Parent won't drop it, so we must deinit it ourselves
@@ -379,6 +402,23 @@ impl<'facet, const BORROW: bool> Partial<'facet, BORROW> {
         }
     }
 
+    /// Find the field for a given field name in a frame
+    fn find_field(frame: &Frame, field_name: &str) -> Option<&'static Field> {
+        match frame.shape.ty {
+            Type::User(UserType::Struct(struct_type)) => {
+                struct_type.fields.iter().find(|f| f.name == field_name)
+            }
+            Type::User(UserType::Enum(_)) => {
+                if let Tracker::Enum { variant, .. } = &frame.tracker {
+                    variant.data.fields.iter().find(|f| f.name == field_name)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
     /// Find the field index for a given field name in a frame
     fn find_field_index(frame: &Frame, field_name: &str) -> Option<usize> {
         match frame.shape.ty {
@@ -619,7 +659,16 @@ impl<'facet, const BORROW: bool> Partial<'facet, BORROW> {
                 "end(): require_full_initialization result: {:?}",
                 result.is_ok()
             );
-            result?
+            result?;
+
+            // The value is complete, so nested values get their invariants
+            // checked here rather than only the root in build()
+            frame.check_invariants()?;
+            if !frame.using_custom_deserialization
+                && let Some(field) = self.parent_field()
+            {
+                frame.check_field_invariants(field)?;
+            }
         }
 
         // Pop the frame and save its data pointer for SmartPointer handling
@@ -353,6 +353,68 @@ impl<'facet, const BORROW: bool> Partial<'facet, BORROW> {
         }
     }
 
+    /// Sets the current frame to a default value built from the shape, even when the
+    /// type itself doesn't implement `Default`.
+    ///
+    /// Shapes that advertise `Default` (derived types need `#[facet(default)]` for
+    /// that) use it. Otherwise structs get each field's
+    /// `#[facet(default)]` or a recursively built default, lists, maps and sets start
+    /// empty, arrays fill every element, smart pointers wrap a default pointee, and
+    /// enums pick their first unit variant (or their first variant if none is unit).
+    ///
+    /// Errors out if some leaf (e.g. a scalar without `Default`) has no default.
+    pub fn set_default_deep(self) -> Result<Self, ReflectError> {
+        let shape = self.shape();
+        // Arrays always advertise `Default`, even when their element has none,
+        // so default them element by element
+        if let Def::Array(array_def) = shape.def {
+            let mut this = self.begin_array()?;
+            for index in 0..array_def.n {
+                this = this.begin_nth_field(index)?.set_default_deep()?.end()?;
+            }
+            return Ok(this);
+        }
+        if shape.is(Characteristic::Default) {
+            return self.set_default();
+        }
+
+        match shape.def {
+            Def::List(_) => return self.begin_list(),
+            Def::Map(_) => return self.begin_map(),
+            Def::Set(_) => return self.begin_set(),
+            Def::Pointer(_) => return self.begin_smart_ptr()?.set_default_deep()?.end(),
+            _ => {}
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(_)) => self.set_fields_default_deep(),
+            Type::User(UserType::Enum(enum_type)) => {
+                let index = enum_type
+                    .variants
+                    .iter()
+                    .position(|v| v.data.fields.is_empty())
+                    .unwrap_or(0);
+                self.select_nth_variant(index)?.set_fields_default_deep()
+            }
+            _ => Err(ReflectError::OperationFailed {
+                shape,
+                operation: "type has no default value",
+            }),
+        }
+    }
+
+    /// Default every field of the current struct or variant, see [`Self::set_default_deep`].
+    fn set_fields_default_deep(mut self) -> Result<Self, ReflectError> {
+        for (index, field) in self.get_fields()?.iter().enumerate() {
+            self = if field.default.is_some() {
+                self.set_nth_field_to_default(index)?
+            } else {
+                self.begin_nth_field(index)?.set_default_deep()?.end()?
+            };
+        }
+        Ok(self)
+    }
+
     /// Copy a value from a Peek into the current frame.
     ///
     /// # Invariants
@@ -10,7 +10,16 @@ impl<const BORROW: bool> Partial<'_, BORROW> {
     /// `begin_set` does not clear the set if it was previously initialized.
     /// `begin_set` does not push a new frame to the stack, and thus does not
     /// require `end` to be called afterwards.
-    pub fn begin_set(mut self) -> Result<Self, ReflectError> {
+    pub fn begin_set(self) -> Result<Self, ReflectError> {
+        self.begin_set_with_capacity(0)
+    }
+
+    /// Like [`Partial::begin_set`], but makes room for `capacity` items up front
+    /// when the set is initialized here.
+    ///
+    /// Formats that know how many items follow (e.g. length-prefixed binary
+    /// formats) use this to avoid reallocating while filling the collection.
+    pub fn begin_set_with_capacity(mut self, capacity: usize) -> Result<Self, ReflectError> {
         crate::trace!("begin_set()");
         let frame = self.frames_mut().last_mut().unwrap();
 
@@ -52,9 +61,8 @@ impl<const BORROW: bool> Partial<'_, BORROW> {
 
         let init_fn = set_def.vtable.init_in_place_with_capacity;
 
-        // Initialize the set with default capacity (0)
         unsafe {
-            init_fn(frame.data, 0);
+            init_fn(frame.data, capacity);
         }
 
         // Update tracker to Set state and mark as initialized
@@ -0,0 +1,164 @@
+use facet_core::{Field, Shape, StructType, Type, UserType, Variant};
+
+use super::{Frame, Partial, PartialState, Tracker, iset::ISet};
+use crate::Peek;
+
+/// A read-only view of a value that [`Partial`] is still building
+///
+/// Only the parts that are already fully initialized can be read: a value
+/// that is completely built is available through [`PeekSoFar::as_peek`], and
+/// for structs and enum variants under construction, each field that has been
+/// set (and whose frame has been ended) is available through
+/// [`PeekSoFar::field`]. Fields that are unset or still being built are
+/// skipped.
+///
+/// This lets deserializers look back at what they've parsed so far (e.g. to
+/// resolve untagged enums) and lets debuggers show in-progress builds.
+#[derive(Clone, Copy)]
+pub struct PeekSoFar<'mem, 'facet> {
+    frame: &'mem Frame,
+    _invariant: core::marker::PhantomData<fn(&'facet ()) -> &'facet ()>,
+}
+
+impl core::fmt::Debug for PeekSoFar<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut s = f.debug_struct("PeekSoFar");
+        s.field("shape", &self.shape());
+        s.field("is_init", &self.is_init());
+        if let Some(variant) = self.variant() {
+            s.field("variant", &variant.name);
+        }
+        if let Some(fields) = self.fields() {
+            let initialized: alloc::vec::Vec<_> = fields
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| self.field_is_init(*index))
+                .map(|(_, field)| field.name)
+                .collect();
+            s.field("initialized_fields", &initialized);
+        }
+        s.finish()
+    }
+}
+
+impl<'mem, 'facet> PeekSoFar<'mem, 'facet> {
+    /// The shape of the value being built
+    #[inline]
+    pub fn shape(&self) -> &'static Shape {
+        self.frame.shape
+    }
+
+    /// Whether the value is completely initialized
+    pub fn is_init(&self) -> bool {
+        match self.frame.tracker {
+            Tracker::Struct { current_child, .. } | Tracker::Enum { current_child, .. } => {
+                self.frame.is_init && current_child.is_none()
+            }
+            _ => self.frame.is_init,
+        }
+    }
+
+    /// The whole value, if it is completely initialized
+    pub fn as_peek(&self) -> Option<Peek<'mem, 'facet>> {
+        if !self.is_init() {
+            return None;
+        }
+        // SAFETY: the frame is fully initialized with a value of its shape
+        Some(unsafe { Peek::unchecked_new(self.frame.data.assume_init().as_const(), self.shape()) })
+    }
+
+    /// The enum variant being built, if this is an enum and a variant has been selected
+    pub fn variant(&self) -> Option<&'static Variant> {
+        match self.frame.tracker {
+            Tracker::Enum { variant, .. } => Some(variant),
+            _ => None,
+        }
+    }
+
+    /// The fields of the struct or selected enum variant, initialized or not
+    pub fn fields(&self) -> Option<&'static [Field]> {
+        if let Some(variant) = self.variant() {
+            return Some(variant.data.fields);
+        }
+        match self.shape().ty {
+            Type::User(UserType::Struct(StructType { fields, .. })) => Some(fields),
+            _ => None,
+        }
+    }
+
+    fn field_is_init(&self, index: usize) -> bool {
+        let (iset, current_child): (Option<&ISet>, _) = match &self.frame.tracker {
+            Tracker::Struct {
+                iset,
+                current_child,
+            } => (Some(iset), *current_child),
+            Tracker::Enum {
+                data,
+                current_child,
+                ..
+            } => (Some(data), *current_child),
+            _ => (None, None),
+        };
+        if current_child == Some(index) {
+            return false;
+        }
+        self.frame.is_init || iset.is_some_and(|iset| iset.get(index))
+    }
+
+    /// The field at `index`, if it has been fully initialized
+    pub fn field(&self, index: usize) -> Option<Peek<'mem, 'facet>> {
+        let field = self.fields()?.get(index)?;
+        if !self.field_is_init(index) {
+            return None;
+        }
+        // SAFETY: the field is in bounds and fully initialized
+        Some(unsafe {
+            Peek::unchecked_new(
+                self.frame.data.field_init(field.offset).as_const(),
+                field.shape(),
+            )
+        })
+    }
+
+    /// The field named `name`, if it has been fully initialized
+    pub fn field_by_name(&self, name: &str) -> Option<Peek<'mem, 'facet>> {
+        let index = self.fields()?.iter().position(|field| field.name == name)?;
+        self.field(index)
+    }
+
+    /// Iterates over the fields that have been fully initialized so far
+    pub fn initialized_fields(
+        &self,
+    ) -> impl Iterator<Item = (&'static Field, Peek<'mem, 'facet>)> + '_ {
+        self.fields()
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, field)| Some((field, self.field(index)?)))
+    }
+}
+
+impl<'facet, const BORROW: bool> Partial<'facet, BORROW> {
+    /// A read-only view of what the current frame holds so far
+    ///
+    /// Returns `None` if the Partial is inactive (poisoned or built).
+    pub fn peek_so_far(&self) -> Option<PeekSoFar<'_, 'facet>> {
+        self.frames_so_far().last()
+    }
+
+    /// Read-only views of every frame, from the root value to the current frame
+    ///
+    /// Parent frames usually report the child being built as uninitialized:
+    /// a field only shows up once its frame has been ended.
+    pub fn frames_so_far(&self) -> impl DoubleEndedIterator<Item = PeekSoFar<'_, 'facet>> {
+        let frames: &[Frame] = if self.state == PartialState::Active {
+            self.frames()
+        } else {
+            &[]
+        };
+        frames.iter().map(|frame| PeekSoFar {
+            frame,
+            _invariant: core::marker::PhantomData,
+        })
+    }
+}
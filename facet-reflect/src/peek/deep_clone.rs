@@ -0,0 +1,156 @@
+use facet_core::{Def, Type, UserType};
+
+use crate::{HeapValue, Partial, ReflectError};
+
+use super::Peek;
+
+impl<'mem, 'facet> Peek<'mem, 'facet> {
+    /// Clones the value behind this peek into a new, owned, type-erased [`HeapValue`].
+    ///
+    /// Values whose shape implements `Clone` are copied with their own `Clone`
+    /// impl. Everything else is rebuilt structurally: struct and enum fields,
+    /// options, results, lists, arrays, maps, sets and smart pointers are
+    /// walked recursively and cloned one by one, so a value only needs each of
+    /// its leaves to be clonable.
+    ///
+    /// This is useful when you only have a [`Peek`] (and no concrete type to
+    /// call `.clone()` on) but need a copy that outlives the original.
+    pub fn deep_clone(&self) -> Result<HeapValue<'facet>, ReflectError> {
+        let partial = Partial::alloc_shape(self.shape)?;
+        clone_into_partial(partial, *self)?.build()
+    }
+}
+
+fn clone_into_partial<'facet>(
+    partial: Partial<'facet>,
+    peek: Peek<'_, 'facet>,
+) -> Result<Partial<'facet>, ReflectError> {
+    let shape = peek.shape;
+
+    if shape.is_clone() {
+        let src = peek.data;
+        // SAFETY: `call_clone_into` fully initializes `dst` with a value of
+        // `shape` when it returns `Some`, and leaves it untouched otherwise.
+        return unsafe {
+            partial.set_from_function(move |dst| {
+                shape
+                    .call_clone_into(src, dst.assume_init())
+                    .ok_or(ReflectError::OperationFailed {
+                        shape,
+                        operation: "type does not implement Clone",
+                    })
+            })
+        };
+    }
+
+    match shape.def {
+        Def::Option(_) => {
+            let option = peek.into_option()?;
+            match option.value() {
+                Some(inner) => clone_into_partial(partial.begin_some()?, inner)?.end(),
+                None => partial.set_default(),
+            }
+        }
+        Def::Result(_) => {
+            let result = peek.into_result()?;
+            if let Some(ok) = result.ok() {
+                clone_into_partial(partial.begin_ok()?, ok)?.end()
+            } else if let Some(err) = result.err() {
+                clone_into_partial(partial.begin_err()?, err)?.end()
+            } else {
+                Err(ReflectError::OperationFailed {
+                    shape,
+                    operation: "result is neither Ok nor Err",
+                })
+            }
+        }
+        Def::List(_) => {
+            let mut partial = partial.begin_list()?;
+            for item in peek.into_list()?.iter() {
+                partial = clone_into_partial(partial.begin_list_item()?, item)?.end()?;
+            }
+            Ok(partial)
+        }
+        Def::Array(_) => {
+            let mut partial = partial.begin_array()?;
+            for (index, item) in peek.into_list_like()?.iter().enumerate() {
+                partial = clone_into_partial(partial.begin_nth_field(index)?, item)?.end()?;
+            }
+            Ok(partial)
+        }
+        Def::Map(_) => {
+            let mut partial = partial.begin_map()?;
+            for (key, value) in peek.into_map()?.iter() {
+                partial = clone_into_partial(partial.begin_key()?, key)?.end()?;
+                partial = clone_into_partial(partial.begin_value()?, value)?.end()?;
+            }
+            Ok(partial)
+        }
+        Def::Set(_) => {
+            let mut partial = partial.begin_set()?;
+            for item in peek.into_set()?.iter() {
+                partial = clone_into_partial(partial.begin_set_item()?, item)?.end()?;
+            }
+            Ok(partial)
+        }
+        Def::Pointer(def) if def.is_lock() => {
+            // Mutex, RwLock and RefCell are cloned by locking the original
+            let ptr = peek.into_pointer()?;
+            let partial = partial.begin_smart_ptr()?;
+            ptr.with_locked(|inner| clone_into_partial(partial, inner))
+                .ok_or(ReflectError::OperationFailed {
+                    shape,
+                    operation: "cannot lock this value, it is locked elsewhere",
+                })??
+                .end()
+        }
+        Def::Pointer(_) => {
+            let inner =
+                peek.into_pointer()?
+                    .borrow_inner()
+                    .ok_or(ReflectError::OperationFailed {
+                        shape,
+                        operation: "cannot borrow the pointee of this pointer",
+                    })?;
+            clone_into_partial(partial.begin_smart_ptr()?, inner)?.end()
+        }
+        _ => match shape.ty {
+            Type::User(UserType::Struct(_)) => {
+                let peek_struct = peek.into_struct()?;
+                let mut partial = partial;
+                for index in 0..peek_struct.field_count() {
+                    let field =
+                        peek_struct
+                            .field(index)
+                            .map_err(|_| ReflectError::OperationFailed {
+                                shape,
+                                operation: "struct field out of bounds",
+                            })?;
+                    partial = clone_into_partial(partial.begin_nth_field(index)?, field)?.end()?;
+                }
+                Ok(partial)
+            }
+            Type::User(UserType::Enum(_)) => {
+                let peek_enum = peek.into_enum()?;
+                let variant_error = |_| ReflectError::OperationFailed {
+                    shape,
+                    operation: "could not determine the active enum variant",
+                };
+                let variant = peek_enum.active_variant().map_err(variant_error)?;
+                let mut partial = partial
+                    .select_nth_variant(peek_enum.variant_index().map_err(variant_error)?)?;
+                for index in 0..variant.data.fields.len() {
+                    let Some(field) = peek_enum.field(index).map_err(variant_error)? else {
+                        break;
+                    };
+                    partial = clone_into_partial(partial.begin_nth_field(index)?, field)?.end()?;
+                }
+                Ok(partial)
+            }
+            _ => Err(ReflectError::OperationFailed {
+                shape,
+                operation: "deep_clone: type is neither Clone nor structurally clonable",
+            }),
+        },
+    }
+}
@@ -0,0 +1,182 @@
+use facet_core::{Def, Field, Type, UserType};
+
+use crate::ReflectError;
+
+use super::{HasFields, Peek};
+
+/// Options for [`Peek::eq_with`].
+///
+/// By default, fields marked `#[facet(ignore_in_eq)]` are skipped and every
+/// other field is compared.
+#[derive(Debug, Clone, Copy)]
+pub struct EqOptions {
+    compare_ignored: bool,
+    ignore_metadata: bool,
+}
+
+impl Default for EqOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EqOptions {
+    /// Creates options that skip `#[facet(ignore_in_eq)]` fields.
+    pub const fn new() -> Self {
+        Self {
+            compare_ignored: false,
+            ignore_metadata: false,
+        }
+    }
+
+    /// Compares `#[facet(ignore_in_eq)]` fields too, like `PartialEq` would.
+    pub const fn compare_ignored(mut self) -> Self {
+        self.compare_ignored = true;
+        self
+    }
+
+    /// Also skips metadata fields, such as source spans.
+    pub const fn ignore_metadata(mut self) -> Self {
+        self.ignore_metadata = true;
+        self
+    }
+
+    fn skips(&self, field: &Field) -> bool {
+        (!self.compare_ignored && field.is_ignored_in_eq())
+            || (self.ignore_metadata && field.is_metadata())
+    }
+}
+
+impl<'mem, 'facet> Peek<'mem, 'facet> {
+    /// Compares this value with another of the same shape, skipping the fields
+    /// `options` says to skip.
+    ///
+    /// Structs, enums, options, results, lists, arrays, maps and smart
+    /// pointers are compared structurally, so a field marked
+    /// `#[facet(ignore_in_eq)]` is skipped however deeply it is nested. Other
+    /// values are compared with their own `PartialEq`.
+    ///
+    /// # Returns
+    ///
+    /// `Err` if the shapes differ, or if a value that must be compared with
+    /// `PartialEq` doesn't implement it.
+    pub fn eq_with(
+        &self,
+        other: &Peek<'_, 'facet>,
+        options: &EqOptions,
+    ) -> Result<bool, ReflectError> {
+        if self.shape != other.shape {
+            return Err(ReflectError::WrongShape {
+                expected: self.shape,
+                actual: other.shape,
+            });
+        }
+
+        match self.shape.def {
+            Def::Option(_) => {
+                let (a, b) = (self.into_option()?.value(), other.into_option()?.value());
+                return match (a, b) {
+                    (Some(a), Some(b)) => a.eq_with(&b, options),
+                    (a, b) => Ok(a.is_none() && b.is_none()),
+                };
+            }
+            Def::Result(_) => {
+                let (a, b) = (self.into_result()?, other.into_result()?);
+                return match (a.ok(), b.ok(), a.err(), b.err()) {
+                    (Some(a), Some(b), _, _) | (_, _, Some(a), Some(b)) => a.eq_with(&b, options),
+                    _ => Ok(false),
+                };
+            }
+            Def::List(_) | Def::Array(_) | Def::Slice(_) => {
+                let (a, b) = (self.into_list_like()?, other.into_list_like()?);
+                if a.len() != b.len() {
+                    return Ok(false);
+                }
+                for (a, b) in a.iter().zip(b.iter()) {
+                    if !a.eq_with(&b, options)? {
+                        return Ok(false);
+                    }
+                }
+                return Ok(true);
+            }
+            Def::Map(_) => {
+                let (a, b) = (self.into_map()?, other.into_map()?);
+                if a.len() != b.len() {
+                    return Ok(false);
+                }
+                // Keys are looked up as-is; only values skip fields
+                for (key, a) in a.iter() {
+                    match b.get_peek(key)? {
+                        Some(b) if a.eq_with(&b, options)? => {}
+                        _ => return Ok(false),
+                    }
+                }
+                return Ok(true);
+            }
+            Def::Pointer(def) if def.is_lock() => {
+                // A lock can't be taken twice at once, but it always equals itself
+                if self.data == other.data {
+                    return Ok(true);
+                }
+                let (a, b) = (self.into_pointer()?, other.into_pointer()?);
+                let locked = a.with_locked(|a| b.with_locked(|b| a.eq_with(&b, options)));
+                return locked
+                    .flatten()
+                    .unwrap_or(Err(ReflectError::OperationFailed {
+                        shape: self.shape,
+                        operation: "cannot lock this value, it is locked elsewhere",
+                    }));
+            }
+            Def::Pointer(_) => {
+                let a = self.into_pointer()?.borrow_inner();
+                let b = other.into_pointer()?.borrow_inner();
+                if let (Some(a), Some(b)) = (a, b) {
+                    return a.eq_with(&b, options);
+                }
+            }
+            _ => {}
+        }
+
+        match self.shape.ty {
+            Type::User(UserType::Struct(struct_type)) => {
+                for field in struct_type.fields {
+                    if options.skips(field) {
+                        continue;
+                    }
+                    let a = unsafe {
+                        Peek::unchecked_new(self.data.field(field.offset), field.shape())
+                    };
+                    let b = unsafe {
+                        Peek::unchecked_new(other.data.field(field.offset), field.shape())
+                    };
+                    if !a.eq_with(&b, options)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Type::User(UserType::Enum(_)) => {
+                let (a, b) = (self.into_enum()?, other.into_enum()?);
+                let variant_error = |_| ReflectError::OperationFailed {
+                    shape: self.shape,
+                    operation: "could not determine the active enum variant",
+                };
+                if a.variant_index().map_err(variant_error)?
+                    != b.variant_index().map_err(variant_error)?
+                {
+                    return Ok(false);
+                }
+                for ((field, a), (_, b)) in a.fields().zip(b.fields()) {
+                    if options.skips(&field) {
+                        continue;
+                    }
+                    if !a.eq_with(&b, options)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            _ => self.partial_eq(other),
+        }
+    }
+}
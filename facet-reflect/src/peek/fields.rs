@@ -1,7 +1,7 @@
 use core::ops::Range;
 
 use alloc::borrow::Cow;
-use facet_core::Field;
+use facet_core::{Def, Field};
 
 use crate::Peek;
 use alloc::{string::String, vec, vec::Vec};
@@ -205,6 +205,10 @@ enum FieldsForSerializeIterState<'mem, 'facet> {
     FlattenedMap {
         map_iter: super::PeekMapIter<'mem, 'facet>,
     },
+    /// Iterating over the entries of a flattened dynamic value's object
+    FlattenedObject {
+        object_iter: super::PeekDynamicValueObjectIter<'mem, 'facet>,
+    },
 }
 
 impl<'mem, 'facet> Iterator for FieldsForSerializeIter<'mem, 'facet> {
@@ -240,6 +244,15 @@ impl<'mem, 'facet> Iterator for FieldsForSerializeIter<'mem, 'facet> {
                     // Map exhausted, continue to next state
                     continue;
                 }
+                FieldsForSerializeIterState::FlattenedObject { mut object_iter } => {
+                    if let Some((key, value_peek)) = object_iter.next() {
+                        self.stack
+                            .push(FieldsForSerializeIterState::FlattenedObject { object_iter });
+                        let field_item = FieldItem::flattened_map_entry(String::from(key));
+                        return Some((field_item, value_peek));
+                    }
+                    continue;
+                }
                 FieldsForSerializeIterState::Fields(mut fields) => {
                     let Some((field, peek)) = fields.next() else {
                         continue;
@@ -258,7 +271,9 @@ impl<'mem, 'facet> Iterator for FieldsForSerializeIter<'mem, 'facet> {
                             self.stack.push(FieldsForSerializeIterState::Fields(
                                 FieldIter::new_struct(struct_peek),
                             ))
-                        } else if let Ok(enum_peek) = peek.into_enum() {
+                        } else if !matches!(peek.shape().def, Def::Option(_))
+                            && let Ok(enum_peek) = peek.into_enum()
+                        {
                             // normally we'd serialize to something like:
                             //
                             //   {
@@ -286,6 +301,15 @@ impl<'mem, 'facet> Iterator for FieldsForSerializeIter<'mem, 'facet> {
                             self.stack.push(FieldsForSerializeIterState::FlattenedMap {
                                 map_iter: map_peek.iter(),
                             });
+                        } else if let Ok(dynamic_peek) = peek.into_dynamic_value() {
+                            // Flattened dynamic value - emit object entries as synthetic
+                            // fields; anything but an object (e.g. null) has none
+                            if let Some(object_iter) = dynamic_peek.object_iter() {
+                                self.stack
+                                    .push(FieldsForSerializeIterState::FlattenedObject {
+                                        object_iter,
+                                    });
+                            }
                         } else if let Ok(option_peek) = peek.into_option() {
                             // Option<T> where T is a struct, enum, or map
                             // If Some, flatten the inner value; if None, skip entirely
@@ -308,9 +332,17 @@ impl<'mem, 'facet> Iterator for FieldsForSerializeIter<'mem, 'facet> {
                                     self.stack.push(FieldsForSerializeIterState::FlattenedMap {
                                         map_iter: map_peek.iter(),
                                     });
+                                } else if let Ok(dynamic_peek) = inner_peek.into_dynamic_value() {
+                                    if let Some(object_iter) = dynamic_peek.object_iter() {
+                                        self.stack.push(
+                                            FieldsForSerializeIterState::FlattenedObject {
+                                                object_iter,
+                                            },
+                                        );
+                                    }
                                 } else {
                                     panic!(
-                                        "cannot flatten Option<{}> - inner type must be struct, enum, or map",
+                                        "cannot flatten Option<{}> - inner type must be struct, enum, map, or dynamic value",
                                         inner_peek.shape()
                                     )
                                 }
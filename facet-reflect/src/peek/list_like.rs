@@ -1,7 +1,8 @@
 use facet_core::{IterVTable, PtrConst, PtrMut, Shape, ShapeLayout};
 
 use super::Peek;
-use core::{fmt::Debug, marker::PhantomData, ptr::NonNull};
+use crate::ReflectError;
+use core::{cmp::Ordering, fmt::Debug, marker::PhantomData, ptr::NonNull};
 
 /// Fields for types which act like lists
 #[derive(Clone, Copy)]
@@ -32,6 +33,15 @@ impl ListLikeDef {
             ListLikeDef::Slice(v) => v.t(),
         }
     }
+
+    /// Returns the length fixed by the type, for arrays
+    #[inline]
+    pub fn fixed_len(&self) -> Option<usize> {
+        match self {
+            ListLikeDef::Array(v) => Some(v.n),
+            ListLikeDef::List(_) | ListLikeDef::Slice(_) => None,
+        }
+    }
 }
 
 /// Iterator over a `PeekListLike`
@@ -275,4 +285,89 @@ impl<'mem, 'facet> PeekListLike<'mem, 'facet> {
     pub fn def(&self) -> ListLikeDef {
         self.def
     }
+
+    /// Binary searches a list sorted by key for `key`, comparing with the key's
+    /// `PartialOrd` vtable entry.
+    ///
+    /// The key of an item is its field named `key_field`, or the item itself if
+    /// `key_field` is `None`. Like [`slice::binary_search`], returns `Ok(index)`
+    /// of a matching item, or `Err(index)` where `key` could be inserted to keep
+    /// the list sorted. The result is unspecified if the list isn't sorted.
+    ///
+    /// Errors if an item has no such field, or if the keys can't be compared.
+    pub fn binary_search_by_key(
+        &self,
+        key_field: Option<&str>,
+        key: &Peek<'_, '_>,
+    ) -> Result<Result<usize, usize>, ReflectError> {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let item = self.get(mid).ok_or(ReflectError::OperationFailed {
+                shape: self.def.t(),
+                operation: "binary search: item out of reach",
+            })?;
+            match compare_key(item, key_field, key)? {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(Ok(mid)),
+            }
+        }
+        Ok(Err(low))
+    }
+
+    /// Finds the item whose key equals `key` in a list sorted by key, see
+    /// [`Self::binary_search_by_key`].
+    ///
+    /// ```
+    /// use facet::Facet;
+    /// use facet_reflect::Peek;
+    ///
+    /// #[derive(Facet)]
+    /// struct User { id: u64, name: String }
+    ///
+    /// let users = vec![
+    ///     User { id: 3, name: "ada".into() },
+    ///     User { id: 8, name: "bob".into() },
+    ///     User { id: 21, name: "cy".into() },
+    /// ];
+    /// let list = Peek::new(&users).into_list_like().unwrap();
+    /// let found = list.find_by_key(Some("id"), &Peek::new(&8u64)).unwrap().unwrap();
+    /// assert_eq!(found.get::<User>().unwrap().name, "bob");
+    /// assert!(list.find_by_key(Some("id"), &Peek::new(&4u64)).unwrap().is_none());
+    /// ```
+    pub fn find_by_key(
+        &self,
+        key_field: Option<&str>,
+        key: &Peek<'_, '_>,
+    ) -> Result<Option<Peek<'mem, 'facet>>, ReflectError> {
+        Ok(self
+            .binary_search_by_key(key_field, key)?
+            .ok()
+            .and_then(|index| self.get(index)))
+    }
+}
+
+/// Key of `item` (its `key_field` field, or itself) compared to `key`
+fn compare_key(
+    item: Peek<'_, '_>,
+    key_field: Option<&str>,
+    key: &Peek<'_, '_>,
+) -> Result<Ordering, ReflectError> {
+    let item_key = match key_field {
+        Some(name) => item
+            .into_struct()?
+            .field_by_name(name)
+            .map_err(|field_error| ReflectError::FieldError {
+                shape: item.shape(),
+                field_error,
+            })?,
+        None => item,
+    };
+    item_key
+        .partial_cmp(key)?
+        .ok_or(ReflectError::OperationFailed {
+            shape: item_key.shape(),
+            operation: "binary search: keys are not comparable",
+        })
 }
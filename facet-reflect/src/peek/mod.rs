@@ -42,7 +42,18 @@ pub use tuple::*;
 mod dynamic_value;
 pub use dynamic_value::*;
 
+mod eq_with;
+pub use eq_with::*;
+
+#[cfg(feature = "alloc")]
+mod path;
+#[cfg(feature = "alloc")]
+pub use path::*;
+
 #[cfg(feature = "alloc")]
 mod owned;
 #[cfg(feature = "alloc")]
 pub use owned::*;
+
+#[cfg(feature = "alloc")]
+mod deep_clone;
@@ -0,0 +1,354 @@
+//! Resolving dotted/indexed paths like `servers[0].ports["http"]` against a [`Peek`].
+
+use alloc::string::{String, ToString};
+use core::ops::Range;
+use facet_core::{Def, KnownPointer, Shape, Type, UserType};
+
+use crate::Peek;
+
+/// Error returned by [`Peek::at`], naming the path segment that failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeekPathError {
+    /// The segment that failed, as written in the path, e.g. `items` or `[3]`.
+    pub segment: String,
+    /// Byte offset of the segment in the path.
+    pub offset: usize,
+    /// What went wrong.
+    pub kind: PeekPathErrorKind,
+}
+
+/// The ways resolving a path segment can fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeekPathErrorKind {
+    /// The path itself is malformed.
+    Syntax {
+        /// What is wrong with it.
+        reason: &'static str,
+    },
+    /// The struct or enum variant has no field by that name.
+    NoSuchField {
+        /// The shape of the struct or enum.
+        shape: &'static Shape,
+    },
+    /// The map has no entry for that key.
+    NoSuchKey {
+        /// The shape of the map.
+        shape: &'static Shape,
+    },
+    /// The index is past the end of the list.
+    IndexOutOfBounds {
+        /// The index in the path.
+        index: usize,
+        /// The length of the list.
+        len: usize,
+    },
+    /// The value is `None`, so there is nothing to step into.
+    NoneValue {
+        /// The shape of the option.
+        shape: &'static Shape,
+    },
+    /// The value is a scalar, set or other value that can't be stepped into
+    /// this way.
+    NotTraversable {
+        /// The shape of the value.
+        shape: &'static Shape,
+    },
+}
+
+impl PeekPathError {
+    /// An error for the segment spanning `range` of `path`.
+    pub fn new(path: &str, range: Range<usize>, kind: PeekPathErrorKind) -> Self {
+        Self {
+            offset: range.start,
+            segment: path[range].to_string(),
+            kind,
+        }
+    }
+}
+
+impl core::fmt::Display for PeekPathError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "at `{}` (offset {}): ", self.segment, self.offset)?;
+        match self.kind {
+            PeekPathErrorKind::Syntax { reason } => write!(f, "invalid path: {reason}"),
+            PeekPathErrorKind::NoSuchField { shape } => write!(f, "`{shape}` has no such field"),
+            PeekPathErrorKind::NoSuchKey { shape } => write!(f, "`{shape}` has no such key"),
+            PeekPathErrorKind::IndexOutOfBounds { index, len } => {
+                write!(f, "index {index} is out of bounds for length {len}")
+            }
+            PeekPathErrorKind::NoneValue { shape } => write!(f, "`{shape}` is None"),
+            PeekPathErrorKind::NotTraversable { shape } => {
+                write!(f, "`{shape}` has no fields, items or entries")
+            }
+        }
+    }
+}
+
+impl core::error::Error for PeekPathError {}
+
+/// One step of a path, as accepted by [`Peek::at`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PeekPathSegment {
+    /// `.name`, or a leading `name`
+    Field(String),
+    /// `[3]`, which is also tried as a map key
+    Index(usize),
+    /// `["key"]` or `[key]`
+    Key(String),
+}
+
+/// Splits a path into [`PeekPathSegment`]s, along with the byte range of each.
+///
+/// This is the syntax of [`Peek::at`], for resolving the same paths against
+/// other representations of a value. Iteration stops after a syntax error.
+pub struct PeekPathSegments<'a> {
+    path: &'a str,
+    pos: usize,
+}
+
+impl<'a> PeekPathSegments<'a> {
+    /// Start splitting `path`.
+    pub fn new(path: &'a str) -> Self {
+        Self { path, pos: 0 }
+    }
+}
+
+impl Iterator for PeekPathSegments<'_> {
+    type Item = Result<(PeekPathSegment, Range<usize>), PeekPathError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.next_segment().transpose();
+        if let Some(Err(_)) = result {
+            self.pos = self.path.len();
+        }
+        result
+    }
+}
+
+impl PeekPathSegments<'_> {
+    fn error(&self, start: usize, reason: &'static str) -> PeekPathError {
+        PeekPathError {
+            segment: self.path[start..].to_string(),
+            offset: start,
+            kind: PeekPathErrorKind::Syntax { reason },
+        }
+    }
+
+    fn next_segment(&mut self) -> Result<Option<(PeekPathSegment, Range<usize>)>, PeekPathError> {
+        let start = self.pos;
+        let rest = &self.path[start..];
+        if rest.is_empty() {
+            return Ok(None);
+        }
+
+        if let Some(inner) = rest.strip_prefix('[') {
+            let (segment, len) = if let Some(quoted) = inner.strip_prefix('"') {
+                let mut key = String::new();
+                let mut chars = quoted.char_indices();
+                let end = loop {
+                    match chars.next() {
+                        Some((i, '"')) => break i,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, c)) => key.push(c),
+                            None => return Err(self.error(start, "unterminated string")),
+                        },
+                        Some((_, c)) => key.push(c),
+                        None => return Err(self.error(start, "unterminated string")),
+                    }
+                };
+                if !quoted[end + 1..].starts_with(']') {
+                    return Err(self.error(start, "expected `]` after the key"));
+                }
+                // `[` + `"` + key + `"` + `]`
+                (PeekPathSegment::Key(key), end + 4)
+            } else {
+                let Some(end) = inner.find(']') else {
+                    return Err(self.error(start, "missing `]`"));
+                };
+                let text = &inner[..end];
+                if text.is_empty() {
+                    return Err(self.error(start, "empty brackets"));
+                }
+                let segment = match text.parse() {
+                    Ok(index) => PeekPathSegment::Index(index),
+                    Err(_) => PeekPathSegment::Key(text.to_string()),
+                };
+                (segment, end + 2)
+            };
+            self.pos += len;
+            return Ok(Some((segment, start..self.pos)));
+        }
+
+        // A field name, preceded by a dot unless it starts the path
+        let name_start = match rest.strip_prefix('.') {
+            Some(_) => start + 1,
+            None if start == 0 => start,
+            None => return Err(self.error(start, "expected `.` or `[`")),
+        };
+        let name = &self.path[name_start..];
+        let len = name.find(['.', '[']).unwrap_or(name.len());
+        if len == 0 {
+            return Err(self.error(start, "empty field name"));
+        }
+        self.pos = name_start + len;
+        Ok(Some((
+            PeekPathSegment::Field(name[..len].to_string()),
+            start..self.pos,
+        )))
+    }
+}
+
+impl<'mem, 'facet> Peek<'mem, 'facet> {
+    /// Resolves a path through structs, enums, lists, tuples and maps.
+    ///
+    /// Paths are made of field names separated by dots, and bracketed
+    /// indices or map keys: `servers[0].ports["http"]`. A bracketed number
+    /// indexes a list or tuple, or looks up a map key with that text; other
+    /// keys can be bare (`[http]`) or quoted (`["with.dots"]`). Field names
+    /// are the serialized names, so `rename` and `rename_all` apply.
+    ///
+    /// Pointers like `Box` and `Arc`, transparent wrappers and `Some` are
+    /// stepped through automatically. An empty path returns this value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use facet::Facet;
+    /// use facet_reflect::Peek;
+    ///
+    /// #[derive(Facet)]
+    /// struct Order {
+    ///     items: Vec<Item>,
+    /// }
+    ///
+    /// #[derive(Facet)]
+    /// struct Item {
+    ///     name: String,
+    /// }
+    ///
+    /// let order = Order {
+    ///     items: vec![Item { name: "tea".into() }, Item { name: "scone".into() }],
+    /// };
+    /// let name = Peek::new(&order).at("items[1].name").unwrap();
+    /// assert_eq!(name.as_str(), Some("scone"));
+    ///
+    /// let err = Peek::new(&order).at("items[2].name").unwrap_err();
+    /// assert_eq!(err.segment, "[2]");
+    /// ```
+    pub fn at(self, path: &str) -> Result<Peek<'mem, 'facet>, PeekPathError> {
+        let mut current = self;
+        for segment in PeekPathSegments::new(path) {
+            let (segment, range) = segment?;
+            current = step(current, &segment, false)
+                .map_err(|kind| PeekPathError::new(path, range, kind))?;
+        }
+        Ok(current)
+    }
+}
+
+/// Steps through pointers, transparent wrappers and `Some`
+///
+/// With `exclusive`, only pointers that own their pointee (`Box`) are
+/// stepped through, so the result can be written to by whoever holds the
+/// root value mutably.
+pub(crate) fn unwrap<'mem, 'facet>(
+    mut peek: Peek<'mem, 'facet>,
+    exclusive: bool,
+) -> Result<Peek<'mem, 'facet>, PeekPathErrorKind> {
+    loop {
+        let shape = peek.shape();
+        if exclusive && matches!(shape.ty, Type::Pointer(_)) {
+            return Err(PeekPathErrorKind::NotTraversable { shape });
+        }
+        peek = match shape.def {
+            Def::Pointer(def) if exclusive && def.known != Some(KnownPointer::Box) => {
+                return Err(PeekPathErrorKind::NotTraversable { shape });
+            }
+            Def::Pointer(_) => peek
+                .into_pointer()
+                .ok()
+                .and_then(|pointer| pointer.borrow_inner())
+                .ok_or(PeekPathErrorKind::NotTraversable { shape })?,
+            Def::Option(_) => peek
+                .into_option()
+                .ok()
+                .and_then(|option| option.value())
+                .ok_or(PeekPathErrorKind::NoneValue { shape })?,
+            _ if shape.inner.is_some() => {
+                let inner = peek.innermost_peek();
+                if inner.shape() == shape {
+                    return Ok(peek);
+                }
+                inner
+            }
+            _ => return Ok(peek),
+        };
+    }
+}
+
+pub(crate) fn step<'mem, 'facet>(
+    peek: Peek<'mem, 'facet>,
+    segment: &PeekPathSegment,
+    exclusive: bool,
+) -> Result<Peek<'mem, 'facet>, PeekPathErrorKind> {
+    let peek = unwrap(peek, exclusive)?;
+    let shape = peek.shape();
+
+    if let Def::Map(_) = shape.def {
+        let key = match segment {
+            PeekPathSegment::Field(key) | PeekPathSegment::Key(key) => key.clone(),
+            PeekPathSegment::Index(index) => index.to_string(),
+        };
+        return map_entry(peek, &key).ok_or(PeekPathErrorKind::NoSuchKey { shape });
+    }
+
+    match segment {
+        PeekPathSegment::Field(name) => match shape.ty {
+            Type::User(UserType::Struct(_)) => peek
+                .into_struct()
+                .ok()
+                .and_then(|struct_| struct_.field_by_name(name).ok())
+                .ok_or(PeekPathErrorKind::NoSuchField { shape }),
+            Type::User(UserType::Enum(_)) => peek
+                .into_enum()
+                .ok()
+                .and_then(|enum_| enum_.field_by_name(name).ok().flatten())
+                .ok_or(PeekPathErrorKind::NoSuchField { shape }),
+            _ => Err(PeekPathErrorKind::NotTraversable { shape }),
+        },
+        PeekPathSegment::Index(index) => {
+            if let Ok(list) = peek.into_list_like() {
+                return list.get(*index).ok_or(PeekPathErrorKind::IndexOutOfBounds {
+                    index: *index,
+                    len: list.len(),
+                });
+            }
+            // Tuples and tuple structs name their fields `0`, `1`, ...
+            match shape.ty {
+                Type::User(UserType::Struct(st)) => peek
+                    .into_struct()
+                    .ok()
+                    .and_then(|struct_| struct_.field(*index).ok())
+                    .ok_or(PeekPathErrorKind::IndexOutOfBounds {
+                        index: *index,
+                        len: st.fields.len(),
+                    }),
+                _ => Err(PeekPathErrorKind::NotTraversable { shape }),
+            }
+        }
+        PeekPathSegment::Key(_) => Err(PeekPathErrorKind::NotTraversable { shape }),
+    }
+}
+
+/// The value for the map key whose text is `key`
+fn map_entry<'mem, 'facet>(peek: Peek<'mem, 'facet>, key: &str) -> Option<Peek<'mem, 'facet>> {
+    let map = peek.into_map().ok()?;
+    map.iter().find_map(|(k, v)| {
+        let k = k.innermost_peek();
+        let matches = match k.as_str() {
+            Some(s) => s == key,
+            None => k.shape().is_display() && k.to_string() == key,
+        };
+        matches.then_some(v)
+    })
+}
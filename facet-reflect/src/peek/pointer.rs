@@ -1,4 +1,4 @@
-use facet_core::PointerDef;
+use facet_core::{Def, PointerDef, PtrConst};
 
 use super::Peek;
 
@@ -24,6 +24,9 @@ impl<'mem, 'facet> PeekPointer<'mem, 'facet> {
     /// Borrows the inner value of the pointer.
     ///
     /// Returns `None` if the pointer doesn't have a borrow function or pointee shape.
+    ///
+    /// If the pointee is a trait object (e.g. `Box<dyn Plugin>`) whose concrete
+    /// type registered its shape, the returned peek has the concrete shape.
     #[inline]
     pub fn borrow_inner(&self) -> Option<Peek<'mem, 'facet>> {
         let borrow_fn = self.def.vtable.borrow_fn?;
@@ -32,9 +35,38 @@ impl<'mem, 'facet> PeekPointer<'mem, 'facet> {
         // SAFETY: We have a valid pointer and borrow_fn is provided by the vtable
         let inner_ptr = unsafe { borrow_fn(self.value.data) };
 
+        if let Def::TraitObject(trait_object) = pointee_shape.def {
+            // SAFETY: borrow_fn returns a wide pointer to the trait object
+            if let Some(concrete_shape) = unsafe { trait_object.concrete_shape(inner_ptr) } {
+                // SAFETY: the concrete shape was registered for the type id of
+                // the value behind the trait object, which is sized
+                let concrete_ptr = PtrConst::new(inner_ptr.raw_ptr());
+                return Some(unsafe { Peek::unchecked_new(concrete_ptr, concrete_shape) });
+            }
+        }
+
         // SAFETY: The borrow_fn returns a valid pointer to the inner value with the correct shape
         let inner_peek = unsafe { Peek::unchecked_new(inner_ptr, pointee_shape) };
 
         Some(inner_peek)
     }
+
+    /// Locks the pointer and runs `f` with a peek at the value it guards.
+    ///
+    /// This is how the contents of `Mutex`, `RwLock` and `RefCell` are read:
+    /// a read lock is preferred, and the lock is released once `f` returns.
+    /// Locking never blocks; `None` is returned if the value is currently
+    /// locked (or mutably borrowed) elsewhere, or if the pointer is not a lock.
+    pub fn with_locked<R>(&self, f: impl FnOnce(Peek<'_, 'facet>) -> R) -> Option<R> {
+        let lock_fn = self.def.vtable.read_fn.or(self.def.vtable.lock_fn)?;
+        let pointee_shape = self.def.pointee()?;
+
+        // SAFETY: the vtable's lock functions expect a pointer to this pointer type
+        let locked = unsafe { lock_fn(self.value.data) }.ok()?;
+
+        // SAFETY: the locked data has the pointee shape and stays valid while
+        // `locked` holds the guard
+        let inner = unsafe { Peek::unchecked_new(locked.data().as_const(), pointee_shape) };
+        Some(f(inner))
+    }
 }
@@ -1,6 +1,6 @@
 use facet_core::{FieldError, StructType};
 
-use crate::Peek;
+use crate::{Peek, ReflectError};
 
 use super::{FieldIter, HasFields};
 
@@ -59,6 +59,42 @@ impl<'mem, 'facet> PeekStruct<'mem, 'facet> {
         }
         Err(FieldError::NoSuchField)
     }
+
+    /// Finds the item of the list field `field_name` whose key equals `key`.
+    ///
+    /// If the field is declared `#[facet(sorted_by = "key")]`, the item's `key`
+    /// field is compared and the list is binary searched. Otherwise the items
+    /// themselves are compared to `key`, one by one.
+    pub fn find_by_key(
+        &self,
+        field_name: &str,
+        key: &Peek<'_, '_>,
+    ) -> Result<Option<Peek<'mem, 'facet>>, ReflectError> {
+        let field_error = |field_error| ReflectError::FieldError {
+            shape: self.value.shape(),
+            field_error,
+        };
+        let field = self
+            .ty
+            .fields
+            .iter()
+            .find(|field| field.name == field_name)
+            .ok_or(field_error(FieldError::NoSuchField))?;
+        let list = self
+            .field_by_name(field_name)
+            .map_err(field_error)?
+            .into_list_like()?;
+
+        if let Some(key_field) = field.sorted_by() {
+            return list.find_by_key(Some(key_field), key);
+        }
+        for item in list.iter() {
+            if item.partial_eq(key)? {
+                return Ok(Some(item));
+            }
+        }
+        Ok(None)
+    }
 }
 
 impl<'mem, 'facet> HasFields<'mem, 'facet> for PeekStruct<'mem, 'facet> {
@@ -431,10 +431,13 @@ impl<'mem, 'facet> Peek<'mem, 'facet> {
                     }
 
                     Def::Pointer(_) => {
-                        if let Ok(ptr) = self.into_pointer()
-                            && let Some(inner) = ptr.borrow_inner()
-                        {
-                            inner.structural_hash(hasher);
+                        if let Ok(ptr) = self.into_pointer() {
+                            if let Some(inner) = ptr.borrow_inner() {
+                                inner.structural_hash(hasher);
+                            } else {
+                                // Mutex, RwLock and RefCell hash what they guard
+                                ptr.with_locked(|inner| inner.structural_hash(hasher));
+                            }
                         }
                     }
 
@@ -533,11 +536,14 @@ impl<'mem, 'facet> Peek<'mem, 'facet> {
         ScalarType::try_from_shape(self.shape)
     }
 
-    /// Read the value from memory into a Rust value.
+    /// Returns a typed reference to the value, if its shape is `T`'s shape.
     ///
-    /// # Panics
+    /// This is how generic traversals special-case a few concrete types: call
+    /// `get::<T>().ok()` on each value and fall back to reflection on `None`.
     ///
-    /// Panics if the shape doesn't match the type `T`.
+    /// # Returns
+    ///
+    /// `Err(ReflectError::WrongShape)` if the shape doesn't match the type `T`.
     #[inline]
     pub fn get<T: Facet<'facet> + ?Sized>(&self) -> Result<&'mem T, ReflectError> {
         if self.shape != T::SHAPE {
@@ -733,6 +739,14 @@ impl<'mem, 'facet> Peek<'mem, 'facet> {
                     }
                 }
 
+                // Box<[T]>, Arc<[T]> and Rc<[T]> own a slice
+                if let Ok(ptr) = self.into_pointer()
+                    && let Some(Def::Slice(def)) = ptr.def.pointee().map(|pointee| pointee.def)
+                    && let Some(slice) = ptr.borrow_inner()
+                {
+                    return Ok(PeekListLike::new(slice, ListLikeDef::Slice(def)));
+                }
+
                 Err(ReflectError::WasNotA {
                     expected: "list, array or slice",
                     actual: self.shape,
@@ -0,0 +1,265 @@
+//! Recording every mutation made through reflection, for change history.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::time::SystemTime;
+
+use facet_core::{Def, Facet, PtrConst, Type, UserType};
+
+use crate::peek::{step, unwrap};
+use crate::{Peek, PeekPathError, PeekPathErrorKind, PeekPathSegments, ReflectError};
+
+use super::Poke;
+
+/// A change made through an [`Audited`] value, handed to its [`AuditSink`]
+#[derive(Debug, Clone, Copy)]
+pub struct Change<'a, 'facet> {
+    /// Where the value was changed, as passed to [`Audited::set`], e.g.
+    /// `servers[2].port`. Empty for the root value.
+    pub path: &'a str,
+    /// The value before the change
+    pub old: Peek<'a, 'facet>,
+    /// The value after the change
+    pub new: Peek<'a, 'facet>,
+    /// When the change was made
+    pub timestamp: SystemTime,
+    /// Who made the change, as set with [`Audited::with_actor`]
+    pub actor: Option<&'a str>,
+}
+
+/// Receives the changes made through an [`Audited`] value
+///
+/// Implemented for closures taking a [`Change`], and for `Vec<AuditRecord>`
+/// which keeps a rendered copy of every change.
+pub trait AuditSink {
+    /// Called after each change, while both the old and new values are alive
+    fn record(&mut self, change: &Change<'_, '_>);
+}
+
+impl<F: FnMut(&Change<'_, '_>)> AuditSink for F {
+    fn record(&mut self, change: &Change<'_, '_>) {
+        self(change)
+    }
+}
+
+impl AuditSink for Vec<AuditRecord> {
+    fn record(&mut self, change: &Change<'_, '_>) {
+        self.push(AuditRecord::from(change));
+    }
+}
+
+/// An owned copy of a [`Change`], with the values rendered through `Debug`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditRecord {
+    /// Where the value was changed
+    pub path: String,
+    /// The value before the change
+    pub old: String,
+    /// The value after the change
+    pub new: String,
+    /// When the change was made
+    pub timestamp: SystemTime,
+    /// Who made the change
+    pub actor: Option<String>,
+}
+
+impl From<&Change<'_, '_>> for AuditRecord {
+    fn from(change: &Change<'_, '_>) -> Self {
+        Self {
+            path: change.path.into(),
+            old: format!("{:?}", change.old),
+            new: format!("{:?}", change.new),
+            timestamp: change.timestamp,
+            actor: change.actor.map(Into::into),
+        }
+    }
+}
+
+/// Error returned by [`Audited::set`]
+#[derive(Debug, Clone)]
+pub enum AuditError {
+    /// The path doesn't lead to a value that can be replaced
+    Path(PeekPathError),
+    /// The value can't be replaced, e.g. because its shape doesn't match
+    Reflect(ReflectError),
+}
+
+impl core::fmt::Display for AuditError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AuditError::Path(err) => write!(f, "{err}"),
+            AuditError::Reflect(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl core::error::Error for AuditError {}
+
+impl From<PeekPathError> for AuditError {
+    fn from(err: PeekPathError) -> Self {
+        AuditError::Path(err)
+    }
+}
+
+impl From<ReflectError> for AuditError {
+    fn from(err: ReflectError) -> Self {
+        AuditError::Reflect(err)
+    }
+}
+
+/// A mutable value whose changes are all recorded into an [`AuditSink`]
+///
+/// Values are replaced by path, with the syntax of [`Peek::at`]. Each
+/// replacement is reported with the old and new values, the time, and the
+/// actor, so admin tools get change history without tracking it themselves.
+/// Writes that leave the value equal to what it was are not reported.
+///
+/// As with [`PokeStruct::set_field`](crate::PokeStruct::set_field), replacing
+/// a field requires the struct or enum holding it to be `#[facet(pod)]`.
+/// List items, map values and `Some` values can be replaced freely. Paths
+/// step through `Box`, but not through shared pointers or references.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_reflect::{AuditRecord, Audited};
+///
+/// #[derive(Facet)]
+/// #[facet(pod)]
+/// struct Limits {
+///     max_users: u32,
+/// }
+///
+/// let mut limits = Limits { max_users: 10 };
+/// let mut audited = Audited::new(&mut limits, Vec::<AuditRecord>::new()).with_actor("alice");
+/// audited.set("max_users", 25u32).unwrap();
+///
+/// let log = audited.into_sink();
+/// assert_eq!(log[0].path, "max_users");
+/// assert_eq!((log[0].old.as_str(), log[0].new.as_str()), ("10", "25"));
+/// assert_eq!(log[0].actor.as_deref(), Some("alice"));
+/// assert_eq!(limits.max_users, 25);
+/// ```
+pub struct Audited<'mem, 'facet, S> {
+    root: Poke<'mem, 'facet>,
+    sink: S,
+    actor: Option<String>,
+}
+
+impl<'mem, 'facet, S: AuditSink> Audited<'mem, 'facet, S> {
+    /// Audit changes made to `value`, recording them into `sink`
+    pub fn new<T: Facet<'facet>>(value: &'mem mut T, sink: S) -> Self {
+        Self::from_poke(Poke::new(value), sink)
+    }
+
+    /// Audit changes made through `poke`, recording them into `sink`
+    pub fn from_poke(poke: Poke<'mem, 'facet>, sink: S) -> Self {
+        Self {
+            root: poke,
+            sink,
+            actor: None,
+        }
+    }
+
+    /// Attribute the following changes to `actor`
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    /// Attribute the following changes to `actor`, or to no one
+    pub fn set_actor(&mut self, actor: Option<String>) {
+        self.actor = actor;
+    }
+
+    /// Who the following changes are attributed to
+    pub fn actor(&self) -> Option<&str> {
+        self.actor.as_deref()
+    }
+
+    /// A read-only view of the whole value
+    pub fn peek(&self) -> Peek<'_, 'facet> {
+        self.root.as_peek()
+    }
+
+    /// The sink changes are recorded into
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// The sink changes are recorded into
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Stop auditing, handing back the sink
+    pub fn into_sink(self) -> S {
+        self.sink
+    }
+
+    /// Replace the value at `path` with `value`, and record the change
+    ///
+    /// An empty path replaces the whole value. Returns an error, leaving the
+    /// value untouched, if the path can't be resolved or the value there
+    /// isn't a `T`.
+    pub fn set<T: Facet<'facet>>(&mut self, path: &str, value: T) -> Result<(), AuditError> {
+        let target = self.resolve(path)?;
+        if target.shape() != T::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: target.shape(),
+                actual: T::SHAPE,
+            }
+            .into());
+        }
+
+        // SAFETY: `resolve` only goes through values owned by the root, which
+        // we borrow mutably, and the shape check above makes this a `T`.
+        let slot = unsafe { target.data().into_mut().as_mut_ptr::<T>() };
+        let old = unsafe { core::ptr::replace(slot, value) };
+
+        let old = Peek::new(&old);
+        let new = unsafe { Peek::unchecked_new(PtrConst::new(slot.cast_const()), T::SHAPE) };
+        if !matches!(old.partial_eq(&new), Ok(true)) {
+            self.sink.record(&Change {
+                path,
+                old,
+                new,
+                timestamp: SystemTime::now(),
+                actor: self.actor.as_deref(),
+            });
+        }
+        Ok(())
+    }
+
+    /// The value at `path`, making sure it can be written through the root
+    fn resolve(&self, path: &str) -> Result<Peek<'_, 'facet>, AuditError> {
+        let mut current = self.root.as_peek();
+        for segment in PeekPathSegments::new(path) {
+            let (segment, range) = segment?;
+            let error = |kind: PeekPathErrorKind| PeekPathError::new(path, range.clone(), kind);
+
+            let parent = unwrap(current, true).map_err(error)?;
+            let shape = parent.shape();
+            let is_field = matches!(
+                shape.ty,
+                Type::User(UserType::Struct(_) | UserType::Enum(_))
+            ) && !matches!(shape.def, Def::Map(_));
+            if is_field && !shape.is_pod() {
+                return Err(ReflectError::NotPod { shape }.into());
+            }
+            current = step(parent, &segment, true).map_err(error)?;
+        }
+        Ok(current)
+    }
+}
+
+impl<S> core::fmt::Debug for Audited<'_, '_, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Audited")
+            .field("root", &self.root)
+            .field("actor", &self.actor)
+            .finish_non_exhaustive()
+    }
+}
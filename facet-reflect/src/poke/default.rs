@@ -0,0 +1,92 @@
+//! Building default values for arbitrary shapes.
+
+use facet_core::{PtrMut, PtrUninit, Shape};
+
+use crate::{HeapValue, ReflectError, build_default};
+
+use super::Poke;
+
+impl<'mem, 'facet> Poke<'mem, 'facet> {
+    /// Builds a default value for `shape`, even if the type doesn't implement `Default`.
+    ///
+    /// Types that advertise `Default` use it; structs without it fall back to a
+    /// default per field, recursively. See [`Partial::set_default_deep`](crate::Partial::set_default_deep)
+    /// for how each kind of type is handled.
+    ///
+    /// ```
+    /// use facet::Facet;
+    /// use facet_reflect::Poke;
+    ///
+    /// #[derive(Facet, Debug, PartialEq)]
+    /// struct Config {
+    ///     name: String,
+    ///     #[facet(default = 3)]
+    ///     retries: u8,
+    /// }
+    ///
+    /// let value = Poke::default_of(Config::SHAPE).unwrap();
+    /// let config: Config = value.materialize().unwrap();
+    /// assert_eq!(config, Config { name: String::new(), retries: 3 });
+    /// ```
+    pub fn default_of(shape: &'static Shape) -> Result<HeapValue<'static>, ReflectError> {
+        build_default(shape)
+    }
+
+    /// Writes a default value for `shape` into `target`, as [`Poke::default_of`] would
+    /// build it, and returns the now-initialized pointer.
+    ///
+    /// On error, `target` is left uninitialized.
+    ///
+    /// # Safety
+    ///
+    /// `target` must be valid for writes and aligned for `shape`'s layout, and must not
+    /// hold a value that still needs dropping.
+    pub unsafe fn default_in_place(
+        shape: &'static Shape,
+        target: PtrUninit,
+    ) -> Result<PtrMut, ReflectError> {
+        let size = sized_size(shape)?;
+        let value = build_default(shape)?;
+        // SAFETY: the value has `shape` and the caller guarantees `target` can hold one.
+        unsafe {
+            value.move_out(|src| {
+                core::ptr::copy_nonoverlapping(src.as_byte_ptr(), target.as_mut_byte_ptr(), size);
+            });
+            Ok(target.assume_init())
+        }
+    }
+
+    /// Replaces this value with a default one, as [`Poke::default_of`] would build it.
+    ///
+    /// Like [`Poke::set`], this replaces the value wholesale, so it works on any type.
+    /// On error, the value is left untouched.
+    pub fn reset_to_default(&mut self) -> Result<(), ReflectError> {
+        let shape = self.shape;
+        let size = sized_size(shape)?;
+        let value = build_default(shape)?;
+        // SAFETY: `self.data` holds an initialized value of `shape`, which is dropped
+        // before the default is moved over it.
+        unsafe {
+            value.move_out(|src| {
+                shape.call_drop_in_place(self.data);
+                core::ptr::copy_nonoverlapping(
+                    src.as_byte_ptr(),
+                    self.data.as_mut_byte_ptr(),
+                    size,
+                );
+            })
+        };
+        Ok(())
+    }
+}
+
+fn sized_size(shape: &'static Shape) -> Result<usize, ReflectError> {
+    shape
+        .layout
+        .sized_layout()
+        .map(|layout| layout.size())
+        .map_err(|_| ReflectError::Unsized {
+            shape,
+            operation: "building a default value",
+        })
+}
@@ -0,0 +1,132 @@
+//! Remembering which parts of a value changed, to send only those.
+
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+
+use crate::{Peek, PeekPathError};
+
+use super::{AuditSink, Audited, Change};
+
+/// A value that remembers the paths changed through it
+///
+/// Changes are made with [`Audited::set`], and their paths collected in the
+/// [`DirtyPaths`] sink. A sync protocol can then send only the changed
+/// subtrees, see [`DirtyPaths::subtrees`], and [`clear`](DirtyPaths::clear)
+/// the paths once they are sent.
+///
+/// Changes made other than through the tracker go unnoticed, and as with
+/// [`Audited`], struct fields can only be replaced in `#[facet(pod)]` types.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_reflect::{DirtyPaths, DirtyTracked};
+///
+/// #[derive(Facet)]
+/// #[facet(pod)]
+/// struct Document {
+///     title: String,
+///     sections: Vec<String>,
+/// }
+///
+/// let mut doc = Document {
+///     title: "Draft".into(),
+///     sections: vec!["intro".into(), "body".into()],
+/// };
+/// let mut tracked = DirtyTracked::new(&mut doc, DirtyPaths::new());
+/// tracked.set("sections[1]", String::from("results")).unwrap();
+///
+/// let changed: Vec<_> = tracked
+///     .sink()
+///     .subtrees(tracked.peek())
+///     .map(|(path, value)| (path, value.unwrap().as_str().unwrap()))
+///     .collect();
+/// assert_eq!(changed, [("sections[1]", "results")]);
+/// ```
+pub type DirtyTracked<'mem, 'facet> = Audited<'mem, 'facet, DirtyPaths>;
+
+/// The paths changed in a value, as recorded by a [`DirtyTracked`] value
+///
+/// Paths use the syntax of [`Peek::at`]. Only the outermost changed paths
+/// are kept: once `servers` is dirty, changes to `servers[0].port` are
+/// already covered, and marking `servers` drops the paths below it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DirtyPaths {
+    paths: BTreeSet<String>,
+}
+
+impl DirtyPaths {
+    /// No paths are dirty
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark the value at `path` as changed, with everything below it
+    ///
+    /// The empty path marks the whole value.
+    pub fn mark(&mut self, path: &str) {
+        let path = normalize(path);
+        if self.is_dirty(path) {
+            return;
+        }
+        self.paths.retain(|dirty| !is_within(dirty, path));
+        self.paths.insert(path.into());
+    }
+
+    /// Whether the value at `path` was changed, either itself or as part of
+    /// a value containing it
+    pub fn is_dirty(&self, path: &str) -> bool {
+        let path = normalize(path);
+        self.paths.iter().any(|dirty| is_within(path, dirty))
+    }
+
+    /// The changed paths, sorted
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.paths.iter().map(String::as_str)
+    }
+
+    /// The number of changed paths
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Whether nothing was changed
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Forget the changes, e.g. once they have been sent
+    pub fn clear(&mut self) {
+        self.paths.clear();
+    }
+
+    /// The changed paths, each with the value now at that path in `root`
+    ///
+    /// A path fails to resolve only if `root` isn't the value the changes
+    /// were recorded on, or was changed without going through the tracker.
+    pub fn subtrees<'a, 'mem, 'facet>(
+        &'a self,
+        root: Peek<'mem, 'facet>,
+    ) -> impl Iterator<Item = (&'a str, Result<Peek<'mem, 'facet>, PeekPathError>)> {
+        self.iter().map(move |path| (path, root.at(path)))
+    }
+}
+
+impl AuditSink for DirtyPaths {
+    fn record(&mut self, change: &Change<'_, '_>) {
+        self.mark(change.path);
+    }
+}
+
+fn normalize(path: &str) -> &str {
+    path.strip_prefix('.').unwrap_or(path)
+}
+
+/// Whether `path` is `ancestor` or a path below it
+fn is_within(path: &str, ancestor: &str) -> bool {
+    ancestor.is_empty()
+        || path
+            .strip_prefix(ancestor)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+}
@@ -0,0 +1,129 @@
+use facet_core::{Facet, ListDef, PtrMut};
+
+use crate::{HeapValue, ReflectError};
+
+use super::Poke;
+
+/// Lets you grow a list, like a `Vec<T>`, and mutate its items.
+///
+/// Appending never breaks the list's own invariants, so unlike struct field
+/// mutation this doesn't require `#[facet(pod)]`.
+pub struct PokeList<'mem, 'facet> {
+    /// The underlying value
+    pub(crate) value: Poke<'mem, 'facet>,
+
+    /// The definition of the list
+    pub(crate) def: ListDef,
+}
+
+impl core::fmt::Debug for PokeList<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PokeList").finish_non_exhaustive()
+    }
+}
+
+impl<'mem, 'facet> PokeList<'mem, 'facet> {
+    /// Returns the list definition.
+    #[inline(always)]
+    pub fn def(&self) -> ListDef {
+        self.def
+    }
+
+    /// Returns the number of items in the list.
+    #[inline]
+    pub fn len(&self) -> usize {
+        unsafe { (self.def.vtable.len)(self.value.data()) }
+    }
+
+    /// Returns true if the list is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns how many items the list can hold without reallocating, if the
+    /// list type reports it.
+    pub fn capacity(&self) -> Option<usize> {
+        let capacity = self.def.capacity()?;
+        Some(unsafe { capacity(self.value.data()) })
+    }
+
+    /// Reserves room for at least `additional` more items.
+    pub fn reserve(&mut self, additional: usize) -> Result<(), ReflectError> {
+        let reserve = self.def.reserve().ok_or(ReflectError::OperationFailed {
+            shape: self.value.shape,
+            operation: "reserve",
+        })?;
+        unsafe { reserve(self.value.data, additional) };
+        Ok(())
+    }
+
+    /// Appends `item` to the list.
+    ///
+    /// The item type must match the list's item type.
+    pub fn push<T: Facet<'facet>>(&mut self, item: T) -> Result<(), ReflectError> {
+        let push = self.push_fn()?;
+        if self.def.t() != T::SHAPE {
+            return Err(ReflectError::WrongShape {
+                expected: self.def.t(),
+                actual: T::SHAPE,
+            });
+        }
+
+        let mut item = core::mem::ManuallyDrop::new(item);
+        unsafe {
+            push(
+                self.value.data,
+                PtrMut::new(&mut *item as *mut T as *mut u8),
+            )
+        };
+        Ok(())
+    }
+
+    /// Appends a value built with [`Partial`](crate::Partial), e.g. by a
+    /// deserializer that only knows the list's item shape.
+    pub fn push_value<const BORROW: bool>(
+        &mut self,
+        item: HeapValue<'facet, BORROW>,
+    ) -> Result<(), ReflectError> {
+        let push = self.push_fn()?;
+        if self.def.t() != item.shape() {
+            return Err(ReflectError::WrongShape {
+                expected: self.def.t(),
+                actual: item.shape(),
+            });
+        }
+
+        let list = self.value.data;
+        // SAFETY: the push function reads the item out of the allocation
+        unsafe { item.move_out(|item| push(list, item)) };
+        Ok(())
+    }
+
+    /// Returns a `Poke` for the item at `index`, or `None` if it's out of
+    /// bounds or the list doesn't give out mutable access to its items.
+    pub fn item(&mut self, index: usize) -> Option<Poke<'_, 'facet>> {
+        let get_mut = self.def.vtable.get_mut?;
+        let item = unsafe { get_mut(self.value.data, index, self.value.shape)? };
+        Some(unsafe { Poke::from_raw_parts(item, self.def.t()) })
+    }
+
+    /// Returns a pointer to the list's contiguous buffer of items, if it has one.
+    pub fn as_mut_ptr(&mut self) -> Option<PtrMut> {
+        let as_mut_ptr = self.def.vtable.as_mut_ptr?;
+        Some(unsafe { as_mut_ptr(self.value.data) })
+    }
+
+    /// Converts this back into the underlying `Poke`.
+    #[inline]
+    pub fn into_inner(self) -> Poke<'mem, 'facet> {
+        self.value
+    }
+
+    fn push_fn(&self) -> Result<facet_core::ListPushFn, ReflectError> {
+        self.def.push().ok_or(ReflectError::OperationFailed {
+            shape: self.value.shape,
+            operation: "push",
+        })
+    }
+}
@@ -0,0 +1,107 @@
+use facet_core::{Facet, MapDef, PtrConst, PtrMut, Shape};
+
+use crate::{HeapValue, ReflectError};
+
+use super::Poke;
+
+/// Lets you insert entries into a map, like a `HashMap<K, V>` or a
+/// `BTreeMap<K, V>`, whatever its key type.
+///
+/// Inserting never breaks the map's own invariants, so unlike struct field
+/// mutation this doesn't require `#[facet(pod)]`.
+pub struct PokeMap<'mem, 'facet> {
+    /// The underlying value
+    pub(crate) value: Poke<'mem, 'facet>,
+
+    /// The definition of the map
+    pub(crate) def: MapDef,
+}
+
+impl core::fmt::Debug for PokeMap<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PokeMap").finish_non_exhaustive()
+    }
+}
+
+impl<'mem, 'facet> PokeMap<'mem, 'facet> {
+    /// Returns the map definition.
+    #[inline(always)]
+    pub fn def(&self) -> MapDef {
+        self.def
+    }
+
+    /// Returns the number of entries in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        unsafe { (self.def.vtable.len)(self.value.data()) }
+    }
+
+    /// Returns true if the map is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the map has an entry for `key`.
+    ///
+    /// The key type must match the map's key type.
+    pub fn contains_key<K: Facet<'facet>>(&self, key: &K) -> Result<bool, ReflectError> {
+        check_shape(self.def.k(), K::SHAPE)?;
+        let key = PtrConst::new(key as *const K as *const u8);
+        Ok(unsafe { (self.def.vtable.contains_key)(self.value.data(), key) })
+    }
+
+    /// Inserts `value` under `key`, replacing any previous value.
+    ///
+    /// The key and value types must match the map's.
+    pub fn insert<K: Facet<'facet>, V: Facet<'facet>>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<(), ReflectError> {
+        check_shape(self.def.k(), K::SHAPE)?;
+        check_shape(self.def.v(), V::SHAPE)?;
+
+        let mut key = core::mem::ManuallyDrop::new(key);
+        let mut value = core::mem::ManuallyDrop::new(value);
+        unsafe {
+            (self.def.vtable.insert)(
+                self.value.data,
+                PtrMut::new(&mut *key as *mut K as *mut u8),
+                PtrMut::new(&mut *value as *mut V as *mut u8),
+            )
+        };
+        Ok(())
+    }
+
+    /// Inserts a key and value built with [`Partial`](crate::Partial), e.g. by
+    /// a deserializer that only knows the map's key and value shapes.
+    pub fn insert_values<const BORROW: bool>(
+        &mut self,
+        key: HeapValue<'facet, BORROW>,
+        value: HeapValue<'facet, BORROW>,
+    ) -> Result<(), ReflectError> {
+        check_shape(self.def.k(), key.shape())?;
+        check_shape(self.def.v(), value.shape())?;
+
+        let map = self.value.data;
+        let insert = self.def.vtable.insert;
+        // SAFETY: the insert function reads both out of their allocations
+        unsafe { key.move_out(|key| value.move_out(|value| insert(map, key, value))) };
+        Ok(())
+    }
+
+    /// Converts this back into the underlying `Poke`.
+    #[inline]
+    pub fn into_inner(self) -> Poke<'mem, 'facet> {
+        self.value
+    }
+}
+
+fn check_shape(expected: &'static Shape, actual: &'static Shape) -> Result<(), ReflectError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(ReflectError::WrongShape { expected, actual })
+    }
+}
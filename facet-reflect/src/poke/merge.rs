@@ -0,0 +1,328 @@
+//! Layered merging of one value into another.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use facet_core::{Def, Facet, Field, PtrMut, Type, UserType};
+
+use crate::{HeapValue, Peek, ReflectError};
+
+use super::Poke;
+
+/// What to do with a node when merging an overlay into a base value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeRule {
+    /// The overlay value replaces the base value
+    Replace,
+    /// The base value is kept and the overlay value is ignored
+    Keep,
+    /// Lists get the overlay's items appended, sets get the union of both.
+    ///
+    /// Behaves like [`MergeRule::Replace`] on any other kind of value.
+    Append,
+    /// Merge recursively:
+    ///
+    /// - structs merge field by field
+    /// - enums merge field by field when both sides hold the same variant,
+    ///   and are replaced otherwise
+    /// - maps get the union of both key sets; values present on both sides
+    ///   are merged recursively
+    /// - `Some` merges into `Some`
+    ///
+    /// Behaves like [`MergeRule::Replace`] on scalars, lists and sets.
+    Merge,
+}
+
+/// Controls how [`merge`] combines a base value with an overlay
+///
+/// The default merges structs and maps recursively, lets overlay lists and
+/// scalars win, and keeps base values where the overlay has `None` — the
+/// usual semantics for layered configuration.
+#[derive(Debug, Clone)]
+pub struct MergeStrategy {
+    structs: MergeRule,
+    maps: MergeRule,
+    lists: MergeRule,
+    none_overrides: bool,
+    rules: Vec<(String, MergeRule)>,
+}
+
+impl Default for MergeStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MergeStrategy {
+    /// The default strategy for layered configuration
+    pub fn new() -> Self {
+        Self {
+            structs: MergeRule::Merge,
+            maps: MergeRule::Merge,
+            lists: MergeRule::Replace,
+            none_overrides: false,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Rule for structs and enums (default: [`MergeRule::Merge`])
+    pub fn with_structs(mut self, rule: MergeRule) -> Self {
+        self.structs = rule;
+        self
+    }
+
+    /// Rule for maps (default: [`MergeRule::Merge`])
+    pub fn with_maps(mut self, rule: MergeRule) -> Self {
+        self.maps = rule;
+        self
+    }
+
+    /// Rule for lists and sets (default: [`MergeRule::Replace`])
+    pub fn with_lists(mut self, rule: MergeRule) -> Self {
+        self.lists = rule;
+        self
+    }
+
+    /// Whether a `None` in the overlay clears a `Some` in the base (default: `false`)
+    pub fn with_none_overrides(mut self, none_overrides: bool) -> Self {
+        self.none_overrides = none_overrides;
+        self
+    }
+
+    /// Use `rule` for the node at `path`, regardless of its kind
+    ///
+    /// Paths are dot-separated field names, with string map keys as
+    /// segments too, e.g. `server.tls` or `upstreams.primary.hosts`.
+    pub fn with_rule_at(mut self, path: impl Into<String>, rule: MergeRule) -> Self {
+        self.rules.push((path.into(), rule));
+        self
+    }
+
+    fn rule_for(&self, path: &str, default: MergeRule) -> MergeRule {
+        self.rules
+            .iter()
+            .rev()
+            .find(|(rule_path, _)| rule_path == path)
+            .map_or(default, |(_, rule)| *rule)
+    }
+}
+
+/// Merge `overlay` into `base` according to `strategy`
+///
+/// `overlay` must have the same shape as `T`. Values taken from the overlay
+/// are copied with [`Peek::deep_clone`].
+pub fn merge<'facet, T: Facet<'facet>>(
+    base: &mut T,
+    overlay: Peek<'_, 'facet>,
+    strategy: &MergeStrategy,
+) -> Result<(), ReflectError> {
+    Poke::new(base).merge(overlay, strategy)
+}
+
+impl<'mem, 'facet> Poke<'mem, 'facet> {
+    /// Merge `overlay` into this value according to `strategy`. See [`merge`].
+    pub fn merge(
+        &mut self,
+        overlay: Peek<'_, 'facet>,
+        strategy: &MergeStrategy,
+    ) -> Result<(), ReflectError> {
+        if self.shape != overlay.shape {
+            return Err(ReflectError::WrongShape {
+                expected: self.shape,
+                actual: overlay.shape,
+            });
+        }
+        let mut path = String::new();
+        merge_node(self.data, overlay, strategy, &mut path)
+    }
+}
+
+fn merge_node(
+    base: PtrMut,
+    overlay: Peek<'_, '_>,
+    strategy: &MergeStrategy,
+    path: &mut String,
+) -> Result<(), ReflectError> {
+    let shape = overlay.shape;
+    let default_rule = match shape.def {
+        Def::Option(_) => MergeRule::Merge,
+        Def::Map(_) => strategy.maps,
+        Def::List(_) | Def::Set(_) => strategy.lists,
+        Def::Undefined => match shape.ty {
+            Type::User(UserType::Struct(_) | UserType::Enum(_)) => strategy.structs,
+            _ => MergeRule::Replace,
+        },
+        _ => MergeRule::Replace,
+    };
+
+    match (strategy.rule_for(path, default_rule), shape.def) {
+        (MergeRule::Keep, _) => Ok(()),
+        (MergeRule::Append, Def::List(def)) => {
+            let push = def.push().ok_or(ReflectError::OperationFailed {
+                shape,
+                operation: "merge: list does not support push",
+            })?;
+            for item in overlay.into_list()?.iter() {
+                let item = item.deep_clone()?;
+                // SAFETY: `push` moves the item out of the heap value.
+                unsafe { item.move_out(|item| push(base, item)) };
+            }
+            Ok(())
+        }
+        (MergeRule::Append, Def::Set(def)) => {
+            for item in overlay.into_set()?.iter() {
+                let item = item.deep_clone()?;
+                // SAFETY: `insert` moves the item out of the heap value.
+                unsafe {
+                    item.move_out(|item| {
+                        (def.vtable.insert)(base, item);
+                    })
+                };
+            }
+            Ok(())
+        }
+        (MergeRule::Merge, Def::Option(def)) => match overlay.into_option()?.value() {
+            None if strategy.none_overrides => replace(base, overlay),
+            None => Ok(()),
+            Some(inner) => {
+                // SAFETY: `base` is an initialized option of this shape.
+                match unsafe { (def.vtable.get_value)(base.as_const()) } {
+                    Some(base_inner) => {
+                        let base_inner = unsafe { Peek::unchecked_new(base_inner, def.t) };
+                        let merged = merged_copy(base_inner, inner, strategy, path)?;
+                        // SAFETY: `replace_with` drops the old inner value and moves
+                        // the merged one out of its heap value.
+                        unsafe {
+                            merged.move_out(|value| {
+                                (def.vtable.replace_with)(base, Some(value.as_const()))
+                            })
+                        };
+                        Ok(())
+                    }
+                    None => replace(base, overlay),
+                }
+            }
+        },
+        (MergeRule::Merge, Def::Map(def)) => {
+            for (key, value) in overlay.into_map()?.iter() {
+                // SAFETY: `base` is an initialized map of this shape, and `key` has
+                // the map's key shape.
+                let value = match unsafe { (def.vtable.get_value_ptr)(base.as_const(), key.data) } {
+                    Some(base_value) => {
+                        let base_value = unsafe { Peek::unchecked_new(base_value, def.v) };
+                        let len = push_segment(path, key.as_str());
+                        let merged = merged_copy(base_value, value, strategy, path)?;
+                        path.truncate(len);
+                        merged
+                    }
+                    None => value.deep_clone()?,
+                };
+                let key = key.deep_clone()?;
+                // SAFETY: `insert` moves both the key and the value out of their
+                // heap values, replacing any previous value for that key.
+                unsafe {
+                    key.move_out(|key| {
+                        value.move_out(|value| (def.vtable.insert)(base, key, value))
+                    })
+                };
+            }
+            Ok(())
+        }
+        (MergeRule::Merge, Def::Undefined) => match shape.ty {
+            Type::User(UserType::Struct(struct_type)) => {
+                merge_fields(base, overlay, struct_type.fields, strategy, path)
+            }
+            Type::User(UserType::Enum(_)) => {
+                // SAFETY: `base` is an initialized value of this shape.
+                let base_enum =
+                    unsafe { Peek::unchecked_new(base.as_const(), shape) }.into_enum()?;
+                let overlay_enum = overlay.into_enum()?;
+                let variant_error = |_| ReflectError::OperationFailed {
+                    shape,
+                    operation: "merge: could not determine the active enum variant",
+                };
+                let base_index = base_enum.variant_index().map_err(variant_error)?;
+                if base_index != overlay_enum.variant_index().map_err(variant_error)? {
+                    return replace(base, overlay);
+                }
+                let variant = overlay_enum.active_variant().map_err(variant_error)?;
+                merge_fields(base, overlay, variant.data.fields, strategy, path)
+            }
+            _ => replace(base, overlay),
+        },
+        _ => replace(base, overlay),
+    }
+}
+
+fn merge_fields(
+    base: PtrMut,
+    overlay: Peek<'_, '_>,
+    fields: &'static [Field],
+    strategy: &MergeStrategy,
+    path: &mut String,
+) -> Result<(), ReflectError> {
+    for field in fields {
+        // SAFETY: both sides hold the same struct or variant, so `field.offset`
+        // is in bounds for both.
+        let (base_field, overlay_field) = unsafe {
+            (
+                base.field(field.offset),
+                Peek::unchecked_new(overlay.data.field(field.offset), field.shape()),
+            )
+        };
+        let len = push_segment(path, Some(field.name));
+        merge_node(base_field, overlay_field, strategy, path)?;
+        path.truncate(len);
+    }
+    Ok(())
+}
+
+/// Merges `overlay` into a deep clone of `base`.
+///
+/// Maps and options only hand out shared pointers to their contents, so
+/// their entries are merged out of place and written back afterwards.
+fn merged_copy<'facet>(
+    base: Peek<'_, 'facet>,
+    overlay: Peek<'_, '_>,
+    strategy: &MergeStrategy,
+    path: &mut String,
+) -> Result<HeapValue<'facet>, ReflectError> {
+    let mut copy = base.deep_clone()?;
+    merge_node(copy.data_mut(), overlay, strategy, path)?;
+    Ok(copy)
+}
+
+/// Appends a path segment, returning the length to truncate back to.
+fn push_segment(path: &mut String, segment: Option<&str>) -> usize {
+    let len = path.len();
+    if let Some(segment) = segment {
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(segment);
+    }
+    len
+}
+
+/// Replaces `base` with a deep clone of `overlay`.
+fn replace(base: PtrMut, overlay: Peek<'_, '_>) -> Result<(), ReflectError> {
+    let shape = overlay.shape;
+    let size = shape
+        .layout
+        .sized_layout()
+        .map_err(|_| ReflectError::Unsized {
+            shape,
+            operation: "merge: replacing value",
+        })?
+        .size();
+    let value = overlay.deep_clone()?;
+    // SAFETY: `base` holds an initialized value of `shape`, which is dropped
+    // before the clone is moved over it.
+    unsafe {
+        value.move_out(|src| {
+            shape.call_drop_in_place(base);
+            core::ptr::copy_nonoverlapping(src.as_byte_ptr(), base.as_mut_byte_ptr(), size);
+        })
+    };
+    Ok(())
+}
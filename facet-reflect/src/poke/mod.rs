@@ -2,7 +2,8 @@
 //!
 //! This module provides the [`Poke`] type for mutating values at runtime.
 //! Unlike [`Peek`](crate::Peek) which provides read-only access, `Poke` allows
-//! modifying struct fields, enum variant data, and collection elements.
+//! modifying struct fields, enum variant data, and collection elements, and
+//! growing lists and maps.
 //!
 //! # Safety
 //!
@@ -22,3 +23,36 @@ pub use struct_::*;
 
 mod enum_;
 pub use enum_::*;
+
+#[cfg(feature = "alloc")]
+mod list;
+#[cfg(feature = "alloc")]
+pub use list::*;
+
+#[cfg(feature = "alloc")]
+mod map;
+#[cfg(feature = "alloc")]
+pub use map::*;
+
+#[cfg(feature = "alloc")]
+mod default;
+
+#[cfg(feature = "std")]
+mod audit;
+#[cfg(feature = "std")]
+pub use audit::*;
+
+#[cfg(feature = "std")]
+mod dirty;
+#[cfg(feature = "std")]
+pub use dirty::*;
+
+#[cfg(feature = "alloc")]
+mod merge;
+#[cfg(feature = "alloc")]
+pub use merge::*;
+
+#[cfg(feature = "alloc")]
+mod mutate;
+#[cfg(feature = "alloc")]
+pub use mutate::*;
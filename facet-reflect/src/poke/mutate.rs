@@ -0,0 +1,490 @@
+//! Random structured mutation of values, for fuzzing.
+
+use alloc::format;
+use alloc::string::String;
+
+use facet_core::{Characteristic, Def, Facet, PtrMut, ScalarType, Shape, Type, UserType, Variant};
+
+use crate::{HeapValue, Partial, Peek, ReflectError};
+
+use super::Poke;
+
+/// The kind of change a [`Mutator`] made
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MutationKind {
+    /// A boolean, number, `char` or `String` was replaced by a nearby or edge-case value
+    FlipScalar,
+    /// An item was removed from a list
+    DropListItem,
+    /// An enum was switched to another variant, with default field values
+    SwapVariant,
+}
+
+/// A change made by [`Mutator::mutate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mutation {
+    /// What was changed
+    pub kind: MutationKind,
+    /// Where it was changed: dot-separated field names, with `[i]` for list
+    /// and array items, e.g. `servers[2].port`. Empty for the root value.
+    pub path: String,
+}
+
+/// Applies random, shape-driven mutations to values
+///
+/// Each call to [`Mutator::mutate`] picks one mutation site uniformly at
+/// random among all scalars, lists and enums reachable in the value, and
+/// mutates it in place. Mutations are deterministic for a given seed, so a
+/// fuzzer can replay them.
+///
+/// This is meant to be used as a structure-aware custom mutator for
+/// `cargo-fuzz`: deserialize the input with any facet format, mutate it, and
+/// serialize it back, so the fuzzed parser sees inputs that are almost valid.
+///
+/// Mutated values are still memory-safe, but may break the logical
+/// invariants of the types involved, like any other field mutation.
+///
+/// Struct and enum fields, `Some` values, list items and array items are
+/// visited; maps, sets and smart pointers are left untouched.
+#[derive(Debug, Clone)]
+pub struct Mutator {
+    state: u64,
+    scalars: bool,
+    lists: bool,
+    variants: bool,
+}
+
+impl Mutator {
+    /// A mutator that applies all kinds of mutations, seeded with `seed`
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: seed,
+            scalars: true,
+            lists: true,
+            variants: true,
+        }
+    }
+
+    /// Whether to flip scalars (default: `true`)
+    pub fn with_scalars(mut self, enabled: bool) -> Self {
+        self.scalars = enabled;
+        self
+    }
+
+    /// Whether to drop list items (default: `true`)
+    pub fn with_lists(mut self, enabled: bool) -> Self {
+        self.lists = enabled;
+        self
+    }
+
+    /// Whether to swap enum variants (default: `true`)
+    pub fn with_variants(mut self, enabled: bool) -> Self {
+        self.variants = enabled;
+        self
+    }
+
+    /// Apply one random mutation to `value`
+    ///
+    /// Returns `None` if `value` has nothing that can be mutated.
+    pub fn mutate<'facet, T: Facet<'facet>>(
+        &mut self,
+        value: &mut T,
+    ) -> Result<Option<Mutation>, ReflectError> {
+        Poke::new(value).mutate(self)
+    }
+
+    /// SplitMix64
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`; `n` must not be zero
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn pick<T: Copy>(&mut self, choices: &[T]) -> T {
+        choices[self.below(choices.len())]
+    }
+}
+
+impl<'mem, 'facet> Poke<'mem, 'facet> {
+    /// Apply one random mutation to this value. See [`Mutator`].
+    pub fn mutate(&mut self, mutator: &mut Mutator) -> Result<Option<Mutation>, ReflectError> {
+        let mut walk = Walk {
+            mutator,
+            target: None,
+            sites: 0,
+            path: String::new(),
+            applied: None,
+        };
+        walk.node(self.data, self.shape)?;
+        if walk.sites == 0 {
+            return Ok(None);
+        }
+
+        walk.target = Some(walk.mutator.below(walk.sites));
+        walk.sites = 0;
+        walk.node(self.data, self.shape)?;
+        Ok(walk.applied)
+    }
+}
+
+/// A pass over a value, either counting mutation sites or applying the
+/// mutation at one of them.
+///
+/// Both passes must visit the same sites in the same order.
+struct Walk<'a> {
+    mutator: &'a mut Mutator,
+    /// Index of the site to mutate, or `None` to only count sites
+    target: Option<usize>,
+    sites: usize,
+    path: String,
+    applied: Option<Mutation>,
+}
+
+impl Walk<'_> {
+    /// Visits a site, returning whether it should be mutated
+    fn site(&mut self, kind: MutationKind) -> bool {
+        let hit = self.target == Some(self.sites);
+        self.sites += 1;
+        if hit {
+            self.applied = Some(Mutation {
+                kind,
+                path: self.path.clone(),
+            });
+        }
+        hit
+    }
+
+    /// In the counting pass, the value behind `ptr` is only read.
+    fn node(&mut self, ptr: PtrMut, shape: &'static Shape) -> Result<(), ReflectError> {
+        if self.applied.is_some() {
+            return Ok(());
+        }
+
+        match shape.def {
+            Def::Scalar => {
+                if let Some(scalar) = shape.scalar_type()
+                    && self.mutator.scalars
+                    && is_flippable(scalar)
+                    && self.site(MutationKind::FlipScalar)
+                {
+                    // SAFETY: `ptr` holds an initialized value of this scalar type.
+                    unsafe { flip_scalar(ptr, scalar, self.mutator) };
+                }
+                Ok(())
+            }
+            Def::List(def) => {
+                // SAFETY: `ptr` holds an initialized list of this shape.
+                let len = unsafe { (def.vtable.len)(ptr.as_const()) };
+                if let (Some(as_mut_ptr), Some(set_len), Some(layout)) = (
+                    def.vtable.as_mut_ptr,
+                    def.set_len(),
+                    def.t.layout.sized_layout().ok(),
+                ) && self.mutator.lists
+                    && len > 0
+                    && self.site(MutationKind::DropListItem)
+                {
+                    let index = self.mutator.below(len);
+                    // SAFETY: the item is dropped, the tail is moved down over it,
+                    // and the length shrunk so that every item is owned once.
+                    unsafe {
+                        let items = as_mut_ptr(ptr).as_mut_byte_ptr();
+                        let item = items.add(index * layout.size());
+                        def.t.call_drop_in_place(PtrMut::new(item));
+                        core::ptr::copy(
+                            item.add(layout.size()),
+                            item,
+                            (len - index - 1) * layout.size(),
+                        );
+                        set_len(ptr, len - 1);
+                    }
+                    return Ok(());
+                }
+
+                let Some(get_mut) = def.vtable.get_mut else {
+                    return Ok(());
+                };
+                for index in 0..len {
+                    // SAFETY: `index` is in bounds for this list.
+                    let Some(item) = (unsafe { get_mut(ptr, index, shape) }) else {
+                        break;
+                    };
+                    let len = push_index(&mut self.path, index);
+                    self.node(item, def.t)?;
+                    self.path.truncate(len);
+                }
+                Ok(())
+            }
+            Def::Array(def) => {
+                let Ok(layout) = def.t.layout.sized_layout() else {
+                    return Ok(());
+                };
+                for index in 0..def.n {
+                    // SAFETY: arrays store their items inline, one after the other.
+                    let item = unsafe { ptr.field(index * layout.size()) };
+                    let len = push_index(&mut self.path, index);
+                    self.node(item, def.t)?;
+                    self.path.truncate(len);
+                }
+                Ok(())
+            }
+            Def::Option(def) => {
+                // SAFETY: `ptr` holds an initialized option of this shape.
+                let Some(inner) = (unsafe { (def.vtable.get_value)(ptr.as_const()) }) else {
+                    return Ok(());
+                };
+                // Options only hand out shared pointers to their contents, so
+                // the inner value is mutated out of place and written back.
+                let start = self.sites;
+                let target = self.target.take();
+                self.node(PtrMut::new(inner.raw_ptr() as *mut u8), def.t)?;
+                self.target = target;
+                let Some(target) = target.filter(|target| (start..self.sites).contains(target))
+                else {
+                    return Ok(());
+                };
+
+                // SAFETY: `inner` is an initialized value of the option's inner shape.
+                let mut copy = unsafe { Peek::unchecked_new(inner, def.t) }.deep_clone()?;
+                self.sites = start;
+                self.target = Some(target);
+                self.node(copy.data_mut(), def.t)?;
+                // SAFETY: `replace_with` drops the old inner value and moves the
+                // mutated one out of its heap value.
+                unsafe {
+                    copy.move_out(|value| (def.vtable.replace_with)(ptr, Some(value.as_const())))
+                };
+                Ok(())
+            }
+            Def::Undefined => match shape.ty {
+                Type::User(UserType::Struct(struct_type)) => self.fields(ptr, struct_type.fields),
+                Type::User(UserType::Enum(enum_type)) => {
+                    // SAFETY: `ptr` holds an initialized enum of this shape.
+                    let peek = unsafe { Peek::unchecked_new(ptr.as_const(), shape) };
+                    let Ok(index) = peek.into_enum()?.variant_index() else {
+                        return Ok(());
+                    };
+                    let mut swappable = enum_type
+                        .variants
+                        .iter()
+                        .enumerate()
+                        .filter(|&(other, variant)| other != index && has_defaults(variant));
+                    if self.mutator.variants
+                        && swappable.clone().next().is_some()
+                        && self.site(MutationKind::SwapVariant)
+                    {
+                        let count = swappable.clone().count();
+                        let pick = self.mutator.below(count);
+                        let (other, _) = swappable.nth(pick).unwrap();
+                        let value = Partial::alloc_shape(shape)?
+                            .select_nth_variant(other)?
+                            .fill_defaults()?
+                            .build()?;
+                        // SAFETY: `ptr` holds an initialized value of `shape`,
+                        // and `value` was built for the same shape.
+                        unsafe { overwrite(ptr, shape, value) };
+                        Ok(())
+                    } else {
+                        self.fields(ptr, enum_type.variants[index].data.fields)
+                    }
+                }
+                _ => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn fields(
+        &mut self,
+        ptr: PtrMut,
+        fields: &'static [facet_core::Field],
+    ) -> Result<(), ReflectError> {
+        for field in fields {
+            let len = self.path.len();
+            if !self.path.is_empty() {
+                self.path.push('.');
+            }
+            self.path.push_str(field.name);
+            // SAFETY: `field.offset` is in bounds for the struct or active variant.
+            self.node(unsafe { ptr.field(field.offset) }, field.shape())?;
+            self.path.truncate(len);
+        }
+        Ok(())
+    }
+}
+
+impl<'facet> Partial<'facet> {
+    /// Set every field of the current struct or variant to its default.
+    fn fill_defaults(mut self) -> Result<Self, ReflectError> {
+        for index in 0..self.get_fields()?.len() {
+            self = self.set_nth_field_to_default(index)?;
+        }
+        Ok(self)
+    }
+}
+
+/// Appends `[index]` to the path, returning the length to truncate back to.
+fn push_index(path: &mut String, index: usize) -> usize {
+    let len = path.len();
+    path.push_str(&format!("[{index}]"));
+    len
+}
+
+/// Whether every field of `variant` can be set to a default value
+fn has_defaults(variant: &Variant) -> bool {
+    variant
+        .data
+        .fields
+        .iter()
+        .all(|field| field.default.is_some() || field.shape().is(Characteristic::Default))
+}
+
+fn is_flippable(scalar: ScalarType) -> bool {
+    matches!(
+        scalar,
+        ScalarType::Bool
+            | ScalarType::Char
+            | ScalarType::String
+            | ScalarType::F32
+            | ScalarType::F64
+            | ScalarType::U8
+            | ScalarType::U16
+            | ScalarType::U32
+            | ScalarType::U64
+            | ScalarType::U128
+            | ScalarType::USize
+            | ScalarType::I8
+            | ScalarType::I16
+            | ScalarType::I32
+            | ScalarType::I64
+            | ScalarType::I128
+            | ScalarType::ISize
+    )
+}
+
+/// Replaces the value behind `ptr` with `value`.
+///
+/// # Safety
+///
+/// `ptr` must hold an initialized value of `shape`, and `value` must have that shape.
+unsafe fn overwrite(ptr: PtrMut, shape: &'static Shape, value: HeapValue<'_>) {
+    let Ok(layout) = shape.layout.sized_layout() else {
+        return;
+    };
+    unsafe {
+        value.move_out(|src| {
+            shape.call_drop_in_place(ptr);
+            core::ptr::copy_nonoverlapping(src.as_byte_ptr(), ptr.as_mut_byte_ptr(), layout.size());
+        })
+    };
+}
+
+/// Strings that tend to trip up parsers and escaping
+const NASTY_STRS: &[&str] = &[
+    "\0", "\"", "\\", "\n", "\u{7f}", "\u{fffd}", "\u{202e}", "🦀",
+];
+
+/// Replaces a scalar by a different, nearby or edge-case value.
+///
+/// # Safety
+///
+/// `ptr` must hold an initialized value of type `scalar`.
+unsafe fn flip_scalar(ptr: PtrMut, scalar: ScalarType, rng: &mut Mutator) {
+    macro_rules! int {
+        ($t:ty) => {{
+            let value = unsafe { ptr.as_mut::<$t>() };
+            let edge = rng.pick(&[0, <$t>::MAX, <$t>::MIN]);
+            *value = if rng.below(2) == 0 && edge != *value {
+                edge
+            } else {
+                *value ^ (1 << rng.below(<$t>::BITS as usize))
+            };
+        }};
+    }
+    macro_rules! float {
+        ($t:ident) => {{
+            let value = unsafe { ptr.as_mut::<$t>() };
+            let edge = rng.pick(&[
+                $t::NAN,
+                $t::INFINITY,
+                $t::NEG_INFINITY,
+                0.0,
+                -0.0,
+                $t::MAX,
+                $t::MIN,
+                $t::MIN_POSITIVE,
+                $t::EPSILON,
+            ]);
+            *value = if rng.below(2) == 0 && edge.to_bits() != value.to_bits() {
+                edge
+            } else {
+                $t::from_bits(value.to_bits() ^ (1 << rng.below($t::MANTISSA_DIGITS as usize)))
+            };
+        }};
+    }
+
+    match scalar {
+        ScalarType::Bool => {
+            let value = unsafe { ptr.as_mut::<bool>() };
+            *value = !*value;
+        }
+        ScalarType::Char => {
+            let value = unsafe { ptr.as_mut::<char>() };
+            const CHARS: &[char] = &[
+                '\0',
+                '"',
+                '\\',
+                '\n',
+                '\u{7f}',
+                '\u{fffd}',
+                '\u{10ffff}',
+                '🦀',
+            ];
+            let index = rng.below(CHARS.len());
+            *value = if CHARS[index] == *value {
+                CHARS[(index + 1) % CHARS.len()]
+            } else {
+                CHARS[index]
+            };
+        }
+        ScalarType::String => {
+            let value = unsafe { ptr.as_mut::<String>() };
+            if value.is_empty() {
+                value.push_str(rng.pick(NASTY_STRS));
+                return;
+            }
+            let chars = value.char_indices().count();
+            let at = value
+                .char_indices()
+                .nth(rng.below(chars))
+                .map_or(0, |(at, _)| at);
+            match rng.below(4) {
+                0 => value.truncate(at),
+                1 => value.insert_str(at, rng.pick(NASTY_STRS)),
+                2 => *value = value.repeat(2),
+                _ => value.clear(),
+            }
+        }
+        ScalarType::F32 => float!(f32),
+        ScalarType::F64 => float!(f64),
+        ScalarType::U8 => int!(u8),
+        ScalarType::U16 => int!(u16),
+        ScalarType::U32 => int!(u32),
+        ScalarType::U64 => int!(u64),
+        ScalarType::U128 => int!(u128),
+        ScalarType::USize => int!(usize),
+        ScalarType::I8 => int!(i8),
+        ScalarType::I16 => int!(i16),
+        ScalarType::I32 => int!(i32),
+        ScalarType::I64 => int!(i64),
+        ScalarType::I128 => int!(i128),
+        ScalarType::ISize => int!(isize),
+        _ => {}
+    }
+}
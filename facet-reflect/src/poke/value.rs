@@ -113,6 +113,18 @@ impl<'mem, 'facet> Poke<'mem, 'facet> {
         matches!(self.shape.ty, Type::User(UserType::Enum(_)))
     }
 
+    /// Returns true if this value is a list.
+    #[inline]
+    pub fn is_list(&self) -> bool {
+        matches!(self.shape.def, Def::List(_))
+    }
+
+    /// Returns true if this value is a map.
+    #[inline]
+    pub fn is_map(&self) -> bool {
+        matches!(self.shape.def, Def::Map(_))
+    }
+
     /// Returns true if this value is a scalar (primitive type).
     #[inline]
     pub fn is_scalar(&self) -> bool {
@@ -133,6 +145,30 @@ impl<'mem, 'facet> Poke<'mem, 'facet> {
         }
     }
 
+    /// Converts this into a `PokeList` if the value is a list.
+    #[cfg(feature = "alloc")]
+    pub fn into_list(self) -> Result<super::PokeList<'mem, 'facet>, ReflectError> {
+        match self.shape.def {
+            Def::List(def) => Ok(super::PokeList { value: self, def }),
+            _ => Err(ReflectError::WasNotA {
+                expected: "list",
+                actual: self.shape,
+            }),
+        }
+    }
+
+    /// Converts this into a `PokeMap` if the value is a map.
+    #[cfg(feature = "alloc")]
+    pub fn into_map(self) -> Result<super::PokeMap<'mem, 'facet>, ReflectError> {
+        match self.shape.def {
+            Def::Map(def) => Ok(super::PokeMap { value: self, def }),
+            _ => Err(ReflectError::WasNotA {
+                expected: "map",
+                actual: self.shape,
+            }),
+        }
+    }
+
     /// Converts this into a `PokeEnum` if the value is an enum.
     pub fn into_enum(self) -> Result<super::PokeEnum<'mem, 'facet>, ReflectError> {
         match self.shape.ty {
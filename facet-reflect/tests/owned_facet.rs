@@ -0,0 +1,155 @@
+#![cfg(feature = "std")]
+
+use facet::Facet;
+use facet_reflect::{FacetMap, FacetVec, OwnedFacet, Partial, ReflectError};
+use facet_testhelpers::IPanic;
+
+#[derive(Facet, Debug, Clone, PartialEq)]
+#[facet(traits(Debug, Clone, PartialEq, Send, Sync))]
+struct Job {
+    name: String,
+    retries: u32,
+}
+
+#[derive(Facet, Debug)]
+struct Unmarked {
+    name: String,
+}
+
+#[derive(Facet, Debug)]
+struct Counter(u32);
+
+#[derive(Facet, Debug)]
+#[facet(traits(Send, Sync))]
+struct Wrapper<T>(T);
+
+#[test]
+fn owned_facet_clone_eq_and_downcast() {
+    let job = Job {
+        name: "backup".to_string(),
+        retries: 3,
+    };
+    let owned = OwnedFacet::new(job.clone());
+    assert_eq!(owned.shape(), Job::SHAPE);
+    assert_eq!(owned.downcast_ref::<Job>(), Some(&job));
+    assert_eq!(owned.downcast_ref::<String>(), None);
+
+    let cloned = owned.try_clone().unwrap();
+    assert_eq!(cloned, owned);
+    assert_ne!(cloned, OwnedFacet::new(String::from("backup")));
+
+    let owned = owned.downcast::<String>().unwrap_err();
+    assert_eq!(owned.downcast::<Job>().unwrap(), job);
+}
+
+#[test]
+fn owned_facet_crosses_threads() {
+    let job = Job {
+        name: "backup".to_string(),
+        retries: 3,
+    };
+    let owned = OwnedFacet::new(vec![job.clone(), job]);
+    let owned = std::thread::spawn(move || {
+        assert_eq!(owned.peek().into_list().unwrap().len(), 2);
+        owned
+    })
+    .join()
+    .unwrap();
+    assert_eq!(owned.downcast::<Vec<Job>>().unwrap().len(), 2);
+}
+
+#[test]
+fn owned_facet_from_heap_value() -> Result<(), IPanic> {
+    let value = Partial::alloc::<Job>()?
+        .set_field("name", String::from("backup"))?
+        .set_field("retries", 3u32)?
+        .build()?;
+    // SAFETY: `Job` is `Send` and `Sync`
+    let owned = unsafe { OwnedFacet::from_heap_value(value)? };
+    assert_eq!(
+        owned.downcast_ref::<Job>(),
+        Some(&Job {
+            name: "backup".to_string(),
+            retries: 3,
+        })
+    );
+
+    // Only types whose shape is marked Send and Sync can be type-erased
+    let value = Partial::alloc::<Unmarked>()?
+        .set_field("name", String::from("backup"))?
+        .build()?;
+    assert!(matches!(
+        // SAFETY: `Unmarked` is `Send` and `Sync`, just not marked so
+        unsafe { OwnedFacet::from_heap_value(value) },
+        Err(ReflectError::OperationFailed { .. })
+    ));
+
+    // Generic types aren't marked: `Wrapper<Rc<u8>>` is neither Send nor Sync
+    let value = Partial::alloc::<Wrapper<u32>>()?
+        .set_nth_field(0, 7u32)?
+        .build()?;
+    assert!(matches!(
+        // SAFETY: `Wrapper<u32>` is `Send` and `Sync`
+        unsafe { OwnedFacet::from_heap_value(value) },
+        Err(ReflectError::OperationFailed { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn owned_facet_without_clone() {
+    let owned = OwnedFacet::new(Counter(7));
+    assert!(matches!(
+        owned.try_clone(),
+        Err(ReflectError::MissingCharacteristic { .. })
+    ));
+    assert_eq!(owned.downcast::<Counter>().unwrap().0, 7);
+}
+
+#[test]
+fn facet_vec_holds_mixed_types() {
+    let job = Job {
+        name: "backup".to_string(),
+        retries: 3,
+    };
+    let mut events: FacetVec = [OwnedFacet::new(job.clone()), OwnedFacet::new(5u32)]
+        .into_iter()
+        .collect();
+    events.push(job.clone());
+    assert_eq!(events.len(), 3);
+
+    let shapes: Vec<_> = events.iter().map(|peek| peek.shape()).collect();
+    assert_eq!(shapes, [Job::SHAPE, u32::SHAPE, Job::SHAPE]);
+    assert_eq!(events.iter_of::<Job>().count(), 2);
+    assert_eq!(events.get_as::<u32>(1), Some(&5));
+    assert_eq!(events.get_as::<Job>(1), None);
+
+    let removed = events.remove(0);
+    assert_eq!(removed.downcast::<Job>().unwrap(), job);
+    assert_eq!(events.into_iter().count(), 2);
+}
+
+#[test]
+fn facet_map_holds_mixed_types() {
+    let mut plugins = FacetMap::new();
+    plugins.insert(
+        "job",
+        Job {
+            name: "backup".to_string(),
+            retries: 3,
+        },
+    );
+    assert!(plugins.insert("limit", 10u64).is_none());
+    assert!(plugins.insert("limit", 20u64).is_some());
+
+    let keys: Vec<_> = plugins
+        .iter()
+        .map(|(key, peek)| (key, peek.shape()))
+        .collect();
+    assert_eq!(keys, [("job", Job::SHAPE), ("limit", u64::SHAPE)]);
+    assert_eq!(plugins.get_as::<u64>("limit"), Some(&20));
+    assert_eq!(plugins.get_as::<Job>("limit"), None);
+    assert!(plugins.remove("job").is_some());
+    assert_eq!(plugins.len(), 1);
+}
@@ -0,0 +1,120 @@
+use std::collections::{BTreeSet, HashMap};
+
+use facet::Facet;
+use facet_reflect::{ReflectError, build_default, default_value};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Limits {
+    max: u32,
+    ratio: f64,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Mode {
+    Fixed { rate: u32 },
+    Off,
+}
+
+/// Deliberately not `Default`
+#[derive(Facet, Debug, PartialEq)]
+struct Config {
+    name: String,
+    limits: Limits,
+    boxed: Box<Limits>,
+    tags: Vec<String>,
+    labels: HashMap<String, u32>,
+    seen: BTreeSet<u64>,
+    grid: [Limits; 2],
+    timeout: Option<u32>,
+    mode: Mode,
+    #[facet(default = 8080)]
+    port: u16,
+}
+
+#[test]
+fn test_default_value_without_default_impl() {
+    let config: Config = default_value().unwrap();
+    let zero = || Limits { max: 0, ratio: 0.0 };
+    assert_eq!(
+        config,
+        Config {
+            name: String::new(),
+            limits: zero(),
+            boxed: Box::new(zero()),
+            tags: vec![],
+            labels: HashMap::new(),
+            seen: BTreeSet::new(),
+            grid: [zero(), zero()],
+            timeout: None,
+            mode: Mode::Off,
+            port: 8080,
+        }
+    );
+}
+
+#[test]
+fn test_default_impl_is_preferred() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[facet(default)]
+    struct Answer(u32);
+
+    impl Default for Answer {
+        fn default() -> Self {
+            Answer(42)
+        }
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Wrapper {
+        answer: Answer,
+    }
+
+    let wrapper: Wrapper = default_value().unwrap();
+    assert_eq!(wrapper.answer, Answer(42));
+}
+
+#[test]
+fn test_enum_falls_back_to_first_variant() {
+    #[derive(Facet, Debug, PartialEq)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum Shape {
+        Circle { radius: f32 },
+        Square(u32),
+    }
+
+    assert_eq!(
+        default_value::<Shape>().unwrap(),
+        Shape::Circle { radius: 0.0 }
+    );
+}
+
+#[test]
+fn test_build_default_from_shape() {
+    let value = build_default(Limits::SHAPE).unwrap();
+    let limits: Limits = value.materialize().unwrap();
+    assert_eq!(limits, Limits { max: 0, ratio: 0.0 });
+}
+
+#[test]
+fn test_missing_leaf_default_is_an_error() {
+    #[derive(Facet, Debug)]
+    #[facet(opaque)]
+    #[allow(dead_code)]
+    struct Handle(u32);
+
+    #[derive(Facet, Debug)]
+    #[allow(dead_code)]
+    struct Holder {
+        handle: Handle,
+    }
+
+    let err = default_value::<Holder>().unwrap_err();
+    assert!(
+        matches!(err, ReflectError::OperationFailed { shape, .. } if shape == Handle::SHAPE),
+        "{err}"
+    );
+}
@@ -3,7 +3,7 @@
 #![allow(unused_assignments)]
 
 use facet::Facet;
-use facet_reflect::{Partial, Resolution};
+use facet_reflect::{Partial, ReflectError, Resolution};
 use facet_testhelpers::{IPanic, test};
 
 // =============================================================================
@@ -219,6 +219,53 @@ fn deferred_deeply_nested_interleaved() -> Result<(), IPanic> {
     Ok(())
 }
 
+#[test]
+fn deferred_nested_struct_field_invariants_checked_on_finish() -> Result<(), IPanic> {
+    fn ordered(range: &Range) -> Result<(), &'static str> {
+        if range.lo <= range.hi {
+            Ok(())
+        } else {
+            Err("lo must not exceed hi")
+        }
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Range {
+        lo: u32,
+        hi: u32,
+    }
+
+    #[derive(Facet, Debug, PartialEq)]
+    struct Window {
+        #[facet(invariants = ordered)]
+        range: Range,
+        label: String,
+    }
+
+    fn build_window(lo: u32, hi: u32) -> Result<Partial<'static>, ReflectError> {
+        let mut partial: Partial<'_> = Partial::alloc::<Window>()?;
+        partial = partial.begin_deferred(Resolution::new())?;
+        partial = partial.begin_field("range")?;
+        partial = partial.set_field("lo", lo)?;
+        partial = partial.end()?;
+        partial = partial.set_field("label", String::from("window"))?;
+        partial = partial.begin_field("range")?;
+        partial = partial.set_field("hi", hi)?;
+        partial = partial.end()?;
+        partial.finish_deferred()
+    }
+
+    let window = build_window(1, 5)?.build()?.materialize::<Window>()?;
+    assert_eq!(window.range, Range { lo: 1, hi: 5 });
+
+    assert!(matches!(
+        build_window(5, 1),
+        Err(ReflectError::UserInvariantFailed { message, .. }) if message == "lo must not exceed hi"
+    ));
+
+    Ok(())
+}
+
 // =============================================================================
 // Enum tests
 // =============================================================================
@@ -1,5 +1,5 @@
 use facet::Facet;
-use facet_reflect::Partial;
+use facet_reflect::{Partial, ReflectError};
 use facet_testhelpers::{IPanic, test};
 
 #[test]
@@ -136,10 +136,116 @@ fn build_nested_with_invariants() -> Result<(), IPanic> {
     partial = partial.begin_field("point")?;
     partial = partial.set_field("x", -10i32)?;
     partial = partial.set_field("y", 20i32)?;
-    partial = partial.end()?;
-    let result = partial.build();
-    // This should succeed because Container itself has no invariants,
-    // only Point does, and we're not checking nested invariants yet
-    assert!(result.is_ok());
+    // Point's invariants are checked as soon as it's complete, even though
+    // Container itself has none
+    let result = partial.end();
+    assert!(matches!(
+        result,
+        Err(ReflectError::UserInvariantFailed { shape, .. }) if shape == Point::SHAPE
+    ));
+    Ok(())
+}
+
+#[test]
+fn build_with_invariant_messages() -> Result<(), IPanic> {
+    #[derive(Facet, PartialEq, Debug)]
+    #[facet(invariants = Range::invariants)]
+    struct Range {
+        start: u32,
+        end: u32,
+    }
+
+    impl Range {
+        fn invariants(&self) -> Result<(), String> {
+            if self.start <= self.end {
+                Ok(())
+            } else {
+                Err(format!("start {} is after end {}", self.start, self.end))
+            }
+        }
+    }
+
+    let mut partial: Partial<'_> = Partial::alloc::<Range>()?;
+    partial = partial.set_field("start", 5u32)?;
+    partial = partial.set_field("end", 1u32)?;
+    let err = partial.build().unwrap_err();
+    assert!(matches!(
+        &err,
+        ReflectError::UserInvariantFailed { message, .. } if message == "start 5 is after end 1"
+    ));
+    Ok(())
+}
+
+#[test]
+fn build_with_field_invariants() -> Result<(), IPanic> {
+    fn valid_port(port: &u32) -> Result<(), &'static str> {
+        if *port < 65536 {
+            Ok(())
+        } else {
+            Err("port must be < 65536")
+        }
+    }
+
+    fn non_empty(host: &str) -> bool {
+        !host.is_empty()
+    }
+
+    #[derive(Facet, PartialEq, Debug)]
+    struct Listen {
+        #[facet(invariants = non_empty)]
+        host: String,
+        #[facet(invariants = valid_port)]
+        port: u32,
+    }
+
+    let mut partial: Partial<'_> = Partial::alloc::<Listen>()?;
+    partial = partial.set_field("host", String::from("localhost"))?;
+    partial = partial.set_field("port", 8080u32)?;
+    let listen: Listen = partial.build()?.materialize::<Listen>()?;
+    assert_eq!(listen.port, 8080);
+
+    let partial: Partial<'_> = Partial::alloc::<Listen>()?;
+    assert!(matches!(
+        partial.set_field("port", 70000u32),
+        Err(ReflectError::UserInvariantFailed { message, .. }) if message == "port must be < 65536"
+    ));
+
+    let partial: Partial<'_> = Partial::alloc::<Listen>()?;
+    assert!(matches!(
+        partial.set_field("host", String::new()),
+        Err(ReflectError::UserInvariantFailed { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn build_generic_struct_with_field_invariants() -> Result<(), IPanic> {
+    fn non_empty<T>(items: &[T]) -> Result<(), &'static str> {
+        if items.is_empty() {
+            Err("at least one item is required")
+        } else {
+            Ok(())
+        }
+    }
+
+    #[derive(Facet, PartialEq, Debug)]
+    struct Batch<T>
+    where
+        T: Clone,
+    {
+        #[facet(invariants = non_empty)]
+        items: Vec<T>,
+    }
+
+    let mut partial: Partial<'_> = Partial::alloc::<Batch<u8>>()?;
+    partial = partial.set_field("items", vec![1u8, 2])?;
+    let batch: Batch<u8> = partial.build()?.materialize::<Batch<u8>>()?;
+    assert_eq!(batch.items, [1, 2]);
+
+    let partial: Partial<'_> = Partial::alloc::<Batch<u8>>()?;
+    assert!(matches!(
+        partial.set_field("items", Vec::<u8>::new()),
+        Err(ReflectError::UserInvariantFailed { message, .. }) if message == "at least one item is required"
+    ));
     Ok(())
 }
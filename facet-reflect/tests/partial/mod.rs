@@ -1,6 +1,7 @@
 #![cfg(feature = "std")]
 
 mod array_building;
+mod default_value;
 mod deferred;
 mod deserialize;
 mod empty_tuples;
@@ -10,6 +11,7 @@ mod list_leak;
 mod map;
 mod map_leak;
 mod misc;
+mod nested_wrappers;
 mod no_uninit;
 mod option_building;
 mod option_leak;
@@ -18,6 +20,7 @@ mod pointer_complex;
 mod put_vec_leak;
 mod result_building;
 mod set;
+mod so_far;
 mod struct_leak;
 mod tuples;
 mod variance;
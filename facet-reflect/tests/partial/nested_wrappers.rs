@@ -0,0 +1,72 @@
+use std::sync::{Arc, Mutex};
+
+use facet::Facet;
+use facet_reflect::Partial;
+use facet_testhelpers::IPanic;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Node {
+    value: u32,
+    next: Option<Box<Node>>,
+}
+
+#[derive(Facet, Debug)]
+struct Shared {
+    counter: Arc<Mutex<u32>>,
+    tags: Option<Vec<String>>,
+}
+
+#[test]
+fn build_option_box_chain() -> Result<(), IPanic> {
+    let node = Partial::alloc::<Node>()?
+        .set_field("value", 1u32)?
+        .begin_field("next")?
+        .begin_some()?
+        .begin_smart_ptr()?
+        .set_field("value", 2u32)?
+        .set_field("next", None::<Box<Node>>)?
+        .end()? // end smart pointer
+        .end()? // end some
+        .end()? // end field
+        .build()?
+        .materialize::<Node>()?;
+
+    assert_eq!(
+        node,
+        Node {
+            value: 1,
+            next: Some(Box::new(Node {
+                value: 2,
+                next: None
+            })),
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn build_arc_mutex_and_option_vec() -> Result<(), IPanic> {
+    let shared = Partial::alloc::<Shared>()?
+        .begin_field("counter")?
+        .begin_smart_ptr()? // into Arc
+        .begin_smart_ptr()? // into Mutex
+        .set(5u32)?
+        .end()?
+        .end()?
+        .end()?
+        .begin_field("tags")?
+        .begin_some()?
+        .begin_list()?
+        .push(String::from("a"))?
+        .push(String::from("b"))?
+        .end()?
+        .end()?
+        .build()?
+        .materialize::<Shared>()?;
+
+    assert_eq!(*shared.counter.lock().unwrap(), 5);
+    assert_eq!(shared.tags, Some(vec!["a".to_string(), "b".to_string()]));
+
+    Ok(())
+}
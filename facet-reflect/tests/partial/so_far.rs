@@ -0,0 +1,94 @@
+use facet::Facet;
+use facet_reflect::Partial;
+use facet_testhelpers::{IPanic, test};
+
+#[derive(Facet, PartialEq, Eq, Debug)]
+struct Request {
+    kind: String,
+    id: u32,
+    body: Body,
+}
+
+#[derive(Facet, PartialEq, Eq, Debug)]
+struct Body {
+    text: String,
+    tags: Vec<String>,
+}
+
+#[derive(Facet, PartialEq, Eq, Debug)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Event {
+    Click { x: i32, y: i32 },
+    Key(char),
+}
+
+#[test]
+fn peek_so_far_struct_fields() -> Result<(), IPanic> {
+    let mut partial: Partial<'_> = Partial::alloc::<Request>()?;
+    let so_far = partial.peek_so_far().unwrap();
+    assert_eq!(so_far.shape(), Request::SHAPE);
+    assert!(!so_far.is_init());
+    assert!(so_far.as_peek().is_none());
+    assert_eq!(so_far.initialized_fields().count(), 0);
+
+    partial = partial.set_field("id", 42u32)?;
+    let so_far = partial.peek_so_far().unwrap();
+    assert!(so_far.field_by_name("kind").is_none());
+    assert_eq!(so_far.field_by_name("id").unwrap().get::<u32>()?, &42);
+
+    // A field that's still being built is skipped by its parent
+    partial = partial.begin_field("body")?;
+    partial = partial.set_field("text", String::from("hi"))?;
+    {
+        let mut frames = partial.frames_so_far();
+        let root = frames.next().unwrap();
+        assert!(root.field_by_name("body").is_none());
+        let names: Vec<_> = root.initialized_fields().map(|(f, _)| f.name).collect();
+        assert_eq!(names, ["id"]);
+
+        let body = frames.next().unwrap();
+        assert_eq!(body.shape(), Body::SHAPE);
+        assert_eq!(body.field_by_name("text").unwrap().get::<String>()?, "hi");
+        assert!(frames.next().is_none());
+    }
+    partial = partial.set_field("tags", vec![String::from("a")])?;
+    partial = partial.end()?;
+
+    let so_far = partial.peek_so_far().unwrap();
+    let body = so_far.field_by_name("body").unwrap().into_struct()?;
+    assert_eq!(body.field_by_name("tags")?.get::<Vec<String>>()?.len(), 1);
+
+    partial = partial.set_field("kind", String::from("post"))?;
+    let so_far = partial.peek_so_far().unwrap();
+    assert_eq!(so_far.initialized_fields().count(), 3);
+
+    let request = partial.build()?.materialize::<Request>()?;
+    assert_eq!(request.id, 42);
+    Ok(())
+}
+
+#[test]
+fn peek_so_far_enum_variant() -> Result<(), IPanic> {
+    let mut partial: Partial<'_> = Partial::alloc::<Event>()?;
+    assert!(partial.peek_so_far().unwrap().variant().is_none());
+
+    partial = partial.select_variant_named("Click")?;
+    partial = partial.set_field("y", 7i32)?;
+    let so_far = partial.peek_so_far().unwrap();
+    assert_eq!(so_far.variant().unwrap().name, "Click");
+    assert!(so_far.field_by_name("x").is_none());
+    assert_eq!(so_far.field_by_name("y").unwrap().get::<i32>()?, &7);
+    Ok(())
+}
+
+#[test]
+fn peek_so_far_scalar() -> Result<(), IPanic> {
+    let mut partial: Partial<'_> = Partial::alloc::<u64>()?;
+    assert!(partial.peek_so_far().unwrap().as_peek().is_none());
+    partial = partial.set(5u64)?;
+    let so_far = partial.peek_so_far().unwrap();
+    assert!(so_far.fields().is_none());
+    assert_eq!(so_far.as_peek().unwrap().get::<u64>()?, &5);
+    Ok(())
+}
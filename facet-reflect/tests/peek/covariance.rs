@@ -18,7 +18,7 @@
 //! the type's variance at runtime.
 
 use facet::{Facet, Variance};
-use facet_reflect::Peek;
+use facet_reflect::{CovariantPeek, Peek};
 
 #[derive(Debug, Facet)]
 struct Borrowed<'a> {
@@ -210,3 +210,20 @@ fn option_variance_propagation() {
     let peek = Peek::new(&opt_ptr);
     assert_eq!(peek.variance(), Variance::Invariant);
 }
+
+/// Interior-mutable containers hand out mutable access, so they're invariant
+#[test]
+fn interior_mutable_containers_are_not_covariant() {
+    use std::cell::RefCell;
+    use std::sync::{Mutex, RwLock};
+
+    let mutex: Mutex<&'static str> = Mutex::new("hello");
+    assert!(CovariantPeek::new(Peek::new(&mutex)).is_none());
+
+    let rwlock: RwLock<&'static str> = RwLock::new("hello");
+    assert!(CovariantPeek::new(Peek::new(&rwlock)).is_none());
+
+    let cell: RefCell<&'static str> = RefCell::new("hello");
+    assert!(CovariantPeek::new(Peek::new(&cell)).is_none());
+    assert!(Peek::new(&cell).try_shrink_lifetime().is_none());
+}
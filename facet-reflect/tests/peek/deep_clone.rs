@@ -0,0 +1,133 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex, RwLock};
+
+use facet::Facet;
+use facet_reflect::Peek;
+use facet_testhelpers::test;
+
+// None of these derive `Clone`, so they must be cloned structurally.
+#[derive(Facet, Debug, PartialEq)]
+struct Inventory {
+    owner: String,
+    items: Vec<Item>,
+    tags: BTreeSet<String>,
+    prices: HashMap<String, f64>,
+    coords: [i32; 3],
+    note: Option<Box<Note>>,
+    shared: Arc<Note>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Item {
+    name: String,
+    quantity: u32,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+struct Note(String);
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Shape {
+    Point,
+    Circle { radius: f64 },
+    Polygon(Vec<(i32, i32)>),
+}
+
+#[test]
+fn deep_clone_struct_graph() {
+    let original = Inventory {
+        owner: "ferris".to_string(),
+        items: vec![
+            Item {
+                name: "crab cake".to_string(),
+                quantity: 3,
+            },
+            Item {
+                name: "shell".to_string(),
+                quantity: 1,
+            },
+        ],
+        tags: ["beach", "rust"].into_iter().map(String::from).collect(),
+        prices: [("crab cake".to_string(), 4.5)].into_iter().collect(),
+        coords: [1, -2, 3],
+        note: Some(Box::new(Note("fragile".to_string()))),
+        shared: Arc::new(Note("shared".to_string())),
+    };
+    let cloned = Peek::new(&original).deep_clone().unwrap();
+    assert_eq!(cloned.shape(), Inventory::SHAPE);
+
+    let cloned: Inventory = cloned.materialize().unwrap();
+    assert_eq!(cloned, original);
+}
+
+#[test]
+fn deep_clone_enum_variants() {
+    for original in [
+        Shape::Point,
+        Shape::Circle { radius: 2.5 },
+        Shape::Polygon(vec![(0, 0), (1, 0), (0, 1)]),
+    ] {
+        let cloned: Shape = Peek::new(&original)
+            .deep_clone()
+            .unwrap()
+            .materialize()
+            .unwrap();
+        assert_eq!(cloned, original);
+    }
+}
+
+#[test]
+fn deep_clone_outlives_original() {
+    let cloned = {
+        let original = vec![Item {
+            name: "temporary".to_string(),
+            quantity: 7,
+        }];
+        Peek::new(&original).deep_clone().unwrap()
+    };
+    let cloned: Vec<Item> = cloned.materialize().unwrap();
+    assert_eq!(cloned[0].name, "temporary");
+    assert_eq!(cloned[0].quantity, 7);
+}
+
+#[test]
+fn deep_clone_none() {
+    let original: Option<Item> = None;
+    let cloned: Option<Item> = Peek::new(&original)
+        .deep_clone()
+        .unwrap()
+        .materialize()
+        .unwrap();
+    assert_eq!(cloned, None);
+}
+
+#[derive(Facet, Debug)]
+struct Guarded {
+    counter: Arc<Mutex<u32>>,
+    names: RwLock<Option<Vec<String>>>,
+}
+
+#[test]
+fn deep_clone_locks() {
+    let original = Guarded {
+        counter: Arc::new(Mutex::new(3)),
+        names: RwLock::new(Some(vec!["crab".to_string()])),
+    };
+    let cloned: Guarded = Peek::new(&original)
+        .deep_clone()
+        .unwrap()
+        .materialize()
+        .unwrap();
+    assert!(!Arc::ptr_eq(&cloned.counter, &original.counter));
+    assert_eq!(*cloned.counter.lock().unwrap(), 3);
+    assert_eq!(
+        *cloned.names.read().unwrap(),
+        Some(vec!["crab".to_string()])
+    );
+
+    // A value locked elsewhere can't be read
+    let _guard = original.counter.lock().unwrap();
+    assert!(Peek::new(&original).deep_clone().is_err());
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use facet::Facet;
+use facet_reflect::{EqOptions, Peek, ReflectError};
+
+#[derive(Debug, Facet, PartialEq, Clone)]
+struct Event {
+    #[facet(ignore_in_eq)]
+    id: u64,
+    name: String,
+    #[facet(ignore_in_eq)]
+    timestamp: u64,
+    tags: Vec<Tag>,
+}
+
+#[derive(Debug, Facet, PartialEq, Clone)]
+struct Tag {
+    label: String,
+    #[facet(ignore_in_eq)]
+    created_at: u64,
+}
+
+#[derive(Debug, Facet, PartialEq, Clone)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Message {
+    Ping,
+    Logged {
+        #[facet(ignore_in_eq)]
+        at: u64,
+        event: Event,
+    },
+}
+
+fn event(id: u64, name: &str, timestamp: u64) -> Event {
+    Event {
+        id,
+        name: name.to_string(),
+        timestamp,
+        tags: vec![Tag {
+            label: "urgent".to_string(),
+            created_at: timestamp,
+        }],
+    }
+}
+
+fn eq_with<'a, T: Facet<'a>>(a: &T, b: &T, options: &EqOptions) -> bool {
+    Peek::new(a).eq_with(&Peek::new(b), options).unwrap()
+}
+
+#[test]
+fn eq_with_skips_ignored_fields() {
+    let a = event(1, "deploy", 100);
+    let b = event(2, "deploy", 200);
+    assert_ne!(a, b);
+    assert!(eq_with(&a, &b, &EqOptions::new()));
+    assert!(!eq_with(&a, &b, &EqOptions::new().compare_ignored()));
+
+    let c = event(1, "rollback", 100);
+    assert!(!eq_with(&a, &c, &EqOptions::new()));
+}
+
+#[test]
+fn eq_with_skips_nested_ignored_fields() {
+    let a = Message::Logged {
+        at: 1,
+        event: event(1, "deploy", 100),
+    };
+    let b = Message::Logged {
+        at: 2,
+        event: event(2, "deploy", 200),
+    };
+    assert!(eq_with(&a, &b, &EqOptions::new()));
+    assert!(!eq_with(&a, &Message::Ping, &EqOptions::new()));
+
+    let a = HashMap::from([("x", Some(event(1, "deploy", 100)))]);
+    let b = HashMap::from([("x", Some(event(2, "deploy", 200)))]);
+    assert!(eq_with(&a, &b, &EqOptions::new()));
+    let c = HashMap::from([("y", Some(event(1, "deploy", 100)))]);
+    assert!(!eq_with(&a, &c, &EqOptions::new()));
+}
+
+#[test]
+fn eq_with_different_shapes() {
+    let a = 1u32;
+    let b = 1u64;
+    assert!(matches!(
+        Peek::new(&a).eq_with(&Peek::new(&b), &EqOptions::new()),
+        Err(ReflectError::WrongShape { .. })
+    ));
+}
+
+#[test]
+fn eq_with_through_locks() {
+    let a = Arc::new(Mutex::new(Some(Box::new(event(1, "deploy", 100)))));
+    let b = Arc::new(Mutex::new(Some(Box::new(event(2, "deploy", 200)))));
+    assert!(eq_with(&a, &b, &EqOptions::new()));
+    assert!(!eq_with(&a, &b, &EqOptions::new().compare_ignored()));
+    assert!(eq_with(&a, &a, &EqOptions::new()));
+
+    let c = Arc::new(Mutex::new(None));
+    assert!(!eq_with(&a, &c, &EqOptions::new()));
+}
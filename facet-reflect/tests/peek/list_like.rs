@@ -93,3 +93,45 @@ fn peek_list_like_slice() {
     // Test out of bounds
     assert!(peek_list.get(5).is_none());
 }
+
+#[test]
+fn peek_list_like_boxed_slice() {
+    let boxed: Box<[i32]> = vec![1, 2, 3].into_boxed_slice();
+    let peek_list = Peek::new(&boxed).into_list_like().unwrap();
+
+    assert_eq!(peek_list.len(), 3);
+    assert_eq!(peek_list.def().fixed_len(), None);
+    let items: Vec<i32> = peek_list
+        .iter()
+        .map(|item| *item.get::<i32>().unwrap())
+        .collect();
+    assert_eq!(items, [1, 2, 3]);
+}
+
+#[test]
+fn peek_list_like_arc_slice() {
+    let shared: std::sync::Arc<[&str]> = ["a", "b"].into();
+    let peek_list = Peek::new(&shared).into_list_like().unwrap();
+
+    assert_eq!(peek_list.len(), 2);
+    assert_eq!(*peek_list.get(1).unwrap().get::<&str>().unwrap(), "b");
+}
+
+#[test]
+fn peek_list_like_fixed_len() {
+    let array = [0u8; 16];
+    let vec = vec![0u8; 16];
+
+    assert_eq!(
+        Peek::new(&array)
+            .into_list_like()
+            .unwrap()
+            .def()
+            .fixed_len(),
+        Some(16)
+    );
+    assert_eq!(
+        Peek::new(&vec).into_list_like().unwrap().def().fixed_len(),
+        None
+    );
+}
@@ -1,15 +1,20 @@
 mod covariance;
+mod deep_clone;
 mod dst;
 mod enum_;
+mod eq_with;
 mod list;
 mod list_like;
 mod map;
 mod ndarray;
 mod option;
+mod path;
 mod pointer;
 mod reference;
 mod result;
 mod serialize;
 mod set;
+mod sorted_lookup;
 mod struct_;
+mod trait_object;
 mod value;
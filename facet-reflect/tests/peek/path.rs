@@ -0,0 +1,153 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use facet::Facet;
+use facet_reflect::{Peek, PeekPathErrorKind};
+use facet_testhelpers::test;
+
+#[derive(Facet)]
+struct Config {
+    name: String,
+    #[facet(rename = "serverList")]
+    servers: Vec<Server>,
+    limits: HashMap<String, u32>,
+    by_port: BTreeMap<u16, String>,
+    fallback: Option<Box<Server>>,
+    shared: Arc<Server>,
+    rule: (u8, Action),
+    on_error: Action,
+}
+
+#[derive(Facet)]
+struct Server {
+    host: String,
+    ports: Vec<u16>,
+}
+
+#[derive(Facet, Debug, PartialEq)]
+#[repr(u8)]
+enum Action {
+    Drop,
+    Redirect { status: u16 },
+}
+
+#[test]
+fn peek_at_resolves_paths() {
+    let config = Config {
+        name: "edge".into(),
+        servers: vec![
+            Server {
+                host: "a".into(),
+                ports: vec![80, 443],
+            },
+            Server {
+                host: "b".into(),
+                ports: vec![8080],
+            },
+        ],
+        limits: HashMap::from([("rps.max".to_string(), 100)]),
+        by_port: BTreeMap::from([(443, "https".to_string())]),
+        fallback: None,
+        shared: Arc::new(Server {
+            host: "shared".into(),
+            ports: vec![],
+        }),
+        rule: (7, Action::Redirect { status: 301 }),
+        on_error: Action::Drop,
+    };
+    let peek = Peek::new(&config);
+
+    assert_eq!(peek.at("name").unwrap().as_str(), Some("edge"));
+    assert_eq!(peek.at("serverList[1].host").unwrap().as_str(), Some("b"));
+    assert_eq!(
+        *peek
+            .at("serverList[0].ports[1]")
+            .unwrap()
+            .get::<u16>()
+            .unwrap(),
+        443
+    );
+    assert_eq!(
+        *peek
+            .at(r#"limits["rps.max"]"#)
+            .unwrap()
+            .get::<u32>()
+            .unwrap(),
+        100
+    );
+    assert_eq!(peek.at("by_port[443]").unwrap().as_str(), Some("https"));
+    assert_eq!(peek.at("shared.host").unwrap().as_str(), Some("shared"));
+    assert_eq!(*peek.at("rule[0]").unwrap().get::<u8>().unwrap(), 7);
+    assert_eq!(
+        *peek.at("rule[1].status").unwrap().get::<u16>().unwrap(),
+        301
+    );
+    assert_eq!(
+        peek.at("on_error").unwrap().get::<Action>().unwrap(),
+        &Action::Drop
+    );
+    assert!(peek.at("").unwrap().get::<Config>().is_ok());
+}
+
+#[test]
+fn peek_at_reports_failing_segment() {
+    let config = Config {
+        name: "edge".into(),
+        servers: vec![
+            Server {
+                host: "a".into(),
+                ports: vec![80, 443],
+            },
+            Server {
+                host: "b".into(),
+                ports: vec![8080],
+            },
+        ],
+        limits: HashMap::from([("rps.max".to_string(), 100)]),
+        by_port: BTreeMap::from([(443, "https".to_string())]),
+        fallback: None,
+        shared: Arc::new(Server {
+            host: "shared".into(),
+            ports: vec![],
+        }),
+        rule: (7, Action::Redirect { status: 301 }),
+        on_error: Action::Drop,
+    };
+    let peek = Peek::new(&config);
+
+    let err = peek.at("serverList[5].host").unwrap_err();
+    assert_eq!(err.segment, "[5]");
+    assert_eq!(err.offset, 10);
+    assert_eq!(
+        err.kind,
+        PeekPathErrorKind::IndexOutOfBounds { index: 5, len: 2 }
+    );
+
+    let err = peek.at("servers").unwrap_err();
+    assert_eq!(err.segment, "servers");
+    assert!(matches!(err.kind, PeekPathErrorKind::NoSuchField { .. }));
+
+    let err = peek.at("fallback.host").unwrap_err();
+    assert_eq!(err.segment, ".host");
+    assert!(matches!(err.kind, PeekPathErrorKind::NoneValue { .. }));
+
+    // Only the active variant's fields can be stepped into
+    let err = peek.at("on_error.status").unwrap_err();
+    assert_eq!(err.segment, ".status");
+    assert!(matches!(err.kind, PeekPathErrorKind::NoSuchField { .. }));
+
+    let err = peek.at("limits.missing").unwrap_err();
+    assert!(matches!(err.kind, PeekPathErrorKind::NoSuchKey { .. }));
+
+    let err = peek.at("name.len").unwrap_err();
+    assert!(matches!(err.kind, PeekPathErrorKind::NotTraversable { .. }));
+    assert_eq!(
+        err.to_string(),
+        "at `.len` (offset 4): `String` has no fields, items or entries"
+    );
+
+    let err = peek.at("serverList[0").unwrap_err();
+    assert!(matches!(err.kind, PeekPathErrorKind::Syntax { .. }));
+    let err = peek.at("name..x").unwrap_err();
+    assert!(matches!(err.kind, PeekPathErrorKind::Syntax { .. }));
+}
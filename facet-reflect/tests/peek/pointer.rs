@@ -1,8 +1,9 @@
 use facet::Facet;
 use facet_reflect::Peek;
 use facet_testhelpers::test;
+use std::cell::RefCell;
 use std::rc::Rc;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, RwLock};
 
 #[test]
 fn test_peek_arc() {
@@ -107,3 +108,36 @@ fn test_peek_rc_with_string() {
 
     assert_eq!(def.pointee(), Some(String::SHAPE));
 }
+
+#[test]
+fn test_peek_with_locked() {
+    let mutex = Mutex::new(1u32);
+    let rwlock = RwLock::new(2u32);
+    let cell = RefCell::new(3u32);
+
+    let read = |peek: Peek<'_, '_>| {
+        peek.into_pointer()
+            .unwrap()
+            .with_locked(|inner| *inner.get::<u32>().unwrap())
+    };
+    assert_eq!(read(Peek::new(&mutex)), Some(1));
+    assert_eq!(read(Peek::new(&rwlock)), Some(2));
+    assert_eq!(read(Peek::new(&cell)), Some(3));
+
+    // The lock is released once the closure returns
+    assert!(mutex.try_lock().is_ok());
+    assert!(cell.try_borrow_mut().is_ok());
+}
+
+#[test]
+fn test_peek_with_locked_when_held() {
+    let mutex = Mutex::new(1u32);
+    let _guard = mutex.lock().unwrap();
+    let pointer = Peek::new(&mutex).into_pointer().unwrap();
+    assert!(pointer.with_locked(|_| ()).is_none());
+
+    // Pointers without a lock have nothing to lock
+    let arc = Arc::new(1u32);
+    let pointer = Peek::new(&arc).into_pointer().unwrap();
+    assert!(pointer.with_locked(|_| ()).is_none());
+}
@@ -0,0 +1,136 @@
+use facet::Facet;
+use facet_reflect::{Peek, ReflectError};
+use facet_testhelpers::test;
+
+#[derive(Facet, Debug, PartialEq)]
+struct Sensor {
+    id: u32,
+    label: String,
+}
+
+#[derive(Facet)]
+struct Site {
+    #[facet(sorted_by = "id")]
+    sensors: Vec<Sensor>,
+    tags: Vec<String>,
+}
+
+#[test]
+fn binary_search_by_item_field() {
+    let site = Site {
+        sensors: [2, 5, 9, 14, 30]
+            .into_iter()
+            .map(|id| Sensor {
+                id,
+                label: format!("s{id}"),
+            })
+            .collect(),
+        tags: vec![],
+    };
+    let sensors = Peek::new(&site.sensors).into_list_like().unwrap();
+
+    assert_eq!(
+        sensors
+            .binary_search_by_key(Some("id"), &Peek::new(&14u32))
+            .unwrap(),
+        Ok(3)
+    );
+    assert_eq!(
+        sensors
+            .binary_search_by_key(Some("id"), &Peek::new(&1u32))
+            .unwrap(),
+        Err(0)
+    );
+    assert_eq!(
+        sensors
+            .binary_search_by_key(Some("id"), &Peek::new(&31u32))
+            .unwrap(),
+        Err(5)
+    );
+}
+
+#[test]
+fn binary_search_scalars_and_arrays() {
+    let sorted = [1.5f64, 2.0, 8.25];
+    let list = Peek::new(&sorted).into_list_like().unwrap();
+    let found = list.find_by_key(None, &Peek::new(&8.25f64)).unwrap();
+    assert_eq!(found.unwrap().get::<f64>().unwrap(), &8.25);
+
+    let empty: Vec<u8> = Vec::new();
+    let list = Peek::new(&empty).into_list_like().unwrap();
+    assert_eq!(
+        list.binary_search_by_key(None, &Peek::new(&3u8)).unwrap(),
+        Err(0)
+    );
+}
+
+#[test]
+fn binary_search_errors() {
+    let site = Site {
+        sensors: [2, 5, 9, 14, 30]
+            .into_iter()
+            .map(|id| Sensor {
+                id,
+                label: format!("s{id}"),
+            })
+            .collect(),
+        tags: vec![],
+    };
+    let sensors = Peek::new(&site.sensors).into_list_like().unwrap();
+
+    let err = sensors
+        .binary_search_by_key(Some("missing"), &Peek::new(&1u32))
+        .unwrap_err();
+    assert!(matches!(err, ReflectError::FieldError { .. }), "{err}");
+
+    let err = sensors
+        .binary_search_by_key(Some("id"), &Peek::new(&1u64))
+        .unwrap_err();
+    assert!(matches!(err, ReflectError::WrongShape { .. }), "{err}");
+
+    let nan = [f64::NAN];
+    let list = Peek::new(&nan).into_list_like().unwrap();
+    let err = list
+        .binary_search_by_key(None, &Peek::new(&1.0f64))
+        .unwrap_err();
+    assert!(matches!(err, ReflectError::OperationFailed { .. }), "{err}");
+}
+
+#[test]
+fn struct_lookup_uses_sorted_by_annotation() {
+    let site = Site {
+        sensors: [2, 5, 9, 14, 30]
+            .into_iter()
+            .map(|id| Sensor {
+                id,
+                label: format!("s{id}"),
+            })
+            .collect(),
+        tags: vec!["roof".to_string(), "north".to_string()],
+    };
+    let peek = Peek::new(&site).into_struct().unwrap();
+
+    let found = peek.find_by_key("sensors", &Peek::new(&9u32)).unwrap();
+    assert_eq!(found.unwrap().get::<Sensor>().unwrap().label, "s9");
+    assert!(
+        peek.find_by_key("sensors", &Peek::new(&10u32))
+            .unwrap()
+            .is_none()
+    );
+
+    // Unannotated lists are scanned, comparing whole items
+    let north = "north".to_string();
+    let found = peek.find_by_key("tags", &Peek::new(&north)).unwrap();
+    assert_eq!(found.unwrap().get::<String>().unwrap(), "north");
+}
+
+#[test]
+fn sorted_by_is_reflected() {
+    use facet::{StructType, Type, UserType};
+
+    let Type::User(UserType::Struct(StructType { fields, .. })) = Site::SHAPE.ty else {
+        panic!("expected a struct");
+    };
+    assert_eq!(fields[0].sorted_by(), Some("id"));
+    assert_eq!(fields[1].sorted_by(), None);
+}
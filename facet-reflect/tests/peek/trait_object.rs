@@ -0,0 +1,100 @@
+use std::any::Any;
+
+use facet::{Def, Facet, impl_facet_for_dyn, register_shape};
+use facet_reflect::Peek;
+use facet_testhelpers::test;
+
+trait Plugin: Any {
+    fn name(&self) -> &str;
+}
+
+impl_facet_for_dyn!(Plugin);
+
+#[derive(Facet)]
+struct Logger {
+    level: u8,
+}
+
+impl Plugin for Logger {
+    fn name(&self) -> &str {
+        "logger"
+    }
+}
+
+#[derive(Facet)]
+struct Cache {
+    capacity: usize,
+    path: String,
+}
+
+impl Plugin for Cache {
+    fn name(&self) -> &str {
+        "cache"
+    }
+}
+
+/// Implements `Plugin` but never registers its shape
+struct Unregistered;
+
+impl Plugin for Unregistered {
+    fn name(&self) -> &str {
+        "unregistered"
+    }
+}
+
+#[derive(Facet)]
+struct Host {
+    plugins: Vec<Box<dyn Plugin>>,
+}
+
+fn register() {
+    register_shape(Logger::SHAPE);
+    register_shape(Cache::SHAPE);
+}
+
+#[test]
+fn peek_through_boxed_trait_object() {
+    register();
+
+    let host = Host {
+        plugins: vec![
+            Box::new(Logger { level: 3 }),
+            Box::new(Cache {
+                capacity: 64,
+                path: "/tmp/cache".to_string(),
+            }),
+        ],
+    };
+    assert_eq!(host.plugins[1].name(), "cache");
+
+    let plugins = Peek::new(&host)
+        .into_struct()
+        .unwrap()
+        .field_by_name("plugins")
+        .unwrap()
+        .into_list()
+        .unwrap();
+
+    let logger = plugins.get(0).unwrap().into_pointer().unwrap();
+    let logger = logger.borrow_inner().unwrap();
+    assert!(logger.shape().is_shape(Logger::SHAPE));
+    assert_eq!(logger.get::<Logger>().unwrap().level, 3);
+
+    let cache = plugins.get(1).unwrap().into_pointer().unwrap();
+    let cache = cache.borrow_inner().unwrap().into_struct().unwrap();
+    let path = cache.field_by_name("path").unwrap();
+    assert_eq!(path.get::<String>().unwrap(), "/tmp/cache");
+}
+
+#[test]
+fn unregistered_trait_object_stays_opaque() {
+    register();
+
+    let boxed: Box<dyn Plugin> = Box::new(Unregistered);
+    assert_eq!(boxed.name(), "unregistered");
+
+    let pointer = Peek::new(&boxed).into_pointer().unwrap();
+    let inner = pointer.borrow_inner().unwrap();
+    assert!(matches!(inner.shape().def, Def::TraitObject(_)));
+    assert_eq!(inner.shape().to_string(), "dyn Plugin");
+}
@@ -57,3 +57,50 @@ fn test_peek_as_str_owned_string() {
     let peek = Peek::new(&s);
     assert_eq!(peek.as_str(), Some("owned string"));
 }
+
+#[test]
+fn test_peek_structural_hash_through_mutex() {
+    use std::sync::{Arc, Mutex};
+
+    fn hash(value: &Arc<Mutex<Option<Box<u32>>>>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        Peek::new(value).structural_hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let a = Arc::new(Mutex::new(Some(Box::new(1))));
+    let b = Arc::new(Mutex::new(Some(Box::new(1))));
+    let c = Arc::new(Mutex::new(Some(Box::new(2))));
+    assert_eq!(hash(&a), hash(&b));
+    assert_ne!(hash(&a), hash(&c));
+}
+
+#[test]
+fn test_peek_get_special_cases_concrete_types() {
+    use facet::Facet;
+    use facet_reflect::HasFields;
+    use std::net::Ipv4Addr;
+
+    #[derive(Facet)]
+    struct Event {
+        name: String,
+        at: Ipv4Addr,
+        count: u32,
+    }
+
+    let event = Event {
+        name: "tick".to_string(),
+        at: Ipv4Addr::LOCALHOST,
+        count: 2,
+    };
+
+    let mut seen = Vec::new();
+    for (field, value) in Peek::new(&event).into_struct().unwrap().fields() {
+        if let Ok(addr) = value.get::<Ipv4Addr>() {
+            seen.push(format!("{}={addr}", field.name));
+        } else {
+            seen.push(field.name.to_string());
+        }
+    }
+    assert_eq!(seen, ["name", "at=127.0.0.1", "count"]);
+}
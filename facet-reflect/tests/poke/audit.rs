@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use facet::Facet;
+use facet_reflect::{AuditError, AuditRecord, Audited, Change, ReflectError};
+
+#[derive(Debug, Facet, PartialEq)]
+#[facet(pod)]
+struct Settings {
+    name: String,
+    limits: Limits,
+    admins: Vec<String>,
+    quotas: HashMap<String, u32>,
+    backup: Option<Box<Limits>>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[facet(pod)]
+struct Limits {
+    max_users: u32,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Sealed {
+    secret: u32,
+}
+
+#[test]
+fn records_path_old_new_timestamp_and_actor() {
+    let mut value = Settings {
+        name: "prod".into(),
+        limits: Limits { max_users: 10 },
+        admins: vec![],
+        quotas: HashMap::new(),
+        backup: None,
+    };
+    let before = SystemTime::now();
+    let mut audited = Audited::new(&mut value, Vec::<AuditRecord>::new()).with_actor("alice");
+    audited.set("limits.max_users", 25u32).unwrap();
+    audited.set_actor(None);
+    audited.set("name", String::from("staging")).unwrap();
+    let log = audited.into_sink();
+
+    assert_eq!(log.len(), 2);
+    assert_eq!(log[0].path, "limits.max_users");
+    assert_eq!((log[0].old.as_str(), log[0].new.as_str()), ("10", "25"));
+    assert_eq!(log[0].actor.as_deref(), Some("alice"));
+    assert!(log[0].timestamp >= before && log[0].timestamp <= SystemTime::now());
+    assert_eq!(log[1].path, "name");
+    assert_eq!(
+        (log[1].old.as_str(), log[1].new.as_str()),
+        ("\"prod\"", "\"staging\"")
+    );
+    assert_eq!(log[1].actor, None);
+
+    assert_eq!(value.limits.max_users, 25);
+    assert_eq!(value.name, "staging");
+}
+
+#[test]
+fn lists_maps_and_boxes_are_reachable() {
+    let mut value = Settings {
+        name: "prod".into(),
+        limits: Limits { max_users: 10 },
+        admins: vec!["root".into()],
+        quotas: HashMap::from([("disk".into(), 100)]),
+        backup: Some(Box::new(Limits { max_users: 1 })),
+    };
+    let mut audited = Audited::new(&mut value, Vec::<AuditRecord>::new());
+    audited.set("admins[0]", String::from("ops")).unwrap();
+    audited.set("quotas[disk]", 200u32).unwrap();
+    audited.set("backup.max_users", 2u32).unwrap();
+    let paths: Vec<_> = audited.into_sink().into_iter().map(|r| r.path).collect();
+
+    assert_eq!(paths, ["admins[0]", "quotas[disk]", "backup.max_users"]);
+    assert_eq!(value.admins, ["ops"]);
+    assert_eq!(value.quotas["disk"], 200);
+    assert_eq!(value.backup.unwrap().max_users, 2);
+}
+
+#[test]
+fn unchanged_values_are_not_recorded() {
+    let mut value = Settings {
+        name: "prod".into(),
+        limits: Limits { max_users: 10 },
+        admins: vec![],
+        quotas: HashMap::new(),
+        backup: None,
+    };
+    let mut audited = Audited::new(&mut value, Vec::<AuditRecord>::new());
+    audited.set("limits.max_users", 10u32).unwrap();
+    assert!(audited.sink().is_empty());
+}
+
+#[test]
+fn closures_see_the_values_themselves() {
+    let mut value = Settings {
+        name: "prod".into(),
+        limits: Limits { max_users: 10 },
+        admins: vec![],
+        quotas: HashMap::new(),
+        backup: None,
+    };
+    let mut seen = Vec::new();
+    let mut audited = Audited::new(&mut value, |change: &Change<'_, '_>| {
+        seen.push((
+            *change.old.get::<u32>().unwrap(),
+            *change.new.get::<u32>().unwrap(),
+        ));
+    });
+    audited.set("limits.max_users", 11u32).unwrap();
+    audited.set("limits.max_users", 12u32).unwrap();
+    drop(audited);
+
+    assert_eq!(seen, [(10, 11), (11, 12)]);
+}
+
+#[test]
+fn failed_writes_leave_the_value_and_log_alone() {
+    let mut value = Settings {
+        name: "prod".into(),
+        limits: Limits { max_users: 10 },
+        admins: vec![],
+        quotas: HashMap::new(),
+        backup: None,
+    };
+    let mut audited = Audited::new(&mut value, Vec::<AuditRecord>::new());
+
+    let err = audited.set("limits.max_users", 25u64).unwrap_err();
+    assert!(matches!(
+        err,
+        AuditError::Reflect(ReflectError::WrongShape { .. })
+    ));
+    let err = audited.set("limits.nope", 25u32).unwrap_err();
+    assert!(matches!(err, AuditError::Path(ref e) if e.segment == ".nope"));
+    assert!(audited.sink().is_empty());
+    assert_eq!(value.limits, Limits { max_users: 10 });
+}
+
+#[test]
+fn fields_of_non_pod_structs_are_refused() {
+    let mut value = Sealed { secret: 1 };
+    let mut audited = Audited::new(&mut value, Vec::<AuditRecord>::new());
+    let err = audited.set("secret", 2u32).unwrap_err();
+    assert!(matches!(
+        err,
+        AuditError::Reflect(ReflectError::NotPod { .. })
+    ));
+
+    // Replacing the whole value is always allowed
+    audited.set("", Sealed { secret: 2 }).unwrap();
+    assert_eq!(audited.sink().len(), 1);
+    drop(audited);
+    assert_eq!(value.secret, 2);
+}
+
+#[test]
+fn shared_pointers_are_not_written_through() {
+    #[derive(Facet)]
+    #[facet(pod)]
+    struct Shared {
+        limits: std::sync::Arc<Limits>,
+    }
+
+    let mut value = Shared {
+        limits: std::sync::Arc::new(Limits { max_users: 1 }),
+    };
+    let mut audited = Audited::new(&mut value, Vec::<AuditRecord>::new());
+    assert!(matches!(
+        audited.set("limits.max_users", 2u32),
+        Err(AuditError::Path(_))
+    ));
+}
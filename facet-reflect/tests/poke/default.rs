@@ -0,0 +1,92 @@
+use core::mem::MaybeUninit;
+
+use facet::{Facet, PtrUninit};
+use facet_reflect::{Poke, ReflectError};
+
+#[derive(Facet, Debug, PartialEq)]
+struct Limits {
+    max: u32,
+}
+
+/// Deliberately not `Default`
+#[derive(Facet, Debug, PartialEq)]
+struct Settings {
+    name: String,
+    limits: Limits,
+    tags: Vec<String>,
+    #[facet(default = 7)]
+    level: u8,
+}
+
+#[test]
+fn default_of_builds_owned_value() {
+    let value = Poke::default_of(Settings::SHAPE).unwrap();
+    assert_eq!(value.shape(), Settings::SHAPE);
+    assert_eq!(
+        value.materialize::<Settings>().unwrap(),
+        Settings {
+            name: String::new(),
+            limits: Limits { max: 0 },
+            tags: Vec::new(),
+            level: 7,
+        }
+    );
+}
+
+#[test]
+fn default_in_place_fills_slot() {
+    let mut slot = MaybeUninit::<Settings>::uninit();
+    unsafe {
+        Poke::default_in_place(Settings::SHAPE, PtrUninit::from_maybe_uninit(&mut slot)).unwrap();
+    }
+    let settings = unsafe { slot.assume_init() };
+    assert_eq!(
+        settings,
+        Settings {
+            name: String::new(),
+            limits: Limits { max: 0 },
+            tags: Vec::new(),
+            level: 7,
+        }
+    );
+}
+
+#[test]
+fn reset_to_default_replaces_value() {
+    let mut settings = Settings {
+        name: "prod".to_string(),
+        limits: Limits { max: 10 },
+        tags: vec!["a".to_string()],
+        level: 1,
+    };
+    Poke::new(&mut settings).reset_to_default().unwrap();
+    assert_eq!(
+        settings,
+        Settings {
+            name: String::new(),
+            limits: Limits { max: 0 },
+            tags: Vec::new(),
+            level: 7,
+        }
+    );
+}
+
+#[test]
+fn reset_to_default_leaves_value_on_error() {
+    #[derive(Facet, Debug, PartialEq)]
+    struct Wrapper {
+        #[facet(opaque)]
+        inner: NoDefaultFacet,
+    }
+
+    /// Opaque and not `Default`, so there is nothing to build
+    #[derive(Debug, PartialEq)]
+    struct NoDefaultFacet(u32);
+
+    let mut value = Wrapper {
+        inner: NoDefaultFacet(5),
+    };
+    let err = Poke::new(&mut value).reset_to_default().unwrap_err();
+    assert!(matches!(err, ReflectError::OperationFailed { .. }), "{err}");
+    assert_eq!(value.inner, NoDefaultFacet(5));
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_reflect::{DirtyPaths, DirtyTracked};
+
+#[derive(Debug, Facet, PartialEq)]
+#[facet(pod)]
+struct Document {
+    title: String,
+    meta: Meta,
+    sections: Vec<String>,
+    labels: HashMap<String, String>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[facet(pod)]
+struct Meta {
+    author: String,
+    revision: u32,
+}
+
+fn changed(tracked: &DirtyTracked<'_, '_>) -> Vec<(String, String)> {
+    tracked
+        .sink()
+        .subtrees(tracked.peek())
+        .map(|(path, value)| (path.to_string(), format!("{:?}", value.unwrap())))
+        .collect()
+}
+
+#[test]
+fn records_changed_paths() {
+    let mut value = Document {
+        title: "Draft".into(),
+        meta: Meta {
+            author: "ana".into(),
+            revision: 1,
+        },
+        sections: vec!["intro".into(), "body".into()],
+        labels: HashMap::from([("status".into(), "wip".into())]),
+    };
+    let mut tracked = DirtyTracked::new(&mut value, DirtyPaths::new());
+    assert!(tracked.sink().is_empty());
+
+    tracked.set("meta.revision", 2u32).unwrap();
+    tracked.set("sections[1]", String::from("results")).unwrap();
+    tracked
+        .set("labels[\"status\"]", String::from("done"))
+        .unwrap();
+
+    assert_eq!(
+        tracked.sink().iter().collect::<Vec<_>>(),
+        ["labels[\"status\"]", "meta.revision", "sections[1]"]
+    );
+    assert!(tracked.sink().is_dirty("meta.revision"));
+    assert!(!tracked.sink().is_dirty("meta"));
+    assert!(!tracked.sink().is_dirty("meta.author"));
+    assert!(!tracked.sink().is_dirty("sections[10]"));
+    assert_eq!(
+        changed(&tracked),
+        [
+            ("labels[\"status\"]".to_string(), "\"done\"".to_string()),
+            ("meta.revision".to_string(), "2".to_string()),
+            ("sections[1]".to_string(), "\"results\"".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn writing_the_same_value_is_not_a_change() {
+    let mut value = Document {
+        title: "Draft".into(),
+        meta: Meta {
+            author: "ana".into(),
+            revision: 1,
+        },
+        sections: vec![],
+        labels: HashMap::new(),
+    };
+    let mut tracked = DirtyTracked::new(&mut value, DirtyPaths::new());
+    tracked.set("title", String::from("Draft")).unwrap();
+    assert!(tracked.sink().is_empty());
+}
+
+#[test]
+fn keeps_only_outermost_paths() {
+    let mut value = Document {
+        title: "Draft".into(),
+        meta: Meta {
+            author: "ana".into(),
+            revision: 1,
+        },
+        sections: vec![],
+        labels: HashMap::new(),
+    };
+    let mut tracked = DirtyTracked::new(&mut value, DirtyPaths::new());
+    tracked.set("meta.revision", 2u32).unwrap();
+    tracked.set("meta.author", String::from("bo")).unwrap();
+    assert_eq!(tracked.sink().len(), 2);
+
+    tracked
+        .set(
+            "meta",
+            Meta {
+                author: "cy".into(),
+                revision: 3,
+            },
+        )
+        .unwrap();
+    assert_eq!(tracked.sink().iter().collect::<Vec<_>>(), ["meta"]);
+    assert!(tracked.sink().is_dirty("meta.author"));
+
+    // Already covered by `meta`
+    tracked.set("meta.revision", 4u32).unwrap();
+    assert_eq!(tracked.sink().iter().collect::<Vec<_>>(), ["meta"]);
+
+    // `metadata` is not below `meta`
+    let mut paths = DirtyPaths::new();
+    paths.mark("meta");
+    assert!(!paths.is_dirty("metadata"));
+    paths.mark("");
+    assert_eq!(paths.iter().collect::<Vec<_>>(), [""]);
+    assert!(paths.is_dirty("anything.at[all]"));
+}
+
+#[test]
+fn clearing_starts_a_new_batch() {
+    let mut value = Document {
+        title: "Draft".into(),
+        meta: Meta {
+            author: "ana".into(),
+            revision: 1,
+        },
+        sections: vec!["intro".into(), "body".into()],
+        labels: HashMap::new(),
+    };
+    let mut tracked = DirtyTracked::new(&mut value, DirtyPaths::new());
+    tracked.set("title", String::from("Final")).unwrap();
+    tracked.sink_mut().clear();
+    tracked.set("sections[0]", String::from("summary")).unwrap();
+    assert_eq!(
+        changed(&tracked),
+        [("sections[0]".to_string(), "\"summary\"".to_string())]
+    );
+
+    let paths = tracked.into_sink();
+    assert_eq!(paths.len(), 1);
+    assert_eq!(value.sections[0], "summary");
+}
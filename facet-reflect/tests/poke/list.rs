@@ -0,0 +1,99 @@
+use facet::Facet;
+use facet_reflect::{Partial, Poke, ReflectError};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn poke_list_push() {
+    let mut values: Vec<u32> = vec![1, 2];
+    let mut list = Poke::new(&mut values).into_list().unwrap();
+
+    list.push(3u32).unwrap();
+    assert_eq!(list.len(), 3);
+    assert!(matches!(
+        list.push(4u64),
+        Err(ReflectError::WrongShape { .. })
+    ));
+
+    assert_eq!(values, [1, 2, 3]);
+}
+
+#[test]
+fn poke_list_reserve() {
+    let mut values: Vec<String> = Vec::new();
+    let mut list = Poke::new(&mut values).into_list().unwrap();
+
+    list.reserve(16).unwrap();
+    assert!(list.capacity().unwrap() >= 16);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn poke_list_push_partial_items() {
+    let mut points: Vec<Point> = Vec::new();
+    let mut list = Poke::new(&mut points).into_list().unwrap();
+
+    // Build items knowing only the list's item shape, like a deserializer would
+    let item_shape = list.def().t();
+    for i in 0..3 {
+        let item = Partial::alloc_shape(item_shape)
+            .unwrap()
+            .set_field("x", i)
+            .unwrap()
+            .set_field("y", -i)
+            .unwrap()
+            .build()
+            .unwrap();
+        list.push_value(item).unwrap();
+    }
+
+    let wrong = Partial::alloc::<u8>()
+        .unwrap()
+        .set(1u8)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(matches!(
+        list.push_value(wrong),
+        Err(ReflectError::WrongShape { .. })
+    ));
+
+    assert_eq!(
+        points,
+        [
+            Point { x: 0, y: 0 },
+            Point { x: 1, y: -1 },
+            Point { x: 2, y: -2 },
+        ]
+    );
+}
+
+#[test]
+fn poke_list_items() {
+    let mut values: Vec<u32> = vec![1, 2, 3];
+    let mut list = Poke::new(&mut values).into_list().unwrap();
+
+    list.item(1).unwrap().set(20u32).unwrap();
+    assert!(list.item(3).is_none());
+    assert!(list.as_mut_ptr().is_some());
+
+    assert_eq!(values, [1, 20, 3]);
+}
+
+#[test]
+fn poke_not_a_list() {
+    let mut value = 5u32;
+    let poke = Poke::new(&mut value);
+    assert!(!poke.is_list());
+    assert!(matches!(
+        poke.into_list(),
+        Err(ReflectError::WasNotA {
+            expected: "list",
+            ..
+        })
+    ));
+}
@@ -0,0 +1,93 @@
+use std::collections::{BTreeMap, HashMap};
+
+use facet::Facet;
+use facet_reflect::{Partial, Poke, ReflectError};
+
+#[derive(Debug, Facet, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
+struct Coord {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn poke_map_insert() {
+    let mut scores: HashMap<String, u32> = HashMap::new();
+    let mut map = Poke::new(&mut scores).into_map().unwrap();
+
+    map.insert("alice".to_string(), 3u32).unwrap();
+    map.insert("alice".to_string(), 4u32).unwrap();
+    assert_eq!(map.len(), 1);
+    assert!(map.contains_key(&"alice".to_string()).unwrap());
+    assert!(!map.contains_key(&"bob".to_string()).unwrap());
+
+    assert!(matches!(
+        map.insert("bob", 1u32),
+        Err(ReflectError::WrongShape { .. })
+    ));
+    assert!(matches!(
+        map.insert("bob".to_string(), 1u64),
+        Err(ReflectError::WrongShape { .. })
+    ));
+
+    assert_eq!(scores, HashMap::from([("alice".to_string(), 4)]));
+}
+
+#[test]
+fn poke_map_insert_partial_entries() {
+    // Non-string keys, built knowing only the map's key and value shapes
+    let mut tiles: BTreeMap<Coord, String> = BTreeMap::new();
+    let mut map = Poke::new(&mut tiles).into_map().unwrap();
+
+    let (key_shape, value_shape) = (map.def().k(), map.def().v());
+    for i in 0..3 {
+        let key = Partial::alloc_shape(key_shape)
+            .unwrap()
+            .set_field("x", i)
+            .unwrap()
+            .set_field("y", i * 2)
+            .unwrap()
+            .build()
+            .unwrap();
+        let value = Partial::alloc_shape(value_shape)
+            .unwrap()
+            .set(format!("tile {i}"))
+            .unwrap()
+            .build()
+            .unwrap();
+        map.insert_values(key, value).unwrap();
+    }
+
+    let key = Partial::alloc::<u8>()
+        .unwrap()
+        .set(1u8)
+        .unwrap()
+        .build()
+        .unwrap();
+    let value = Partial::alloc_shape(value_shape)
+        .unwrap()
+        .set(String::new())
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(matches!(
+        map.insert_values(key, value),
+        Err(ReflectError::WrongShape { .. })
+    ));
+
+    assert_eq!(tiles.len(), 3);
+    assert_eq!(tiles[&Coord { x: 2, y: 4 }], "tile 2");
+}
+
+#[test]
+fn poke_not_a_map() {
+    let mut values = vec![1u32];
+    let poke = Poke::new(&mut values);
+    assert!(!poke.is_map());
+    assert!(matches!(
+        poke.into_map(),
+        Err(ReflectError::WasNotA {
+            expected: "map",
+            ..
+        })
+    ));
+}
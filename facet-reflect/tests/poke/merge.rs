@@ -0,0 +1,284 @@
+use std::collections::{BTreeSet, HashMap};
+
+use facet::Facet;
+use facet_reflect::{MergeRule, MergeStrategy, Peek, ReflectError, merge};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Config {
+    name: String,
+    port: u16,
+    tls: Option<Tls>,
+    hosts: Vec<String>,
+    features: BTreeSet<String>,
+    upstreams: HashMap<String, Upstream>,
+    mode: Mode,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Tls {
+    cert: String,
+    key: Option<String>,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Upstream {
+    url: String,
+    weight: u32,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Mode {
+    Dev { verbose: bool, seed: u64 },
+    Prod { replicas: u32 },
+}
+
+#[test]
+fn merge_default_strategy() {
+    let mut config = Config {
+        name: "base".to_string(),
+        port: 80,
+        tls: Some(Tls {
+            cert: "base.pem".to_string(),
+            key: Some("base.key".to_string()),
+        }),
+        hosts: vec!["a".to_string()],
+        features: ["x".to_string()].into_iter().collect(),
+        upstreams: [(
+            "primary".to_string(),
+            Upstream {
+                url: "http://primary".to_string(),
+                weight: 1,
+            },
+        )]
+        .into_iter()
+        .collect(),
+        mode: Mode::Dev {
+            verbose: false,
+            seed: 7,
+        },
+    };
+    let overlay = Config {
+        name: "overlay".to_string(),
+        port: 8080,
+        tls: Some(Tls {
+            cert: "overlay.pem".to_string(),
+            key: None,
+        }),
+        hosts: vec!["b".to_string()],
+        features: ["y".to_string()].into_iter().collect(),
+        upstreams: [(
+            "secondary".to_string(),
+            Upstream {
+                url: "http://secondary".to_string(),
+                weight: 2,
+            },
+        )]
+        .into_iter()
+        .collect(),
+        mode: Mode::Dev {
+            verbose: true,
+            seed: 7,
+        },
+    };
+    merge(&mut config, Peek::new(&overlay), &MergeStrategy::new()).unwrap();
+
+    assert_eq!(config.name, "overlay");
+    assert_eq!(config.port, 8080);
+    // `Some` merges into `Some`, and `None` in the overlay keeps the base value
+    assert_eq!(
+        config.tls,
+        Some(Tls {
+            cert: "overlay.pem".to_string(),
+            key: Some("base.key".to_string()),
+        })
+    );
+    // lists and sets are replaced by default
+    assert_eq!(config.hosts, vec!["b".to_string()]);
+    assert_eq!(config.features, overlay.features);
+    // maps are unioned
+    assert_eq!(config.upstreams.len(), 2);
+    assert_eq!(config.upstreams["primary"].weight, 1);
+    assert_eq!(config.upstreams["secondary"].weight, 2);
+    assert_eq!(
+        config.mode,
+        Mode::Dev {
+            verbose: true,
+            seed: 7
+        }
+    );
+}
+
+#[test]
+fn merge_append_and_none_overrides() {
+    let mut config = Config {
+        name: "base".to_string(),
+        port: 80,
+        tls: Some(Tls {
+            cert: "base.pem".to_string(),
+            key: Some("base.key".to_string()),
+        }),
+        hosts: vec!["a".to_string()],
+        features: ["x".to_string()].into_iter().collect(),
+        upstreams: HashMap::new(),
+        mode: Mode::Prod { replicas: 1 },
+    };
+    let overlay = Config {
+        name: "overlay".to_string(),
+        port: 8080,
+        tls: Some(Tls {
+            cert: "overlay.pem".to_string(),
+            key: None,
+        }),
+        hosts: vec!["b".to_string()],
+        features: ["y".to_string()].into_iter().collect(),
+        upstreams: HashMap::new(),
+        mode: Mode::Prod { replicas: 1 },
+    };
+    let strategy = MergeStrategy::new()
+        .with_lists(MergeRule::Append)
+        .with_none_overrides(true);
+    merge(&mut config, Peek::new(&overlay), &strategy).unwrap();
+
+    assert_eq!(config.hosts, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(
+        config.features.iter().collect::<Vec<_>>(),
+        vec![&"x".to_string(), &"y".to_string()]
+    );
+    assert_eq!(config.tls.unwrap().key, None);
+}
+
+#[test]
+fn merge_map_values_recursively() {
+    let mut config = Config {
+        name: "base".to_string(),
+        port: 80,
+        tls: None,
+        hosts: vec![],
+        features: BTreeSet::new(),
+        upstreams: [(
+            "primary".to_string(),
+            Upstream {
+                url: "http://primary".to_string(),
+                weight: 1,
+            },
+        )]
+        .into_iter()
+        .collect(),
+        mode: Mode::Prod { replicas: 1 },
+    };
+    let overlay = Config {
+        name: "base".to_string(),
+        port: 80,
+        tls: None,
+        hosts: vec![],
+        features: BTreeSet::new(),
+        upstreams: [(
+            "primary".to_string(),
+            Upstream {
+                url: "http://primary".to_string(),
+                weight: 10,
+            },
+        )]
+        .into_iter()
+        .collect(),
+        mode: Mode::Prod { replicas: 1 },
+    };
+    merge(&mut config, Peek::new(&overlay), &MergeStrategy::new()).unwrap();
+    assert_eq!(config.upstreams["primary"].weight, 10);
+    assert_eq!(config.upstreams["primary"].url, "http://primary");
+}
+
+#[test]
+fn merge_rules_per_path() {
+    let mut config = Config {
+        name: "base".to_string(),
+        port: 80,
+        tls: None,
+        hosts: vec!["a".to_string()],
+        features: BTreeSet::new(),
+        upstreams: [(
+            "primary".to_string(),
+            Upstream {
+                url: "http://primary".to_string(),
+                weight: 1,
+            },
+        )]
+        .into_iter()
+        .collect(),
+        mode: Mode::Prod { replicas: 1 },
+    };
+    let overlay = Config {
+        name: "overlay".to_string(),
+        port: 8080,
+        tls: None,
+        hosts: vec!["b".to_string()],
+        features: BTreeSet::new(),
+        upstreams: [(
+            "secondary".to_string(),
+            Upstream {
+                url: "http://secondary".to_string(),
+                weight: 2,
+            },
+        )]
+        .into_iter()
+        .collect(),
+        mode: Mode::Prod { replicas: 1 },
+    };
+    let strategy = MergeStrategy::new()
+        .with_rule_at("name", MergeRule::Keep)
+        .with_rule_at("hosts", MergeRule::Append)
+        .with_rule_at("upstreams", MergeRule::Replace);
+    merge(&mut config, Peek::new(&overlay), &strategy).unwrap();
+
+    assert_eq!(config.name, "base");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.hosts, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(
+        config.upstreams.keys().collect::<Vec<_>>(),
+        vec!["secondary"]
+    );
+}
+
+#[test]
+fn merge_different_variant_replaces() {
+    let mut config = Config {
+        name: "base".to_string(),
+        port: 80,
+        tls: None,
+        hosts: vec![],
+        features: BTreeSet::new(),
+        upstreams: HashMap::new(),
+        mode: Mode::Dev {
+            verbose: false,
+            seed: 7,
+        },
+    };
+    let overlay = Config {
+        name: "base".to_string(),
+        port: 80,
+        tls: None,
+        hosts: vec![],
+        features: BTreeSet::new(),
+        upstreams: HashMap::new(),
+        mode: Mode::Prod { replicas: 3 },
+    };
+    merge(&mut config, Peek::new(&overlay), &MergeStrategy::new()).unwrap();
+    assert_eq!(config.mode, Mode::Prod { replicas: 3 });
+}
+
+#[test]
+fn merge_wrong_shape() {
+    let mut config = Config {
+        name: "base".to_string(),
+        port: 80,
+        tls: None,
+        hosts: vec![],
+        features: BTreeSet::new(),
+        upstreams: HashMap::new(),
+        mode: Mode::Prod { replicas: 1 },
+    };
+    let err = merge(&mut config, Peek::new(&42u32), &MergeStrategy::new()).unwrap_err();
+    assert!(matches!(err, ReflectError::WrongShape { .. }));
+}
@@ -1,3 +1,10 @@
+mod audit;
+mod default;
+mod dirty;
 mod enum_;
+mod list;
+mod map;
+mod merge;
+mod mutate;
 mod struct_;
 mod value;
@@ -0,0 +1,119 @@
+use facet::Facet;
+use facet_reflect::{MutationKind, Mutator};
+
+#[derive(Debug, Clone, Facet, PartialEq)]
+struct Request {
+    id: u32,
+    name: String,
+    retry: Option<Retry>,
+    tags: Vec<String>,
+    method: Method,
+}
+
+#[derive(Debug, Clone, Facet, PartialEq)]
+struct Retry {
+    attempts: u8,
+}
+
+#[derive(Debug, Clone, Facet, PartialEq)]
+#[repr(u8)]
+enum Method {
+    Get,
+    Post { body: String },
+}
+
+#[test]
+fn mutate_is_deterministic_for_a_seed() {
+    let request = Request {
+        id: 7,
+        name: "ping".into(),
+        retry: Some(Retry { attempts: 3 }),
+        tags: vec!["a".into(), "b".into(), "c".into()],
+        method: Method::Post {
+            body: "hello".into(),
+        },
+    };
+    let run = |seed| {
+        let mut mutator = Mutator::new(seed);
+        let mut value = request.clone();
+        let mutations: Vec<_> = (0..20)
+            .map(|_| mutator.mutate(&mut value).unwrap())
+            .collect();
+        (value, mutations)
+    };
+    assert_eq!(run(42), run(42));
+}
+
+#[test]
+fn mutate_reaches_every_kind_of_site() {
+    let request = Request {
+        id: 7,
+        name: "ping".into(),
+        retry: Some(Retry { attempts: 3 }),
+        tags: vec!["a".into(), "b".into(), "c".into()],
+        method: Method::Post {
+            body: "hello".into(),
+        },
+    };
+    let mut kinds = Vec::new();
+    let mut paths = Vec::new();
+    for seed in 0..200 {
+        let mut value = request.clone();
+        let mutation = Mutator::new(seed).mutate(&mut value).unwrap().unwrap();
+        assert_ne!(value, request, "{mutation:?} changed nothing");
+        kinds.push(mutation.kind);
+        paths.push(mutation.path);
+    }
+    assert!(kinds.contains(&MutationKind::FlipScalar));
+    assert!(kinds.contains(&MutationKind::DropListItem));
+    assert!(kinds.contains(&MutationKind::SwapVariant));
+    assert!(paths.iter().any(|path| path == "retry.attempts"));
+    assert!(paths.iter().any(|path| path == "tags[1]"));
+    assert!(paths.iter().any(|path| path == "method.body"));
+}
+
+#[test]
+fn mutate_drops_list_items() {
+    let mut mutator = Mutator::new(1).with_scalars(false).with_variants(false);
+    let mut value = Request {
+        id: 7,
+        name: "ping".into(),
+        retry: None,
+        tags: vec!["a".into(), "b".into(), "c".into()],
+        method: Method::Get,
+    };
+    for len in (0..3).rev() {
+        let mutation = mutator.mutate(&mut value).unwrap().unwrap();
+        assert_eq!(mutation.kind, MutationKind::DropListItem);
+        assert_eq!(mutation.path, "tags");
+        assert_eq!(value.tags.len(), len);
+    }
+    assert_eq!(mutator.mutate(&mut value).unwrap(), None);
+    assert_eq!(value.name, "ping");
+}
+
+#[test]
+fn mutate_swaps_to_variant_with_defaults() {
+    let mut mutator = Mutator::new(3).with_scalars(false).with_lists(false);
+    let mut value = Request {
+        id: 7,
+        name: "ping".into(),
+        retry: None,
+        tags: vec![],
+        method: Method::Post {
+            body: "hello".into(),
+        },
+    };
+    let mutation = mutator.mutate(&mut value).unwrap().unwrap();
+    assert_eq!(mutation.kind, MutationKind::SwapVariant);
+    assert_eq!(mutation.path, "method");
+    assert_eq!(value.method, Method::Get);
+
+    mutator.mutate(&mut value).unwrap();
+    assert_eq!(
+        value.method,
+        Method::Post {
+            body: String::new()
+        }
+    );
+}
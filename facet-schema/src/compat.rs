@@ -0,0 +1,285 @@
+//! Walking two shapes side by side.
+
+use facet_core::{
+    Characteristic, ConstTypeId, Def, Field, FieldFlags, ScalarType, Shape, StructKind, StructType,
+    Type, UserType, Variant,
+};
+
+use crate::{Change, ChangeKind, CompatReport};
+
+/// Compare two versions of a shape and classify every difference.
+///
+/// Names are compared as they are serialized, so `rename` and `rename_all`
+/// apply and skipped fields are ignored. Flattened struct fields count as
+/// fields of their parent. A field is optional if it is an `Option`, or
+/// falls back to a default when missing.
+///
+/// Wrappers that serialize as what they hold, such as `Box`, `Arc` and
+/// transparent newtypes, are looked through.
+pub fn check_compat(old: &Shape, new: &Shape) -> CompatReport {
+    let mut checker = Checker {
+        changes: Vec::new(),
+        seen: Vec::new(),
+    };
+    checker.compare(String::new(), old, new);
+    CompatReport {
+        changes: checker.changes,
+    }
+}
+
+struct Checker {
+    changes: Vec<Change>,
+    /// Pairs of types already compared, so recursive types terminate
+    seen: Vec<(ConstTypeId, ConstTypeId)>,
+}
+
+impl Checker {
+    fn push(&mut self, path: String, kind: ChangeKind) {
+        self.changes.push(Change { path, kind });
+    }
+
+    fn type_changed(&mut self, path: String, old: &Shape, new: &Shape) {
+        self.push(
+            path,
+            ChangeKind::TypeChanged {
+                old: old.to_string(),
+                new: new.to_string(),
+            },
+        );
+    }
+
+    fn compare(&mut self, path: String, old: &Shape, new: &Shape) {
+        let (old, new) = (unwrap(old), unwrap(new));
+        if core::ptr::eq(old, new) || old.id == new.id {
+            return;
+        }
+        let pair = (old.id, new.id);
+        if self.seen.contains(&pair) {
+            return;
+        }
+        self.seen.push(pair);
+
+        match (old.def, new.def) {
+            (Def::Option(o), Def::Option(n)) => return self.compare(path, o.t(), n.t()),
+            (Def::List(o), Def::List(n)) => return self.compare(items(&path), o.t(), n.t()),
+            (Def::Slice(o), Def::Slice(n)) => return self.compare(items(&path), o.t(), n.t()),
+            (Def::Set(o), Def::Set(n)) => return self.compare(items(&path), o.t(), n.t()),
+            (Def::Array(o), Def::Array(n)) => {
+                if o.n != n.n {
+                    return self.type_changed(path, old, new);
+                }
+                return self.compare(items(&path), o.t(), n.t());
+            }
+            (Def::Map(o), Def::Map(n)) => {
+                self.compare(format!("{path}[key]"), o.k(), n.k());
+                return self.compare(format!("{path}[value]"), o.v(), n.v());
+            }
+            _ => {}
+        }
+
+        match (&old.ty, &new.ty) {
+            (Type::User(UserType::Struct(o)), Type::User(UserType::Struct(n)))
+                if same_struct_kind(o, n) && is_container(old) && is_container(new) =>
+            {
+                self.compare_fields(&path, old, o, new, n)
+            }
+            (Type::User(UserType::Enum(o)), Type::User(UserType::Enum(n)))
+                if is_container(old) && is_container(new) =>
+            {
+                self.compare_variants(&path, o.variants, n.variants)
+            }
+            _ => {
+                let same_scalar = matches!(
+                    (wire_scalar(old), wire_scalar(new)),
+                    (Some(o), Some(n)) if o == n
+                );
+                if !same_scalar {
+                    self.type_changed(path, old, new);
+                }
+            }
+        }
+    }
+
+    fn compare_fields(
+        &mut self,
+        path: &str,
+        old_shape: &Shape,
+        old: &StructType,
+        new_shape: &Shape,
+        new: &StructType,
+    ) {
+        let old_fields = wire_fields(old.fields);
+        let new_fields = wire_fields(new.fields);
+        let denies_unknown = old_shape.has_deny_unknown_fields_attr();
+
+        for old_field in &old_fields {
+            let field_path = child(path, old_field.effective_name());
+            let old_required = is_required(old_field, old_shape);
+            let Some(new_field) = new_fields
+                .iter()
+                .find(|f| f.effective_name() == old_field.effective_name())
+            else {
+                self.push(
+                    field_path,
+                    ChangeKind::FieldRemoved {
+                        required: old_required,
+                    },
+                );
+                continue;
+            };
+
+            let new_required = is_required(new_field, new_shape);
+            match (old_required, new_required) {
+                (true, false) => self.push(field_path.clone(), ChangeKind::FieldMadeOptional),
+                (false, true) => self.push(field_path.clone(), ChangeKind::FieldMadeRequired),
+                _ => {}
+            }
+
+            // `T` -> `Option<T>` is covered by the optionality change above
+            let (old_ty, new_ty) = (old_field.shape(), new_field.shape());
+            match (old_ty.def, new_ty.def) {
+                (Def::Option(_), Def::Option(_)) => self.compare(field_path, old_ty, new_ty),
+                (Def::Option(o), _) => self.compare(field_path, o.t(), new_ty),
+                (_, Def::Option(n)) => self.compare(field_path, old_ty, n.t()),
+                _ => self.compare(field_path, old_ty, new_ty),
+            }
+        }
+
+        for new_field in &new_fields {
+            if old_fields
+                .iter()
+                .any(|f| f.effective_name() == new_field.effective_name())
+            {
+                continue;
+            }
+            self.push(
+                child(path, new_field.effective_name()),
+                ChangeKind::FieldAdded {
+                    required: is_required(new_field, new_shape),
+                    denied_by_old: denies_unknown,
+                },
+            );
+        }
+    }
+
+    fn compare_variants(&mut self, path: &str, old: &[Variant], new: &[Variant]) {
+        for old_variant in old {
+            let variant_path = child(path, old_variant.name);
+            match new.iter().find(|v| v.name == old_variant.name) {
+                Some(new_variant) => {
+                    if !same_struct_kind(&old_variant.data, &new_variant.data) {
+                        self.push(
+                            variant_path,
+                            ChangeKind::TypeChanged {
+                                old: variant_kind(&old_variant.data).into(),
+                                new: variant_kind(&new_variant.data).into(),
+                            },
+                        );
+                        continue;
+                    }
+                    // Variant fields have no container of their own, so
+                    // defaults only come from the fields themselves
+                    self.compare_fields(
+                        &variant_path,
+                        <() as facet_core::Facet>::SHAPE,
+                        &old_variant.data,
+                        <() as facet_core::Facet>::SHAPE,
+                        &new_variant.data,
+                    );
+                }
+                None => self.push(variant_path, ChangeKind::VariantRemoved),
+            }
+        }
+        for new_variant in new {
+            if !old.iter().any(|v| v.name == new_variant.name) {
+                self.push(child(path, new_variant.name), ChangeKind::VariantAdded);
+            }
+        }
+    }
+}
+
+/// Look through wrappers that serialize as what they hold.
+fn unwrap(mut shape: &Shape) -> &Shape {
+    loop {
+        shape = match shape.def {
+            Def::Pointer(ptr) => match ptr.pointee() {
+                Some(pointee) => pointee,
+                None => return shape,
+            },
+            _ if shape.is_transparent() => match shape.inner {
+                Some(inner) => inner,
+                None => return shape,
+            },
+            _ => return shape,
+        };
+    }
+}
+
+/// Structs and enums we can look into, as opposed to opaque ones or
+/// types that serialize through a proxy or as a string.
+fn is_container(shape: &Shape) -> bool {
+    matches!(shape.def, Def::Undefined) && shape.proxy.is_none()
+}
+
+/// The scalar type, with the string types folded into one since they
+/// serialize the same way.
+fn wire_scalar(shape: &Shape) -> Option<ScalarType> {
+    match ScalarType::try_from_shape(shape)? {
+        ScalarType::Str | ScalarType::CowStr => Some(ScalarType::String),
+        other => Some(other),
+    }
+}
+
+fn same_struct_kind(old: &StructType, new: &StructType) -> bool {
+    let named = |kind: StructKind| matches!(kind, StructKind::Struct);
+    let unit = |kind: StructKind| matches!(kind, StructKind::Unit);
+    named(old.kind) == named(new.kind) && unit(old.kind) == unit(new.kind)
+}
+
+fn variant_kind(data: &StructType) -> &'static str {
+    match data.kind {
+        StructKind::Unit => "unit variant",
+        StructKind::Struct => "struct variant",
+        StructKind::TupleStruct | StructKind::Tuple => "tuple variant",
+    }
+}
+
+/// Fields as they appear on the wire: skipped fields dropped, flattened
+/// structs inlined.
+fn wire_fields(fields: &'static [Field]) -> Vec<&'static Field> {
+    let mut out = Vec::new();
+    for field in fields {
+        if field.flags.contains(FieldFlags::SKIP) {
+            continue;
+        }
+        if field.is_flattened()
+            && let Type::User(UserType::Struct(inner)) = unwrap(field.shape()).ty
+        {
+            out.extend(wire_fields(inner.fields));
+            continue;
+        }
+        out.push(field);
+    }
+    out
+}
+
+/// Whether a message must contain this field to be read.
+fn is_required(field: &Field, container: &Shape) -> bool {
+    let optional = matches!(field.shape().def, Def::Option(_))
+        || field.has_default()
+        || field.should_skip_deserializing()
+        || (container.has_default_attr() && field.shape().is(Characteristic::Default));
+    !optional
+}
+
+fn child(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
+    }
+}
+
+fn items(path: &str) -> String {
+    format!("{path}[]")
+}
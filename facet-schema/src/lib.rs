@@ -0,0 +1,59 @@
+//! Compatibility checks between versions of `Facet` shapes.
+//!
+//! [`check_compat`] compares the shape a message type had when data was
+//! written with the shape it has now, and lists every difference with a
+//! [`Severity`]. Wire it into CI to stop changes that would leave old
+//! messages unreadable, or new messages unreadable by old consumers.
+//!
+//! | Change | Severity |
+//! |--------|----------|
+//! | optional field added | compatible |
+//! | required field added | breaking |
+//! | optional field removed | compatible |
+//! | required field removed | breaking |
+//! | field made optional | compatible |
+//! | field made required | breaking |
+//! | variant added or removed | breaking |
+//! | type changed | breaking |
+//!
+//! Adding even an optional field is breaking when the old struct has
+//! `#[facet(deny_unknown_fields)]`, since old readers reject it.
+//!
+//! # Example
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_schema::check_compat;
+//!
+//! mod v1 {
+//!     #[derive(facet::Facet)]
+//!     pub struct Order {
+//!         pub id: u64,
+//!         pub note: String,
+//!     }
+//! }
+//!
+//! mod v2 {
+//!     #[derive(facet::Facet)]
+//!     pub struct Order {
+//!         pub id: u64,
+//!         pub coupon: Option<String>,
+//!     }
+//! }
+//!
+//! let report = check_compat(v1::Order::SHAPE, v2::Order::SHAPE);
+//! assert!(!report.is_compatible());
+//! assert_eq!(
+//!     report.to_string(),
+//!     "breaking: `note`: required field removed\n\
+//!      compatible: `coupon`: optional field added"
+//! );
+//! ```
+
+#![forbid(unsafe_code)]
+
+mod compat;
+mod report;
+
+pub use compat::check_compat;
+pub use report::{Change, ChangeKind, CompatReport, Severity};
@@ -0,0 +1,161 @@
+//! Compatibility report types.
+
+use core::fmt;
+
+/// How much a change matters to readers and writers of the schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Old and new readers can both still read each other's messages.
+    Compatible,
+    /// Some messages written with one version can't be read by the other.
+    Breaking,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Compatible => "compatible",
+            Severity::Breaking => "breaking",
+        })
+    }
+}
+
+/// What changed at a given path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChangeKind {
+    /// A field exists only in the new shape.
+    FieldAdded {
+        /// Whether messages must contain the field.
+        required: bool,
+        /// Whether the old struct rejects fields it doesn't know.
+        denied_by_old: bool,
+    },
+    /// A field exists only in the old shape.
+    FieldRemoved {
+        /// Whether old readers required the field.
+        required: bool,
+    },
+    /// A required field became optional.
+    FieldMadeOptional,
+    /// An optional field became required.
+    FieldMadeRequired,
+    /// A variant exists only in the new enum.
+    VariantAdded,
+    /// A variant exists only in the old enum.
+    VariantRemoved,
+    /// The value has a different type.
+    TypeChanged {
+        /// The old type.
+        old: String,
+        /// The new type.
+        new: String,
+    },
+}
+
+/// One difference between two shapes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Where the change is, as serialized names joined with `.`; `[]` stands
+    /// for sequence items and `[key]`/`[value]` for map entries. Empty for
+    /// the root.
+    pub path: String,
+    /// What changed.
+    pub kind: ChangeKind,
+}
+
+impl Change {
+    /// Classify this change.
+    pub fn severity(&self) -> Severity {
+        match &self.kind {
+            ChangeKind::FieldAdded {
+                required,
+                denied_by_old,
+            } if !required && !denied_by_old => Severity::Compatible,
+            ChangeKind::FieldRemoved { required: false } | ChangeKind::FieldMadeOptional => {
+                Severity::Compatible
+            }
+            _ => Severity::Breaking,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "(root): ")?;
+        } else {
+            write!(f, "`{}`: ", self.path)?;
+        }
+        match &self.kind {
+            ChangeKind::FieldAdded {
+                required,
+                denied_by_old,
+            } => {
+                f.write_str(if *required {
+                    "required field added"
+                } else {
+                    "optional field added"
+                })?;
+                if *denied_by_old {
+                    f.write_str(" (old version denies unknown fields)")?;
+                }
+                Ok(())
+            }
+            ChangeKind::FieldRemoved { required } => f.write_str(if *required {
+                "required field removed"
+            } else {
+                "optional field removed"
+            }),
+            ChangeKind::FieldMadeOptional => f.write_str("field made optional"),
+            ChangeKind::FieldMadeRequired => f.write_str("field made required"),
+            ChangeKind::VariantAdded => f.write_str("variant added"),
+            ChangeKind::VariantRemoved => f.write_str("variant removed"),
+            ChangeKind::TypeChanged { old, new } => {
+                write!(f, "type changed from `{old}` to `{new}`")
+            }
+        }
+    }
+}
+
+/// The result of [`check_compat`](crate::check_compat): every difference
+/// found between two shapes, in traversal order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompatReport {
+    /// All changes, compatible or not.
+    pub changes: Vec<Change>,
+}
+
+impl CompatReport {
+    /// Returns true if no change is breaking.
+    pub fn is_compatible(&self) -> bool {
+        self.breaking().next().is_none()
+    }
+
+    /// The breaking changes.
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|change| change.severity() == Severity::Breaking)
+    }
+
+    /// The most severe change, or `None` if the shapes are equivalent.
+    pub fn severity(&self) -> Option<Severity> {
+        self.changes.iter().map(Change::severity).max()
+    }
+}
+
+impl fmt::Display for CompatReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return f.write_str("no changes");
+        }
+        for (i, change) in self.changes.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}: {change}", change.severity())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_schema::{Change, ChangeKind, Severity, check_compat};
+
+fn change(path: &str, kind: ChangeKind) -> Change {
+    Change {
+        path: path.to_string(),
+        kind,
+    }
+}
+
+#[derive(Facet)]
+struct Address {
+    city: String,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Status {
+    Active,
+    Suspended { reason: String },
+}
+
+#[derive(Facet)]
+struct User {
+    id: u64,
+    name: String,
+    address: Address,
+    tags: Vec<String>,
+    status: Status,
+}
+
+#[test]
+fn identical_shapes_have_no_changes() {
+    let report = check_compat(User::SHAPE, User::SHAPE);
+    assert!(report.changes.is_empty());
+    assert!(report.is_compatible());
+    assert_eq!(report.severity(), None);
+    assert_eq!(report.to_string(), "no changes");
+}
+
+#[test]
+fn equivalent_shapes_from_different_types_have_no_changes() {
+    #[derive(Facet)]
+    struct AddressCopy {
+        city: Box<str>,
+    }
+
+    #[derive(Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum StatusCopy {
+        Active,
+        Suspended { reason: String },
+    }
+
+    #[derive(Facet)]
+    struct UserCopy {
+        tags: Vec<String>,
+        status: StatusCopy,
+        id: u64,
+        address: Box<AddressCopy>,
+        name: String,
+    }
+
+    let report = check_compat(User::SHAPE, UserCopy::SHAPE);
+    assert_eq!(report.changes, vec![]);
+}
+
+#[test]
+fn field_changes_are_classified() {
+    #[derive(Facet)]
+    struct UserV2 {
+        id: u64,
+        #[facet(default)]
+        display_name: String,
+        address: Address,
+        tags: Option<Vec<String>>,
+        status: Status,
+        email: String,
+        nickname: Option<String>,
+    }
+
+    let report = check_compat(User::SHAPE, UserV2::SHAPE);
+    assert_eq!(
+        report.changes,
+        vec![
+            change("name", ChangeKind::FieldRemoved { required: true }),
+            change("tags", ChangeKind::FieldMadeOptional),
+            change(
+                "display_name",
+                ChangeKind::FieldAdded {
+                    required: false,
+                    denied_by_old: false
+                }
+            ),
+            change(
+                "email",
+                ChangeKind::FieldAdded {
+                    required: true,
+                    denied_by_old: false
+                }
+            ),
+            change(
+                "nickname",
+                ChangeKind::FieldAdded {
+                    required: false,
+                    denied_by_old: false
+                }
+            ),
+        ]
+    );
+    let breaking: Vec<_> = report.breaking().map(|c| c.path.as_str()).collect();
+    assert_eq!(breaking, ["name", "email"]);
+    assert_eq!(report.severity(), Some(Severity::Breaking));
+}
+
+#[test]
+fn nested_type_changes_have_paths() {
+    #[derive(Facet)]
+    struct AddressV2 {
+        city: u32,
+    }
+
+    #[derive(Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    enum StatusV2 {
+        Active,
+        Banned,
+        Suspended { reason: String, until: Option<u64> },
+    }
+
+    #[derive(Facet)]
+    struct UserV2 {
+        id: u32,
+        name: String,
+        address: AddressV2,
+        tags: Vec<u8>,
+        status: StatusV2,
+    }
+
+    let report = check_compat(User::SHAPE, UserV2::SHAPE);
+    assert_eq!(
+        report.to_string(),
+        "breaking: `id`: type changed from `u64` to `u32`\n\
+         breaking: `address.city`: type changed from `String` to `u32`\n\
+         breaking: `tags[]`: type changed from `String` to `u8`\n\
+         compatible: `status.Suspended.until`: optional field added\n\
+         breaking: `status.Banned`: variant added"
+    );
+}
+
+#[test]
+fn renames_and_maps() {
+    #[derive(Facet)]
+    struct Config {
+        limits: HashMap<String, u32>,
+    }
+
+    #[derive(Facet)]
+    #[facet(rename_all = "camelCase")]
+    struct ConfigV2 {
+        limits: HashMap<String, u64>,
+    }
+
+    #[derive(Facet)]
+    struct ConfigV3 {
+        #[facet(rename = "limits")]
+        quotas: HashMap<String, u32>,
+    }
+
+    let report = check_compat(Config::SHAPE, ConfigV2::SHAPE);
+    assert_eq!(
+        report.changes,
+        vec![change(
+            "limits[value]",
+            ChangeKind::TypeChanged {
+                old: "u32".into(),
+                new: "u64".into()
+            }
+        )]
+    );
+    assert!(check_compat(Config::SHAPE, ConfigV3::SHAPE).is_compatible());
+}
+
+#[test]
+fn deny_unknown_fields_makes_additions_breaking() {
+    #[derive(Facet)]
+    #[facet(deny_unknown_fields)]
+    struct Strict {
+        a: u8,
+    }
+
+    #[derive(Facet)]
+    struct StrictV2 {
+        a: u8,
+        b: Option<u8>,
+    }
+
+    let report = check_compat(Strict::SHAPE, StrictV2::SHAPE);
+    assert!(!report.is_compatible());
+    assert_eq!(
+        report.to_string(),
+        "breaking: `b`: optional field added (old version denies unknown fields)"
+    );
+}
+
+#[test]
+fn recursive_types_terminate() {
+    #[derive(Facet)]
+    struct Node {
+        value: u32,
+        children: Vec<Node>,
+    }
+
+    #[derive(Facet)]
+    struct NodeV2 {
+        value: u32,
+        children: Vec<NodeV2>,
+        label: Option<String>,
+    }
+
+    let report = check_compat(Node::SHAPE, NodeV2::SHAPE);
+    assert!(report.is_compatible());
+    assert_eq!(report.changes.len(), 1, "{report}");
+}
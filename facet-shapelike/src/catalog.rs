@@ -0,0 +1,346 @@
+//! A flat, cross-referenced dump of shapes for tools outside Rust.
+//!
+//! [`Catalog`] lists every shape reachable from a set of roots, once each,
+//! with its fields, variants, docs and attributes. Shapes refer to each other
+//! by [`ShapeEntry::id`] instead of being nested, so IDE plugins and code
+//! generators can load the whole data model from one JSON document.
+
+use std::collections::HashMap;
+
+use facet::Facet;
+use facet_core::{Attr, ConstTypeId, Def, Field, FieldFlags, Shape, StructKind, Type, UserType};
+use facet_reflect::Peek;
+
+/// Every shape reachable from some roots
+#[derive(Facet, Debug, Clone, PartialEq)]
+pub struct Catalog {
+    /// The shapes, roots first, then in the order they were reached
+    pub shapes: Vec<ShapeEntry>,
+}
+
+/// One shape of a [`Catalog`]
+#[derive(Facet, Debug, Clone, PartialEq)]
+pub struct ShapeEntry {
+    /// Unique name of the shape within the catalog, e.g. `Vec<u32>`
+    pub id: String,
+    /// The type's name without generic parameters, e.g. `Vec`
+    pub name: String,
+    /// What kind of type this is: `struct`, `enum`, `list`, `map`, `set`,
+    /// `array`, `slice`, `option`, `result`, `pointer`, `scalar`, ...
+    pub kind: String,
+    /// Doc comment lines
+    pub doc: Vec<String>,
+    /// Attributes set on the type with `#[facet(...)]`
+    pub attributes: Vec<AttributeEntry>,
+    /// Generic type parameters
+    pub type_params: Vec<Relation>,
+    /// Shapes this one is built from, other than through fields: list items,
+    /// map keys and values, pointees, ...
+    pub relations: Vec<Relation>,
+    /// Fields of a struct
+    pub fields: Vec<FieldEntry>,
+    /// Variants of an enum
+    pub variants: Vec<VariantEntry>,
+}
+
+/// A reference from one shape to another
+#[derive(Facet, Debug, Clone, PartialEq)]
+pub struct Relation {
+    /// What the other shape is to this one, e.g. `item`, `key` or `value`, or
+    /// the name of a type parameter
+    pub role: String,
+    /// The [`ShapeEntry::id`] of the other shape
+    pub shape: String,
+}
+
+/// A field of a struct or enum variant
+#[derive(Facet, Debug, Clone, PartialEq)]
+pub struct FieldEntry {
+    /// The field name in Rust
+    pub name: String,
+    /// The name the field is serialized under, if renamed
+    pub rename: Option<String>,
+    /// The [`ShapeEntry::id`] of the field's type
+    pub shape: String,
+    /// Doc comment lines
+    pub doc: Vec<String>,
+    /// Flags like `flatten`, `sensitive` or `skip`
+    pub flags: Vec<String>,
+    /// Whether the field has a default, so it may be missing from input
+    pub has_default: bool,
+    /// Other attributes set on the field with `#[facet(...)]`
+    pub attributes: Vec<AttributeEntry>,
+}
+
+/// A variant of an enum
+#[derive(Facet, Debug, Clone, PartialEq)]
+pub struct VariantEntry {
+    /// The variant name
+    pub name: String,
+    /// `unit`, `tuple` or `struct`
+    pub kind: String,
+    /// Doc comment lines
+    pub doc: Vec<String>,
+    /// Attributes set on the variant with `#[facet(...)]`
+    pub attributes: Vec<AttributeEntry>,
+    /// The variant's fields
+    pub fields: Vec<FieldEntry>,
+}
+
+/// An attribute, like `#[facet(unit = "bytes")]` or `#[facet(orm::primary_key)]`
+#[derive(Facet, Debug, Clone, PartialEq)]
+pub struct AttributeEntry {
+    /// The namespace, for attributes of extension crates
+    pub ns: Option<String>,
+    /// The attribute name
+    pub key: String,
+    /// The attribute's value, in Rust syntax, if it has one
+    pub value: Option<String>,
+}
+
+const FLAG_NAMES: &[(FieldFlags, &str)] = &[
+    (FieldFlags::SENSITIVE, "sensitive"),
+    (FieldFlags::FLATTEN, "flatten"),
+    (FieldFlags::SKIP, "skip"),
+    (FieldFlags::SKIP_SERIALIZING, "skip_serializing"),
+    (FieldFlags::SKIP_DESERIALIZING, "skip_deserializing"),
+    (FieldFlags::CHILD, "child"),
+];
+
+impl Catalog {
+    /// The catalog of `T` and every shape it refers to
+    pub fn of<'a, T: Facet<'a>>() -> Self {
+        Self::from_shapes([T::SHAPE])
+    }
+
+    /// The catalog of every shape registered with
+    /// [`register_shape`](facet_core::register_shape), and the shapes they
+    /// refer to
+    pub fn registered() -> Self {
+        Self::from_shapes(facet_core::registered_shapes())
+    }
+
+    /// The catalog of `roots` and every shape they refer to
+    pub fn from_shapes(roots: impl IntoIterator<Item = &'static Shape>) -> Self {
+        let mut builder = Builder::default();
+        for shape in roots {
+            builder.id(shape);
+        }
+        let mut queue = core::mem::take(&mut builder.discovered);
+        let mut next = 0;
+        while let Some(&shape) = queue.get(next) {
+            next += 1;
+            let entry = builder.entry(shape);
+            queue.append(&mut builder.discovered);
+            builder.shapes.push(entry);
+        }
+        Self {
+            shapes: builder.shapes,
+        }
+    }
+
+    /// The entry with the given [`ShapeEntry::id`]
+    pub fn get(&self, id: &str) -> Option<&ShapeEntry> {
+        self.shapes.iter().find(|shape| shape.id == id)
+    }
+
+    /// The catalog as a pretty-printed JSON document
+    pub fn to_json(&self) -> String {
+        facet_json::to_string_pretty(self).expect("catalogs only hold strings and lists")
+    }
+}
+
+/// Assigns ids to shapes and renders their entries
+#[derive(Default)]
+struct Builder {
+    ids: HashMap<ConstTypeId, String>,
+    taken: HashMap<String, usize>,
+    /// Shapes given an id since the last entry was rendered
+    discovered: Vec<&'static Shape>,
+    shapes: Vec<ShapeEntry>,
+}
+
+impl Builder {
+    /// The id of `shape`, assigning one (and queueing the shape) if it has none
+    fn id(&mut self, shape: &'static Shape) -> String {
+        if let Some(id) = self.ids.get(&shape.id) {
+            return id.clone();
+        }
+        // Distinct types can display the same, e.g. two `Config`s from
+        // different modules
+        let name = display_name(shape);
+        let count = self.taken.entry(name.clone()).or_default();
+        *count += 1;
+        let id = match *count {
+            1 => name,
+            n => format!("{name}#{n}"),
+        };
+        self.ids.insert(shape.id, id.clone());
+        self.discovered.push(shape);
+        id
+    }
+
+    fn relation(&mut self, role: &str, shape: &'static Shape) -> Relation {
+        Relation {
+            role: role.into(),
+            shape: self.id(shape),
+        }
+    }
+
+    fn entry(&mut self, shape: &'static Shape) -> ShapeEntry {
+        let mut relations = Vec::new();
+        let kind = match shape.def {
+            Def::Scalar => "scalar",
+            Def::Map(def) => {
+                relations.push(self.relation("key", def.k()));
+                relations.push(self.relation("value", def.v()));
+                "map"
+            }
+            Def::Set(def) => {
+                relations.push(self.relation("item", def.t()));
+                "set"
+            }
+            Def::List(def) => {
+                relations.push(self.relation("item", def.t()));
+                "list"
+            }
+            Def::Array(def) => {
+                relations.push(self.relation("item", def.t()));
+                "array"
+            }
+            Def::NdArray(def) => {
+                relations.push(self.relation("item", def.t()));
+                "ndarray"
+            }
+            Def::Slice(def) => {
+                relations.push(self.relation("item", def.t()));
+                "slice"
+            }
+            Def::Option(def) => {
+                relations.push(self.relation("some", def.t()));
+                "option"
+            }
+            Def::Result(def) => {
+                relations.push(self.relation("ok", def.t()));
+                relations.push(self.relation("err", def.e()));
+                "result"
+            }
+            Def::Pointer(def) => {
+                if let Some(pointee) = def.pointee() {
+                    relations.push(self.relation("pointee", pointee));
+                }
+                "pointer"
+            }
+            Def::DynamicValue(_) => "dynamic",
+            Def::TraitObject(_) => "trait_object",
+            _ => match shape.ty {
+                Type::User(UserType::Struct(_)) => "struct",
+                Type::User(UserType::Enum(_)) => "enum",
+                Type::User(UserType::Union(_)) => "union",
+                Type::Primitive(_) => "scalar",
+                Type::Pointer(_) => "pointer",
+                _ => "opaque",
+            },
+        };
+        if let Some(inner) = shape.inner {
+            relations.push(self.relation("inner", inner));
+        }
+
+        let type_params = shape
+            .type_params
+            .iter()
+            .map(|param| self.relation(param.name, param.shape))
+            .collect();
+        let (fields, variants) = match shape.ty {
+            Type::User(UserType::Struct(struct_type)) if kind == "struct" => {
+                (self.fields(struct_type.fields), Vec::new())
+            }
+            Type::User(UserType::Enum(enum_type)) if kind == "enum" => {
+                let variants = enum_type
+                    .variants
+                    .iter()
+                    .map(|variant| VariantEntry {
+                        name: variant.name.into(),
+                        kind: match variant.data.kind {
+                            StructKind::Unit => "unit",
+                            StructKind::Struct => "struct",
+                            StructKind::TupleStruct | StructKind::Tuple => "tuple",
+                        }
+                        .into(),
+                        doc: doc(variant.doc),
+                        attributes: attributes(variant.attributes),
+                        fields: self.fields(variant.data.fields),
+                    })
+                    .collect();
+                (Vec::new(), variants)
+            }
+            _ => (Vec::new(), Vec::new()),
+        };
+
+        ShapeEntry {
+            id: self.id(shape),
+            name: shape.type_identifier.into(),
+            kind: kind.into(),
+            doc: doc(shape.doc),
+            attributes: attributes(shape.attributes),
+            type_params,
+            relations,
+            fields,
+            variants,
+        }
+    }
+
+    fn fields(&mut self, fields: &'static [Field]) -> Vec<FieldEntry> {
+        fields
+            .iter()
+            .map(|field| FieldEntry {
+                name: field.name.into(),
+                rename: field.rename.map(Into::into),
+                shape: self.id(field.shape()),
+                doc: doc(field.doc),
+                flags: FLAG_NAMES
+                    .iter()
+                    .filter(|(flag, _)| field.flags.contains(*flag))
+                    .map(|(_, name)| (*name).into())
+                    .collect(),
+                has_default: field.has_default(),
+                attributes: attributes(field.attributes),
+            })
+            .collect()
+    }
+}
+
+/// The type's name with its generic parameters, e.g. `Option<String>`
+fn display_name(shape: &'static Shape) -> String {
+    // Some shapes display without their parameters
+    let name = shape.to_string();
+    if shape.type_params.is_empty() || name != shape.type_identifier {
+        return name;
+    }
+    let params: Vec<String> = shape
+        .type_params
+        .iter()
+        .map(|param| display_name(param.shape))
+        .collect();
+    format!("{}<{}>", shape.type_identifier, params.join(", "))
+}
+
+fn doc(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|line| line.trim().to_string()).collect()
+}
+
+fn attributes(attrs: &'static [Attr]) -> Vec<AttributeEntry> {
+    attrs
+        .iter()
+        .map(|attr| {
+            let shape = attr.data.shape;
+            // SAFETY: attribute data is a static value of its shape
+            let peek = unsafe { Peek::unchecked_new(attr.data.ptr(), shape) };
+            AttributeEntry {
+                ns: attr.ns.map(Into::into),
+                key: attr.key.into(),
+                value: (!shape.is_type::<()>()).then(|| format!("{peek:?}")),
+            }
+        })
+        .collect()
+}
@@ -1,3 +1,4 @@
+pub mod catalog;
 pub mod shape_like;
 pub mod types;
 
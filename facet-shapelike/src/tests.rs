@@ -1,3 +1,4 @@
+use crate::catalog::Catalog;
 use crate::shape_like::ShapeLike;
 use facet::Facet;
 use facet_args as args;
@@ -88,3 +89,83 @@ fn test_args_attributes_roundtrip() {
         facet_json::from_str(&json).expect("Failed to deserialize ShapeLike");
     facet_assert::assert_same!(shape_like, deserialized)
 }
+
+/// A server in the fleet
+#[derive(Facet)]
+struct Server {
+    /// Host name
+    #[facet(rename = "hostName")]
+    host: String,
+    #[facet(sensitive, default)]
+    token: Option<String>,
+    #[facet(unit = "bytes")]
+    memory: u64,
+    roles: Vec<Role>,
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Role {
+    Primary,
+    Replica { lag: u32 },
+}
+
+#[test]
+fn test_catalog_lists_reachable_shapes_once() {
+    let catalog = Catalog::of::<Server>();
+    let ids: Vec<&str> = catalog.shapes.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(
+        ids,
+        [
+            "Server",
+            "String",
+            "Option<String>",
+            "u64",
+            "Vec<Role>",
+            "Role",
+            "u32"
+        ]
+    );
+
+    let server = catalog.get("Server").unwrap();
+    assert_eq!(server.kind, "struct");
+    assert_eq!(server.doc, ["A server in the fleet"]);
+    let host = &server.fields[0];
+    assert_eq!(host.rename.as_deref(), Some("hostName"));
+    assert_eq!(host.doc, ["Host name"]);
+    assert_eq!(server.fields[1].shape, "Option<String>");
+    assert_eq!(server.fields[1].flags, ["sensitive"]);
+    assert!(server.fields[1].has_default);
+    assert_eq!(server.fields[2].attributes[0].key, "unit");
+    assert_eq!(
+        server.fields[2].attributes[0].value.as_deref(),
+        Some("\"bytes\"")
+    );
+
+    let roles = catalog.get("Vec<Role>").unwrap();
+    assert_eq!(roles.kind, "list");
+    assert_eq!(roles.relations[0].role, "item");
+    assert_eq!(roles.relations[0].shape, "Role");
+
+    let role = catalog.get("Role").unwrap();
+    assert_eq!(role.variants[1].kind, "struct");
+    assert_eq!(role.variants[1].fields[0].shape, "u32");
+}
+
+#[test]
+fn test_catalog_json_roundtrip() {
+    let catalog = Catalog::of::<Server>();
+    let json = catalog.to_json();
+    assert!(json.contains("\"id\": \"Vec<Role>\""));
+    let parsed: Catalog = facet_json::from_str(&json).unwrap();
+    assert_eq!(parsed, catalog);
+}
+
+#[test]
+fn test_catalog_of_registered_shapes() {
+    facet_core::register_shape(Role::SHAPE);
+    let catalog = Catalog::registered();
+    assert!(catalog.get("Role").is_some());
+    assert!(catalog.get("u32").is_some());
+}
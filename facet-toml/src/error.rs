@@ -265,6 +265,74 @@ impl From<ReflectError> for TomlError {
     }
 }
 
+/// A 1-based line and column in TOML input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column number in characters, starting at 1
+    pub column: usize,
+}
+
+impl Location {
+    /// Compute the location of a byte offset in `input`.
+    pub fn from_offset(input: &str, offset: usize) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Location {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Find where in `input` a deserialization error occurred.
+///
+/// Works for syntax errors as well as for values that don't fit their field,
+/// such as a string where a `u16` was expected.
+///
+/// ```
+/// use facet::Facet;
+/// use facet_toml::{Location, error_location};
+///
+/// #[derive(Facet, Debug)]
+/// struct Server {
+///     port: u16,
+/// }
+///
+/// let input = "[server]\nport = \"eighty\"\n";
+/// #[derive(Facet, Debug)]
+/// struct Config {
+///     server: Server,
+/// }
+///
+/// let err = facet_toml::from_str::<Config>(input).unwrap_err();
+/// assert_eq!(
+///     error_location(input, &err),
+///     Some(Location { line: 2, column: 8 })
+/// );
+/// ```
+pub fn error_location(
+    input: &str,
+    error: &facet_format::DeserializeError<TomlError>,
+) -> Option<Location> {
+    let span = match error {
+        facet_format::DeserializeError::Parser(err) => err.span,
+        other => other.span(),
+    }?;
+    Some(Location::from_offset(input, span.offset))
+}
+
 /// Result type for TOML operations
 #[allow(dead_code)]
 pub type Result<T> = core::result::Result<T, TomlError>;
@@ -33,7 +33,7 @@ mod serializer;
 #[cfg(feature = "axum")]
 mod axum;
 
-pub use error::{TomlError, TomlErrorKind};
+pub use error::{Location, TomlError, TomlErrorKind, error_location};
 pub use parser::{TomlParser, TomlProbe};
 pub use serializer::{
     SerializeOptions, TomlSerializeError, TomlSerializer, to_string, to_string_with_options, to_vec,
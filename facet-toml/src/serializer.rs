@@ -1,6 +1,9 @@
 extern crate alloc;
 
-use alloc::{string::String, vec::Vec};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 use core::fmt::Write;
 
 use facet_format::{FormatSerializer, ScalarValue, SerializeError};
@@ -9,6 +12,9 @@ use facet_format::{FormatSerializer, ScalarValue, SerializeError};
 #[derive(Debug, Clone, Default)]
 pub struct SerializeOptions {
     /// Whether to use inline tables for nested structures (default: false)
+    ///
+    /// By default, nested structs are written as `[table]` sections and
+    /// sequences of structs as `[[array-of-tables]]` sections.
     pub inline_tables: bool,
 }
 
@@ -30,6 +36,12 @@ pub struct TomlSerializeError {
     msg: String,
 }
 
+impl TomlSerializeError {
+    fn new(msg: impl Into<String>) -> Self {
+        Self { msg: msg.into() }
+    }
+}
+
 impl core::fmt::Display for TomlSerializeError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(&self.msg)
@@ -38,30 +50,56 @@ impl core::fmt::Display for TomlSerializeError {
 
 impl std::error::Error for TomlSerializeError {}
 
-#[derive(Debug, Clone)]
-enum Ctx {
-    /// Top-level table (root)
-    Root { first: bool },
-    /// Nested table (e.g., `[section]`)
-    /// Note: Currently unused. Will be used for pretty printing with table headers.
-    #[allow(dead_code)]
-    Table { first: bool, path: Vec<String> },
-    /// Inline table (e.g., `{ key = value }`)
-    InlineTable { first: bool },
-    /// Array (e.g., `[1, 2, 3]`)
-    Array { first: bool },
+/// A buffered TOML value. Tables are buffered so that plain keys can be
+/// written before `[table]` and `[[array-of-tables]]` sections, which TOML
+/// requires.
+#[derive(Debug)]
+enum Node {
+    /// A literal, already in TOML syntax
+    Literal(String),
+    /// `None` / unit; omitted from tables
+    Null,
+    Array(Vec<Node>),
+    Table {
+        /// Structs become `[table]` sections; maps stay inline
+        is_struct: bool,
+        entries: Vec<(String, Node)>,
+    },
+}
+
+impl Node {
+    fn is_struct_table(&self) -> bool {
+        matches!(
+            self,
+            Node::Table {
+                is_struct: true,
+                ..
+            }
+        )
+    }
+
+    /// A non-empty array whose items are all struct tables, written as `[[key]]`.
+    fn is_array_of_tables(&self) -> bool {
+        matches!(self, Node::Array(items) if !items.is_empty() && items.iter().all(Node::is_struct_table))
+    }
+}
+
+#[derive(Debug)]
+enum Frame {
+    Array(Vec<Node>),
+    Table {
+        is_struct: bool,
+        entries: Vec<(String, Node)>,
+        key: Option<String>,
+    },
 }
 
 /// TOML serializer with configurable formatting options.
 pub struct TomlSerializer {
     out: String,
-    stack: Vec<Ctx>,
-    /// Formatting options (currently unused, reserved for pretty printing)
-    #[allow(dead_code)]
+    stack: Vec<Frame>,
+    next_is_struct: bool,
     options: SerializeOptions,
-    /// Current table path for dotted keys (reserved for pretty printing)
-    #[allow(dead_code)]
-    current_path: Vec<String>,
 }
 
 impl TomlSerializer {
@@ -75,8 +113,8 @@ impl TomlSerializer {
         Self {
             out: String::new(),
             stack: Vec::new(),
+            next_is_struct: false,
             options,
-            current_path: Vec::new(),
         }
     }
 
@@ -85,34 +123,183 @@ impl TomlSerializer {
         self.out
     }
 
-    /// Check if we're in an inline context (inline table or array)
-    /// Note: Reserved for pretty printing implementation.
-    #[allow(dead_code)]
-    fn is_inline_context(&self) -> bool {
-        matches!(
-            self.stack.last(),
-            Some(Ctx::InlineTable { .. }) | Some(Ctx::Array { .. })
-        )
+    fn value(&mut self, node: Node) -> Result<(), TomlSerializeError> {
+        match self.stack.last_mut() {
+            Some(Frame::Array(items)) => items.push(node),
+            Some(Frame::Table { entries, key, .. }) => {
+                let key = key
+                    .take()
+                    .ok_or_else(|| TomlSerializeError::new("value without a key"))?;
+                entries.push((key, node));
+            }
+            None => self.render_root(node)?,
+        }
+        Ok(())
+    }
+
+    fn render_root(&mut self, node: Node) -> Result<(), TomlSerializeError> {
+        let Node::Table { entries, .. } = node else {
+            return Err(TomlSerializeError::new(
+                "TOML documents must be a table at the top level",
+            ));
+        };
+        let mut path = Vec::new();
+        self.write_table_body(&mut path, &entries)?;
+        Ok(())
+    }
+
+    /// Write the entries of a table: plain keys first, then sub-tables, then
+    /// arrays of tables.
+    fn write_table_body(
+        &mut self,
+        path: &mut Vec<String>,
+        entries: &[(String, Node)],
+    ) -> Result<(), TomlSerializeError> {
+        let inline_only = self.options.inline_tables;
+        let is_section =
+            |node: &Node| !inline_only && (node.is_struct_table() || node.is_array_of_tables());
+
+        for (key, node) in entries {
+            if matches!(node, Node::Null) || is_section(node) {
+                continue;
+            }
+            write_key(&mut self.out, key);
+            self.out.push_str(" = ");
+            write_inline(&mut self.out, node, path, key)?;
+            self.out.push('\n');
+        }
+
+        for (key, node) in entries {
+            if inline_only {
+                break;
+            }
+            match node {
+                Node::Table {
+                    is_struct: true,
+                    entries: sub,
+                } => {
+                    path.push(key.clone());
+                    // A table holding only sections needs no header of its own.
+                    if sub.is_empty() || sub.iter().any(|(_, n)| !is_section(n)) {
+                        self.write_header(path, false);
+                    }
+                    self.write_table_body(path, sub)?;
+                    path.pop();
+                }
+                Node::Array(items) if node.is_array_of_tables() => {
+                    path.push(key.clone());
+                    for item in items {
+                        if let Node::Table { entries: sub, .. } = item {
+                            self.write_header(path, true);
+                            self.write_table_body(path, sub)?;
+                        }
+                    }
+                    path.pop();
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn write_header(&mut self, path: &[String], array: bool) {
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        self.out.push_str(if array { "[[" } else { "[" });
+        for (i, segment) in path.iter().enumerate() {
+            if i > 0 {
+                self.out.push('.');
+            }
+            write_key(&mut self.out, segment);
+        }
+        self.out.push_str(if array { "]]\n" } else { "]\n" });
+    }
+}
+
+/// Write a key, bare if possible and quoted otherwise.
+fn write_key(out: &mut String, key: &str) {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        out.push_str(key);
+    } else {
+        write_toml_string(out, key);
     }
+}
 
-    /// Write a TOML string value with proper escaping
-    fn write_toml_string(&mut self, s: &str) {
-        self.out.push('"');
-        for c in s.chars() {
-            match c {
-                '"' => self.out.push_str(r#"\""#),
-                '\\' => self.out.push_str(r"\\"),
-                '\n' => self.out.push_str(r"\n"),
-                '\r' => self.out.push_str(r"\r"),
-                '\t' => self.out.push_str(r"\t"),
-                c if c.is_control() => {
-                    write!(self.out, "\\u{:04X}", c as u32).unwrap();
+/// Write a value in inline syntax (`[..]`, `{ .. }` or a literal).
+fn write_inline(
+    out: &mut String,
+    node: &Node,
+    path: &[String],
+    key: &str,
+) -> Result<(), TomlSerializeError> {
+    match node {
+        Node::Literal(lit) => out.push_str(lit),
+        Node::Null => {
+            let mut location = path.join(".");
+            if !location.is_empty() {
+                location.push('.');
+            }
+            location.push_str(key);
+            return Err(TomlSerializeError::new(alloc::format!(
+                "TOML does not support null values (in `{location}`)"
+            )));
+        }
+        Node::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_inline(out, item, path, key)?;
+            }
+            out.push(']');
+        }
+        Node::Table { entries, .. } => {
+            let mut entries = entries
+                .iter()
+                .filter(|(_, n)| !matches!(n, Node::Null))
+                .peekable();
+            if entries.peek().is_none() {
+                out.push_str("{}");
+                return Ok(());
+            }
+            out.push_str("{ ");
+            for (i, (k, v)) in entries.enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
                 }
-                c => self.out.push(c),
+                write_key(out, k);
+                out.push_str(" = ");
+                write_inline(out, v, path, k)?;
+            }
+            out.push_str(" }");
+        }
+    }
+    Ok(())
+}
+
+/// Write a TOML string value with proper escaping
+fn write_toml_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str(r#"\""#),
+            '\\' => out.push_str(r"\\"),
+            '\n' => out.push_str(r"\n"),
+            '\r' => out.push_str(r"\r"),
+            '\t' => out.push_str(r"\t"),
+            c if c.is_control() => {
+                write!(out, "\\u{:04X}", c as u32).unwrap();
             }
+            c => out.push(c),
         }
-        self.out.push('"');
     }
+    out.push('"');
 }
 
 impl Default for TomlSerializer {
@@ -124,187 +311,116 @@ impl Default for TomlSerializer {
 impl FormatSerializer for TomlSerializer {
     type Error = TomlSerializeError;
 
+    fn struct_metadata(&mut self, _shape: &facet_core::Shape) -> Result<(), Self::Error> {
+        self.next_is_struct = true;
+        Ok(())
+    }
+
     fn begin_struct(&mut self) -> Result<(), Self::Error> {
-        match self.stack.last_mut() {
-            None => {
-                // Root level - just start tracking as root
-                self.stack.push(Ctx::Root { first: true });
-                Ok(())
-            }
-            Some(Ctx::InlineTable { .. }) | Some(Ctx::Array { .. }) => {
-                // We're in an inline context - use inline table syntax
-                self.out.push_str("{ ");
-                self.stack.push(Ctx::InlineTable { first: true });
-                Ok(())
-            }
-            Some(Ctx::Root { .. }) | Some(Ctx::Table { .. }) => {
-                // Nested table - will be handled via dotted keys or [table] headers
-                // For now, use inline table
-                self.out.push_str("{ ");
-                self.stack.push(Ctx::InlineTable { first: true });
-                Ok(())
-            }
-        }
+        self.stack.push(Frame::Table {
+            is_struct: core::mem::take(&mut self.next_is_struct),
+            entries: Vec::new(),
+            key: None,
+        });
+        Ok(())
     }
 
     fn field_key(&mut self, key: &str) -> Result<(), Self::Error> {
         match self.stack.last_mut() {
-            Some(Ctx::Root { first }) | Some(Ctx::Table { first, .. }) => {
-                // Top-level or table field
-                if !*first {
-                    self.out.push('\n');
-                }
-                *first = false;
-
-                // Write the key
-                if key
-                    .chars()
-                    .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
-                {
-                    // Simple key
-                    self.out.push_str(key);
-                } else {
-                    // Quoted key
-                    self.write_toml_string(key);
-                }
-                self.out.push_str(" = ");
-                Ok(())
-            }
-            Some(Ctx::InlineTable { first }) => {
-                // Inline table field
-                if !*first {
-                    self.out.push_str(", ");
-                }
-                *first = false;
-
-                // Write the key
-                if key
-                    .chars()
-                    .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
-                {
-                    self.out.push_str(key);
-                } else {
-                    self.write_toml_string(key);
-                }
-                self.out.push_str(" = ");
+            Some(Frame::Table { key: slot, .. }) => {
+                *slot = Some(key.to_string());
                 Ok(())
             }
-            _ => Err(TomlSerializeError {
-                msg: "field_key called outside of a struct context".into(),
-            }),
+            _ => Err(TomlSerializeError::new(
+                "field_key called outside of a struct context",
+            )),
         }
     }
 
     fn end_struct(&mut self) -> Result<(), Self::Error> {
         match self.stack.pop() {
-            Some(Ctx::Root { .. }) => {
-                // Root table ends - add final newline if there's content
-                if !self.out.is_empty() && !self.out.ends_with('\n') {
-                    self.out.push('\n');
-                }
-                Ok(())
-            }
-            Some(Ctx::InlineTable { .. }) => {
-                self.out.push_str(" }");
-                Ok(())
-            }
-            Some(Ctx::Table { .. }) => {
-                // Nested table ends
-                Ok(())
-            }
-            _ => Err(TomlSerializeError {
-                msg: "end_struct called without matching begin_struct".into(),
-            }),
+            Some(Frame::Table {
+                is_struct, entries, ..
+            }) => self.value(Node::Table { is_struct, entries }),
+            _ => Err(TomlSerializeError::new(
+                "end_struct called without matching begin_struct",
+            )),
         }
     }
 
     fn begin_seq(&mut self) -> Result<(), Self::Error> {
-        self.out.push('[');
-        self.stack.push(Ctx::Array { first: true });
+        self.next_is_struct = false;
+        self.stack.push(Frame::Array(Vec::new()));
         Ok(())
     }
 
     fn end_seq(&mut self) -> Result<(), Self::Error> {
         match self.stack.pop() {
-            Some(Ctx::Array { .. }) => {
-                self.out.push(']');
-                Ok(())
-            }
-            _ => Err(TomlSerializeError {
-                msg: "end_seq called without matching begin_seq".into(),
-            }),
+            Some(Frame::Array(items)) => self.value(Node::Array(items)),
+            _ => Err(TomlSerializeError::new(
+                "end_seq called without matching begin_seq",
+            )),
         }
     }
 
     fn scalar(&mut self, scalar: ScalarValue<'_>) -> Result<(), Self::Error> {
-        // Handle comma separator for arrays
-        if let Some(Ctx::Array { first }) = self.stack.last_mut() {
-            if !*first {
-                self.out.push_str(", ");
-            }
-            *first = false;
-        }
-
+        self.next_is_struct = false;
+        let mut out = String::new();
         match scalar {
-            ScalarValue::Null => {
-                // TOML doesn't have null - this is an error
-                return Err(TomlSerializeError {
-                    msg: "TOML does not support null values".into(),
-                });
-            }
+            ScalarValue::Null => return self.value(Node::Null),
             ScalarValue::Bool(v) => {
-                self.out.push_str(if v { "true" } else { "false" });
+                out.push_str(if v { "true" } else { "false" });
             }
             ScalarValue::I64(v) => {
                 #[cfg(feature = "fast")]
-                self.out.push_str(itoa::Buffer::new().format(v));
+                out.push_str(itoa::Buffer::new().format(v));
                 #[cfg(not(feature = "fast"))]
-                write!(self.out, "{}", v).unwrap();
+                write!(out, "{}", v).unwrap();
             }
             ScalarValue::U64(v) => {
                 #[cfg(feature = "fast")]
-                self.out.push_str(itoa::Buffer::new().format(v));
+                out.push_str(itoa::Buffer::new().format(v));
                 #[cfg(not(feature = "fast"))]
-                write!(self.out, "{}", v).unwrap();
+                write!(out, "{}", v).unwrap();
             }
             ScalarValue::I128(v) => {
                 #[cfg(feature = "fast")]
-                self.out.push_str(itoa::Buffer::new().format(v));
+                out.push_str(itoa::Buffer::new().format(v));
                 #[cfg(not(feature = "fast"))]
-                write!(self.out, "{}", v).unwrap();
+                write!(out, "{}", v).unwrap();
             }
             ScalarValue::U128(v) => {
                 #[cfg(feature = "fast")]
-                self.out.push_str(itoa::Buffer::new().format(v));
+                out.push_str(itoa::Buffer::new().format(v));
                 #[cfg(not(feature = "fast"))]
-                write!(self.out, "{}", v).unwrap();
+                write!(out, "{}", v).unwrap();
             }
             ScalarValue::F64(v) => {
                 if v.is_nan() {
-                    self.out.push_str("nan");
+                    out.push_str("nan");
                 } else if v.is_infinite() {
                     if v.is_sign_positive() {
-                        self.out.push_str("inf");
+                        out.push_str("inf");
                     } else {
-                        self.out.push_str("-inf");
+                        out.push_str("-inf");
                     }
                 } else {
                     #[cfg(feature = "fast")]
-                    self.out.push_str(zmij::Buffer::new().format(v));
+                    out.push_str(zmij::Buffer::new().format(v));
                     #[cfg(not(feature = "fast"))]
-                    write!(self.out, "{}", v).unwrap();
+                    write!(out, "{}", v).unwrap();
                 }
             }
             ScalarValue::Str(s) => {
-                self.write_toml_string(&s);
+                write_toml_string(&mut out, &s);
             }
             ScalarValue::Bytes(_) => {
-                return Err(TomlSerializeError {
-                    msg: "TOML does not natively support byte arrays".into(),
-                });
+                return Err(TomlSerializeError::new(
+                    "TOML does not natively support byte arrays",
+                ));
             }
         }
-        Ok(())
+        self.value(Node::Literal(out))
     }
 }
 
@@ -5,7 +5,8 @@
 
 use facet::Facet;
 use facet_testhelpers::test;
-use std::collections::HashMap;
+use facet_toml::SerializeOptions;
+use std::collections::{BTreeMap, HashMap};
 
 // ============================================================================
 // Basic struct tests
@@ -1146,9 +1147,140 @@ fn test_serialize_nested_struct() {
     };
 
     let toml = facet_toml::to_string(&config).unwrap();
-    // Nested structs become inline tables with current serializer
-    assert!(toml.contains("name = \"test\""));
-    assert!(toml.contains("server = { host = \"localhost\", port = 8080 }"));
+    assert_eq!(
+        toml,
+        "name = \"test\"\n\n[server]\nhost = \"localhost\"\nport = 8080\n"
+    );
+
+    let inline =
+        facet_toml::to_string_with_options(&config, &SerializeOptions::new().inline_tables())
+            .unwrap();
+    assert!(inline.contains("server = { host = \"localhost\", port = 8080 }"));
+}
+
+#[test]
+fn test_serialize_cargo_style_manifest() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Package {
+        name: String,
+        version: String,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Bin {
+        name: String,
+        path: String,
+        test: Option<bool>,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Manifest {
+        package: Package,
+        bin: Vec<Bin>,
+        dependencies: BTreeMap<String, String>,
+    }
+
+    let manifest = Manifest {
+        package: Package {
+            name: "demo".to_string(),
+            version: "0.1.0".to_string(),
+        },
+        bin: vec![
+            Bin {
+                name: "a".to_string(),
+                path: "src/a.rs".to_string(),
+                test: None,
+            },
+            Bin {
+                name: "b".to_string(),
+                path: "src/b.rs".to_string(),
+                test: Some(false),
+            },
+        ],
+        dependencies: BTreeMap::from([
+            ("facet".to_string(), "0.41".to_string()),
+            ("serde-json".to_string(), "1".to_string()),
+        ]),
+    };
+
+    let toml = facet_toml::to_string(&manifest).unwrap();
+    assert_eq!(
+        toml,
+        r#"dependencies = { facet = "0.41", serde-json = "1" }
+
+[package]
+name = "demo"
+version = "0.1.0"
+
+[[bin]]
+name = "a"
+path = "src/a.rs"
+
+[[bin]]
+name = "b"
+path = "src/b.rs"
+test = false
+"#
+    );
+
+    let parsed: Manifest = facet_toml::from_str(&toml).unwrap();
+    assert_eq!(parsed, manifest);
+}
+
+#[test]
+fn test_serialize_nested_tables_and_quoted_keys() {
+    #[derive(Debug, Facet, PartialEq)]
+    struct Inner {
+        depth: u32,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Middle {
+        inner: Inner,
+    }
+
+    #[derive(Debug, Facet, PartialEq)]
+    struct Outer {
+        middle: Middle,
+        labels: BTreeMap<String, u32>,
+        groups: Vec<Vec<u32>>,
+    }
+
+    let outer = Outer {
+        middle: Middle {
+            inner: Inner { depth: 2 },
+        },
+        labels: BTreeMap::from([("with space".to_string(), 1)]),
+        groups: vec![vec![1], vec![2, 3]],
+    };
+
+    let toml = facet_toml::to_string(&outer).unwrap();
+    assert_eq!(
+        toml,
+        "labels = { \"with space\" = 1 }\ngroups = [[1], [2, 3]]\n\n[middle.inner]\ndepth = 2\n"
+    );
+    let parsed: Outer = facet_toml::from_str(&toml).unwrap();
+    assert_eq!(parsed, outer);
+}
+
+#[test]
+fn test_wrong_type_error_location() {
+    #[derive(Debug, Facet)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Debug, Facet)]
+    struct Config {
+        server: Server,
+    }
+
+    let input = "[server]\nhost = \"localhost\"\nport = \"eighty\"\n";
+    let err = facet_toml::from_str::<Config>(input).unwrap_err();
+    let location = facet_toml::error_location(input, &err).unwrap();
+    assert_eq!(location, facet_toml::Location { line: 3, column: 8 });
+    assert_eq!(location.to_string(), "3:8");
 }
 
 #[test]
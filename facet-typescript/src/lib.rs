@@ -193,7 +193,8 @@ impl TypeScriptGenerator {
                     }
 
                     // Generate doc comment for field
-                    if !field.doc.is_empty() {
+                    let unit = field.unit();
+                    if !field.doc.is_empty() || unit.is_some() {
                         self.write_indent();
                         self.output.push_str("/**\n");
                         for line in field.doc {
@@ -202,6 +203,10 @@ impl TypeScriptGenerator {
                             self.output.push_str(line);
                             self.output.push('\n');
                         }
+                        if let Some(unit) = unit {
+                            self.write_indent();
+                            writeln!(self.output, " * @unit {unit}").unwrap();
+                        }
                         self.write_indent();
                         self.output.push_str(" */\n");
                     }
@@ -401,6 +406,30 @@ mod tests {
         insta::assert_snapshot!(ts);
     }
 
+    #[test]
+    fn test_field_unit() {
+        #[derive(Facet)]
+        struct Timeouts {
+            /// How long to wait for a connection
+            #[facet(unit = "seconds")]
+            connect: u32,
+            #[facet(unit = "bytes")]
+            max_body: u64,
+        }
+
+        let ts = to_typescript::<Timeouts>();
+        assert!(
+            ts.contains(
+                "How long to wait for a connection\n   * @unit seconds\n   */\n  connect: number;"
+            ),
+            "{ts}"
+        );
+        assert!(
+            ts.contains("/**\n   * @unit bytes\n   */\n  max_body: number;"),
+            "{ts}"
+        );
+    }
+
     #[test]
     fn test_simple_enum() {
         #[derive(Facet)]
@@ -0,0 +1,352 @@
+//! Indexing into values: `value["users"][0]["name"]`, `get`, and JSON pointers.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::ops::{Index, IndexMut};
+
+use crate::object::VObject;
+use crate::value::{Value, ValueType};
+
+static NULL: Value = Value::NULL;
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for usize {}
+    impl Sealed for str {}
+    impl Sealed for alloc::string::String {}
+    impl<T: Sealed + ?Sized> Sealed for &T {}
+}
+
+/// A type that can index into a [`Value`]: `usize` for arrays, strings for objects.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait ValueIndex: private::Sealed {
+    #[doc(hidden)]
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value>;
+
+    #[doc(hidden)]
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value>;
+
+    #[doc(hidden)]
+    fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value;
+}
+
+impl ValueIndex for usize {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        value.as_array()?.get(*self)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        value.as_array_mut()?.get_mut(*self)
+    }
+
+    fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+        let value_type = value.value_type();
+        let Some(array) = value.as_array_mut() else {
+            panic!("cannot index into a {value_type:?} value with an integer");
+        };
+        let len = array.len();
+        array
+            .get_mut(*self)
+            .unwrap_or_else(|| panic!("index {self} out of bounds for array of length {len}"))
+    }
+}
+
+impl ValueIndex for str {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        value.as_object()?.get(self)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        value.as_object_mut()?.get_mut(self)
+    }
+
+    fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+        if value.is_null() {
+            *value = VObject::new().into();
+        }
+        let value_type = value.value_type();
+        let Some(object) = value.as_object_mut() else {
+            panic!("cannot index into a {value_type:?} value with a string");
+        };
+        if !object.contains_key(self) {
+            object.insert(self, Value::NULL);
+        }
+        object.get_mut(self).unwrap()
+    }
+}
+
+impl ValueIndex for String {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        self.as_str().index_into(value)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        self.as_str().index_into_mut(value)
+    }
+
+    fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+        self.as_str().index_or_insert(value)
+    }
+}
+
+impl<T: ValueIndex + ?Sized> ValueIndex for &T {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        (**self).index_into(value)
+    }
+
+    fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        (**self).index_into_mut(value)
+    }
+
+    fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+        (**self).index_or_insert(value)
+    }
+}
+
+impl Value {
+    /// Gets an array element or object entry, or `None` if it doesn't exist
+    /// or this value is of the wrong type.
+    #[must_use]
+    pub fn get<I: ValueIndex>(&self, index: I) -> Option<&Value> {
+        index.index_into(self)
+    }
+
+    /// Mutable version of [`Value::get`].
+    pub fn get_mut<I: ValueIndex>(&mut self, index: I) -> Option<&mut Value> {
+        index.index_into_mut(self)
+    }
+
+    /// Looks up a value by [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901),
+    /// e.g. `/users/0/name`.
+    ///
+    /// The empty pointer refers to the whole value. Returns `None` if the
+    /// pointer is malformed or doesn't resolve.
+    #[must_use]
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        let mut current = self;
+        for token in pointer_tokens(pointer)? {
+            current = match current.value_type() {
+                ValueType::Object => current.as_object()?.get(&token)?,
+                ValueType::Array => current.as_array()?.get(parse_array_index(&token)?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Mutable version of [`Value::pointer`].
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        let mut current = self;
+        for token in pointer_tokens(pointer)? {
+            current = match current.value_type() {
+                ValueType::Object => current.as_object_mut()?.get_mut(&token)?,
+                ValueType::Array => current
+                    .as_array_mut()?
+                    .get_mut(parse_array_index(&token)?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Replaces the value at `pointer`, returning the previous one.
+    ///
+    /// The parent of the target must exist. Objects get a new entry if the
+    /// last token is missing; arrays accept an existing index, or `-` to append.
+    /// Returns `Err(value)` if the pointer doesn't resolve.
+    pub fn set_pointer(&mut self, pointer: &str, value: impl Into<Value>) -> Result<Value, Value> {
+        let value = value.into();
+        if pointer.is_empty() {
+            return Ok(core::mem::replace(self, value));
+        }
+        let Some((parent, last)) = pointer.rsplit_once('/') else {
+            return Err(value);
+        };
+        let Some(parent) = self.pointer_mut(parent) else {
+            return Err(value);
+        };
+        let last = unescape_token(last);
+        match parent.value_type() {
+            ValueType::Object => {
+                let object = parent.as_object_mut().unwrap();
+                Ok(object.insert(last.as_ref(), value).unwrap_or(Value::NULL))
+            }
+            ValueType::Array => {
+                let array = parent.as_array_mut().unwrap();
+                if last == "-" {
+                    array.push(value);
+                    return Ok(Value::NULL);
+                }
+                match parse_array_index(&last).and_then(|index| array.get_mut(index)) {
+                    Some(slot) => Ok(core::mem::replace(slot, value)),
+                    None => Err(value),
+                }
+            }
+            _ => Err(value),
+        }
+    }
+
+    /// Removes the value at `pointer` from its parent object or array and returns it.
+    pub fn remove_pointer(&mut self, pointer: &str) -> Option<Value> {
+        let (parent, last) = pointer.rsplit_once('/')?;
+        let parent = self.pointer_mut(parent)?;
+        let last = unescape_token(last);
+        match parent.value_type() {
+            ValueType::Object => parent.as_object_mut()?.remove(&last),
+            ValueType::Array => parent.as_array_mut()?.remove(parse_array_index(&last)?),
+            _ => None,
+        }
+    }
+}
+
+/// Splits a JSON pointer into unescaped reference tokens.
+fn pointer_tokens(pointer: &str) -> Option<impl Iterator<Item = Cow<'_, str>>> {
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        return None;
+    }
+    Some(pointer.split('/').skip(1).map(unescape_token))
+}
+
+fn unescape_token(token: &str) -> Cow<'_, str> {
+    if token.contains('~') {
+        Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Array indices in pointers are plain decimal numbers without leading zeros.
+fn parse_array_index(token: &str) -> Option<usize> {
+    if token.is_empty()
+        || (token.len() > 1 && token.starts_with('0'))
+        || !token.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    token.parse().ok()
+}
+
+impl<I: ValueIndex> Index<I> for Value {
+    type Output = Value;
+
+    /// Indexes into an array or object.
+    ///
+    /// Returns [`Value::NULL`] if the element doesn't exist or this value is of
+    /// the wrong type, so lookups can be chained: `value["users"][0]["name"]`.
+    fn index(&self, index: I) -> &Value {
+        index.index_into(self).unwrap_or(&NULL)
+    }
+}
+
+impl<I: ValueIndex> IndexMut<I> for Value {
+    /// Mutably indexes into an array or object.
+    ///
+    /// Indexing an object with a missing key inserts `null` under that key,
+    /// and indexing a `null` value with a string turns it into an empty object
+    /// first, so nested entries can be created in place:
+    /// `value["server"]["port"] = 8080.into()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds for an array, or if this value is
+    /// neither an array nor (for string keys) an object or `null`.
+    fn index_mut(&mut self, index: I) -> &mut Value {
+        index.index_or_insert(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value;
+
+    use super::*;
+
+    #[test]
+    fn test_index_chaining() {
+        let v = value!({
+            "users": [
+                { "name": "alice", "roles": ["admin"] },
+                { "name": "bob" }
+            ],
+            "a/b": 1,
+            "m~n": 2
+        });
+        assert_eq!(v["users"][0]["name"], Value::from("alice"));
+        assert_eq!(v["users"][1]["name"].as_string().unwrap().as_str(), "bob");
+        // Missing entries and type mismatches index to null
+        assert!(v["users"][5]["name"].is_null());
+        assert!(v["nope"]["deeper"].is_null());
+        assert!(v["users"]["name"].is_null());
+        assert!(v.get("nope").is_none());
+        assert!(v.get(0).is_none());
+        assert_eq!(v.get(String::from("a/b")), Some(&Value::from(1)));
+    }
+
+    #[test]
+    fn test_index_mut_creates_entries() {
+        let mut v = Value::NULL;
+        v["server"]["port"] = Value::from(8080);
+        v["server"]["host"] = Value::from("localhost");
+        assert_eq!(v.pointer("/server/port"), Some(&Value::from(8080)));
+
+        let mut v = value!({ "users": [{ "name": "alice" }, { "name": "bob" }] });
+        v["users"][1]["name"] = Value::from("carol");
+        assert_eq!(v["users"][1]["name"], Value::from("carol"));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_index_mut_out_of_bounds() {
+        let mut v = value!({ "users": [{ "name": "alice" }, { "name": "bob" }] });
+        v["users"][2] = Value::NULL;
+    }
+
+    #[test]
+    fn test_pointer() {
+        let v = value!({
+            "users": [
+                { "name": "alice", "roles": ["admin"] },
+                { "name": "bob" }
+            ],
+            "a/b": 1,
+            "m~n": 2
+        });
+        assert_eq!(v.pointer(""), Some(&v));
+        assert_eq!(v.pointer("/users/0/roles/0"), Some(&Value::from("admin")));
+        assert_eq!(v.pointer("/a~1b"), Some(&Value::from(1)));
+        assert_eq!(v.pointer("/m~0n"), Some(&Value::from(2)));
+        assert_eq!(v.pointer("users"), None);
+        assert_eq!(v.pointer("/users/01"), None);
+        assert_eq!(v.pointer("/users/+1"), None);
+        assert_eq!(v.pointer("/users/2"), None);
+    }
+
+    #[test]
+    fn test_pointer_mutation() {
+        let mut v = value!({
+            "users": [
+                { "name": "alice", "roles": ["admin"] },
+                { "name": "bob" }
+            ]
+        });
+        *v.pointer_mut("/users/1/name").unwrap() = Value::from("dave");
+        assert_eq!(v["users"][1]["name"], Value::from("dave"));
+
+        assert_eq!(v.set_pointer("/users/1/age", 42), Ok(Value::NULL));
+        assert_eq!(v.set_pointer("/users/0/roles/-", "ops"), Ok(Value::NULL));
+        assert_eq!(
+            v.set_pointer("/users/0/roles/0", "owner"),
+            Ok(Value::from("admin"))
+        );
+        assert_eq!(v["users"][0]["roles"], value!(["owner", "ops"]));
+        assert!(v.set_pointer("/missing/child", 1).is_err());
+
+        assert_eq!(
+            v.remove_pointer("/users/0"),
+            Some(value!({ "name": "alice", "roles": ["owner", "ops"] }))
+        );
+        assert_eq!(v["users"][0]["age"], Value::from(42));
+        assert_eq!(v.remove_pointer("/users/5"), None);
+    }
+}
@@ -0,0 +1,409 @@
+//! Map values to flat key-value entries, for key-value stores like Redis.
+//!
+//! [`to_kv`] flattens a value into `{prefix}:{path} -> scalar` entries, where
+//! the path joins field names, map keys, list indices and variant names with
+//! `:`. [`from_kv`] rebuilds the value from the entries found by a scan of
+//! `{prefix}:*`, using the target shape to tell lists from maps and scalars
+//! apart. [`to_kv_hash`] and [`from_kv_hash`] do the same for the fields of a
+//! single hash, so a whole object can be stored under one key.
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_value::{from_kv, to_kv};
+//!
+//! #[derive(Debug, Facet, PartialEq)]
+//! struct Session {
+//!     user: String,
+//!     roles: Vec<String>,
+//!     expires: Option<u64>,
+//! }
+//!
+//! let session = Session {
+//!     user: "amos".into(),
+//!     roles: vec!["admin".into()],
+//!     expires: Some(3600),
+//! };
+//! let entries = to_kv("session:42", &session).unwrap();
+//! assert_eq!(
+//!     entries,
+//!     [
+//!         ("session:42:user".to_string(), "amos".to_string()),
+//!         ("session:42:roles:0".to_string(), "admin".to_string()),
+//!         ("session:42:expires".to_string(), "3600".to_string()),
+//!     ]
+//! );
+//! assert_eq!(from_kv::<Session, _, _>("session:42", entries).unwrap(), session);
+//! ```
+//!
+//! A `:` or `\` inside a field name or map key is escaped with a `\`, so
+//! `{"a:b": 1}` is stored under `{prefix}:a\:b`.
+//!
+//! `None` and empty collections produce no entries, so they can't be told
+//! apart when reading back: missing optional fields become `None`, and
+//! missing collections become empty. Map keys must be strings, and tagged
+//! or untagged enums are not supported.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use facet_core::{Def, Facet, Shape, StructKind, Type, UserType};
+use facet_reflect::{HasFields, Peek};
+
+use crate::{VArray, VObject, Value, ValueError, ValueErrorKind, from_value};
+
+/// Separator between path segments
+const SEPARATOR: char = ':';
+
+/// Escapes a separator or itself inside a path segment
+const ESCAPE: char = '\\';
+
+/// Flatten `value` into `{prefix}:{path} -> scalar` entries.
+///
+/// A scalar at the root is stored under `prefix` itself.
+pub fn to_kv<'facet, T: Facet<'facet>>(
+    prefix: &str,
+    value: &T,
+) -> Result<Vec<(String, String)>, ValueError> {
+    let mut entries = Vec::new();
+    flatten(Peek::new(value), &mut String::from(prefix), &mut entries)?;
+    Ok(entries)
+}
+
+/// Flatten `value` into `{path} -> scalar` fields of a single hash.
+///
+/// A scalar at the root is stored under the empty field name.
+pub fn to_kv_hash<'facet, T: Facet<'facet>>(
+    value: &T,
+) -> Result<Vec<(String, String)>, ValueError> {
+    let mut entries = Vec::new();
+    flatten(Peek::new(value), &mut String::new(), &mut entries)?;
+    Ok(entries)
+}
+
+/// Rebuild a value from the entries written by [`to_kv`] under `prefix`.
+///
+/// Entries outside of `prefix` are ignored, so the result of a broader scan
+/// can be passed as is.
+pub fn from_kv<T, K, V>(
+    prefix: &str,
+    entries: impl IntoIterator<Item = (K, V)>,
+) -> Result<T, ValueError>
+where
+    T: Facet<'static>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut root = Node::default();
+    for (key, value) in entries {
+        let key = key.as_ref();
+        let Some(path) = key.strip_prefix(prefix) else {
+            continue;
+        };
+        if path.is_empty() {
+            root.insert("", value.as_ref());
+        } else if let Some(path) = path.strip_prefix(SEPARATOR) {
+            root.insert(path, value.as_ref());
+        }
+    }
+    from_value(root.to_value(T::SHAPE)?)
+}
+
+/// Rebuild a value from the hash fields written by [`to_kv_hash`].
+pub fn from_kv_hash<T, K, V>(fields: impl IntoIterator<Item = (K, V)>) -> Result<T, ValueError>
+where
+    T: Facet<'static>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut root = Node::default();
+    for (field, value) in fields {
+        root.insert(field.as_ref(), value.as_ref());
+    }
+    from_value(root.to_value(T::SHAPE)?)
+}
+
+fn unsupported(message: impl Into<String>) -> ValueError {
+    ValueError::new(ValueErrorKind::Unsupported {
+        message: message.into(),
+    })
+}
+
+fn flatten(
+    peek: Peek<'_, '_>,
+    path: &mut String,
+    entries: &mut Vec<(String, String)>,
+) -> Result<(), ValueError> {
+    let shape = peek.shape();
+    match shape.def {
+        Def::Scalar => {
+            let value = match peek.as_str() {
+                Some(s) => s.to_string(),
+                None => peek.to_string(),
+            };
+            entries.push((path.clone(), value));
+            return Ok(());
+        }
+        Def::Option(_) => {
+            if let Some(inner) = peek.into_option()?.value() {
+                flatten(inner, path, entries)?;
+            }
+            return Ok(());
+        }
+        Def::Pointer(_) => {
+            if let Some(inner) = peek.into_pointer()?.borrow_inner() {
+                flatten(inner, path, entries)?;
+            }
+            return Ok(());
+        }
+        Def::List(_) | Def::Array(_) | Def::Slice(_) => {
+            for (index, item) in peek.into_list_like()?.iter().enumerate() {
+                segment(path, &index.to_string(), |path| {
+                    flatten(item, path, entries)
+                })?;
+            }
+            return Ok(());
+        }
+        Def::Set(_) => {
+            for (index, item) in peek.into_set()?.iter().enumerate() {
+                segment(path, &index.to_string(), |path| {
+                    flatten(item, path, entries)
+                })?;
+            }
+            return Ok(());
+        }
+        Def::Map(_) => {
+            for (key, value) in peek.into_map()?.iter() {
+                let key = key
+                    .as_str()
+                    .ok_or_else(|| unsupported("key-value mapping needs string map keys"))?;
+                segment(path, key, |path| flatten(value, path, entries))?;
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    match shape.ty {
+        Type::User(UserType::Struct(_)) if shape.inner.is_some() => {
+            let inner = peek
+                .into_struct()?
+                .field(0)
+                .map_err(|e| unsupported(e.to_string()))?;
+            flatten(inner, path, entries)
+        }
+        Type::User(UserType::Struct(_)) => {
+            for (field, value) in peek.into_struct()?.fields_for_serialize() {
+                segment(path, &field.name, |path| flatten(value, path, entries))?;
+            }
+            Ok(())
+        }
+        Type::User(UserType::Enum(_)) => {
+            if shape.is_untagged() || shape.get_tag_attr().is_some() {
+                return Err(unsupported(
+                    "key-value mapping only supports externally tagged enums",
+                ));
+            }
+            let peek_enum = peek.into_enum()?;
+            let variant = peek_enum
+                .active_variant()
+                .map_err(|e| unsupported(e.to_string()))?;
+            match variant.data.kind {
+                StructKind::Unit => {
+                    entries.push((path.clone(), variant.name.to_string()));
+                    Ok(())
+                }
+                StructKind::Tuple | StructKind::TupleStruct if variant.data.fields.len() == 1 => {
+                    let value = peek_enum
+                        .field(0)
+                        .map_err(|e| unsupported(e.to_string()))?
+                        .ok_or_else(|| unsupported("missing variant field"))?;
+                    segment(path, variant.name, |path| flatten(value, path, entries))
+                }
+                _ => segment(path, variant.name, |path| {
+                    for (field, value) in peek_enum.fields_for_serialize() {
+                        segment(path, &field.name, |path| flatten(value, path, entries))?;
+                    }
+                    Ok(())
+                }),
+            }
+        }
+        _ => Err(unsupported(format!(
+            "key-value mapping does not support {shape}"
+        ))),
+    }
+}
+
+/// Runs `f` with `name` appended to the path, escaped.
+fn segment(
+    path: &mut String,
+    name: &str,
+    f: impl FnOnce(&mut String) -> Result<(), ValueError>,
+) -> Result<(), ValueError> {
+    let len = path.len();
+    if !path.is_empty() {
+        path.push(SEPARATOR);
+    }
+    for c in name.chars() {
+        if c == SEPARATOR || c == ESCAPE {
+            path.push(ESCAPE);
+        }
+        path.push(c);
+    }
+    let result = f(path);
+    path.truncate(len);
+    result
+}
+
+/// A tree of entries, split on the path separator
+#[derive(Default)]
+struct Node {
+    value: Option<String>,
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn insert(&mut self, path: &str, value: &str) {
+        if path.is_empty() {
+            self.value = Some(value.to_string());
+            return;
+        }
+        let mut head = String::new();
+        let mut rest = "";
+        let mut chars = path.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                ESCAPE => head.extend(chars.next().map(|(_, c)| c)),
+                SEPARATOR => {
+                    rest = &path[index + 1..];
+                    break;
+                }
+                c => head.push(c),
+            }
+        }
+        self.children.entry(head).or_default().insert(rest, value);
+    }
+
+    /// Children in index order, for sequences
+    fn items(&self) -> Result<Vec<&Node>, ValueError> {
+        let mut items = self
+            .children
+            .iter()
+            .map(|(index, node)| {
+                index
+                    .parse::<usize>()
+                    .map(|index| (index, node))
+                    .map_err(|_| unsupported(format!("expected a list index, got `{index}`")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        items.sort_by_key(|&(index, _)| index);
+        Ok(items.into_iter().map(|(_, node)| node).collect())
+    }
+
+    fn to_value(&self, shape: &'static Shape) -> Result<Value, ValueError> {
+        match shape.def {
+            Def::Scalar => {
+                return Ok(match &self.value {
+                    Some(value) => Value::from(value.as_str()),
+                    None => Value::NULL,
+                });
+            }
+            Def::Option(def) => return self.to_value(def.t()),
+            Def::Pointer(def) => {
+                let pointee = def.pointee().ok_or_else(|| unsupported("opaque pointer"))?;
+                return self.to_value(pointee);
+            }
+            Def::List(_) | Def::Array(_) | Def::Slice(_) | Def::Set(_) => {
+                let t = match shape.def {
+                    Def::List(def) => def.t(),
+                    Def::Array(def) => def.t(),
+                    Def::Slice(def) => def.t(),
+                    Def::Set(def) => def.t(),
+                    _ => unreachable!(),
+                };
+                let mut array = VArray::new();
+                for item in self.items()? {
+                    array.push(item.to_value(t)?);
+                }
+                return Ok(array.into());
+            }
+            Def::Map(def) => {
+                let mut object = VObject::new();
+                for (key, node) in &self.children {
+                    object.insert(key.as_str(), node.to_value(def.v())?);
+                }
+                return Ok(object.into());
+            }
+            _ => {}
+        }
+
+        match shape.ty {
+            Type::User(UserType::Struct(_)) if shape.inner.is_some() => {
+                self.to_value(shape.inner.unwrap())
+            }
+            Type::User(UserType::Struct(struct_type)) => {
+                if struct_type.kind == StructKind::Tuple {
+                    let mut array = VArray::new();
+                    for (index, field) in struct_type.fields.iter().enumerate() {
+                        let node = self.children.get(&index.to_string());
+                        array.push(node.unwrap_or(&Node::default()).to_value(field.shape())?);
+                    }
+                    return Ok(array.into());
+                }
+                self.fields(struct_type.fields)
+            }
+            Type::User(UserType::Enum(enum_type)) => {
+                if let Some(name) = &self.value {
+                    return Ok(Value::from(name.as_str()));
+                }
+                let Some((name, node)) = self.children.iter().next() else {
+                    return Ok(Value::NULL);
+                };
+                let variant = enum_type
+                    .variants
+                    .iter()
+                    .find(|variant| variant.name == name)
+                    .ok_or_else(|| unsupported(format!("unknown variant `{name}`")))?;
+                let value = match variant.data.fields {
+                    [field] if variant.data.kind != StructKind::Struct => {
+                        node.to_value(field.shape())?
+                    }
+                    fields if variant.data.kind == StructKind::Struct => node.fields(fields)?,
+                    fields => {
+                        let mut array = VArray::new();
+                        for (index, field) in fields.iter().enumerate() {
+                            let node = node.children.get(&index.to_string());
+                            array.push(node.unwrap_or(&Node::default()).to_value(field.shape())?);
+                        }
+                        array.into()
+                    }
+                };
+                let mut object = VObject::new();
+                object.insert(name.as_str(), value);
+                Ok(object.into())
+            }
+            _ => Err(unsupported(format!(
+                "key-value mapping does not support {shape}"
+            ))),
+        }
+    }
+
+    /// An object with one entry per field present in the tree
+    fn fields(&self, fields: &'static [facet_core::Field]) -> Result<Value, ValueError> {
+        let mut object = VObject::new();
+        for field in fields {
+            if field.is_flattened() {
+                let value = self.to_value(field.shape())?;
+                if let Some(inner) = value.as_object() {
+                    for (key, value) in inner.iter() {
+                        object.insert(key.clone(), value.clone());
+                    }
+                }
+            } else if let Some(node) = self.children.get(field.name) {
+                object.insert(field.name, node.to_value(field.shape())?);
+            }
+        }
+        Ok(object.into())
+    }
+}
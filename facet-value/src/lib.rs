@@ -50,6 +50,11 @@ pub use datetime::*;
 mod other;
 pub use other::{OtherKind, VQName, VUuid};
 
+#[cfg(feature = "alloc")]
+mod index;
+#[cfg(feature = "alloc")]
+pub use index::ValueIndex;
+
 #[cfg(feature = "alloc")]
 mod facet_impl;
 #[cfg(feature = "alloc")]
@@ -62,6 +67,16 @@ pub use deserialize::ValueErrorReport;
 #[cfg(feature = "alloc")]
 pub use deserialize::{PathSegment, ValueError, ValueErrorKind, from_value};
 
+#[cfg(feature = "alloc")]
+mod kv;
+#[cfg(feature = "alloc")]
+pub use kv::{from_kv, from_kv_hash, to_kv, to_kv_hash};
+
+#[cfg(feature = "alloc")]
+mod seed;
+#[cfg(feature = "alloc")]
+pub use seed::seed_values;
+
 #[cfg(feature = "alloc")]
 mod format;
 #[cfg(feature = "alloc")]
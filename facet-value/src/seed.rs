@@ -0,0 +1,337 @@
+//! Sample documents for a shape, to seed fuzzers with.
+//!
+//! A fuzzer that starts from random bytes spends most of its time on input
+//! the parser rejects straight away. [`seed_values`] walks a [`Shape`] and
+//! builds a handful of documents that deserialize into it, which can then be
+//! written out with any format's serializer as a starting corpus:
+//!
+//! ```
+//! use facet::Facet;
+//! use facet_value::seed_values;
+//!
+//! #[derive(Facet)]
+//! struct Request {
+//!     path: String,
+//!     retries: Option<u8>,
+//! }
+//!
+//! let seeds = seed_values(Request::SHAPE);
+//! assert_eq!(seeds.len(), 3);
+//! assert_eq!(seeds[0], facet_value::value!({"path": "", "retries": null}));
+//! ```
+//!
+//! The documents follow the conventions of the format crates: tuple structs
+//! are arrays, enums are externally tagged unless the shape says otherwise,
+//! and scalars that aren't primitives are written as their `Display` form.
+//! Types the generator doesn't know how to build are left out, so a field of
+//! such a type makes the whole document go missing rather than be invalid.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use facet_core::{
+    Characteristic, Def, EnumType, ScalarType, Shape, StructKind, StructType, Type, UserType,
+    Variant,
+};
+use facet_reflect::Partial;
+
+use crate::{VArray, VObject, Value};
+
+/// Each seed is small, typical or extreme, in turn
+const FLAVORS: usize = 3;
+
+/// How deep the generator goes before it stops adding optional values and
+/// collection items, so recursive types still get finite documents
+const MAX_DEPTH: usize = 6;
+
+/// Builds documents that deserialize into a value of `shape`.
+///
+/// There are at least three seeds: one with empty collections, zeros and
+/// `None`s, one with typical values, and one with extremes such as
+/// `u64::MAX` and non-ASCII strings. Shapes containing enums get more, so
+/// every variant of the largest enum shows up in at least one seed.
+/// Duplicates are removed.
+pub fn seed_values(shape: &'static Shape) -> Vec<Value> {
+    let count = FLAVORS.max(widest_enum(shape, 0));
+    let mut seeds: Vec<Value> = Vec::new();
+    for index in 0..count {
+        if let Some(value) = (Seed { index }).value(shape, 0)
+            && !seeds.contains(&value)
+        {
+            seeds.push(value);
+        }
+    }
+    seeds
+}
+
+/// The number of variants of the largest enum reachable from `shape`.
+fn widest_enum(shape: &'static Shape, depth: usize) -> usize {
+    if depth > MAX_DEPTH {
+        return 0;
+    }
+    let children = |shapes: &mut dyn Iterator<Item = &'static Shape>| {
+        shapes
+            .map(|child| widest_enum(child, depth + 1))
+            .max()
+            .unwrap_or(0)
+    };
+    let fields = |st: &StructType| children(&mut st.fields.iter().map(|f| f.shape()));
+    match (&shape.ty, &shape.def) {
+        (Type::User(UserType::Struct(st)), _) => fields(st),
+        (Type::User(UserType::Enum(et)), _) => et
+            .variants
+            .iter()
+            .map(|variant| fields(&variant.data))
+            .max()
+            .unwrap_or(0)
+            .max(et.variants.len()),
+        (_, Def::Option(def)) => widest_enum(def.t(), depth + 1),
+        (_, Def::List(def)) => widest_enum(def.t(), depth + 1),
+        (_, Def::Slice(def)) => widest_enum(def.t(), depth + 1),
+        (_, Def::Array(def)) => widest_enum(def.t(), depth + 1),
+        (_, Def::Set(def)) => widest_enum(def.t(), depth + 1),
+        (_, Def::Map(def)) => widest_enum(def.v(), depth + 1),
+        (_, Def::Pointer(def)) => def.pointee().map_or(0, |p| widest_enum(p, depth + 1)),
+        _ => shape.inner.map_or(0, |inner| widest_enum(inner, depth + 1)),
+    }
+}
+
+/// The choices made while building one seed.
+#[derive(Clone, Copy)]
+struct Seed {
+    index: usize,
+}
+
+impl Seed {
+    /// 0 for small values, 1 for typical ones, 2 for extremes
+    fn flavor(self) -> usize {
+        self.index % FLAVORS
+    }
+
+    /// The seed used for the `n`th item of a collection, so items differ
+    fn nth(self, n: usize) -> Self {
+        Seed {
+            index: self.index + n + 1,
+        }
+    }
+
+    fn value(self, shape: &'static Shape, depth: usize) -> Option<Value> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+
+        match &shape.def {
+            Def::Option(def) => {
+                if self.flavor() == 0 || depth == MAX_DEPTH {
+                    return Some(Value::NULL);
+                }
+                return Some(self.value(def.t(), depth + 1).unwrap_or(Value::NULL));
+            }
+            Def::Pointer(def) => return self.value(def.pointee()?, depth + 1),
+            _ => {}
+        }
+
+        // Proxied types are written as their proxy
+        if let Some(proxy) = shape.proxy {
+            return self.value(proxy.shape, depth);
+        }
+        if let Some(builder) = shape.builder_shape {
+            return self.value(builder, depth);
+        }
+        if let Some(inner) = shape.inner
+            && !matches!(
+                shape.def,
+                Def::List(_) | Def::Map(_) | Def::Set(_) | Def::Array(_)
+            )
+        {
+            return self.value(inner, depth);
+        }
+
+        match &shape.ty {
+            Type::User(UserType::Struct(st)) => return self.fields(st, depth),
+            Type::User(UserType::Enum(et)) => return self.variant(shape, et, depth),
+            _ => {}
+        }
+
+        match &shape.def {
+            Def::Scalar => self.scalar(shape),
+            Def::List(def) => Some(self.items(def.t(), depth)),
+            Def::Slice(def) => Some(self.items(def.t(), depth)),
+            Def::Set(def) => Some(self.items(def.t(), depth)),
+            Def::Array(def) => (0..def.n)
+                .map(|n| self.nth(n).value(def.t(), depth + 1))
+                .collect::<Option<VArray>>()
+                .map(Value::from),
+            Def::Map(def) => Some(self.entries(def.k(), def.v(), depth)),
+            Def::DynamicValue(_) => Some(match self.flavor() {
+                0 => Value::NULL,
+                1 => Value::from("seed"),
+                _ => crate::value!({"key": [1, true, null]}),
+            }),
+            _ => None,
+        }
+    }
+
+    /// How many items a collection gets
+    fn len(self, depth: usize) -> usize {
+        if depth >= MAX_DEPTH {
+            return 0;
+        }
+        [0, 1, 3][self.flavor()]
+    }
+
+    /// A list of items; items that can't be built leave it empty.
+    fn items(self, item: &'static Shape, depth: usize) -> Value {
+        (0..self.len(depth))
+            .map(|n| self.nth(n).value(item, depth + 1))
+            .collect::<Option<VArray>>()
+            .unwrap_or_default()
+            .into()
+    }
+
+    /// A map; only keys that are written as strings are supported, other
+    /// maps are left empty.
+    fn entries(self, key: &'static Shape, value: &'static Shape, depth: usize) -> Value {
+        let mut object = VObject::new();
+        for n in 0..self.len(depth) {
+            let seed = self.nth(n);
+            let (Some(key), Some(value)) =
+                (seed.value(key, depth + 1), seed.value(value, depth + 1))
+            else {
+                return VObject::new().into();
+            };
+            let Some(key) = key.as_string() else {
+                return VObject::new().into();
+            };
+            object.insert(key.as_str(), value);
+        }
+        object.into()
+    }
+
+    fn fields(self, st: &StructType, depth: usize) -> Option<Value> {
+        match st.kind {
+            StructKind::Unit => Some(Value::NULL),
+            StructKind::Tuple | StructKind::TupleStruct => st
+                .fields
+                .iter()
+                .map(|field| self.value(field.shape(), depth + 1))
+                .collect::<Option<VArray>>()
+                .map(Value::from),
+            StructKind::Struct => {
+                let mut object = VObject::new();
+                for field in st.fields {
+                    if field.should_skip_deserializing() {
+                        continue;
+                    }
+                    let value = match self.value(field.shape(), depth + 1) {
+                        Some(value) => value,
+                        None if field.has_default() => continue,
+                        None => return None,
+                    };
+                    if field.is_flattened() {
+                        for (key, value) in value.as_object()?.iter() {
+                            object.insert(key.clone(), value.clone());
+                        }
+                    } else {
+                        object.insert(field.name, value);
+                    }
+                }
+                Some(object.into())
+            }
+        }
+    }
+
+    /// One variant, taking turns between seeds; variants that can't be
+    /// built are skipped.
+    fn variant(self, shape: &'static Shape, et: &EnumType, depth: usize) -> Option<Value> {
+        let count = et.variants.len();
+        (0..count).find_map(|offset| {
+            let variant = &et.variants[(self.index + offset) % count];
+            self.tagged(shape, variant, depth)
+        })
+    }
+
+    fn tagged(self, shape: &'static Shape, variant: &Variant, depth: usize) -> Option<Value> {
+        let data = &variant.data;
+        let payload = match (data.kind, data.fields) {
+            (StructKind::Unit, _) => None,
+            (StructKind::TupleStruct | StructKind::Tuple, [field]) => {
+                Some(self.value(field.shape(), depth + 1)?)
+            }
+            _ => Some(self.fields(data, depth)?),
+        };
+
+        if shape.is_untagged() {
+            return Some(payload.unwrap_or(Value::NULL));
+        }
+        match (shape.get_tag_attr(), shape.get_content_attr()) {
+            (Some(tag), Some(content)) => {
+                let mut object = VObject::new();
+                object.insert(tag, variant.name);
+                if let Some(payload) = payload {
+                    object.insert(content, payload);
+                }
+                Some(object.into())
+            }
+            (Some(tag), None) => {
+                // Internally tagged: the tag goes next to the fields, so the
+                // payload has to be an object
+                let mut object = match payload {
+                    Some(payload) => payload.as_object()?.clone(),
+                    None => VObject::new(),
+                };
+                object.insert(tag, variant.name);
+                Some(object.into())
+            }
+            (None, _) => Some(match payload {
+                Some(payload) => {
+                    let mut object = VObject::new();
+                    object.insert(variant.name, payload);
+                    object.into()
+                }
+                None => Value::from(variant.name),
+            }),
+        }
+    }
+
+    fn scalar(self, shape: &'static Shape) -> Option<Value> {
+        let flavor = self.flavor();
+        let value = match shape.scalar_type() {
+            Some(ScalarType::Unit) => Value::NULL,
+            Some(ScalarType::Bool) => Value::from(flavor != 0),
+            Some(ScalarType::Char) => Value::from(["a", "z", "é"][flavor]),
+            Some(ScalarType::Str | ScalarType::String | ScalarType::CowStr) => {
+                Value::from(["", "seed", "ünïcödé \"quoted\"\n\t✓"][flavor])
+            }
+            Some(ScalarType::F32) => Value::from([0.0, 1.5, f32::MAX][flavor]),
+            Some(ScalarType::F64) => Value::from([0.0, 1.5, -1.0e300][flavor]),
+            Some(ScalarType::U8) => Value::from([0, 42, u8::MAX][flavor]),
+            Some(ScalarType::U16) => Value::from([0, 42, u16::MAX][flavor]),
+            Some(ScalarType::U32) => Value::from([0, 42, u32::MAX][flavor]),
+            Some(ScalarType::U64 | ScalarType::U128) => Value::from([0, 42, u64::MAX][flavor]),
+            Some(ScalarType::USize) => Value::from([0, 42, usize::MAX][flavor]),
+            Some(ScalarType::I8) => Value::from([0, -42, i8::MIN][flavor]),
+            Some(ScalarType::I16) => Value::from([0, -42, i16::MIN][flavor]),
+            Some(ScalarType::I32) => Value::from([0, -42, i32::MIN][flavor]),
+            Some(ScalarType::I64 | ScalarType::I128) => Value::from([0, -42, i64::MIN][flavor]),
+            Some(ScalarType::ISize) => Value::from([0, -42, isize::MIN][flavor]),
+            _ => return displayed_default(shape),
+        };
+        Some(value)
+    }
+}
+
+/// Other scalars, like UUIDs and paths, are parsed from strings: use the
+/// `Display` form of their default value when they have one.
+fn displayed_default(shape: &'static Shape) -> Option<Value> {
+    if !shape.is(Characteristic::Default) || !shape.is(Characteristic::Display) {
+        return None;
+    }
+    let value = Partial::alloc_shape(shape)
+        .ok()?
+        .set_default()
+        .ok()?
+        .build()
+        .ok()?;
+    Some(Value::from(value.peek().to_string()))
+}
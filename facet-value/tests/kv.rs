@@ -0,0 +1,182 @@
+//! Tests for the key-value mapping
+
+use std::collections::{BTreeMap, HashMap};
+
+use facet::Facet;
+use facet_value::{from_kv, from_kv_hash, to_kv, to_kv_hash};
+
+#[derive(Debug, Clone, Facet, PartialEq)]
+struct Profile {
+    name: String,
+    age: u32,
+    active: bool,
+    score: f64,
+    address: Address,
+    nickname: Option<String>,
+    tags: Vec<String>,
+    limits: BTreeMap<String, u64>,
+    status: Status,
+    contact: Contact,
+}
+
+#[derive(Debug, Clone, Facet, PartialEq)]
+struct Address {
+    city: String,
+    zip: Option<u32>,
+}
+
+#[derive(Debug, Clone, Facet, PartialEq)]
+#[repr(u8)]
+enum Status {
+    Active,
+    Banned,
+}
+
+#[derive(Debug, Clone, Facet, PartialEq)]
+#[repr(u8)]
+enum Contact {
+    Email(String),
+    Phone { country: u16, number: String },
+}
+
+fn entry(key: &str, value: &str) -> (String, String) {
+    (key.to_string(), value.to_string())
+}
+
+#[test]
+fn flattens_to_prefixed_entries() {
+    let profile = Profile {
+        name: "Ada".into(),
+        age: 36,
+        active: true,
+        score: 1.5,
+        address: Address {
+            city: "London".into(),
+            zip: None,
+        },
+        nickname: Some("countess".into()),
+        tags: vec!["math".into(), "engines".into()],
+        limits: BTreeMap::from([("daily".into(), 10), ("monthly".into(), 200)]),
+        status: Status::Banned,
+        contact: Contact::Phone {
+            country: 44,
+            number: "123".into(),
+        },
+    };
+    let entries = to_kv("user:1", &profile).unwrap();
+    assert_eq!(
+        entries,
+        [
+            entry("user:1:name", "Ada"),
+            entry("user:1:age", "36"),
+            entry("user:1:active", "true"),
+            entry("user:1:score", "1.5"),
+            entry("user:1:address:city", "London"),
+            entry("user:1:nickname", "countess"),
+            entry("user:1:tags:0", "math"),
+            entry("user:1:tags:1", "engines"),
+            entry("user:1:limits:daily", "10"),
+            entry("user:1:limits:monthly", "200"),
+            entry("user:1:status", "Banned"),
+            entry("user:1:contact:Phone:country", "44"),
+            entry("user:1:contact:Phone:number", "123"),
+        ]
+    );
+}
+
+#[test]
+fn roundtrips_through_a_scan() {
+    let profile = Profile {
+        name: "Ada".into(),
+        age: 36,
+        active: true,
+        score: 1.5,
+        address: Address {
+            city: "London".into(),
+            zip: None,
+        },
+        nickname: Some("countess".into()),
+        tags: vec!["math".into(), "engines".into()],
+        limits: BTreeMap::from([("daily".into(), 10), ("monthly".into(), 200)]),
+        status: Status::Banned,
+        contact: Contact::Phone {
+            country: 44,
+            number: "123".into(),
+        },
+    };
+    let mut store: HashMap<String, String> =
+        to_kv("user:1", &profile).unwrap().into_iter().collect();
+    store.extend(to_kv("user:2", &Status::Active).unwrap());
+    store.insert("user:10:name".into(), "someone else".into());
+
+    let back: Profile = from_kv("user:1", &store).unwrap();
+    assert_eq!(back, profile);
+    let status: Status = from_kv("user:2", store).unwrap();
+    assert_eq!(status, Status::Active);
+}
+
+#[test]
+fn roundtrips_through_a_hash() {
+    let profile = Profile {
+        name: "Ada".into(),
+        age: 36,
+        active: true,
+        score: 1.5,
+        address: Address {
+            city: "London".into(),
+            zip: None,
+        },
+        nickname: Some("countess".into()),
+        tags: (0..12).map(|i| i.to_string()).collect(),
+        limits: BTreeMap::from([("daily".into(), 10), ("monthly".into(), 200)]),
+        status: Status::Banned,
+        contact: Contact::Email("ada@example.com".into()),
+    };
+
+    let fields = to_kv_hash(&profile).unwrap();
+    assert!(fields.contains(&entry("contact:Email", "ada@example.com")));
+    let back: Profile = from_kv_hash(fields).unwrap();
+    assert_eq!(back, profile);
+}
+
+#[test]
+fn missing_entries_are_empty() {
+    let profile = Profile {
+        name: "Ada".into(),
+        age: 36,
+        active: true,
+        score: 1.5,
+        address: Address {
+            city: "London".into(),
+            zip: None,
+        },
+        nickname: None,
+        tags: vec![],
+        limits: BTreeMap::new(),
+        status: Status::Banned,
+        contact: Contact::Phone {
+            country: 44,
+            number: "123".into(),
+        },
+    };
+
+    let entries = to_kv("p", &profile).unwrap();
+    assert!(!entries.iter().any(|(key, _)| key.starts_with("p:tags")));
+    let back: Profile = from_kv("p", entries).unwrap();
+    assert_eq!(back, profile);
+}
+
+#[test]
+fn non_string_map_keys_are_rejected() {
+    let map = HashMap::from([(1u32, "one".to_string())]);
+    assert!(to_kv("m", &map).is_err());
+}
+
+#[test]
+fn separators_in_keys_are_escaped() {
+    let map = BTreeMap::from([("a:b".to_string(), 1u32), ("c\\".to_string(), 2)]);
+    let entries = to_kv("m", &map).unwrap();
+    assert_eq!(entries, [entry("m:a\\:b", "1"), entry("m:c\\\\", "2")]);
+    let back: BTreeMap<String, u32> = from_kv("m", entries).unwrap();
+    assert_eq!(back, map);
+}
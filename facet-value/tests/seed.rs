@@ -0,0 +1,117 @@
+//! Tests for the fuzzing seed generator
+
+use std::collections::BTreeMap;
+
+use facet::Facet;
+use facet_value::{from_value, seed_values, value};
+
+#[derive(Debug, Facet, PartialEq)]
+struct Order {
+    id: u64,
+    customer: String,
+    note: Option<String>,
+    lines: Vec<Line>,
+    totals: BTreeMap<String, i32>,
+    status: Status,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Line {
+    sku: String,
+    quantity: u16,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+enum Status {
+    Open,
+    Held { reason: String },
+    Shipped(u32),
+    Cancelled,
+    Refunded,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[facet(tag = "kind")]
+enum Event {
+    Started { at: u64 },
+    Stopped,
+}
+
+#[derive(Debug, Facet, PartialEq)]
+#[repr(u8)]
+#[facet(tag = "t", content = "c")]
+enum Message {
+    Ping,
+    Text(String),
+}
+
+#[derive(Debug, Facet, PartialEq)]
+struct Tree {
+    label: String,
+    children: Vec<Tree>,
+    parent: Option<Box<Tree>>,
+}
+
+#[test]
+fn seeds_cover_flavors_and_variants() {
+    let seeds = seed_values(Order::SHAPE);
+    // Five variants, so five seeds
+    assert_eq!(seeds.len(), 5);
+    assert_eq!(
+        seeds[0],
+        value!({
+            "id": 0,
+            "customer": "",
+            "note": null,
+            "lines": [],
+            "totals": {},
+            "status": "Open"
+        })
+    );
+    assert_eq!(
+        seeds[1]["lines"],
+        value!([{"sku": "ünïcödé \"quoted\"\n\t✓", "quantity": 65535}])
+    );
+    assert_eq!(seeds[1]["status"], value!({"Held": {"reason": "seed"}}));
+    assert_eq!(seeds[2]["id"], value!(18446744073709551615u64));
+    assert_eq!(seeds[2]["totals"].as_object().unwrap().len(), 3);
+
+    let statuses: Vec<_> = seeds.iter().map(|seed| seed["status"].clone()).collect();
+    assert!(statuses.contains(&value!({"Shipped": 4294967295u32})));
+    assert!(statuses.contains(&value!("Cancelled")));
+    assert!(statuses.contains(&value!("Refunded")));
+
+    for seed in seeds {
+        from_value::<Order>(seed).unwrap();
+    }
+}
+
+#[test]
+fn seeds_follow_enum_tagging() {
+    assert_eq!(
+        seed_values(Event::SHAPE),
+        [
+            value!({"at": 0, "kind": "Started"}),
+            value!({"kind": "Stopped"}),
+            value!({"at": 18446744073709551615u64, "kind": "Started"}),
+        ]
+    );
+    assert_eq!(
+        seed_values(Message::SHAPE),
+        [value!({"t": "Ping"}), value!({"t": "Text", "c": "seed"}),]
+    );
+    for seed in seed_values(Event::SHAPE) {
+        from_value::<Event>(seed).unwrap();
+    }
+}
+
+#[test]
+fn seeds_for_recursive_types_are_finite() {
+    let seeds = seed_values(Tree::SHAPE);
+    assert_eq!(seeds.len(), 3);
+    for seed in seeds {
+        from_value::<Tree>(seed).unwrap();
+    }
+}
@@ -0,0 +1,67 @@
+//! XLSX export error types.
+
+use core::fmt;
+
+/// Error type for XLSX export.
+#[derive(Debug)]
+pub enum XlsxError {
+    /// The row type isn't a struct, so there are no columns to export.
+    NotAStruct {
+        /// The row type.
+        type_name: String,
+    },
+    /// A field holds something that doesn't fit in a cell, e.g. a list.
+    UnsupportedCell {
+        /// The column (field name).
+        column: String,
+        /// The field's type.
+        type_name: String,
+    },
+    /// The sheet name is empty, longer than 31 characters, or contains one
+    /// of `[ ] : * ? / \`.
+    InvalidSheetName {
+        /// The sheet name.
+        name: String,
+    },
+    /// Writing the archive failed.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for XlsxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XlsxError::NotAStruct { type_name } => {
+                write!(f, "rows must be structs, but `{type_name}` is not")
+            }
+            XlsxError::UnsupportedCell { column, type_name } => {
+                write!(
+                    f,
+                    "column `{column}` holds a `{type_name}`, which doesn't fit in a cell"
+                )
+            }
+            XlsxError::InvalidSheetName { name } => write!(f, "invalid sheet name `{name}`"),
+            XlsxError::Io(err) => write!(f, "I/O error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for XlsxError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            XlsxError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for XlsxError {
+    fn from(err: std::io::Error) -> Self {
+        XlsxError::Io(err)
+    }
+}
+
+impl From<zip::result::ZipError> for XlsxError {
+    fn from(err: zip::result::ZipError) -> Self {
+        XlsxError::Io(std::io::Error::other(err))
+    }
+}
@@ -0,0 +1,55 @@
+//! XLSX export for lists of structs.
+//!
+//! This crate writes a slice of any `Facet` struct as an Excel workbook with
+//! a single sheet: one row per value, one column per field, and a bold
+//! header row of field names. It's meant for reports — hand a list of
+//! records to someone who lives in a spreadsheet.
+//!
+//! - Column names are the serialized field names, so `rename` and
+//!   `rename_all` are honored. Skipped fields are left out, flattened fields
+//!   add their own columns, and sensitive fields read `[REDACTED]`.
+//! - Numbers become numeric cells and `bool`s boolean cells. Integers too
+//!   large to be exact as an `f64` are written as text instead.
+//! - Strings, unit enum variants and other `Display` types become text.
+//! - `None` leaves the cell empty.
+//! - Columns are sized to their longest value, within limits.
+//!
+//! Fields holding lists, maps or nested structs have no cell form and are
+//! reported as errors.
+//!
+//! # Example
+//!
+//! ```
+//! use facet::Facet;
+//!
+//! #[derive(Facet)]
+//! #[facet(rename_all = "PascalCase")]
+//! struct Incident {
+//!     id: u32,
+//!     service: String,
+//!     minutes_down: Option<f64>,
+//!     resolved: bool,
+//! }
+//!
+//! let incidents = [
+//!     Incident { id: 101, service: "api".into(), minutes_down: Some(12.5), resolved: true },
+//!     Incident { id: 102, service: "search".into(), minutes_down: None, resolved: false },
+//! ];
+//!
+//! let table = facet_xlsx::Table::from_rows(&incidents).unwrap();
+//! assert_eq!(table.columns(), ["Id", "Service", "MinutesDown", "Resolved"]);
+//!
+//! let bytes = facet_xlsx::to_vec(&incidents).unwrap();
+//! // std::fs::write("incidents.xlsx", bytes).unwrap();
+//! # assert!(bytes.starts_with(b"PK"));
+//! ```
+
+#![forbid(unsafe_code)]
+
+mod error;
+mod table;
+mod writer;
+
+pub use error::XlsxError;
+pub use table::{Cell, Table};
+pub use writer::{XlsxWriter, to_vec, to_writer};
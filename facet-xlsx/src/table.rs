@@ -0,0 +1,192 @@
+//! Turning a list of structs into rows and columns.
+
+use facet_core::{Def, Facet, FieldFlags, ScalarType, Shape, Type, UserType};
+use facet_reflect::{HasFields, Peek};
+
+use crate::XlsxError;
+
+/// A single cell value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    /// No value, e.g. a `None` field.
+    Empty,
+    /// A `bool`.
+    Bool(bool),
+    /// A number small enough to be exact as an `f64`.
+    Number(f64),
+    /// Text, including integers too large to be exact as an `f64`.
+    Text(String),
+}
+
+/// Rows of cells under named columns, built from a list of structs.
+///
+/// Each struct is a row and each field a column, named by its serialized
+/// name, so `rename` and `rename_all` are honored. Flattened fields add
+/// their own columns, skipped fields add none, and sensitive fields read
+/// `[REDACTED]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    /// Build a table with one row per value.
+    ///
+    /// Fails if `T` is not a struct, or if a field holds a list, map or other
+    /// value with no cell form.
+    pub fn from_rows<'facet, T: Facet<'facet>>(rows: &[T]) -> Result<Self, XlsxError> {
+        let mut columns = Vec::new();
+        shape_columns(T::SHAPE, &mut columns)?;
+
+        let mut table = Table {
+            columns,
+            rows: Vec::with_capacity(rows.len()),
+        };
+        for row in rows {
+            let peek = Peek::new(row);
+            let struct_ = peek.into_struct().map_err(|_| XlsxError::NotAStruct {
+                type_name: T::SHAPE.to_string(),
+            })?;
+            let mut cells = vec![Cell::Empty; table.columns.len()];
+            for (item, value) in struct_.fields_for_serialize() {
+                let column = match table.columns.iter().position(|c| *c == item.name) {
+                    Some(column) => column,
+                    // Flattened maps only reveal their keys at runtime
+                    None => {
+                        table.columns.push(item.name.to_string());
+                        cells.push(Cell::Empty);
+                        table.columns.len() - 1
+                    }
+                };
+                cells[column] = if item.field.is_some_and(|f| f.is_sensitive()) {
+                    Cell::Text("[REDACTED]".into())
+                } else {
+                    cell(value).ok_or_else(|| XlsxError::UnsupportedCell {
+                        column: item.name.to_string(),
+                        type_name: value.shape().to_string(),
+                    })?
+                };
+            }
+            table.rows.push(cells);
+        }
+
+        // Rows read before a new column was found are short by one cell
+        for row in &mut table.rows {
+            row.resize(table.columns.len(), Cell::Empty);
+        }
+        Ok(table)
+    }
+
+    /// The column names, in field order.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// The rows, each with one cell per column.
+    pub fn rows(&self) -> &[Vec<Cell>] {
+        &self.rows
+    }
+}
+
+/// Add the columns of the struct `shape` to `columns`
+fn shape_columns(shape: &'static Shape, columns: &mut Vec<String>) -> Result<(), XlsxError> {
+    let Type::User(UserType::Struct(st)) = shape.ty else {
+        return Err(XlsxError::NotAStruct {
+            type_name: shape.to_string(),
+        });
+    };
+    for field in st.fields {
+        if field.flags.contains(FieldFlags::SKIP)
+            || field.flags.contains(FieldFlags::SKIP_SERIALIZING)
+        {
+            continue;
+        }
+        if field.is_flattened() {
+            // Flattened maps and enums name their columns at runtime
+            if let Type::User(UserType::Struct(_)) = field.shape().ty {
+                shape_columns(field.shape(), columns)?;
+            }
+            continue;
+        }
+        columns.push(field.name.to_string());
+    }
+    Ok(())
+}
+
+/// Integers beyond this lose precision as an `f64`
+const MAX_EXACT: i128 = 1 << 53;
+
+/// The cell for a field value, or `None` if it has no cell form
+fn cell(peek: Peek<'_, '_>) -> Option<Cell> {
+    let shape = peek.shape();
+    match shape.def {
+        Def::Option(_) => {
+            return match peek.into_option().ok()?.value() {
+                Some(inner) => cell(inner),
+                None => Some(Cell::Empty),
+            };
+        }
+        Def::Pointer(_) => return cell(peek.into_pointer().ok()?.borrow_inner()?),
+        _ => {}
+    }
+    if shape.inner.is_some() {
+        let inner = peek.innermost_peek();
+        if inner.shape() != shape {
+            return cell(inner);
+        }
+    }
+
+    if let Some(scalar) = peek.scalar_type() {
+        let integer = |n: i128| {
+            if (-MAX_EXACT..=MAX_EXACT).contains(&n) {
+                Cell::Number(n as f64)
+            } else {
+                Cell::Text(n.to_string())
+            }
+        };
+        let float = |n: f64| {
+            if n.is_finite() {
+                Cell::Number(n)
+            } else {
+                Cell::Text(n.to_string())
+            }
+        };
+        return Some(match scalar {
+            ScalarType::Unit => Cell::Empty,
+            ScalarType::Bool => Cell::Bool(*peek.get::<bool>().ok()?),
+            ScalarType::U8 => integer(*peek.get::<u8>().ok()? as i128),
+            ScalarType::U16 => integer(*peek.get::<u16>().ok()? as i128),
+            ScalarType::U32 => integer(*peek.get::<u32>().ok()? as i128),
+            ScalarType::U64 => integer(*peek.get::<u64>().ok()? as i128),
+            ScalarType::USize => integer(*peek.get::<usize>().ok()? as i128),
+            ScalarType::I8 => integer(*peek.get::<i8>().ok()? as i128),
+            ScalarType::I16 => integer(*peek.get::<i16>().ok()? as i128),
+            ScalarType::I32 => integer(*peek.get::<i32>().ok()? as i128),
+            ScalarType::I64 => integer(*peek.get::<i64>().ok()? as i128),
+            ScalarType::ISize => integer(*peek.get::<isize>().ok()? as i128),
+            ScalarType::I128 => integer(*peek.get::<i128>().ok()?),
+            ScalarType::U128 => match i128::try_from(*peek.get::<u128>().ok()?) {
+                Ok(n) => integer(n),
+                Err(_) => Cell::Text(peek.get::<u128>().ok()?.to_string()),
+            },
+            ScalarType::F32 => float(*peek.get::<f32>().ok()? as f64),
+            ScalarType::F64 => float(*peek.get::<f64>().ok()?),
+            _ => match peek.as_str() {
+                Some(s) => Cell::Text(s.to_string()),
+                None => Cell::Text(shape.is_display().then(|| peek.to_string())?),
+            },
+        });
+    }
+
+    if let Type::User(UserType::Enum(_)) = shape.ty {
+        let variant = peek.into_enum().ok()?.active_variant().ok()?;
+        if variant.data.fields.is_empty() {
+            return Some(Cell::Text(variant.name.to_string()));
+        }
+    }
+    if let Some(s) = peek.as_str() {
+        return Some(Cell::Text(s.to_string()));
+    }
+    shape.is_display().then(|| Cell::Text(peek.to_string()))
+}
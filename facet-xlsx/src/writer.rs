@@ -0,0 +1,335 @@
+//! Writing a [`Table`] as an XLSX workbook.
+//!
+//! An XLSX file is a zip archive of XML parts. This writes the smallest set
+//! Excel, LibreOffice and Numbers accept: the content types, the package and
+//! workbook relationships, the workbook, a stylesheet (for the bold header)
+//! and a single worksheet. Strings are stored inline rather than in a shared
+//! string table.
+
+use std::fmt::Write as _;
+use std::io::{Cursor, Seek, Write};
+
+use facet_core::Facet;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+use crate::{Cell, Table, XlsxError};
+
+/// Columns are never narrower than this many characters
+const MIN_WIDTH: usize = 8;
+/// Columns are never wider than this many characters
+const MAX_WIDTH: usize = 60;
+
+/// XLSX writer with workbook options.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_xlsx::XlsxWriter;
+///
+/// #[derive(Facet)]
+/// struct Host {
+///     name: String,
+///     load: f64,
+/// }
+///
+/// let hosts = [Host { name: "web-1".into(), load: 0.42 }];
+/// let bytes = XlsxWriter::new()
+///     .with_sheet_name("Hosts")
+///     .to_vec(&hosts)
+///     .unwrap();
+/// assert!(bytes.starts_with(b"PK"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct XlsxWriter {
+    sheet_name: String,
+}
+
+impl Default for XlsxWriter {
+    fn default() -> Self {
+        Self {
+            sheet_name: "Sheet1".into(),
+        }
+    }
+}
+
+impl XlsxWriter {
+    /// Create a writer with the default options: a single sheet named `Sheet1`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the name of the sheet.
+    pub fn with_sheet_name(mut self, name: impl Into<String>) -> Self {
+        self.sheet_name = name.into();
+        self
+    }
+
+    /// Export `rows` to an XLSX file in memory.
+    pub fn to_vec<'facet, T: Facet<'facet>>(&self, rows: &[T]) -> Result<Vec<u8>, XlsxError> {
+        let mut out = Cursor::new(Vec::new());
+        self.to_writer(&mut out, rows)?;
+        Ok(out.into_inner())
+    }
+
+    /// Export `rows` to an XLSX file written to `writer`.
+    pub fn to_writer<'facet, W, T>(&self, writer: W, rows: &[T]) -> Result<(), XlsxError>
+    where
+        W: Write + Seek,
+        T: Facet<'facet>,
+    {
+        self.write_table(writer, &Table::from_rows(rows)?)
+    }
+
+    /// Write an already built table to `writer`.
+    pub fn write_table<W: Write + Seek>(&self, writer: W, table: &Table) -> Result<(), XlsxError> {
+        let name = &self.sheet_name;
+        if name.is_empty()
+            || name.chars().count() > 31
+            || name.contains(['[', ']', ':', '*', '?', '/', '\\'])
+        {
+            return Err(XlsxError::InvalidSheetName { name: name.clone() });
+        }
+
+        let mut zip = ZipWriter::new(writer);
+        let options = SimpleFileOptions::default();
+        let parts = [
+            ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+            ("_rels/.rels", ROOT_RELS.to_string()),
+            ("xl/workbook.xml", workbook(name)),
+            ("xl/_rels/workbook.xml.rels", WORKBOOK_RELS.to_string()),
+            ("xl/styles.xml", STYLES.to_string()),
+            ("xl/worksheets/sheet1.xml", worksheet(table)),
+        ];
+        for (path, content) in parts {
+            zip.start_file(path, options)?;
+            zip.write_all(content.as_bytes())?;
+        }
+        zip.finish()?;
+        Ok(())
+    }
+}
+
+/// Export `rows` to an XLSX file in memory, with the default options.
+///
+/// The first row holds the column names in bold and stays in view when
+/// scrolling. See [`Table`] for how values become cells.
+///
+/// # Example
+///
+/// ```
+/// use facet::Facet;
+/// use facet_xlsx::to_vec;
+///
+/// #[derive(Facet)]
+/// struct Deploy {
+///     #[facet(rename = "Service")]
+///     service: String,
+///     replicas: u32,
+///     canary: bool,
+/// }
+///
+/// let bytes = to_vec(&[Deploy { service: "api".into(), replicas: 3, canary: false }]).unwrap();
+/// assert!(bytes.starts_with(b"PK"));
+/// ```
+pub fn to_vec<'facet, T: Facet<'facet>>(rows: &[T]) -> Result<Vec<u8>, XlsxError> {
+    XlsxWriter::new().to_vec(rows)
+}
+
+/// Export `rows` to an XLSX file written to `writer`, with the default options.
+pub fn to_writer<'facet, W, T>(writer: W, rows: &[T]) -> Result<(), XlsxError>
+where
+    W: Write + Seek,
+    T: Facet<'facet>,
+{
+    XlsxWriter::new().to_writer(writer, rows)
+}
+
+const CONTENT_TYPES: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+    r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+    r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+    r#"<Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>"#,
+    r#"<Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#,
+    r#"<Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/>"#,
+    r#"</Types>"#,
+);
+
+const ROOT_RELS: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>"#,
+    r#"</Relationships>"#,
+);
+
+const WORKBOOK_RELS: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>"#,
+    r#"<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>"#,
+    r#"</Relationships>"#,
+);
+
+/// Style 0 is the default, style 1 is bold for the header row
+const STYLES: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+    r#"<fonts count="2"><font><sz val="11"/><name val="Calibri"/></font>"#,
+    r#"<font><b/><sz val="11"/><name val="Calibri"/></font></fonts>"#,
+    r#"<fills count="2"><fill><patternFill patternType="none"/></fill>"#,
+    r#"<fill><patternFill patternType="gray125"/></fill></fills>"#,
+    r#"<borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders>"#,
+    r#"<cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs>"#,
+    r#"<cellXfs count="2"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/>"#,
+    r#"<xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1"/></cellXfs>"#,
+    r#"<cellStyles count="1"><cellStyle name="Normal" xfId="0" builtinId="0"/></cellStyles>"#,
+    r#"</styleSheet>"#,
+);
+
+fn workbook(sheet_name: &str) -> String {
+    format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+            r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+            r#"<sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets>"#,
+            r#"</workbook>"#,
+        ),
+        escape(sheet_name)
+    )
+}
+
+fn worksheet(table: &Table) -> String {
+    let mut out = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+        // Keep the header row in view
+        r#"<sheetViews><sheetView workbookViewId="0">"#,
+        r#"<pane ySplit="1" topLeftCell="A2" activePane="bottomLeft" state="frozen"/>"#,
+        r#"</sheetView></sheetViews>"#,
+    ));
+
+    if !table.columns().is_empty() {
+        out.push_str("<cols>");
+        for (index, width) in column_widths(table).into_iter().enumerate() {
+            write!(
+                out,
+                r#"<col min="{n}" max="{n}" width="{width}" customWidth="1"/>"#,
+                n = index + 1
+            )
+            .unwrap();
+        }
+        out.push_str("</cols>");
+    }
+
+    out.push_str("<sheetData>");
+    out.push_str(r#"<row r="1">"#);
+    for (column, name) in table.columns().iter().enumerate() {
+        write!(
+            out,
+            r#"<c r="{}1" s="1" t="inlineStr"><is>{}</is></c>"#,
+            column_letters(column),
+            text(name)
+        )
+        .unwrap();
+    }
+    out.push_str("</row>");
+    for (index, row) in table.rows().iter().enumerate() {
+        let r = index + 2;
+        write!(out, r#"<row r="{r}">"#).unwrap();
+        for (column, cell) in row.iter().enumerate() {
+            let at = column_letters(column);
+            match cell {
+                Cell::Empty => {}
+                Cell::Bool(b) => {
+                    write!(out, r#"<c r="{at}{r}" t="b"><v>{}</v></c>"#, u8::from(*b)).unwrap()
+                }
+                Cell::Number(n) => write!(out, r#"<c r="{at}{r}"><v>{n}</v></c>"#).unwrap(),
+                Cell::Text(s) => write!(
+                    out,
+                    r#"<c r="{at}{r}" t="inlineStr"><is>{}</is></c>"#,
+                    text(s)
+                )
+                .unwrap(),
+            }
+        }
+        out.push_str("</row>");
+    }
+    out.push_str("</sheetData></worksheet>");
+    out
+}
+
+/// Column widths in characters: wide enough for the longest line in the
+/// column, within [`MIN_WIDTH`] and [`MAX_WIDTH`], plus a little padding
+fn column_widths(table: &Table) -> Vec<usize> {
+    let longest_line = |s: &str| s.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    table
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(column, name)| {
+            let longest = table
+                .rows()
+                .iter()
+                .map(|row| match &row[column] {
+                    Cell::Empty => 0,
+                    Cell::Bool(b) => {
+                        if *b {
+                            4
+                        } else {
+                            5
+                        }
+                    }
+                    Cell::Number(n) => n.to_string().len(),
+                    Cell::Text(s) => longest_line(s),
+                })
+                .chain([longest_line(name)])
+                .max()
+                .unwrap_or(0);
+            (longest + 2).clamp(MIN_WIDTH, MAX_WIDTH)
+        })
+        .collect()
+}
+
+/// The letters of a zero-based column index: A, B, ..., Z, AA, AB, ...
+fn column_letters(mut index: usize) -> String {
+    let mut letters = Vec::new();
+    loop {
+        letters.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    letters.reverse();
+    String::from_utf8(letters).unwrap()
+}
+
+/// A `<t>` element holding `s`, keeping leading and trailing whitespace
+fn text(s: &str) -> String {
+    if s.starts_with(char::is_whitespace) || s.ends_with(char::is_whitespace) {
+        format!(r#"<t xml:space="preserve">{}</t>"#, escape(s))
+    } else {
+        format!("<t>{}</t>", escape(s))
+    }
+}
+
+/// Escape `s` for XML text and attributes, dropping control characters XML
+/// can't hold
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c.is_control() && (c as u32) < 0x20 => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read};
+
+use facet::Facet;
+use facet_xlsx::{Cell, Table, XlsxError, XlsxWriter, to_vec};
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Status {
+    Up,
+    Down,
+}
+
+#[derive(Facet)]
+struct Labels {
+    region: String,
+}
+
+#[derive(Facet)]
+struct Host {
+    #[facet(rename = "Host Name")]
+    name: String,
+    cpus: u16,
+    load: Option<f64>,
+    healthy: bool,
+    status: Status,
+    serial: u64,
+    #[facet(sensitive)]
+    token: String,
+    #[facet(skip)]
+    cache: Vec<u8>,
+    #[facet(flatten)]
+    labels: Labels,
+}
+
+fn read_part(bytes: &[u8], name: &str) -> String {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+    let mut part = archive.by_name(name).unwrap();
+    let mut out = String::new();
+    part.read_to_string(&mut out).unwrap();
+    out
+}
+
+#[test]
+fn test_table() {
+    let hosts = vec![
+        Host {
+            name: "web-1 & <friends>".into(),
+            cpus: 8,
+            load: Some(0.5),
+            healthy: true,
+            status: Status::Up,
+            serial: u64::MAX,
+            token: "hunter2".into(),
+            cache: vec![1, 2, 3],
+            labels: Labels {
+                region: "eu".into(),
+            },
+        },
+        Host {
+            name: "db-1".into(),
+            cpus: 32,
+            load: None,
+            healthy: false,
+            status: Status::Down,
+            serial: 7,
+            token: "hunter3".into(),
+            cache: Vec::new(),
+            labels: Labels {
+                region: "us".into(),
+            },
+        },
+    ];
+    let table = Table::from_rows(&hosts).unwrap();
+    assert_eq!(
+        table.columns(),
+        [
+            "Host Name",
+            "cpus",
+            "load",
+            "healthy",
+            "status",
+            "serial",
+            "token",
+            "region"
+        ]
+    );
+    let row = &table.rows()[1];
+    assert_eq!(row[1], Cell::Number(32.0));
+    assert_eq!(row[2], Cell::Empty);
+    assert_eq!(row[3], Cell::Bool(false));
+    assert_eq!(row[4], Cell::Text("Down".into()));
+    assert_eq!(row[5], Cell::Number(7.0));
+    assert_eq!(row[6], Cell::Text("[REDACTED]".into()));
+    assert_eq!(row[7], Cell::Text("us".into()));
+    // Too large to be exact as a number
+    assert_eq!(table.rows()[0][5], Cell::Text(u64::MAX.to_string()));
+}
+
+#[test]
+fn test_workbook() {
+    let hosts = vec![
+        Host {
+            name: "web-1 & <friends>".into(),
+            cpus: 8,
+            load: Some(0.5),
+            healthy: true,
+            status: Status::Up,
+            serial: u64::MAX,
+            token: "hunter2".into(),
+            cache: vec![1, 2, 3],
+            labels: Labels {
+                region: "eu".into(),
+            },
+        },
+        Host {
+            name: "db-1".into(),
+            cpus: 32,
+            load: None,
+            healthy: false,
+            status: Status::Down,
+            serial: 7,
+            token: "hunter3".into(),
+            cache: Vec::new(),
+            labels: Labels {
+                region: "us".into(),
+            },
+        },
+    ];
+    let bytes = XlsxWriter::new()
+        .with_sheet_name("Fleet")
+        .to_vec(&hosts)
+        .unwrap();
+
+    let workbook = read_part(&bytes, "xl/workbook.xml");
+    assert!(workbook.contains(r#"<sheet name="Fleet" sheetId="1" r:id="rId1"/>"#));
+
+    let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+    assert!(
+        sheet.contains(r#"<c r="A1" s="1" t="inlineStr"><is><t>Host Name</t></is></c>"#),
+        "{sheet}"
+    );
+    assert!(
+        sheet
+            .contains(r#"<c r="A2" t="inlineStr"><is><t>web-1 &amp; &lt;friends&gt;</t></is></c>"#),
+        "{sheet}"
+    );
+    assert!(sheet.contains(r#"<c r="B3"><v>32</v></c>"#), "{sheet}");
+    assert!(sheet.contains(r#"<c r="C2"><v>0.5</v></c>"#), "{sheet}");
+    assert!(!sheet.contains(r#"r="C3""#), "{sheet}");
+    assert!(sheet.contains(r#"<c r="D2" t="b"><v>1</v></c>"#), "{sheet}");
+    assert!(!sheet.contains("hunter2"), "{sheet}");
+    // "web-1 & <friends>" is 17 characters, plus padding
+    assert!(
+        sheet.contains(r#"<col min="1" max="1" width="19" customWidth="1"/>"#),
+        "{sheet}"
+    );
+    assert!(
+        sheet.contains(r#"<col min="2" max="2" width="8" customWidth="1"/>"#),
+        "{sheet}"
+    );
+
+    for part in [
+        "[Content_Types].xml",
+        "_rels/.rels",
+        "xl/_rels/workbook.xml.rels",
+        "xl/styles.xml",
+    ] {
+        assert!(!read_part(&bytes, part).is_empty());
+    }
+}
+
+#[test]
+fn test_empty_and_errors() {
+    let bytes = to_vec::<Host>(&[]).unwrap();
+    let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+    assert!(sheet.contains("<t>Host Name</t>"), "{sheet}");
+    assert!(!sheet.contains(r#"<row r="2">"#), "{sheet}");
+
+    #[derive(Facet)]
+    struct Nested {
+        ports: Vec<u16>,
+    }
+    let err = to_vec(&[Nested { ports: vec![80] }]).unwrap_err();
+    assert!(
+        matches!(&err, XlsxError::UnsupportedCell { column, .. } if column == "ports"),
+        "{err}"
+    );
+
+    let err = to_vec(&[1u32, 2]).unwrap_err();
+    assert!(matches!(err, XlsxError::NotAStruct { .. }), "{err}");
+
+    let err = XlsxWriter::new()
+        .with_sheet_name("Q1/Q2")
+        .to_vec(&Vec::<Host>::new())
+        .unwrap_err();
+    assert!(matches!(err, XlsxError::InvalidSheetName { .. }), "{err}");
+
+    let wide: BTreeMap<u8, u8> = BTreeMap::new();
+    assert!(to_vec(&[wide]).is_err());
+}
@@ -0,0 +1,59 @@
+use facet::Facet;
+use facet_format::{DeserializeError, DuplicateKeyPolicy, FormatDeserializer};
+use facet_xml::{self as xml, XmlError, XmlParser};
+
+fn deserialize<T: Facet<'static>>(
+    input: &str,
+    policy: DuplicateKeyPolicy,
+) -> Result<T, DeserializeError<XmlError>> {
+    FormatDeserializer::new_owned(XmlParser::new(input.as_bytes()))
+        .with_duplicate_key_policy(policy)
+        .deserialize()
+}
+
+#[test]
+fn repeated_elements_are_list_items() {
+    #[derive(Debug, Facet)]
+    struct Playlist {
+        #[facet(xml::element)]
+        name: String,
+        #[facet(xml::elements)]
+        song: Vec<String>,
+    }
+
+    let input = "<Playlist><name>mix</name><song>a</song><song>b</song></Playlist>";
+    let playlist: Playlist = deserialize(input, DuplicateKeyPolicy::Error).unwrap();
+    assert_eq!(playlist.song, ["a", "b"]);
+
+    let input = "<Playlist><name>mix</name><song>a</song><name>other</name></Playlist>";
+    let err = deserialize::<Playlist>(input, DuplicateKeyPolicy::Error).unwrap_err();
+    assert!(matches!(err, DeserializeError::DuplicateKey { key, .. } if key == "name"));
+}
+
+#[test]
+fn flattened_fields_follow_the_policy() {
+    #[derive(Debug, Facet)]
+    struct Meta {
+        #[facet(xml::element)]
+        owner: String,
+    }
+
+    #[derive(Debug, Facet)]
+    struct Document {
+        #[facet(xml::element)]
+        title: String,
+        #[facet(flatten)]
+        meta: Meta,
+    }
+
+    let input = "<Document><title>t</title><owner>alice</owner><owner>bob</owner></Document>";
+
+    let doc: Document = deserialize(input, DuplicateKeyPolicy::LastWins).unwrap();
+    assert_eq!(doc.meta.owner, "bob");
+
+    let doc: Document = deserialize(input, DuplicateKeyPolicy::FirstWins).unwrap();
+    assert_eq!(doc.meta.owner, "alice");
+
+    let err = deserialize::<Document>(input, DuplicateKeyPolicy::Error).unwrap_err();
+    assert!(matches!(err, DeserializeError::DuplicateKey { key, .. } if key == "owner"));
+}
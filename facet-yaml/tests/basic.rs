@@ -286,6 +286,22 @@ required: hello
     assert_eq!(obj.optional, None);
 }
 
+#[test]
+fn test_missing_field_mentions_doc_comment() {
+    #[derive(Facet, Debug)]
+    struct ServerConfig {
+        host: String,
+        /// Port the admin API listens on.
+        admin_port: u16,
+    }
+
+    let err = from_str::<ServerConfig>("host: localhost\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "missing field `admin_port` in type `ServerConfig` (Port the admin API listens on.)"
+    );
+}
+
 #[test]
 fn test_deserialize_with_optional_null() {
     let yaml = r#"
@@ -126,6 +126,65 @@ pub mod builtin {
             #[storage(flag)]
             SkipDeserializing,
 
+            /// On an `Option<Option<T>>` field, tells an absent field (`None`) apart
+            /// from an explicit null (`Some(None)`), as PATCH payloads need.
+            /// `None` is skipped when serializing.
+            ///
+            /// Usage: `#[facet(double_option)]`
+            DoubleOption,
+
+            /// On an `Arc<str>` (or `Option<Arc<str>>`) field, routes the string
+            /// through the deserializer's interner, so equal strings share one
+            /// allocation.
+            ///
+            /// Usage: `#[facet(intern)]`
+            Intern,
+
+            /// On a list field, declares that items are sorted by the given field
+            /// of theirs, so lookups can binary search instead of scanning.
+            ///
+            /// Usage: `#[facet(sorted_by = "id")]`
+            SortedBy(&'static str),
+
+            /// On a numeric field, names the unit its value is measured in.
+            /// Printers humanize `"bytes"` and `"seconds"` values, and schema
+            /// exporters document the unit.
+            ///
+            /// Usage: `#[facet(unit = "bytes")]`
+            Unit(&'static str),
+
+            /// Encrypts a field's value in serialized output, through the
+            /// `FieldCipher` configured on the serializer and deserializer.
+            /// Encrypted fields are also treated as sensitive.
+            ///
+            /// Usage: `#[facet(encrypt)]`
+            Encrypt,
+
+            /// On a `String` or `Vec<u8>` field, names the codec binary
+            /// serializers compress its value with. Text formats write the
+            /// value as-is, and schema exporters document the codec.
+            ///
+            /// Usage: `#[facet(compress = "zstd")]`
+            Compress(&'static str),
+
+            /// Display order of a field among its siblings. Pretty printers and
+            /// schema exporters list ordered fields first, by ascending order.
+            ///
+            /// Usage: `#[facet(order = 1)]`
+            Order(i64),
+
+            /// Display group of a field. Pretty printers and schema exporters
+            /// cluster the fields of a group together under its name.
+            ///
+            /// Usage: `#[facet(group = "network")]`
+            Group(&'static str),
+
+            /// Skips this field when values are compared with
+            /// `Peek::eq_with`, e.g. for timestamps or generated ids.
+            ///
+            /// Usage: `#[facet(ignore_in_eq)]`
+            IgnoreInEq,
+
             /// For enums: variants are serialized without a discriminator tag.
             ///
             /// Usage: `#[facet(untagged)]`
@@ -182,8 +241,10 @@ pub mod builtin {
             /// Usage: `#[facet(type_tag = "com.example.MyType")]`
             TypeTag(&'static str),
 
-            /// Type invariant validation function.
-            /// Stores a type-erased function pointer: `fn(PtrConst) -> bool`.
+            /// Type invariant validation function, checked whenever a value is
+            /// built through reflection (e.g. by a deserializer). On a container it
+            /// receives `&Self`, on a field a reference to the field's value. It
+            /// returns a `bool`, or a `Result<(), E: Display>` to explain failures.
             ///
             /// Usage: `#[facet(invariants = validate_fn)]`
             Invariants(predicate InvariantsFn),
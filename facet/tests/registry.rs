@@ -0,0 +1,76 @@
+#![cfg(feature = "registry")]
+
+use facet::Facet;
+
+mod config {
+    #[derive(facet::Facet)]
+    pub struct Config {
+        pub name: String,
+    }
+
+    #[derive(facet::Facet)]
+    #[repr(u8)]
+    #[allow(dead_code)]
+    pub enum Mode {
+        Fast,
+        Safe,
+    }
+}
+
+#[derive(Facet)]
+struct Borrowed<'a> {
+    name: &'a str,
+}
+
+#[derive(Facet)]
+struct Generic<T> {
+    value: T,
+}
+
+#[test]
+fn derived_types_are_found_by_path() {
+    let registry = facet::registry();
+    assert_eq!(
+        registry.find("registry::config::Config"),
+        Some(config::Config::SHAPE)
+    );
+    assert_eq!(
+        registry.find("registry::config::Mode"),
+        Some(config::Mode::SHAPE)
+    );
+    assert_eq!(registry.find("registry::Borrowed"), Some(Borrowed::SHAPE));
+
+    // The module path is required, and must match exactly
+    assert_eq!(registry.find("Config"), None);
+    assert_eq!(registry.find("registry::Config"), None);
+    assert_eq!(registry.find("registry::config::Config::"), None);
+}
+
+#[test]
+fn generic_types_are_not_registered() {
+    let _ = Generic { value: 1u8 };
+    assert!(
+        facet::registry()
+            .iter()
+            .all(|registration| registration.shape().type_identifier != "Generic")
+    );
+}
+
+#[test]
+fn derived_types_are_in_the_shape_registry() {
+    let id = config::Config::SHAPE.stable_id();
+    assert_eq!(
+        facet::registered_shape_by_stable_id(id),
+        Some(config::Config::SHAPE)
+    );
+    assert_eq!(
+        facet::registered_shape(core::any::TypeId::of::<config::Mode>()),
+        Some(config::Mode::SHAPE)
+    );
+
+    let registration = facet::registry()
+        .iter()
+        .find(|registration| registration.shape() == config::Mode::SHAPE)
+        .unwrap();
+    assert_eq!(registration.module_path(), "registry::config");
+}
@@ -0,0 +1,59 @@
+use facet::Facet;
+
+mod v1 {
+    #[derive(facet::Facet)]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+}
+
+mod v2 {
+    #[derive(facet::Facet)]
+    pub struct Point {
+        pub x: i32,
+        pub z: i32,
+    }
+}
+
+#[derive(Facet)]
+#[repr(u8)]
+#[allow(dead_code)]
+enum Node {
+    Leaf(u32),
+    Branch(Vec<Node>),
+}
+
+#[test]
+fn stable_id_is_pinned() {
+    // These must never change: other builds rely on them
+    assert_eq!(u32::SHAPE.stable_id().to_string(), "0e1bb986eb45132a");
+    assert_eq!(v1::Point::SHAPE.stable_id().to_string(), "a4b6dd7c1bf68d31");
+}
+
+#[test]
+fn stable_id_tracks_structure() {
+    assert_eq!(u32::SHAPE.stable_id(), u32::SHAPE.stable_id());
+    assert_ne!(u32::SHAPE.stable_id(), i32::SHAPE.stable_id());
+    assert_ne!(<Vec<u32>>::SHAPE.stable_id(), <Vec<u64>>::SHAPE.stable_id());
+    assert_ne!(v1::Point::SHAPE.stable_id(), v2::Point::SHAPE.stable_id());
+
+    // Recursive types hash their fields by name, so this terminates
+    let id = Node::SHAPE.stable_id();
+    assert_eq!(facet::StableTypeId::from_u64(id.as_u64()), id);
+}
+
+#[test]
+fn registered_shape_by_stable_id() {
+    facet::register_shape(v1::Point::SHAPE);
+    let id = v1::Point::SHAPE.stable_id();
+    assert_eq!(
+        facet::registered_shape_by_stable_id(id),
+        Some(v1::Point::SHAPE)
+    );
+    // Derived types may have added themselves, so look for one that can't
+    assert_eq!(
+        facet::registered_shape_by_stable_id(<(u8, i16)>::SHAPE.stable_id()),
+        None
+    );
+}